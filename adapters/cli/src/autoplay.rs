@@ -0,0 +1,98 @@
+//! Headless balance runs in which a bot plays a fixed number of waves.
+
+use std::{io::Write, time::Duration};
+
+use anyhow::{bail, Result};
+use maze_defence_core::RoundOutcome;
+use maze_defence_rendering::{FrameInput, FrameTick, Scene};
+use maze_defence_world::query;
+
+use crate::Simulation;
+
+/// Frame length used when stepping the simulation, matching a 60 Hz renderer.
+pub(crate) const FRAME: Duration = Duration::from_millis(16);
+/// Simulated time after which a wave that has not resolved is considered stuck.
+const WAVE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Steps `simulation` until the autoplayer saw `waves` waves resolve, writing one line per
+/// wave followed by the win rate.
+///
+/// Fails when a wave does not resolve within [`WAVE_TIMEOUT`] of simulated time, so an
+/// unattended batch run cannot hang.
+pub(crate) fn run(
+    simulation: &mut Simulation,
+    scene: &mut Scene,
+    waves: u32,
+    mut output: impl Write,
+) -> Result<()> {
+    let waves = usize::try_from(waves).unwrap_or(usize::MAX);
+    play_waves(
+        simulation,
+        waves,
+        |simulation| {
+            let _ = simulation.run_frame(FrameTick::single(FRAME), FrameInput::default(), scene);
+        },
+        |simulation, wave, outcome| {
+            writeln!(
+                output,
+                "wave {wave}: {} (difficulty {}, {} gold)",
+                outcome_label(outcome),
+                query::difficulty_level(simulation.world()).get(),
+                query::gold(simulation.world()).get()
+            )?;
+            Ok(())
+        },
+    )?;
+
+    let wins = simulation.autoplay_outcomes()[..waves]
+        .iter()
+        .filter(|outcome| **outcome == RoundOutcome::Win)
+        .count();
+    writeln!(
+        output,
+        "won {wins} of {waves} waves ({:.1}%)",
+        wins as f64 * 100.0 / waves as f64
+    )?;
+    Ok(())
+}
+
+/// Calls `step` until the autoplayer saw `waves` waves resolve, passing each wave's
+/// one-based number and outcome to `on_wave` as soon as it resolves.
+///
+/// Every call to `step` must advance the simulation by [`FRAME`]; a wave that does not
+/// resolve within [`WAVE_TIMEOUT`] of simulated time fails the run.
+pub(crate) fn play_waves(
+    simulation: &mut Simulation,
+    waves: usize,
+    mut step: impl FnMut(&mut Simulation),
+    mut on_wave: impl FnMut(&Simulation, usize, RoundOutcome) -> Result<()>,
+) -> Result<()> {
+    let mut reported = 0;
+    let mut waiting = Duration::ZERO;
+    while reported < waves {
+        step(simulation);
+        waiting += FRAME;
+
+        let resolved = simulation.autoplay_outcomes().len().min(waves);
+        while reported < resolved {
+            let outcome = simulation.autoplay_outcomes()[reported];
+            reported += 1;
+            waiting = Duration::ZERO;
+            on_wave(simulation, reported, outcome)?;
+        }
+        if waiting >= WAVE_TIMEOUT {
+            bail!(
+                "wave {} did not resolve within {WAVE_TIMEOUT:?}",
+                reported + 1
+            );
+        }
+    }
+    Ok(())
+}
+
+pub(crate) const fn outcome_label(outcome: RoundOutcome) -> &'static str {
+    match outcome {
+        RoundOutcome::Win => "win",
+        RoundOutcome::Loss => "loss",
+    }
+}
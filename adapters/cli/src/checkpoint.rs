@@ -0,0 +1,236 @@
+//! World checkpoints saved after every resolved wave, and what changed between two of them.
+//!
+//! A checkpoint records the wave it was taken after, the difficulty level, the gold in the
+//! bank and the maze standing at that moment. Diffing two checkpoints answers "what did the
+//! player do since that wave" when going through a replay.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use maze_defence_core::{DifficultyLevel, Gold, WaveId};
+use serde::{Deserialize, Serialize};
+
+use crate::layout_transfer::{TowerLayoutSnapshot, TowerLayoutTower};
+
+/// State of the world once a wave resolved.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct WorldCheckpoint {
+    /// Wave that had just resolved.
+    pub(crate) wave: WaveId,
+    /// Difficulty level after the wave resolved.
+    pub(crate) difficulty_level: DifficultyLevel,
+    /// Gold in the bank after the wave's rewards.
+    pub(crate) gold: Gold,
+    /// Towers standing in the maze.
+    pub(crate) layout: TowerLayoutSnapshot,
+}
+
+impl WorldCheckpoint {
+    /// Writes the checkpoint as JSON into `directory`, named after its one-based wave
+    /// number, and returns the path written.
+    pub(crate) fn save(&self, directory: &Path) -> Result<PathBuf> {
+        let path = directory.join(format!("wave-{:04}.json", self.wave.get() + 1));
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Reads a checkpoint written by [`Self::save`].
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("{} is not a world checkpoint", path.display()))
+    }
+}
+
+/// What changed from one checkpoint to a later one.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CheckpointDiff {
+    /// Wave the earlier checkpoint was taken after.
+    pub(crate) wave_before: WaveId,
+    /// Wave the later checkpoint was taken after.
+    pub(crate) wave_after: WaveId,
+    /// Gold in the bank at the earlier checkpoint.
+    pub(crate) gold_before: Gold,
+    /// Gold in the bank at the later checkpoint.
+    pub(crate) gold_after: Gold,
+    /// Difficulty level at the earlier checkpoint.
+    pub(crate) difficulty_before: DifficultyLevel,
+    /// Difficulty level at the later checkpoint.
+    pub(crate) difficulty_after: DifficultyLevel,
+    /// Towers standing only in the later checkpoint.
+    pub(crate) towers_added: Vec<TowerLayoutTower>,
+    /// Towers standing only in the earlier checkpoint.
+    pub(crate) towers_removed: Vec<TowerLayoutTower>,
+}
+
+impl CheckpointDiff {
+    /// Gold gained between the checkpoints, negative when the bank shrank.
+    pub(crate) fn gold_delta(&self) -> i128 {
+        i128::from(self.gold_after.get()) - i128::from(self.gold_before.get())
+    }
+
+    /// Difficulty levels gained between the checkpoints, negative when it dropped.
+    pub(crate) fn difficulty_delta(&self) -> i64 {
+        i64::from(self.difficulty_after.get()) - i64::from(self.difficulty_before.get())
+    }
+}
+
+/// Compares checkpoint `before` with checkpoint `after`.
+///
+/// Towers are matched on kind, origin and rotation, so a tower moved or rotated shows up as
+/// one removal and one addition.
+pub(crate) fn diff_checkpoints(
+    before: &WorldCheckpoint,
+    after: &WorldCheckpoint,
+) -> CheckpointDiff {
+    let mut towers_removed = before.layout.towers.clone();
+    let mut towers_added = Vec::new();
+    for tower in &after.layout.towers {
+        if let Some(index) = towers_removed.iter().position(|kept| kept == tower) {
+            let _ = towers_removed.swap_remove(index);
+        } else {
+            towers_added.push(tower.clone());
+        }
+    }
+    let order = |tower: &TowerLayoutTower| (tower.origin.row(), tower.origin.column());
+    towers_removed.sort_by_key(order);
+    towers_added.sort_by_key(order);
+
+    CheckpointDiff {
+        wave_before: before.wave,
+        wave_after: after.wave,
+        gold_before: before.gold,
+        gold_after: after.gold,
+        difficulty_before: before.difficulty_level,
+        difficulty_after: after.difficulty_level,
+        towers_added,
+        towers_removed,
+    }
+}
+
+impl fmt::Display for CheckpointDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Since wave {} (now after wave {}):",
+            self.wave_before.get() + 1,
+            self.wave_after.get() + 1
+        )?;
+        writeln!(
+            f,
+            "  Gold: {} -> {} ({:+})",
+            self.gold_before.get(),
+            self.gold_after.get(),
+            self.gold_delta()
+        )?;
+        writeln!(
+            f,
+            "  Difficulty: {} -> {} ({:+})",
+            self.difficulty_before.get(),
+            self.difficulty_after.get(),
+            self.difficulty_delta()
+        )?;
+        write_towers(f, "added", &self.towers_added)?;
+        write_towers(f, "removed", &self.towers_removed)
+    }
+}
+
+fn write_towers(
+    f: &mut fmt::Formatter<'_>,
+    change: &str,
+    towers: &[TowerLayoutTower],
+) -> fmt::Result {
+    if towers.is_empty() {
+        return writeln!(f, "  Towers {change}: none");
+    }
+    writeln!(f, "  Towers {change}: {}", towers.len())?;
+    for tower in towers {
+        writeln!(
+            f,
+            "    {:?} at ({}, {})",
+            tower.kind,
+            tower.origin.column(),
+            tower.origin.row()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{CellCoord, TowerKind, TowerRotation};
+
+    fn checkpoint(
+        wave: u32,
+        gold: u64,
+        difficulty: u32,
+        towers: &[(TowerKind, u32, u32)],
+    ) -> WorldCheckpoint {
+        WorldCheckpoint {
+            wave: WaveId::new(wave),
+            difficulty_level: DifficultyLevel::new(difficulty),
+            gold: Gold::new(gold),
+            layout: TowerLayoutSnapshot {
+                columns: 10,
+                rows: 10,
+                tile_length: 100.0,
+                cells_per_tile: 4,
+                towers: towers
+                    .iter()
+                    .map(|&(kind, column, row)| TowerLayoutTower {
+                        kind,
+                        origin: CellCoord::new(column, row),
+                        rotation: TowerRotation::Deg0,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn diffs_list_tower_changes_and_gold_and_difficulty_deltas() {
+        let before = checkpoint(
+            2,
+            120,
+            4,
+            &[(TowerKind::Basic, 0, 0), (TowerKind::Barrier, 8, 0)],
+        );
+        let after = checkpoint(
+            4,
+            95,
+            5,
+            &[
+                (TowerKind::Repulsor, 12, 4),
+                (TowerKind::Basic, 0, 0),
+                (TowerKind::Basic, 4, 4),
+            ],
+        );
+        let json = serde_json::to_string(&after).expect("serialize checkpoint");
+        let after: WorldCheckpoint = serde_json::from_str(&json).expect("parse checkpoint");
+
+        let diff = diff_checkpoints(&before, &after);
+        assert_eq!(diff.gold_delta(), -25);
+        assert_eq!(diff.difficulty_delta(), 1);
+        assert_eq!(diff.towers_removed.len(), 1);
+        assert_eq!(diff.towers_removed[0].kind, TowerKind::Barrier);
+        assert_eq!(
+            diff.to_string(),
+            "Since wave 3 (now after wave 5):\n\
+             \x20 Gold: 120 -> 95 (-25)\n\
+             \x20 Difficulty: 4 -> 5 (+1)\n\
+             \x20 Towers added: 2\n\
+             \x20   Basic at (4, 4)\n\
+             \x20   Repulsor at (12, 4)\n\
+             \x20 Towers removed: 1\n\
+             \x20   Barrier at (8, 0)\n"
+        );
+        assert!(diff_checkpoints(&after, &after).towers_added.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use glam::Vec2;
+use maze_defence_core::{BugId, Combo};
+use maze_defence_rendering::{BugPresentation, ComboFlash};
+
+/// How long the text of a triggered combo stays on screen.
+const COMBO_FLASH_DURATION: Duration = Duration::from_millis(700);
+
+/// Buffers triggered combos until their bugs' positions are captured, then keeps the
+/// combo's text visible where it hit.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComboFlashes {
+    triggered: Vec<(BugId, Combo)>,
+    playing: Vec<(Vec2, Combo, Duration)>,
+}
+
+impl ComboFlashes {
+    /// Records that a hit on `bug` triggered `combo`.
+    pub(crate) fn record(&mut self, bug: BugId, combo: Combo) {
+        self.triggered.push((bug, combo));
+    }
+
+    /// Starts a flash at every recorded bug's presentation on the previous frame.
+    ///
+    /// Bugs that were never presented are dropped without an effect.
+    pub(crate) fn capture(&mut self, previous: &[BugPresentation]) {
+        for (bug, combo) in self.triggered.drain(..) {
+            if let Some(presentation) = previous.iter().find(|view| view.id == bug) {
+                self.playing
+                    .push((presentation.position(), combo, Duration::ZERO));
+            }
+        }
+    }
+
+    /// Advances every flash, dropping those that finished playing.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.playing.retain_mut(|(_, _, elapsed)| {
+            *elapsed = elapsed.saturating_add(dt);
+            *elapsed < COMBO_FLASH_DURATION
+        });
+    }
+
+    /// Forgets every flash, e.g. when the board is cleared.
+    pub(crate) fn clear(&mut self) {
+        self.triggered.clear();
+        self.playing.clear();
+    }
+
+    /// Flashes that are still playing.
+    pub(crate) fn flashes(&self) -> impl Iterator<Item = ComboFlash> + '_ {
+        self.playing.iter().map(|(position, combo, elapsed)| {
+            ComboFlash::new(
+                *position,
+                *combo,
+                elapsed.as_secs_f32() / COMBO_FLASH_DURATION.as_secs_f32(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_rendering::{BugHealthPresentation, Color};
+
+    #[test]
+    fn combos_flash_over_their_bug_until_they_play_out() {
+        let mut flashes = ComboFlashes::default();
+        flashes.record(BugId::new(4), Combo::Shatter);
+        flashes.record(BugId::new(8), Combo::Shatter);
+        flashes.capture(&[BugPresentation::new_circle(
+            BugId::new(4),
+            Vec2::new(2.5, 6.5),
+            Color::from_rgb_u8(0x10, 0x20, 0x30),
+            BugHealthPresentation::new(3, 3),
+        )]);
+
+        flashes.advance(COMBO_FLASH_DURATION / 2);
+        let playing: Vec<_> = flashes.flashes().collect();
+        assert_eq!(playing.len(), 1, "unpresented bugs are dropped");
+        assert_eq!(playing[0].position, Vec2::new(2.5, 6.5));
+        assert_eq!(playing[0].combo, Combo::Shatter);
+        assert!((playing[0].progress - 0.5).abs() < 1e-3);
+
+        flashes.advance(COMBO_FLASH_DURATION / 2);
+        assert_eq!(flashes.flashes().count(), 0);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use glam::Vec2;
+use maze_defence_core::BugId;
+use maze_defence_rendering::{BugEscape, BugPresentation};
+
+/// Grace period during which a bug that reached the exit keeps sliding off the board.
+const ESCAPE_DURATION: Duration = Duration::from_millis(400);
+/// Direction used when the exit step of a bug is unknown; exits sit on the bottom edge.
+const DEFAULT_ESCAPE_DIRECTION: Vec2 = Vec2::new(0.0, 1.0);
+
+/// Buffers exit events until the exited bugs' last presentations are captured, then
+/// keeps those bugs visible while their escape animation plays.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BugEscapes {
+    exited: Vec<(BugId, Vec2)>,
+    escaping: Vec<(BugPresentation, Vec2, Duration)>,
+}
+
+impl BugEscapes {
+    /// Records a bug that left through the exit travelling along `direction`.
+    pub(crate) fn record(&mut self, bug: BugId, direction: Option<Vec2>) {
+        self.exited
+            .push((bug, direction.unwrap_or(DEFAULT_ESCAPE_DIRECTION)));
+    }
+
+    /// Starts the escape of every recorded bug from its presentation on the previous frame.
+    ///
+    /// Bugs that were never presented, e.g. because they exited on the frame they spawned,
+    /// are dropped without an animation.
+    pub(crate) fn capture(&mut self, previous: &[BugPresentation]) {
+        for (bug, direction) in self.exited.drain(..) {
+            if let Some(presentation) = previous.iter().find(|presentation| presentation.id == bug)
+            {
+                self.escaping
+                    .push((*presentation, direction, Duration::ZERO));
+            }
+        }
+    }
+
+    /// Advances every escape, dropping bugs whose grace period elapsed.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.escaping.retain_mut(|(_, _, elapsed)| {
+            *elapsed = elapsed.saturating_add(dt);
+            *elapsed < ESCAPE_DURATION
+        });
+    }
+
+    /// Forgets every escape, e.g. when the board is cleared.
+    pub(crate) fn clear(&mut self) {
+        self.exited.clear();
+        self.escaping.clear();
+    }
+
+    /// Escapes that are still playing.
+    pub(crate) fn escapes(&self) -> impl Iterator<Item = BugEscape> + '_ {
+        self.escaping
+            .iter()
+            .map(|(presentation, direction, elapsed)| {
+                BugEscape::new(
+                    *presentation,
+                    *direction,
+                    elapsed.as_secs_f32() / ESCAPE_DURATION.as_secs_f32(),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_rendering::{BugHealthPresentation, BugVisual, Color};
+
+    fn bug(id: u32, position: Vec2) -> BugPresentation {
+        BugPresentation::new_circle(
+            BugId::new(id),
+            position,
+            Color::from_rgb_u8(0x10, 0x20, 0x30),
+            BugHealthPresentation::new(3, 3),
+        )
+    }
+
+    #[test]
+    fn exited_bugs_slide_off_and_fade_until_the_grace_period_ends() {
+        let mut escapes = BugEscapes::default();
+        escapes.record(BugId::new(1), None);
+        escapes.record(BugId::new(2), Some(Vec2::new(-2.0, 0.0)));
+        escapes.record(BugId::new(9), None);
+        escapes.capture(&[bug(1, Vec2::new(4.5, 7.5)), bug(2, Vec2::new(0.5, 3.5))]);
+        assert_eq!(escapes.escapes().count(), 2, "unpresented bugs are dropped");
+
+        escapes.advance(ESCAPE_DURATION / 2);
+        let halfway: Vec<_> = escapes
+            .escapes()
+            .map(|escape| escape.presentation())
+            .collect();
+        assert_eq!(halfway[0].position(), Vec2::new(4.5, 8.0));
+        assert_eq!(halfway[1].position(), Vec2::new(0.0, 3.5));
+        let BugVisual::PrimitiveCircle { color } = halfway[0].style else {
+            panic!("circle bugs stay circles while escaping");
+        };
+        assert!((color.alpha - 0.5).abs() < 1e-3);
+
+        escapes.advance(ESCAPE_DURATION / 2);
+        assert_eq!(escapes.escapes().count(), 0);
+    }
+}
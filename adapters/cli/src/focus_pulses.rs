@@ -0,0 +1,225 @@
+//! Camera focus pulses that point the player at important events.
+
+use std::{cmp::Ordering, collections::BinaryHeap, time::Duration};
+
+use glam::Vec2;
+use maze_defence_rendering::{CameraFocus, FocusPulse, FrameInput, TileGridPresentation};
+
+/// How long a pulse holds the camera and its highlight ring.
+const PULSE_DURATION: Duration = Duration::from_millis(1_500);
+/// Longest a request waits for earlier pulses before it is no longer worth showing.
+const MAX_WAIT: Duration = Duration::from_secs(3);
+
+/// Events worth pulling the player's attention to, least important first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum FocusReason {
+    /// A boss entered the maze.
+    BossSpawned,
+    /// The first bug of the wave reached the exit.
+    FirstLeak,
+}
+
+/// Pending pulse, ordered by importance and then by the order it was requested in.
+#[derive(Clone, Copy, Debug)]
+struct FocusRequest {
+    reason: FocusReason,
+    sequence: u64,
+    requested_at: Duration,
+    center: Vec2,
+}
+
+impl PartialEq for FocusRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FocusRequest {}
+
+impl PartialOrd for FocusRequest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FocusRequest {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.reason
+            .cmp(&other.reason)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Queue of focus pulses, played one at a time with the most important request first.
+///
+/// A playing pulse rings its event and, when the camera is zoomed in away from it, moves
+/// the camera onto the event. Player input cancels the playing pulse and every queued one
+/// so the camera never fights the player.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FocusPulses {
+    queue: BinaryHeap<FocusRequest>,
+    playing: Option<(FocusRequest, Duration)>,
+    clock: Duration,
+    next_sequence: u64,
+    leaked_this_wave: bool,
+}
+
+impl FocusPulses {
+    /// Queues a pulse for `reason` at the cell-space point `center`.
+    pub(crate) fn request(&mut self, reason: FocusReason, center: Vec2) {
+        self.queue.push(FocusRequest {
+            reason,
+            sequence: self.next_sequence,
+            requested_at: self.clock,
+            center,
+        });
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.start_next();
+    }
+
+    /// Queues a first-leak pulse at `center` unless a bug already leaked this wave.
+    pub(crate) fn leaked(&mut self, center: Vec2) {
+        if !std::mem::replace(&mut self.leaked_this_wave, true) {
+            self.request(FocusReason::FirstLeak, center);
+        }
+    }
+
+    /// Re-arms the first-leak pulse for a newly launched wave.
+    pub(crate) fn wave_started(&mut self) {
+        self.leaked_this_wave = false;
+    }
+
+    /// Cancels the playing pulse and drops every queued one.
+    pub(crate) fn interrupt(&mut self) {
+        self.queue.clear();
+        self.playing = None;
+    }
+
+    /// Advances the playing pulse, starting the next queued one once it finishes.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.clock = self.clock.saturating_add(dt);
+        if let Some((_, elapsed)) = self.playing.as_mut() {
+            *elapsed = elapsed.saturating_add(dt);
+            if *elapsed >= PULSE_DURATION {
+                self.playing = None;
+            }
+        }
+        self.start_next();
+    }
+
+    /// Highlight ring of the playing pulse, if any.
+    pub(crate) fn pulse(&self) -> Option<FocusPulse> {
+        self.playing.map(|(request, elapsed)| {
+            FocusPulse::new(
+                request.center,
+                elapsed.as_secs_f32() / PULSE_DURATION.as_secs_f32(),
+            )
+        })
+    }
+
+    /// Moves `camera` onto the playing pulse when the pulse lies outside its framing.
+    ///
+    /// Without a camera the whole board is on screen, so nothing needs to move.
+    pub(crate) fn focus(
+        &self,
+        camera: Option<CameraFocus>,
+        tile_grid: &TileGridPresentation,
+    ) -> Option<CameraFocus> {
+        let (Some(camera), Some((request, _))) = (camera, self.playing) else {
+            return camera;
+        };
+        if is_framed(camera, tile_grid, request.center) {
+            return Some(camera);
+        }
+        Some(CameraFocus::new(request.center, camera.zoom))
+    }
+
+    fn start_next(&mut self) {
+        if self.playing.is_some() {
+            return;
+        }
+        while let Some(request) = self.queue.pop() {
+            if self.clock.saturating_sub(request.requested_at) <= MAX_WAIT {
+                self.playing = Some((request, Duration::ZERO));
+                return;
+            }
+        }
+    }
+}
+
+/// Whether `input` carries a deliberate player action that should cancel focus pulses.
+pub(crate) fn interrupts(input: &FrameInput) -> bool {
+    input.mode_toggle
+        || input.start_wave.is_some()
+        || input.confirm_action
+        || input.remove_action
+        || input.group_hotkey.is_some()
+        || input.upgrade_selection
+        || input.dismiss_wave_report
+}
+
+/// Whether `point` is on screen for any viewport the board fits into at `camera`'s zoom.
+fn is_framed(camera: CameraFocus, tile_grid: &TileGridPresentation, point: Vec2) -> bool {
+    let cell_length = tile_grid.cell_length();
+    if !cell_length.is_finite() || cell_length <= 0.0 || camera.zoom <= 0.0 {
+        return true;
+    }
+    let bordered = Vec2::new(tile_grid.bordered_width(), tile_grid.bordered_height());
+    let half_extent = bordered / cell_length / (2.0 * camera.zoom);
+    let offset = (point - camera.center).abs();
+    offset.x <= half_extent.x && offset.y <= half_extent.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_rendering::Color;
+
+    fn grid() -> TileGridPresentation {
+        TileGridPresentation::new(10, 10, 100.0, 1, Color::from_rgb_u8(0, 0, 0))
+            .expect("valid grid")
+    }
+
+    #[test]
+    fn pulses_play_by_importance_and_yield_to_the_player() {
+        let mut pulses = FocusPulses::default();
+        pulses.request(FocusReason::BossSpawned, Vec2::new(1.5, 1.5));
+        pulses.request(FocusReason::BossSpawned, Vec2::new(2.5, 2.5));
+        pulses.leaked(Vec2::new(9.5, 9.5));
+        pulses.leaked(Vec2::new(8.5, 9.5));
+        assert_eq!(
+            pulses.pulse().map(|pulse| pulse.center),
+            Some(Vec2::new(1.5, 1.5)),
+            "the first request plays at once"
+        );
+
+        pulses.advance(PULSE_DURATION);
+        assert_eq!(
+            pulses.pulse().map(|pulse| pulse.center),
+            Some(Vec2::new(9.5, 9.5)),
+            "the first leak outranks the queued boss and repeat leaks are ignored"
+        );
+
+        let camera = CameraFocus::new(Vec2::new(2.0, 2.0), 2.0);
+        assert_eq!(
+            pulses.focus(Some(camera), &grid()),
+            Some(CameraFocus::new(Vec2::new(9.5, 9.5), 2.0)),
+            "an off-screen pulse moves the camera"
+        );
+        assert_eq!(pulses.focus(None, &grid()), None);
+
+        pulses.interrupt();
+        assert_eq!(pulses.pulse(), None);
+        assert_eq!(pulses.focus(Some(camera), &grid()), Some(camera));
+
+        pulses.wave_started();
+        pulses.leaked(Vec2::new(3.5, 3.5));
+        pulses.request(FocusReason::BossSpawned, Vec2::new(4.5, 4.5));
+        pulses.advance(MAX_WAIT + PULSE_DURATION);
+        assert_eq!(
+            pulses.pulse(),
+            None,
+            "requests that waited too long are dropped"
+        );
+    }
+}
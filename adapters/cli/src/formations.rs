@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use maze_defence_core::{BugId, BugRank, FormationId, FormationShape};
+use maze_defence_rendering::FormationEntrance;
+
+use crate::ScheduledSpawn;
+
+/// Largest squad merged from a species burst.
+const FORMATION_MAX_SIZE: u32 = 5;
+/// Spawns of the same species at most this far behind a squad's leader join the squad.
+const FORMATION_WINDOW: Duration = Duration::from_millis(1_500);
+/// Duration of the shared entrance animation played when a squad spawns.
+const FORMATION_ENTRANCE: Duration = Duration::from_millis(600);
+
+/// Squad shape and size attached to a scheduled spawn that enters as a formation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ScheduledFormation {
+    pub(crate) shape: FormationShape,
+    pub(crate) size: u32,
+}
+
+/// Merges bursts of identical bugs into squads that spawn together as formations.
+///
+/// Each squad is led by the earliest spawn of its burst and absorbs up to
+/// `FORMATION_MAX_SIZE - 1` later spawns with the same stats scheduled within
+/// `FORMATION_WINDOW`. Squad shapes cycle through every shape in order so identical
+/// schedules always yield identical formations.
+pub(crate) fn group_into_formations(scheduled: Vec<ScheduledSpawn>) -> Vec<ScheduledSpawn> {
+    let mut absorbed = vec![false; scheduled.len()];
+    let mut grouped = Vec::with_capacity(scheduled.len());
+    let mut squads = 0_usize;
+
+    for (index, leader) in scheduled.iter().enumerate() {
+        if absorbed[index] {
+            continue;
+        }
+
+        let mut size = 1_u32;
+        if leader.rank == BugRank::Regular {
+            for (offset, follower) in scheduled.iter().enumerate().skip(index + 1) {
+                if size == FORMATION_MAX_SIZE
+                    || follower.at.saturating_sub(leader.at) > FORMATION_WINDOW
+                {
+                    break;
+                }
+                if !absorbed[offset] && same_squad(leader, follower) {
+                    absorbed[offset] = true;
+                    size += 1;
+                }
+            }
+        }
+
+        let mut spawn = leader.clone();
+        if size > 1 {
+            spawn.formation = Some(ScheduledFormation {
+                shape: FormationShape::ALL[squads % FormationShape::ALL.len()],
+                size,
+            });
+            squads += 1;
+        }
+        grouped.push(spawn);
+    }
+    grouped
+}
+
+fn same_squad(leader: &ScheduledSpawn, follower: &ScheduledSpawn) -> bool {
+    follower.rank == BugRank::Regular
+        && leader.color == follower.color
+        && leader.health == follower.health
+        && leader.step_ms == follower.step_ms
+        && leader.pathing == follower.pathing
+        && leader.bounty == follower.bounty
+        && leader.archetype == follower.archetype
+}
+
+/// Tracks squads whose entrance animation is still playing.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FormationEntrances {
+    squads: BTreeMap<FormationId, (Vec<BugId>, Duration)>,
+}
+
+impl FormationEntrances {
+    /// Starts the entrance animation of a freshly spawned squad.
+    pub(crate) fn record(&mut self, formation: FormationId, bugs: &[BugId]) {
+        let _ = self
+            .squads
+            .insert(formation, (bugs.to_vec(), Duration::ZERO));
+    }
+
+    /// Advances every entrance, dropping squads whose animation finished.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.squads.retain(|_, (_, elapsed)| {
+            *elapsed = elapsed.saturating_add(dt);
+            *elapsed < FORMATION_ENTRANCE
+        });
+    }
+
+    /// Forgets every squad, e.g. when the board is cleared.
+    pub(crate) fn clear(&mut self) {
+        self.squads.clear();
+    }
+
+    /// Entrance state shared by the squad the bug spawned with, if it is still playing.
+    pub(crate) fn entrance_for(&self, bug: BugId) -> Option<FormationEntrance> {
+        self.squads
+            .iter()
+            .find(|(_, (bugs, _))| bugs.contains(&bug))
+            .map(|(formation, (_, elapsed))| {
+                FormationEntrance::new(
+                    *formation,
+                    elapsed.as_secs_f32() / FORMATION_ENTRANCE.as_secs_f32(),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{BugColor, CellCoord, Health};
+    use std::num::NonZeroU32;
+
+    fn spawn(at_ms: u64, health: u32) -> ScheduledSpawn {
+        ScheduledSpawn::new(
+            Duration::from_millis(at_ms),
+            CellCoord::new(3, 0),
+            BugColor::from_rgb(0x10, 0x20, 0x30),
+            Health::new(health),
+            NonZeroU32::new(250).expect("non-zero step"),
+        )
+    }
+
+    #[test]
+    fn bursts_merge_into_cycling_squads_and_entrances_expire() {
+        let scheduled = vec![
+            spawn(0, 3),
+            spawn(100, 9),
+            spawn(300, 3),
+            spawn(600, 3),
+            spawn(2_000, 3),
+            spawn(2_200, 3),
+            spawn(5_000, 3),
+        ];
+        let grouped = group_into_formations(scheduled);
+        let squads: Vec<_> = grouped
+            .iter()
+            .map(|spawn| (spawn.at.as_millis(), spawn.formation))
+            .collect();
+        assert_eq!(
+            squads,
+            vec![
+                (
+                    0,
+                    Some(ScheduledFormation {
+                        shape: FormationShape::Line,
+                        size: 3,
+                    })
+                ),
+                (100, None),
+                (
+                    2_000,
+                    Some(ScheduledFormation {
+                        shape: FormationShape::Wedge,
+                        size: 2,
+                    })
+                ),
+                (5_000, None),
+            ]
+        );
+
+        let mut entrances = FormationEntrances::default();
+        entrances.record(FormationId::new(4), &[BugId::new(1), BugId::new(2)]);
+        entrances.advance(FORMATION_ENTRANCE / 2);
+        let entrance = entrances.entrance_for(BugId::new(2)).expect("entrance");
+        assert_eq!(entrance.formation, FormationId::new(4));
+        assert!((entrance.progress - 0.5).abs() < 1e-3);
+        assert_eq!(entrances.entrance_for(BugId::new(3)), None);
+        entrances.advance(FORMATION_ENTRANCE);
+        assert_eq!(entrances.entrance_for(BugId::new(1)), None);
+    }
+}
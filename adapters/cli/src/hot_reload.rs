@@ -0,0 +1,287 @@
+//! Dev-build hot reloading of balance and palette files.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use maze_defence_core::{Damage, Gold, TowerContent, TowerKind, TowerStats};
+use maze_defence_pressure_v2::PressureTuning;
+use maze_defence_rendering::{Color, Scene};
+use serde::Deserialize;
+
+use crate::{rgb_color, DEFAULT_GRID_LINE_RGB, DEFAULT_WALL_RGB};
+
+/// Pressure generator tuning, mirroring every field of `PressureTuning`.
+const TUNING_FILE: &str = "tuning.toml";
+/// Per-kind tower statistics keyed by `basic`, `barrier` and `repulsor` tables.
+const TOWERS_FILE: &str = "towers.toml";
+/// Board colours given as `[red, green, blue]` triples.
+const PALETTE_FILE: &str = "palette.toml";
+/// Minimum time between two modification checks of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Board colours that palette files may override.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Palette {
+    grid_line: Color,
+    wall: Color,
+}
+
+impl Palette {
+    /// Recolours the grid lines and perimeter walls of the scene.
+    pub(crate) fn apply(&self, scene: &mut Scene) {
+        scene.tile_grid.line_color = self.grid_line;
+        scene.wall_color = self.wall;
+    }
+}
+
+/// Content parsed from disk that waits for the next wave boundary before it applies.
+#[derive(Debug, Default)]
+pub(crate) struct ContentReload {
+    pub(crate) tuning: Option<PressureTuning>,
+    pub(crate) towers: Option<TowerContent>,
+    pub(crate) palette: Option<Palette>,
+}
+
+impl ContentReload {
+    /// Replaces staged content with every file reloaded in `newer`.
+    pub(crate) fn merge(&mut self, newer: Self) {
+        if newer.tuning.is_some() {
+            self.tuning = newer.tuning;
+        }
+        if newer.towers.is_some() {
+            self.towers = newer.towers;
+        }
+        if newer.palette.is_some() {
+            self.palette = newer.palette;
+        }
+    }
+}
+
+/// Polls a content directory and parses the files whose modification time changed.
+///
+/// Files that are missing or fail to parse are skipped with a message on stderr, so a
+/// half-saved edit never replaces content that is already in use.
+#[derive(Debug)]
+pub(crate) struct ContentWatcher {
+    tuning: WatchedFile,
+    towers: WatchedFile,
+    palette: WatchedFile,
+    since_poll: Duration,
+}
+
+impl ContentWatcher {
+    /// Watches the content files inside `directory`; existing files load on the first poll.
+    pub(crate) fn new(directory: &Path) -> Self {
+        Self {
+            tuning: WatchedFile::new(directory.join(TUNING_FILE)),
+            towers: WatchedFile::new(directory.join(TOWERS_FILE)),
+            palette: WatchedFile::new(directory.join(PALETTE_FILE)),
+            since_poll: POLL_INTERVAL,
+        }
+    }
+
+    /// Advances the poll timer and reloads every file edited since the previous check.
+    pub(crate) fn poll(&mut self, dt: Duration) -> ContentReload {
+        self.since_poll = self.since_poll.saturating_add(dt);
+        if self.since_poll < POLL_INTERVAL {
+            return ContentReload::default();
+        }
+        self.since_poll = Duration::ZERO;
+
+        ContentReload {
+            tuning: self.tuning.reload(parse_tuning),
+            towers: self.towers.reload(parse_towers),
+            palette: self.palette.reload(parse_palette),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WatchedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl WatchedFile {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+        }
+    }
+
+    fn reload<T, E: fmt::Display>(&mut self, parse: fn(&str) -> Result<T, E>) -> Option<T> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+
+        let parsed = fs::read_to_string(&self.path)
+            .map_err(|error| error.to_string())
+            .and_then(|text| parse(&text).map_err(|error| error.to_string()));
+        match parsed {
+            Ok(content) => {
+                eprintln!(
+                    "Reloaded {}; applying at the next wave boundary.",
+                    self.path.display()
+                );
+                Some(content)
+            }
+            Err(error) => {
+                eprintln!("Ignoring {}: {error}", self.path.display());
+                None
+            }
+        }
+    }
+}
+
+/// Parses tuning overrides and rejects values the generator cannot sample from.
+fn parse_tuning(text: &str) -> Result<PressureTuning, String> {
+    let tuning: PressureTuning = toml::from_str(text).map_err(|error| error.to_string())?;
+    tuning.validate().map_err(|error| error.to_string())?;
+    Ok(tuning)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TowersFile {
+    basic: Option<TowerStatsFile>,
+    barrier: Option<TowerStatsFile>,
+    repulsor: Option<TowerStatsFile>,
+    frost: Option<TowerStatsFile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TowerStatsFile {
+    build_cost: Option<u64>,
+    fire_cooldown_ms: Option<u32>,
+    projectile_damage: Option<u32>,
+}
+
+impl TowerStatsFile {
+    fn over(self, base: TowerStats) -> TowerStats {
+        TowerStats {
+            build_cost: self.build_cost.map_or(base.build_cost, Gold::new),
+            fire_cooldown_ms: self.fire_cooldown_ms.unwrap_or(base.fire_cooldown_ms),
+            projectile_damage: self
+                .projectile_damage
+                .map_or(base.projectile_damage, Damage::new),
+        }
+    }
+}
+
+/// Parses tower overrides; kinds and fields left out keep their built-in statistics.
+fn parse_towers(text: &str) -> Result<TowerContent, toml::de::Error> {
+    let file: TowersFile = toml::from_str(text)?;
+    let overrides = [
+        (TowerKind::Basic, file.basic),
+        (TowerKind::Barrier, file.barrier),
+        (TowerKind::Repulsor, file.repulsor),
+        (TowerKind::Frost, file.frost),
+    ];
+    Ok(overrides.into_iter().fold(
+        TowerContent::default(),
+        |content, (kind, stats)| match stats {
+            Some(stats) => content.with_stats(kind, stats.over(kind.base_stats())),
+            None => content,
+        },
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PaletteFile {
+    grid_line: [u8; 3],
+    wall: [u8; 3],
+}
+
+impl Default for PaletteFile {
+    fn default() -> Self {
+        Self {
+            grid_line: DEFAULT_GRID_LINE_RGB,
+            wall: DEFAULT_WALL_RGB,
+        }
+    }
+}
+
+/// Parses palette overrides; colours left out keep the runner's defaults.
+fn parse_palette(text: &str) -> Result<Palette, toml::de::Error> {
+    let file: PaletteFile = toml::from_str(text)?;
+    Ok(Palette {
+        grid_line: rgb_color(file.grid_line),
+        wall: rgb_color(file.wall),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_files_override_only_the_listed_values() {
+        let content =
+            parse_towers("[basic]\nbuild_cost = 4\n\n[repulsor]\nprojectile_damage = 2\n")
+                .expect("valid towers file");
+        assert_eq!(
+            content.stats(TowerKind::Basic),
+            TowerStats {
+                build_cost: Gold::new(4),
+                ..TowerKind::Basic.base_stats()
+            }
+        );
+        assert_eq!(
+            content.stats(TowerKind::Barrier),
+            TowerKind::Barrier.base_stats()
+        );
+        assert_eq!(
+            content.stats(TowerKind::Repulsor).projectile_damage,
+            Damage::new(2)
+        );
+        assert!(parse_towers("[basic]\nrange = 3\n").is_err());
+
+        let palette = parse_palette("wall = [1, 2, 3]\n").expect("valid palette file");
+        assert_eq!(palette.wall, Color::from_rgb_u8(1, 2, 3));
+        assert_eq!(palette.grid_line, rgb_color(DEFAULT_GRID_LINE_RGB));
+
+        let tuning = parse_tuning("[count]\ncap = 40.0\n").expect("tuning");
+        assert_eq!(tuning.count.cap, 40.0);
+        assert_eq!(tuning.count.floor, PressureTuning::default().count.floor);
+        assert!(parse_tuning("[hp]\ndeviation = -1.0\n")
+            .expect_err("negative deviation")
+            .contains("hp.deviation"));
+    }
+
+    #[test]
+    fn watcher_loads_edited_files_once() {
+        let directory =
+            std::env::temp_dir().join(format!("maze-defence-hot-reload-{}", std::process::id()));
+        fs::create_dir_all(&directory).expect("create content directory");
+        fs::write(directory.join(TOWERS_FILE), "[barrier]\nbuild_cost = 1\n").expect("write");
+        fs::write(directory.join(PALETTE_FILE), "wall = \"red\"\n").expect("write");
+
+        let mut watcher = ContentWatcher::new(&directory);
+        let reload = watcher.poll(Duration::ZERO);
+        let towers = reload.towers.expect("towers reloaded");
+        assert_eq!(towers.stats(TowerKind::Barrier).build_cost, Gold::new(1));
+        assert!(reload.tuning.is_none(), "missing files are skipped");
+        assert!(reload.palette.is_none(), "invalid files are skipped");
+
+        let mut staged = ContentReload::default();
+        staged.merge(reload);
+        staged.merge(watcher.poll(POLL_INTERVAL));
+        assert_eq!(
+            staged.towers,
+            Some(towers),
+            "unchanged files are not reparsed"
+        );
+
+        fs::remove_dir_all(&directory).expect("remove content directory");
+    }
+}
@@ -3,7 +3,7 @@
 use std::{error::Error, fmt};
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
-use maze_defence_core::{CellCoord, TowerKind};
+use maze_defence_core::{CellCoord, TowerKind, TowerRotation};
 use serde::{Deserialize, Serialize};
 
 const SNAPSHOT_DOMAIN: &str = "maze";
@@ -13,6 +13,8 @@ const SNAPSHOT_VERSION_V2: &str = "v2";
 pub(crate) const SNAPSHOT_HEADER_V2: &str = "maze:v2";
 /// Delimiter used to separate the prefix, grid dimensions and payload.
 const FIELD_DELIMITER: char = ':';
+/// Bit set on the tower kind byte when the footprint is rotated by 90°.
+const ROTATED_FLAG: u8 = 0x80;
 
 /// Snapshot of the towers placed within the maze and the grid configuration.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -34,14 +36,7 @@ impl TowerLayoutSnapshot {
     #[must_use]
     pub(crate) fn encode(&self) -> String {
         let mut payload = Vec::with_capacity(8 + self.towers.len() * 5);
-        encode_varint(self.cells_per_tile, &mut payload);
-        payload.extend(self.tile_length.to_bits().to_le_bytes());
-        encode_varint(self.towers.len() as u32, &mut payload);
-        for tower in &self.towers {
-            payload.push(encode_tower_kind(tower.kind));
-            encode_varint(tower.origin.column(), &mut payload);
-            encode_varint(tower.origin.row(), &mut payload);
-        }
+        self.write_payload(&mut payload);
         let encoded = URL_SAFE_NO_PAD.encode(payload);
         format!(
             "{SNAPSHOT_HEADER_V2}:{}x{}:{encoded}",
@@ -49,6 +44,49 @@ impl TowerLayoutSnapshot {
         )
     }
 
+    /// Appends the binary v2 payload, which excludes the grid dimensions, to `buffer`.
+    pub(crate) fn write_payload(&self, buffer: &mut Vec<u8>) {
+        encode_varint(self.cells_per_tile, buffer);
+        buffer.extend(self.tile_length.to_bits().to_le_bytes());
+        encode_varint(self.towers.len() as u32, buffer);
+        for tower in &self.towers {
+            buffer.push(encode_tower_kind(tower.kind, tower.rotation));
+            encode_varint(tower.origin.column(), buffer);
+            encode_varint(tower.origin.row(), buffer);
+        }
+    }
+
+    /// Reads a binary v2 payload written by [`Self::write_payload`] starting at `cursor`.
+    pub(crate) fn read_payload(
+        columns: u32,
+        rows: u32,
+        bytes: &[u8],
+        cursor: &mut usize,
+    ) -> Result<Self, LayoutTransferError> {
+        let cells_per_tile = decode_varint(bytes, cursor)?;
+        let tile_length = f32::from_bits(read_u32(bytes, cursor)?);
+        let tower_count = decode_varint(bytes, cursor)? as usize;
+        let mut towers = Vec::with_capacity(tower_count.min(bytes.len()));
+        for _ in 0..tower_count {
+            let (kind, rotation) = decode_tower_kind(read_u8(bytes, cursor)?)?;
+            let column = decode_varint(bytes, cursor)?;
+            let row = decode_varint(bytes, cursor)?;
+            towers.push(TowerLayoutTower {
+                kind,
+                origin: CellCoord::new(column, row),
+                rotation,
+            });
+        }
+
+        Ok(Self {
+            columns,
+            rows,
+            tile_length,
+            cells_per_tile,
+            towers,
+        })
+    }
+
     /// Decodes a snapshot from the provided string representation.
     pub(crate) fn decode(value: &str) -> Result<Self, LayoutTransferError> {
         let trimmed = value.trim();
@@ -82,6 +120,8 @@ pub(crate) struct TowerLayoutTower {
     pub kind: TowerKind,
     /// Upper-left cell anchoring the tower's footprint.
     pub origin: CellCoord,
+    /// Rotation applied to the tower's footprint.
+    pub rotation: TowerRotation,
 }
 
 /// Errors that can occur while decoding layout transfer strings.
@@ -170,37 +210,16 @@ fn decode_v2(
         .decode(payload.as_bytes())
         .map_err(LayoutTransferError::InvalidEncoding)?;
     let mut cursor = 0usize;
-
-    let cells_per_tile = decode_varint(&bytes, &mut cursor)?;
-    let tile_length_bits = read_u32(&bytes, &mut cursor)?;
-    let tile_length = f32::from_bits(tile_length_bits);
-    let tower_count = decode_varint(&bytes, &mut cursor)? as usize;
-    let mut towers = Vec::with_capacity(tower_count);
-    for _ in 0..tower_count {
-        let kind_byte = read_u8(&bytes, &mut cursor)?;
-        let kind = decode_tower_kind(kind_byte)?;
-        let column = decode_varint(&bytes, &mut cursor)?;
-        let row = decode_varint(&bytes, &mut cursor)?;
-        towers.push(TowerLayoutTower {
-            kind,
-            origin: CellCoord::new(column, row),
-        });
-    }
+    let snapshot = TowerLayoutSnapshot::read_payload(columns, rows, &bytes, &mut cursor)?;
 
     if cursor != bytes.len() {
         return Err(LayoutTransferError::TrailingBinaryData);
     }
 
-    Ok(TowerLayoutSnapshot {
-        columns,
-        rows,
-        tile_length,
-        cells_per_tile,
-        towers,
-    })
+    Ok(snapshot)
 }
 
-fn encode_varint(mut value: u32, buffer: &mut Vec<u8>) {
+pub(crate) fn encode_varint(mut value: u32, buffer: &mut Vec<u8>) {
     loop {
         let byte = (value & 0x7f) as u8;
         value >>= 7;
@@ -212,7 +231,7 @@ fn encode_varint(mut value: u32, buffer: &mut Vec<u8>) {
     }
 }
 
-fn decode_varint(bytes: &[u8], cursor: &mut usize) -> Result<u32, LayoutTransferError> {
+pub(crate) fn decode_varint(bytes: &[u8], cursor: &mut usize) -> Result<u32, LayoutTransferError> {
     let mut value = 0u32;
     let mut shift = 0u32;
     for _ in 0..5 {
@@ -230,6 +249,39 @@ fn decode_varint(bytes: &[u8], cursor: &mut usize) -> Result<u32, LayoutTransfer
     Err(LayoutTransferError::VarintOverflow)
 }
 
+pub(crate) fn encode_varint_u64(mut value: u64, buffer: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer.push(byte);
+            break;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+pub(crate) fn decode_varint_u64(
+    bytes: &[u8],
+    cursor: &mut usize,
+) -> Result<u64, LayoutTransferError> {
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    for _ in 0..10 {
+        if *cursor >= bytes.len() {
+            return Err(LayoutTransferError::TruncatedBinaryPayload);
+        }
+        let byte = bytes[*cursor];
+        *cursor += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+    Err(LayoutTransferError::VarintOverflow)
+}
+
 fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, LayoutTransferError> {
     if bytes.len().saturating_sub(*cursor) < 4 {
         return Err(LayoutTransferError::TruncatedBinaryPayload);
@@ -240,7 +292,17 @@ fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, LayoutTransferError
     Ok(u32::from_le_bytes(buffer))
 }
 
-fn read_u8(bytes: &[u8], cursor: &mut usize) -> Result<u8, LayoutTransferError> {
+pub(crate) fn read_u64(bytes: &[u8], cursor: &mut usize) -> Result<u64, LayoutTransferError> {
+    if bytes.len().saturating_sub(*cursor) < 8 {
+        return Err(LayoutTransferError::TruncatedBinaryPayload);
+    }
+    let mut buffer = [0u8; 8];
+    buffer.copy_from_slice(&bytes[*cursor..*cursor + 8]);
+    *cursor += 8;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+pub(crate) fn read_u8(bytes: &[u8], cursor: &mut usize) -> Result<u8, LayoutTransferError> {
     if *cursor >= bytes.len() {
         return Err(LayoutTransferError::TruncatedBinaryPayload);
     }
@@ -249,16 +311,31 @@ fn read_u8(bytes: &[u8], cursor: &mut usize) -> Result<u8, LayoutTransferError>
     Ok(byte)
 }
 
-fn encode_tower_kind(kind: TowerKind) -> u8 {
-    match kind {
+fn encode_tower_kind(kind: TowerKind, rotation: TowerRotation) -> u8 {
+    let kind = match kind {
         TowerKind::Basic => 0,
+        TowerKind::Barrier => 1,
+        TowerKind::Repulsor => 2,
+        TowerKind::Frost => 3,
+    };
+    match rotation {
+        TowerRotation::Deg0 => kind,
+        TowerRotation::Deg90 => kind | ROTATED_FLAG,
     }
 }
 
-fn decode_tower_kind(value: u8) -> Result<TowerKind, LayoutTransferError> {
-    match value {
-        0 => Ok(TowerKind::Basic),
-        other => Err(LayoutTransferError::UnknownTowerKind(other)),
+fn decode_tower_kind(value: u8) -> Result<(TowerKind, TowerRotation), LayoutTransferError> {
+    let rotation = if value & ROTATED_FLAG == 0 {
+        TowerRotation::Deg0
+    } else {
+        TowerRotation::Deg90
+    };
+    match value & !ROTATED_FLAG {
+        0 => Ok((TowerKind::Basic, rotation)),
+        1 => Ok((TowerKind::Barrier, rotation)),
+        2 => Ok((TowerKind::Repulsor, rotation)),
+        3 => Ok((TowerKind::Frost, rotation)),
+        _ => Err(LayoutTransferError::UnknownTowerKind(value)),
     }
 }
 
@@ -312,10 +389,17 @@ mod tests {
             TowerLayoutTower {
                 kind: TowerKind::Basic,
                 origin: CellCoord::new(5, 7),
+                rotation: TowerRotation::Deg0,
             },
             TowerLayoutTower {
                 kind: TowerKind::Basic,
                 origin: CellCoord::new(12, 4),
+                rotation: TowerRotation::Deg0,
+            },
+            TowerLayoutTower {
+                kind: TowerKind::Barrier,
+                origin: CellCoord::new(1, 1),
+                rotation: TowerRotation::Deg90,
             },
         ];
         let snapshot = TowerLayoutSnapshot {
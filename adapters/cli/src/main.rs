@@ -9,45 +9,82 @@
 
 //! Command-line adapter that boots the Maze Defence experience.
 
+mod autoplay;
+mod checkpoint;
+mod combo_flashes;
+mod escapes;
+mod focus_pulses;
+mod formations;
+#[cfg(feature = "dev")]
+mod hot_reload;
 mod layout_transfer;
+mod outcome_log;
+mod repl;
+mod run_summary;
+mod score;
+mod selection;
+mod sight;
+mod spectate;
+mod spectator_feed;
+mod sweep;
+mod whiffs;
 
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     convert::TryFrom,
     f32::consts::{FRAC_PI_2, PI},
     fmt,
-    num::NonZeroU32,
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant},
 };
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use checkpoint::WorldCheckpoint;
 use clap::{Parser, ValueEnum};
+use combo_flashes::ComboFlashes;
+use escapes::BugEscapes;
+use focus_pulses::{FocusPulses, FocusReason};
+use formations::{group_into_formations, FormationEntrances, ScheduledFormation};
 use glam::Vec2;
+#[cfg(feature = "dev")]
+use hot_reload::{ContentReload, ContentWatcher, Palette};
 use layout_transfer::{TowerLayoutSnapshot, TowerLayoutTower};
 use maze_defence_core::{
-    BugColor, BugId, BugView, CellCoord, CellPointHalf, CellRect, CellRectSize, Command,
-    DifficultyLevel, Event, Gold, Health, PendingWaveDifficulty, PlacementError, PlayMode,
-    PressureWaveInputs, PressureWavePlan, ProjectileSnapshot, RemovalError, RoundOutcome,
-    SpawnPatchId, SpeciesId, SpeciesPrototype, SpeciesTableVersion, StatsReport, TileCoord,
-    TowerCooldownView, TowerId, TowerKind, TowerTarget, WaveDifficulty, WaveId,
+    AccuracyModel, BetweenWaveEvent, BugArchetype, BugColor, BugId, BugRank, BugSnapshot, BugView,
+    CellCoord, CellPointHalf, CellRect, CellRectSize, Command, DifficultyLevel, DodgeChance, Event,
+    ExitBehavior, Gold, Health, NestTarget, PathingPersonality, PendingWaveDifficulty,
+    PlacementError, PlayMode, PressureWaveInputs, PressureWavePlan, ProjectileSnapshot,
+    RemovalError, RoundOutcome, SandboxRules, SimClock, SpawnPatchDescriptor, SpawnPatchId,
+    SpeciesId, SpeciesPrototype, SpeciesTableVersion, StatsReport, TileCoord, TowerContent,
+    TowerCooldownView, TowerId, TowerKind, TowerTarget, TowerView, WaveContract, WaveDifficulty,
+    WaveId, WaveReport, MAX_CELLS_PER_TILE,
 };
+#[cfg(feature = "dev")]
+use maze_defence_pressure_v2::PressureV2;
 use maze_defence_rendering::{
-    visuals, AnalyticsPresentation, BugHealthPresentation, BugPresentation, BugVisual, Color,
-    ControlPanelView, DifficultyButtonPresentation, DifficultyPresentation,
-    DifficultySelectionPresentation, FrameInput, FrameSimulationBreakdown, GoldPresentation,
-    GroundKind, GroundSpriteTiles, Presentation, RenderingBackend, Scene, SceneProjectile,
-    SceneTower, SceneWall, SpawnEffect, SpriteKey, TileGridPresentation, TileSpacePosition,
-    TowerInteractionFeedback, TowerPreview, TowerTargetLine,
+    visuals, AnalyticsPresentation, AttackRegion, BetweenWaveEventPresentation,
+    BugHealthPresentation, BugInspection, BugLodPolicy, BugPresentation, BugVisual,
+    ClockPresentation, Color, ControlPanelView, DifficultyButtonPresentation,
+    DifficultyPresentation, DifficultySelectionPresentation, ExitPortal, FrameInput,
+    FrameSimulationBreakdown, FrameTick, GoldFeedback, GoldPresentation, GroundKind,
+    GroundSpriteTiles, LevelProgressPresentation, NestPresentation, Notifications, NumberFormat,
+    PathPreview, Presentation, RenderingBackend, Scene, SceneProjectile, SceneTower, SceneWall,
+    SpawnEdge, SpawnEffect, SpriteKey, TileGridPresentation, TileSpacePosition,
+    TowerCooldownIndicator, TowerInteractionFeedback, TowerPreview, TowerTargetLine,
+    WaveCompositionPresentation, WaveContractPresentation, WaveCountdownPresentation,
+    WaveReportPresentation,
 };
 use maze_defence_rendering_macroquad::MacroquadBackend;
 use maze_defence_system_analytics::{
     select_shortest_navigation_path, total_tower_dps, tower_count, tower_coverage_mean_bps,
-    tower_firing_completion_percent_bps, Analytics, AnalyticsScratch,
+    tower_firing_completion_percent_bps, Analytics, AnalyticsScratch, WaveLaunchSnapshot,
 };
+use maze_defence_system_autoplayer::Autoplayer;
 use maze_defence_system_bootstrap::Bootstrap;
 use maze_defence_system_builder::{
-    Builder as TowerBuilder, BuilderInput as TowerBuilderInput,
+    drag_line_origins, Builder as TowerBuilder, BuilderInput as TowerBuilderInput,
     PlacementPreview as BuilderPlacementPreview,
 };
 use maze_defence_system_movement::Movement;
@@ -55,22 +92,42 @@ use maze_defence_system_spawning::{Config as SpawningConfig, Spawning};
 use maze_defence_system_tower_combat::TowerCombat;
 use maze_defence_system_tower_targeting::TowerTargeting;
 use maze_defence_world::{self as world, query, World};
+use outcome_log::WaveOutcomeLog;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+use run_summary::{RunRules, RunSummary};
+use score::RunTally;
+use selection::TowerSelection;
+use spectate::Spectator;
+use spectator_feed::SpectatorFeedLog;
+use whiffs::ProjectileWhiffs;
 
 const DEFAULT_GRID_COLUMNS: u32 = 10;
 const DEFAULT_GRID_ROWS: u32 = 10;
 const DEFAULT_TILE_LENGTH: f32 = 100.0;
 const DEFAULT_BUG_STEP_MS: u64 = 250;
 const DEFAULT_BUG_SPAWN_INTERVAL_MS: u64 = 1_000;
+const DEFAULT_GRID_LINE_RGB: [u8; 3] = [31, 54, 22];
+const DEFAULT_WALL_RGB: [u8; 3] = [68, 45, 15];
 const SPAWN_RNG_SEED: u64 = 0x4d59_5df4_d0f3_3173;
 const TILE_LENGTH_TOLERANCE: f32 = 1e-3;
 const DEFAULT_BUG_HEADING: f32 = 0.0;
+/// Fast-forward speed from which all but the last world tick of a frame are batched.
+const BATCHED_TICK_SPEED: u32 = 8;
 const GROUND_TILE_MULTIPLIER: f32 = 4.0;
 const MIN_SPAWN_BAND: usize = 5;
 const MAX_SPAWN_BAND: usize = 10;
 const SPAWN_BAND_FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
 const SPAWN_BAND_FNV_PRIME: u64 = 0x0000_0001_0000_01b3;
+const TOWER_READY_FLASH: Duration = Duration::from_millis(250);
+const BUG_PICK_RADIUS_CELLS: f32 = 0.75;
+const SPAWN_EFFECT_BASE_RADIUS_CELLS: f32 = 0.35;
+const SPAWN_EFFECT_TIER_RADIUS_STEP: f32 = 0.25;
+const SPAWN_EFFECT_BASE_DURATION: Duration = Duration::from_millis(400);
+const SPAWN_EFFECT_TIER_DURATION_STEP: Duration = Duration::from_millis(200);
+const SPAWN_EFFECT_MAX_TIER: u32 = 3;
+/// Score a run needs for every point of prestige it awards when it ends.
+const SCORE_PER_PRESTIGE: u64 = 10;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct PlacementRejection {
     kind: TowerKind,
@@ -103,6 +160,66 @@ pub fn push_tower_targets(scene: &mut Scene, targets: &[TowerTarget]) {
     }
 }
 
+/// Populates the scene with reload indicators derived from tower cooldown snapshots.
+///
+/// Reload progress is measured against the fire cooldown `content` assigns to each kind.
+/// `ready_flash` resolves the current flash intensity for each tower so adapters can
+/// highlight the moment a reload completes.
+pub fn push_tower_cooldowns(
+    scene: &mut Scene,
+    cooldowns: &TowerCooldownView,
+    towers: &TowerView,
+    content: &TowerContent,
+    mut ready_flash: impl FnMut(TowerId) -> f32,
+) {
+    scene.tower_cooldowns.clear();
+    for snapshot in cooldowns.iter() {
+        let Some(tower) = towers.iter().find(|tower| tower.id == snapshot.tower) else {
+            continue;
+        };
+        let cooldown_ms = content.stats(snapshot.kind).fire_cooldown_ms;
+        let progress = if cooldown_ms == 0 {
+            1.0
+        } else {
+            let remaining = snapshot.ready_in.as_secs_f32() * 1_000.0;
+            1.0 - remaining / cooldown_ms as f32
+        };
+        let mut indicator = TowerCooldownIndicator::new(
+            snapshot.tower,
+            tower.region,
+            progress,
+            ready_flash(snapshot.tower),
+        );
+        if let Some(heat) = snapshot.heat {
+            indicator = indicator.with_heat(heat.fraction(), heat.overheated);
+        }
+        scene.tower_cooldowns.push(indicator);
+    }
+}
+
+/// Selects the bug closest to `cursor`, ignoring bugs further than `radius` away.
+///
+/// Both the cursor and the radius are expressed in cell units. Ties resolve to the bug that
+/// appears first in `bugs`, which keeps picking deterministic for id-sorted inputs.
+pub fn pick_hovered_bug(
+    bugs: &[BugPresentation],
+    cursor: Vec2,
+    radius: f32,
+) -> Option<&BugPresentation> {
+    let radius_squared = radius * radius;
+    let mut closest: Option<(&BugPresentation, f32)> = None;
+    for bug in bugs {
+        let distance_squared = (bug.position() - cursor).length_squared();
+        if distance_squared > radius_squared {
+            continue;
+        }
+        if closest.is_none_or(|(_, best)| distance_squared < best) {
+            closest = Some((bug, distance_squared));
+        }
+    }
+    closest.map(|(bug, _)| bug)
+}
+
 /// Populates the scene with projectiles derived from world snapshots.
 ///
 /// `target_position` resolves the current cell-space destination for each projectile
@@ -153,6 +270,10 @@ pub fn push_projectiles(
     }
 }
 
+fn rgb_color([red, green, blue]: [u8; 3]) -> Color {
+    Color::from_rgb_u8(red, green, blue)
+}
+
 fn parse_difficulty_level(value: &str) -> std::result::Result<DifficultyLevel, String> {
     value
         .parse::<u32>()
@@ -169,17 +290,17 @@ struct CliArgs {
     #[arg(short = 's', long = "size", value_name = "WIDTHxHEIGHT", conflicts_with_all = ["width", "height"])]
     grid_size: Option<GridSizeArg>,
     /// Number of columns in the tile grid when using explicit dimensions.
-    #[arg(long, value_name = "COLUMNS", requires = "height")]
+    #[arg(long, value_name = "COLUMNS", requires = "height", value_parser = clap::value_parser!(u32).range(1..))]
     width: Option<u32>,
     /// Number of rows in the tile grid when using explicit dimensions.
-    #[arg(long, value_name = "ROWS", requires = "width")]
+    #[arg(long, value_name = "ROWS", requires = "width", value_parser = clap::value_parser!(u32).range(1..))]
     height: Option<u32>,
     /// Number of cells drawn along each tile edge when rendering.
     #[arg(
         long = "cells-per-tile",
         value_name = "COUNT",
         default_value_t = TileGridPresentation::DEFAULT_CELLS_PER_TILE,
-        value_parser = clap::value_parser!(u32).range(1..)
+        value_parser = clap::value_parser!(u32).range(1..=i64::from(MAX_CELLS_PER_TILE))
     )]
     cells_per_tile: u32,
     /// Milliseconds each bug waits between steps. Smaller values make bugs move faster.
@@ -202,17 +323,45 @@ struct CliArgs {
     #[arg(long = "difficulty", value_name = "LEVEL", value_parser = parse_difficulty_level)]
     difficulty: Option<DifficultyLevel>,
     /// Sets the gold amount available when the simulation launches.
-    #[arg(long = "gold", value_name = "AMOUNT", value_parser = clap::value_parser!(u32))]
-    gold: Option<u32>,
-    /// Requests that the renderer either synchronise presentation with the display refresh rate or run uncapped.
+    #[arg(long = "gold", value_name = "AMOUNT", value_parser = clap::value_parser!(u64))]
+    gold: Option<u64>,
+    /// Launches the next wave automatically this many milliseconds after a round resolves.
+    #[arg(
+        long = "auto-wave-delay-ms",
+        value_name = "MILLISECONDS",
+        value_parser = clap::value_parser!(u64).range(1..=600_000)
+    )]
+    auto_wave_delay_ms: Option<u64>,
+    /// Limits Builder mode to this many milliseconds between waves, then switches to Attack mode.
+    #[arg(
+        long = "builder-time-limit-ms",
+        value_name = "MILLISECONDS",
+        value_parser = clap::value_parser!(u64).range(1..=600_000)
+    )]
+    builder_time_limit_ms: Option<u64>,
+    /// Requests that the renderer either synchronise presentation with the display refresh rate or run uncapped, overriding the profile.
     #[arg(long, value_enum, value_name = "on|off")]
     vsync: Option<VsyncMode>,
     /// Restores the provided layout snapshot before the first frame renders.
     #[arg(long, value_name = "LAYOUT")]
     layout: Option<String>,
-    /// Controls whether per-second frame timing metrics are printed to stdout.
-    #[arg(long = "show-fps", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
-    show_fps: Toggle,
+    /// Replays a shared run: rebuilds its final maze and starts over on its seed, difficulty
+    /// and rules.
+    #[arg(
+        long = "import-run",
+        value_name = "CODE",
+        conflicts_with_all = ["layout", "seed", "difficulty"]
+    )]
+    import_run: Option<String>,
+    /// Controls whether per-second frame timing metrics are printed to stdout, overriding the profile.
+    #[arg(long = "show-fps", value_enum, value_name = "on|off")]
+    show_fps: Option<Toggle>,
+    /// Controls whether tower reload indicators are visible when the game launches, overriding the profile.
+    #[arg(long = "tower-cooldowns", value_enum, value_name = "on|off")]
+    tower_cooldowns: Option<Toggle>,
+    /// Controls whether animations and flashing effects are toned down, overriding the profile and the system setting.
+    #[arg(long = "reduced-motion", value_enum, value_name = "on|off")]
+    reduced_motion: Option<Toggle>,
     /// Selects whether sprites or primitive shapes render towers and bugs.
     #[arg(
         long = "visual-style",
@@ -221,6 +370,178 @@ struct CliArgs {
         default_value_t = VisualStyle::Sprites
     )]
     visual_style: VisualStyle,
+    /// Enables sandbox rules: free tower placement, instant cooldowns and debug bug spawns.
+    #[arg(long = "sandbox", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    sandbox: Toggle,
+    /// Spawns bursts of identical bugs as squads in line, wedge or blob formations.
+    #[arg(long = "formations", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    formations: Toggle,
+    /// Makes towers build heat when firing and stall for a while once they overheat.
+    #[arg(long = "tower-heat", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    tower_heat: Toggle,
+    /// Lets walls and other towers block shots, so mazes need open firing lanes.
+    #[arg(
+        long = "line-of-sight",
+        value_enum,
+        value_name = "on|off",
+        default_value_t = Toggle::Off
+    )]
+    line_of_sight: Toggle,
+    /// Lets projectiles miss bugs that moved more than CELLS cells while the shot was in flight
+    /// and bugs whose species dodges.
+    #[arg(
+        long = "accuracy-drift-cells",
+        value_name = "CELLS",
+        value_parser = clap::value_parser!(u32).range(0..=1_000)
+    )]
+    accuracy_drift_cells: Option<u32>,
+    /// Sends bugs reaching the exit back to a spawner with PERCENT percent of their health
+    /// instead of ending the round.
+    #[arg(
+        long = "loop-around",
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u32).range(1..=99)
+    )]
+    loop_around: Option<u32>,
+    /// Controls whether the runner ignores player input and directs the camera automatically.
+    #[arg(long = "spectate", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    spectate: Toggle,
+    /// Draws a ring around the first leak of each wave and every boss spawn, moving a zoomed-in
+    /// camera onto them; any player input cancels the pulse.
+    #[arg(long = "focus-pulses", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    focus_pulses: Toggle,
+    /// Fast-forwards the game, simulating MULTIPLIER frames for every rendered one. From 8x
+    /// upwards tower cooldowns and projectiles integrate in larger steps; bugs, shots and hits
+    /// still resolve exactly as at normal speed.
+    #[arg(
+        long = "speed",
+        value_name = "MULTIPLIER",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..=32)
+    )]
+    speed: u32,
+    /// Runs headlessly, reading inspection and stepping commands from stdin instead of opening a window.
+    #[arg(long = "repl", value_enum, value_name = "on|off", default_value_t = Toggle::Off)]
+    repl: Toggle,
+    /// Runs headlessly while a bot builds the maze and launches WAVES waves, then prints the
+    /// outcome of each wave and the win rate.
+    #[arg(
+        long = "autoplay",
+        value_name = "WAVES",
+        conflicts_with = "repl",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    autoplay: Option<u32>,
+    /// Seeds wave generation and between-wave rolls so a run can be reproduced.
+    #[arg(long = "seed", value_name = "SEED")]
+    seed: Option<u64>,
+    /// Plays every combination of sweep seed, difficulty and tuning for the --autoplay wave
+    /// count in parallel and writes one CSV record per wave to PATH.
+    #[arg(
+        long = "sweep-output",
+        value_name = "PATH",
+        requires = "autoplay",
+        conflicts_with = "spectate"
+    )]
+    sweep_output: Option<PathBuf>,
+    /// Writes one JSON line per resolved wave to PATH, recording its difficulty, composition,
+    /// towers, leaks and duration for balancing dashboards.
+    #[arg(
+        long = "wave-outcomes",
+        value_name = "PATH",
+        conflicts_with = "sweep_output"
+    )]
+    wave_outcomes: Option<PathBuf>,
+    /// Saves a JSON checkpoint of the wave, difficulty level, gold and towers into DIRECTORY
+    /// after every resolved wave, one file per wave.
+    #[arg(
+        long = "checkpoints",
+        value_name = "DIRECTORY",
+        conflicts_with = "sweep_output"
+    )]
+    checkpoints: Option<PathBuf>,
+    /// Prints what changed between two checkpoints saved by --checkpoints, then exits.
+    #[arg(
+        long = "diff-checkpoints",
+        value_names = ["BEFORE", "AFTER"],
+        num_args = 2
+    )]
+    diff_checkpoints: Vec<PathBuf>,
+    /// Writes the board as JSON lines to PATH every frame, as periodic keyframes and the
+    /// changes in between, so a remote viewer can follow the run without simulating it.
+    #[arg(
+        long = "spectator-feed",
+        value_name = "PATH",
+        conflicts_with = "sweep_output"
+    )]
+    spectator_feed: Option<PathBuf>,
+    /// Number of seeds each sweep combination is played with, counting up from --seed.
+    #[arg(
+        long = "sweep-seeds",
+        value_name = "COUNT",
+        default_value_t = 1,
+        requires = "sweep_output",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    sweep_seeds: u64,
+    /// Starting difficulty level swept over; repeat for several levels. Defaults to --difficulty.
+    #[arg(
+        long = "sweep-difficulty",
+        value_name = "LEVEL",
+        requires = "sweep_output",
+        value_parser = parse_difficulty_level
+    )]
+    sweep_difficulties: Vec<DifficultyLevel>,
+    /// Number of sweep runs played at once; defaults to the available parallelism.
+    #[arg(
+        long = "sweep-jobs",
+        value_name = "COUNT",
+        requires = "sweep_output",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    sweep_jobs: Option<u32>,
+    /// Covers the tile at COLUMN,ROW with a bridge deck; repeat to lay out longer bridges.
+    #[arg(long = "bridge", value_name = "COLUMN,ROW")]
+    bridges: Vec<TileArg>,
+    /// Floods the tile at COLUMN,ROW with water that bugs only cross on a bridge; repeat to
+    /// lay out lakes and rivers.
+    #[arg(long = "void", value_name = "COLUMN,ROW")]
+    void_tiles: Vec<TileArg>,
+    /// Turns every spawner set into a nest with HP health; towers without a bug in range
+    /// shoot nests, and each destroyed nest closes its spawners and thins later waves.
+    #[arg(
+        long = "nest-health",
+        value_name = "HP",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    nest_health: Option<u32>,
+    /// Profile storing presentation preferences; defaults to maze-defence/profile.toml in the
+    /// user's configuration directory.
+    #[arg(long = "profile", value_name = "PATH")]
+    profile: Option<PathBuf>,
+    /// Watches DIR for tuning.toml, towers.toml and palette.toml and applies edits between waves.
+    #[cfg(feature = "dev")]
+    #[arg(long = "content-dir", value_name = "DIR")]
+    content_dir: Option<std::path::PathBuf>,
+    /// Content directory whose tuning, towers and palette a sweep is played with; repeat to
+    /// compare several tunings. Defaults to --content-dir.
+    #[cfg(feature = "dev")]
+    #[arg(
+        long = "sweep-content",
+        value_name = "DIR",
+        requires = "sweep_output",
+        conflicts_with = "content_dir"
+    )]
+    sweep_content: Vec<std::path::PathBuf>,
+}
+
+/// Profile location under `$XDG_CONFIG_HOME`, falling back to `$HOME/.config`.
+fn default_profile_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("maze-defence").join("profile.toml"))
 }
 
 /// CLI argument controlling whether vertical sync is requested from the rendering backend.
@@ -298,10 +619,65 @@ impl FromStr for GridSizeArg {
     }
 }
 
+/// Tile coordinates parsed from a COLUMN,ROW command-line argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TileArg {
+    column: u32,
+    row: u32,
+}
+
+impl FromStr for TileArg {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (column, row) = value
+            .split_once(',')
+            .ok_or_else(|| "expected format COLUMN,ROW".to_string())?;
+
+        let column = column
+            .trim()
+            .parse::<u32>()
+            .map_err(|error| format!("invalid column: {error}"))?;
+        let row = row
+            .trim()
+            .parse::<u32>()
+            .map_err(|error| format!("invalid row: {error}"))?;
+
+        Ok(Self { column, row })
+    }
+}
+
 /// Entry point for the Maze Defence command-line interface.
 fn main() -> Result<()> {
     let args = CliArgs::parse();
-    let show_fps = args.show_fps.enabled();
+
+    if let [before, after] = args.diff_checkpoints.as_slice() {
+        let diff = checkpoint::diff_checkpoints(
+            &WorldCheckpoint::load(before)?,
+            &WorldCheckpoint::load(after)?,
+        );
+        print!("{diff}");
+        return Ok(());
+    }
+
+    let imported_run = args
+        .import_run
+        .as_deref()
+        .map(|code| {
+            RunSummary::decode(code)
+                .map_err(|error| anyhow!("Failed to decode share code: {error}"))
+        })
+        .transpose()
+        .with_context(|| "failed to import run from --import-run")?;
+    if let Some(run) = &imported_run {
+        eprintln!(
+            "Imported run: wave {} reached after {} with a score of {} on seed {}",
+            run.wave,
+            NumberFormat::from_env().duration(run.elapsed),
+            run.score,
+            run.seed
+        );
+    }
 
     let layout_snapshot = args
         .layout
@@ -311,7 +687,8 @@ fn main() -> Result<()> {
                 .map_err(|error| anyhow!("Failed to decode layout snapshot: {error}"))
         })
         .transpose()
-        .with_context(|| "failed to restore layout from --layout")?;
+        .with_context(|| "failed to restore layout from --layout")?
+        .or_else(|| imported_run.as_ref().map(|run| run.layout.clone()));
 
     let (columns, rows) = if let Some(snapshot) = &layout_snapshot {
         (snapshot.columns, snapshot.rows)
@@ -323,26 +700,43 @@ fn main() -> Result<()> {
         (DEFAULT_GRID_COLUMNS, DEFAULT_GRID_ROWS)
     };
 
-    let bug_step_duration = Duration::from_millis(args.bug_step_ms);
-    let bug_spawn_interval = Duration::from_millis(args.bug_spawn_interval_ms);
-    let initial_difficulty = args.difficulty;
-    let initial_gold = args.gold.map(Gold::new);
-    let mut simulation = Simulation::new(
-        columns,
-        rows,
-        DEFAULT_TILE_LENGTH,
-        args.cells_per_tile,
-        bug_step_duration,
-        bug_spawn_interval,
-        args.visual_style,
-        initial_difficulty,
-        initial_gold,
-    );
-    if let Some(snapshot) = layout_snapshot.as_ref() {
-        simulation
-            .apply_layout_snapshot(snapshot)
-            .map_err(anyhow::Error::from)
-            .with_context(|| "failed to restore layout from --layout")?;
+    if let (Some(path), Some(waves)) = (args.sweep_output.as_deref(), args.autoplay) {
+        return run_sweep(
+            &args,
+            (columns, rows),
+            layout_snapshot.as_ref(),
+            path,
+            waves,
+        );
+    }
+
+    let mut simulation = new_simulation(
+        &args,
+        (columns, rows),
+        layout_snapshot.as_ref(),
+        imported_run
+            .as_ref()
+            .map(|run| DifficultyLevel::new(run.difficulty))
+            .or(args.difficulty),
+        imported_run.as_ref().map(|run| run.seed).or(args.seed),
+    )?;
+    if let Some(run) = &imported_run {
+        simulation.apply_run_rules(run.rules);
+    }
+    if let Some(path) = args.wave_outcomes.as_deref() {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        simulation.log_wave_outcomes(WaveOutcomeLog::new(std::io::BufWriter::new(file)));
+    }
+    if let Some(directory) = args.checkpoints.as_deref() {
+        std::fs::create_dir_all(directory)
+            .with_context(|| format!("failed to create {}", directory.display()))?;
+        simulation.save_checkpoints_to(directory.to_path_buf());
+    }
+    if let Some(path) = args.spectator_feed.as_deref() {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        simulation.stream_to_spectators(SpectatorFeedLog::new(std::io::BufWriter::new(file)));
     }
     let bootstrap = Bootstrap;
     let (banner, grid_scene, wall_color) = {
@@ -354,38 +748,37 @@ fn main() -> Result<()> {
             tile_grid.rows().get(),
             tile_grid.tile_length(),
             args.cells_per_tile,
-            Color::from_rgb_u8(31, 54, 22),
+            rgb_color(DEFAULT_GRID_LINE_RGB),
         )?;
-        let wall_color = Color::from_rgb_u8(68, 45, 15);
+        let wall_color = rgb_color(DEFAULT_WALL_RGB);
         (banner, grid_scene, wall_color)
     };
 
-    let mut scene = Scene::new(
-        grid_scene,
-        wall_color,
-        None,
-        Vec::new(),
-        Vec::new(),
-        Vec::new(),
-        Vec::new(),
-        Vec::new(),
-        Vec::new(),
-        None,
-        query::play_mode(simulation.world()),
-        None,
-        None,
-        None,
-        Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
-        Some(GoldPresentation::new(query::gold(simulation.world()))),
-        Some(DifficultyPresentation::new(
+    let mut scene = Scene {
+        control_panel: Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
+        gold: Some(GoldPresentation::new(query::gold(simulation.world()))),
+        difficulty: Some(DifficultyPresentation::new(
             query::difficulty_level(simulation.world()).get(),
         )),
-        None,
-        None,
-        false,
-    );
+        ..Scene::new(grid_scene, wall_color, query::play_mode(simulation.world()))
+    };
     simulation.populate_scene(&mut scene);
 
+    if let Some(waves) = args.autoplay {
+        simulation.enable_autoplay();
+        return autoplay::run(&mut simulation, &mut scene, waves, std::io::stdout());
+    }
+
+    if args.repl.enabled() {
+        return repl::run(
+            &mut simulation,
+            &mut scene,
+            std::io::stdin().lock(),
+            std::io::stdout(),
+        );
+    }
+
+    simulation.set_speed(args.speed);
     let presentation = Presentation::new(banner, Color::from_rgb_u8(85, 142, 52), scene);
 
     let backend = match args.vsync {
@@ -393,25 +786,174 @@ fn main() -> Result<()> {
         Some(VsyncMode::Off) => MacroquadBackend::default().with_vsync(false),
         None => MacroquadBackend::default(),
     };
+    let backend = match args.show_fps {
+        Some(show_fps) => backend.with_show_fps(show_fps.enabled()),
+        None => backend,
+    };
+    let backend = match args.tower_cooldowns {
+        Some(tower_cooldowns) => backend.with_show_tower_cooldowns(tower_cooldowns.enabled()),
+        None => backend,
+    };
+    let backend = match args.reduced_motion {
+        Some(reduced_motion) => backend.with_reduced_motion(reduced_motion.enabled()),
+        None => backend,
+    };
     let backend = backend
-        .with_show_fps(show_fps)
-        .with_sprite_loading(args.visual_style == VisualStyle::Sprites);
+        .with_sprite_loading(args.visual_style == VisualStyle::Sprites)
+        .with_profile_path(args.profile.or_else(default_profile_path));
 
-    backend.run(presentation, move |dt, input, scene| {
-        simulation.handle_input(input);
-        simulation.advance(dt);
-        let populate_start = Instant::now();
-        simulation.populate_scene(scene);
-        let scene_population = populate_start.elapsed();
-        let advance_profile = simulation.last_advance_profile();
-        FrameSimulationBreakdown::new(
-            advance_profile.total,
-            advance_profile.pathfinding,
-            scene_population,
-        )
+    backend.run(presentation, move |tick, input, scene| {
+        simulation.run_frame(tick, input, scene)
     })
 }
 
+/// Creates a simulation configured by the command-line arguments.
+fn new_simulation(
+    args: &CliArgs,
+    (columns, rows): (u32, u32),
+    layout_snapshot: Option<&TowerLayoutSnapshot>,
+    difficulty: Option<DifficultyLevel>,
+    seed: Option<u64>,
+) -> Result<Simulation> {
+    let mut simulation = Simulation::new(
+        columns,
+        rows,
+        DEFAULT_TILE_LENGTH,
+        args.cells_per_tile,
+        Duration::from_millis(args.bug_step_ms),
+        Duration::from_millis(args.bug_spawn_interval_ms),
+        args.visual_style,
+        difficulty,
+        args.gold.map(Gold::new),
+    );
+    let grid = query::tile_grid(simulation.world());
+    if (grid.columns().get(), grid.rows().get()) != (columns, rows)
+        || query::cells_per_tile(simulation.world()) != args.cells_per_tile
+    {
+        bail!(
+            "a {columns}x{rows} grid with {} cells per tile is too large to simulate",
+            args.cells_per_tile
+        );
+    }
+    simulation.configure_auto_wave(args.auto_wave_delay_ms.map(Duration::from_millis));
+    simulation.configure_builder_timer(args.builder_time_limit_ms.map(Duration::from_millis));
+    if let Some(seed) = seed {
+        simulation.configure_wave_seed(seed);
+    }
+    if args.sandbox.enabled() {
+        simulation.enable_sandbox();
+    }
+    if args.tower_heat.enabled() {
+        simulation.enable_tower_heat();
+    }
+    if args.line_of_sight.enabled() {
+        simulation.enable_line_of_sight();
+    }
+    if let Some(cells) = args.accuracy_drift_cells {
+        simulation.enable_accuracy(AccuracyModel::new(cells));
+    }
+    if let Some(health_percent) = args.loop_around {
+        simulation.configure_exit_behavior(ExitBehavior::LoopAround { health_percent });
+    }
+    if args.formations.enabled() {
+        simulation.enable_formations();
+    }
+    if !args.bridges.is_empty() {
+        simulation.configure_bridges(&args.bridges);
+    }
+    if !args.void_tiles.is_empty() {
+        simulation.configure_void_tiles(&args.void_tiles);
+    }
+    if let Some(health) = args.nest_health {
+        simulation.enable_nests(Health::new(health));
+    }
+    if args.spectate.enabled() {
+        simulation.enable_spectate();
+    }
+    if args.focus_pulses.enabled() {
+        simulation.enable_focus_pulses();
+    }
+    #[cfg(feature = "dev")]
+    if let Some(directory) = args.content_dir.as_deref() {
+        simulation.watch_content(directory);
+    }
+    if let Some(snapshot) = layout_snapshot {
+        simulation
+            .apply_layout_snapshot(snapshot)
+            .map_err(anyhow::Error::from)
+            .with_context(|| "failed to restore layout from --layout")?;
+    }
+    Ok(simulation)
+}
+
+/// Plays the sweep requested by the `--sweep-*` arguments and writes its records to `path`.
+fn run_sweep(
+    args: &CliArgs,
+    dimensions: (u32, u32),
+    layout_snapshot: Option<&TowerLayoutSnapshot>,
+    path: &Path,
+    waves: u32,
+) -> Result<()> {
+    let difficulties: Vec<Option<DifficultyLevel>> = if args.sweep_difficulties.is_empty() {
+        vec![args.difficulty]
+    } else {
+        args.sweep_difficulties.iter().copied().map(Some).collect()
+    };
+    #[cfg(feature = "dev")]
+    let tunings: Vec<Option<PathBuf>> = if args.sweep_content.is_empty() {
+        vec![args.content_dir.clone()]
+    } else {
+        args.sweep_content.iter().cloned().map(Some).collect()
+    };
+    #[cfg(not(feature = "dev"))]
+    let tunings: Vec<Option<PathBuf>> = vec![None];
+    let first_seed = args.seed.unwrap_or(0);
+    let seeds: Vec<u64> = (0..args.sweep_seeds)
+        .map(|offset| first_seed.wrapping_add(offset))
+        .collect();
+    let runs = sweep::runs(&tunings, &difficulties, &seeds);
+    let jobs = args
+        .sweep_jobs
+        .and_then(|jobs| NonZeroUsize::new(usize::try_from(jobs).unwrap_or(usize::MAX)))
+        .or_else(|| std::thread::available_parallelism().ok())
+        .unwrap_or(NonZeroUsize::MIN);
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let summary = sweep::run(
+        &runs,
+        waves,
+        jobs,
+        |run| {
+            // Only development builds can swap in another tuning.
+            #[cfg_attr(not(feature = "dev"), allow(unused_mut))]
+            let mut simulation = new_simulation(
+                args,
+                dimensions,
+                layout_snapshot,
+                run.difficulty,
+                Some(run.seed),
+            )?;
+            #[cfg(feature = "dev")]
+            if let Some(directory) = run.tuning.as_deref() {
+                simulation.watch_content(directory);
+            }
+            Ok(simulation)
+        },
+        std::io::BufWriter::new(file),
+    )?;
+    println!(
+        "played {} runs of {waves} waves, wrote {} records to {}",
+        runs.len(),
+        summary.records,
+        path.display()
+    );
+    if summary.failed > 0 {
+        bail!("{} of {} sweep runs failed", summary.failed, runs.len());
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Simulation {
     world: World,
@@ -421,18 +963,43 @@ struct Simulation {
     tower_targeting: TowerTargeting,
     tower_combat: TowerCombat,
     tower_cooldowns: TowerCooldownView,
+    tower_ready_flashes: HashMap<TowerId, ReadyFlash>,
     projectiles: Vec<ProjectileSnapshot>,
     current_targets: Vec<TowerTarget>,
+    nest_targets: Vec<NestTarget>,
     pending_events: Vec<Event>,
     scratch_commands: Vec<Command>,
     queued_commands: Vec<Command>,
     pending_input: FrameInput,
+    /// Time by which drawn bug motion leads the last simulation update.
+    motion_lead: Duration,
     builder_preview: Option<BuilderPlacementPreview>,
+    drag_placement: Option<DragPlacement>,
+    hovered_bug: Option<BugId>,
+    tower_selection: TowerSelection,
+    path_preview: Option<(CellRect, PathPreview)>,
+    spectator: Option<Spectator>,
+    focus_pulses: Option<FocusPulses>,
+    speed: u32,
+    starting_difficulty: DifficultyLevel,
+    tally: RunTally,
+    run_summary: Option<RunSummary>,
+    prestige_earned: u32,
+    autoplayer: Option<Autoplayer>,
+    console_output: bool,
     tower_feedback: Option<TowerInteractionFeedback>,
     analytics_report: Option<StatsReport>,
+    wave_report: Option<WaveReport>,
+    between_wave_event: Option<BetweenWaveEvent>,
+    latest_wave: Option<WaveId>,
     analytics: Analytics,
+    wave_outcome_log: Option<WaveOutcomeLog>,
+    checkpoint_directory: Option<PathBuf>,
+    spectator_feed: Option<SpectatorFeedLog>,
     applied_commands: Vec<Command>,
     gold: Gold,
+    gold_feedback: GoldFeedback,
+    notifications: Notifications,
     difficulty_level: DifficultyLevel,
     pending_wave_difficulty: PendingWaveDifficulty,
     pending_wave_launch: Option<PendingWaveLaunch>,
@@ -452,9 +1019,25 @@ struct Simulation {
     active_wave_plan: Option<PressureWavePlan>,
     last_attack_plan: Option<ReplayAttackPlan>,
     ready_wave_launches: VecDeque<ReadyWaveLaunch>,
+    rejected_wave_plans: HashSet<PressureWaveInputs>,
     auto_spawn_enabled: bool,
     pending_outcome_command: bool,
     awaiting_round_resolution: bool,
+    debug_spawn_count: usize,
+    formations_enabled: bool,
+    formation_entrances: FormationEntrances,
+    bug_escapes: BugEscapes,
+    projectile_whiffs: ProjectileWhiffs,
+    combo_flashes: ComboFlashes,
+    selected_tower_kind: TowerKind,
+    #[cfg(feature = "dev")]
+    content_watcher: Option<ContentWatcher>,
+    #[cfg(feature = "dev")]
+    staged_content: ContentReload,
+    #[cfg(feature = "dev")]
+    pressure_generator: Option<PressureV2>,
+    #[cfg(feature = "dev")]
+    palette: Option<Palette>,
     #[cfg(test)]
     last_frame_events: Vec<Event>,
 }
@@ -529,6 +1112,55 @@ struct BugMotion {
     elapsed: Duration,
 }
 
+/// Origins visited while the player drags a placement across the grid.
+#[derive(Clone, Debug)]
+struct DragPlacement {
+    anchor: CellCoord,
+    visited: BTreeSet<CellCoord>,
+}
+
+impl DragPlacement {
+    fn new(anchor: CellCoord) -> Self {
+        let mut visited = BTreeSet::new();
+        let _ = visited.insert(anchor);
+        Self { anchor, visited }
+    }
+}
+
+/// Tracks when a tower's reload last completed so the ready flash can fade out.
+#[derive(Clone, Copy, Debug, Default)]
+struct ReadyFlash {
+    previous_ready_in: Option<Duration>,
+    elapsed: Option<Duration>,
+}
+
+impl ReadyFlash {
+    fn observe(&mut self, ready_in: Duration, dt: Duration) {
+        if let Some(elapsed) = self.elapsed.as_mut() {
+            *elapsed = elapsed.saturating_add(dt);
+        }
+
+        if let Some(previous) = self.previous_ready_in {
+            let reloading = !previous.is_zero();
+            let became_ready = ready_in.is_zero();
+            let fired_on_ready = ready_in > previous;
+            if reloading && (became_ready || fired_on_ready) {
+                self.elapsed = Some(Duration::ZERO);
+            }
+        }
+        self.previous_ready_in = Some(ready_in);
+    }
+
+    fn intensity(&self) -> f32 {
+        match self.elapsed {
+            Some(elapsed) if elapsed < TOWER_READY_FLASH => {
+                1.0 - elapsed.as_secs_f32() / TOWER_READY_FLASH.as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+}
+
 impl BugMotion {
     fn new(from: CellCoord, to: CellCoord, step_duration: Duration) -> Self {
         Self {
@@ -550,12 +1182,13 @@ impl BugMotion {
         }
     }
 
-    fn progress(&self) -> f32 {
+    /// Share of the step completed `lead` after the last update.
+    fn progress(&self, lead: Duration) -> f32 {
         if self.step_duration.is_zero() {
             return 1.0;
         }
 
-        let numerator = self.elapsed.as_secs_f32();
+        let numerator = self.elapsed.saturating_add(lead).as_secs_f32();
         let denominator = self.step_duration.as_secs_f32();
         if denominator <= f32::EPSILON {
             1.0
@@ -572,6 +1205,12 @@ struct ScheduledSpawn {
     color: BugColor,
     health: Health,
     step_ms: NonZeroU32,
+    pathing: PathingPersonality,
+    bounty: Gold,
+    rank: BugRank,
+    dodge: DodgeChance,
+    archetype: BugArchetype,
+    formation: Option<ScheduledFormation>,
 }
 
 impl ScheduledSpawn {
@@ -589,6 +1228,12 @@ impl ScheduledSpawn {
             color,
             health,
             step_ms,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
+            formation: None,
         }
     }
 }
@@ -597,7 +1242,7 @@ impl ScheduledSpawn {
 struct WaveState {
     scheduled: Vec<ScheduledSpawn>,
     next_spawn: usize,
-    elapsed: Duration,
+    clock: SimClock,
 }
 
 impl WaveState {
@@ -606,18 +1251,19 @@ impl WaveState {
         plan: &PressureWavePlan,
         species: &HashMap<SpeciesId, SpeciesPrototype>,
         spawners: &[CellCoord],
+        patches: &[SpawnPatchDescriptor],
         seed: u64,
     ) -> Self {
         if plan.spawns().is_empty() || spawners.is_empty() {
             return Self {
                 scheduled: Vec::new(),
                 next_spawn: 0,
-                elapsed: Duration::ZERO,
+                clock: SimClock::new(),
             };
         }
 
         let mut rng = ChaCha8Rng::seed_from_u64(seed);
-        let mut ordered_spawners: Vec<CellCoord> = spawners.iter().copied().collect();
+        let mut ordered_spawners: Vec<CellCoord> = spawners.to_vec();
         ordered_spawners.sort_by_key(|coord| (coord.row(), coord.column()));
 
         let spawner_count = ordered_spawners.len();
@@ -625,19 +1271,26 @@ impl WaveState {
             return Self {
                 scheduled: Vec::new(),
                 next_spawn: 0,
-                elapsed: Duration::ZERO,
+                clock: SimClock::new(),
             };
         }
 
         let mut species_ids = BTreeSet::new();
+        let mut lanes = HashMap::new();
         for spawn in plan.spawns() {
-            let _ = species_ids.insert(SpeciesId::new(spawn.species_id()));
+            let species_id = SpeciesId::new(spawn.species_id());
+            let _ = species_ids.insert(species_id);
+            if let Some(lane) = spawn.lane() {
+                let _ = lanes.entry(species_id).or_insert(lane);
+            }
         }
+        let mut lane_patches: Vec<&SpawnPatchDescriptor> = patches.iter().collect();
+        lane_patches.sort_by_key(|patch| patch.id());
         if species_ids.is_empty() {
             return Self {
                 scheduled: Vec::new(),
                 next_spawn: 0,
-                elapsed: Duration::ZERO,
+                clock: SimClock::new(),
             };
         }
 
@@ -664,13 +1317,29 @@ impl WaveState {
                 rng.gen_range(min_band..=max_band)
             };
 
-            let mut cells = Vec::with_capacity(band_len);
-            if spawner_count == band_len {
-                cells.extend(ordered_spawners.iter().copied());
-            } else {
-                for offset in 0..band_len {
-                    let index = (next_band_start + offset) % spawner_count;
-                    cells.push(ordered_spawners[index]);
+            // Laned species enter through their patch, wrapping round-robin when the level
+            // has fewer patches than lanes; the band is still drawn so the RNG stream, and
+            // with it every unlaned species, stays the same.
+            let mut cells: Vec<CellCoord> = lanes
+                .get(species_id)
+                .filter(|_| !lane_patches.is_empty())
+                .map(|lane| {
+                    let patch = lane_patches[*lane as usize % lane_patches.len()];
+                    ordered_spawners
+                        .iter()
+                        .copied()
+                        .filter(|cell| patch.extent().contains(*cell))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if cells.is_empty() {
+                if spawner_count == band_len {
+                    cells.extend(ordered_spawners.iter().copied());
+                } else {
+                    for offset in 0..band_len {
+                        let index = (next_band_start + offset) % spawner_count;
+                        cells.push(ordered_spawners[index]);
+                    }
                 }
             }
 
@@ -685,6 +1354,7 @@ impl WaveState {
         for cell in &ordered_spawners {
             let _ = availability.insert(*cell, Duration::ZERO);
         }
+        let mut throttles: Vec<PatchThrottle> = patches.iter().map(PatchThrottle::new).collect();
 
         let mut scheduled = Vec::with_capacity(plan.spawns().len());
         for (index, spawn) in plan.spawns().iter().enumerate() {
@@ -729,12 +1399,18 @@ impl WaveState {
                     )
                 });
 
-            let step_ms = resolve_step_ms(prototype.step_ms(), spawn.speed_mult());
+            let step_ms = prototype.step_ms_at(spawn.speed_mult());
             let color = prototype.color();
             let health = Health::new(spawn.hp());
             let planned_at = Duration::from_millis(u64::from(spawn.time_ms()));
             let ready_at = availability.get(&cell).copied().unwrap_or_default();
-            let scheduled_at = planned_at.max(ready_at);
+            let mut scheduled_at = planned_at.max(ready_at);
+            if let Some(throttle) = throttles
+                .iter_mut()
+                .find(|throttle| throttle.extent.contains(cell))
+            {
+                scheduled_at = throttle.admit(scheduled_at);
+            }
             let cooldown = Duration::from_millis(u64::from(step_ms.get()));
             let _ = availability.insert(cell, scheduled_at.saturating_add(cooldown));
 
@@ -746,6 +1422,12 @@ impl WaveState {
                     color,
                     health,
                     step_ms,
+                    pathing: prototype.pathing(),
+                    bounty: prototype.bounty(),
+                    rank: prototype.rank(),
+                    dodge: prototype.dodge(),
+                    archetype: spawn.archetype(),
+                    formation: None,
                 },
             ));
         }
@@ -769,7 +1451,7 @@ impl WaveState {
         Self {
             scheduled,
             next_spawn: 0,
-            elapsed: Duration::ZERO,
+            clock: SimClock::new(),
         }
     }
 
@@ -777,7 +1459,7 @@ impl WaveState {
         Self {
             scheduled,
             next_spawn: 0,
-            elapsed: Duration::ZERO,
+            clock: SimClock::new(),
         }
     }
 
@@ -786,26 +1468,67 @@ impl WaveState {
     }
 
     fn advance(&mut self, dt: Duration, out: &mut Vec<Command>) {
-        self.elapsed = self.elapsed.saturating_add(dt);
+        self.clock.advance(dt);
+        self.release_due_spawns(out);
+    }
+
+    /// Jumps the wave clock to `at_ms` and releases every spawn due by then, in order.
+    #[cfg(test)]
+    fn advance_to_ms(&mut self, at_ms: u64, out: &mut Vec<Command>) {
+        let _ = self.clock.advance_to_ms(at_ms);
+        self.release_due_spawns(out);
+    }
+
+    fn release_due_spawns(&mut self, out: &mut Vec<Command>) {
         while let Some(spawn) = self.scheduled.get(self.next_spawn) {
-            if self.elapsed < spawn.at {
+            if self.clock.elapsed() < spawn.at {
                 break;
             }
-            out.push(Command::SpawnBug {
-                spawner: spawn.spawner,
-                color: spawn.color,
-                health: spawn.health,
-                step_ms: spawn.step_ms.get(),
+            out.push(match spawn.formation {
+                Some(formation) => Command::SpawnFormation {
+                    anchor: spawn.spawner,
+                    shape: formation.shape,
+                    size: formation.size,
+                    color: spawn.color,
+                    health: spawn.health,
+                    step_ms: spawn.step_ms.get(),
+                    pathing: spawn.pathing,
+                    bounty: spawn.bounty,
+                    dodge: spawn.dodge,
+                    archetype: spawn.archetype,
+                },
+                None => Command::SpawnBug {
+                    spawner: spawn.spawner,
+                    color: spawn.color,
+                    health: spawn.health,
+                    step_ms: spawn.step_ms.get(),
+                    pathing: spawn.pathing,
+                    bounty: spawn.bounty,
+                    rank: spawn.rank,
+                    dodge: spawn.dodge,
+                    archetype: spawn.archetype,
+                },
             });
             self.next_spawn += 1;
         }
     }
 
-    fn pending_spawn_effects(&self) -> Vec<(CellCoord, BugColor)> {
+    fn pending_spawn_effects(&self) -> Vec<SpawnEffectSource> {
+        let baseline = self
+            .scheduled
+            .iter()
+            .map(|spawn| spawn.health)
+            .min_by_key(|health| health.get())
+            .unwrap_or(Health::ZERO);
         let mut effects = BTreeMap::new();
         for spawn in self.scheduled.iter().skip(self.next_spawn) {
             let key = (spawn.spawner.row(), spawn.spawner.column());
-            let _ = effects.entry(key).or_insert((spawn.spawner, spawn.color));
+            let _ = effects.entry(key).or_insert(SpawnEffectSource {
+                cell: spawn.spawner,
+                color: spawn.color,
+                tier: health_tier(spawn.health, baseline),
+                time_until: spawn.at.saturating_sub(self.clock.elapsed()),
+            });
         }
         effects.into_values().collect()
     }
@@ -815,6 +1538,87 @@ impl WaveState {
     }
 }
 
+/// Release state of a spawn patch, limiting how many bugs leave it at once and how often.
+///
+/// A release is a single instant; bugs arriving while the current release is full, or
+/// before the patch's cooldown has passed, are deferred to the next release in arrival
+/// order.
+#[derive(Clone, Debug)]
+struct PatchThrottle {
+    extent: CellRect,
+    max_simultaneous: u32,
+    min_interval: Duration,
+    released_at: Duration,
+    released: u32,
+}
+
+impl PatchThrottle {
+    fn new(descriptor: &SpawnPatchDescriptor) -> Self {
+        Self {
+            extent: descriptor.extent(),
+            max_simultaneous: descriptor.max_simultaneous().get(),
+            min_interval: descriptor.min_interval(),
+            released_at: Duration::ZERO,
+            released: 0,
+        }
+    }
+
+    /// Returns when a bug ready at `at` leaves the patch and records its release.
+    fn admit(&mut self, at: Duration) -> Duration {
+        let at = at.max(self.released_at);
+        if self.released > 0 && at == self.released_at && self.released < self.max_simultaneous {
+            self.released += 1;
+            return at;
+        }
+        let release = if self.released == 0 {
+            at
+        } else {
+            at.max(self.released_at.saturating_add(self.min_interval))
+        };
+        self.released_at = release;
+        self.released = 1;
+        release
+    }
+}
+
+/// Next pending spawn at a spawner, used to telegraph it in the scene.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SpawnEffectSource {
+    cell: CellCoord,
+    color: BugColor,
+    tier: u32,
+    time_until: Duration,
+}
+
+impl SpawnEffectSource {
+    fn into_effect(self) -> SpawnEffect {
+        let duration = SPAWN_EFFECT_BASE_DURATION + SPAWN_EFFECT_TIER_DURATION_STEP * self.tier;
+        let radius = SPAWN_EFFECT_BASE_RADIUS_CELLS
+            * (1.0 + SPAWN_EFFECT_TIER_RADIUS_STEP * self.tier as f32);
+        let progress = 1.0 - self.time_until.as_secs_f32() / duration.as_secs_f32();
+        SpawnEffect::new(
+            self.cell.column(),
+            self.cell.row(),
+            Color::from_rgb_u8(self.color.red(), self.color.green(), self.color.blue()),
+            radius,
+            duration,
+            progress,
+        )
+    }
+}
+
+/// Number of health doublings above the weakest spawn in the wave, capped at
+/// `SPAWN_EFFECT_MAX_TIER`.
+fn health_tier(health: Health, baseline: Health) -> u32 {
+    let mut threshold = baseline.get().max(1).saturating_mul(2);
+    let mut tier = 0;
+    while tier < SPAWN_EFFECT_MAX_TIER && health.get() >= threshold {
+        tier += 1;
+        threshold = threshold.saturating_mul(2);
+    }
+    tier
+}
+
 #[derive(Clone, Debug)]
 struct PendingWaveLaunch {
     inputs: PressureWaveInputs,
@@ -839,17 +1643,6 @@ struct ReplayAttackPlan {
     scheduled_spawns: Vec<ScheduledSpawn>,
 }
 
-fn resolve_step_ms(base: NonZeroU32, speed_mult: f32) -> NonZeroU32 {
-    let baseline = base.get().max(1);
-    let multiplier = if speed_mult.is_finite() && speed_mult > f32::EPSILON {
-        speed_mult
-    } else {
-        1.0
-    };
-    let adjusted = (baseline as f32 / multiplier).round() as u32;
-    NonZeroU32::new(adjusted.max(1)).expect("non-zero step duration")
-}
-
 fn spawn_band_seed(inputs: &PressureWaveInputs) -> u64 {
     fn fnv1a(mut state: u64, bytes: &[u8]) -> u64 {
         for byte in bytes {
@@ -866,487 +1659,726 @@ fn spawn_band_seed(inputs: &PressureWaveInputs) -> u64 {
     fnv1a(hash, &inputs.difficulty().get().to_le_bytes())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use maze_defence_core::{DifficultyLevel, LevelId, PressureSpawnRecord, WaveDifficulty};
-    use std::{collections::HashSet, num::NonZeroU32, time::Duration};
-
-    fn species_proto(color: BugColor, health: u32, step_ms: u32) -> SpeciesPrototype {
-        SpeciesPrototype::new(
-            color,
-            Health::new(health),
-            NonZeroU32::new(step_ms.max(1)).expect("non-zero step"),
-        )
-    }
-
-    fn build_plan(
-        spawn_count: usize,
-        species: u32,
-        spacing: u32,
-        prototype: SpeciesPrototype,
-    ) -> PressureWavePlan {
-        let mut spawns = Vec::with_capacity(spawn_count);
-        for index in 0..spawn_count {
-            spawns.push(PressureSpawnRecord::new(
-                spacing.saturating_mul(u32::try_from(index).unwrap_or(0)),
-                10,
-                1.0,
-                species,
-            ));
-        }
-        let mut prototypes = Vec::new();
-        let index = usize::try_from(species).unwrap_or(0);
-        if prototypes.len() <= index {
-            prototypes.resize(
-                index + 1,
-                SpeciesPrototype::new(
-                    BugColor::from_rgb(0xff, 0xff, 0xff),
-                    Health::new(1),
-                    NonZeroU32::new(1).expect("non-zero default step"),
-                ),
+#[cfg_attr(test, allow(dead_code))]
+impl Simulation {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        columns: u32,
+        rows: u32,
+        tile_length: f32,
+        cells_per_tile: u32,
+        bug_step: Duration,
+        bug_spawn_interval: Duration,
+        visual_style: VisualStyle,
+        initial_difficulty: Option<DifficultyLevel>,
+        initial_gold: Option<Gold>,
+    ) -> Self {
+        let mut world = World::new();
+        let mut pending_events = Vec::new();
+        world::apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(columns),
+                rows: TileCoord::new(rows),
+                tile_length,
+                cells_per_tile,
+            },
+            &mut pending_events,
+        );
+        world::apply(
+            &mut world,
+            Command::ConfigureBugStep {
+                step_duration: bug_step,
+            },
+            &mut pending_events,
+        );
+
+        if let Some(level) = initial_difficulty {
+            world::apply(
+                &mut world,
+                Command::SetDifficultyLevel { level },
+                &mut pending_events,
             );
         }
-        prototypes[index] = prototype;
-        PressureWavePlan::new(spawns, prototypes)
-    }
 
-    fn band_spawners(count: u32) -> Vec<CellCoord> {
-        (0..count).map(|column| CellCoord::new(column, 0)).collect()
+        if let Some(amount) = initial_gold {
+            world::apply(&mut world, Command::SetGold { amount }, &mut pending_events);
+        }
+
+        let initial_play_mode = query::play_mode(&world);
+        pending_events.push(Event::PlayModeChanged {
+            mode: initial_play_mode,
+        });
+        let gold = query::gold(&world);
+        let difficulty_level = query::difficulty_level(&world);
+        let pending_wave_difficulty = query::pending_wave_difficulty(&world);
+        let species_table = query::species_table(&world);
+        let mut species_prototypes = HashMap::new();
+        for definition in species_table.iter() {
+            let _ = species_prototypes.insert(definition.id(), definition.prototype());
+        }
+        let species_table_version = species_table.version();
+        let mut patch_origins = HashMap::new();
+        for descriptor in query::patch_table(&world).iter() {
+            let _ = patch_origins.insert(descriptor.id(), descriptor.origin());
+        }
+        let mut simulation = Self {
+            world,
+            builder: TowerBuilder::default(),
+            movement: Movement::default(),
+            spawning: Spawning::new(SpawningConfig::new(
+                bug_spawn_interval,
+                bug_step,
+                SPAWN_RNG_SEED,
+            )),
+            tower_targeting: TowerTargeting::new(),
+            tower_combat: TowerCombat::new(),
+            tower_cooldowns: TowerCooldownView::default(),
+            tower_ready_flashes: HashMap::new(),
+            projectiles: Vec::new(),
+            current_targets: Vec::new(),
+            nest_targets: Vec::new(),
+            pending_events,
+            scratch_commands: Vec::new(),
+            queued_commands: Vec::new(),
+            pending_input: FrameInput::default(),
+            motion_lead: Duration::ZERO,
+            builder_preview: None,
+            drag_placement: None,
+            hovered_bug: None,
+            tower_selection: TowerSelection::default(),
+            path_preview: None,
+            spectator: None,
+            focus_pulses: None,
+            speed: 1,
+            starting_difficulty: difficulty_level,
+            tally: RunTally::default(),
+            run_summary: None,
+            prestige_earned: 0,
+            autoplayer: None,
+            console_output: true,
+            tower_feedback: None,
+            analytics_report: None,
+            wave_report: None,
+            between_wave_event: None,
+            latest_wave: None,
+            analytics: Analytics::new(),
+            wave_outcome_log: None,
+            checkpoint_directory: None,
+            spectator_feed: None,
+            applied_commands: Vec::new(),
+            gold,
+            gold_feedback: GoldFeedback::new(gold),
+            notifications: Notifications::default(),
+            difficulty_level,
+            pending_wave_difficulty,
+            pending_wave_launch: None,
+            last_placement_rejection: None,
+            last_removal_rejection: None,
+            bug_step_duration: bug_step,
+            bug_motions: HashMap::new(),
+            bug_headings: HashMap::new(),
+            cells_per_tile,
+            species_table_version,
+            species_prototypes,
+            patch_origins,
+            visual_style,
+            last_advance_profile: AdvanceProfile::default(),
+            last_announced_play_mode: initial_play_mode,
+            active_wave: None,
+            active_wave_plan: None,
+            last_attack_plan: None,
+            ready_wave_launches: VecDeque::new(),
+            rejected_wave_plans: HashSet::new(),
+            auto_spawn_enabled: false,
+            pending_outcome_command: false,
+            awaiting_round_resolution: false,
+            debug_spawn_count: 0,
+            formations_enabled: false,
+            formation_entrances: FormationEntrances::default(),
+            bug_escapes: BugEscapes::default(),
+            projectile_whiffs: ProjectileWhiffs::default(),
+            combo_flashes: ComboFlashes::default(),
+            selected_tower_kind: TowerKind::Basic,
+            #[cfg(feature = "dev")]
+            content_watcher: None,
+            #[cfg(feature = "dev")]
+            staged_content: ContentReload::default(),
+            #[cfg(feature = "dev")]
+            pressure_generator: None,
+            #[cfg(feature = "dev")]
+            palette: None,
+            #[cfg(test)]
+            last_frame_events: Vec::new(),
+        };
+        let mut analytics_events = Vec::new();
+        simulation.apply_command(Command::RequestAnalyticsRefresh, &mut analytics_events);
+        simulation.pending_events.append(&mut analytics_events);
+        simulation.refresh_species_and_patches();
+        let _ = simulation.process_pending_events(None, TowerBuilderInput::default());
+        simulation.builder_preview = simulation.compute_builder_preview();
+        simulation.last_announced_play_mode = query::play_mode(&simulation.world);
+        simulation
     }
 
-    #[test]
-    fn species_uses_multiple_spawners() {
-        let mut species = HashMap::new();
-        let color = BugColor::from_rgb(0x12, 0x34, 0x56);
-        let prototype = species_proto(color, 5, 500);
-        let plan = build_plan(12, 0, 200, prototype);
-        let _ = species.insert(SpeciesId::new(0), prototype);
-        let spawners = band_spawners(20);
+    fn world(&self) -> &World {
+        &self.world
+    }
 
-        let wave = WaveState::new(&plan, &species, &spawners, 0xfeed_beef);
-        assert_eq!(wave.scheduled.len(), plan.spawns().len());
+    #[cfg(test)]
+    fn active_wave_plan(&self) -> Option<&PressureWavePlan> {
+        self.active_wave_plan.as_ref()
+    }
 
-        let unique: BTreeSet<_> = wave.scheduled.iter().map(|spawn| spawn.spawner).collect();
-        assert!(unique.len() >= MIN_SPAWN_BAND.min(spawners.len()));
+    /// Runs one frame of the runner loop: applies input, runs the fixed updates due and
+    /// repopulates the scene.
+    ///
+    /// A frame without updates still applies its input, and bugs are drawn `tick.alpha` of
+    /// a step ahead of the last update so motion stays smooth on fast displays.
+    fn run_frame(
+        &mut self,
+        tick: FrameTick,
+        input: FrameInput,
+        scene: &mut Scene,
+    ) -> FrameSimulationBreakdown {
+        self.handle_input(input);
+        if tick.steps == 0 {
+            self.advance(Duration::ZERO);
+        }
+        for _ in 0..tick.steps {
+            self.fast_forward(tick.step);
+        }
+        self.motion_lead = tick.lead();
+        let populate_start = Instant::now();
+        self.populate_scene(scene);
+        let scene_population = populate_start.elapsed();
+        let advance_profile = self.last_advance_profile();
+        FrameSimulationBreakdown::new(
+            advance_profile.total,
+            advance_profile.pathfinding,
+            scene_population,
+        )
     }
 
-    #[test]
-    fn respects_per_spawner_cadence() {
-        let mut species = HashMap::new();
-        let prototype = species_proto(BugColor::from_rgb(1, 2, 3), 5, 400);
-        let plan = build_plan(30, 0, 10, prototype);
-        let _ = species.insert(SpeciesId::new(0), prototype);
-        let spawners = band_spawners(5);
+    fn handle_input(&mut self, input: FrameInput) {
+        if let Some(groups) = input.restore_selection_groups.clone() {
+            self.tower_selection.restore_groups(groups);
+        }
 
-        let wave = WaveState::new(&plan, &species, &spawners, 0x1234_5678);
+        if let Some(rules) = input.game_rules {
+            self.queued_commands
+                .push(Command::ConfigureGameRules { rules });
+        }
 
-        let mut per_cell: HashMap<CellCoord, Vec<(Duration, u32)>> = HashMap::new();
-        for spawn in &wave.scheduled {
-            per_cell
-                .entry(spawn.spawner)
-                .or_default()
-                .push((spawn.at, spawn.step_ms.get()));
+        if self.spectator.is_some() {
+            self.pending_input = FrameInput::default();
+            return;
         }
 
-        for entries in per_cell.values_mut() {
-            if entries.len() < 2 {
-                continue;
-            }
-            entries.sort_by_key(|entry| entry.0);
-            for window in entries.windows(2) {
-                let previous = window[0];
-                let next = window[1];
-                let required_gap = Duration::from_millis(u64::from(previous.1));
-                assert!(next.0 >= previous.0 + required_gap);
+        if focus_pulses::interrupts(&input) {
+            if let Some(pulses) = self.focus_pulses.as_mut() {
+                pulses.interrupt();
             }
         }
-    }
 
-    #[test]
-    fn deterministic_band_assignment() {
-        let mut species = HashMap::new();
-        let prototype = species_proto(BugColor::from_rgb(5, 6, 7), 9, 360);
-        let plan = build_plan(16, 0, 120, prototype);
-        let _ = species.insert(SpeciesId::new(0), prototype);
-        let spawners = band_spawners(12);
+        if input.mode_toggle {
+            let current_mode = query::play_mode(&self.world);
+            let next_mode = match current_mode {
+                PlayMode::Attack => PlayMode::Builder,
+                PlayMode::Builder => PlayMode::Attack,
+            };
+            self.queued_commands
+                .push(Command::SetPlayMode { mode: next_mode });
+        }
 
-        let left = WaveState::new(&plan, &species, &spawners, 0x77aa_bbcc);
-        let right = WaveState::new(&plan, &species, &spawners, 0x77aa_bbcc);
+        if let Some(difficulty) = input.start_wave {
+            self.initiate_wave_launch(difficulty);
+        }
 
-        assert_eq!(left.scheduled.len(), right.scheduled.len());
-        for (lhs, rhs) in left.scheduled.iter().zip(right.scheduled.iter()) {
-            assert_eq!(lhs.spawner, rhs.spawner);
-            assert_eq!(lhs.at, rhs.at);
-            assert_eq!(lhs.step_ms, rhs.step_ms);
+        if input.replay_wave {
+            self.replay_last_attack_plan();
         }
-    }
 
-    #[test]
-    fn wave_state_uses_plan_prototypes_for_missing_species() {
-        let prototype_a = species_proto(BugColor::from_rgb(0x10, 0x20, 0x30), 7, 420);
-        let prototype_b = species_proto(BugColor::from_rgb(0x40, 0x50, 0x60), 9, 360);
-        let spawns = vec![
-            PressureSpawnRecord::new(0, 10, 1.0, 0),
-            PressureSpawnRecord::new(100, 12, 1.0, 1),
-        ];
-        let plan = PressureWavePlan::new(spawns, vec![prototype_a, prototype_b]);
-        let mut species = HashMap::new();
-        let _ = species.insert(SpeciesId::new(0), prototype_a);
-        let spawners = band_spawners(4);
+        if let Some(reward) = input.skip_wave_countdown {
+            self.queued_commands
+                .push(Command::SkipWaveCountdown { reward });
+        }
 
-        let wave = WaveState::new(&plan, &species, &spawners, 0xfeed_face);
-        let colors: HashSet<_> = wave.scheduled.iter().map(|spawn| spawn.color).collect();
-        assert!(colors.contains(&prototype_a.color()));
-        assert!(colors.contains(&prototype_b.color()));
-    }
+        if input.dismiss_wave_report {
+            self.wave_report = None;
+        }
 
-    #[test]
-    fn spawn_effect_preview_uses_ready_launch_plan() {
-        let mut simulation = Simulation::new(
-            4,
-            4,
-            48.0,
-            1,
-            Duration::from_millis(400),
-            Duration::from_millis(1_000),
-            VisualStyle::Primitives,
-            None,
-            None,
-        );
-        simulation.ready_wave_launches.clear();
-        simulation.active_wave = None;
-        simulation.active_wave_plan = None;
+        if let Some(seed) = input.set_wave_seed {
+            self.set_wave_seed(seed);
+        }
 
-        let tint = BugColor::from_rgb(0xaa, 0xbb, 0xcc);
-        simulation.species_prototypes.clear();
-        let prototype = species_proto(tint, 12, 360);
-        let _ = simulation
-            .species_prototypes
-            .insert(SpeciesId::new(1), prototype);
+        if let Some(contract) = input.toggle_contract {
+            let accepted = !query::accepted_contracts(&self.world).contains(&contract);
+            self.queued_commands
+                .push(Command::SetWaveContract { contract, accepted });
+        }
 
-        let inputs = PressureWaveInputs::new(
-            0x1234_5678,
-            LevelId::new(2),
-            WaveId::new(3),
-            DifficultyLevel::new(4),
-        );
-        let plan = build_plan(8, 1, 90, prototype);
-        simulation.ready_wave_launches.push_back(ReadyWaveLaunch {
-            inputs,
-            wave: WaveId::new(3),
-            difficulty: WaveDifficulty::Normal,
-            plan,
-            scheduled_spawns: None,
-        });
+        if input.rotate_placement {
+            let rotation = query::placement_rotation(&self.world).rotated();
+            self.queued_commands
+                .push(Command::SetPlacementRotation { rotation });
+        }
 
-        let effects = simulation.spawn_effects();
-        assert!(!effects.is_empty());
-        let expected = Color::from_rgb_u8(tint.red(), tint.green(), tint.blue());
-        let effect = effects
+        if input.cycle_tower_kind {
+            let index = TowerKind::ALL
+                .iter()
+                .position(|kind| *kind == self.selected_tower_kind)
+                .unwrap_or(0);
+            self.selected_tower_kind = TowerKind::ALL[(index + 1) % TowerKind::ALL.len()];
+        }
+
+        if let Some(kind) = input.select_tower_kind {
+            self.selected_tower_kind = kind;
+        }
+
+        if let Some(accept) = input.merchant_choice {
+            self.queued_commands
+                .push(Command::AnswerMerchantOffer { accept });
+        }
+
+        let mut input = input;
+        if let Some(hotkey) = input.group_hotkey {
+            let towers = query::towers(&self.world);
+            if hotkey.assign {
+                self.tower_selection.assign_group(hotkey.slot, &towers);
+            } else {
+                self.tower_selection.recall_group(hotkey.slot, &towers);
+            }
+        }
+
+        if input.selection_modifier || self.tower_selection.is_dragging() {
+            self.update_tower_selection(&input);
+            input.confirm_action = false;
+            input.confirm_held = false;
+        } else if input.confirm_action {
+            self.tower_selection.clear();
+        }
+
+        if input.remove_action
+            && !self.tower_selection.is_empty()
+            && query::play_mode(&self.world) == PlayMode::Builder
+        {
+            for tower in self.tower_selection.selected() {
+                self.queued_commands.push(Command::RemoveTower { tower });
+            }
+            self.tower_selection.clear();
+            input.remove_action = false;
+        }
+
+        if input.cycle_selection_targeting {
+            self.cycle_selection_targeting();
+        }
+
+        if input.upgrade_selection {
+            for tower in self.tower_selection.selected() {
+                self.queued_commands.push(Command::UpgradeTower { tower });
+            }
+        }
+
+        if input.remove_action
+            && query::sandbox_rules(&self.world).debug_spawns
+            && query::play_mode(&self.world) == PlayMode::Attack
+        {
+            self.queue_debug_spawn(&input);
+        }
+
+        if input.confirm_action && query::play_mode(&self.world) == PlayMode::Attack {
+            if let Some(bug) = self.hovered_bug {
+                self.queued_commands
+                    .push(Command::MarkPriorityTarget { bug });
+            }
+        }
+
+        self.pending_input = FrameInput {
+            mode_toggle: false,
+            start_wave: None,
+            replay_wave: false,
+            group_hotkey: None,
+            restore_selection_groups: None,
+            game_rules: None,
+            cycle_selection_targeting: false,
+            upgrade_selection: false,
+            skip_wave_countdown: None,
+            dismiss_wave_report: false,
+            merchant_choice: None,
+            toggle_contract: None,
+            rotate_placement: false,
+            cycle_tower_kind: false,
+            select_tower_kind: None,
+            set_wave_seed: None,
+            ..input
+        };
+    }
+
+    /// Moves every selected tower to the policy after the first selected tower's one.
+    fn cycle_selection_targeting(&mut self) {
+        let towers = query::towers(&self.world);
+        let selected: Vec<TowerId> = self.tower_selection.selected().collect();
+        let Some(current) = selected
             .first()
-            .expect("at least one spawn effect should be generated");
-        assert!((effect.color.red - expected.red).abs() <= f32::EPSILON);
-        assert!((effect.color.green - expected.green).abs() <= f32::EPSILON);
-        assert!((effect.color.blue - expected.blue).abs() <= f32::EPSILON);
+            .and_then(|id| towers.iter().find(|tower| tower.id == *id))
+            .map(|tower| tower.targeting)
+        else {
+            return;
+        };
+        let policy = current.next();
+        for tower in selected {
+            self.queued_commands
+                .push(Command::SetTowerTargeting { tower, policy });
+        }
     }
 
-    #[test]
-    fn layout_import_bypasses_gold_costs() {
-        let snapshot = TowerLayoutSnapshot::decode("maze:v2:10x10:BAAAyEJDABMlAA8jAAklAA0ZABEdABUfABkjAB8lAB8hABsdABcZABMXAA8TAAkVAAkRAA0NABENABURABkTAB0XAB8RACMVACMZAB8dABsNABcLABMHAA8HAAsHAAcLAAUBAAkBAA0BABEBABUBABkBAB0BACEBACUBACUFACUJACUNACURACUdACUhACUlACEJACEFABsJABcHAB8NAB0FAAcHAA0dAAEBAAEFAAEJAAENAAEVAAkdAAEZAAEdAAchAAcZAAMRAAUlAAEj")
-            .expect("snapshot should decode");
+    fn update_tower_selection(&mut self, input: &FrameInput) {
+        let cell = input
+            .cursor_world_space
+            .map(|world| self.world_position_to_cell(world));
 
-        let mut simulation = Simulation::new(
-            snapshot.columns,
-            snapshot.rows,
-            snapshot.tile_length,
-            snapshot.cells_per_tile,
-            Duration::from_millis(DEFAULT_BUG_STEP_MS),
-            Duration::from_millis(DEFAULT_BUG_SPAWN_INTERVAL_MS),
-            VisualStyle::Primitives,
-            None,
-            None,
-        );
+        if input.confirm_action {
+            if let Some(cell) = cell {
+                self.tower_selection.begin_drag(cell);
+            }
+        } else if input.confirm_held {
+            if let Some(cell) = cell {
+                self.tower_selection.extend_drag(cell);
+            }
+        } else {
+            self.tower_selection
+                .finish_drag(&query::towers(&self.world));
+        }
+    }
 
-        let initial_gold = query::gold(simulation.world());
-        let required_gold: u32 = snapshot
-            .towers
-            .iter()
-            .map(|tower| tower.kind.build_cost().get())
-            .sum();
-        assert!(required_gold > initial_gold.get());
+    fn enable_formations(&mut self) {
+        self.formations_enabled = true;
+    }
 
-        simulation
-            .apply_layout_snapshot(&snapshot)
-            .expect("layout restoration should succeed");
+    /// Reloads tuning, tower and palette files from `directory` whenever they change.
+    #[cfg(feature = "dev")]
+    fn watch_content(&mut self, directory: &std::path::Path) {
+        self.content_watcher = Some(ContentWatcher::new(directory));
+    }
 
-        let restored = query::towers(simulation.world());
-        assert_eq!(restored.iter().count(), snapshot.towers.len());
-        assert_eq!(query::gold(simulation.world()), initial_gold);
+    /// Stages edited content files and applies them once no wave is launching or running.
+    #[cfg(feature = "dev")]
+    fn reload_content(&mut self, dt: Duration) {
+        if let Some(watcher) = self.content_watcher.as_mut() {
+            self.staged_content.merge(watcher.poll(dt));
+        }
+        let at_wave_boundary = self.active_wave.is_none()
+            && self.pending_wave_launch.is_none()
+            && self.ready_wave_launches.is_empty()
+            && !self.awaiting_round_resolution;
+        if !at_wave_boundary {
+            return;
+        }
+
+        let staged = std::mem::take(&mut self.staged_content);
+        if let Some(tuning) = staged.tuning {
+            self.pressure_generator = Some(PressureV2::new(tuning));
+            self.rejected_wave_plans.clear();
+            // Replace plans prefetched with the previous tuning.
+            let context = query::wave_seed_context(&self.world);
+            let level_id = query::level_id(&self.world);
+            for difficulty in WaveDifficulty::ALL {
+                self.request_wave_plan(context.inputs(level_id, difficulty));
+            }
+        }
+        if let Some(content) = staged.towers {
+            self.queued_commands
+                .push(Command::ConfigureTowerContent { content });
+        }
+        if staged.palette.is_some() {
+            self.palette = staged.palette;
+        }
     }
 
-    #[test]
-    fn initial_gold_applies_immediately() {
-        let simulation = Simulation::new(
-            4,
-            4,
-            48.0,
-            1,
-            Duration::from_millis(400),
-            Duration::from_millis(1_000),
-            VisualStyle::Primitives,
-            None,
-            Some(Gold::new(450)),
-        );
+    fn enable_tower_heat(&mut self) {
+        self.queued_commands
+            .push(Command::ConfigureTowerHeat { enabled: true });
+    }
 
-        assert_eq!(simulation.gold, Gold::new(450));
-        let emitted = simulation.last_frame_events.iter().any(
-            |event| matches!(event, Event::GoldChanged { amount } if *amount == Gold::new(450)),
-        );
-        assert!(emitted, "gold change should emit an event");
+    fn enable_line_of_sight(&mut self) {
+        self.queued_commands
+            .push(Command::ConfigureLineOfSight { enabled: true });
     }
 
-    #[test]
-    fn initial_difficulty_level_applies_immediately() {
-        let simulation = Simulation::new(
-            4,
-            4,
-            48.0,
-            1,
-            Duration::from_millis(400),
-            Duration::from_millis(1_000),
-            VisualStyle::Primitives,
-            Some(DifficultyLevel::new(7)),
-            None,
-        );
+    fn configure_exit_behavior(&mut self, behavior: ExitBehavior) {
+        self.queued_commands
+            .push(Command::ConfigureExitBehavior { behavior });
+    }
 
-        assert_eq!(simulation.difficulty_level, DifficultyLevel::new(7));
-        let emitted = simulation
-            .last_frame_events
-            .iter()
-            .any(|event| matches!(event, Event::DifficultyLevelChanged { level } if *level == DifficultyLevel::new(7)));
-        assert!(emitted, "difficulty change should emit an event");
+    fn log_wave_outcomes(&mut self, log: WaveOutcomeLog) {
+        self.wave_outcome_log = Some(log);
     }
 
-    #[test]
-    fn replay_last_attack_plan_enqueues_cached_launch() {
-        let mut simulation = Simulation::new(
-            4,
-            4,
-            48.0,
-            1,
-            Duration::from_millis(400),
-            Duration::from_millis(1_000),
-            VisualStyle::Primitives,
-            None,
-            None,
-        );
+    fn save_checkpoints_to(&mut self, directory: PathBuf) {
+        self.checkpoint_directory = Some(directory);
+    }
 
-        let inputs = PressureWaveInputs::new(
-            0xfeed_beef,
-            LevelId::new(1),
-            WaveId::new(2),
-            DifficultyLevel::new(3),
-        );
-        let color = BugColor::from_rgb(0xaa, 0xbb, 0xcc);
-        let prototype = species_proto(color, 5, 500);
-        let plan = build_plan(3, 0, 200, prototype);
-        let scheduled = vec![ScheduledSpawn::new(
-            Duration::from_millis(250),
-            CellCoord::new(0, 0),
-            color,
-            Health::new(5),
-            NonZeroU32::new(400).expect("non-zero step"),
-        )];
+    fn stream_to_spectators(&mut self, feed: SpectatorFeedLog) {
+        self.spectator_feed = Some(feed);
+    }
 
-        let mut emitted = Vec::new();
-        simulation.apply_command(Command::SetPlayMode { mode: PlayMode::Attack }, &mut emitted);
-        simulation.pending_events.extend(emitted);
+    fn enable_accuracy(&mut self, model: AccuracyModel) {
+        self.queued_commands
+            .push(Command::ConfigureAccuracy { model: Some(model) });
+    }
 
-        simulation.last_attack_plan = Some(ReplayAttackPlan {
-            inputs: inputs.clone(),
-            difficulty: WaveDifficulty::Normal,
-            plan: plan.clone(),
-            scheduled_spawns: scheduled.clone(),
+    fn enable_sandbox(&mut self) {
+        self.queued_commands.push(Command::ConfigureSandbox {
+            rules: SandboxRules::preset(),
         });
+    }
 
-        assert!(simulation.can_replay_last_attack_plan());
+    /// Covers every cell of the provided tiles with a bridge deck.
+    fn configure_bridges(&mut self, tiles: &[TileArg]) {
+        let cells = self.tile_cells(tiles);
+        self.queued_commands
+            .push(Command::ConfigureBridges { cells });
+    }
 
-        simulation.replay_last_attack_plan();
+    /// Turns every cell of the provided tiles into void.
+    fn configure_void_tiles(&mut self, tiles: &[TileArg]) {
+        let cells = self.tile_cells(tiles);
+        self.queued_commands
+            .push(Command::ConfigureVoidCells { cells });
+    }
 
-        let launch = simulation
-            .take_ready_wave_launch()
-            .expect("replay should enqueue launch");
-        assert_eq!(launch.inputs, inputs);
-        assert_eq!(launch.plan, plan);
-        let scheduled_from_launch = launch
-            .scheduled_spawns
-            .expect("replay launch should include scheduled spawns");
-        assert_eq!(scheduled_from_launch, scheduled);
+    fn enable_nests(&mut self, health: Health) {
+        self.queued_commands.push(Command::ConfigureNests {
+            health: Some(health),
+        });
+    }
 
-        let queued_cache = simulation
-            .queued_commands()
+    /// Every cell of the provided tiles.
+    fn tile_cells(&self, tiles: &[TileArg]) -> Vec<CellCoord> {
+        let cells_per_tile = self.cells_per_tile.max(1);
+        tiles
             .iter()
-            .any(|command| matches!(command, Command::CachePressureWave { .. }));
-        assert!(queued_cache, "replay should queue cache command");
+            .flat_map(|tile| {
+                let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS
+                    .saturating_add(tile.column.saturating_mul(cells_per_tile));
+                let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS
+                    .saturating_add(tile.row.saturating_mul(cells_per_tile));
+                (0..cells_per_tile).flat_map(move |row_offset| {
+                    (0..cells_per_tile).map(move |column_offset| {
+                        CellCoord::new(
+                            column.saturating_add(column_offset),
+                            row.saturating_add(row_offset),
+                        )
+                    })
+                })
+            })
+            .collect()
     }
-}
 
-#[cfg_attr(test, allow(dead_code))]
-impl Simulation {
-    fn new(
-        columns: u32,
-        rows: u32,
-        tile_length: f32,
-        cells_per_tile: u32,
-        bug_step: Duration,
-        bug_spawn_interval: Duration,
-        visual_style: VisualStyle,
-        initial_difficulty: Option<DifficultyLevel>,
-        initial_gold: Option<Gold>,
-    ) -> Self {
-        let mut world = World::new();
-        let mut pending_events = Vec::new();
-        world::apply(
-            &mut world,
-            Command::ConfigureTileGrid {
-                columns: TileCoord::new(columns),
-                rows: TileCoord::new(rows),
-                tile_length,
-                cells_per_tile,
-            },
-            &mut pending_events,
-        );
-        world::apply(
-            &mut world,
-            Command::ConfigureBugStep {
-                step_duration: bug_step,
-            },
-            &mut pending_events,
-        );
-
-        if let Some(level) = initial_difficulty {
-            world::apply(
-                &mut world,
-                Command::SetDifficultyLevel { level },
-                &mut pending_events,
-            );
-        }
-
-        if let Some(amount) = initial_gold {
-            world::apply(&mut world, Command::SetGold { amount }, &mut pending_events);
+    /// Spawns the next species of the table at the cursor, cycling through the species
+    /// with every click.
+    fn queue_debug_spawn(&mut self, input: &FrameInput) {
+        let Some(position) = input.cursor_world_space else {
+            return;
+        };
+        let mut species: Vec<SpeciesId> = self.species_prototypes.keys().copied().collect();
+        if species.is_empty() {
+            return;
         }
+        species.sort_unstable();
 
-        let initial_play_mode = query::play_mode(&world);
-        pending_events.push(Event::PlayModeChanged {
-            mode: initial_play_mode,
+        let species = species[self.debug_spawn_count % species.len()];
+        self.debug_spawn_count = self.debug_spawn_count.wrapping_add(1);
+        self.queued_commands.push(Command::DebugSpawnBug {
+            cell: self.world_position_to_cell(position),
+            species,
         });
-        let gold = query::gold(&world);
-        let difficulty_level = query::difficulty_level(&world);
-        let pending_wave_difficulty = query::pending_wave_difficulty(&world);
-        let species_table = query::species_table(&world);
-        let mut species_prototypes = HashMap::new();
-        for definition in species_table.iter() {
-            let _ = species_prototypes.insert(definition.id(), definition.prototype());
+    }
+
+    /// Enables the rules a shared run was played with on top of any given on the command line.
+    fn apply_run_rules(&mut self, rules: RunRules) {
+        if rules.tower_heat {
+            self.enable_tower_heat();
         }
-        let species_table_version = species_table.version();
-        let mut patch_origins = HashMap::new();
-        for descriptor in query::patch_table(&world).iter() {
-            let _ = patch_origins.insert(descriptor.id(), descriptor.origin());
+        if rules.line_of_sight {
+            self.enable_line_of_sight();
         }
-        let mut simulation = Self {
-            world,
-            builder: TowerBuilder::default(),
-            movement: Movement::default(),
-            spawning: Spawning::new(SpawningConfig::new(
-                bug_spawn_interval,
-                bug_step,
-                SPAWN_RNG_SEED,
-            )),
-            tower_targeting: TowerTargeting::new(),
-            tower_combat: TowerCombat::new(),
-            tower_cooldowns: TowerCooldownView::default(),
-            projectiles: Vec::new(),
-            current_targets: Vec::new(),
-            pending_events,
-            scratch_commands: Vec::new(),
-            queued_commands: Vec::new(),
-            pending_input: FrameInput::default(),
-            builder_preview: None,
-            tower_feedback: None,
-            analytics_report: None,
-            analytics: Analytics::new(),
-            applied_commands: Vec::new(),
-            gold,
-            difficulty_level,
-            pending_wave_difficulty,
-            pending_wave_launch: None,
-            last_placement_rejection: None,
-            last_removal_rejection: None,
-            bug_step_duration: bug_step,
-            bug_motions: HashMap::new(),
-            bug_headings: HashMap::new(),
-            cells_per_tile,
-            species_table_version,
-            species_prototypes,
-            patch_origins,
-            visual_style,
-            last_advance_profile: AdvanceProfile::default(),
-            last_announced_play_mode: initial_play_mode,
-            active_wave: None,
-            active_wave_plan: None,
-            last_attack_plan: None,
-            ready_wave_launches: VecDeque::new(),
-            auto_spawn_enabled: false,
-            pending_outcome_command: false,
-            awaiting_round_resolution: false,
-            #[cfg(test)]
-            last_frame_events: Vec::new(),
+        if rules.formations {
+            self.enable_formations();
+        }
+        if rules.sandbox {
+            self.enable_sandbox();
+        }
+        if let Some(health_percent) = rules.loop_around {
+            self.configure_exit_behavior(ExitBehavior::LoopAround { health_percent });
+        }
+        if let Some(health) = rules.nest_health {
+            self.enable_nests(Health::new(health));
+        }
+        if let Some(cells) = rules.accuracy_drift_cells {
+            self.enable_accuracy(AccuracyModel::new(cells));
+        }
+    }
+
+    /// Summarises the run that just ended in defeat and prints its share code.
+    ///
+    /// The run is scored on the difficulty level it ended on. Runs outside sandbox sessions also award one prestige per [`SCORE_PER_PRESTIGE`]
+    /// points of score.
+    fn record_run_summary(&mut self) {
+        let loop_around = match query::exit_behavior(&self.world) {
+            ExitBehavior::LoopAround { health_percent } => Some(health_percent),
+            ExitBehavior::EndRound => None,
         };
-        let mut analytics_events = Vec::new();
-        simulation.apply_command(Command::RequestAnalyticsRefresh, &mut analytics_events);
-        simulation.pending_events.append(&mut analytics_events);
-        simulation.refresh_species_and_patches();
-        let _ = simulation.process_pending_events(None, TowerBuilderInput::default());
-        simulation.builder_preview = simulation.compute_builder_preview();
-        simulation.last_announced_play_mode = query::play_mode(&simulation.world);
-        simulation
+        let seed_context = query::wave_seed_context(&self.world);
+        let elapsed = Duration::from_secs(query::elapsed(&self.world).as_secs());
+        let summary = RunSummary {
+            seed: seed_context.global_seed(),
+            difficulty: self.starting_difficulty.get(),
+            rules: RunRules {
+                tower_heat: query::tower_heat_enabled(&self.world),
+                line_of_sight: query::line_of_sight_enabled(&self.world),
+                formations: self.formations_enabled,
+                sandbox: query::is_sandbox_session(&self.world),
+                loop_around,
+                nest_health: query::nest_health(&self.world).map(|health| health.get()),
+                accuracy_drift_cells: query::accuracy_model(&self.world)
+                    .map(|model| model.max_drift_cells()),
+            },
+            score: self
+                .tally
+                .score(query::difficulty_level(&self.world), elapsed),
+            wave: seed_context.wave().get(),
+            elapsed,
+            layout: self.capture_layout_snapshot(),
+        };
+        if self.console_output && self.autoplayer.is_none() && !cfg!(test) {
+            println!(
+                "Run over: wave {} after {} with a score of {}",
+                summary.wave,
+                NumberFormat::from_env().duration(summary.elapsed),
+                summary.score
+            );
+            println!("{}", summary.encode());
+        }
+        if !summary.rules.sandbox {
+            self.prestige_earned = self.prestige_earned.saturating_add(
+                u32::try_from(summary.score.get() / SCORE_PER_PRESTIGE).unwrap_or(u32::MAX),
+            );
+        }
+        self.run_summary = Some(summary);
     }
 
-    fn world(&self) -> &World {
-        &self.world
+    fn set_speed(&mut self, speed: u32) {
+        self.speed = speed.max(1);
     }
 
-    #[cfg(test)]
-    fn active_wave_plan(&self) -> Option<&PressureWavePlan> {
-        self.active_wave_plan.as_ref()
+    fn enable_focus_pulses(&mut self) {
+        self.focus_pulses = Some(FocusPulses::default());
     }
 
-    fn handle_input(&mut self, input: FrameInput) {
-        if input.mode_toggle {
-            let current_mode = query::play_mode(&self.world);
-            let next_mode = match current_mode {
-                PlayMode::Attack => PlayMode::Builder,
-                PlayMode::Builder => PlayMode::Attack,
-            };
-            self.queued_commands
-                .push(Command::SetPlayMode { mode: next_mode });
+    fn enable_spectate(&mut self) {
+        self.spectator = Some(Spectator::default());
+        self.tower_selection.clear();
+        self.queued_commands.push(Command::SetPlayMode {
+            mode: PlayMode::Attack,
+        });
+    }
+
+    /// Hands the builder controls to a bot that fills a serpentine maze between waves and
+    /// launches the next wave once it can afford nothing more.
+    fn enable_autoplay(&mut self) {
+        let grid = query::tile_grid(&self.world);
+        let cells_per_tile = self.cells_per_tile.max(1);
+        let area = CellRect::from_origin_and_size(
+            CellCoord::new(
+                TileGridPresentation::SIDE_BORDER_CELL_LAYERS,
+                TileGridPresentation::TOP_BORDER_CELL_LAYERS,
+            ),
+            CellRectSize::new(
+                grid.columns().get().saturating_mul(cells_per_tile),
+                grid.rows().get().saturating_mul(cells_per_tile),
+            ),
+        );
+        // Towers snap to half tiles, matching the world's placement alignment.
+        self.autoplayer = Some(Autoplayer::new(area, cells_per_tile / 2));
+    }
+
+    fn autoplay_outcomes(&self) -> &[RoundOutcome] {
+        self.autoplayer
+            .as_ref()
+            .map_or(&[], |autoplayer| autoplayer.outcomes())
+    }
+
+    /// Report of the most recently resolved wave, until the player dismisses it.
+    fn wave_report(&self) -> Option<&WaveReport> {
+        self.wave_report.as_ref()
+    }
+
+    /// Stops printing wave compositions and layout snapshots, for runs sharing stdout.
+    fn silence_console(&mut self) {
+        self.console_output = false;
+    }
+
+    fn launch_autoplayed_wave_if_ready(&mut self) {
+        let Some(autoplayer) = self.autoplayer.as_ref() else {
+            return;
+        };
+        if !self.ready_wave_launches.is_empty()
+            || !autoplayer.ready_to_launch(
+                query::play_mode(&self.world),
+                query::gold(&self.world),
+                &query::tower_content(&self.world),
+            )
+        {
+            return;
         }
+        self.initiate_wave_launch(WaveDifficulty::Normal);
+    }
 
-        if let Some(difficulty) = input.start_wave {
-            self.initiate_wave_launch(difficulty);
+    fn launch_spectated_wave_if_idle(&mut self) {
+        if self.spectator.is_none()
+            || query::next_wave_countdown(&self.world).is_some()
+            || !self.ready_wave_launches.is_empty()
+        {
+            return;
         }
+        if query::play_mode(&self.world) == PlayMode::Builder {
+            self.queued_commands.push(Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            });
+            return;
+        }
+        self.initiate_wave_launch(WaveDifficulty::Normal);
+    }
 
-        if input.replay_wave {
-            self.replay_last_attack_plan();
+    fn configure_auto_wave(&mut self, delay: Option<Duration>) {
+        self.queued_commands
+            .push(Command::ConfigureAutoWave { delay });
+    }
+
+    fn configure_builder_timer(&mut self, limit: Option<Duration>) {
+        self.queued_commands
+            .push(Command::ConfigureBuilderTimer { limit });
+    }
+
+    fn configure_wave_seed(&mut self, seed: u64) {
+        self.queued_commands
+            .push(Command::ConfigureWaveSeed { seed });
+    }
+
+    /// Re-rolls the wave seed between waves; a launch already keyed by the current seed
+    /// keeps it.
+    fn set_wave_seed(&mut self, seed: u64) {
+        if self.pending_wave_launch.is_some()
+            || !self.ready_wave_launches.is_empty()
+            || self.active_wave.is_some()
+            || self.awaiting_round_resolution
+        {
+            return;
         }
 
-        self.pending_input = FrameInput {
-            mode_toggle: false,
-            start_wave: None,
-            replay_wave: false,
-            ..input
-        };
+        self.queued_commands.push(Command::SetWaveSeed { seed });
     }
 
     fn initiate_wave_launch(&mut self, difficulty: WaveDifficulty) {
@@ -1362,25 +2394,74 @@ impl Simulation {
         }
 
         let context = query::wave_seed_context(&self.world);
-        let level_id = query::level_id(&self.world);
-        let base_difficulty = context.difficulty_level();
-        let effective_level = match difficulty {
-            WaveDifficulty::Normal => base_difficulty,
-            WaveDifficulty::Hard => base_difficulty.saturating_add(1),
-        };
-
-        let inputs = PressureWaveInputs::new(
-            context.global_seed(),
-            level_id,
-            context.wave(),
-            effective_level,
-        );
+        let inputs = context.inputs(query::level_id(&self.world), difficulty);
 
         self.pending_wave_launch = Some(PendingWaveLaunch {
             inputs: inputs.clone(),
             wave: context.wave(),
             difficulty,
         });
+
+        // A prefetched plan is re-cached rather than regenerated so the species table
+        // matches the chosen difficulty without stalling the launch frame.
+        if let Some(plan) = query::pressure_wave_plan(&self.world, &inputs) {
+            let plan = plan.clone();
+            self.queued_commands
+                .push(Command::CachePressureWave { inputs, plan });
+            return;
+        }
+        if !self.wave_plan_requested(&inputs) {
+            self.request_wave_plan(inputs);
+        }
+    }
+
+    /// Requests the plans of every difficulty choice for the upcoming wave while the
+    /// player is still deciding, so either launch finds its plan already cached.
+    fn prefetch_wave_plans(&mut self) {
+        if self.pending_wave_launch.is_some()
+            || !self.ready_wave_launches.is_empty()
+            || self.active_wave.is_some()
+            || self.awaiting_round_resolution
+        {
+            return;
+        }
+
+        let context = query::wave_seed_context(&self.world);
+        let level_id = query::level_id(&self.world);
+        for difficulty in WaveDifficulty::ALL {
+            let inputs = context.inputs(level_id, difficulty);
+            if query::pressure_wave_plan(&self.world, &inputs).is_none()
+                && !self.rejected_wave_plans.contains(&inputs)
+                && !self.wave_plan_requested(&inputs)
+            {
+                self.request_wave_plan(inputs);
+            }
+        }
+    }
+
+    fn wave_plan_requested(&self, inputs: &PressureWaveInputs) -> bool {
+        self.queued_commands.iter().any(|command| match command {
+            Command::GeneratePressureWave { inputs: queued }
+            | Command::CachePressureWave { inputs: queued, .. } => queued == inputs,
+            _ => false,
+        })
+    }
+
+    fn request_wave_plan(&mut self, inputs: PressureWaveInputs) {
+        #[cfg(feature = "dev")]
+        if let Some(generator) = self.pressure_generator.as_mut() {
+            let mut spawns = Vec::new();
+            let mut prototypes = Vec::new();
+            generator.generate(&inputs, &mut spawns, &mut prototypes);
+            let mut plan = PressureWavePlan::new(spawns, prototypes);
+            if generator.paced_last_wave() {
+                plan = plan.with_paced();
+            }
+            self.queued_commands
+                .push(Command::CachePressureWave { inputs, plan });
+            return;
+        }
+
         self.queued_commands
             .push(Command::GeneratePressureWave { inputs });
     }
@@ -1410,19 +2491,31 @@ impl Simulation {
             scheduled_spawns: Some(scheduled_spawns),
         });
 
-        self.queued_commands.push(Command::CachePressureWave { inputs, plan });
+        self.queued_commands
+            .push(Command::CachePressureWave { inputs, plan });
     }
 
     fn record_attack_plan_events(&mut self, events: &[Event]) -> Vec<ReadyWaveLaunch> {
         for event in events {
+            if let Event::PressureWaveRejected { inputs, reason } = event {
+                let _ = self.rejected_wave_plans.insert(inputs.as_ref().clone());
+                if self
+                    .pending_wave_launch
+                    .as_ref()
+                    .is_some_and(|pending| pending.inputs == **inputs)
+                {
+                    eprintln!("Wave plan rejected: {reason:?}");
+                    self.pending_wave_launch = None;
+                }
+            }
             if let Event::PressureWaveReady { inputs, plan } = event {
                 if let Some(pending) = &self.pending_wave_launch {
-                    if pending.inputs == *inputs {
+                    if pending.inputs == **inputs {
                         let launch = ReadyWaveLaunch {
-                            inputs: inputs.clone(),
+                            inputs: inputs.as_ref().clone(),
                             wave: pending.wave,
                             difficulty: pending.difficulty,
-                            plan: plan.clone(),
+                            plan: plan.as_ref().clone(),
                             scheduled_spawns: None,
                         };
                         self.ready_wave_launches.push_back(launch);
@@ -1467,7 +2560,19 @@ impl Simulation {
         } else {
             let spawners = query::bug_spawners(&self.world);
             let band_seed = spawn_band_seed(&inputs);
-            WaveState::new(plan_ref, &self.species_prototypes, &spawners, band_seed)
+            let patches = query::patch_table(&self.world);
+            let wave = WaveState::new(
+                plan_ref,
+                &self.species_prototypes,
+                &spawners,
+                patches.descriptors(),
+                band_seed,
+            );
+            if self.formations_enabled {
+                WaveState::from_schedule(group_into_formations(wave.scheduled))
+            } else {
+                wave
+            }
         };
 
         let schedule_snapshot = wave_state.scheduled().to_vec();
@@ -1492,6 +2597,9 @@ impl Simulation {
         difficulty: WaveDifficulty,
         plan: &PressureWavePlan,
     ) {
+        if !self.console_output {
+            return;
+        }
         if plan.prototypes().is_empty() {
             println!(
                 "\n=== Wave {} ({:?}) ===\n  No species scheduled.\n",
@@ -1559,6 +2667,7 @@ impl Simulation {
             .map(|tower| TowerLayoutTower {
                 kind: tower.kind,
                 origin: tower.region.origin(),
+                rotation: tower.rotation,
             })
             .collect();
         TowerLayoutSnapshot {
@@ -1639,6 +2748,7 @@ impl Simulation {
             self.queued_commands.push(Command::ImportTower {
                 kind: layout_tower.kind,
                 origin: layout_tower.origin,
+                rotation: layout_tower.rotation,
             });
         }
 
@@ -1661,9 +2771,28 @@ impl Simulation {
         self.last_announced_play_mode = query::play_mode(&self.world);
     }
 
+    /// Advances the simulation by one frame of `dt` for every step of the game speed.
+    ///
+    /// From [`BATCHED_TICK_SPEED`] upwards every frame but the last ticks the world with
+    /// [`Command::BatchedTick`], so cooldowns and projectiles integrate in larger steps while
+    /// bugs still move every frame; the last frame settles them before the scene is drawn.
+    fn fast_forward(&mut self, dt: Duration) {
+        let frames = self.speed;
+        for frame in 1..=frames {
+            self.advance_frame(dt, frames >= BATCHED_TICK_SPEED && frame < frames);
+        }
+    }
+
     fn advance(&mut self, dt: Duration) {
+        self.advance_frame(dt, false);
+    }
+
+    fn advance_frame(&mut self, dt: Duration, batched: bool) {
         let frame_start = Instant::now();
+        #[cfg(feature = "dev")]
+        self.reload_content(dt);
         let builder_preview = self.compute_builder_preview();
+        self.update_drag_placement(builder_preview);
         let builder_input = self.prepare_builder_input();
 
         self.pending_events.clear();
@@ -1672,15 +2801,47 @@ impl Simulation {
         self.advance_bug_motions(dt);
         if !dt.is_zero() {
             let mut emitted = Vec::new();
-            self.apply_command(Command::Tick { dt }, &mut emitted);
+            let tick = if batched {
+                Command::BatchedTick { dt }
+            } else {
+                Command::Tick { dt }
+            };
+            self.apply_command(tick, &mut emitted);
             self.pending_events.append(&mut emitted);
         }
 
         let events_profile = self.process_pending_events(builder_preview, builder_input);
+        self.update_ready_flashes(dt);
+        self.gold_feedback.advance(dt);
+        self.notifications.advance(dt);
+        self.formation_entrances.advance(dt);
+        self.bug_escapes.advance(dt);
+        self.projectile_whiffs.advance(dt);
+        self.combo_flashes.advance(dt);
+        if let Some(spectator) = self.spectator.as_mut() {
+            spectator.advance(dt);
+        }
+        if let Some(pulses) = self.focus_pulses.as_mut() {
+            pulses.advance(dt);
+        }
+        self.launch_spectated_wave_if_idle();
+        self.launch_autoplayed_wave_if_ready();
+        self.prefetch_wave_plans();
         self.builder_preview = self.compute_builder_preview();
         self.last_advance_profile =
             AdvanceProfile::new(frame_start.elapsed(), events_profile.pathfinding);
         self.announce_builder_mode_if_changed();
+        self.write_spectator_feed();
+    }
+
+    fn write_spectator_feed(&mut self) {
+        let Some(feed) = self.spectator_feed.as_mut() else {
+            return;
+        };
+        if let Err(error) = feed.record(&self.world) {
+            eprintln!("stopped writing the spectator feed: {error:#}");
+            self.spectator_feed = None;
+        }
     }
 
     fn announce_builder_mode_if_changed(&mut self) {
@@ -1688,7 +2849,8 @@ impl Simulation {
         if current_mode != self.last_announced_play_mode {
             let previous_mode = self.last_announced_play_mode;
             self.last_announced_play_mode = current_mode;
-            if cfg!(test) {
+            // The autoplayer toggles modes every wave; its runs print results instead.
+            if cfg!(test) || self.autoplayer.is_some() {
                 return;
             }
             if current_mode == PlayMode::Builder || previous_mode == PlayMode::Builder {
@@ -1709,6 +2871,18 @@ impl Simulation {
         }
     }
 
+    fn update_ready_flashes(&mut self, dt: Duration) {
+        let cooldowns = &self.tower_cooldowns;
+        self.tower_ready_flashes
+            .retain(|tower, _| cooldowns.iter().any(|snapshot| snapshot.tower == *tower));
+        for snapshot in cooldowns.iter() {
+            self.tower_ready_flashes
+                .entry(snapshot.tower)
+                .or_default()
+                .observe(snapshot.ready_in, dt);
+        }
+    }
+
     fn apply_command(&mut self, command: Command, out_events: &mut Vec<Event>) {
         self.applied_commands.push(command.clone());
         match command {
@@ -1788,7 +2962,7 @@ impl Simulation {
         if let Some(motion) = self.bug_motions.get(&id) {
             let from = Self::cell_center(motion.from);
             let to = Self::cell_center(motion.to);
-            let progress = motion.progress();
+            let progress = motion.progress(self.motion_lead);
             return from + (to - from) * progress;
         }
 
@@ -1854,6 +3028,11 @@ impl Simulation {
         let use_sprite_visuals = self.visual_style == VisualStyle::Sprites;
         const DEFAULT_TURRET_HEADING: f32 = 0.0;
 
+        #[cfg(feature = "dev")]
+        if let Some(palette) = self.palette {
+            palette.apply(scene);
+        }
+
         scene.ground = if use_sprite_visuals {
             self.ground_tiles()
         } else {
@@ -1867,8 +3046,24 @@ impl Simulation {
                 .iter()
                 .map(|wall| SceneWall::new(wall.column(), wall.row())),
         );
+        scene.exit_portal = ExitPortal::from_target_cells(&query::target_cells(&self.world));
+        scene.spawn_edges.clear();
+        scene.spawn_edges.extend(
+            query::bug_spawners(&self.world)
+                .into_iter()
+                .map(|cell| SpawnEdge::new(cell.column(), cell.row())),
+        );
 
         let bug_view = query::bug_view(&self.world);
+        self.bug_escapes.capture(&scene.bugs);
+        scene.escaping_bugs.clear();
+        scene.escaping_bugs.extend(self.bug_escapes.escapes());
+        self.projectile_whiffs.capture(&scene.bugs);
+        scene.whiffs.clear();
+        scene.whiffs.extend(self.projectile_whiffs.whiffs());
+        self.combo_flashes.capture(&scene.bugs);
+        scene.combo_flashes.clear();
+        scene.combo_flashes.extend(self.combo_flashes.flashes());
         scene.bugs.clear();
         let mut bug_positions = HashMap::new();
         for bug in bug_view.iter() {
@@ -1903,14 +3098,42 @@ impl Simulation {
                 BugPresentation::new_circle(bug.id, position, tint, health)
             };
 
-            scene.bugs.push(presentation);
+            scene.bugs.push(
+                presentation
+                    .with_entrance(self.formation_entrances.entrance_for(bug.id))
+                    .with_status(bug.status),
+            );
         }
 
+        scene.hovered_bug = if query::play_mode(&self.world) == PlayMode::Attack {
+            self.pending_input
+                .cursor_world_space
+                .and_then(|world| {
+                    let cursor = self.world_position_to_cell_space(world);
+                    pick_hovered_bug(&scene.bugs, cursor, BUG_PICK_RADIUS_CELLS)
+                })
+                .and_then(|hovered| {
+                    let bug = bug_view.iter().find(|bug| bug.id == hovered.id)?;
+                    Some(self.inspect_bug(bug, hovered.position()))
+                })
+        } else {
+            None
+        };
+        self.hovered_bug = scene.hovered_bug.as_ref().map(|inspection| inspection.bug);
+        scene.priority_target = query::priority_target(&self.world);
+
         let tower_view = query::towers(&self.world);
+        self.tower_selection.retain_existing(&tower_view);
+        scene.selected_towers.clear();
+        scene
+            .selected_towers
+            .extend(self.tower_selection.selected());
+        scene.selection_box = self.tower_selection.drag_region();
+        scene.selection_groups = Some(self.tower_selection.groups());
         scene.towers.clear();
         scene.towers.extend(tower_view.iter().map(|tower| {
             let descriptor = SceneTower::new(tower.id, tower.kind, tower.region);
-            if use_sprite_visuals {
+            if use_sprite_visuals && tower.kind.fires() {
                 let visual = visuals::tower_sprite_visual(tower.region, DEFAULT_TURRET_HEADING);
                 descriptor.with_visual(visual)
             } else {
@@ -1919,6 +3142,18 @@ impl Simulation {
         }));
 
         push_tower_targets(scene, &self.current_targets);
+        let tower_content = query::tower_content(&self.world);
+        push_tower_cooldowns(
+            scene,
+            &self.tower_cooldowns,
+            &tower_view,
+            &tower_content,
+            |tower| {
+                self.tower_ready_flashes
+                    .get(&tower)
+                    .map_or(0.0, ReadyFlash::intensity)
+            },
+        );
         push_projectiles(scene, &self.projectiles, |bug, fallback| {
             bug_positions.get(&bug).copied().unwrap_or(fallback)
         });
@@ -1927,6 +3162,8 @@ impl Simulation {
         scene.spawn_effects.extend(self.spawn_effects());
 
         scene.play_mode = query::play_mode(&self.world);
+        scene.selected_tower_kind = Some(self.selected_tower_kind);
+        scene.debug_stats = Some(query::debug_stats(&self.world));
         scene.tower_preview = if scene.play_mode == PlayMode::Builder {
             self.builder_preview().map(|preview| {
                 TowerPreview::new(
@@ -1939,6 +3176,14 @@ impl Simulation {
         } else {
             None
         };
+        scene.path_preview = scene
+            .tower_preview
+            .map(|preview| self.path_preview_for(preview.region));
+        scene.danger_heatmap = if scene.play_mode == PlayMode::Builder {
+            Some(query::danger_heatmap(&self.world))
+        } else {
+            None
+        };
         scene.active_tower_footprint_tiles = if scene.play_mode == PlayMode::Builder {
             Some(self.selected_tower_footprint_tiles())
         } else {
@@ -1958,15 +3203,128 @@ impl Simulation {
         } else {
             None
         };
+        scene.attack_region = self.attack_region(scene.tower_preview, scene.hovered_tower);
         scene.tower_feedback = self.tower_feedback;
         scene.gold = Some(GoldPresentation::new(self.gold));
+        scene.gold_feedback.clone_from(&self.gold_feedback);
+        scene.notifications.clone_from(&self.notifications);
+        scene.clock = Some(ClockPresentation::new(
+            query::elapsed(&self.world),
+            query::wave_elapsed(&self.world),
+        ));
+        scene.level_progress = self.latest_wave.map(|wave| {
+            LevelProgressPresentation::new(query::level_id(&self.world), wave.get() + 1)
+        });
+        scene.wave_countdown = query::next_wave_countdown(&self.world)
+            .zip(query::early_start_bonus(&self.world))
+            .map(|(remaining, bonus)| WaveCountdownPresentation::new(remaining, bonus));
+        scene.builder_countdown = query::builder_time_left(&self.world);
         scene.difficulty = Some(DifficultyPresentation::new(self.difficulty_level.get()));
         scene.difficulty_selection = Some(self.difficulty_selection_presentation());
+        scene.wave_contracts = if self.active_wave.is_none() && !self.awaiting_round_resolution {
+            let accepted = query::accepted_contracts(&self.world);
+            WaveContract::ALL
+                .into_iter()
+                .map(|contract| {
+                    WaveContractPresentation::new(contract, accepted.contains(&contract))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        scene.wave_composition = self.next_wave_composition();
         scene.analytics = self
             .analytics_report
             .clone()
             .map(AnalyticsPresentation::new);
+        scene.wave_report = self.wave_report.clone().map(WaveReportPresentation::new);
+        scene.between_wave_event = self.between_wave_event.map(|event| {
+            let awaiting_choice = matches!(event, BetweenWaveEvent::MerchantOffer { .. })
+                && query::merchant_offer(&self.world).is_some()
+                && self.spectator.is_none();
+            BetweenWaveEventPresentation::new(event, awaiting_choice)
+        });
         scene.replay_available = self.can_replay_last_attack_plan();
+        scene.sandbox = query::is_sandbox_session(&self.world);
+        scene.wave_seed = Some(query::wave_seed_context(&self.world).global_seed());
+        scene.wave_shield = query::wave_shield(&self.world)
+            .map(|shield| BugHealthPresentation::new(shield.current, shield.capacity));
+        scene.base_health = Some(BugHealthPresentation::new(
+            query::base_health(&self.world),
+            query::game_rules(&self.world).base_health,
+        ));
+        scene.prestige_earned = self.prestige_earned;
+        scene.bridges.clear();
+        scene.bridges.extend(query::bridge_cells(&self.world));
+        scene.void_cells.clear();
+        scene.void_cells.extend(query::void_cells(&self.world));
+        scene.nests.clear();
+        scene
+            .nests
+            .extend(query::nests(&self.world).into_iter().map(|nest| {
+                NestPresentation::new(
+                    nest.cells,
+                    BugHealthPresentation::new(nest.health.get(), nest.max_health.get()),
+                )
+            }));
+        scene.camera_focus = self
+            .spectator
+            .as_mut()
+            .and_then(|spectator| spectator.focus(&scene.bugs, &scene.towers));
+        if let Some(pulses) = self.focus_pulses.as_ref() {
+            scene.camera_focus = pulses.focus(scene.camera_focus, &scene.tile_grid);
+        }
+        scene.focus_pulse = self.focus_pulses.as_ref().and_then(FocusPulses::pulse);
+        BugLodPolicy::default().apply(scene);
+    }
+
+    fn inspect_bug(&self, bug: &BugSnapshot, position: Vec2) -> BugInspection {
+        let species = self.species_for_color(bug.color);
+        let baseline_step_ms = species
+            .and_then(|id| self.species_prototypes.get(&id))
+            .map(|prototype| prototype.step_ms().get())
+            .unwrap_or_else(|| {
+                u32::try_from(self.bug_step_duration.as_millis()).unwrap_or(u32::MAX)
+            });
+        let speed_multiplier = if bug.step_ms == 0 {
+            1.0
+        } else {
+            baseline_step_ms as f32 / bug.step_ms as f32
+        };
+        let bounty = bug
+            .bounty
+            .saturating_mul(u64::from(query::kill_reward_multiplier(&self.world)));
+        BugInspection {
+            bug: bug.id,
+            position,
+            species,
+            health: BugHealthPresentation::new(bug.health.get(), bug.max_health.get()),
+            speed_multiplier,
+            step_ms: bug.step_ms,
+            bounty,
+            boss_phase: bug.boss_phase,
+            effects: query::bug_effects(&self.world, bug.id),
+        }
+    }
+
+    fn species_for_color(&self, color: BugColor) -> Option<SpeciesId> {
+        let mut candidates: Vec<SpeciesId> = self
+            .species_prototypes
+            .iter()
+            .filter(|(_, prototype)| prototype.color() == color)
+            .map(|(id, _)| *id)
+            .collect();
+        candidates.sort();
+        if let Some(id) = candidates.first() {
+            return Some(*id);
+        }
+
+        let plan = self.active_wave_plan.as_ref()?;
+        plan.prototypes()
+            .iter()
+            .position(|prototype| prototype.color() == color)
+            .and_then(|index| u32::try_from(index).ok())
+            .map(SpeciesId::new)
     }
 
     fn spawn_effects(&self) -> Vec<SpawnEffect> {
@@ -1980,13 +3338,7 @@ impl Simulation {
 
         effect_sources
             .into_iter()
-            .map(|(cell, color)| {
-                SpawnEffect::new(
-                    cell.column(),
-                    cell.row(),
-                    Color::from_rgb_u8(color.red(), color.green(), color.blue()),
-                )
-            })
+            .map(SpawnEffectSource::into_effect)
             .collect()
     }
 
@@ -2002,7 +3354,7 @@ impl Simulation {
         &self,
         plan: &PressureWavePlan,
         inputs: &PressureWaveInputs,
-    ) -> Vec<(CellCoord, BugColor)> {
+    ) -> Vec<SpawnEffectSource> {
         if plan.spawns().is_empty() {
             return Vec::new();
         }
@@ -2013,10 +3365,59 @@ impl Simulation {
         }
 
         let seed = spawn_band_seed(inputs);
-        let preview = WaveState::new(plan, &self.species_prototypes, &spawners, seed);
+        let patches = query::patch_table(&self.world);
+        let preview = WaveState::new(
+            plan,
+            &self.species_prototypes,
+            &spawners,
+            patches.descriptors(),
+            seed,
+        );
         preview.pending_spawn_effects()
     }
 
+    /// Line-of-sight clipped range of the previewed tower, or else the hovered one, for
+    /// kinds whose shots obstacles block.
+    fn attack_region(
+        &self,
+        preview: Option<TowerPreview>,
+        hovered: Option<TowerId>,
+    ) -> Option<AttackRegion> {
+        let (kind, region) = match preview {
+            Some(preview) => (preview.kind, preview.region),
+            None => {
+                let hovered = hovered?;
+                query::towers(&self.world)
+                    .iter()
+                    .find(|tower| tower.id == hovered)
+                    .map(|tower| (tower.kind, tower.region))?
+            }
+        };
+        if !query::line_of_sight_enabled(&self.world) || !kind.requires_line_of_sight() {
+            return None;
+        }
+        sight::attack_region(
+            &self.world,
+            region,
+            kind.range_in_cells(self.cells_per_tile),
+        )
+    }
+
+    /// Composition of the wave the selected difficulty launches next, once its plan is cached.
+    fn next_wave_composition(&self) -> Option<WaveCompositionPresentation> {
+        if self.active_wave.is_some() || self.awaiting_round_resolution {
+            return None;
+        }
+        let difficulty = match self.pending_wave_difficulty {
+            PendingWaveDifficulty::Selected(difficulty) => difficulty,
+            PendingWaveDifficulty::Unset => WaveDifficulty::Normal,
+        };
+        let inputs =
+            query::wave_seed_context(&self.world).inputs(query::level_id(&self.world), difficulty);
+        let plan = query::pressure_wave_plan(&self.world, &inputs)?;
+        Some(WaveCompositionPresentation::from_plan(difficulty, plan))
+    }
+
     fn difficulty_selection_presentation(&self) -> DifficultySelectionPresentation {
         let (normal_selected, hard_selected) = match self.pending_wave_difficulty {
             PendingWaveDifficulty::Selected(WaveDifficulty::Normal) => (true, false),
@@ -2024,10 +3425,14 @@ impl Simulation {
             PendingWaveDifficulty::Unset => (false, false),
         };
 
-        let normal_level = self.difficulty_level.get();
-        let hard_level = self.difficulty_level.saturating_add(1).get();
-        let normal_multiplier = normal_level.saturating_add(1);
-        let hard_multiplier = hard_level.saturating_add(1);
+        let normal_level = query::effective_difficulty(&self.world, WaveDifficulty::Normal)
+            .level()
+            .get();
+        let hard_level = query::effective_difficulty(&self.world, WaveDifficulty::Hard)
+            .level()
+            .get();
+        let normal_multiplier = query::reward_multiplier(&self.world, WaveDifficulty::Normal);
+        let hard_multiplier = query::reward_multiplier(&self.world, WaveDifficulty::Hard);
 
         DifficultySelectionPresentation::new(
             DifficultyButtonPresentation::new(
@@ -2082,10 +3487,17 @@ impl Simulation {
                 launches.push(launch);
             }
             self.handle_bug_motion_events(&events);
+            self.request_focus_pulses(&events);
             self.record_tower_feedback(&events);
+            self.invalidate_path_preview(&events);
+            self.launch_wave_after_countdown(&events);
             self.update_gold_from_events(&events);
             self.update_difficulty_level_from_events(&events);
             self.update_analytics_report_from_events(&events);
+            self.update_wave_report_from_events(&events);
+            self.write_wave_outcomes(&events);
+            self.write_checkpoints(&events);
+            self.update_between_wave_event_from_events(&events);
             self.update_pending_wave_difficulty_from_events(&events);
             self.update_pressure_configuration_from_events(&events);
 
@@ -2093,10 +3505,30 @@ impl Simulation {
                 self.activate_wave(launch, &mut emitted_events, &mut next_events);
             }
 
+            let kills = events
+                .iter()
+                .filter(|event| matches!(event, Event::BugDied { .. }))
+                .count();
+            let waves_cleared = events
+                .iter()
+                .filter(|event| {
+                    matches!(
+                        event,
+                        Event::WaveReportReady { report } if report.outcome() == RoundOutcome::Win
+                    )
+                })
+                .count();
+            self.tally.kills = self.tally.kills.saturating_add(kills as u64);
+            self.tally.waves_cleared = self
+                .tally
+                .waves_cleared
+                .saturating_add(waves_cleared as u64);
+
             if events
                 .iter()
                 .any(|event| matches!(event, Event::RoundLost { .. }))
             {
+                self.record_run_summary();
                 self.active_wave = None;
                 self.active_wave_plan = None;
                 self.awaiting_round_resolution = false;
@@ -2136,10 +3568,10 @@ impl Simulation {
             if wave_complete {
                 self.active_wave = None;
                 self.active_wave_plan = None;
-                if query::bug_view(&self.world).iter().next().is_none() {
-                    if self.queue_round_outcome(RoundOutcome::Win) || self.pending_outcome_command {
-                        self.awaiting_round_resolution = false;
-                    }
+                if query::bug_view(&self.world).iter().next().is_none()
+                    && (self.queue_round_outcome(RoundOutcome::Win) || self.pending_outcome_command)
+                {
+                    self.awaiting_round_resolution = false;
                 }
             }
 
@@ -2149,6 +3581,7 @@ impl Simulation {
                 let occupancy_view = query::occupancy_view(&self.world);
                 let target_cells = query::target_cells(&self.world);
                 let navigation_view = query::navigation_field(&self.world);
+                let flanker_navigation_view = query::flanker_navigation_field(&self.world);
                 let reservation_ledger = query::reservation_ledger(&self.world);
                 let pathfinding_start = Instant::now();
                 self.movement.handle(
@@ -2156,6 +3589,7 @@ impl Simulation {
                     &bug_view,
                     occupancy_view,
                     navigation_view,
+                    flanker_navigation_view,
                     reservation_ledger,
                     &target_cells,
                     |cell| query::is_cell_blocked(&self.world, cell),
@@ -2185,6 +3619,15 @@ impl Simulation {
             );
             builder_preview = None;
             builder_input = TowerBuilderInput::default();
+            if let Some(autoplayer) = self.autoplayer.as_mut() {
+                autoplayer.handle(
+                    &events,
+                    query::play_mode(&self.world),
+                    query::gold(&self.world),
+                    &query::tower_content(&self.world),
+                    &mut self.scratch_commands,
+                );
+            }
             let mut commands = std::mem::take(&mut self.scratch_commands);
             for command in commands.drain(..) {
                 self.apply_command(command, &mut emitted_events);
@@ -2195,10 +3638,9 @@ impl Simulation {
             if self.awaiting_round_resolution
                 && self.active_wave.is_none()
                 && query::bug_view(&self.world).iter().next().is_none()
+                && (self.queue_round_outcome(RoundOutcome::Win) || self.pending_outcome_command)
             {
-                if self.queue_round_outcome(RoundOutcome::Win) || self.pending_outcome_command {
-                    self.awaiting_round_resolution = false;
-                }
+                self.awaiting_round_resolution = false;
             }
 
             let mut analytics_events = Vec::new();
@@ -2211,6 +3653,29 @@ impl Simulation {
                 },
                 &mut analytics_events,
             );
+            self.analytics.record_wave_outcomes(
+                &events,
+                |wave, launch| {
+                    let inputs = PressureWaveInputs::new(
+                        query::wave_seed_context(world).global_seed(),
+                        query::level_id(world),
+                        wave,
+                        launch.effective_difficulty.level(),
+                    )
+                    .with_pressure_share(launch.pressure_share)
+                    .with_history(launch.history);
+                    let plan = query::pressure_wave_plan(world, &inputs)?;
+                    Some(WaveLaunchSnapshot {
+                        inputs,
+                        plan,
+                        towers: query::towers(world)
+                            .iter()
+                            .map(|tower| tower.kind)
+                            .collect(),
+                    })
+                },
+                &mut analytics_events,
+            );
             if !analytics_events.is_empty() {
                 next_events.extend(analytics_events);
             }
@@ -2265,7 +3730,10 @@ impl Simulation {
         let mut bug_view_cache: Option<BugView> = None;
         for event in events {
             match event {
-                Event::BugAdvanced { bug_id, from, to } => {
+                Event::BugAdvanced { bug_id, from, to }
+                | Event::BugPushedBack {
+                    bug_id, from, to, ..
+                } => {
                     let step_duration = self
                         .bug_specific_step_duration(*bug_id, &mut bug_view_cache)
                         .or_else(|| {
@@ -2285,17 +3753,69 @@ impl Simulation {
                     let _ = self.bug_motions.remove(bug_id);
                     let _ = self.bug_headings.insert(*bug_id, DEFAULT_BUG_HEADING);
                 }
-                Event::BugExited { bug_id, .. } => {
-                    let _ = self.bug_motions.remove(bug_id);
+                Event::FormationSpawned {
+                    formation, bugs, ..
+                } => {
+                    self.formation_entrances.record(*formation, bugs);
+                }
+                Event::BugExited { bug_id, .. }
+                | Event::BugLooped { bug_id, .. }
+                | Event::BugWornOut { bug_id, .. } => {
+                    let direction = self
+                        .bug_motions
+                        .remove(bug_id)
+                        .map(|motion| {
+                            Vec2::new(
+                                motion.to.column() as f32 - motion.from.column() as f32,
+                                motion.to.row() as f32 - motion.from.row() as f32,
+                            )
+                        })
+                        .filter(|step| *step != Vec2::ZERO);
+                    self.bug_escapes.record(*bug_id, direction);
                     let _ = self.bug_headings.remove(bug_id);
                 }
-                Event::BugDied { bug } => {
+                Event::BugDied { bug, .. } => {
                     let _ = self.bug_motions.remove(bug);
                     let _ = self.bug_headings.remove(bug);
                 }
+                Event::ProjectileMissed { target, .. } => {
+                    self.projectile_whiffs.record(*target);
+                }
+                Event::ComboTriggered { bug, combo, .. } => {
+                    self.combo_flashes.record(*bug, *combo);
+                }
                 Event::PlayModeChanged { mode } if *mode == PlayMode::Builder => {
                     self.bug_motions.clear();
                     self.bug_headings.clear();
+                    self.formation_entrances.clear();
+                    self.bug_escapes.clear();
+                    self.projectile_whiffs.clear();
+                    self.combo_flashes.clear();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn request_focus_pulses(&mut self, events: &[Event]) {
+        let Some(pulses) = self.focus_pulses.as_mut() else {
+            return;
+        };
+        let mut bug_view = None;
+        for event in events {
+            match event {
+                Event::WaveStarted { .. } => pulses.wave_started(),
+                Event::BugExited { cell, .. }
+                | Event::BugLooped { exit: cell, .. }
+                | Event::BugWornOut { exit: cell, .. } => pulses.leaked(Self::cell_center(*cell)),
+                Event::BugSpawned { bug_id, cell, .. } => {
+                    let is_boss = bug_view
+                        .get_or_insert_with(|| query::bug_view(&self.world))
+                        .iter()
+                        .any(|bug| bug.id == *bug_id && bug.boss_phase.is_some());
+                    if is_boss {
+                        pulses.request(FocusReason::BossSpawned, Self::cell_center(*cell));
+                    }
                 }
                 _ => {}
             }
@@ -2371,7 +3891,33 @@ impl Simulation {
         }
     }
 
+    fn launch_wave_after_countdown(&mut self, events: &[Event]) {
+        let countdown_elapsed = events
+            .iter()
+            .any(|event| matches!(event, Event::NextWaveCountdown { remaining_ms: 0 }));
+        if !countdown_elapsed {
+            return;
+        }
+
+        let difficulty = match self.pending_wave_difficulty {
+            PendingWaveDifficulty::Selected(difficulty) => difficulty,
+            PendingWaveDifficulty::Unset => WaveDifficulty::Normal,
+        };
+        self.initiate_wave_launch(difficulty);
+    }
+
+    fn invalidate_path_preview(&mut self, events: &[Event]) {
+        if events
+            .iter()
+            .any(|event| matches!(event, Event::MazeLayoutChanged))
+        {
+            self.path_preview = None;
+        }
+    }
+
     fn update_gold_from_events(&mut self, events: &[Event]) {
+        self.gold_feedback.observe(events);
+        self.notifications.observe(events);
         for event in events {
             if let Event::GoldChanged { amount } = event {
                 self.gold = *amount;
@@ -2395,6 +3941,62 @@ impl Simulation {
         }
     }
 
+    fn update_wave_report_from_events(&mut self, events: &[Event]) {
+        if self.spectator.is_some() {
+            return;
+        }
+        for event in events {
+            if let Event::WaveReportReady { report } = event {
+                self.wave_report = Some(report.as_ref().clone());
+            }
+        }
+    }
+
+    fn write_wave_outcomes(&mut self, events: &[Event]) {
+        let Some(log) = self.wave_outcome_log.as_mut() else {
+            return;
+        };
+        if let Err(error) = log.record(events) {
+            eprintln!("stopped writing wave outcomes: {error:#}");
+            self.wave_outcome_log = None;
+        }
+    }
+
+    fn write_checkpoints(&mut self, events: &[Event]) {
+        let Some(directory) = self.checkpoint_directory.as_deref() else {
+            return;
+        };
+        for event in events {
+            let Event::WaveReportReady { report } = event else {
+                continue;
+            };
+            let checkpoint = WorldCheckpoint {
+                wave: report.wave(),
+                difficulty_level: query::difficulty_level(&self.world),
+                gold: query::gold(&self.world),
+                layout: self.capture_layout_snapshot(),
+            };
+            if let Err(error) = checkpoint.save(directory) {
+                eprintln!("stopped saving checkpoints: {error:#}");
+                self.checkpoint_directory = None;
+                return;
+            }
+        }
+    }
+
+    fn update_between_wave_event_from_events(&mut self, events: &[Event]) {
+        for event in events {
+            match event {
+                Event::BetweenWaveEventRolled { event } => self.between_wave_event = Some(*event),
+                Event::WaveStarted { wave, .. } => {
+                    self.between_wave_event = None;
+                    self.latest_wave = Some(*wave);
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn update_pending_wave_difficulty_from_events(&mut self, events: &[Event]) {
         for event in events {
             if let Event::PendingWaveDifficultyChanged { pending } = event {
@@ -2417,6 +4019,7 @@ impl Simulation {
             if !self.current_targets.is_empty() {
                 self.current_targets.clear();
             }
+            self.nest_targets.clear();
             self.tower_cooldowns = TowerCooldownView::default();
             if !self.projectiles.is_empty() {
                 self.projectiles.clear();
@@ -2424,298 +4027,1599 @@ impl Simulation {
             return;
         }
 
-        self.tower_cooldowns = query::tower_cooldowns(&self.world);
-        self.projectiles.clear();
-        self.projectiles.extend(query::projectiles(&self.world));
+        self.tower_cooldowns = query::tower_cooldowns(&self.world);
+        self.projectiles.clear();
+        self.projectiles.extend(query::projectiles(&self.world));
+
+        let towers = query::towers(&self.world);
+        let bugs = query::bug_view(&self.world);
+        let cells_per_tile = query::cells_per_tile(&self.world);
+        let world = &self.world;
+        let line_of_sight = query::line_of_sight_enabled(world);
+        self.tower_targeting.handle_in_sight(
+            play_mode,
+            &towers,
+            &bugs,
+            cells_per_tile,
+            query::priority_target(world),
+            |from, to| !line_of_sight || query::raycast(world, from, to).is_clear(),
+            &mut self.current_targets,
+        );
+        self.tower_targeting.handle_nests(
+            play_mode,
+            &towers,
+            &query::nests(&self.world),
+            &self.current_targets,
+            cells_per_tile,
+            &mut self.nest_targets,
+        );
+
+        self.tower_combat.handle(
+            play_mode,
+            self.tower_cooldowns.clone(),
+            &self.current_targets,
+            &mut self.queued_commands,
+        );
+        self.tower_combat.handle_nests(
+            play_mode,
+            self.tower_cooldowns.clone(),
+            &self.nest_targets,
+            &mut self.queued_commands,
+        );
+    }
+
+    fn update_drag_placement(&mut self, preview: Option<BuilderPlacementPreview>) {
+        let Some(preview) = preview else {
+            self.drag_placement = None;
+            return;
+        };
+        if query::play_mode(&self.world) != PlayMode::Builder {
+            self.drag_placement = None;
+            return;
+        }
+
+        if self.pending_input.confirm_action {
+            self.drag_placement = Some(DragPlacement::new(preview.origin));
+            return;
+        }
+
+        if !self.pending_input.confirm_held {
+            self.drag_placement = None;
+            return;
+        }
+
+        let footprint = self.placement_footprint(preview.kind);
+        let Some(drag) = self.drag_placement.as_mut() else {
+            return;
+        };
+        let mut origins = drag_line_origins(drag.anchor, preview.origin, footprint);
+        origins.retain(|origin| drag.visited.insert(*origin));
+
+        let previews: Vec<BuilderPlacementPreview> = origins
+            .into_iter()
+            .map(|origin| {
+                let region = CellRect::from_origin_and_size(origin, footprint);
+                BuilderPlacementPreview::new(
+                    preview.kind,
+                    origin,
+                    region,
+                    self.region_is_placeable(region),
+                    None,
+                )
+            })
+            .collect();
+        self.builder
+            .handle_drag(&previews, &mut self.queued_commands);
+    }
+
+    fn prepare_builder_input(&mut self) -> TowerBuilderInput {
+        let cursor_cell = self
+            .pending_input
+            .cursor_tile_space
+            .map(|tile| self.tile_position_to_cell(tile));
+        let input = TowerBuilderInput::new(
+            self.pending_input.confirm_action,
+            self.pending_input.remove_action,
+            cursor_cell,
+        );
+        self.pending_input.confirm_action = false;
+        self.pending_input.remove_action = false;
+        input
+    }
+
+    fn compute_builder_preview(&self) -> Option<BuilderPlacementPreview> {
+        let tile_position = self.pending_input.cursor_tile_space?;
+        let origin = self.tile_position_to_cell(tile_position);
+        let kind = self.selected_tower_kind();
+        let footprint = self.placement_footprint(kind);
+        let region = CellRect::from_origin_and_size(origin, footprint);
+        let mut placeable = self.region_is_placeable(region);
+        let rejection = self
+            .last_placement_rejection
+            .and_then(|rejection| {
+                if rejection.kind == kind && rejection.origin == origin {
+                    Some(rejection.reason)
+                } else {
+                    None
+                }
+            })
+            .or_else(|| {
+                self.region_is_reserved(region)
+                    .then_some(PlacementError::SpawnReserved)
+            });
+        if rejection.is_some() {
+            placeable = false;
+        }
+        Some(BuilderPlacementPreview::new(
+            kind, origin, region, placeable, rejection,
+        ))
+    }
+
+    fn selected_tower_kind(&self) -> TowerKind {
+        self.selected_tower_kind
+    }
+
+    fn placement_footprint(&self, kind: TowerKind) -> CellRectSize {
+        query::placement_rotation(&self.world).apply(kind.footprint())
+    }
+
+    fn ground_tiles(&self) -> Option<GroundSpriteTiles> {
+        if self.cells_per_tile == 0 {
+            return None;
+        }
+
+        let footprint = TowerKind::Basic.footprint();
+        if footprint.width() == 0 || footprint.height() == 0 {
+            return None;
+        }
+
+        let base_tiles = Vec2::new(
+            footprint.width() as f32 / self.cells_per_tile as f32,
+            footprint.height() as f32 / self.cells_per_tile as f32,
+        );
+
+        if base_tiles.x <= f32::EPSILON || base_tiles.y <= f32::EPSILON {
+            return None;
+        }
+
+        let span_tiles = base_tiles * GROUND_TILE_MULTIPLIER;
+        visuals::ground_sprite_tiles(
+            span_tiles,
+            self.cells_per_tile,
+            SpriteKey::GroundGrass,
+            GroundKind::Grass,
+        )
+    }
+
+    fn selected_tower_footprint_tiles(&self) -> Vec2 {
+        if self.cells_per_tile == 0 {
+            return Vec2::ZERO;
+        }
+
+        let footprint = self.placement_footprint(self.selected_tower_kind());
+        Vec2::new(
+            footprint.width() as f32 / self.cells_per_tile as f32,
+            footprint.height() as f32 / self.cells_per_tile as f32,
+        )
+    }
+
+    fn tile_position_to_cell(&self, position: TileSpacePosition) -> CellCoord {
+        let cells_per_tile = self.cells_per_tile.max(1);
+        let column_cells = (position.column_in_tiles() * cells_per_tile as f32).round() as u32;
+        let row_cells = (position.row_in_tiles() * cells_per_tile as f32).round() as u32;
+        let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS.saturating_add(column_cells);
+        let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS.saturating_add(row_cells);
+        CellCoord::new(column, row)
+    }
+
+    fn world_position_to_cell(&self, position: Vec2) -> CellCoord {
+        let tile_grid = query::tile_grid(&self.world);
+        let cells_per_tile = self.cells_per_tile.max(1);
+        let tile_length = tile_grid.tile_length();
+        if tile_length <= f32::EPSILON {
+            return CellCoord::new(
+                TileGridPresentation::SIDE_BORDER_CELL_LAYERS,
+                TileGridPresentation::TOP_BORDER_CELL_LAYERS,
+            );
+        }
+
+        let cell_length = tile_length / cells_per_tile as f32;
+        if cell_length <= f32::EPSILON {
+            return CellCoord::new(
+                TileGridPresentation::SIDE_BORDER_CELL_LAYERS,
+                TileGridPresentation::TOP_BORDER_CELL_LAYERS,
+            );
+        }
+
+        let column_cells = Self::world_axis_to_cell_index(
+            position.x,
+            tile_grid.columns().get(),
+            cells_per_tile,
+            cell_length,
+        );
+        let row_cells = Self::world_axis_to_cell_index(
+            position.y,
+            tile_grid.rows().get(),
+            cells_per_tile,
+            cell_length,
+        );
+
+        let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS.saturating_add(column_cells);
+        let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS.saturating_add(row_cells);
+        CellCoord::new(column, row)
+    }
+
+    fn world_position_to_cell_space(&self, position: Vec2) -> Vec2 {
+        let tile_length = query::tile_grid(&self.world).tile_length();
+        let cells_per_tile = self.cells_per_tile.max(1);
+        let cell_length = tile_length / cells_per_tile as f32;
+        let border = Vec2::new(
+            TileGridPresentation::SIDE_BORDER_CELL_LAYERS as f32,
+            TileGridPresentation::TOP_BORDER_CELL_LAYERS as f32,
+        );
+        if cell_length <= f32::EPSILON {
+            return border;
+        }
+        border + position / cell_length
+    }
+
+    fn world_axis_to_cell_index(
+        value: f32,
+        tiles: u32,
+        cells_per_tile: u32,
+        cell_length: f32,
+    ) -> u32 {
+        if tiles == 0 || cells_per_tile == 0 || cell_length <= f32::EPSILON {
+            return 0;
+        }
+
+        let total_cells = tiles.saturating_mul(cells_per_tile);
+        if total_cells == 0 {
+            return 0;
+        }
+
+        let index = (value / cell_length).floor();
+        if !index.is_finite() {
+            return 0;
+        }
+
+        let max_index = (total_cells - 1) as f32;
+        if index < 0.0 {
+            return 0;
+        }
+
+        if index > max_index {
+            return total_cells - 1;
+        }
+
+        index as u32
+    }
+
+    fn region_is_placeable(&self, region: CellRect) -> bool {
+        let size = region.size();
+        if size.width() == 0 || size.height() == 0 {
+            return false;
+        }
+
+        let origin = region.origin();
+        for column_offset in 0..size.width() {
+            let Some(column) = origin.column().checked_add(column_offset) else {
+                return false;
+            };
+            for row_offset in 0..size.height() {
+                let Some(row) = origin.row().checked_add(row_offset) else {
+                    return false;
+                };
+                let cell = CellCoord::new(column, row);
+                if query::is_cell_blocked(&self.world, cell) {
+                    return false;
+                }
+            }
+        }
+
+        !self.region_is_reserved(region)
+    }
+
+    fn region_is_reserved(&self, region: CellRect) -> bool {
+        query::spawn_reservations(&self.world)
+            .iter()
+            .any(|reservation| reservation.overlaps(region))
+    }
+
+    fn path_preview_for(&mut self, region: CellRect) -> PathPreview {
+        if let Some((cached_region, preview)) = &self.path_preview {
+            if *cached_region == region {
+                return preview.clone();
+            }
+        }
+
+        let preview = match query::path_preview(&self.world, region) {
+            Some(route) => PathPreview::Route(route),
+            None => PathPreview::Blocked,
+        };
+        self.path_preview = Some((region, preview.clone()));
+        preview
+    }
+
+    fn builder_preview(&self) -> Option<BuilderPlacementPreview> {
+        self.builder_preview
+    }
+
+    #[cfg(test)]
+    fn bug_step_ms(&self) -> u32 {
+        use std::convert::TryFrom;
+
+        u32::try_from(self.bug_step_duration.as_millis()).unwrap_or(u32::MAX)
+    }
+
+    #[cfg(test)]
+    fn bug_step_duration(&self) -> Duration {
+        self.bug_step_duration
+    }
+
+    #[cfg(test)]
+    fn last_frame_events(&self) -> &[Event] {
+        &self.last_frame_events
+    }
+
+    #[cfg(test)]
+    #[cfg(test)]
+    fn tower_feedback(&self) -> Option<TowerInteractionFeedback> {
+        self.tower_feedback
+    }
+
+    #[cfg(test)]
+    fn current_targets(&self) -> &[TowerTarget] {
+        &self.current_targets
+    }
+
+    #[cfg(test)]
+    fn tower_cooldowns(&self) -> &TowerCooldownView {
+        &self.tower_cooldowns
+    }
+
+    #[cfg(test)]
+    fn projectiles(&self) -> &[ProjectileSnapshot] {
+        &self.projectiles
+    }
+
+    #[cfg(test)]
+    fn queued_commands(&self) -> &[Command] {
+        &self.queued_commands
+    }
+
+    fn flush_queued_commands(&mut self) {
+        if self.queued_commands.is_empty() {
+            return;
+        }
+
+        let mut emitted = Vec::new();
+        let mut queued = std::mem::take(&mut self.queued_commands);
+        for command in queued.drain(..) {
+            let resolves_round = matches!(command, Command::ResolveRound { .. });
+            self.apply_command(command, &mut emitted);
+            if resolves_round {
+                self.pending_outcome_command = false;
+                self.awaiting_round_resolution = false;
+            }
+            self.pending_events.append(&mut emitted);
+        }
+        self.queued_commands = queued;
+    }
+}
+
+impl Drop for Simulation {
+    fn drop(&mut self) {
+        if cfg!(test) || !self.console_output {
+            return;
+        }
+
+        let snapshot = self.capture_layout_snapshot();
+        let encoded = snapshot.encode();
+        println!("{encoded}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{
+        DifficultyLevel, GameRules, LevelId, PressureSpawnRecord, WaveDifficulty,
+    };
+    use maze_defence_rendering::{run_frames, ScriptedFrameInput};
+    use std::{collections::HashSet, num::NonZeroU32, time::Duration};
+
+    fn species_proto(color: BugColor, health: u32, step_ms: u32) -> SpeciesPrototype {
+        SpeciesPrototype::new(
+            color,
+            Health::new(health),
+            NonZeroU32::new(step_ms.max(1)).expect("non-zero step"),
+        )
+    }
+
+    fn build_plan(
+        spawn_count: usize,
+        species: u32,
+        spacing: u32,
+        prototype: SpeciesPrototype,
+    ) -> PressureWavePlan {
+        let mut spawns = Vec::with_capacity(spawn_count);
+        for index in 0..spawn_count {
+            spawns.push(PressureSpawnRecord::new(
+                spacing.saturating_mul(u32::try_from(index).unwrap_or(0)),
+                10,
+                1.0,
+                species,
+            ));
+        }
+        let mut prototypes = Vec::new();
+        let index = usize::try_from(species).unwrap_or(0);
+        if prototypes.len() <= index {
+            prototypes.resize(
+                index + 1,
+                SpeciesPrototype::new(
+                    BugColor::from_rgb(0xff, 0xff, 0xff),
+                    Health::new(1),
+                    NonZeroU32::new(1).expect("non-zero default step"),
+                ),
+            );
+        }
+        prototypes[index] = prototype;
+        PressureWavePlan::new(spawns, prototypes)
+    }
+
+    fn blank_scene(simulation: &Simulation) -> Scene {
+        Scene::new(
+            TileGridPresentation::new(4, 4, 48.0, 1, Color::from_rgb_u8(0, 0, 0))
+                .expect("valid grid"),
+            Color::from_rgb_u8(0, 0, 0),
+            query::play_mode(simulation.world()),
+        )
+    }
+
+    fn band_spawners(count: u32) -> Vec<CellCoord> {
+        (0..count).map(|column| CellCoord::new(column, 0)).collect()
+    }
+
+    #[test]
+    fn wave_clock_jumps_release_the_same_spawns_as_frame_ticks() {
+        let mut species = HashMap::new();
+        let prototype = species_proto(BugColor::from_rgb(0x21, 0x43, 0x65), 5, 500);
+        let plan = build_plan(12, 0, 200, prototype);
+        let _ = species.insert(SpeciesId::new(0), prototype);
+        let spawners = band_spawners(4);
+        let mut ticked = WaveState::new(&plan, &species, &spawners, &[], 0xc10c);
+        let mut jumped = ticked.clone();
+
+        let mut ticked_commands = Vec::new();
+        for _ in 0..64 {
+            ticked.advance(Duration::from_millis(16), &mut ticked_commands);
+        }
+        let mut jumped_commands = Vec::new();
+        jumped.advance_to_ms(1_024, &mut jumped_commands);
+        assert_eq!(jumped_commands, ticked_commands);
+        assert_eq!(jumped.clock.elapsed(), ticked.clock.elapsed());
+        assert_eq!(jumped.clock.ticks(), 1);
+
+        jumped.advance_to_ms(u64::MAX, &mut jumped_commands);
+        assert!(jumped.finished());
+        assert_eq!(jumped_commands.len(), plan.spawns().len());
+    }
+
+    #[test]
+    fn species_uses_multiple_spawners() {
+        let mut species = HashMap::new();
+        let color = BugColor::from_rgb(0x12, 0x34, 0x56);
+        let prototype = species_proto(color, 5, 500);
+        let plan = build_plan(12, 0, 200, prototype);
+        let _ = species.insert(SpeciesId::new(0), prototype);
+        let spawners = band_spawners(20);
+
+        let wave = WaveState::new(&plan, &species, &spawners, &[], 0xfeed_beef);
+        assert_eq!(wave.scheduled.len(), plan.spawns().len());
+
+        let unique: BTreeSet<_> = wave.scheduled.iter().map(|spawn| spawn.spawner).collect();
+        assert!(unique.len() >= MIN_SPAWN_BAND.min(spawners.len()));
+    }
+
+    #[test]
+    fn respects_per_spawner_cadence() {
+        let mut species = HashMap::new();
+        let prototype = species_proto(BugColor::from_rgb(1, 2, 3), 5, 400);
+        let plan = build_plan(30, 0, 10, prototype);
+        let _ = species.insert(SpeciesId::new(0), prototype);
+        let spawners = band_spawners(5);
+
+        let wave = WaveState::new(&plan, &species, &spawners, &[], 0x1234_5678);
+
+        let mut per_cell: HashMap<CellCoord, Vec<(Duration, u32)>> = HashMap::new();
+        for spawn in &wave.scheduled {
+            per_cell
+                .entry(spawn.spawner)
+                .or_default()
+                .push((spawn.at, spawn.step_ms.get()));
+        }
+
+        for entries in per_cell.values_mut() {
+            if entries.len() < 2 {
+                continue;
+            }
+            entries.sort_by_key(|entry| entry.0);
+            for window in entries.windows(2) {
+                let previous = window[0];
+                let next = window[1];
+                let required_gap = Duration::from_millis(u64::from(previous.1));
+                assert!(next.0 >= previous.0 + required_gap);
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic_band_assignment() {
+        let mut species = HashMap::new();
+        let prototype = species_proto(BugColor::from_rgb(5, 6, 7), 9, 360);
+        let plan = build_plan(16, 0, 120, prototype);
+        let _ = species.insert(SpeciesId::new(0), prototype);
+        let spawners = band_spawners(12);
+
+        let left = WaveState::new(&plan, &species, &spawners, &[], 0x77aa_bbcc);
+        let right = WaveState::new(&plan, &species, &spawners, &[], 0x77aa_bbcc);
+
+        assert_eq!(left.scheduled.len(), right.scheduled.len());
+        for (lhs, rhs) in left.scheduled.iter().zip(right.scheduled.iter()) {
+            assert_eq!(lhs.spawner, rhs.spawner);
+            assert_eq!(lhs.at, rhs.at);
+            assert_eq!(lhs.step_ms, rhs.step_ms);
+        }
+    }
+
+    #[test]
+    fn spawn_patches_limit_simultaneous_releases() {
+        let mut species = HashMap::new();
+        let prototype = species_proto(BugColor::from_rgb(3, 4, 5), 5, 500);
+        let plan = build_plan(12, 0, 0, prototype);
+        let _ = species.insert(SpeciesId::new(0), prototype);
+        let spawners = band_spawners(12);
+        let patch = SpawnPatchDescriptor::new(
+            SpawnPatchId::new(0),
+            CellCoord::new(0, 0),
+            CellRect::from_origin_and_size(CellCoord::new(0, 0), CellRectSize::new(12, 1)),
+        )
+        .with_throughput(
+            NonZeroU32::new(3).expect("non-zero limit"),
+            Duration::from_millis(300),
+        );
+
+        let unlimited = WaveState::new(&plan, &species, &spawners, &[], 0xabcd);
+        let simultaneous = unlimited
+            .scheduled
+            .iter()
+            .filter(|spawn| spawn.at.is_zero())
+            .count();
+        assert!(simultaneous > 3);
+
+        let patches = [patch];
+        let throttled = WaveState::new(&plan, &species, &spawners, &patches, 0xabcd);
+        assert_eq!(throttled.scheduled.len(), 12);
+        let mut releases: BTreeMap<Duration, usize> = BTreeMap::new();
+        for spawn in &throttled.scheduled {
+            *releases.entry(spawn.at).or_default() += 1;
+        }
+        assert!(releases.values().all(|&count| count <= 3));
+        let times: Vec<Duration> = releases.keys().copied().collect();
+        assert!(times
+            .windows(2)
+            .all(|pair| pair[1] - pair[0] >= Duration::from_millis(300)));
+        let replayed = WaveState::new(&plan, &species, &spawners, &patches, 0xabcd);
+        assert_eq!(throttled.scheduled, replayed.scheduled, "deterministic");
+    }
+
+    #[test]
+    fn laned_species_enter_through_their_patch_round_robin() {
+        let colors = [(0x11, 0x22, 0x33), (0x44, 0x55, 0x66), (0x77, 0x88, 0x99)];
+        let prototypes: Vec<_> = colors
+            .iter()
+            .map(|&(red, green, blue)| species_proto(BugColor::from_rgb(red, green, blue), 5, 400))
+            .collect();
+        let spawns = (0..12)
+            .map(|index| {
+                let species = index % 3;
+                PressureSpawnRecord::new(index * 100, 10, 1.0, species).with_lane(species)
+            })
+            .collect();
+        let plan = PressureWavePlan::new(spawns, prototypes);
+        let patch = |id, column| {
+            SpawnPatchDescriptor::new(
+                SpawnPatchId::new(id),
+                CellCoord::new(column, 0),
+                CellRect::from_origin_and_size(CellCoord::new(column, 0), CellRectSize::new(4, 1)),
+            )
+        };
+        let patches = [patch(1, 4), patch(0, 0)];
+
+        let wave = WaveState::new(&plan, &HashMap::new(), &band_spawners(8), &patches, 0x1a4e);
+        for spawn in &wave.scheduled {
+            let species = colors
+                .iter()
+                .position(|&(red, green, blue)| spawn.color == BugColor::from_rgb(red, green, blue))
+                .expect("known species");
+            let expected = if species == 1 { 4..8 } else { 0..4 };
+            assert!(
+                expected.contains(&spawn.spawner.column()),
+                "species {species} spawned at {:?}",
+                spawn.spawner
+            );
+        }
+    }
+
+    #[test]
+    fn spawn_speed_multipliers_set_the_bug_cadence() {
+        let prototype = species_proto(BugColor::from_rgb(9, 8, 7), 5, 400);
+        let spawns = vec![
+            PressureSpawnRecord::new(0, 10, 2.0, 0),
+            PressureSpawnRecord::new(100, 10, 0.5, 0),
+            PressureSpawnRecord::new(200, 10, 100.0, 0),
+        ];
+        let plan = PressureWavePlan::new(spawns, vec![prototype]);
+        let mut species = HashMap::new();
+        let _ = species.insert(SpeciesId::new(0), prototype);
+
+        let wave = WaveState::new(&plan, &species, &band_spawners(4), &[], 0x5eed);
+        let mut steps: Vec<u32> = wave
+            .scheduled
+            .iter()
+            .map(|spawn| spawn.step_ms.get())
+            .collect();
+        steps.sort_unstable();
+        assert_eq!(steps, vec![100, 200, 800], "fast spawns clamp at 4x");
+    }
+
+    #[test]
+    fn wave_state_uses_plan_prototypes_for_missing_species() {
+        let prototype_a = species_proto(BugColor::from_rgb(0x10, 0x20, 0x30), 7, 420);
+        let prototype_b = species_proto(BugColor::from_rgb(0x40, 0x50, 0x60), 9, 360);
+        let spawns = vec![
+            PressureSpawnRecord::new(0, 10, 1.0, 0),
+            PressureSpawnRecord::new(100, 12, 1.0, 1),
+        ];
+        let plan = PressureWavePlan::new(spawns, vec![prototype_a, prototype_b]);
+        let mut species = HashMap::new();
+        let _ = species.insert(SpeciesId::new(0), prototype_a);
+        let spawners = band_spawners(4);
+
+        let wave = WaveState::new(&plan, &species, &spawners, &[], 0xfeed_face);
+        let colors: HashSet<_> = wave.scheduled.iter().map(|spawn| spawn.color).collect();
+        assert!(colors.contains(&prototype_a.color()));
+        assert!(colors.contains(&prototype_b.color()));
+    }
+
+    #[test]
+    fn spawn_effect_preview_uses_ready_launch_plan() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.ready_wave_launches.clear();
+        simulation.active_wave = None;
+        simulation.active_wave_plan = None;
+
+        let tint = BugColor::from_rgb(0xaa, 0xbb, 0xcc);
+        simulation.species_prototypes.clear();
+        let prototype = species_proto(tint, 12, 360);
+        let _ = simulation
+            .species_prototypes
+            .insert(SpeciesId::new(1), prototype);
+
+        let inputs = PressureWaveInputs::new(
+            0x1234_5678,
+            LevelId::new(2),
+            WaveId::new(3),
+            DifficultyLevel::new(4),
+        );
+        let plan = build_plan(8, 1, 90, prototype);
+        simulation.ready_wave_launches.push_back(ReadyWaveLaunch {
+            inputs,
+            wave: WaveId::new(3),
+            difficulty: WaveDifficulty::Normal,
+            plan,
+            scheduled_spawns: None,
+        });
+
+        let effects = simulation.spawn_effects();
+        assert!(!effects.is_empty());
+        let expected = Color::from_rgb_u8(tint.red(), tint.green(), tint.blue());
+        let effect = effects
+            .first()
+            .expect("at least one spawn effect should be generated");
+        assert!((effect.color.red - expected.red).abs() <= f32::EPSILON);
+        assert!((effect.color.green - expected.green).abs() <= f32::EPSILON);
+        assert!((effect.color.blue - expected.blue).abs() <= f32::EPSILON);
+        assert!((effect.radius - SPAWN_EFFECT_BASE_RADIUS_CELLS).abs() <= f32::EPSILON);
+        assert_eq!(effect.duration, SPAWN_EFFECT_BASE_DURATION);
+    }
+
+    #[test]
+    fn spawn_effect_rings_grow_with_health_tier() {
+        let baseline = Health::new(10);
+        assert_eq!(health_tier(Health::new(10), baseline), 0);
+        assert_eq!(health_tier(Health::new(19), baseline), 0);
+        assert_eq!(health_tier(Health::new(20), baseline), 1);
+        assert_eq!(health_tier(Health::new(45), baseline), 2);
+        assert_eq!(
+            health_tier(Health::new(10_000), baseline),
+            SPAWN_EFFECT_MAX_TIER
+        );
+
+        let source = |tier, time_until| SpawnEffectSource {
+            cell: CellCoord::new(2, 0),
+            color: BugColor::from_rgb(0x10, 0x20, 0x30),
+            tier,
+            time_until,
+        };
+        let scout = source(0, Duration::ZERO).into_effect();
+        let tank = source(2, Duration::from_millis(400)).into_effect();
+        assert!(tank.radius > scout.radius);
+        assert!(tank.duration > scout.duration);
+        assert!((scout.progress - 1.0).abs() <= f32::EPSILON);
+        assert!((tank.progress - 0.5).abs() <= 1e-4);
+    }
+
+    #[test]
+    fn layout_import_bypasses_gold_costs() {
+        let snapshot = TowerLayoutSnapshot::decode("maze:v2:10x10:BAAAyEJDABMlAA8jAAklAA0ZABEdABUfABkjAB8lAB8hABsdABcZABMXAA8TAAkVAAkRAA0NABENABURABkTAB0XAB8RACMVACMZAB8dABsNABcLABMHAA8HAAsHAAcLAAUBAAkBAA0BABEBABUBABkBAB0BACEBACUBACUFACUJACUNACURACUdACUhACUlACEJACEFABsJABcHAB8NAB0FAAcHAA0dAAEBAAEFAAEJAAENAAEVAAkdAAEZAAEdAAchAAcZAAMRAAUlAAEj")
+            .expect("snapshot should decode");
+
+        let mut simulation = Simulation::new(
+            snapshot.columns,
+            snapshot.rows,
+            snapshot.tile_length,
+            snapshot.cells_per_tile,
+            Duration::from_millis(DEFAULT_BUG_STEP_MS),
+            Duration::from_millis(DEFAULT_BUG_SPAWN_INTERVAL_MS),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+
+        let initial_gold = query::gold(simulation.world());
+        let required_gold: u64 = snapshot
+            .towers
+            .iter()
+            .map(|tower| tower.kind.build_cost().get())
+            .sum();
+        assert!(required_gold > initial_gold.get());
+
+        simulation
+            .apply_layout_snapshot(&snapshot)
+            .expect("layout restoration should succeed");
+
+        let restored = query::towers(simulation.world());
+        assert_eq!(restored.iter().count(), snapshot.towers.len());
+        assert_eq!(query::gold(simulation.world()), initial_gold);
+    }
+
+    #[test]
+    fn initial_gold_applies_immediately() {
+        let simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            Some(Gold::new(450)),
+        );
+
+        assert_eq!(simulation.gold, Gold::new(450));
+        let emitted = simulation.last_frame_events.iter().any(
+            |event| matches!(event, Event::GoldChanged { amount } if *amount == Gold::new(450)),
+        );
+        assert!(emitted, "gold change should emit an event");
+    }
+
+    #[test]
+    fn initial_difficulty_level_applies_immediately() {
+        let simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            Some(DifficultyLevel::new(7)),
+            None,
+        );
+
+        assert_eq!(simulation.difficulty_level, DifficultyLevel::new(7));
+        let emitted = simulation
+            .last_frame_events
+            .iter()
+            .any(|event| matches!(event, Event::DifficultyLevelChanged { level } if *level == DifficultyLevel::new(7)));
+        assert!(emitted, "difficulty change should emit an event");
+    }
 
-        let towers = query::towers(&self.world);
-        let bugs = query::bug_view(&self.world);
-        let cells_per_tile = query::cells_per_tile(&self.world);
-        self.tower_targeting.handle(
-            play_mode,
-            &towers,
-            &bugs,
-            cells_per_tile,
-            &mut self.current_targets,
+    #[test]
+    fn replay_last_attack_plan_enqueues_cached_launch() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
         );
 
-        if self.current_targets.is_empty() {
-            return;
-        }
-
-        self.tower_combat.handle(
-            play_mode,
-            self.tower_cooldowns.clone(),
-            &self.current_targets,
-            &mut self.queued_commands,
+        let inputs = PressureWaveInputs::new(
+            0xfeed_beef,
+            LevelId::new(1),
+            WaveId::new(2),
+            DifficultyLevel::new(3),
         );
-    }
+        let color = BugColor::from_rgb(0xaa, 0xbb, 0xcc);
+        let prototype = species_proto(color, 5, 500);
+        let plan = build_plan(3, 0, 200, prototype);
+        let scheduled = vec![ScheduledSpawn::new(
+            Duration::from_millis(250),
+            CellCoord::new(0, 0),
+            color,
+            Health::new(5),
+            NonZeroU32::new(400).expect("non-zero step"),
+        )];
 
-    fn prepare_builder_input(&mut self) -> TowerBuilderInput {
-        let cursor_cell = self
-            .pending_input
-            .cursor_tile_space
-            .map(|tile| self.tile_position_to_cell(tile));
-        let input = TowerBuilderInput::new(
-            self.pending_input.confirm_action,
-            self.pending_input.remove_action,
-            cursor_cell,
+        let mut emitted = Vec::new();
+        simulation.apply_command(
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut emitted,
         );
-        self.pending_input.confirm_action = false;
-        self.pending_input.remove_action = false;
-        input
-    }
+        simulation.pending_events.extend(emitted);
 
-    fn compute_builder_preview(&self) -> Option<BuilderPlacementPreview> {
-        let tile_position = self.pending_input.cursor_tile_space?;
-        let origin = self.tile_position_to_cell(tile_position);
-        let kind = self.selected_tower_kind();
-        let footprint = Self::tower_footprint(kind);
-        let region = CellRect::from_origin_and_size(origin, footprint);
-        let mut placeable = self.region_is_placeable(region);
-        let rejection = self.last_placement_rejection.and_then(|rejection| {
-            if rejection.kind == kind && rejection.origin == origin {
-                Some(rejection.reason)
-            } else {
-                None
-            }
+        simulation.last_attack_plan = Some(ReplayAttackPlan {
+            inputs: inputs.clone(),
+            difficulty: WaveDifficulty::Normal,
+            plan: plan.clone(),
+            scheduled_spawns: scheduled.clone(),
         });
-        if rejection.is_some() {
-            placeable = false;
-        }
-        Some(BuilderPlacementPreview::new(
-            kind, origin, region, placeable, rejection,
-        ))
-    }
 
-    fn selected_tower_kind(&self) -> TowerKind {
-        TowerKind::Basic
-    }
+        assert!(simulation.can_replay_last_attack_plan());
 
-    fn tower_footprint(kind: TowerKind) -> CellRectSize {
-        match kind {
-            TowerKind::Basic => CellRectSize::new(4, 4),
-        }
-    }
+        simulation.replay_last_attack_plan();
 
-    fn ground_tiles(&self) -> Option<GroundSpriteTiles> {
-        if self.cells_per_tile == 0 {
-            return None;
-        }
+        let launch = simulation
+            .take_ready_wave_launch()
+            .expect("replay should enqueue launch");
+        assert_eq!(launch.inputs, inputs);
+        assert_eq!(launch.plan, plan);
+        let scheduled_from_launch = launch
+            .scheduled_spawns
+            .expect("replay launch should include scheduled spawns");
+        assert_eq!(scheduled_from_launch, scheduled);
 
-        let footprint = Self::tower_footprint(TowerKind::Basic);
-        if footprint.width() == 0 || footprint.height() == 0 {
-            return None;
-        }
+        let queued_cache = simulation
+            .queued_commands()
+            .iter()
+            .any(|command| matches!(command, Command::CachePressureWave { .. }));
+        assert!(queued_cache, "replay should queue cache command");
+    }
 
-        let base_tiles = Vec2::new(
-            footprint.width() as f32 / self.cells_per_tile as f32,
-            footprint.height() as f32 / self.cells_per_tile as f32,
+    #[test]
+    fn both_difficulty_plans_are_prefetched_before_launch() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
         );
+        simulation.handle_input(FrameInput {
+            mode_toggle: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
+        simulation.advance(Duration::from_millis(16));
+
+        let context = query::wave_seed_context(simulation.world());
+        let level_id = query::level_id(simulation.world());
+        let [normal, hard] =
+            WaveDifficulty::ALL.map(|difficulty| context.inputs(level_id, difficulty));
+        assert_ne!(normal, hard);
+        assert!(query::pressure_wave_plan(simulation.world(), &normal).is_some());
+        assert!(query::pressure_wave_plan(simulation.world(), &hard).is_some());
+        assert!(
+            simulation.queued_commands().is_empty(),
+            "nothing left to fetch"
+        );
+        let mut scene = blank_scene(&simulation);
+        simulation.populate_scene(&mut scene);
+        let composition = scene
+            .wave_composition
+            .as_ref()
+            .expect("prefetched plans are previewed");
+        assert_eq!(composition.difficulty(), WaveDifficulty::Normal);
+        let planned =
+            query::pressure_wave_plan(simulation.world(), &normal).map(|plan| plan.spawns().len());
+        assert_eq!(usize::try_from(composition.bug_count()).ok(), planned);
+
+        simulation.handle_input(FrameInput {
+            start_wave: Some(WaveDifficulty::Hard),
+            ..FrameInput::default()
+        });
+        assert!(
+            !simulation
+                .queued_commands()
+                .iter()
+                .any(|command| matches!(command, Command::GeneratePressureWave { .. })),
+            "a prefetched launch must not regenerate its plan"
+        );
+        simulation.advance(Duration::from_millis(16));
 
-        if base_tiles.x <= f32::EPSILON || base_tiles.y <= f32::EPSILON {
-            return None;
-        }
-
-        let span_tiles = base_tiles * GROUND_TILE_MULTIPLIER;
-        visuals::ground_sprite_tiles(
-            span_tiles,
-            self.cells_per_tile,
-            SpriteKey::GroundGrass,
-            GroundKind::Grass,
-        )
+        assert_eq!(
+            simulation.active_wave_plan.as_ref(),
+            query::pressure_wave_plan(simulation.world(), &hard)
+        );
+        simulation.populate_scene(&mut scene);
+        assert!(scene.wave_composition.is_none(), "running waves hide it");
     }
 
-    fn selected_tower_footprint_tiles(&self) -> Vec2 {
-        if self.cells_per_tile == 0 {
-            return Vec2::ZERO;
+    #[test]
+    fn autoplay_builds_a_maze_and_launches_a_wave() {
+        let mut simulation = Simulation::new(
+            10,
+            10,
+            48.0,
+            4,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.enable_autoplay();
+        for _ in 0..4 {
+            simulation.advance(Duration::from_millis(16));
         }
 
-        let footprint = Self::tower_footprint(self.selected_tower_kind());
-        Vec2::new(
-            footprint.width() as f32 / self.cells_per_tile as f32,
-            footprint.height() as f32 / self.cells_per_tile as f32,
-        )
+        assert_eq!(query::towers(simulation.world()).iter().count(), 10);
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Attack);
+        assert!(
+            simulation.active_wave.is_some(),
+            "the bot launches a wave once it spent its gold"
+        );
+        assert!(simulation.autoplay_outcomes().is_empty());
     }
 
-    fn tile_position_to_cell(&self, position: TileSpacePosition) -> CellCoord {
-        let cells_per_tile = self.cells_per_tile.max(1);
-        let column_cells = (position.column_in_tiles() * cells_per_tile as f32).round() as u32;
-        let row_cells = (position.row_in_tiles() * cells_per_tile as f32).round() as u32;
-        let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS.saturating_add(column_cells);
-        let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS.saturating_add(row_cells);
-        CellCoord::new(column, row)
+    #[test]
+    fn won_waves_and_kills_count_towards_the_score() {
+        let mut simulation = Simulation::new(
+            10,
+            10,
+            48.0,
+            4,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.enable_autoplay();
+        autoplay::play_waves(
+            &mut simulation,
+            2,
+            |simulation| simulation.advance(autoplay::FRAME),
+            |_, _, _| Ok(()),
+        )
+        .expect("both waves resolve");
+
+        let wins = simulation
+            .autoplay_outcomes()
+            .iter()
+            .filter(|outcome| **outcome == RoundOutcome::Win)
+            .count();
+        assert!(wins > 0, "the bot's maze holds at least one wave");
+        assert_eq!(simulation.tally.waves_cleared, wins as u64);
+        assert!(simulation.tally.kills > 0);
     }
 
-    fn world_position_to_cell(&self, position: Vec2) -> CellCoord {
-        let tile_grid = query::tile_grid(&self.world);
-        let cells_per_tile = self.cells_per_tile.max(1);
-        let tile_length = tile_grid.tile_length();
-        if tile_length <= f32::EPSILON {
-            return CellCoord::new(
-                TileGridPresentation::SIDE_BORDER_CELL_LAYERS,
-                TileGridPresentation::TOP_BORDER_CELL_LAYERS,
+    #[test]
+    fn fast_forward_matches_normal_speed_at_every_rendered_frame() {
+        let autoplay = || {
+            let mut simulation = Simulation::new(
+                10,
+                10,
+                48.0,
+                4,
+                Duration::from_millis(400),
+                Duration::from_millis(1_000),
+                VisualStyle::Primitives,
+                None,
+                None,
             );
+            simulation.enable_autoplay();
+            simulation
+        };
+        let state = |simulation: &Simulation| {
+            let world = simulation.world();
+            format!(
+                "{:?} {:?} {:?} {:?}",
+                query::bug_view(world).iter().collect::<Vec<_>>(),
+                query::tower_cooldowns(world),
+                query::projectiles(world).collect::<Vec<_>>(),
+                query::gold(world),
+            )
+        };
+        let mut normal = autoplay();
+        let mut fast = autoplay();
+        fast.set_speed(BATCHED_TICK_SPEED);
+        let dt = Duration::from_millis(16);
+        let mut fired = false;
+        for _ in 0..60 {
+            for _ in 0..BATCHED_TICK_SPEED {
+                normal.advance(dt);
+            }
+            fast.fast_forward(dt);
+            fired |= query::projectiles(fast.world()).next().is_some();
+            assert_eq!(state(&normal), state(&fast));
         }
+        assert!(fired, "towers should shoot while fast-forwarding");
+    }
 
-        let cell_length = tile_length / cells_per_tile as f32;
-        if cell_length <= f32::EPSILON {
-            return CellCoord::new(
-                TileGridPresentation::SIDE_BORDER_CELL_LAYERS,
-                TileGridPresentation::TOP_BORDER_CELL_LAYERS,
-            );
-        }
+    #[test]
+    fn spectate_mode_ignores_input_and_launches_waves() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.enable_spectate();
 
-        let column_cells = Self::world_axis_to_cell_index(
-            position.x,
-            tile_grid.columns().get(),
-            cells_per_tile,
-            cell_length,
+        simulation.handle_input(FrameInput {
+            mode_toggle: true,
+            replay_wave: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
+
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Attack);
+        assert!(
+            simulation.pending_wave_launch.is_some() || !simulation.ready_wave_launches.is_empty(),
+            "spectate mode should launch a wave without player input"
         );
-        let row_cells = Self::world_axis_to_cell_index(
-            position.y,
-            tile_grid.rows().get(),
-            cells_per_tile,
-            cell_length,
+    }
+
+    #[test]
+    fn sandbox_right_click_spawns_bugs_at_the_cursor() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
         );
+        simulation.enable_sandbox();
+        simulation.handle_input(FrameInput {
+            mode_toggle: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Attack);
+
+        let cursor = Vec2::new(72.0, 72.0);
+        simulation.handle_input(FrameInput {
+            cursor_world_space: Some(cursor),
+            remove_action: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
 
-        let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS.saturating_add(column_cells);
-        let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS.saturating_add(row_cells);
-        CellCoord::new(column, row)
+        let cell = simulation.world_position_to_cell(cursor);
+        let spawned: Vec<CellCoord> = simulation
+            .last_frame_events
+            .iter()
+            .filter_map(|event| match event {
+                Event::BugSpawned { cell, .. } => Some(*cell),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(spawned, vec![cell]);
+        assert!(query::is_sandbox_session(simulation.world()));
     }
 
-    fn world_axis_to_cell_index(
-        value: f32,
-        tiles: u32,
-        cells_per_tile: u32,
-        cell_length: f32,
-    ) -> u32 {
-        if tiles == 0 || cells_per_tile == 0 || cell_length <= f32::EPSILON {
-            return 0;
-        }
+    #[test]
+    fn sandbox_sessions_replace_the_wave_seed_and_prefetch_its_plans() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let reroll = FrameInput {
+            set_wave_seed: Some(1_234),
+            ..FrameInput::default()
+        };
+        simulation.handle_input(reroll.clone());
+        simulation.advance(Duration::from_millis(16));
+        assert_ne!(
+            query::wave_seed_context(simulation.world()).global_seed(),
+            1_234,
+            "only sandbox sessions may replace the seed"
+        );
 
-        let total_cells = tiles.saturating_mul(cells_per_tile);
-        if total_cells == 0 {
-            return 0;
-        }
+        simulation.enable_sandbox();
+        simulation.handle_input(reroll);
+        simulation.advance(Duration::from_millis(16));
+        simulation.advance(Duration::from_millis(16));
+        let context = query::wave_seed_context(simulation.world());
+        assert_eq!(context.global_seed(), 1_234);
+        let inputs = context.inputs(query::level_id(simulation.world()), WaveDifficulty::Normal);
+        assert!(query::pressure_wave_plan(simulation.world(), &inputs).is_some());
+
+        let mut scene = blank_scene(&simulation);
+        simulation.populate_scene(&mut scene);
+        assert_eq!(scene.wave_seed, Some(1_234));
+    }
 
-        let index = (value / cell_length).floor();
-        if !index.is_finite() {
-            return 0;
+    #[test]
+    fn losing_a_round_records_a_share_code_of_the_run() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.configure_wave_seed(77);
+        simulation.enable_tower_heat();
+        simulation.handle_input(FrameInput {
+            mode_toggle: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
+        simulation.handle_input(FrameInput {
+            start_wave: Some(WaveDifficulty::Normal),
+            ..FrameInput::default()
+        });
+        for _ in 0..2_000 {
+            if simulation.run_summary.is_some() {
+                break;
+            }
+            simulation.advance(Duration::from_millis(16));
         }
 
-        let max_index = (total_cells - 1) as f32;
-        if index < 0.0 {
-            return 0;
-        }
+        let summary = simulation
+            .run_summary
+            .clone()
+            .expect("the leak ends the run");
+        assert_eq!(summary.seed, 77);
+        assert_eq!(summary.wave, 1);
+        let elapsed = query::elapsed(simulation.world());
+        assert!(elapsed > Duration::ZERO, "the wave ran before the leak");
+        assert_eq!(summary.elapsed.as_secs(), elapsed.as_secs());
+        assert!(summary.rules.tower_heat);
+        assert_eq!((summary.layout.columns, summary.layout.rows), (4, 4));
+        assert_eq!(
+            RunSummary::decode(&summary.encode()).expect("code decodes"),
+            summary
+        );
+    }
 
-        if index > max_index {
-            return total_cells - 1;
+    #[test]
+    fn run_summaries_score_a_point_per_minute_survived() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.tally.kills = 3;
+        simulation.handle_input(FrameInput {
+            mode_toggle: true,
+            ..FrameInput::default()
+        });
+        for _ in 0..(150_000 / 16) {
+            simulation.advance(Duration::from_millis(16));
         }
+        simulation.record_run_summary();
 
-        index as u32
+        let summary = simulation.run_summary.clone().expect("summary recorded");
+        assert_eq!(summary.elapsed, Duration::from_secs(150));
+        assert_eq!(summary.score.get(), 3 + 2, "kills plus two full minutes");
     }
 
-    fn region_is_placeable(&self, region: CellRect) -> bool {
-        let size = region.size();
-        if size.width() == 0 || size.height() == 0 {
-            return false;
-        }
-
-        let origin = region.origin();
-        for column_offset in 0..size.width() {
-            let Some(column) = origin.column().checked_add(column_offset) else {
-                return false;
-            };
-            for row_offset in 0..size.height() {
-                let Some(row) = origin.row().checked_add(row_offset) else {
-                    return false;
-                };
-                let cell = CellCoord::new(column, row);
-                if query::is_cell_blocked(&self.world, cell) {
-                    return false;
-                }
+    #[test]
+    fn game_rules_arrive_with_the_first_frame_and_lost_runs_award_prestige() {
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let starting_gold = simulation.gold;
+        simulation.handle_input(FrameInput {
+            game_rules: Some(GameRules {
+                bonus_gold: Gold::new(50),
+                base_health: 2,
+            }),
+            mode_toggle: true,
+            ..FrameInput::default()
+        });
+        simulation.advance(Duration::from_millis(16));
+        assert_eq!(simulation.gold, starting_gold.saturating_add(Gold::new(50)));
+        let mut scene = blank_scene(&simulation);
+        simulation.populate_scene(&mut scene);
+        assert_eq!(scene.base_health, Some(BugHealthPresentation::new(2, 2)));
+
+        simulation.tally.kills = 25;
+        simulation.handle_input(FrameInput {
+            start_wave: Some(WaveDifficulty::Normal),
+            ..FrameInput::default()
+        });
+        for _ in 0..2_000 {
+            if simulation.run_summary.is_some() {
+                break;
             }
+            simulation.advance(Duration::from_millis(16));
         }
 
-        true
+        assert!(simulation.run_summary.is_some(), "the leaks end the run");
+        simulation.populate_scene(&mut scene);
+        assert_eq!(scene.prestige_earned, 2);
     }
 
-    fn builder_preview(&self) -> Option<BuilderPlacementPreview> {
-        self.builder_preview
-    }
+    #[test]
+    fn scripted_input_drives_build_launch_and_pause() {
+        let mut simulation = Simulation::new(
+            8,
+            8,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let mut scene = blank_scene(&simulation);
+        let frame = Duration::from_millis(16);
+        let mut run = |script: &mut ScriptedFrameInput, simulation: &mut Simulation| {
+            let frames = script.remaining();
+            run_frames(script, &mut scene, frame, frames, |tick, input, scene| {
+                simulation.run_frame(tick, input, scene)
+            });
+        };
 
-    #[cfg(test)]
-    fn bug_step_ms(&self) -> u32 {
-        use std::convert::TryFrom;
+        let hover = FrameInput {
+            cursor_world_space: Some(Vec2::new(96.0, 96.0)),
+            cursor_tile_space: Some(TileSpacePosition::from_indices(2, 2)),
+            ..FrameInput::default()
+        };
+        let mut build = ScriptedFrameInput::new([
+            hover.clone(),
+            FrameInput {
+                confirm_action: true,
+                ..hover
+            },
+        ])
+        .idle(1);
+        run(&mut build, &mut simulation);
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Builder);
+        assert_eq!(query::towers(simulation.world()).iter().count(), 1);
+
+        let mut launch = ScriptedFrameInput::default()
+            .then(FrameInput {
+                mode_toggle: true,
+                ..FrameInput::default()
+            })
+            .then(FrameInput {
+                start_wave: Some(WaveDifficulty::Normal),
+                ..FrameInput::default()
+            })
+            .idle(30);
+        run(&mut launch, &mut simulation);
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Attack);
+        assert!(
+            simulation.active_wave.is_some(),
+            "the launched wave should be running"
+        );
 
-        u32::try_from(self.bug_step_duration.as_millis()).unwrap_or(u32::MAX)
+        let mut pause = ScriptedFrameInput::default()
+            .then(FrameInput {
+                mode_toggle: true,
+                ..FrameInput::default()
+            })
+            .idle(1);
+        run(&mut pause, &mut simulation);
+        assert_eq!(query::play_mode(simulation.world()), PlayMode::Builder);
+        let paused_at = query::elapsed(simulation.world());
+        run(&mut ScriptedFrameInput::default().idle(10), &mut simulation);
+        assert_eq!(query::elapsed(simulation.world()), paused_at);
+        assert_eq!(scene.play_mode, PlayMode::Builder);
     }
 
-    #[cfg(test)]
-    fn bug_step_duration(&self) -> Duration {
-        self.bug_step_duration
-    }
+    #[test]
+    fn hotbar_selection_feeds_the_placement_preview() {
+        let mut simulation = Simulation::new(
+            8,
+            8,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let mut scene = blank_scene(&simulation);
+        let hover = FrameInput {
+            cursor_world_space: Some(Vec2::new(96.0, 96.0)),
+            cursor_tile_space: Some(TileSpacePosition::from_indices(2, 2)),
+            ..FrameInput::default()
+        };
+        let mut script = ScriptedFrameInput::new([
+            FrameInput {
+                select_tower_kind: Some(TowerKind::Barrier),
+                ..hover.clone()
+            },
+            hover,
+        ]);
+        let frames = script.remaining();
+        run_frames(
+            &mut script,
+            &mut scene,
+            Duration::from_millis(16),
+            frames,
+            |tick, input, scene| simulation.run_frame(tick, input, scene),
+        );
 
-    #[cfg(test)]
-    fn last_frame_events(&self) -> &[Event] {
-        &self.last_frame_events
+        assert_eq!(scene.selected_tower_kind, Some(TowerKind::Barrier));
+        assert_eq!(
+            scene.tower_preview.map(|preview| preview.kind),
+            Some(TowerKind::Barrier),
+            "the picked kind stays selected after the frame it was picked on"
+        );
     }
 
-    #[cfg(test)]
-    #[cfg(test)]
-    fn tower_feedback(&self) -> Option<TowerInteractionFeedback> {
-        self.tower_feedback
+    #[test]
+    fn frames_run_the_fixed_updates_due_and_keep_input_between_updates() {
+        let mut simulation = Simulation::new(
+            8,
+            8,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let mut scene = blank_scene(&simulation);
+        simulation.apply_command(
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut Vec::new(),
+        );
+        let step = Duration::from_millis(10);
+        let idle = FrameTick {
+            step,
+            steps: 0,
+            alpha: 0.5,
+        };
+        let picked = FrameInput {
+            select_tower_kind: Some(TowerKind::Barrier),
+            ..FrameInput::default()
+        };
+        let _ = simulation.run_frame(idle, picked, &mut scene);
+        assert_eq!(query::elapsed(simulation.world()), Duration::ZERO);
+        assert_eq!(simulation.selected_tower_kind(), TowerKind::Barrier);
+        assert_eq!(simulation.motion_lead, Duration::from_millis(5));
+
+        let catch_up = FrameTick {
+            step,
+            steps: 3,
+            alpha: 0.0,
+        };
+        let _ = simulation.run_frame(catch_up, FrameInput::default(), &mut scene);
+        assert_eq!(query::elapsed(simulation.world()), catch_up.simulated());
     }
 
-    #[cfg(test)]
-    fn current_targets(&self) -> &[TowerTarget] {
-        &self.current_targets
+    #[test]
+    fn repl_steps_the_simulation_and_prints_world_dumps() {
+        let mut simulation = Simulation::new(
+            8,
+            8,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let mut scene = blank_scene(&simulation);
+        let script = "status\nattack\nwave\nstep 500\nbugs\nfly\nstep soon\nquit\ngrid\n";
+        let mut output = Vec::new();
+        repl::run(&mut simulation, &mut scene, script.as_bytes(), &mut output).expect("repl");
+
+        let output = String::from_utf8(output).expect("utf-8 output");
+        assert!(output.contains("mode: Builder"));
+        assert!(output.contains("mode: Attack"));
+        assert!(output.contains("wave: #"));
+        assert!(output.contains("advanced 500ms"));
+        assert!(output.contains("unknown command `fly`"));
+        assert!(output.contains("invalid duration `soon`"));
+        assert!(!output.contains("####"), "commands after quit are ignored");
+        assert!(query::elapsed(simulation.world()) >= Duration::from_millis(500));
     }
 
-    #[cfg(test)]
-    fn tower_cooldowns(&self) -> &TowerCooldownView {
-        &self.tower_cooldowns
-    }
+    #[test]
+    fn bridge_tiles_cover_every_cell_of_the_tile() {
+        assert_eq!("1, 2".parse::<TileArg>(), Ok(TileArg { column: 1, row: 2 }));
+        assert!("1x2".parse::<TileArg>().is_err());
 
-    #[cfg(test)]
-    fn projectiles(&self) -> &[ProjectileSnapshot] {
-        &self.projectiles
+        let mut simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            2,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        simulation.configure_bridges(&[TileArg { column: 1, row: 2 }]);
+        simulation.configure_void_tiles(&[TileArg { column: 1, row: 2 }]);
+        simulation.advance(Duration::from_millis(16));
+
+        let column = TileGridPresentation::SIDE_BORDER_CELL_LAYERS + 2;
+        let row = TileGridPresentation::TOP_BORDER_CELL_LAYERS + 4;
+        assert_eq!(
+            query::bridge_cells(simulation.world()),
+            vec![
+                CellCoord::new(column, row),
+                CellCoord::new(column + 1, row),
+                CellCoord::new(column, row + 1),
+                CellCoord::new(column + 1, row + 1),
+            ]
+        );
+        assert_eq!(
+            query::void_cells(simulation.world()),
+            query::bridge_cells(simulation.world())
+        );
     }
 
-    #[cfg(test)]
-    fn queued_commands(&self) -> &[Command] {
-        &self.queued_commands
+    #[test]
+    fn ready_flash_starts_when_reload_completes_and_fades() {
+        let mut flash = ReadyFlash::default();
+        flash.observe(Duration::from_millis(300), Duration::ZERO);
+        assert_eq!(flash.intensity(), 0.0);
+
+        flash.observe(Duration::ZERO, Duration::from_millis(300));
+        assert!((flash.intensity() - 1.0).abs() <= f32::EPSILON);
+
+        flash.observe(Duration::ZERO, TOWER_READY_FLASH / 2);
+        assert!((flash.intensity() - 0.5).abs() <= 1e-3);
+
+        flash.observe(Duration::ZERO, TOWER_READY_FLASH);
+        assert_eq!(flash.intensity(), 0.0);
     }
 
-    fn flush_queued_commands(&mut self) {
-        if self.queued_commands.is_empty() {
-            return;
-        }
+    #[test]
+    fn push_tower_cooldowns_reports_reload_progress() {
+        let tower = TowerId::new(4);
+        let region = CellRect::from_origin_and_size(CellCoord::new(2, 2), CellRectSize::new(4, 4));
+        let towers = TowerView::from_snapshots(vec![maze_defence_core::TowerSnapshot {
+            id: tower,
+            kind: TowerKind::Basic,
+            region,
+            rotation: maze_defence_core::TowerRotation::Deg0,
+            targeting: maze_defence_core::TargetingPolicy::Nearest,
+            upgrades: 0,
+        }]);
+        let cooldown_ms = u64::from(TowerKind::Basic.fire_cooldown_ms());
+        let cooldowns =
+            TowerCooldownView::from_snapshots(vec![maze_defence_core::TowerCooldownSnapshot {
+                tower,
+                kind: TowerKind::Basic,
+                ready_in: Duration::from_millis(cooldown_ms / 4),
+                heat: None,
+            }]);
 
-        let mut emitted = Vec::new();
-        let mut queued = std::mem::take(&mut self.queued_commands);
-        for command in queued.drain(..) {
-            let resolves_round = matches!(command, Command::ResolveRound { .. });
-            self.apply_command(command, &mut emitted);
-            if resolves_round {
-                self.pending_outcome_command = false;
-                self.awaiting_round_resolution = false;
-            }
-            self.pending_events.append(&mut emitted);
-        }
-        self.queued_commands = queued;
+        let simulation = Simulation::new(
+            4,
+            4,
+            48.0,
+            1,
+            Duration::from_millis(400),
+            Duration::from_millis(1_000),
+            VisualStyle::Primitives,
+            None,
+            None,
+        );
+        let mut scene = blank_scene(&simulation);
+
+        push_tower_cooldowns(
+            &mut scene,
+            &cooldowns,
+            &towers,
+            &TowerContent::default(),
+            |_| 0.25,
+        );
+
+        assert_eq!(scene.tower_cooldowns.len(), 1);
+        let indicator = scene.tower_cooldowns[0];
+        assert_eq!(indicator.tower, tower);
+        assert_eq!(indicator.region, region);
+        assert!((indicator.progress - 0.75).abs() <= 1e-3);
+        assert!(!indicator.is_ready());
+        assert!((indicator.ready_flash - 0.25).abs() <= f32::EPSILON);
     }
-}
 
-impl Drop for Simulation {
-    fn drop(&mut self) {
-        if cfg!(test) {
-            return;
-        }
+    #[test]
+    fn pick_hovered_bug_selects_nearest_bug_within_radius() {
+        let health = BugHealthPresentation::new(3, 3);
+        let color = Color::from_rgb_u8(255, 0, 0);
+        let bugs = vec![
+            BugPresentation::new_circle(BugId::new(1), Vec2::new(2.5, 2.5), color, health),
+            BugPresentation::new_circle(BugId::new(2), Vec2::new(3.5, 2.5), color, health),
+            BugPresentation::new_circle(BugId::new(3), Vec2::new(8.5, 8.5), color, health),
+        ];
 
-        let snapshot = self.capture_layout_snapshot();
-        let encoded = snapshot.encode();
-        println!("{encoded}");
+        let picked = pick_hovered_bug(&bugs, Vec2::new(3.2, 2.6), BUG_PICK_RADIUS_CELLS)
+            .expect("a bug lies within the pick radius");
+        assert_eq!(picked.id, BugId::new(2));
+
+        assert!(pick_hovered_bug(&bugs, Vec2::new(6.0, 6.0), BUG_PICK_RADIUS_CELLS).is_none());
+
+        let tie = pick_hovered_bug(&bugs, Vec2::new(3.0, 2.5), BUG_PICK_RADIUS_CELLS)
+            .expect("both bugs are equidistant");
+        assert_eq!(tie.id, BugId::new(1));
     }
 }
@@ -0,0 +1,99 @@
+//! Wave outcome records written as JSON lines for balancing dashboards.
+
+use std::{fmt, io::Write};
+
+use anyhow::Result;
+use maze_defence_core::{Event, WaveOutcome};
+
+/// Writes every published [`WaveOutcome`] as one line of JSON.
+pub(crate) struct WaveOutcomeLog {
+    output: Box<dyn Write>,
+}
+
+impl WaveOutcomeLog {
+    /// Creates a log writing its records to `output`.
+    pub(crate) fn new(output: impl Write + 'static) -> Self {
+        Self {
+            output: Box::new(output),
+        }
+    }
+
+    /// Writes the outcome records among `events` and flushes them.
+    pub(crate) fn record(&mut self, events: &[Event]) -> Result<()> {
+        let mut recorded = false;
+        for event in events {
+            if let Event::WaveOutcomeRecorded { outcome } = event {
+                write_record(&mut self.output, outcome)?;
+                recorded = true;
+            }
+        }
+        if recorded {
+            self.output.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for WaveOutcomeLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaveOutcomeLog").finish_non_exhaustive()
+    }
+}
+
+fn write_record(output: &mut impl Write, outcome: &WaveOutcome) -> Result<()> {
+    serde_json::to_writer(&mut *output, outcome)?;
+    writeln!(output)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{
+        DifficultyLevel, Gold, LevelId, RoundOutcome, SpeciesCombat, SpeciesSpawns, TowerKind,
+        TowerKindCount, WaveDifficulty, WaveId,
+    };
+
+    #[test]
+    fn outcomes_are_written_one_json_object_per_line() {
+        let outcome = WaveOutcome {
+            wave: WaveId::new(1),
+            level: LevelId::new(0),
+            game_seed: 9,
+            difficulty: WaveDifficulty::Normal,
+            difficulty_level: DifficultyLevel::new(2),
+            outcome: RoundOutcome::Loss,
+            duration_ms: 1_500,
+            leaks: 3,
+            gold_earned: Gold::new(4),
+            composition: vec![SpeciesSpawns {
+                species: 0,
+                count: 6,
+                total_hp: 30,
+            }],
+            towers: vec![TowerKindCount {
+                kind: TowerKind::Basic,
+                count: 2,
+            }],
+            species_combat: vec![SpeciesCombat {
+                species: 0,
+                bugs: 6,
+                killed: 3,
+                average_lifetime_ms: 9_500,
+                average_cells_travelled: 14,
+                damage_absorbed: 27,
+                damage_per_second: 1,
+            }],
+        };
+        let mut output = Vec::new();
+        write_record(&mut output, &outcome).expect("write to memory");
+        write_record(&mut output, &outcome).expect("write to memory");
+
+        let text = String::from_utf8(output).expect("utf-8 output");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: WaveOutcome = serde_json::from_str(lines[0]).expect("parse record");
+        assert_eq!(parsed, outcome);
+        assert!(lines[0].contains("\"leaks\":3"), "{}", lines[0]);
+    }
+}
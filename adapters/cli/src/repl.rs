@@ -0,0 +1,165 @@
+//! Headless read-eval-print loop that steps the simulation and prints world dumps.
+
+use std::{
+    io::{BufRead, Write},
+    time::Duration,
+};
+
+use anyhow::Result;
+use maze_defence_core::{PlayMode, WaveDifficulty};
+use maze_defence_rendering::{FrameInput, FrameTick, Scene};
+use maze_defence_world::{inspect, query};
+
+use crate::Simulation;
+
+/// Frame length used when stepping the simulation, matching a 60 Hz renderer.
+const FRAME: Duration = Duration::from_millis(16);
+/// Simulated time advanced by `step` when no duration is given.
+const DEFAULT_STEP: Duration = Duration::from_millis(100);
+
+const HELP: &str = "\
+commands:
+  step [MS]            advance the simulation by MS milliseconds (default 100)
+  build | attack       switch the play mode
+  wave [normal|hard]   launch the next wave
+  status               clock, gold, difficulty and wave progress
+  seed [SEED]          show the wave seed, or replace it in a sandbox session
+  grid                 ASCII map of the cell grid
+  bugs                 table of living bugs
+  towers               table of placed towers
+  dump                 every section above
+  help                 this message
+  quit                 leave the REPL";
+
+/// Reads commands from `input` until `quit` or end of input, writing replies to `output`.
+///
+/// The simulation receives exactly the frames a renderer would send, so state observed
+/// here matches an interactive session driven by the same inputs.
+pub(crate) fn run(
+    simulation: &mut Simulation,
+    scene: &mut Scene,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> Result<()> {
+    writeln!(output, "Maze Defence REPL; type `help` for commands.")?;
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+        let reply = match (words.next(), words.next()) {
+            (None, _) => String::new(),
+            (Some("quit" | "exit"), _) => break,
+            (Some("help"), _) => HELP.to_owned(),
+            (Some("step"), duration) => match parse_step(duration) {
+                Ok(duration) => {
+                    step(simulation, scene, duration);
+                    format!("advanced {}ms", duration.as_millis())
+                }
+                Err(message) => message,
+            },
+            (Some(mode @ ("build" | "attack")), _) => {
+                let mode = if mode == "build" {
+                    PlayMode::Builder
+                } else {
+                    PlayMode::Attack
+                };
+                if query::play_mode(simulation.world()) != mode {
+                    frame(
+                        simulation,
+                        scene,
+                        FrameInput {
+                            mode_toggle: true,
+                            ..FrameInput::default()
+                        },
+                    );
+                }
+                format!("mode: {:?}", query::play_mode(simulation.world()))
+            }
+            (Some("wave"), difficulty) => match parse_difficulty(difficulty) {
+                Ok(difficulty) => {
+                    frame(
+                        simulation,
+                        scene,
+                        FrameInput {
+                            start_wave: Some(difficulty),
+                            ..FrameInput::default()
+                        },
+                    );
+                    inspect::wave_status(simulation.world())
+                }
+                Err(message) => message,
+            },
+            (Some("seed"), seed) => match parse_seed(seed) {
+                Ok(seed) => {
+                    if seed.is_some() {
+                        frame(
+                            simulation,
+                            scene,
+                            FrameInput {
+                                set_wave_seed: seed,
+                                ..FrameInput::default()
+                            },
+                        );
+                    }
+                    let seed = query::wave_seed_context(simulation.world()).global_seed();
+                    format!("wave seed: {seed}")
+                }
+                Err(message) => message,
+            },
+            (Some("status"), _) => inspect::wave_status(simulation.world()),
+            (Some("grid"), _) => inspect::grid(simulation.world()),
+            (Some("bugs"), _) => inspect::bugs(simulation.world()),
+            (Some("towers"), _) => inspect::towers(simulation.world()),
+            (Some("dump"), _) => inspect::dump(simulation.world()),
+            (Some(command), _) => format!("unknown command `{command}`; type `help`"),
+        };
+        if !reply.is_empty() {
+            writeln!(output, "{}", reply.trim_end())?;
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    writeln!(output)?;
+    Ok(())
+}
+
+fn parse_step(duration: Option<&str>) -> Result<Duration, String> {
+    duration.map_or(Ok(DEFAULT_STEP), |milliseconds| {
+        milliseconds
+            .parse()
+            .map(Duration::from_millis)
+            .map_err(|error| format!("invalid duration `{milliseconds}`: {error}"))
+    })
+}
+
+fn parse_seed(seed: Option<&str>) -> Result<Option<u64>, String> {
+    seed.map(|text| {
+        text.parse()
+            .map_err(|error| format!("invalid seed `{text}`: {error}"))
+    })
+    .transpose()
+}
+
+fn parse_difficulty(difficulty: Option<&str>) -> Result<WaveDifficulty, String> {
+    match difficulty {
+        None | Some("normal") => Ok(WaveDifficulty::Normal),
+        Some("hard") => Ok(WaveDifficulty::Hard),
+        Some(other) => Err(format!("unknown difficulty `{other}`; use normal or hard")),
+    }
+}
+
+/// Advances the simulation by `duration` in renderer-sized frames.
+fn step(simulation: &mut Simulation, scene: &mut Scene, duration: Duration) {
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        let dt = remaining.min(FRAME);
+        let _ = simulation.run_frame(FrameTick::single(dt), FrameInput::default(), scene);
+        remaining -= dt;
+    }
+}
+
+/// Runs a single frame carrying `input`.
+fn frame(simulation: &mut Simulation, scene: &mut Scene, input: FrameInput) {
+    let _ = simulation.run_frame(FrameTick::single(FRAME), input, scene);
+}
@@ -0,0 +1,240 @@
+//! End-of-run summaries shared as compact share codes.
+
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+use crate::{
+    layout_transfer::{
+        decode_varint, decode_varint_u64, encode_varint, encode_varint_u64, read_u64, read_u8,
+        LayoutTransferError, TowerLayoutSnapshot,
+    },
+    score::Score,
+};
+
+/// Prefix of every share code.
+const SHARE_CODE_DOMAIN: &str = "run";
+/// Version of the share code payload.
+const SHARE_CODE_VERSION: &str = "v2";
+
+const HEAT_FLAG: u8 = 0x01;
+const FORMATIONS_FLAG: u8 = 0x02;
+const SANDBOX_FLAG: u8 = 0x04;
+const LOOP_AROUND_FLAG: u8 = 0x08;
+const NESTS_FLAG: u8 = 0x10;
+const ACCURACY_FLAG: u8 = 0x20;
+const LINE_OF_SIGHT_FLAG: u8 = 0x40;
+
+/// Outcome of a run that ended in defeat, with what another player needs to retry it.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RunSummary {
+    /// Global seed the run's waves were generated from.
+    pub(crate) seed: u64,
+    /// Difficulty level the run started at.
+    pub(crate) difficulty: u32,
+    /// Optional rules the run was played with.
+    pub(crate) rules: RunRules,
+    /// Score the run earned before the defeat.
+    pub(crate) score: Score,
+    /// One-based number of the wave the run ended on.
+    pub(crate) wave: u32,
+    /// Simulated time the run lasted, in whole seconds.
+    pub(crate) elapsed: Duration,
+    /// Maze standing when the run ended, enough to draw a thumbnail or rebuild it.
+    pub(crate) layout: TowerLayoutSnapshot,
+}
+
+/// Optional rules that change how a run plays out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RunRules {
+    /// Whether towers built heat while firing.
+    pub(crate) tower_heat: bool,
+    /// Whether walls and other towers blocked shots.
+    pub(crate) line_of_sight: bool,
+    /// Whether bursts spawned as formations.
+    pub(crate) formations: bool,
+    /// Whether any sandbox toggle was enabled.
+    pub(crate) sandbox: bool,
+    /// Health percentage kept by bugs looping around, when exits looped.
+    pub(crate) loop_around: Option<u32>,
+    /// Health of every nest, when spawners were destructible.
+    pub(crate) nest_health: Option<u32>,
+    /// Cells a target could drift and still be hit, when shots could miss.
+    pub(crate) accuracy_drift_cells: Option<u32>,
+}
+
+impl RunSummary {
+    /// Encodes the summary as a single-line `run:v2:` share code.
+    pub(crate) fn encode(&self) -> String {
+        let mut payload = Vec::with_capacity(32 + self.layout.towers.len() * 5);
+        payload.extend(self.seed.to_le_bytes());
+        encode_varint(self.difficulty, &mut payload);
+        encode_varint_u64(self.score.get(), &mut payload);
+        encode_varint(self.wave, &mut payload);
+        let elapsed_secs = u32::try_from(self.elapsed.as_secs()).unwrap_or(u32::MAX);
+        encode_varint(elapsed_secs, &mut payload);
+        self.rules.write(&mut payload);
+        encode_varint(self.layout.columns, &mut payload);
+        encode_varint(self.layout.rows, &mut payload);
+        self.layout.write_payload(&mut payload);
+        format!(
+            "{SHARE_CODE_DOMAIN}:{SHARE_CODE_VERSION}:{}",
+            URL_SAFE_NO_PAD.encode(payload)
+        )
+    }
+
+    /// Decodes a share code produced by [`Self::encode`].
+    pub(crate) fn decode(value: &str) -> Result<Self, LayoutTransferError> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(LayoutTransferError::EmptyPayload);
+        }
+
+        let mut parts = trimmed.splitn(3, ':');
+        let domain = parts.next().ok_or(LayoutTransferError::MissingPrefix)?;
+        let version = parts.next().ok_or(LayoutTransferError::MissingVersion)?;
+        let payload = parts.next().ok_or(LayoutTransferError::MissingPayload)?;
+        if domain != SHARE_CODE_DOMAIN {
+            return Err(LayoutTransferError::InvalidPrefix(domain.to_owned()));
+        }
+        if version != SHARE_CODE_VERSION {
+            return Err(LayoutTransferError::UnsupportedVersion(version.to_owned()));
+        }
+
+        let bytes = URL_SAFE_NO_PAD
+            .decode(payload.as_bytes())
+            .map_err(LayoutTransferError::InvalidEncoding)?;
+        let mut cursor = 0usize;
+        let seed = read_u64(&bytes, &mut cursor)?;
+        let difficulty = decode_varint(&bytes, &mut cursor)?;
+        let score = Score::new(decode_varint_u64(&bytes, &mut cursor)?);
+        let wave = decode_varint(&bytes, &mut cursor)?;
+        let elapsed = Duration::from_secs(u64::from(decode_varint(&bytes, &mut cursor)?));
+        let rules = RunRules::read(&bytes, &mut cursor)?;
+        let columns = decode_varint(&bytes, &mut cursor)?;
+        let rows = decode_varint(&bytes, &mut cursor)?;
+        if columns == 0 || rows == 0 {
+            return Err(LayoutTransferError::InvalidDimensions(format!(
+                "{columns}x{rows}"
+            )));
+        }
+        let layout = TowerLayoutSnapshot::read_payload(columns, rows, &bytes, &mut cursor)?;
+        if cursor != bytes.len() {
+            return Err(LayoutTransferError::TrailingBinaryData);
+        }
+
+        Ok(Self {
+            seed,
+            difficulty,
+            rules,
+            score,
+            wave,
+            elapsed,
+            layout,
+        })
+    }
+}
+
+impl RunRules {
+    /// Writes a flag byte followed by the value of every rule that carries one.
+    fn write(&self, buffer: &mut Vec<u8>) {
+        let flags = [
+            (self.tower_heat, HEAT_FLAG),
+            (self.line_of_sight, LINE_OF_SIGHT_FLAG),
+            (self.formations, FORMATIONS_FLAG),
+            (self.sandbox, SANDBOX_FLAG),
+            (self.loop_around.is_some(), LOOP_AROUND_FLAG),
+            (self.nest_health.is_some(), NESTS_FLAG),
+            (self.accuracy_drift_cells.is_some(), ACCURACY_FLAG),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(0, |flags, (_, flag)| flags | flag);
+        buffer.push(flags);
+        for value in [
+            self.loop_around,
+            self.nest_health,
+            self.accuracy_drift_cells,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            encode_varint(value, buffer);
+        }
+    }
+
+    fn read(bytes: &[u8], cursor: &mut usize) -> Result<Self, LayoutTransferError> {
+        let flags = read_u8(bytes, cursor)?;
+        let mut value = |flag: u8| {
+            (flags & flag != 0)
+                .then(|| decode_varint(bytes, cursor))
+                .transpose()
+        };
+        Ok(Self {
+            tower_heat: flags & HEAT_FLAG != 0,
+            line_of_sight: flags & LINE_OF_SIGHT_FLAG != 0,
+            formations: flags & FORMATIONS_FLAG != 0,
+            sandbox: flags & SANDBOX_FLAG != 0,
+            loop_around: value(LOOP_AROUND_FLAG)?,
+            nest_health: value(NESTS_FLAG)?,
+            accuracy_drift_cells: value(ACCURACY_FLAG)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout_transfer::TowerLayoutTower;
+    use maze_defence_core::{CellCoord, TowerKind, TowerRotation};
+
+    #[test]
+    fn share_codes_round_trip_and_reject_foreign_payloads() {
+        let summary = RunSummary {
+            seed: 0xdead_beef_1234,
+            difficulty: 3,
+            rules: RunRules {
+                tower_heat: true,
+                line_of_sight: true,
+                nest_health: Some(40),
+                accuracy_drift_cells: Some(0),
+                ..RunRules::default()
+            },
+            score: Score::new(u64::from(u32::MAX) + 187),
+            wave: 9,
+            elapsed: Duration::from_secs(754),
+            layout: TowerLayoutSnapshot {
+                columns: 12,
+                rows: 9,
+                tile_length: 100.0,
+                cells_per_tile: 4,
+                towers: vec![TowerLayoutTower {
+                    kind: TowerKind::Barrier,
+                    origin: CellCoord::new(8, 4),
+                    rotation: TowerRotation::Deg90,
+                }],
+            },
+        };
+
+        let code = summary.encode();
+        assert!(code.starts_with("run:v2:"));
+        assert_eq!(RunSummary::decode(&code).expect("code decodes"), summary);
+
+        let layout = summary.layout.encode();
+        assert!(matches!(
+            RunSummary::decode(&layout),
+            Err(LayoutTransferError::InvalidPrefix(_))
+        ));
+        let payload = URL_SAFE_NO_PAD
+            .decode(&code["run:v2:".len()..])
+            .expect("payload decodes");
+        let truncated = format!(
+            "run:v2:{}",
+            URL_SAFE_NO_PAD.encode(&payload[..payload.len() - 1])
+        );
+        assert!(matches!(
+            RunSummary::decode(&truncated),
+            Err(LayoutTransferError::TruncatedBinaryPayload)
+        ));
+    }
+}
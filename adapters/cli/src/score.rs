@@ -0,0 +1,106 @@
+//! Score a run earns before it ends in defeat.
+//!
+//! The score is `(kills + 10 × waves cleared + minutes survived) × (100 + 10 × difficulty)
+//! / 100`: every bug killed is worth a point, every wave won ten, every full minute of
+//! simulated time one, and each difficulty level the run ended on adds 10% to the total.
+
+use std::{fmt, time::Duration};
+
+use maze_defence_core::DifficultyLevel;
+
+/// Points a run earns for every wave it won.
+const POINTS_PER_WAVE: u64 = 10;
+/// Simulated time a run must survive for every point it earns.
+const SURVIVAL_PER_POINT: Duration = Duration::from_secs(60);
+/// Percentage added to the score for every difficulty level.
+const DIFFICULTY_BONUS_PERCENT: u64 = 10;
+
+/// What a run achieved while it was played.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RunTally {
+    /// Bugs killed.
+    pub(crate) kills: u64,
+    /// Waves won.
+    pub(crate) waves_cleared: u64,
+}
+
+impl RunTally {
+    /// Score of a run that achieved this tally and ended after `elapsed` of simulated time
+    /// on `difficulty`.
+    pub(crate) fn score(&self, difficulty: DifficultyLevel, elapsed: Duration) -> Score {
+        let survival = elapsed.as_secs() / SURVIVAL_PER_POINT.as_secs();
+        let points = self
+            .kills
+            .saturating_add(self.waves_cleared.saturating_mul(POINTS_PER_WAVE))
+            .saturating_add(survival);
+        let percent = 100 + u128::from(difficulty.get()) * u128::from(DIFFICULTY_BONUS_PERCENT);
+        let scaled = u128::from(points) * percent / 100;
+        Score(u64::try_from(scaled).unwrap_or(u64::MAX))
+    }
+}
+
+/// Points a run scored; see the [module documentation](self) for the formula.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Score(u64);
+
+impl Score {
+    /// Wraps a score read back from a share code.
+    pub(crate) const fn new(points: u64) -> Self {
+        Self(points)
+    }
+
+    /// Points the score stands for.
+    pub(crate) const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(kills: u64, waves_cleared: u64) -> RunTally {
+        RunTally {
+            kills,
+            waves_cleared,
+        }
+    }
+
+    #[test]
+    fn every_term_adds_to_the_score() {
+        let easy = DifficultyLevel::new(0);
+        assert_eq!(tally(0, 0).score(easy, Duration::ZERO), Score::new(0));
+        assert_eq!(
+            tally(7, 0).score(easy, Duration::ZERO),
+            Score::new(7),
+            "a point per kill"
+        );
+        assert_eq!(
+            tally(0, 3).score(easy, Duration::ZERO),
+            Score::new(30),
+            "ten points per wave cleared"
+        );
+        assert_eq!(
+            tally(0, 0).score(easy, Duration::from_secs(179)),
+            Score::new(2),
+            "a point per full minute survived"
+        );
+        assert_eq!(
+            tally(7, 3).score(DifficultyLevel::new(5), Duration::from_secs(180)),
+            Score::new(60),
+            "each difficulty level adds 10% to the 40 points"
+        );
+    }
+
+    #[test]
+    fn scores_saturate_instead_of_overflowing() {
+        let score = tally(u64::MAX, u64::MAX).score(DifficultyLevel::new(u32::MAX), Duration::MAX);
+        assert_eq!(score.get(), u64::MAX);
+    }
+}
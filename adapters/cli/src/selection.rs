@@ -0,0 +1,241 @@
+use std::collections::BTreeSet;
+
+use maze_defence_core::{CellCoord, CellRect, CellRectSize, TowerId, TowerView};
+
+/// Number of numbered selection groups reachable through the digit hotkeys.
+pub(crate) const GROUP_SLOTS: usize = 9;
+
+/// Presentation-layer tower selection together with the numbered selection groups.
+///
+/// Groups remember the origin cells of their towers rather than tower identifiers, so they
+/// survive being saved and restored in a later session with a rebuilt maze.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TowerSelection {
+    selected: BTreeSet<TowerId>,
+    groups: [BTreeSet<CellCoord>; GROUP_SLOTS],
+    drag: Option<SelectionDrag>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SelectionDrag {
+    anchor: CellCoord,
+    corner: CellCoord,
+}
+
+impl TowerSelection {
+    /// Iterates over the selected towers in ascending identifier order.
+    pub(crate) fn selected(&self) -> impl Iterator<Item = TowerId> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// Reports whether no tower is currently selected.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Drops every tower from the active selection.
+    pub(crate) fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Starts a selection box anchored at the provided cell.
+    pub(crate) fn begin_drag(&mut self, cell: CellCoord) {
+        self.drag = Some(SelectionDrag {
+            anchor: cell,
+            corner: cell,
+        });
+    }
+
+    /// Moves the free corner of the active selection box, if any.
+    pub(crate) fn extend_drag(&mut self, cell: CellCoord) {
+        if let Some(drag) = self.drag.as_mut() {
+            drag.corner = cell;
+        }
+    }
+
+    /// Reports whether a selection box is currently being dragged.
+    pub(crate) fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Cell-space rectangle covered by the active selection box, if any.
+    pub(crate) fn drag_region(&self) -> Option<CellRect> {
+        let drag = self.drag?;
+        let left = drag.anchor.column().min(drag.corner.column());
+        let top = drag.anchor.row().min(drag.corner.row());
+        let right = drag.anchor.column().max(drag.corner.column());
+        let bottom = drag.anchor.row().max(drag.corner.row());
+        Some(CellRect::from_origin_and_size(
+            CellCoord::new(left, top),
+            CellRectSize::new(right - left + 1, bottom - top + 1),
+        ))
+    }
+
+    /// Completes the active selection box.
+    ///
+    /// A box that never left its anchor cell toggles the tower under it; larger boxes add
+    /// every tower they overlap to the selection.
+    pub(crate) fn finish_drag(&mut self, towers: &TowerView) {
+        let Some(region) = self.drag_region() else {
+            return;
+        };
+        self.drag = None;
+
+        let single_cell = region.size().width() == 1 && region.size().height() == 1;
+        let covered: Vec<TowerId> = towers
+            .iter()
+            .filter(|tower| regions_overlap(tower.region, region))
+            .map(|tower| tower.id)
+            .collect();
+
+        if single_cell {
+            for tower in covered {
+                if !self.selected.remove(&tower) {
+                    let _ = self.selected.insert(tower);
+                }
+            }
+        } else {
+            self.selected.extend(covered);
+        }
+    }
+
+    /// Stores the origins of the selected towers in the numbered group slot.
+    pub(crate) fn assign_group(&mut self, slot: usize, towers: &TowerView) {
+        if let Some(group) = self.groups.get_mut(slot) {
+            *group = towers
+                .iter()
+                .filter(|tower| self.selected.contains(&tower.id))
+                .map(|tower| tower.region.origin())
+                .collect();
+        }
+    }
+
+    /// Replaces the active selection with the towers standing on the group's origins.
+    pub(crate) fn recall_group(&mut self, slot: usize, towers: &TowerView) {
+        if let Some(group) = self.groups.get(slot) {
+            self.selected = towers
+                .iter()
+                .filter(|tower| group.contains(&tower.region.origin()))
+                .map(|tower| tower.id)
+                .collect();
+        }
+    }
+
+    /// Tower origins stored in each group slot, first slot first.
+    pub(crate) fn groups(&self) -> Vec<Vec<CellCoord>> {
+        self.groups
+            .iter()
+            .map(|group| group.iter().copied().collect())
+            .collect()
+    }
+
+    /// Replaces the group slots with previously saved origins; extra slots are ignored.
+    pub(crate) fn restore_groups(&mut self, groups: Vec<Vec<CellCoord>>) {
+        self.groups = Default::default();
+        for (slot, origins) in self.groups.iter_mut().zip(groups) {
+            *slot = origins.into_iter().collect();
+        }
+    }
+
+    /// Forgets selected towers that no longer exist in the world.
+    ///
+    /// Groups keep their origins so a tower rebuilt on the same cell rejoins its group.
+    pub(crate) fn retain_existing(&mut self, towers: &TowerView) {
+        let existing: BTreeSet<TowerId> = towers.iter().map(|tower| tower.id).collect();
+        self.selected.retain(|tower| existing.contains(tower));
+    }
+}
+
+fn regions_overlap(a: CellRect, b: CellRect) -> bool {
+    let a_origin = a.origin();
+    let b_origin = b.origin();
+    a_origin.column() < b_origin.column() + b.size().width()
+        && b_origin.column() < a_origin.column() + a.size().width()
+        && a_origin.row() < b_origin.row() + b.size().height()
+        && b_origin.row() < a_origin.row() + a.size().height()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{TargetingPolicy, TowerKind, TowerRotation, TowerSnapshot};
+
+    fn tower(id: u32, column: u32, row: u32) -> TowerSnapshot {
+        TowerSnapshot {
+            id: TowerId::new(id),
+            kind: TowerKind::Basic,
+            region: CellRect::from_origin_and_size(
+                CellCoord::new(column, row),
+                CellRectSize::new(2, 2),
+            ),
+            rotation: TowerRotation::Deg0,
+            targeting: TargetingPolicy::Nearest,
+            upgrades: 0,
+        }
+    }
+
+    #[test]
+    fn box_selects_overlapping_towers_and_click_toggles() {
+        let towers =
+            TowerView::from_snapshots(vec![tower(1, 0, 0), tower(2, 4, 0), tower(3, 10, 10)]);
+        let mut selection = TowerSelection::default();
+
+        selection.begin_drag(CellCoord::new(1, 1));
+        selection.extend_drag(CellCoord::new(5, 3));
+        selection.finish_drag(&towers);
+        assert_eq!(
+            selection.selected().collect::<Vec<_>>(),
+            vec![TowerId::new(1), TowerId::new(2)]
+        );
+
+        selection.begin_drag(CellCoord::new(4, 1));
+        selection.finish_drag(&towers);
+        assert_eq!(
+            selection.selected().collect::<Vec<_>>(),
+            vec![TowerId::new(1)]
+        );
+        assert!(!selection.is_dragging());
+    }
+
+    #[test]
+    fn groups_recall_assigned_towers_that_still_exist() {
+        let towers = TowerView::from_snapshots(vec![tower(1, 0, 0), tower(2, 4, 0)]);
+        let mut selection = TowerSelection::default();
+        selection.begin_drag(CellCoord::new(0, 0));
+        selection.extend_drag(CellCoord::new(5, 1));
+        selection.finish_drag(&towers);
+        selection.assign_group(2, &towers);
+        selection.clear();
+        assert!(selection.is_empty());
+
+        let remaining = TowerView::from_snapshots(vec![tower(2, 4, 0)]);
+        selection.retain_existing(&remaining);
+        selection.recall_group(2, &remaining);
+        assert_eq!(
+            selection.selected().collect::<Vec<_>>(),
+            vec![TowerId::new(2)]
+        );
+    }
+
+    #[test]
+    fn restored_groups_select_the_towers_rebuilt_on_their_origins() {
+        let towers = TowerView::from_snapshots(vec![tower(1, 0, 0), tower(2, 4, 0)]);
+        let mut selection = TowerSelection::default();
+        selection.begin_drag(CellCoord::new(4, 0));
+        selection.finish_drag(&towers);
+        selection.assign_group(0, &towers);
+        let saved = selection.groups();
+        assert_eq!(saved.len(), GROUP_SLOTS);
+        assert_eq!(saved[0], vec![CellCoord::new(4, 0)]);
+
+        let rebuilt = TowerView::from_snapshots(vec![tower(7, 4, 0), tower(8, 10, 10)]);
+        let mut restored = TowerSelection::default();
+        restored.restore_groups(saved);
+        restored.recall_group(0, &rebuilt);
+        assert_eq!(
+            restored.selected().collect::<Vec<_>>(),
+            vec![TowerId::new(7)]
+        );
+        assert_eq!(restored.groups(), selection.groups());
+    }
+}
@@ -0,0 +1,137 @@
+//! Attackable regions of towers whose shots walls and other towers block.
+
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+use maze_defence_core::{CellPointHalf, CellRect, RaycastHit};
+use maze_defence_rendering::AttackRegion;
+use maze_defence_world::{query, World};
+
+/// Directions sampled around the tower centre; enough for a smooth outline at any range.
+const RAYS: u32 = 96;
+
+/// Clips the range circle of a tower covering `region` to what it can see.
+///
+/// One ray per sampled direction is cast from the tower centre to its range; rays stop
+/// where they enter the first wall or tower cell. Returns `None` for empty footprints.
+pub(crate) fn attack_region(
+    world: &World,
+    region: CellRect,
+    radius_cells: u32,
+) -> Option<AttackRegion> {
+    let size = region.size();
+    if size.width() == 0 || size.height() == 0 {
+        return None;
+    }
+    let origin = region.origin();
+    let center_half = CellPointHalf::new(
+        i64::from(origin.column()) * 2 + i64::from(size.width()),
+        i64::from(origin.row()) * 2 + i64::from(size.height()),
+    );
+    let center = Vec2::new(
+        center_half.column_half() as f32,
+        center_half.row_half() as f32,
+    );
+    let radius_half = radius_cells as f32 * 2.0;
+
+    let outline = (0..RAYS)
+        .map(|ray| {
+            let angle = ray as f32 * TAU / RAYS as f32;
+            let end = center + Vec2::from_angle(angle) * radius_half;
+            let end_half = CellPointHalf::new(end.x.round() as i64, end.y.round() as i64);
+            let end = Vec2::new(end_half.column_half() as f32, end_half.row_half() as f32);
+            let reach = match query::raycast(world, center_half, end_half) {
+                RaycastHit::Wall { cell } | RaycastHit::Tower { cell, .. } => {
+                    let corner = Vec2::new(cell.column() as f32, cell.row() as f32) * 2.0;
+                    entry_fraction(center, end, corner, corner + Vec2::splat(2.0))
+                }
+                RaycastHit::Clear | RaycastHit::OutOfBounds => 1.0,
+            };
+            (center + (end - center) * reach) * 0.5
+        })
+        .collect();
+    Some(AttackRegion::new(center * 0.5, outline))
+}
+
+/// Fraction of the segment `from..to` travelled before it enters the box `min..max`.
+fn entry_fraction(from: Vec2, to: Vec2, min: Vec2, max: Vec2) -> f32 {
+    let delta = to - from;
+    let mut enter = 0.0_f32;
+    for axis in 0..2 {
+        if delta[axis].abs() <= f32::EPSILON {
+            continue;
+        }
+        let near = if delta[axis] > 0.0 {
+            min[axis]
+        } else {
+            max[axis]
+        };
+        enter = enter.max((near - from[axis]) / delta[axis]);
+    }
+    enter.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use maze_defence_core::{CellCoord, Command, PlayMode, TileCoord, TowerKind};
+    use maze_defence_world::apply;
+
+    use super::*;
+
+    #[test]
+    fn towers_in_the_way_clip_the_region_and_open_directions_reach_the_range() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        for command in [
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(12),
+                rows: TileCoord::new(12),
+                tile_length: 1.0,
+                cells_per_tile: 2,
+            },
+            Command::SetPlayMode {
+                mode: PlayMode::Builder,
+            },
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(9, 9),
+            },
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(15, 9),
+            },
+        ] {
+            apply(&mut world, command, &mut events);
+        }
+        let shooter = query::towers(&world)
+            .iter()
+            .find(|tower| tower.region.origin() == CellCoord::new(9, 9))
+            .expect("tower placed")
+            .region;
+
+        let region = attack_region(&world, shooter, 8).expect("non-empty footprint");
+        assert_eq!(region.center(), Vec2::new(11.0, 11.0));
+        assert_eq!(region.outline().len(), RAYS as usize);
+        let reach = |ray: usize| region.outline()[ray].distance(region.center());
+        // East runs into the neighbour's western edge at column 15.
+        assert!((reach(0) - 4.0).abs() < 1e-3, "east reach {}", reach(0));
+        for ray in [RAYS as usize / 4, RAYS as usize / 2, RAYS as usize * 3 / 4] {
+            assert!((reach(ray) - 8.0).abs() < 1e-3, "open ray {ray}");
+        }
+    }
+
+    #[test]
+    fn entry_fraction_measures_the_first_face_crossed() {
+        let from = Vec2::new(0.0, 0.0);
+        let to = Vec2::new(10.0, 0.0);
+        let fraction = entry_fraction(from, to, Vec2::new(4.0, -1.0), Vec2::new(6.0, 1.0));
+        assert!((fraction - 0.4).abs() < 1e-6);
+        let diagonal = entry_fraction(
+            from,
+            Vec2::new(10.0, 10.0),
+            Vec2::splat(2.0),
+            Vec2::splat(4.0),
+        );
+        assert!((diagonal - 0.2).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use glam::Vec2;
+use maze_defence_rendering::{BugPresentation, CameraFocus, SceneTower};
+
+/// Time the camera lingers on a point of interest before cycling to the next one.
+const POINT_OF_INTEREST_DWELL: Duration = Duration::from_secs(6);
+/// Magnification applied while the camera follows a point of interest.
+const SPECTATE_ZOOM: f32 = 1.6;
+/// Rate, per second, at which the camera closes the distance to its target.
+const FOLLOW_RATE: f32 = 3.0;
+/// Cell-space radius used when counting neighbours for the densest bug cluster.
+const CLUSTER_RADIUS_CELLS: f32 = 4.0;
+
+/// Points of interest the spectator camera cycles through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PointOfInterest {
+    BugCluster,
+    NewestTower,
+}
+
+impl PointOfInterest {
+    const CYCLE: [Self; 2] = [Self::BugCluster, Self::NewestTower];
+}
+
+/// Hands-off camera director used when the runner is in spectate mode.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Spectator {
+    elapsed: Duration,
+    frame_dt: Duration,
+    center: Option<Vec2>,
+}
+
+impl Spectator {
+    /// Advances the dwell timer and records the frame delta used for camera smoothing.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.elapsed = self.elapsed.saturating_add(dt);
+        self.frame_dt = dt;
+    }
+
+    /// Chooses the camera framing for the current frame.
+    ///
+    /// The camera alternates between the densest bug cluster and the newest tower, falling
+    /// back to whichever is available. With nothing to follow the whole board is shown.
+    pub(crate) fn focus(
+        &mut self,
+        bugs: &[BugPresentation],
+        towers: &[SceneTower],
+    ) -> Option<CameraFocus> {
+        let slot = (self.elapsed.as_millis() / POINT_OF_INTEREST_DWELL.as_millis()) as usize;
+        let preferred = PointOfInterest::CYCLE[slot % PointOfInterest::CYCLE.len()];
+        let resolve = |poi| match poi {
+            PointOfInterest::BugCluster => densest_bug_cluster(bugs, CLUSTER_RADIUS_CELLS),
+            PointOfInterest::NewestTower => newest_tower_center(towers),
+        };
+        let fallback = PointOfInterest::CYCLE
+            .into_iter()
+            .find(|poi| *poi != preferred)
+            .and_then(resolve);
+        let Some(target) = resolve(preferred).or(fallback) else {
+            self.center = None;
+            return None;
+        };
+
+        let center = match self.center {
+            Some(current) => {
+                let blend = 1.0 - (-FOLLOW_RATE * self.frame_dt.as_secs_f32()).exp();
+                current.lerp(target, blend)
+            }
+            None => target,
+        };
+        self.center = Some(center);
+        Some(CameraFocus::new(center, SPECTATE_ZOOM))
+    }
+}
+
+/// Centroid of the bugs surrounding the bug with the most neighbours within `radius`.
+pub(crate) fn densest_bug_cluster(bugs: &[BugPresentation], radius: f32) -> Option<Vec2> {
+    let positions: Vec<Vec2> = bugs.iter().map(BugPresentation::position).collect();
+    let radius_squared = radius * radius;
+    let neighbours = |anchor: Vec2| {
+        positions
+            .iter()
+            .copied()
+            .filter(move |position| position.distance_squared(anchor) <= radius_squared)
+    };
+    let anchor = positions
+        .iter()
+        .copied()
+        .max_by_key(|anchor| neighbours(*anchor).count())?;
+    let (sum, count) = neighbours(anchor).fold((Vec2::ZERO, 0_u32), |(sum, count), position| {
+        (sum + position, count + 1)
+    });
+    Some(sum / count as f32)
+}
+
+fn newest_tower_center(towers: &[SceneTower]) -> Option<Vec2> {
+    let tower = towers.iter().max_by_key(|tower| tower.id)?;
+    let origin = tower.region.origin();
+    let size = tower.region.size();
+    Some(Vec2::new(
+        origin.column() as f32 + size.width() as f32 * 0.5,
+        origin.row() as f32 + size.height() as f32 * 0.5,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{BugId, CellCoord, CellRect, CellRectSize, TowerId, TowerKind};
+    use maze_defence_rendering::{BugHealthPresentation, Color};
+
+    fn bug(id: u32, position: Vec2) -> BugPresentation {
+        BugPresentation::new_circle(
+            BugId::new(id),
+            position,
+            Color::from_rgb_u8(200, 40, 40),
+            BugHealthPresentation::new(3, 3),
+        )
+    }
+
+    #[test]
+    fn camera_follows_densest_cluster_then_cycles_to_newest_tower() {
+        let bugs = vec![
+            bug(1, Vec2::new(2.0, 2.0)),
+            bug(2, Vec2::new(3.0, 2.0)),
+            bug(3, Vec2::new(2.0, 3.0)),
+            bug(4, Vec2::new(20.0, 20.0)),
+        ];
+        let towers = vec![
+            SceneTower::new(
+                TowerId::new(1),
+                TowerKind::Basic,
+                CellRect::from_origin_and_size(CellCoord::new(10, 10), CellRectSize::new(2, 2)),
+            ),
+            SceneTower::new(
+                TowerId::new(4),
+                TowerKind::Basic,
+                CellRect::from_origin_and_size(CellCoord::new(6, 0), CellRectSize::new(2, 2)),
+            ),
+        ];
+
+        let cluster = densest_bug_cluster(&bugs, CLUSTER_RADIUS_CELLS).expect("cluster");
+        assert!((cluster - Vec2::new(7.0 / 3.0, 7.0 / 3.0)).length() < 1e-5);
+
+        let mut spectator = Spectator::default();
+        let focus = spectator.focus(&bugs, &towers).expect("focus on bugs");
+        assert_eq!(focus.center, cluster);
+
+        spectator.advance(POINT_OF_INTEREST_DWELL);
+        let focus = spectator.focus(&bugs, &towers).expect("focus on tower");
+        let target = Vec2::new(7.0, 1.0);
+        assert!(focus.center.distance(target) < cluster.distance(target));
+
+        assert_eq!(spectator.focus(&[], &[]), None);
+    }
+}
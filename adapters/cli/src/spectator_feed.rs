@@ -0,0 +1,129 @@
+//! Board state streamed as JSON lines for remote spectators.
+
+use std::{fmt, io::Write};
+
+use anyhow::Result;
+use maze_defence_rendering::{
+    FeedBug, FeedFrame, FeedProjectile, FeedState, FeedTower, SpectatorFeed,
+};
+use maze_defence_world::{query, World};
+
+/// Frames between keyframes, about five seconds at sixty frames per second.
+const KEYFRAME_INTERVAL_FRAMES: u64 = 300;
+
+/// Writes the frames of a [`SpectatorFeed`] as one line of JSON each.
+///
+/// Every simulation frame is diffed against the previous one, so a viewer reading the
+/// stream from any keyframe onwards can redraw the board without running the game.
+pub(crate) struct SpectatorFeedLog {
+    output: Box<dyn Write>,
+    feed: SpectatorFeed,
+    frame: u64,
+}
+
+impl SpectatorFeedLog {
+    /// Creates a log writing its frames to `output`.
+    pub(crate) fn new(output: impl Write + 'static) -> Self {
+        Self {
+            output: Box::new(output),
+            feed: SpectatorFeed::new(KEYFRAME_INTERVAL_FRAMES),
+            frame: 0,
+        }
+    }
+
+    /// Writes the changes to the board since the previous frame and flushes them.
+    pub(crate) fn record(&mut self, world: &World) -> Result<()> {
+        let tick = self.frame;
+        self.frame += 1;
+        if let Some(frame) = self.feed.publish(tick, feed_state(world)) {
+            write_frame(&mut self.output, frame)?;
+            self.output.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SpectatorFeedLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpectatorFeedLog")
+            .field("frame", &self.frame)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Board state a spectator draws, captured from `world`.
+fn feed_state(world: &World) -> FeedState {
+    let bugs = query::bug_view(world)
+        .into_vec()
+        .into_iter()
+        .map(|bug| FeedBug {
+            id: bug.id,
+            cell: bug.cell,
+            color: bug.color,
+            health: bug.health,
+            max_health: bug.max_health,
+        })
+        .collect();
+    let towers = query::towers(world)
+        .into_vec()
+        .into_iter()
+        .map(|tower| FeedTower {
+            id: tower.id,
+            kind: tower.kind,
+            region: tower.region,
+            rotation: tower.rotation,
+        })
+        .collect();
+    let projectiles = query::projectiles(world)
+        .map(|projectile| FeedProjectile {
+            id: projectile.projectile,
+            origin_half: projectile.origin_half,
+            dest_half: projectile.dest_half,
+            progress_permille: progress_permille(
+                projectile.travelled_half,
+                projectile.distance_half,
+            ),
+        })
+        .collect();
+    FeedState::new(query::gold(world), bugs, towers, projectiles)
+}
+
+fn progress_permille(travelled: u128, distance: u128) -> u16 {
+    if distance == 0 {
+        return 1_000;
+    }
+    u16::try_from(travelled.min(distance) * 1_000 / distance).unwrap_or(1_000)
+}
+
+fn write_frame(output: &mut impl Write, frame: &FeedFrame) -> Result<()> {
+    serde_json::to_writer(&mut *output, frame)?;
+    writeln!(output)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_rendering::SpectatorView;
+
+    #[test]
+    fn frames_are_written_one_json_object_per_line_and_rebuild_the_board() {
+        let world = World::new();
+        let state = feed_state(&world);
+        let mut feed = SpectatorFeed::new(KEYFRAME_INTERVAL_FRAMES);
+        let mut output = Vec::new();
+        let frame = feed
+            .publish(0, state.clone())
+            .expect("the first frame is a keyframe");
+        write_frame(&mut output, frame).expect("write to memory");
+        assert!(feed.publish(1, state.clone()).is_none());
+
+        let text = String::from_utf8(output).expect("utf-8");
+        assert_eq!(text.lines().count(), 1);
+        let mut viewer = SpectatorView::default();
+        let frame: FeedFrame = serde_json::from_str(text.trim_end()).expect("frame parses");
+        assert!(viewer.apply(&frame));
+        assert_eq!(viewer.state(), Some(&state));
+        assert_eq!(progress_permille(3, 4), 750);
+    }
+}
@@ -0,0 +1,300 @@
+//! Monte Carlo balance sweeps that autoplay many seeded runs in parallel.
+//!
+//! Every combination of tuning, starting difficulty and seed is played headlessly by the
+//! autoplayer for the same number of waves. Each resolved wave becomes one CSV record, so
+//! analysis notebooks can plot survival, leaks and the economy curve of every run.
+
+use std::{
+    io::Write,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::Result;
+use maze_defence_core::{DifficultyLevel, RoundOutcome};
+use maze_defence_world::query;
+
+use crate::{
+    autoplay::{self, FRAME},
+    Simulation,
+};
+
+/// Column names of the records written by [`run`].
+pub(crate) const HEADER: &str = "tuning,start_difficulty,seed,wave,outcome,waves_survived,\
+leaks,gold_earned,gold,towers,difficulty";
+
+/// One autoplayed run of a sweep.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SweepRun {
+    /// Content directory the run is played with, or `None` for the built-in content.
+    pub(crate) tuning: Option<PathBuf>,
+    /// Starting difficulty level, or `None` for the world's default.
+    pub(crate) difficulty: Option<DifficultyLevel>,
+    /// Global wave seed of the run.
+    pub(crate) seed: u64,
+}
+
+/// Runs that played to completion or failed while a sweep was written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SweepSummary {
+    /// Number of CSV records written, excluding the header.
+    pub(crate) records: usize,
+    /// Number of runs that stopped before their last wave resolved.
+    pub(crate) failed: usize,
+}
+
+/// Every combination of `tunings`, `difficulties` and `seeds`, varying seeds fastest.
+pub(crate) fn runs(
+    tunings: &[Option<PathBuf>],
+    difficulties: &[Option<DifficultyLevel>],
+    seeds: &[u64],
+) -> Vec<SweepRun> {
+    tunings
+        .iter()
+        .flat_map(|tuning| {
+            difficulties.iter().flat_map(move |&difficulty| {
+                seeds.iter().map(move |&seed| SweepRun {
+                    tuning: tuning.clone(),
+                    difficulty,
+                    seed,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Plays `runs` on up to `jobs` threads and writes their records to `output` in run order.
+///
+/// `build` creates the simulation of a run on the thread that plays it. A run that fails
+/// keeps the records of the waves it resolved; its error is reported on stderr and counted
+/// in the returned summary so the remaining runs still complete.
+pub(crate) fn run(
+    runs: &[SweepRun],
+    waves: u32,
+    jobs: NonZeroUsize,
+    build: impl Fn(&SweepRun) -> Result<Simulation> + Sync,
+    mut output: impl Write,
+) -> Result<SweepSummary> {
+    let waves = usize::try_from(waves).unwrap_or(usize::MAX);
+    let next = AtomicUsize::new(0);
+    let mut played: Vec<(usize, PlayedRun)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.get().min(runs.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut played = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(run) = runs.get(index) else {
+                            break;
+                        };
+                        played.push((index, play(run, waves, &build)));
+                    }
+                    played
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    played.sort_by_key(|(index, _)| *index);
+
+    let mut summary = SweepSummary::default();
+    writeln!(output, "{HEADER}")?;
+    for (index, played) in &played {
+        let run = &runs[*index];
+        let start_difficulty = played
+            .start_difficulty
+            .map_or_else(String::new, |level| level.get().to_string());
+        let tuning = run.tuning.as_ref().map_or_else(
+            || "default".to_owned(),
+            |directory| directory.display().to_string(),
+        );
+        for record in &played.records {
+            writeln!(
+                output,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&tuning),
+                start_difficulty,
+                run.seed,
+                record.wave,
+                autoplay::outcome_label(record.outcome),
+                record.waves_survived,
+                record.leaks,
+                record.gold_earned,
+                record.gold,
+                record.towers,
+                record.difficulty.get()
+            )?;
+        }
+        summary.records += played.records.len();
+        if let Some(error) = &played.error {
+            summary.failed += 1;
+            eprintln!(
+                "sweep run {} (tuning {tuning}, seed {}) failed: {error:#}",
+                index + 1,
+                run.seed
+            );
+        }
+    }
+    output.flush()?;
+    Ok(summary)
+}
+
+/// Records of one run and the error that stopped it early, if any.
+#[derive(Debug)]
+struct PlayedRun {
+    start_difficulty: Option<DifficultyLevel>,
+    records: Vec<WaveRecord>,
+    error: Option<anyhow::Error>,
+}
+
+/// State of a run right after one of its waves resolved.
+#[derive(Clone, Copy, Debug)]
+struct WaveRecord {
+    wave: usize,
+    outcome: RoundOutcome,
+    waves_survived: usize,
+    leaks: u32,
+    gold_earned: u64,
+    gold: u64,
+    towers: usize,
+    difficulty: DifficultyLevel,
+}
+
+fn play(
+    run: &SweepRun,
+    waves: usize,
+    build: &impl Fn(&SweepRun) -> Result<Simulation>,
+) -> PlayedRun {
+    let mut simulation = match build(run) {
+        Ok(simulation) => simulation,
+        Err(error) => {
+            return PlayedRun {
+                start_difficulty: run.difficulty,
+                records: Vec::new(),
+                error: Some(error),
+            }
+        }
+    };
+    simulation.silence_console();
+    simulation.enable_autoplay();
+    let start_difficulty = Some(query::difficulty_level(simulation.world()));
+
+    let mut records = Vec::new();
+    let mut waves_survived = 0;
+    let result = autoplay::play_waves(
+        &mut simulation,
+        waves,
+        |simulation| simulation.advance(FRAME),
+        |simulation, wave, outcome| {
+            if outcome == RoundOutcome::Win {
+                waves_survived += 1;
+            }
+            let world = simulation.world();
+            let report = simulation.wave_report();
+            records.push(WaveRecord {
+                wave,
+                outcome,
+                waves_survived,
+                leaks: report.map_or(0, |report| report.leaks()),
+                gold_earned: report.map_or(0, |report| report.gold_earned().get()),
+                gold: query::gold(world).get(),
+                towers: query::towers(world).iter().count(),
+                difficulty: query::difficulty_level(world),
+            });
+            Ok(())
+        },
+    );
+    PlayedRun {
+        start_difficulty,
+        records,
+        error: result.err(),
+    }
+}
+
+/// Quotes `value` when it contains a character CSV treats specially.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::VisualStyle;
+
+    fn sweep(jobs: usize) -> (SweepSummary, String) {
+        let runs = runs(&[None], &[None, Some(DifficultyLevel::new(2))], &[7, 8]);
+        assert_eq!(runs.len(), 4);
+        let mut output = Vec::new();
+        let summary = run(
+            &runs,
+            1,
+            NonZeroUsize::new(jobs).expect("non-zero"),
+            |run| {
+                let mut simulation = Simulation::new(
+                    6,
+                    6,
+                    48.0,
+                    2,
+                    Duration::from_millis(100),
+                    Duration::from_millis(1_000),
+                    VisualStyle::Primitives,
+                    run.difficulty,
+                    None,
+                );
+                simulation.configure_wave_seed(run.seed);
+                Ok(simulation)
+            },
+            &mut output,
+        )
+        .expect("sweep writes");
+        (summary, String::from_utf8(output).expect("utf-8"))
+    }
+
+    #[test]
+    fn sweeps_write_one_record_per_wave_in_run_order_regardless_of_jobs() {
+        let (summary, csv) = sweep(3);
+        assert_eq!(
+            summary,
+            SweepSummary {
+                records: 4,
+                failed: 0
+            }
+        );
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines.len(), 5);
+        let keys: Vec<Vec<&str>> = lines[1..]
+            .iter()
+            .map(|line| line.split(',').take(4).collect())
+            .collect();
+        assert_eq!(keys[0], ["default", "0", "7", "1"]);
+        assert_eq!(keys[1], ["default", "0", "8", "1"]);
+        assert_eq!(keys[3], ["default", "2", "8", "1"]);
+        assert!(lines[1..]
+            .iter()
+            .all(|line| line.split(',').count() == HEADER.split(',').count()));
+
+        assert_eq!(sweep(1).1, csv, "records must not depend on scheduling");
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("content/easy"), "content/easy");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use glam::Vec2;
+use maze_defence_core::BugId;
+use maze_defence_rendering::{BugPresentation, ProjectileWhiff};
+
+/// How long the puff of a missed projectile stays on screen.
+const WHIFF_DURATION: Duration = Duration::from_millis(350);
+
+/// Buffers missed projectiles until their targets' positions are captured, then keeps a
+/// puff visible where each miss happened.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProjectileWhiffs {
+    missed: Vec<BugId>,
+    playing: Vec<(Vec2, Duration)>,
+}
+
+impl ProjectileWhiffs {
+    /// Records a projectile that missed `target`.
+    pub(crate) fn record(&mut self, target: BugId) {
+        self.missed.push(target);
+    }
+
+    /// Starts a puff at every recorded target's presentation on the previous frame.
+    ///
+    /// Targets that were never presented are dropped without an effect.
+    pub(crate) fn capture(&mut self, previous: &[BugPresentation]) {
+        for target in self.missed.drain(..) {
+            if let Some(presentation) = previous.iter().find(|bug| bug.id == target) {
+                self.playing.push((presentation.position(), Duration::ZERO));
+            }
+        }
+    }
+
+    /// Advances every puff, dropping those that finished playing.
+    pub(crate) fn advance(&mut self, dt: Duration) {
+        self.playing.retain_mut(|(_, elapsed)| {
+            *elapsed = elapsed.saturating_add(dt);
+            *elapsed < WHIFF_DURATION
+        });
+    }
+
+    /// Forgets every puff, e.g. when the board is cleared.
+    pub(crate) fn clear(&mut self) {
+        self.missed.clear();
+        self.playing.clear();
+    }
+
+    /// Puffs that are still playing.
+    pub(crate) fn whiffs(&self) -> impl Iterator<Item = ProjectileWhiff> + '_ {
+        self.playing.iter().map(|(position, elapsed)| {
+            ProjectileWhiff::new(
+                *position,
+                elapsed.as_secs_f32() / WHIFF_DURATION.as_secs_f32(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_rendering::{BugHealthPresentation, Color};
+
+    #[test]
+    fn missed_targets_leave_a_puff_until_it_plays_out() {
+        let mut whiffs = ProjectileWhiffs::default();
+        whiffs.record(BugId::new(4));
+        whiffs.record(BugId::new(8));
+        whiffs.capture(&[BugPresentation::new_circle(
+            BugId::new(4),
+            Vec2::new(2.5, 6.5),
+            Color::from_rgb_u8(0x10, 0x20, 0x30),
+            BugHealthPresentation::new(3, 3),
+        )]);
+
+        whiffs.advance(WHIFF_DURATION / 2);
+        let playing: Vec<_> = whiffs.whiffs().collect();
+        assert_eq!(playing.len(), 1, "unpresented targets are dropped");
+        assert_eq!(playing[0].position, Vec2::new(2.5, 6.5));
+        assert!((playing[0].progress - 0.5).abs() < 1e-3);
+
+        whiffs.advance(WHIFF_DURATION / 2);
+        assert_eq!(whiffs.whiffs().count(), 0);
+    }
+}
@@ -0,0 +1,144 @@
+//! Fixed-rate simulation updates decoupled from the display refresh rate.
+
+use std::time::Duration;
+
+/// Simulation work scheduled for one rendered frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTick {
+    /// Simulated time advanced by every update.
+    pub step: Duration,
+    /// Updates to run before the frame is drawn; zero when the display outpaces the
+    /// simulation.
+    pub steps: u32,
+    /// Share of the next update already elapsed, in `[0, 1)`, for interpolating motion
+    /// between the last update and the next one.
+    pub alpha: f32,
+}
+
+impl FrameTick {
+    /// Tick running exactly one update of `step`, as a frame-locked loop would.
+    #[must_use]
+    pub const fn single(step: Duration) -> Self {
+        Self {
+            step,
+            steps: 1,
+            alpha: 0.0,
+        }
+    }
+
+    /// Simulated time covered by the frame's updates.
+    #[must_use]
+    pub fn simulated(&self) -> Duration {
+        self.step.saturating_mul(self.steps)
+    }
+
+    /// Time by which drawn motion should lead the last update, i.e. `alpha` of a step.
+    #[must_use]
+    pub fn lead(&self) -> Duration {
+        self.step.mul_f32(self.alpha)
+    }
+}
+
+/// Accumulates real frame time and releases it as updates of a fixed step.
+///
+/// A 144 Hz display runs an update only every few frames and a 30 Hz display runs two per
+/// frame, so the game plays at the same speed on both. After a long stall, such as a window
+/// drag, at most [`FixedTimestep::MAX_STEPS_PER_FRAME`] updates catch up and the rest of the
+/// backlog is dropped rather than snowballing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    /// Default update rate of 60 updates per second.
+    pub const DEFAULT_STEP: Duration = Duration::from_nanos(16_666_667);
+    /// Most updates a single frame runs.
+    pub const MAX_STEPS_PER_FRAME: u32 = 5;
+
+    /// Creates an accumulator releasing updates of `step`; a zero step is raised to one
+    /// millisecond.
+    #[must_use]
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step: step.max(Duration::from_millis(1)),
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Simulated time advanced by each update.
+    #[must_use]
+    pub const fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Adds a frame of `frame_dt` real time and returns the updates it makes due.
+    pub fn advance(&mut self, frame_dt: Duration) -> FrameTick {
+        self.accumulator = self.accumulator.saturating_add(frame_dt);
+        let due = self.accumulator.as_nanos() / self.step.as_nanos();
+        let steps = u32::try_from(due)
+            .unwrap_or(u32::MAX)
+            .min(Self::MAX_STEPS_PER_FRAME);
+        self.accumulator = if u128::from(steps) < due {
+            Duration::from_nanos(
+                u64::try_from(self.accumulator.as_nanos() % self.step.as_nanos())
+                    .unwrap_or_default(),
+            )
+        } else {
+            self.accumulator - self.step.saturating_mul(steps)
+        };
+        FrameTick {
+            step: self.step,
+            steps,
+            alpha: (self.accumulator.as_secs_f64() / self.step.as_secs_f64()) as f32,
+        }
+    }
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_STEP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_rates_do_not_change_the_simulated_time() {
+        let second = Duration::from_secs(1);
+        for hz in [30_u32, 60, 144, 240] {
+            let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+            let frame = second / hz;
+            let simulated: Duration = (0..hz).map(|_| timestep.advance(frame).simulated()).sum();
+            let drift = second.abs_diff(simulated);
+            assert!(
+                drift <= timestep.step(),
+                "{hz} Hz simulated {simulated:?} in a second"
+            );
+        }
+    }
+
+    #[test]
+    fn fast_displays_interpolate_and_stalls_drop_the_backlog() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        let first = timestep.advance(Duration::from_millis(4));
+        assert_eq!(first.steps, 0);
+        assert!((first.alpha - 0.4).abs() < 1e-4);
+        assert_eq!(first.lead(), Duration::from_millis(4));
+
+        let second = timestep.advance(Duration::from_millis(8));
+        assert_eq!(second.steps, 1);
+        assert!((second.alpha - 0.2).abs() < 1e-4);
+
+        let stalled = timestep.advance(Duration::from_secs(2));
+        assert_eq!(stalled.steps, FixedTimestep::MAX_STEPS_PER_FRAME);
+        assert!(stalled.alpha < 1.0);
+        assert_eq!(
+            FrameTick::single(Duration::from_millis(16)).simulated(),
+            Duration::from_millis(16)
+        );
+    }
+}
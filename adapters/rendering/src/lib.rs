@@ -12,10 +12,28 @@
 use anyhow::Result as AnyResult;
 use glam::Vec2;
 use maze_defence_core::{
-    BugId, CellCoord, CellRect, Gold, PlacementError, PlayMode, ProjectileId, RemovalError,
-    StatsReport, TowerId, TowerKind, WaveDifficulty,
+    BetweenWaveEvent, BossPhase, BugEffect, BugId, BugRank, BugStatus, CellCoord, CellRect,
+    CellRectSize, Combo, DangerHeatmap, Event, FormationId, GameRules, Gold, LevelId,
+    PlacementError, PlayMode, PreparationReward, PressureWavePlan, ProjectileId, RemovalError,
+    SpeciesId, StatsReport, StatusEffect, TowerId, TowerKind, WaveContract, WaveDifficulty,
+    WaveReport, WorldDebugStats,
+};
+use std::{collections::VecDeque, error::Error, fmt, time::Duration};
+
+mod fixed_timestep;
+mod notifications;
+mod number_format;
+mod projection;
+mod spectator_feed;
+
+pub use fixed_timestep::{FixedTimestep, FrameTick};
+pub use notifications::{Notifications, Toast};
+pub use number_format::NumberFormat;
+pub use projection::Projection;
+pub use spectator_feed::{
+    FeedBug, FeedChange, FeedFrame, FeedProjectile, FeedState, FeedTower, SpectatorFeed,
+    SpectatorView,
 };
-use std::{error::Error, fmt, time::Duration};
 
 /// RGBA color used when presenting frames.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -65,6 +83,20 @@ impl Color {
             alpha: self.alpha,
         }
     }
+
+    /// Returns a new color mixed towards `other` by the provided amount, keeping this
+    /// color's alpha.
+    #[must_use]
+    pub fn mix(self, other: Self, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+
+        Self {
+            red: self.red + (other.red - self.red) * amount,
+            green: self.green + (other.green - self.green) * amount,
+            blue: self.blue + (other.blue - self.blue) * amount,
+            alpha: self.alpha,
+        }
+    }
 }
 
 fn lighten_channel(channel: f32, amount: f32) -> f32 {
@@ -200,6 +232,11 @@ pub enum BugVisual {
         /// Tint applied to the sprite when drawing.
         tint: Color,
     },
+    /// Draws a small single-colour dot; used for bugs beyond the [`BugLodPolicy`] budget.
+    Dot {
+        /// Fill colour of the dot.
+        color: Color,
+    },
 }
 
 /// Categories of ground tiles rendered beneath the maze.
@@ -241,8 +278,47 @@ pub struct FrameInput {
     pub cursor_tile_space: Option<TileSpacePosition>,
     /// Whether the adapter detected a placement confirmation on this frame.
     pub confirm_action: bool,
+    /// Whether the placement button is held inside the grid, extending a drag placement.
+    pub confirm_held: bool,
     /// Whether the adapter detected a tower removal request on this frame.
     pub remove_action: bool,
+    /// Whether the selection modifier is held, turning clicks and drags into tower selection.
+    pub selection_modifier: bool,
+    /// Selection group hotkey pressed on this frame, if any.
+    pub group_hotkey: Option<GroupHotkey>,
+    /// Selection groups restored from a previous session, sent once before any group hotkey.
+    pub restore_selection_groups: Option<Vec<Vec<CellCoord>>>,
+    /// Starting bonuses for the new game, sent once on the first frame.
+    pub game_rules: Option<GameRules>,
+    /// Whether the player switched the selected towers to the next targeting policy on this frame.
+    pub cycle_selection_targeting: bool,
+    /// Whether the player requested an upgrade of every selected tower on this frame.
+    pub upgrade_selection: bool,
+    /// Reward chosen when the player skipped the next-wave countdown on this frame, if any.
+    pub skip_wave_countdown: Option<PreparationReward>,
+    /// Whether the player dismissed the after-action report on this frame.
+    pub dismiss_wave_report: bool,
+    /// Answer to the open merchant offer given on this frame, if any.
+    pub merchant_choice: Option<bool>,
+    /// Contract the player toggled for the next wave on this frame, if any.
+    pub toggle_contract: Option<WaveContract>,
+    /// Whether the player rotated the tower placement footprint on this frame.
+    pub rotate_placement: bool,
+    /// Whether the player switched to the next buildable tower kind on this frame.
+    pub cycle_tower_kind: bool,
+    /// Tower kind the player picked from the hotbar on this frame, if any.
+    pub select_tower_kind: Option<TowerKind>,
+    /// Wave seed the player pasted or re-rolled on this frame, if any.
+    pub set_wave_seed: Option<u64>,
+}
+
+/// Numbered selection group hotkey observed by an adapter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupHotkey {
+    /// Zero-based slot addressed by the hotkey.
+    pub slot: usize,
+    /// Whether the current selection should be stored in the slot instead of recalled from it.
+    pub assign: bool,
 }
 
 /// Per-frame diagnostics emitted by simulations to help adapters report performance breakdowns.
@@ -350,7 +426,38 @@ impl TileSpacePosition {
         } else {
             self.steps_per_tile
         };
-        self.column_steps % steps_per_tile == 0 && self.row_steps % steps_per_tile == 0
+        self.column_steps.is_multiple_of(steps_per_tile)
+            && self.row_steps.is_multiple_of(steps_per_tile)
+    }
+}
+
+/// Region a tower can attack once walls and other towers clip its range.
+///
+/// The outline is star-shaped around the tower centre: it lists, in angular order, the
+/// farthest point the tower can see in each sampled direction, in cell space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttackRegion {
+    center: Vec2,
+    outline: Vec<Vec2>,
+}
+
+impl AttackRegion {
+    /// Creates a region around `center` bounded by `outline`.
+    #[must_use]
+    pub fn new(center: Vec2, outline: Vec<Vec2>) -> Self {
+        Self { center, outline }
+    }
+
+    /// Centre of the tower the region belongs to, in cell space.
+    #[must_use]
+    pub const fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    /// Boundary points in angular order, in cell space.
+    #[must_use]
+    pub fn outline(&self) -> &[Vec2] {
+        &self.outline
     }
 }
 
@@ -404,6 +511,65 @@ impl SceneWall {
     }
 }
 
+/// Marks the gap in the perimeter wall that bugs leave the maze through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExitPortal {
+    /// Cells of the wall gap, spanning a single row.
+    pub region: CellRect,
+}
+
+impl ExitPortal {
+    /// Frames the wall gap directly above the world's exit target cells.
+    ///
+    /// Returns `None` when there are no target cells.
+    #[must_use]
+    pub fn from_target_cells(targets: &[CellCoord]) -> Option<Self> {
+        let first = targets.iter().map(|cell| cell.column()).min()?;
+        let last = targets.iter().map(|cell| cell.column()).max()?;
+        let row = targets.iter().map(|cell| cell.row()).min()?;
+        Some(Self {
+            region: CellRect::from_origin_and_size(
+                CellCoord::new(first, row.saturating_sub(1)),
+                CellRectSize::new(last - first + 1, 1),
+            ),
+        })
+    }
+}
+
+/// Border cell bugs may spawn from, hatched so the spawn edges read at a glance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpawnEdge {
+    /// Zero-based column of the spawner cell.
+    pub column: u32,
+    /// Zero-based row of the spawner cell.
+    pub row: u32,
+}
+
+impl SpawnEdge {
+    /// Creates a new spawn edge descriptor.
+    #[must_use]
+    pub const fn new(column: u32, row: u32) -> Self {
+        Self { column, row }
+    }
+}
+
+/// Destructible nest guarding a group of spawner cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestPresentation {
+    /// Spawner cells covered by the nest.
+    pub cells: Vec<CellCoord>,
+    /// Current and maximum health of the nest.
+    pub health: BugHealthPresentation,
+}
+
+impl NestPresentation {
+    /// Creates a new nest descriptor.
+    #[must_use]
+    pub fn new(cells: Vec<CellCoord>, health: BugHealthPresentation) -> Self {
+        Self { cells, health }
+    }
+}
+
 /// Describes a transient spawn effect tied to a specific cell.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SpawnEffect {
@@ -413,13 +579,33 @@ pub struct SpawnEffect {
     pub row: u32,
     /// Tint used when drawing the effect.
     pub color: Color,
+    /// Radius of the ring expressed in cell units.
+    pub radius: f32,
+    /// Lead time over which the ring telegraphs the upcoming spawn.
+    pub duration: Duration,
+    /// Portion of `duration` that has elapsed, clamped to `[0.0, 1.0]`.
+    pub progress: f32,
 }
 
 impl SpawnEffect {
     /// Creates a new spawn effect descriptor.
     #[must_use]
-    pub const fn new(column: u32, row: u32, color: Color) -> Self {
-        Self { column, row, color }
+    pub fn new(
+        column: u32,
+        row: u32,
+        color: Color,
+        radius: f32,
+        duration: Duration,
+        progress: f32,
+    ) -> Self {
+        Self {
+            column,
+            row,
+            color,
+            radius: radius.max(0.0),
+            duration,
+            progress: clamp_unit(progress),
+        }
     }
 }
 
@@ -460,6 +646,15 @@ impl TowerPreview {
     }
 }
 
+/// Route bugs would follow if the previewed tower were placed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathPreview {
+    /// Cells visited from the spawner to the exit, in walking order.
+    Route(Vec<CellCoord>),
+    /// The placement would cut every spawner off from the exits.
+    Blocked,
+}
+
 /// Feedback surfaced to adapters about the most recent tower interaction.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TowerInteractionFeedback {
@@ -711,6 +906,83 @@ pub struct BugPresentation {
     pub style: BugVisual,
     /// Health configuration used to draw the bug's health bar.
     pub health: BugHealthPresentation,
+    /// Whether the bug's health bar is drawn when health bars are enabled.
+    pub health_bar: bool,
+    /// Entrance of the squad the bug spawned with, while that entrance is still playing.
+    pub entrance: Option<FormationEntrance>,
+    /// Status effect the bug suffers, if any.
+    pub status: Option<BugStatus>,
+}
+
+/// Shared entrance animation state of a squad that spawned as a formation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FormationEntrance {
+    /// Identifier of the squad.
+    pub formation: FormationId,
+    /// Normalised entrance progress in the inclusive range `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl FormationEntrance {
+    /// Creates a new entrance state, clamping the progress into `0.0..=1.0`.
+    #[must_use]
+    pub fn new(formation: FormationId, progress: f32) -> Self {
+        Self {
+            formation,
+            progress: clamp_unit(progress),
+        }
+    }
+}
+
+/// Ice blue that slowed bugs are tinted towards.
+const SLOWED_TINT: Color = Color::from_rgb_u8(0x8c, 0xd8, 0xff);
+
+/// Distance, in cells, an escaping bug slides past the exit before it disappears.
+const BUG_ESCAPE_SLIDE_CELLS: f32 = 1.0;
+
+/// Bug that reached the exit and slides off the board while fading out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BugEscape {
+    /// Presentation of the bug on the last frame before it exited.
+    pub bug: BugPresentation,
+    /// Unit direction, in cell space, in which the bug leaves the board.
+    pub direction: Vec2,
+    /// Normalised escape progress in the inclusive range `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl BugEscape {
+    /// Creates a new escape, normalising the direction and clamping the progress.
+    #[must_use]
+    pub fn new(bug: BugPresentation, direction: Vec2, progress: f32) -> Self {
+        Self {
+            bug,
+            direction: direction.normalize_or_zero(),
+            progress: clamp_unit(progress),
+        }
+    }
+
+    /// Bug presentation displaced along the escape direction and faded by the progress.
+    #[must_use]
+    pub fn presentation(&self) -> BugPresentation {
+        let fade = |color: Color| Color {
+            alpha: color.alpha * (1.0 - self.progress),
+            ..color
+        };
+        let style = match self.bug.style {
+            BugVisual::PrimitiveCircle { color } => {
+                BugVisual::PrimitiveCircle { color: fade(color) }
+            }
+            BugVisual::Sprite { sprite, tint } => BugVisual::Sprite {
+                sprite,
+                tint: fade(tint),
+            },
+            BugVisual::Dot { color } => BugVisual::Dot { color: fade(color) },
+        };
+        let position =
+            self.bug.position() + self.direction * (BUG_ESCAPE_SLIDE_CELLS * self.progress);
+        BugPresentation::from_parts(self.bug.id, position, style, self.bug.health)
+    }
 }
 
 impl BugPresentation {
@@ -757,6 +1029,40 @@ impl BugPresentation {
             offset,
             style,
             health,
+            health_bar: true,
+            entrance: None,
+            status: None,
+        }
+    }
+
+    /// Marks the bug as part of a squad whose formation entrance is still playing.
+    #[must_use]
+    pub fn with_entrance(mut self, entrance: Option<FormationEntrance>) -> Self {
+        self.entrance = entrance;
+        self
+    }
+
+    /// Records the status effect the bug suffers.
+    #[must_use]
+    pub fn with_status(mut self, status: Option<BugStatus>) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Tints `color` to show the bug's status effect.
+    ///
+    /// Slowed bugs are drawn mixed towards ice blue, fading back to `color` as the slow
+    /// wears off.
+    #[must_use]
+    pub fn status_tint(&self, color: Color) -> Color {
+        let Some(status) = self.status else {
+            return color;
+        };
+        match status.effect {
+            StatusEffect::Slowed => {
+                let left = status.remaining_ms as f32 / status.effect.duration_ms() as f32;
+                color.mix(SLOWED_TINT, 0.25 + 0.5 * left.min(1.0))
+            }
         }
     }
 
@@ -767,6 +1073,74 @@ impl BugPresentation {
     }
 }
 
+/// Level of detail applied to bugs once a wave fields more of them than can be drawn in
+/// full every frame.
+///
+/// The policy runs while the scene is populated, so backends simply draw what the scene
+/// describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BugLodPolicy {
+    detail_budget: usize,
+}
+
+impl Default for BugLodPolicy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DETAIL_BUDGET)
+    }
+}
+
+impl BugLodPolicy {
+    /// Bugs drawn in full detail when no other budget is configured.
+    pub const DEFAULT_DETAIL_BUDGET: usize = 150;
+
+    /// Creates a policy that keeps at most `detail_budget` bugs in full detail.
+    #[must_use]
+    pub const fn new(detail_budget: usize) -> Self {
+        Self { detail_budget }
+    }
+
+    /// Reduces the bugs of `scene` beyond the detail budget to dots without health bars.
+    ///
+    /// The bugs nearest the camera focus, or the board centre without a camera, keep their
+    /// detail; ties are broken by bug identifier so the split is stable between frames.
+    /// Scenes within the budget are left untouched.
+    pub fn apply(&self, scene: &mut Scene) {
+        if scene.bugs.len() <= self.detail_budget {
+            return;
+        }
+
+        let focus = scene.camera_focus.map_or_else(
+            || {
+                let grid = &scene.tile_grid;
+                let cell_length = grid.cell_length();
+                if cell_length > f32::EPSILON {
+                    Vec2::new(grid.bordered_width(), grid.bordered_height()) / cell_length * 0.5
+                } else {
+                    Vec2::ZERO
+                }
+            },
+            |camera| camera.center,
+        );
+        let mut order: Vec<(f32, BugId, usize)> = scene
+            .bugs
+            .iter()
+            .enumerate()
+            .map(|(index, bug)| (bug.position().distance_squared(focus), bug.id, index))
+            .collect();
+        order.sort_by(|left, right| left.0.total_cmp(&right.0).then(left.1.cmp(&right.1)));
+
+        for &(_, _, index) in &order[self.detail_budget..] {
+            let bug = &mut scene.bugs[index];
+            let color = match bug.style {
+                BugVisual::PrimitiveCircle { color } | BugVisual::Dot { color } => color,
+                BugVisual::Sprite { tint, .. } => tint,
+            };
+            bug.style = BugVisual::Dot { color };
+            bug.health_bar = false;
+        }
+    }
+}
+
 fn decompose_axis(value: f32) -> u32 {
     if value.is_nan() {
         return 0;
@@ -807,6 +1181,29 @@ impl BugHealthPresentation {
     }
 }
 
+/// Details about the bug currently hovered by the cursor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BugInspection {
+    /// Identifier allocated to the bug by the world.
+    pub bug: BugId,
+    /// Bug position expressed in cell units.
+    pub position: Vec2,
+    /// Species the bug was spawned from, when it can be resolved.
+    pub species: Option<SpeciesId>,
+    /// Current and maximum health of the bug.
+    pub health: BugHealthPresentation,
+    /// Movement speed relative to the configured baseline step cadence.
+    pub speed_multiplier: f32,
+    /// Milliseconds the bug currently waits between steps.
+    pub step_ms: u32,
+    /// Gold paid for killing the bug at the current reward multiplier.
+    pub bounty: Gold,
+    /// Scripted phase when the bug is a boss.
+    pub boss_phase: Option<BossPhase>,
+    /// Lasting effects on the bug, such as a slow, its archetype or the wave shield.
+    pub effects: Vec<BugEffect>,
+}
+
 /// Cell-space line segment describing an active tower targeting beam.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TowerTargetLine {
@@ -833,6 +1230,123 @@ impl TowerTargetLine {
     }
 }
 
+/// Cooldown indicator rendered alongside a tower while in attack mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TowerCooldownIndicator {
+    /// Identifier of the tower that owns the cooldown.
+    pub tower: TowerId,
+    /// Region of cells occupied by the tower.
+    pub region: CellRect,
+    /// Normalised reload progress in the inclusive range `0.0..=1.0`, where `1.0` means ready.
+    pub progress: f32,
+    /// Intensity of the "ready" flash in the inclusive range `0.0..=1.0`.
+    pub ready_flash: f32,
+    /// Heat gauge fill in the inclusive range `0.0..=1.0`, present while tower heat is enabled.
+    pub heat: Option<f32>,
+    /// Whether the tower is stalled after overheating.
+    pub overheated: bool,
+}
+
+impl TowerCooldownIndicator {
+    /// Creates a new cooldown indicator, clamping both ratios into `0.0..=1.0`.
+    #[must_use]
+    pub fn new(tower: TowerId, region: CellRect, progress: f32, ready_flash: f32) -> Self {
+        Self {
+            tower,
+            region,
+            progress: clamp_unit(progress),
+            ready_flash: clamp_unit(ready_flash),
+            heat: None,
+            overheated: false,
+        }
+    }
+
+    /// Attaches a heat gauge to the indicator, clamping the fill into `0.0..=1.0`.
+    #[must_use]
+    pub fn with_heat(mut self, heat: f32, overheated: bool) -> Self {
+        self.heat = Some(clamp_unit(heat));
+        self.overheated = overheated;
+        self
+    }
+
+    /// Reports whether the tower has finished reloading.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.progress >= 1.0
+    }
+}
+
+fn clamp_unit(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Puff left where a projectile missed its target, fading as it plays out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProjectileWhiff {
+    /// Position of the missed bug expressed in cell coordinates.
+    pub position: Vec2,
+    /// Normalised effect progress in the inclusive range `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl ProjectileWhiff {
+    /// Creates a new whiff, clamping the progress.
+    #[must_use]
+    pub fn new(position: Vec2, progress: f32) -> Self {
+        Self {
+            position,
+            progress: clamp_unit(progress),
+        }
+    }
+}
+
+/// Text naming a combo, rising from the bug it hit as it fades.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComboFlash {
+    /// Position of the bug the combo hit, expressed in cell coordinates.
+    pub position: Vec2,
+    /// Combo that triggered.
+    pub combo: Combo,
+    /// Normalised effect progress in the inclusive range `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl ComboFlash {
+    /// Creates a new combo flash, clamping the progress.
+    #[must_use]
+    pub fn new(position: Vec2, combo: Combo, progress: f32) -> Self {
+        Self {
+            position,
+            combo,
+            progress: clamp_unit(progress),
+        }
+    }
+}
+
+/// Ring expanding around an important event the player's attention is drawn to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusPulse {
+    /// Cell-space point the ring is centred on.
+    pub center: Vec2,
+    /// Normalised pulse progress in the inclusive range `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl FocusPulse {
+    /// Creates a new pulse, clamping the progress.
+    #[must_use]
+    pub fn new(center: Vec2, progress: f32) -> Self {
+        Self {
+            center,
+            progress: clamp_unit(progress),
+        }
+    }
+}
+
 /// Projectile currently travelling between a tower and its cached target.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SceneProjectile {
@@ -899,6 +1413,213 @@ impl GoldPresentation {
     }
 }
 
+/// Gold gain floating from the play area toward the gold counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GoldGain {
+    amount: Gold,
+    elapsed: Duration,
+}
+
+impl GoldGain {
+    /// Returns the amount of gold gained.
+    #[must_use]
+    pub const fn amount(&self) -> Gold {
+        self.amount
+    }
+
+    /// Fraction of the float animation that has completed, in the range `[0, 1]`.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        (self.elapsed.as_secs_f32() / GoldFeedback::GAIN_DURATION.as_secs_f32()).clamp(0.0, 1.0)
+    }
+}
+
+/// Animated economy feedback derived from gold-related world events.
+///
+/// Gains spawn `+N` floats, while spending and unaffordable placements flash the counter red.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GoldFeedback {
+    last_amount: Option<Gold>,
+    gains: Vec<GoldGain>,
+    flash_elapsed: Option<Duration>,
+}
+
+impl GoldFeedback {
+    /// Time taken by a gain to float into the gold counter.
+    pub const GAIN_DURATION: Duration = Duration::from_millis(900);
+    /// Time taken by the red spend/reject flash to fade out.
+    pub const FLASH_DURATION: Duration = Duration::from_millis(350);
+
+    /// Creates feedback state seeded with the currently displayed gold amount.
+    #[must_use]
+    pub fn new(amount: Gold) -> Self {
+        Self {
+            last_amount: Some(amount),
+            ..Self::default()
+        }
+    }
+
+    /// Starts animations for the gold changes and rejected placements in `events`.
+    pub fn observe(&mut self, events: &[Event]) {
+        for event in events {
+            match event {
+                Event::GoldChanged { amount } => {
+                    if let Some(previous) = self.last_amount {
+                        if amount.get() > previous.get() {
+                            self.gains.push(GoldGain {
+                                amount: Gold::new(amount.get() - previous.get()),
+                                elapsed: Duration::ZERO,
+                            });
+                        } else if amount.get() < previous.get() {
+                            self.flash_elapsed = Some(Duration::ZERO);
+                        }
+                    }
+                    self.last_amount = Some(*amount);
+                }
+                Event::TowerPlacementRejected {
+                    reason: PlacementError::InsufficientFunds,
+                    ..
+                } => {
+                    self.flash_elapsed = Some(Duration::ZERO);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Advances the running animations and drops the ones that have finished.
+    pub fn advance(&mut self, dt: Duration) {
+        for gain in &mut self.gains {
+            gain.elapsed = gain.elapsed.saturating_add(dt);
+        }
+        self.gains.retain(|gain| gain.elapsed < Self::GAIN_DURATION);
+        if let Some(elapsed) = self.flash_elapsed.as_mut() {
+            *elapsed = elapsed.saturating_add(dt);
+            if *elapsed >= Self::FLASH_DURATION {
+                self.flash_elapsed = None;
+            }
+        }
+    }
+
+    /// Gains that are still floating toward the counter.
+    #[must_use]
+    pub fn gains(&self) -> &[GoldGain] {
+        &self.gains
+    }
+
+    /// Intensity of the red spend/reject flash in the range `[0, 1]`.
+    #[must_use]
+    pub fn flash(&self) -> f32 {
+        self.flash_elapsed.map_or(0.0, |elapsed| {
+            1.0 - (elapsed.as_secs_f32() / Self::FLASH_DURATION.as_secs_f32()).clamp(0.0, 1.0)
+        })
+    }
+}
+
+/// Snapshot of the in-game clock and active wave stopwatch for UI presentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockPresentation {
+    elapsed: Duration,
+    wave_elapsed: Option<Duration>,
+}
+
+impl ClockPresentation {
+    /// Creates a new clock descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(elapsed: Duration, wave_elapsed: Option<Duration>) -> Self {
+        Self {
+            elapsed,
+            wave_elapsed,
+        }
+    }
+
+    /// Returns the total in-game time elapsed.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the time spent in the active wave, if a wave is running.
+    #[must_use]
+    pub const fn wave_elapsed(&self) -> Option<Duration> {
+        self.wave_elapsed
+    }
+}
+
+/// Furthest wave launched on the current level for UI presentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LevelProgressPresentation {
+    level: LevelId,
+    wave: u32,
+}
+
+impl LevelProgressPresentation {
+    /// Creates a new progress descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(level: LevelId, wave: u32) -> Self {
+        Self { level, wave }
+    }
+
+    /// Returns the level being played.
+    #[must_use]
+    pub const fn level(&self) -> LevelId {
+        self.level
+    }
+
+    /// Returns the one-based number of the latest wave launched on the level.
+    #[must_use]
+    pub const fn wave(&self) -> u32 {
+        self.wave
+    }
+}
+
+/// Camera framing requested by the simulation, expressed in cell space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraFocus {
+    /// Cell-space point that should appear at the centre of the play area.
+    pub center: Vec2,
+    /// Magnification applied on top of the fit-to-screen scale.
+    pub zoom: f32,
+}
+
+impl CameraFocus {
+    /// Creates a new camera focus descriptor.
+    #[must_use]
+    pub const fn new(center: Vec2, zoom: f32) -> Self {
+        Self { center, zoom }
+    }
+}
+
+/// Snapshot of the automatic next-wave countdown for UI presentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaveCountdownPresentation {
+    remaining: Duration,
+    early_start_bonus: Gold,
+}
+
+impl WaveCountdownPresentation {
+    /// Creates a new countdown descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(remaining: Duration, early_start_bonus: Gold) -> Self {
+        Self {
+            remaining,
+            early_start_bonus,
+        }
+    }
+
+    /// Returns the time left before the next wave launches automatically.
+    #[must_use]
+    pub const fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Returns the gold awarded for skipping the countdown now.
+    #[must_use]
+    pub const fn early_start_bonus(&self) -> Gold {
+        self.early_start_bonus
+    }
+}
+
 /// Snapshot of the active difficulty level for UI presentation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DifficultyPresentation {
@@ -939,6 +1660,56 @@ impl AnalyticsPresentation {
     }
 }
 
+/// After-action report shown once a wave resolves, until the player dismisses it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaveReportPresentation {
+    report: WaveReport,
+}
+
+impl WaveReportPresentation {
+    /// Creates a new wave report descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(report: WaveReport) -> Self {
+        Self { report }
+    }
+
+    /// Returns the wave report captured for presentation.
+    #[must_use]
+    pub const fn report(&self) -> &WaveReport {
+        &self.report
+    }
+}
+
+/// Random between-wave event announced to the player until the next wave starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetweenWaveEventPresentation {
+    event: BetweenWaveEvent,
+    awaiting_choice: bool,
+}
+
+impl BetweenWaveEventPresentation {
+    /// Creates a new between-wave event descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(event: BetweenWaveEvent, awaiting_choice: bool) -> Self {
+        Self {
+            event,
+            awaiting_choice,
+        }
+    }
+
+    /// Returns the event that was rolled.
+    #[must_use]
+    pub const fn event(&self) -> BetweenWaveEvent {
+        self.event
+    }
+
+    /// Reports whether the event still waits for the player to accept or decline it.
+    #[must_use]
+    pub const fn awaiting_choice(&self) -> bool {
+        self.awaiting_choice
+    }
+}
+
 /// Snapshot describing how a specific difficulty option should be presented to the player.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DifficultyButtonPresentation {
@@ -1020,6 +1791,281 @@ impl DifficultySelectionPresentation {
     }
 }
 
+/// Contract offered in the control panel together with whether the player accepted it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaveContractPresentation {
+    contract: WaveContract,
+    accepted: bool,
+}
+
+impl WaveContractPresentation {
+    /// Creates a new contract descriptor for presentation purposes.
+    #[must_use]
+    pub const fn new(contract: WaveContract, accepted: bool) -> Self {
+        Self { contract, accepted }
+    }
+
+    /// Returns the contract being offered.
+    #[must_use]
+    pub const fn contract(&self) -> WaveContract {
+        self.contract
+    }
+
+    /// Reports whether the contract applies to the next wave.
+    #[must_use]
+    pub const fn accepted(&self) -> bool {
+        self.accepted
+    }
+}
+
+/// One species of the upcoming wave, shown as a tinted icon with its bug count.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpeciesPreviewPresentation {
+    tint: Color,
+    count: u32,
+    health: u32,
+    rank: BugRank,
+    threat_share: f32,
+}
+
+impl SpeciesPreviewPresentation {
+    /// Tint applied to bugs of the species.
+    #[must_use]
+    pub const fn tint(&self) -> Color {
+        self.tint
+    }
+
+    /// Number of bugs of the species the wave spawns.
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Base health of a bug of the species.
+    #[must_use]
+    pub const fn health(&self) -> u32 {
+        self.health
+    }
+
+    /// Rank the species spawns with.
+    #[must_use]
+    pub const fn rank(&self) -> BugRank {
+        self.rank
+    }
+
+    /// Fraction of the wave's threat the species carries, in the range 0.0..=1.0.
+    #[must_use]
+    pub const fn threat_share(&self) -> f32 {
+        self.threat_share
+    }
+}
+
+/// Species composition of the next wave, ranked by threat so players can pick counters.
+///
+/// A bug's threat is its health multiplied by the steps it takes per second, i.e. the
+/// damage per second towers must deal to stop it from advancing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WaveCompositionPresentation {
+    difficulty: WaveDifficulty,
+    species: Vec<SpeciesPreviewPresentation>,
+}
+
+impl WaveCompositionPresentation {
+    /// Summarises the spawns of `plan`, launched at `difficulty`, per species.
+    #[must_use]
+    pub fn from_plan(difficulty: WaveDifficulty, plan: &PressureWavePlan) -> Self {
+        let prototypes = plan.prototypes();
+        let mut totals = vec![(0_u32, 0.0_f64); prototypes.len()];
+        for spawn in plan.spawns() {
+            let Some((prototype, (count, threat))) = usize::try_from(spawn.species_id())
+                .ok()
+                .and_then(|index| Some((prototypes.get(index)?, totals.get_mut(index)?)))
+            else {
+                continue;
+            };
+            let steps_per_second =
+                1_000.0 / f64::from(prototype.step_ms_at(spawn.speed_mult()).get());
+            *count += 1;
+            *threat += f64::from(spawn.hp()) * steps_per_second;
+        }
+
+        let wave_threat: f64 = totals.iter().map(|(_, threat)| threat).sum();
+        let mut ranked: Vec<(f64, SpeciesPreviewPresentation)> = prototypes
+            .iter()
+            .zip(totals)
+            .filter(|(_, (count, _))| *count > 0)
+            .map(|(prototype, (count, threat))| {
+                let color = prototype.color();
+                let share = if wave_threat > 0.0 {
+                    threat / wave_threat
+                } else {
+                    0.0
+                };
+                let species = SpeciesPreviewPresentation {
+                    tint: Color::from_rgb_u8(color.red(), color.green(), color.blue()),
+                    count,
+                    health: prototype.health().get(),
+                    rank: prototype.rank(),
+                    threat_share: share as f32,
+                };
+                (threat, species)
+            })
+            .collect();
+        ranked.sort_by(|(left, _), (right, _)| right.total_cmp(left));
+
+        Self {
+            difficulty,
+            species: ranked.into_iter().map(|(_, species)| species).collect(),
+        }
+    }
+
+    /// Difficulty the previewed wave launches at.
+    #[must_use]
+    pub const fn difficulty(&self) -> WaveDifficulty {
+        self.difficulty
+    }
+
+    /// Species of the wave, most threatening first.
+    #[must_use]
+    pub fn species(&self) -> &[SpeciesPreviewPresentation] {
+        &self.species
+    }
+
+    /// Total number of bugs the wave spawns.
+    #[must_use]
+    pub fn bug_count(&self) -> u32 {
+        self.species.iter().map(|species| species.count).sum()
+    }
+}
+
+/// Render layers in back-to-front drawing order.
+///
+/// Backends draw every layer in turn, so anything in a later layer covers anything in an
+/// earlier one. Within a layer, draw order is up to the backend but must be stable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderLayer {
+    /// Ground sprites and the builder-mode tile grid.
+    Ground,
+    /// Cells that warn about danger, such as spawn telegraphs and the traffic heatmap.
+    Hazards,
+    /// Perimeter and interior walls.
+    Walls,
+    /// Bugs, including bugs still escaping through the exit.
+    Bugs,
+    /// Towers and the bridge decks they stand on, drawn over bugs tunnelling beneath.
+    Towers,
+    /// Projectiles in flight.
+    Projectiles,
+    /// Transient effects such as targeting beams and formation rings.
+    Fx,
+    /// Indicators anchored to the board: health bars, reload bars, previews and tooltips.
+    UiOverlays,
+}
+
+impl RenderLayer {
+    /// Every layer in back-to-front drawing order.
+    pub const ALL: [Self; 8] = [
+        Self::Ground,
+        Self::Hazards,
+        Self::Walls,
+        Self::Bugs,
+        Self::Towers,
+        Self::Projectiles,
+        Self::Fx,
+        Self::UiOverlays,
+    ];
+}
+
+/// Scene items drawn in a fixed render layer.
+pub trait Layered {
+    /// Layer the item is drawn in.
+    const LAYER: RenderLayer;
+}
+
+impl Layered for GroundSpriteTiles {
+    const LAYER: RenderLayer = RenderLayer::Ground;
+}
+
+impl Layered for SpawnEffect {
+    const LAYER: RenderLayer = RenderLayer::Hazards;
+}
+
+impl Layered for DangerHeatmap {
+    const LAYER: RenderLayer = RenderLayer::Hazards;
+}
+
+impl Layered for SpawnEdge {
+    const LAYER: RenderLayer = RenderLayer::Hazards;
+}
+
+impl Layered for NestPresentation {
+    const LAYER: RenderLayer = RenderLayer::Hazards;
+}
+
+impl Layered for SceneWall {
+    const LAYER: RenderLayer = RenderLayer::Walls;
+}
+
+impl Layered for ExitPortal {
+    const LAYER: RenderLayer = RenderLayer::Walls;
+}
+
+impl Layered for BugPresentation {
+    const LAYER: RenderLayer = RenderLayer::Bugs;
+}
+
+impl Layered for BugEscape {
+    const LAYER: RenderLayer = RenderLayer::Bugs;
+}
+
+impl Layered for SceneTower {
+    const LAYER: RenderLayer = RenderLayer::Towers;
+}
+
+impl Layered for SceneProjectile {
+    const LAYER: RenderLayer = RenderLayer::Projectiles;
+}
+
+impl Layered for ProjectileWhiff {
+    const LAYER: RenderLayer = RenderLayer::Projectiles;
+}
+
+impl Layered for FocusPulse {
+    const LAYER: RenderLayer = RenderLayer::Fx;
+}
+
+impl Layered for ComboFlash {
+    const LAYER: RenderLayer = RenderLayer::Fx;
+}
+
+impl Layered for TowerTargetLine {
+    const LAYER: RenderLayer = RenderLayer::Fx;
+}
+
+impl Layered for FormationEntrance {
+    const LAYER: RenderLayer = RenderLayer::Fx;
+}
+
+impl Layered for BugHealthPresentation {
+    const LAYER: RenderLayer = RenderLayer::UiOverlays;
+}
+
+impl Layered for TowerCooldownIndicator {
+    const LAYER: RenderLayer = RenderLayer::UiOverlays;
+}
+
+impl Layered for TowerPreview {
+    const LAYER: RenderLayer = RenderLayer::UiOverlays;
+}
+
+impl Layered for PathPreview {
+    const LAYER: RenderLayer = RenderLayer::UiOverlays;
+}
+
+impl Layered for BugInspection {
+    const LAYER: RenderLayer = RenderLayer::UiOverlays;
+}
+
 /// Scene description combining the tile grid, perimeter wall colour and inhabitants.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Scene {
@@ -1031,6 +2077,10 @@ pub struct Scene {
     pub ground: Option<GroundSpriteTiles>,
     /// Cell-sized walls populating the maze interior.
     pub walls: Vec<SceneWall>,
+    /// Gap in the perimeter wall bugs leave through, if the maze has an exit.
+    pub exit_portal: Option<ExitPortal>,
+    /// Border cells bugs may spawn from.
+    pub spawn_edges: Vec<SpawnEdge>,
     /// Bugs currently visible within the maze, positioned using deterministic cell descriptors.
     pub bugs: Vec<BugPresentation>,
     /// Towers currently visible within the maze.
@@ -1041,12 +2091,32 @@ pub struct Scene {
     pub spawn_effects: Vec<SpawnEffect>,
     /// Targeting beams emitted by towers while in attack mode.
     pub tower_targets: Vec<TowerTargetLine>,
+    /// Reload indicators for towers while in attack mode.
+    pub tower_cooldowns: Vec<TowerCooldownIndicator>,
     /// Tower currently hovered by the cursor, if any.
     pub hovered_tower: Option<TowerId>,
+    /// Bug currently hovered by the cursor, if any.
+    pub hovered_bug: Option<BugInspection>,
+    /// Bug flagged by the player as the priority target, if any.
+    pub priority_target: Option<BugId>,
+    /// Towers currently selected by the player, in ascending identifier order.
+    pub selected_towers: Vec<TowerId>,
+    /// Cell-space rectangle covered by an in-progress selection box, if any.
+    pub selection_box: Option<CellRect>,
+    /// Tower origin cells stored in each numbered selection group, first slot first; `None`
+    /// when the simulation does not keep selection groups.
+    pub selection_groups: Option<Vec<Vec<CellCoord>>>,
     /// Active play mode for the simulation.
     pub play_mode: PlayMode,
     /// Optional builder placement preview emitted by the simulation.
     pub tower_preview: Option<TowerPreview>,
+    /// Route bugs would take if the previewed tower were placed, if a preview is active.
+    pub path_preview: Option<PathPreview>,
+    /// Line-of-sight clipped range of the previewed or hovered tower; `None` shows the full
+    /// range circle.
+    pub attack_region: Option<AttackRegion>,
+    /// Historical bug traffic heatmap offered while planning builds.
+    pub danger_heatmap: Option<DangerHeatmap>,
     /// Footprint of the currently selected tower expressed in tile units.
     pub active_tower_footprint_tiles: Option<Vec2>,
     /// Feedback about the last tower placement/removal attempt.
@@ -1055,63 +2125,132 @@ pub struct Scene {
     pub control_panel: Option<ControlPanelView>,
     /// Current gold balance presented to the player.
     pub gold: Option<GoldPresentation>,
+    /// Animated gold gains and spend/reject flashes for the gold counter.
+    pub gold_feedback: GoldFeedback,
+    /// In-game clock and wave stopwatch presented to the player.
+    pub clock: Option<ClockPresentation>,
+    /// Latest wave launched on the current level, once a wave has launched.
+    pub level_progress: Option<LevelProgressPresentation>,
+    /// Automatic next-wave countdown presented to the player, if one is running.
+    pub wave_countdown: Option<WaveCountdownPresentation>,
+    /// Building time left before Builder mode ends automatically, if building time is limited.
+    pub builder_countdown: Option<Duration>,
     /// Current difficulty level presented to the player.
     pub difficulty: Option<DifficultyPresentation>,
     /// Presentation state for the Normal/Hard difficulty buttons.
     pub difficulty_selection: Option<DifficultySelectionPresentation>,
+    /// Contracts the player may accept before the next wave; empty while a wave is running.
+    pub wave_contracts: Vec<WaveContractPresentation>,
+    /// Species composition of the next wave; `None` while a wave is running or unplanned.
+    pub wave_composition: Option<WaveCompositionPresentation>,
     /// Latest analytics report available to the adapter, if any.
     pub analytics: Option<AnalyticsPresentation>,
+    /// After-action report for the most recently resolved wave, if not yet dismissed.
+    pub wave_report: Option<WaveReportPresentation>,
+    /// Random event rolled after the last wave, if any.
+    pub between_wave_event: Option<BetweenWaveEventPresentation>,
     /// Whether the control panel should enable the replay button.
     pub replay_available: bool,
+    /// Camera framing requested by the simulation; `None` shows the whole board.
+    pub camera_focus: Option<CameraFocus>,
+    /// Whether the session has run with sandbox rules and is excluded from records.
+    pub sandbox: bool,
+    /// Cells covered by a bridge deck that ground bugs tunnel beneath.
+    pub bridges: Vec<CellCoord>,
+    /// Bugs that recently reached the exit and are still sliding off the board.
+    pub escaping_bugs: Vec<BugEscape>,
+    /// Projectiles that recently missed their target.
+    pub whiffs: Vec<ProjectileWhiff>,
+    /// Toasts explaining recently rejected player actions.
+    pub notifications: Notifications,
+    /// Ring drawing attention to an important event, if one is playing.
+    pub focus_pulse: Option<FocusPulse>,
+    /// Void cells, such as water, that bugs cannot walk and towers cannot be built on.
+    pub void_cells: Vec<CellCoord>,
+    /// Open nests that towers can destroy to close their spawners.
+    pub nests: Vec<NestPresentation>,
+    /// Tower kind the builder places next, highlighted in the hotbar; `None` hides the hotbar.
+    pub selected_tower_kind: Option<TowerKind>,
+    /// Internal world counters for the developer overlay; `None` when the simulation
+    /// does not report them.
+    pub debug_stats: Option<WorldDebugStats>,
+    /// Seed the next wave's plan derives from; `None` when the simulation does not expose it.
+    pub wave_seed: Option<u64>,
+    /// Shield shared by the active wave's bugs; `None` unless the wave was launched shielded.
+    pub wave_shield: Option<BugHealthPresentation>,
+    /// Bugs that may still reach the exit before the round is lost, out of the base's full
+    /// health; `None` when the simulation does not report it.
+    pub base_health: Option<BugHealthPresentation>,
+    /// Prestige awarded by the runs that ended during this session so far.
+    pub prestige_earned: u32,
+    /// Combo texts flashing where recent hits triggered a combo.
+    pub combo_flashes: Vec<ComboFlash>,
 }
 
 impl Scene {
-    /// Creates a new scene descriptor.
+    /// Creates a scene showing the board in the provided play mode.
+    ///
+    /// Everything else starts empty; set the remaining public fields directly, for example
+    /// with struct update syntax: `Scene { bugs, ..Scene::new(grid, wall_color, mode) }`.
     #[must_use]
-    #[allow(clippy::too_many_arguments)] // Scene construction intentionally enumerates every channel explicitly.
-    pub fn new(
-        tile_grid: TileGridPresentation,
-        wall_color: Color,
-        ground: Option<GroundSpriteTiles>,
-        walls: Vec<SceneWall>,
-        bugs: Vec<BugPresentation>,
-        towers: Vec<SceneTower>,
-        projectiles: Vec<SceneProjectile>,
-        spawn_effects: Vec<SpawnEffect>,
-        tower_targets: Vec<TowerTargetLine>,
-        hovered_tower: Option<TowerId>,
-        play_mode: PlayMode,
-        tower_preview: Option<TowerPreview>,
-        active_tower_footprint_tiles: Option<Vec2>,
-        tower_feedback: Option<TowerInteractionFeedback>,
-        control_panel: Option<ControlPanelView>,
-        gold: Option<GoldPresentation>,
-        difficulty: Option<DifficultyPresentation>,
-        difficulty_selection: Option<DifficultySelectionPresentation>,
-        analytics: Option<AnalyticsPresentation>,
-        replay_available: bool,
-    ) -> Self {
+    pub fn new(tile_grid: TileGridPresentation, wall_color: Color, play_mode: PlayMode) -> Self {
         Self {
             tile_grid,
             wall_color,
-            ground,
-            walls,
-            bugs,
-            towers,
-            projectiles,
-            spawn_effects,
-            tower_targets,
-            hovered_tower,
+            ground: None,
+            walls: Vec::new(),
+            exit_portal: None,
+            spawn_edges: Vec::new(),
+            bugs: Vec::new(),
+            towers: Vec::new(),
+            projectiles: Vec::new(),
+            spawn_effects: Vec::new(),
+            tower_targets: Vec::new(),
+            tower_cooldowns: Vec::new(),
+            hovered_tower: None,
+            hovered_bug: None,
+            priority_target: None,
+            selected_towers: Vec::new(),
+            selection_box: None,
+            selection_groups: None,
             play_mode,
-            tower_preview,
-            active_tower_footprint_tiles,
-            tower_feedback,
-            control_panel,
-            gold,
-            difficulty,
-            difficulty_selection,
-            analytics,
-            replay_available,
+            tower_preview: None,
+            path_preview: None,
+            attack_region: None,
+            danger_heatmap: None,
+            active_tower_footprint_tiles: None,
+            tower_feedback: None,
+            control_panel: None,
+            gold: None,
+            gold_feedback: GoldFeedback::default(),
+            clock: None,
+            level_progress: None,
+            wave_countdown: None,
+            builder_countdown: None,
+            difficulty: None,
+            difficulty_selection: None,
+            wave_contracts: Vec::new(),
+            wave_composition: None,
+            analytics: None,
+            wave_report: None,
+            between_wave_event: None,
+            replay_available: false,
+            camera_focus: None,
+            sandbox: false,
+            bridges: Vec::new(),
+            escaping_bugs: Vec::new(),
+            whiffs: Vec::new(),
+            notifications: Notifications::default(),
+            focus_pulse: None,
+            void_cells: Vec::new(),
+            nests: Vec::new(),
+            selected_tower_kind: None,
+            debug_stats: None,
+            wave_seed: None,
+            wave_shield: None,
+            base_health: None,
+            prestige_earned: 0,
+            combo_flashes: Vec::new(),
         }
     }
 
@@ -1152,13 +2291,89 @@ impl Presentation {
 pub trait RenderingBackend {
     /// Runs the rendering backend until it is requested to exit.
     ///
-    /// The provided `update_scene` closure receives the simulated frame delta,
-    /// per-frame input captured by the adapter, and may mutate the scene before
-    /// it is rendered, allowing adapters to animate world snapshots
-    /// deterministically.
+    /// The provided `update_scene` closure receives the fixed simulation updates due on
+    /// the frame, as released by a [`FixedTimestep`], per-frame input captured by the
+    /// adapter, and may mutate the scene before it is rendered, allowing adapters to
+    /// animate world snapshots deterministically whatever the display refresh rate.
     fn run<F>(self, presentation: Presentation, update_scene: F) -> AnyResult<()>
     where
-        F: FnMut(Duration, FrameInput, &mut Scene) -> FrameSimulationBreakdown + 'static;
+        F: FnMut(FrameTick, FrameInput, &mut Scene) -> FrameSimulationBreakdown + 'static;
+}
+
+/// Source of the input snapshot handed to the scene update on every frame.
+///
+/// Backends gather input from their windowing library through this trait, which lets
+/// [`ScriptedFrameInput`] stand in for a real window when driving runner loops in tests.
+pub trait FrameInputSource {
+    /// Returns the input observed for the next frame of `scene`.
+    fn next_frame(&mut self, scene: &Scene) -> FrameInput;
+}
+
+/// Frame input source that replays a scripted sequence of frames and then idles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScriptedFrameInput {
+    frames: VecDeque<FrameInput>,
+}
+
+impl ScriptedFrameInput {
+    /// Creates a script replaying the provided frames in order.
+    #[must_use]
+    pub fn new<I>(frames: I) -> Self
+    where
+        I: IntoIterator<Item = FrameInput>,
+    {
+        Self {
+            frames: frames.into_iter().collect(),
+        }
+    }
+
+    /// Appends a frame carrying the provided input.
+    #[must_use]
+    pub fn then(mut self, input: FrameInput) -> Self {
+        self.frames.push_back(input);
+        self
+    }
+
+    /// Appends `frames` frames without any input.
+    #[must_use]
+    pub fn idle(mut self, frames: usize) -> Self {
+        self.frames
+            .extend(std::iter::repeat_n(FrameInput::default(), frames));
+        self
+    }
+
+    /// Number of scripted frames that have not been replayed yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+impl FrameInputSource for ScriptedFrameInput {
+    fn next_frame(&mut self, _scene: &Scene) -> FrameInput {
+        self.frames.pop_front().unwrap_or_default()
+    }
+}
+
+/// Drives a scene update for `frames` frames of `dt` without a window.
+///
+/// Mirrors the contract of [`RenderingBackend::run`]: every frame pulls input from `source`
+/// and hands it to `update_scene` together with the scene and a tick running one update of
+/// `dt`.
+pub fn run_frames<S, F>(
+    source: &mut S,
+    scene: &mut Scene,
+    dt: Duration,
+    frames: usize,
+    mut update_scene: F,
+) where
+    S: FrameInputSource + ?Sized,
+    F: FnMut(FrameTick, FrameInput, &mut Scene) -> FrameSimulationBreakdown,
+{
+    for _ in 0..frames {
+        let input = source.next_frame(scene);
+        let _ = update_scene(FrameTick::single(dt), input, scene);
+    }
 }
 
 /// Errors that can occur when constructing rendering descriptors.
@@ -1339,6 +2554,30 @@ mod tests {
         assert!((bug.position() - expected).length() <= f32::EPSILON);
     }
 
+    #[test]
+    fn slowed_bugs_are_tinted_blue_until_the_slow_wears_off() {
+        let red = Color::from_rgb_u8(255, 0, 0);
+        let bug = BugPresentation::new_circle(
+            BugId::new(7),
+            Vec2::new(3.5, 4.5),
+            red,
+            BugHealthPresentation::new(3, 3),
+        );
+        let slowed = |remaining_ms| {
+            bug.with_status(Some(BugStatus {
+                effect: StatusEffect::Slowed,
+                remaining_ms,
+            }))
+            .status_tint(red)
+        };
+
+        assert_eq!(bug.status_tint(red), red);
+        let fresh = slowed(StatusEffect::Slowed.duration_ms());
+        let fading = slowed(100);
+        assert!(fresh.blue > fading.blue && fading.blue > red.blue);
+        assert!(fresh.red < fading.red && fading.red < red.red);
+    }
+
     #[test]
     fn sprite_instance_square_preserves_defaults() {
         let sprite = SpriteInstance::square(SpriteKey::BugBody, Vec2::splat(1.25));
@@ -1477,6 +2716,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gold_feedback_floats_gains_and_flashes_on_spend_or_reject() {
+        let mut feedback = GoldFeedback::new(Gold::new(10));
+        feedback.observe(&[Event::GoldChanged {
+            amount: Gold::new(15),
+        }]);
+        assert_eq!(feedback.gains().len(), 1);
+        assert_eq!(feedback.gains()[0].amount(), Gold::new(5));
+        assert_eq!(feedback.flash(), 0.0);
+
+        feedback.advance(GoldFeedback::GAIN_DURATION / 2);
+        assert!((feedback.gains()[0].progress() - 0.5).abs() < 1e-3);
+
+        feedback.observe(&[Event::GoldChanged {
+            amount: Gold::new(3),
+        }]);
+        assert_eq!(feedback.flash(), 1.0);
+
+        feedback.advance(GoldFeedback::GAIN_DURATION);
+        assert!(feedback.gains().is_empty());
+        assert_eq!(feedback.flash(), 0.0);
+
+        feedback.observe(&[Event::TowerPlacementRejected {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(0, 0),
+            reason: PlacementError::InsufficientFunds,
+        }]);
+        assert_eq!(feedback.flash(), 1.0);
+    }
+
     #[test]
     fn clamp_world_position_limits_coordinates_to_grid_bounds() {
         let presentation = TileGridPresentation::new(5, 4, 32.0, 4, Color::from_rgb_u8(0, 0, 0))
@@ -1566,28 +2835,10 @@ mod tests {
             BugHealthPresentation::new(3, 3),
         )];
 
-        let scene = Scene::new(
-            tile_grid,
-            wall_color,
-            None,
-            Vec::new(),
-            bugs.clone(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            None,
-            PlayMode::Attack,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
-        );
+        let scene = Scene {
+            bugs: bugs.clone(),
+            ..Scene::new(tile_grid, wall_color, PlayMode::Attack)
+        };
 
         assert_eq!(scene.tile_grid, tile_grid);
         assert_eq!(scene.wall_color, wall_color);
@@ -1600,6 +2851,10 @@ mod tests {
         assert!(scene.projectiles.is_empty());
         assert!(scene.tower_targets.is_empty());
         assert!(scene.hovered_tower.is_none());
+        assert!(scene.hovered_bug.is_none());
+        assert!(scene.priority_target.is_none());
+        assert!(scene.selected_towers.is_empty());
+        assert!(scene.selection_box.is_none());
         assert!(scene.tower_feedback.is_none());
         assert!(scene.ground.is_none());
         assert!(scene.control_panel.is_none());
@@ -1641,36 +2896,22 @@ mod tests {
             Vec2::new(6.5, 8.5),
         );
 
-        let scene = Scene::new(
-            tile_grid,
-            wall_color,
-            None,
-            Vec::new(),
-            vec![],
-            vec![SceneTower::new(
+        let scene = Scene {
+            towers: vec![SceneTower::new(
                 TowerId::new(1),
                 TowerKind::Basic,
                 preview_region,
             )],
-            Vec::new(),
-            Vec::new(),
-            vec![target_line],
-            None,
-            PlayMode::Builder,
-            Some(placement_preview),
-            Some(Vec2::splat(1.0)),
-            Some(TowerInteractionFeedback::PlacementRejected {
+            tower_targets: vec![target_line],
+            tower_preview: Some(placement_preview),
+            active_tower_footprint_tiles: Some(Vec2::splat(1.0)),
+            tower_feedback: Some(TowerInteractionFeedback::PlacementRejected {
                 kind: TowerKind::Basic,
                 origin: maze_defence_core::CellCoord::new(4, 6),
                 reason: PlacementError::Occupied,
             }),
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
-        );
+            ..Scene::new(tile_grid, wall_color, PlayMode::Builder)
+        };
 
         assert_eq!(scene.play_mode, PlayMode::Builder);
         assert_eq!(scene.tower_preview, Some(placement_preview));
@@ -1709,34 +2950,127 @@ mod tests {
         )
         .expect("default cells_per_tile is valid");
 
-        let scene = Scene::new(
-            tile_grid,
-            Color::from_rgb_u8(64, 64, 64),
-            None,
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            None,
-            PlayMode::Attack,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
-        );
+        let scene = Scene::new(tile_grid, Color::from_rgb_u8(64, 64, 64), PlayMode::Attack);
 
         assert_eq!(scene.total_height(), tile_grid.bordered_height());
         assert!(scene.difficulty_selection.is_none());
         assert!(scene.analytics.is_none());
     }
 
+    #[test]
+    fn bug_lod_keeps_the_bugs_nearest_the_focus_in_full_detail() {
+        let grid = TileGridPresentation::new(4, 4, 32.0, 4, Color::from_rgb_u8(0, 0, 0))
+            .expect("valid grid");
+        let color = Color::from_rgb_u8(10, 20, 30);
+        let health = BugHealthPresentation::new(1, 1);
+        let mut scene = Scene {
+            bugs: (0..4)
+                .map(|index| {
+                    BugPresentation::new_circle(
+                        BugId::new(index),
+                        Vec2::new(9.0 + index as f32 * 2.0, 9.0),
+                        color,
+                        health,
+                    )
+                })
+                .collect(),
+            ..Scene::new(grid, color, PlayMode::Attack)
+        };
+        let untouched = scene.bugs.clone();
+        BugLodPolicy::new(4).apply(&mut scene);
+        assert_eq!(scene.bugs, untouched, "within budget");
+
+        BugLodPolicy::new(2).apply(&mut scene);
+        let dots: Vec<bool> = scene
+            .bugs
+            .iter()
+            .map(|bug| matches!(bug.style, BugVisual::Dot { .. }))
+            .collect();
+        assert_eq!(
+            dots,
+            vec![false, false, true, true],
+            "board centre is (9, 9)"
+        );
+        assert!(scene
+            .bugs
+            .iter()
+            .all(|bug| bug.health_bar == !matches!(bug.style, BugVisual::Dot { .. })));
+
+        scene.camera_focus = Some(CameraFocus::new(Vec2::new(16.0, 9.0), 2.0));
+        for bug in &mut scene.bugs {
+            *bug = BugPresentation::new_circle(bug.id, bug.position(), color, health);
+        }
+        BugLodPolicy::new(1).apply(&mut scene);
+        assert!(matches!(
+            scene.bugs[3].style,
+            BugVisual::PrimitiveCircle { .. }
+        ));
+        assert!(matches!(scene.bugs[0].style, BugVisual::Dot { color: dot } if dot == color));
+    }
+
+    #[test]
+    fn exit_portal_frames_the_gap_above_the_targets() {
+        assert_eq!(ExitPortal::from_target_cells(&[]), None);
+        let portal = ExitPortal::from_target_cells(&[CellCoord::new(5, 8), CellCoord::new(4, 8)])
+            .expect("targets present");
+        assert_eq!(
+            portal.region,
+            CellRect::from_origin_and_size(CellCoord::new(4, 7), CellRectSize::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn wave_composition_ranks_species_by_threat() {
+        use maze_defence_core::{BugColor, Health, PressureSpawnRecord, SpeciesPrototype};
+        use std::num::NonZeroU32;
+
+        let prototype = |red, health, step_ms| {
+            SpeciesPrototype::new(
+                BugColor::from_rgb(red, 0, 0),
+                Health::new(health),
+                NonZeroU32::new(step_ms).expect("non-zero"),
+            )
+        };
+        // Many slow, frail bugs, a few fast, tough ones and an unused prototype.
+        let prototypes = vec![
+            prototype(10, 2, 1_000),
+            prototype(20, 10, 250),
+            prototype(30, 50, 500),
+        ];
+        let mut spawns: Vec<_> = (0..6)
+            .map(|index| PressureSpawnRecord::new(index * 100, 2, 1.0, 0))
+            .collect();
+        spawns.extend((0..2).map(|index| PressureSpawnRecord::new(index * 100, 10, 1.0, 1)));
+        let plan = PressureWavePlan::new(spawns, prototypes);
+
+        let composition = WaveCompositionPresentation::from_plan(WaveDifficulty::Hard, &plan);
+        assert_eq!(composition.difficulty(), WaveDifficulty::Hard);
+        assert_eq!(composition.bug_count(), 8);
+        let species = composition.species();
+        assert_eq!(species.len(), 2, "species without spawns are omitted");
+        assert_eq!(species[0].tint(), Color::from_rgb_u8(20, 0, 0));
+        assert_eq!((species[0].count(), species[0].health()), (2, 10));
+        assert_eq!(species[1].count(), 6);
+        // 2 bugs x 10 hp x 4 steps/s against 6 bugs x 2 hp x 1 step/s.
+        assert!((species[0].threat_share() - 80.0 / 92.0).abs() < 1e-6);
+        assert!((species[1].threat_share() - 12.0 / 92.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn render_layers_stack_items_back_to_front() {
+        let mut sorted = RenderLayer::ALL;
+        sorted.sort();
+        assert_eq!(sorted, RenderLayer::ALL);
+
+        assert!(GroundSpriteTiles::LAYER < SpawnEffect::LAYER);
+        assert!(SceneWall::LAYER < BugPresentation::LAYER);
+        assert_eq!(BugEscape::LAYER, BugPresentation::LAYER);
+        assert!(BugPresentation::LAYER < SceneTower::LAYER);
+        assert!(SceneProjectile::LAYER < TowerTargetLine::LAYER);
+        assert!(TowerTargetLine::LAYER < TowerCooldownIndicator::LAYER);
+        assert_eq!(BugInspection::LAYER, RenderLayer::UiOverlays);
+    }
+
     #[test]
     fn ground_sprite_tiles_create_expected_descriptor() {
         let span_tiles = Vec2::splat(4.0);
@@ -0,0 +1,176 @@
+//! Toast messages explaining why a player action was refused.
+
+use std::time::Duration;
+
+use maze_defence_core::{Event, PlacementError, RemovalError};
+
+/// Short-lived messages that explain rejected player actions.
+///
+/// Every rejection maps to a message telling the player what went wrong and how to
+/// proceed. A message repeated within [`Notifications::REPEAT_COOLDOWN`] of being shown is
+/// dropped, so a drag across occupied tiles raises a single toast; later repeats restart
+/// the toast that is still visible instead of stacking another one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Notifications {
+    toasts: Vec<Toast>,
+}
+
+impl Notifications {
+    /// Time a toast stays on screen, including its fade.
+    pub const TOAST_DURATION: Duration = Duration::from_millis(3_000);
+    /// Time during which a repeated message is ignored.
+    pub const REPEAT_COOLDOWN: Duration = Duration::from_millis(1_500);
+    /// Most toasts shown at once; older ones give way to newer ones.
+    pub const MAX_VISIBLE: usize = 3;
+
+    /// Raises toasts for the rejections in `events`.
+    pub fn observe(&mut self, events: &[Event]) {
+        for message in events.iter().filter_map(rejection_message) {
+            self.show(message);
+        }
+    }
+
+    /// Advances every toast and drops the ones that have expired.
+    pub fn advance(&mut self, dt: Duration) {
+        for toast in &mut self.toasts {
+            toast.elapsed = toast.elapsed.saturating_add(dt);
+        }
+        self.toasts
+            .retain(|toast| toast.elapsed < Self::TOAST_DURATION);
+    }
+
+    /// Toasts currently on screen, oldest first.
+    #[must_use]
+    pub fn toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    fn show(&mut self, message: &'static str) {
+        if let Some(index) = self
+            .toasts
+            .iter()
+            .position(|toast| toast.message == message)
+        {
+            if self.toasts[index].elapsed < Self::REPEAT_COOLDOWN {
+                return;
+            }
+            let _ = self.toasts.remove(index);
+        }
+        self.toasts.push(Toast {
+            message,
+            elapsed: Duration::ZERO,
+        });
+        let excess = self.toasts.len().saturating_sub(Self::MAX_VISIBLE);
+        let _ = self.toasts.drain(..excess);
+    }
+}
+
+/// Message shown to the player while it fades out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toast {
+    message: &'static str,
+    elapsed: Duration,
+}
+
+impl Toast {
+    /// Text of the toast.
+    #[must_use]
+    pub const fn message(&self) -> &'static str {
+        self.message
+    }
+
+    /// Opacity, fading out over the last half second.
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        let remaining = Notifications::TOAST_DURATION.saturating_sub(self.elapsed);
+        (remaining.as_secs_f32() / 0.5).min(1.0)
+    }
+}
+
+/// Message explaining the rejection reported by `event`, if it rejects a player action.
+fn rejection_message(event: &Event) -> Option<&'static str> {
+    let message = match event {
+        Event::TowerPlacementRejected { reason, .. } => match reason {
+            PlacementError::InvalidMode => {
+                "Towers can only be built in Builder mode. Press Space to switch."
+            }
+            PlacementError::OutOfBounds => "Towers must fit inside the maze.",
+            PlacementError::Misaligned => "Towers must line up with the tile grid.",
+            PlacementError::Occupied => "Something is already built there.",
+            PlacementError::Unbuildable => "Towers can't be built over water or void.",
+            PlacementError::PathBlocked => {
+                "That tower would seal the maze. Bugs need a path to the exit."
+            }
+            PlacementError::InsufficientFunds => {
+                "Not enough gold. Kill bugs to earn more, or sell a tower."
+            }
+            PlacementError::SpawnReserved => "Bugs are about to spawn there.",
+        },
+        Event::TowerRemovalRejected { reason, .. } => match reason {
+            RemovalError::InvalidMode => {
+                "Towers can only be sold in Builder mode. Press Space to switch."
+            }
+            RemovalError::MissingTower => "That tower is already gone.",
+        },
+        _ => return None,
+    };
+    Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{CellCoord, TowerId, TowerKind};
+
+    fn placement_rejected(reason: PlacementError) -> Event {
+        Event::TowerPlacementRejected {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(1, 1),
+            reason,
+        }
+    }
+
+    fn messages(notifications: &Notifications) -> Vec<&'static str> {
+        notifications.toasts().iter().map(Toast::message).collect()
+    }
+
+    #[test]
+    fn rejections_raise_toasts_that_repeat_only_after_the_cooldown() {
+        let mut notifications = Notifications::default();
+        notifications.observe(&[
+            placement_rejected(PlacementError::Occupied),
+            placement_rejected(PlacementError::Occupied),
+            Event::TowerRemovalRejected {
+                tower: TowerId::new(3),
+                reason: RemovalError::InvalidMode,
+            },
+            Event::MazeLayoutChanged,
+        ]);
+        assert_eq!(
+            messages(&notifications),
+            vec![
+                "Something is already built there.",
+                "Towers can only be sold in Builder mode. Press Space to switch.",
+            ]
+        );
+
+        notifications.advance(Notifications::REPEAT_COOLDOWN);
+        notifications.observe(&[placement_rejected(PlacementError::Occupied)]);
+        assert_eq!(
+            messages(&notifications).last(),
+            Some(&"Something is already built there."),
+            "a repeat after the cooldown moves the toast to the front"
+        );
+
+        notifications.observe(&[
+            placement_rejected(PlacementError::PathBlocked),
+            placement_rejected(PlacementError::InsufficientFunds),
+        ]);
+        assert_eq!(notifications.toasts().len(), Notifications::MAX_VISIBLE);
+        assert!(!messages(&notifications)
+            .contains(&"Towers can only be sold in Builder mode. Press Space to switch."));
+
+        notifications.advance(Notifications::TOAST_DURATION);
+        assert_eq!(notifications.toasts().len(), 0);
+    }
+}
@@ -0,0 +1,185 @@
+//! Locale-aware formatting of the numbers and durations shown to the player.
+
+use std::time::Duration;
+
+/// Separators used when writing numbers for the player's locale.
+///
+/// Late-game gold, health and damage run into the millions, so counts are grouped in
+/// thousands. Durations switch units as they grow: `850 ms`, `12.5 s`, `3 m 05 s`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    group_separator: char,
+    decimal_separator: char,
+}
+
+impl NumberFormat {
+    /// `1,234,567.8`, used by English and most East Asian locales.
+    pub const ENGLISH: Self = Self::new(',', '.');
+    /// `1.234.567,8`, used by German, Spanish, Italian, Dutch and Portuguese locales.
+    pub const CONTINENTAL: Self = Self::new('.', ',');
+    /// `1 234 567,8`, used by French, Nordic and most Slavic locales.
+    pub const SPACED: Self = Self::new(' ', ',');
+
+    /// Creates a format with the given separators.
+    #[must_use]
+    pub const fn new(group_separator: char, decimal_separator: char) -> Self {
+        Self {
+            group_separator,
+            decimal_separator,
+        }
+    }
+
+    /// Format of a POSIX or BCP 47 locale name such as `de_DE.UTF-8` or `fr-CA`.
+    ///
+    /// Only the language decides the separators; unknown languages use [`Self::ENGLISH`].
+    #[must_use]
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro" | "sl" | "hr"
+            | "sr" => Self::CONTINENTAL,
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "uk" | "hu"
+            | "bg" | "et" | "lv" | "lt" => Self::SPACED,
+            _ => Self::ENGLISH,
+        }
+    }
+
+    /// Format of the locale named by `LC_ALL`, `LC_NUMERIC` or `LANG`, checked in that
+    /// order as POSIX does.
+    #[must_use]
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Self::ENGLISH, |locale| Self::for_locale(&locale))
+    }
+
+    /// Writes a count with its thousands grouped, e.g. `12,345`.
+    #[must_use]
+    pub fn count(&self, value: u64) -> String {
+        let digits = value.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                grouped.push(self.group_separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Writes `value` rounded to `places` decimal places, grouping its whole part.
+    #[must_use]
+    pub fn decimal(&self, value: f64, places: u32) -> String {
+        let scale = 10_u64.pow(places);
+        let scaled = (value.abs() * scale as f64).round() as u64;
+        let mut text = String::new();
+        if value < 0.0 && scaled > 0 {
+            text.push('-');
+        }
+        text.push_str(&self.count(scaled / scale));
+        if places > 0 {
+            text.push(self.decimal_separator);
+            text.push_str(&format!(
+                "{:0width$}",
+                scaled % scale,
+                width = places as usize
+            ));
+        }
+        text
+    }
+
+    /// Writes a percentage given in basis points with two decimal places, e.g. `12.50%`.
+    #[must_use]
+    pub fn basis_points(&self, value: u32) -> String {
+        format!("{}%", self.decimal(f64::from(value) / 100.0, 2))
+    }
+
+    /// Writes a duration in the largest unit that keeps it readable.
+    ///
+    /// Under a second reads as whole milliseconds, under a minute as seconds with one
+    /// decimal, under an hour as minutes and seconds, and anything longer as hours and
+    /// minutes.
+    #[must_use]
+    pub fn duration(&self, duration: Duration) -> String {
+        let seconds = duration.as_secs();
+        if seconds == 0 {
+            format!("{} ms", duration.subsec_millis())
+        } else if seconds < 60 {
+            let tenths = duration.as_millis() / 100;
+            format!("{} s", self.decimal(tenths as f64 / 10.0, 1))
+        } else if seconds < 3_600 {
+            format!("{} m {:02} s", seconds / 60, seconds % 60)
+        } else {
+            format!(
+                "{} h {:02} m",
+                self.count(seconds / 3_600),
+                seconds / 60 % 60
+            )
+        }
+    }
+
+    /// Writes a stopwatch reading as `mm:ss.t`, e.g. `03:07.4`.
+    #[must_use]
+    pub fn clock(&self, duration: Duration) -> String {
+        let seconds = duration.as_secs();
+        format!(
+            "{:02}:{:02}{}{}",
+            seconds / 60,
+            seconds % 60,
+            self.decimal_separator,
+            duration.subsec_millis() / 100
+        )
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_decimals_use_the_locale_separators() {
+        let english = NumberFormat::ENGLISH;
+        assert_eq!(english.count(0), "0");
+        assert_eq!(english.count(999), "999");
+        assert_eq!(english.count(1_000), "1,000");
+        assert_eq!(english.count(12_345_678), "12,345,678");
+        assert_eq!(english.decimal(1_234.56, 1), "1,234.6");
+        assert_eq!(english.decimal(-0.04, 1), "0.0");
+        assert_eq!(english.basis_points(1_250), "12.50%");
+
+        let german = NumberFormat::for_locale("de_DE.UTF-8");
+        assert_eq!(german, NumberFormat::CONTINENTAL);
+        assert_eq!(german.count(1_234_567), "1.234.567");
+        assert_eq!(german.decimal(1_234.5, 2), "1.234,50");
+
+        let french = NumberFormat::for_locale("fr-CA");
+        assert_eq!(french.count(98_765), "98 765");
+        assert_eq!(NumberFormat::for_locale("C"), NumberFormat::ENGLISH);
+    }
+
+    #[test]
+    fn durations_switch_units_as_they_grow() {
+        let english = NumberFormat::ENGLISH;
+        assert_eq!(english.duration(Duration::from_millis(850)), "850 ms");
+        assert_eq!(english.duration(Duration::from_millis(12_540)), "12.5 s");
+        assert_eq!(english.duration(Duration::from_secs(185)), "3 m 05 s");
+        assert_eq!(english.duration(Duration::from_secs(3_720)), "1 h 02 m");
+        assert_eq!(english.clock(Duration::from_millis(187_400)), "03:07.4");
+
+        let german = NumberFormat::CONTINENTAL;
+        assert_eq!(german.duration(Duration::from_millis(12_540)), "12,5 s");
+        assert_eq!(german.clock(Duration::from_millis(187_400)), "03:07,4");
+    }
+}
@@ -0,0 +1,258 @@
+//! Conversions between screen pixels, grid world units and cell coordinates.
+
+use glam::Vec2;
+
+use crate::{CameraFocus, Scene, TileGridPresentation};
+
+/// Maps a scene's board onto the screen and back.
+///
+/// Three spaces are involved:
+/// - *screen* space is measured in pixels from the window's top-left corner;
+/// - *world* space is measured in world units from the top-left corner of the playable tile
+///   grid, matching [`FrameInput::cursor_world_space`](crate::FrameInput::cursor_world_space);
+/// - *cell* space is measured in cells from the top-left corner of the bordered grid, matching
+///   bug, projectile and camera positions.
+///
+/// Forward conversions (`*_to_screen`) and their inverses (`screen_to_*`) round-trip for
+/// any projection with a positive scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projection {
+    origin: Vec2,
+    scale: f32,
+    cell_length: f32,
+    border: Vec2,
+    grid_size: Vec2,
+}
+
+impl Projection {
+    /// Fits `tile_grid` into a `viewport` anchored at the screen's top-left corner.
+    ///
+    /// Without a camera the bordered grid is scaled to fit and centred; a camera multiplies
+    /// the fitted scale by its zoom and centres its cell-space point instead.
+    #[must_use]
+    pub fn new(
+        tile_grid: &TileGridPresentation,
+        viewport: Vec2,
+        camera: Option<CameraFocus>,
+    ) -> Self {
+        let bordered = Vec2::new(tile_grid.bordered_width(), tile_grid.bordered_height());
+        let fitted = if bordered.x == 0.0 || bordered.y == 0.0 {
+            1.0
+        } else {
+            let width_ratio = if viewport.x <= f32::EPSILON {
+                f32::INFINITY
+            } else {
+                viewport.x / bordered.x
+            };
+            width_ratio.min(viewport.y / bordered.y)
+        };
+        let zoom = camera
+            .map(|focus| focus.zoom)
+            .filter(|zoom| zoom.is_finite() && *zoom > 0.0)
+            .unwrap_or(1.0);
+        let scale = fitted * zoom;
+        let cell_length = if tile_grid.cells_per_tile == 0 {
+            0.0
+        } else {
+            tile_grid.cell_length()
+        };
+
+        let origin = match camera {
+            Some(focus) => viewport * 0.5 - focus.center * cell_length * scale,
+            None => {
+                let centred = (viewport - bordered * scale) * 0.5;
+                Vec2::new(centred.x.max(0.0), centred.y)
+            }
+        };
+
+        Self {
+            origin,
+            scale,
+            cell_length,
+            border: Vec2::new(
+                TileGridPresentation::SIDE_BORDER_CELL_LAYERS as f32,
+                TileGridPresentation::TOP_BORDER_CELL_LAYERS as f32,
+            ),
+            grid_size: Vec2::new(tile_grid.width(), tile_grid.height()),
+        }
+    }
+
+    /// Projects `scene` onto a screen of the given size, leaving room for its control panel.
+    #[must_use]
+    pub fn for_scene(scene: &Scene, screen_width: f32, screen_height: f32) -> Self {
+        let panel_width = scene
+            .control_panel
+            .map(|panel| panel.width.max(0.0))
+            .unwrap_or(0.0)
+            .min(screen_width);
+        let viewport = Vec2::new((screen_width - panel_width).max(0.0), screen_height);
+        Self::new(&scene.tile_grid, viewport, scene.camera_focus)
+    }
+
+    /// Pixels per world unit.
+    #[must_use]
+    pub const fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Pixels per cell.
+    #[must_use]
+    pub fn cell_step(&self) -> f32 {
+        self.cell_length * self.scale
+    }
+
+    /// Screen position of the bordered grid's top-left corner.
+    #[must_use]
+    pub const fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Screen position of the playable grid's top-left corner.
+    #[must_use]
+    pub fn grid_origin(&self) -> Vec2 {
+        self.origin + self.border * self.cell_step()
+    }
+
+    /// On-screen size of the playable grid in pixels.
+    #[must_use]
+    pub fn grid_screen_size(&self) -> Vec2 {
+        self.grid_size * self.scale
+    }
+
+    /// Converts a cell-space position to screen pixels.
+    #[must_use]
+    pub fn cell_to_screen(&self, cell: Vec2) -> Vec2 {
+        self.origin + cell * self.cell_step()
+    }
+
+    /// Converts screen pixels to a cell-space position.
+    ///
+    /// Returns the origin cell when the projection has no area.
+    #[must_use]
+    pub fn screen_to_cell(&self, screen: Vec2) -> Vec2 {
+        let cell_step = self.cell_step();
+        if cell_step <= f32::EPSILON {
+            return Vec2::ZERO;
+        }
+        (screen - self.origin) / cell_step
+    }
+
+    /// Converts a world-space position to screen pixels.
+    #[must_use]
+    pub fn world_to_screen(&self, world: Vec2) -> Vec2 {
+        self.grid_origin() + world * self.scale
+    }
+
+    /// Converts screen pixels to a world-space position, which may lie outside the grid.
+    ///
+    /// Returns the world origin when the projection has no area.
+    #[must_use]
+    pub fn screen_to_world(&self, screen: Vec2) -> Vec2 {
+        if self.scale <= f32::EPSILON {
+            return Vec2::ZERO;
+        }
+        (screen - self.grid_origin()) / self.scale
+    }
+
+    /// Reports whether a screen position lies over the playable grid.
+    #[must_use]
+    pub fn grid_contains(&self, screen: Vec2) -> bool {
+        let min = self.grid_origin();
+        let max = min + self.grid_screen_size();
+        screen.x >= min.x && screen.x < max.x && screen.y >= min.y && screen.y < max.y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn grids() -> Vec<TileGridPresentation> {
+        [(4, 4, 32.0, 4), (10, 6, 48.0, 1), (7, 12, 20.0, 3)]
+            .into_iter()
+            .map(|(columns, rows, tile_length, cells_per_tile)| {
+                TileGridPresentation::new(
+                    columns,
+                    rows,
+                    tile_length,
+                    cells_per_tile,
+                    Color::from_rgb_u8(0, 0, 0),
+                )
+                .expect("valid grid")
+            })
+            .collect()
+    }
+
+    fn cameras() -> [Option<CameraFocus>; 3] {
+        [
+            None,
+            Some(CameraFocus::new(Vec2::new(3.5, 4.5), 2.0)),
+            Some(CameraFocus::new(Vec2::new(0.0, 9.0), 0.5)),
+        ]
+    }
+
+    /// Deterministic sample points spread across and beyond a 1000x800 screen.
+    fn screen_points() -> impl Iterator<Item = Vec2> {
+        let mut state: u32 = 0x9e37_79b9;
+        (0..64).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let x = (state % 1_400) as f32 - 200.0;
+            let y = ((state / 1_400) % 1_200) as f32 - 200.0;
+            Vec2::new(x + 0.25, y + 0.75)
+        })
+    }
+
+    fn assert_close(left: Vec2, right: Vec2) {
+        assert!(
+            (left - right).length() <= 1e-3 * (1.0 + right.length()),
+            "{left} != {right}"
+        );
+    }
+
+    #[test]
+    fn conversions_round_trip_for_every_space() {
+        for grid in grids() {
+            for camera in cameras() {
+                let projection = Projection::new(&grid, Vec2::new(1_000.0, 800.0), camera);
+                for screen in screen_points() {
+                    let world = projection.screen_to_world(screen);
+                    assert_close(projection.world_to_screen(world), screen);
+                    let cell = projection.screen_to_cell(screen);
+                    assert_close(projection.cell_to_screen(cell), screen);
+                    assert_close(
+                        projection.screen_to_cell(projection.cell_to_screen(cell)),
+                        cell,
+                    );
+
+                    let border_cells = Vec2::new(
+                        TileGridPresentation::SIDE_BORDER_CELL_LAYERS as f32,
+                        TileGridPresentation::TOP_BORDER_CELL_LAYERS as f32,
+                    );
+                    assert_close(cell - border_cells, world / grid.cell_length());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fitted_grids_centre_in_the_viewport_and_cameras_centre_their_focus() {
+        let grid = grids()[0];
+        let viewport = Vec2::new(760.0, 480.0);
+        let fitted = Projection::new(&grid, viewport, None);
+        let bordered = Vec2::new(grid.bordered_width(), grid.bordered_height());
+        assert_close(
+            fitted.origin() * 2.0 + bordered * fitted.scale(),
+            Vec2::new(viewport.x, viewport.y),
+        );
+        assert!(fitted.grid_contains(fitted.grid_origin()));
+        assert!(!fitted.grid_contains(fitted.grid_origin() + fitted.grid_screen_size()));
+
+        let focus = CameraFocus::new(Vec2::new(3.5, 4.5), 2.0);
+        let zoomed = Projection::new(&grid, viewport, Some(focus));
+        assert!((zoomed.scale() - fitted.scale() * 2.0).abs() <= 1e-4);
+        assert_close(zoomed.cell_to_screen(focus.center), viewport * 0.5);
+    }
+}
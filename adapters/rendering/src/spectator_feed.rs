@@ -0,0 +1,404 @@
+//! Presentation state streamed to remote spectators as keyframes and per-tick diffs.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use maze_defence_core::{
+    BugColor, BugId, CellCoord, CellPointHalf, CellRect, Gold, Health, ProjectileId, TowerId,
+    TowerKind, TowerRotation,
+};
+use serde::{Deserialize, Serialize};
+
+/// Everything a remote viewer needs to draw the board at one tick.
+///
+/// Entities are kept sorted by identifier, so equal boards compare and serialize equally.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedState {
+    /// Gold held by the defender.
+    pub gold: Gold,
+    /// Bugs on the board.
+    pub bugs: Vec<FeedBug>,
+    /// Towers standing on the board.
+    pub towers: Vec<FeedTower>,
+    /// Projectiles in flight.
+    pub projectiles: Vec<FeedProjectile>,
+}
+
+impl FeedState {
+    /// Creates a state from entities in any order.
+    #[must_use]
+    pub fn new(
+        gold: Gold,
+        mut bugs: Vec<FeedBug>,
+        mut towers: Vec<FeedTower>,
+        mut projectiles: Vec<FeedProjectile>,
+    ) -> Self {
+        bugs.sort_by_key(|bug| bug.id);
+        towers.sort_by_key(|tower| tower.id);
+        projectiles.sort_by_key(|projectile| projectile.id);
+        Self {
+            gold,
+            bugs,
+            towers,
+            projectiles,
+        }
+    }
+}
+
+/// Bug as drawn by a spectator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedBug {
+    /// Identifier allocated to the bug by the world.
+    pub id: BugId,
+    /// Cell the bug occupies.
+    pub cell: CellCoord,
+    /// Colour the bug is drawn with.
+    pub color: BugColor,
+    /// Health the bug has left.
+    pub health: Health,
+    /// Health the bug spawned with.
+    pub max_health: Health,
+}
+
+/// Tower as drawn by a spectator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedTower {
+    /// Identifier allocated to the tower by the world.
+    pub id: TowerId,
+    /// Kind of tower.
+    pub kind: TowerKind,
+    /// Cells the tower occupies.
+    pub region: CellRect,
+    /// Rotation of the tower's footprint.
+    pub rotation: TowerRotation,
+}
+
+/// Projectile as drawn by a spectator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedProjectile {
+    /// Identifier allocated to the projectile by the world.
+    pub id: ProjectileId,
+    /// Point the projectile was fired from, in half-cell units.
+    pub origin_half: CellPointHalf,
+    /// Point the projectile flies to, in half-cell units.
+    pub dest_half: CellPointHalf,
+    /// Share of the flight already covered, in thousandths.
+    pub progress_permille: u16,
+}
+
+/// One change between consecutive [`FeedState`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FeedChange {
+    /// The defender's gold changed.
+    Gold {
+        /// Gold now held.
+        amount: Gold,
+    },
+    /// A bug appeared or changed.
+    Bug {
+        /// Bug as it now stands.
+        bug: FeedBug,
+    },
+    /// A bug left the board.
+    BugRemoved {
+        /// Identifier of the removed bug.
+        id: BugId,
+    },
+    /// A tower appeared or changed.
+    Tower {
+        /// Tower as it now stands.
+        tower: FeedTower,
+    },
+    /// A tower left the board.
+    TowerRemoved {
+        /// Identifier of the removed tower.
+        id: TowerId,
+    },
+    /// A projectile appeared or moved.
+    Projectile {
+        /// Projectile as it now stands.
+        projectile: FeedProjectile,
+    },
+    /// A projectile landed or was discarded.
+    ProjectileRemoved {
+        /// Identifier of the removed projectile.
+        id: ProjectileId,
+    },
+}
+
+/// Unit of the spectator stream.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "frame", rename_all = "snake_case")]
+pub enum FeedFrame {
+    /// Complete state; viewers joining the stream start from one.
+    Keyframe {
+        /// Tick the state was captured on.
+        tick: u64,
+        /// State of the board.
+        state: FeedState,
+    },
+    /// Changes since the previous frame.
+    Delta {
+        /// Tick the changes were captured on.
+        tick: u64,
+        /// Changes in the order a viewer applies them.
+        changes: Vec<FeedChange>,
+    },
+}
+
+/// Turns successive [`FeedState`]s into a stream of keyframes and diffs.
+///
+/// A keyframe opens the stream and is repeated every `keyframe_interval` ticks; the ticks in
+/// between only carry what changed, and unchanged ticks produce no frame. The feed keeps the
+/// latest keyframe and the deltas since, which is exactly what a viewer joining mid-stream
+/// has to replay, so its history never outgrows one keyframe interval.
+#[derive(Clone, Debug)]
+pub struct SpectatorFeed {
+    keyframe_interval: u64,
+    published: Option<FeedState>,
+    keyframe_tick: u64,
+    history: VecDeque<FeedFrame>,
+}
+
+impl SpectatorFeed {
+    /// Creates a feed that repeats its keyframe every `keyframe_interval` ticks; zero is
+    /// treated as one.
+    #[must_use]
+    pub fn new(keyframe_interval: u64) -> Self {
+        Self {
+            keyframe_interval: keyframe_interval.max(1),
+            published: None,
+            keyframe_tick: 0,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Records the state at `tick` and returns the frame to broadcast, if anything changed.
+    pub fn publish(&mut self, tick: u64, state: FeedState) -> Option<&FeedFrame> {
+        let frame = match &self.published {
+            Some(published) if tick.saturating_sub(self.keyframe_tick) < self.keyframe_interval => {
+                let changes = diff(published, &state);
+                if changes.is_empty() {
+                    return None;
+                }
+                FeedFrame::Delta { tick, changes }
+            }
+            _ => {
+                self.keyframe_tick = tick;
+                self.history.clear();
+                FeedFrame::Keyframe {
+                    tick,
+                    state: state.clone(),
+                }
+            }
+        };
+        self.published = Some(state);
+        self.history.push_back(frame);
+        self.history.back()
+    }
+
+    /// Frames a viewer joining now replays: the latest keyframe and every delta since.
+    pub fn catch_up(&self) -> impl Iterator<Item = &FeedFrame> {
+        self.history.iter()
+    }
+}
+
+/// Rebuilds the board from a spectator stream without running the simulation.
+#[derive(Clone, Debug, Default)]
+pub struct SpectatorView {
+    state: Option<FeedState>,
+    tick: u64,
+}
+
+impl SpectatorView {
+    /// Applies `frame` and reports whether it was used.
+    ///
+    /// Deltas that arrive before the first keyframe are skipped, since the state they
+    /// change is unknown.
+    pub fn apply(&mut self, frame: &FeedFrame) -> bool {
+        match frame {
+            FeedFrame::Keyframe { tick, state } => {
+                self.state = Some(state.clone());
+                self.tick = *tick;
+            }
+            FeedFrame::Delta { tick, changes } => {
+                let Some(state) = self.state.as_mut() else {
+                    return false;
+                };
+                for change in changes {
+                    apply_change(state, *change);
+                }
+                self.tick = *tick;
+            }
+        }
+        true
+    }
+
+    /// Board as of the last applied frame, once a keyframe arrived.
+    #[must_use]
+    pub fn state(&self) -> Option<&FeedState> {
+        self.state.as_ref()
+    }
+
+    /// Tick of the last applied frame.
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+}
+
+fn diff(before: &FeedState, after: &FeedState) -> Vec<FeedChange> {
+    let mut changes = Vec::new();
+    if before.gold != after.gold {
+        changes.push(FeedChange::Gold { amount: after.gold });
+    }
+    diff_entities(
+        &before.bugs,
+        &after.bugs,
+        |bug| bug.id,
+        |bug| FeedChange::Bug { bug },
+        |id| FeedChange::BugRemoved { id },
+        &mut changes,
+    );
+    diff_entities(
+        &before.towers,
+        &after.towers,
+        |tower| tower.id,
+        |tower| FeedChange::Tower { tower },
+        |id| FeedChange::TowerRemoved { id },
+        &mut changes,
+    );
+    diff_entities(
+        &before.projectiles,
+        &after.projectiles,
+        |projectile| projectile.id,
+        |projectile| FeedChange::Projectile { projectile },
+        |id| FeedChange::ProjectileRemoved { id },
+        &mut changes,
+    );
+    changes
+}
+
+/// Appends the upserts and removals turning `before` into `after`.
+fn diff_entities<T: Copy + PartialEq, K: Ord + Copy>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> K,
+    upserted: impl Fn(T) -> FeedChange,
+    removed: impl Fn(K) -> FeedChange,
+    changes: &mut Vec<FeedChange>,
+) {
+    let previous: BTreeMap<K, &T> = before.iter().map(|entity| (key(entity), entity)).collect();
+    for entity in after {
+        if previous.get(&key(entity)) != Some(&entity) {
+            changes.push(upserted(*entity));
+        }
+    }
+    let current: BTreeMap<K, &T> = after.iter().map(|entity| (key(entity), entity)).collect();
+    for entity in before {
+        if !current.contains_key(&key(entity)) {
+            changes.push(removed(key(entity)));
+        }
+    }
+}
+
+fn apply_change(state: &mut FeedState, change: FeedChange) {
+    match change {
+        FeedChange::Gold { amount } => state.gold = amount,
+        FeedChange::Bug { bug } => upsert(&mut state.bugs, bug, |bug| bug.id),
+        FeedChange::BugRemoved { id } => state.bugs.retain(|bug| bug.id != id),
+        FeedChange::Tower { tower } => upsert(&mut state.towers, tower, |tower| tower.id),
+        FeedChange::TowerRemoved { id } => state.towers.retain(|tower| tower.id != id),
+        FeedChange::Projectile { projectile } => {
+            upsert(&mut state.projectiles, projectile, |projectile| {
+                projectile.id
+            });
+        }
+        FeedChange::ProjectileRemoved { id } => {
+            state.projectiles.retain(|projectile| projectile.id != id);
+        }
+    }
+}
+
+/// Replaces the entity sharing `entity`'s key or inserts it in key order.
+fn upsert<T, K: Ord>(entities: &mut Vec<T>, entity: T, key: impl Fn(&T) -> K) {
+    match entities.binary_search_by_key(&key(&entity), &key) {
+        Ok(index) => entities[index] = entity,
+        Err(index) => entities.insert(index, entity),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bug(id: u32, column: u32, health: u32) -> FeedBug {
+        FeedBug {
+            id: BugId::new(id),
+            cell: CellCoord::new(column, 2),
+            color: BugColor::from_rgb(0x20, 0x80, 0x40),
+            health: Health::new(health),
+            max_health: Health::new(10),
+        }
+    }
+
+    fn board(gold: u64, bugs: Vec<FeedBug>) -> FeedState {
+        FeedState::new(Gold::new(gold), bugs, Vec::new(), Vec::new())
+    }
+
+    #[test]
+    fn viewers_rebuild_the_board_from_keyframes_and_deltas() {
+        let boards = [
+            board(5, vec![bug(1, 0, 10)]),
+            board(5, vec![bug(2, 0, 10), bug(1, 1, 10)]),
+            board(5, vec![bug(2, 0, 10), bug(1, 1, 10)]),
+            board(6, vec![bug(2, 1, 4)]),
+            board(6, Vec::new()),
+        ];
+        let mut feed = SpectatorFeed::new(3);
+        let mut stream = Vec::new();
+        for (tick, state) in boards.iter().enumerate() {
+            if let Some(frame) = feed.publish(tick as u64, state.clone()) {
+                stream.push(serde_json::to_string(frame).expect("frame serializes"));
+            }
+        }
+        assert_eq!(stream.len(), 4, "the unchanged tick is not broadcast");
+        assert!(stream[2].starts_with(r#"{"frame":"keyframe","tick":3"#));
+
+        let mut viewer = SpectatorView::default();
+        for line in &stream {
+            let frame: FeedFrame = serde_json::from_str(line).expect("frame deserializes");
+            assert!(viewer.apply(&frame));
+        }
+        assert_eq!(viewer.state(), boards.last());
+        assert_eq!(viewer.tick(), 4);
+    }
+
+    #[test]
+    fn late_joiners_replay_only_the_latest_keyframe_and_its_deltas() {
+        let mut feed = SpectatorFeed::new(4);
+        let mut last = None;
+        for tick in 0..10 {
+            let state = board(tick, vec![bug(1, tick as u32, 10)]);
+            let _ = feed.publish(tick, state.clone());
+            last = Some(state);
+        }
+        let frames: Vec<_> = feed.catch_up().collect();
+        assert_eq!(
+            frames.len(),
+            2,
+            "keyframe at tick 8 and the delta at tick 9"
+        );
+        assert!(matches!(frames[0], FeedFrame::Keyframe { tick: 8, .. }));
+
+        let mut viewer = SpectatorView::default();
+        assert!(
+            !viewer.apply(frames[1]),
+            "deltas before a keyframe are skipped"
+        );
+        for frame in frames {
+            assert!(viewer.apply(frame));
+        }
+        assert_eq!(viewer.state(), last.as_ref());
+    }
+}
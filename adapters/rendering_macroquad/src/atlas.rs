@@ -0,0 +1,262 @@
+//! Packing of sprite images into a single atlas texture.
+//!
+//! Drawing every sprite from one texture lets Macroquad batch consecutive sprite draws
+//! regardless of their key. Packing runs once on the first launch; the packed pixels and
+//! layout are cached next to the sprite manifest and reused while the sources are unchanged.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use macroquad::texture::Image;
+use serde::{Deserialize, Serialize};
+
+/// Gutter around every packed sprite, filled by repeating the sprite's edge pixels so
+/// linear filtering never samples a neighbouring sprite.
+const GUTTER: u32 = 1;
+/// Largest atlas edge accepted; Macroquad images store their dimensions as `u16`.
+const MAX_ATLAS_EDGE: u32 = u16::MAX as u32;
+/// Version of the cached layout format; bump it whenever packing output changes.
+const CACHE_VERSION: u32 = 1;
+/// Cached atlas layout, stored as TOML.
+const CACHE_LAYOUT_FILE: &str = "atlas.toml";
+/// Cached atlas pixels, stored as raw RGBA8 rows.
+const CACHE_PIXELS_FILE: &str = "atlas.rgba";
+
+/// Pixel rectangle of a sprite inside the atlas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AtlasRegion {
+    /// Left edge in pixels.
+    pub x: u32,
+    /// Top edge in pixels.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+/// Placement of every sprite inside the atlas, keyed by manifest sprite name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AtlasLayout {
+    /// Atlas width in pixels, a power of two.
+    pub width: u32,
+    /// Atlas height in pixels, a power of two.
+    pub height: u32,
+    /// Region occupied by each sprite, excluding its gutter.
+    pub regions: BTreeMap<String, AtlasRegion>,
+}
+
+impl AtlasLayout {
+    /// Packs sprites of the given sizes into shelves, tallest sprites first.
+    ///
+    /// The atlas is at least as wide as the widest sprite and roughly square; ties are
+    /// broken by name so identical inputs always produce identical layouts.
+    pub(crate) fn pack(sizes: &BTreeMap<String, (u32, u32)>) -> Result<Self> {
+        let padded = |extent: u32| extent + 2 * GUTTER;
+        let widest = sizes.values().map(|(width, _)| padded(*width)).max();
+        let area: u64 = sizes
+            .values()
+            .map(|(width, height)| u64::from(padded(*width)) * u64::from(padded(*height)))
+            .sum();
+        let side = (area as f64).sqrt().ceil() as u32;
+        let width = widest.unwrap_or(1).max(side).max(1).next_power_of_two();
+
+        let mut order: Vec<_> = sizes.iter().collect();
+        order.sort_by(|(a_name, (_, a_height)), (b_name, (_, b_height))| {
+            b_height.cmp(a_height).then(a_name.cmp(b_name))
+        });
+
+        let mut regions = BTreeMap::new();
+        let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+        for (name, (sprite_width, sprite_height)) in order {
+            if x + padded(*sprite_width) > width {
+                x = 0;
+                y += shelf_height;
+                shelf_height = 0;
+            }
+            let _ = regions.insert(
+                name.clone(),
+                AtlasRegion {
+                    x: x + GUTTER,
+                    y: y + GUTTER,
+                    width: *sprite_width,
+                    height: *sprite_height,
+                },
+            );
+            x += padded(*sprite_width);
+            shelf_height = shelf_height.max(padded(*sprite_height));
+        }
+
+        let height = (y + shelf_height).max(1).next_power_of_two();
+        if width > MAX_ATLAS_EDGE || height > MAX_ATLAS_EDGE {
+            bail!("sprite atlas of {width}x{height} pixels exceeds {MAX_ATLAS_EDGE} pixels");
+        }
+        Ok(Self {
+            width,
+            height,
+            regions,
+        })
+    }
+}
+
+/// Packed atlas pixels together with their layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PackedAtlas {
+    /// Placement of every sprite.
+    pub layout: AtlasLayout,
+    /// Atlas pixels as RGBA8 rows, top row first.
+    pub pixels: Vec<u8>,
+}
+
+impl PackedAtlas {
+    /// Packs the decoded sprite images into one atlas.
+    pub(crate) fn pack(images: &BTreeMap<String, Image>) -> Result<Self> {
+        let sizes = images
+            .iter()
+            .map(|(name, image)| {
+                (
+                    name.clone(),
+                    (u32::from(image.width), u32::from(image.height)),
+                )
+            })
+            .collect();
+        let layout = AtlasLayout::pack(&sizes)?;
+        let mut pixels = vec![0; layout.width as usize * layout.height as usize * 4];
+        for (name, image) in images {
+            let region = layout.regions[name];
+            blit_with_gutter(&mut pixels, layout.width, image, region);
+        }
+        Ok(Self { layout, pixels })
+    }
+
+    /// Loads a cached atlas packed from sources with the same fingerprint.
+    ///
+    /// Returns `None` when the cache is missing, stale or unreadable.
+    pub(crate) fn load_cached(directory: &Path, fingerprint: &str) -> Option<Self> {
+        let layout = fs::read_to_string(directory.join(CACHE_LAYOUT_FILE)).ok()?;
+        let cached: CachedLayout = toml::from_str(&layout).ok()?;
+        if cached.version != CACHE_VERSION || cached.fingerprint != fingerprint {
+            return None;
+        }
+        let pixels = fs::read(directory.join(CACHE_PIXELS_FILE)).ok()?;
+        let expected = cached.layout.width as usize * cached.layout.height as usize * 4;
+        (pixels.len() == expected).then_some(Self {
+            layout: cached.layout,
+            pixels,
+        })
+    }
+
+    /// Caches the atlas so later launches with the same fingerprint skip packing.
+    pub(crate) fn store(&self, directory: &Path, fingerprint: &str) -> Result<()> {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("failed to create {}", directory.display()))?;
+        let cached = CachedLayout {
+            version: CACHE_VERSION,
+            fingerprint: fingerprint.to_string(),
+            layout: self.layout.clone(),
+        };
+        let layout = toml::to_string(&cached).context("failed to encode atlas layout")?;
+        fs::write(directory.join(CACHE_PIXELS_FILE), &self.pixels)
+            .with_context(|| format!("failed to write atlas pixels to {}", directory.display()))?;
+        fs::write(directory.join(CACHE_LAYOUT_FILE), layout)
+            .with_context(|| format!("failed to write atlas layout to {}", directory.display()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedLayout {
+    version: u32,
+    fingerprint: String,
+    layout: AtlasLayout,
+}
+
+/// Copies `image` into `region`, then repeats its edge pixels across the surrounding gutter.
+fn blit_with_gutter(pixels: &mut [u8], atlas_width: u32, image: &Image, region: AtlasRegion) {
+    if region.width == 0 || region.height == 0 {
+        return;
+    }
+    let source_width = region.width as i64;
+    let source_height = region.height as i64;
+    let gutter = i64::from(GUTTER);
+    for row in -gutter..source_height + gutter {
+        for column in -gutter..source_width + gutter {
+            let source_row = row.clamp(0, source_height - 1) as usize;
+            let source_column = column.clamp(0, source_width - 1) as usize;
+            let source = (source_row * region.width as usize + source_column) * 4;
+            let target_row = (i64::from(region.y) + row) as usize;
+            let target_column = (i64::from(region.x) + column) as usize;
+            let target = (target_row * atlas_width as usize + target_column) * 4;
+            pixels[target..target + 4].copy_from_slice(&image.bytes[source..source + 4]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u16, height: u16, shade: u8) -> Image {
+        let mut bytes = Vec::new();
+        for index in 0..u32::from(width) * u32::from(height) {
+            bytes.extend_from_slice(&[shade, index as u8, 0, 255]);
+        }
+        Image {
+            bytes,
+            width,
+            height,
+        }
+    }
+
+    fn pixel(atlas: &PackedAtlas, x: u32, y: u32) -> [u8; 4] {
+        let index = (y * atlas.layout.width + x) as usize * 4;
+        atlas.pixels[index..index + 4].try_into().expect("pixel")
+    }
+
+    #[test]
+    fn packing_places_sprites_without_overlap_and_extrudes_gutters() {
+        let images = BTreeMap::from([
+            ("Tall".to_string(), image(2, 6, 10)),
+            ("Wide".to_string(), image(5, 2, 20)),
+            ("Small".to_string(), image(1, 1, 30)),
+        ]);
+        let atlas = PackedAtlas::pack(&images).expect("pack");
+        let layout = &atlas.layout;
+        assert!(layout.width.is_power_of_two() && layout.height.is_power_of_two());
+
+        let regions: Vec<_> = layout.regions.values().copied().collect();
+        for (index, a) in regions.iter().enumerate() {
+            assert!(a.x >= GUTTER && a.y >= GUTTER);
+            assert!(a.x + a.width + GUTTER <= layout.width);
+            assert!(a.y + a.height + GUTTER <= layout.height);
+            for b in &regions[index + 1..] {
+                let apart = a.x + a.width + GUTTER <= b.x - GUTTER
+                    || b.x + b.width + GUTTER <= a.x - GUTTER
+                    || a.y + a.height + GUTTER <= b.y - GUTTER
+                    || b.y + b.height + GUTTER <= a.y - GUTTER;
+                assert!(apart, "{a:?} overlaps {b:?}");
+            }
+        }
+
+        let wide = layout.regions["Wide"];
+        assert_eq!(pixel(&atlas, wide.x, wide.y), [20, 0, 0, 255]);
+        assert_eq!(pixel(&atlas, wide.x + 4, wide.y + 1), [20, 9, 0, 255]);
+        assert_eq!(pixel(&atlas, wide.x - 1, wide.y - 1), [20, 0, 0, 255]);
+        assert_eq!(pixel(&atlas, wide.x + 5, wide.y + 2), [20, 9, 0, 255]);
+        assert_eq!(PackedAtlas::pack(&images).expect("repack"), atlas);
+    }
+
+    #[test]
+    fn cached_atlases_are_reused_only_for_matching_fingerprints() {
+        let directory =
+            std::env::temp_dir().join(format!("maze-defence-atlas-{}", std::process::id()));
+        let images = BTreeMap::from([("BugBody".to_string(), image(3, 3, 7))]);
+        let atlas = PackedAtlas::pack(&images).expect("pack");
+
+        assert_eq!(PackedAtlas::load_cached(&directory, "a"), None);
+        atlas.store(&directory, "a").expect("store");
+        assert_eq!(PackedAtlas::load_cached(&directory, "a"), Some(atlas));
+        assert_eq!(PackedAtlas::load_cached(&directory, "b"), None);
+
+        fs::remove_dir_all(&directory).expect("remove atlas cache");
+    }
+}
@@ -0,0 +1,57 @@
+//! Tower kinds bound to the number keys, persisted in the profile.
+
+use maze_defence_core::TowerKind;
+use serde::{Deserialize, Serialize};
+
+/// Number of hotbar slots, one per key from `1` to `9`.
+pub(crate) const HOTBAR_SLOTS: usize = 9;
+
+/// Tower kinds bound to the hotbar slots, first slot first.
+///
+/// The profile stores the bindings as a list such as `["Basic", "Barrier"]`; slots past
+/// the end of the list, or past [`HOTBAR_SLOTS`], are unbound.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Hotbar {
+    slots: Vec<TowerKind>,
+}
+
+impl Default for Hotbar {
+    fn default() -> Self {
+        Self {
+            slots: TowerKind::ALL.to_vec(),
+        }
+    }
+}
+
+impl Hotbar {
+    /// Tower kind bound to the zero-based `slot`, if any.
+    pub(crate) fn kind(&self, slot: usize) -> Option<TowerKind> {
+        self.bindings().nth(slot)
+    }
+
+    /// Bound tower kinds in slot order.
+    pub(crate) fn bindings(&self) -> impl Iterator<Item = TowerKind> + '_ {
+        self.slots.iter().copied().take(HOTBAR_SLOTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_past_the_bindings_are_unbound() {
+        let hotbar = Hotbar::default();
+        assert_eq!(hotbar.kind(0), Some(TowerKind::Basic));
+        assert_eq!(hotbar.kind(2), Some(TowerKind::Repulsor));
+        assert_eq!(hotbar.kind(3), Some(TowerKind::Frost));
+        assert_eq!(hotbar.kind(4), None);
+
+        let crowded = Hotbar {
+            slots: vec![TowerKind::Barrier; HOTBAR_SLOTS + 1],
+        };
+        assert_eq!(crowded.bindings().count(), HOTBAR_SLOTS);
+        assert_eq!(crowded.kind(HOTBAR_SLOTS), None);
+    }
+}
@@ -20,27 +20,56 @@
 //! can host widgets. All UI-specific calls live inside the local `ui` module to
 //! avoid leaking Macroquad UI types throughout the renderer.
 
+mod atlas;
+mod hotbar;
+mod motion;
+mod prestige;
+mod profile;
+mod records;
+mod settings;
 mod sprites;
+mod throttle;
 mod ui;
-
-use self::ui::{draw_control_panel_ui, ControlPanelUiContext, ControlPanelUiResult};
+mod wave_seed;
+
+use self::hotbar::HOTBAR_SLOTS;
+use self::profile::Profile;
+use self::records::RecordBanner;
+use self::settings::{flash_intensity, MotionPreference, RenderSettings, RenderToggle};
+use self::throttle::IdleThrottle;
+use self::ui::{
+    draw_control_panel_ui, draw_merchant_offer_ui, draw_wave_report_ui, ControlPanelUiContext,
+    ControlPanelUiResult, MerchantOfferUiContext, WaveReportUiContext, WaveSeedAction,
+};
 use anyhow::{Context, Result};
 use glam::Vec2;
 use macroquad::math::Vec2 as MacroquadVec2;
 use macroquad::{
     color::BLACK,
-    input::{is_key_pressed, is_mouse_button_pressed, mouse_position, KeyCode, MouseButton},
+    input::{
+        get_last_key_pressed, is_key_down, is_key_pressed, is_mouse_button_down,
+        is_mouse_button_pressed, mouse_position, mouse_wheel, KeyCode, MouseButton,
+    },
+};
+use maze_defence_core::{
+    BetweenWaveEvent, BossPhase, BugArchetype, BugEffect, BugId, CellCoord, CellRect, Combo,
+    DangerHeatmap, FormationId, PlayMode, PreparationReward, StatusEffect, TowerId, TowerKind,
+    WaveContract, WaveDifficulty, WorldDebugStats,
 };
-use maze_defence_core::{CellRect, PlayMode, TowerId, TowerKind, WaveDifficulty};
 use maze_defence_rendering::{
-    visuals::heading_from_target_line, BugPresentation, BugVisual, Color, ControlPanelView,
-    FrameInput, FrameSimulationBreakdown, Presentation, RenderingBackend, Scene, SceneProjectile,
-    SceneTower, SceneWall, SpawnEffect, SpriteInstance, SpriteKey, TileGridPresentation,
-    TowerPreview, TowerTargetLine, TowerVisual,
+    visuals::heading_from_target_line, AttackRegion, BugEscape, BugHealthPresentation,
+    BugInspection, BugPresentation, BugVisual, Color, ComboFlash, ControlPanelView, ExitPortal,
+    FixedTimestep, FocusPulse, FormationEntrance, FrameInput, FrameInputSource,
+    FrameSimulationBreakdown, FrameTick, GoldFeedback, GroundSpriteTiles, GroupHotkey, Layered,
+    NestPresentation, Notifications, NumberFormat, PathPreview, Presentation, ProjectileWhiff,
+    Projection, RenderLayer, RenderingBackend, Scene, SceneProjectile, SceneTower, SceneWall,
+    SpawnEdge, SpawnEffect, SpriteInstance, SpriteKey, TileGridPresentation,
+    TowerCooldownIndicator, TowerPreview, TowerTargetLine, TowerVisual,
 };
 use std::{
     collections::{HashMap, VecDeque},
     f32::consts::{FRAC_PI_2, PI},
+    path::PathBuf,
     sync::mpsc,
     time::{Duration, Instant},
 };
@@ -54,6 +83,12 @@ pub struct ControlPanelInputState {
     mode_toggle_latched: bool,
     start_wave_latched: Option<WaveDifficulty>,
     replay_wave_latched: bool,
+    skip_countdown_latched: Option<PreparationReward>,
+    dismiss_report_latched: bool,
+    merchant_choice_latched: Option<bool>,
+    toggle_contract_latched: Option<WaveContract>,
+    select_tower_kind_latched: Option<TowerKind>,
+    set_wave_seed_latched: Option<u64>,
 }
 
 impl ControlPanelInputState {
@@ -91,6 +126,86 @@ impl ControlPanelInputState {
     pub fn register_replay_wave(&mut self) {
         self.replay_wave_latched = true;
     }
+
+    /// Returns the reward chosen when the control panel skipped the countdown and clears the
+    /// latch.
+    pub fn take_skip_countdown(&mut self) -> Option<PreparationReward> {
+        self.skip_countdown_latched.take()
+    }
+
+    /// Records that a control-panel button requested skipping the countdown this frame.
+    pub fn register_skip_countdown(&mut self, reward: PreparationReward) {
+        self.skip_countdown_latched = Some(reward);
+    }
+
+    /// Returns whether the after-action report was dismissed and clears the latch.
+    pub fn take_dismiss_report(&mut self) -> bool {
+        let latched = self.dismiss_report_latched;
+        self.dismiss_report_latched = false;
+        latched
+    }
+
+    /// Records that the after-action report's continue button was pressed this frame.
+    pub fn register_dismiss_report(&mut self) {
+        self.dismiss_report_latched = true;
+    }
+
+    /// Returns the latched answer to the merchant offer, clearing it so it is sent once.
+    pub fn take_merchant_choice(&mut self) -> Option<bool> {
+        self.merchant_choice_latched.take()
+    }
+
+    /// Records the answer the player gave in the merchant offer dialog this frame.
+    pub fn register_merchant_choice(&mut self, accept: bool) {
+        self.merchant_choice_latched = Some(accept);
+    }
+
+    /// Returns the latched contract toggle, clearing it so the toggle fires once.
+    pub fn take_toggle_contract(&mut self) -> Option<WaveContract> {
+        self.toggle_contract_latched.take()
+    }
+
+    /// Records that a contract button in the control panel was pressed this frame.
+    pub fn register_toggle_contract(&mut self, contract: WaveContract) {
+        self.toggle_contract_latched = Some(contract);
+    }
+
+    /// Returns the tower kind picked from the hotbar, clearing it so it is sent once.
+    pub fn take_select_tower_kind(&mut self) -> Option<TowerKind> {
+        self.select_tower_kind_latched.take()
+    }
+
+    /// Records that a hotbar key or button picked `kind` this frame.
+    pub fn register_select_tower_kind(&mut self, kind: TowerKind) {
+        self.select_tower_kind_latched = Some(kind);
+    }
+
+    /// Returns the wave seed pasted or re-rolled in the control panel, clearing it so it is
+    /// sent once.
+    pub fn take_set_wave_seed(&mut self) -> Option<u64> {
+        self.set_wave_seed_latched.take()
+    }
+
+    /// Records that the control panel replaced the wave seed with `seed` this frame.
+    pub fn register_set_wave_seed(&mut self, seed: u64) {
+        self.set_wave_seed_latched = Some(seed);
+    }
+}
+
+/// Reports whether the player pressed a key, held a mouse button, scrolled or moved the
+/// cursor since the previous frame.
+fn input_activity(last_cursor: &mut (f32, f32)) -> bool {
+    let cursor = mouse_position();
+    let moved = cursor != *last_cursor;
+    *last_cursor = cursor;
+    let (wheel_x, wheel_y) = mouse_wheel();
+    moved
+        || wheel_x != 0.0
+        || wheel_y != 0.0
+        || get_last_key_pressed().is_some()
+        || [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+            .into_iter()
+            .any(is_mouse_button_down)
 }
 
 /// Snapshot of edge-triggered keyboard shortcuts observed during a single frame.
@@ -102,10 +217,30 @@ struct KeyboardShortcuts {
     toggle_target_lines: bool,
     /// `H` toggles bug health-bar overlays.
     toggle_bug_health_bars: bool,
+    /// `C` toggles tower cooldown indicators.
+    toggle_tower_cooldowns: bool,
+    /// `M` toggles the danger heatmap overlay in builder mode.
+    toggle_danger_heatmap: bool,
     /// `Enter` launches an attack wave at normal difficulty.
     spawn_wave: bool,
     /// `Delete` removes the currently selected element.
     delete_pressed: bool,
+    /// `Shift` turns clicks and drags into tower selection.
+    selection_modifier: bool,
+    /// `Shift`+`1`-`9` recall a selection group and `Ctrl`+`1`-`9` assign the selection.
+    group_hotkey: Option<GroupHotkey>,
+    /// `G` switches the selected towers to the next targeting policy.
+    cycle_selection_targeting: bool,
+    /// `U` upgrades every selected tower.
+    upgrade_selection: bool,
+    /// `1`-`9` without a modifier pick the tower kind bound to that hotbar slot.
+    hotbar_slot: Option<usize>,
+    /// `R` rotates the tower placement footprint by 90°.
+    rotate_placement: bool,
+    /// `B` switches to the next buildable tower kind.
+    cycle_tower_kind: bool,
+    /// `F3` shows or hides the developer overlay.
+    toggle_debug_overlay: bool,
 }
 
 impl KeyboardShortcuts {
@@ -113,49 +248,107 @@ impl KeyboardShortcuts {
         let quit_requested = is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Q);
         let toggle_target_lines = is_key_pressed(KeyCode::T);
         let toggle_bug_health_bars = is_key_pressed(KeyCode::H);
+        let toggle_tower_cooldowns = is_key_pressed(KeyCode::C);
+        let toggle_danger_heatmap = is_key_pressed(KeyCode::M);
         let spawn_wave = is_key_pressed(KeyCode::Enter);
         let delete_pressed = is_key_pressed(KeyCode::Delete);
+        let selection_modifier =
+            is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        let assign = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+        let number_key = NUMBER_KEYS.iter().position(|key| is_key_pressed(*key));
+        let group_hotkey = number_key
+            .filter(|_| assign || selection_modifier)
+            .map(|slot| GroupHotkey { slot, assign });
+        let cycle_selection_targeting = is_key_pressed(KeyCode::G);
+        let upgrade_selection = is_key_pressed(KeyCode::U);
+        let hotbar_slot = number_key.filter(|_| !assign && !selection_modifier);
+        let rotate_placement = is_key_pressed(KeyCode::R);
+        let cycle_tower_kind = is_key_pressed(KeyCode::B);
+        let toggle_debug_overlay = is_key_pressed(KeyCode::F3);
 
         Self {
             quit_requested,
             toggle_target_lines,
             toggle_bug_health_bars,
+            toggle_tower_cooldowns,
+            toggle_danger_heatmap,
             spawn_wave,
             delete_pressed,
+            selection_modifier,
+            group_hotkey,
+            cycle_selection_targeting,
+            upgrade_selection,
+            hotbar_slot,
+            rotate_placement,
+            cycle_tower_kind,
+            toggle_debug_overlay,
         }
     }
+
+    /// Rendering preferences whose key was pressed this frame.
+    fn render_toggles(&self) -> impl Iterator<Item = RenderToggle> {
+        [
+            (self.toggle_target_lines, RenderToggle::TargetLines),
+            (self.toggle_bug_health_bars, RenderToggle::BugHealthBars),
+            (self.toggle_tower_cooldowns, RenderToggle::TowerCooldowns),
+            (self.toggle_danger_heatmap, RenderToggle::DangerHeatmap),
+        ]
+        .into_iter()
+        .filter_map(|(pressed, toggle)| pressed.then_some(toggle))
+    }
 }
 
+const NUMBER_KEYS: [KeyCode; HOTBAR_SLOTS] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
 /// Rendering backend implemented on top of macroquad.
 #[derive(Debug)]
 pub struct MacroquadBackend {
     swap_interval: Option<i32>,
-    show_fps: bool,
+    show_fps: Option<bool>,
+    show_tower_cooldowns: Option<bool>,
+    reduced_motion: Option<bool>,
     sprite_atlas: Option<SpriteAtlas>,
     turret_headings: HashMap<TowerId, f32>,
     load_sprites: bool,
+    profile_path: Option<PathBuf>,
+    number_format: Option<NumberFormat>,
 }
 
 impl Default for MacroquadBackend {
     fn default() -> Self {
         Self {
             swap_interval: None,
-            show_fps: false,
+            show_fps: None,
+            show_tower_cooldowns: None,
+            reduced_motion: None,
             sprite_atlas: None,
             turret_headings: HashMap::new(),
             load_sprites: true,
+            profile_path: None,
+            number_format: None,
         }
     }
 }
 
 impl MacroquadBackend {
-    /// Returns a backend that requests the platform's default swap interval.
+    /// Returns a backend that applies the rendering settings stored in its profile.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Configures the backend to request a specific swap interval from the platform.
+    /// Configures the backend to request a specific swap interval from the platform,
+    /// overriding the vsync preference stored in the profile.
     #[must_use]
     pub fn with_swap_interval(mut self, swap_interval: Option<i32>) -> Self {
         self.swap_interval = swap_interval;
@@ -170,10 +363,27 @@ impl MacroquadBackend {
         self.with_swap_interval(swap_interval)
     }
 
-    /// Configures whether the backend prints frame timing metrics once per second.
+    /// Configures whether the backend prints frame timing metrics once per second,
+    /// overriding the profile for this launch.
     #[must_use]
     pub fn with_show_fps(mut self, show: bool) -> Self {
-        self.show_fps = show;
+        self.show_fps = Some(show);
+        self
+    }
+
+    /// Configures whether tower cooldown indicators are visible when the game launches,
+    /// overriding the profile for this launch.
+    #[must_use]
+    pub fn with_show_tower_cooldowns(mut self, show: bool) -> Self {
+        self.show_tower_cooldowns = Some(show);
+        self
+    }
+
+    /// Configures whether animations and flashing effects are toned down, overriding both
+    /// the profile and the operating system's reduced-motion setting for this launch.
+    #[must_use]
+    pub fn with_reduced_motion(mut self, reduce: bool) -> Self {
+        self.reduced_motion = Some(reduce);
         self
     }
 
@@ -183,6 +393,23 @@ impl MacroquadBackend {
         self.load_sprites = enabled;
         self
     }
+
+    /// Configures the file that persists presentation preferences such as collapsed
+    /// control panel sections and rendering toggles. Without a profile, preferences last
+    /// for the session only.
+    #[must_use]
+    pub fn with_profile_path(mut self, path: Option<PathBuf>) -> Self {
+        self.profile_path = path;
+        self
+    }
+
+    /// Configures the separators used to write numbers and durations, overriding the
+    /// locale named by the environment.
+    #[must_use]
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = Some(format);
+        self
+    }
 }
 
 fn scene_requests_sprites(scene: &Scene) -> bool {
@@ -316,15 +543,20 @@ impl FpsCounter {
 impl RenderingBackend for MacroquadBackend {
     fn run<F>(self, presentation: Presentation, mut update_scene: F) -> Result<()>
     where
-        F: FnMut(Duration, FrameInput, &mut Scene) -> FrameSimulationBreakdown + 'static,
+        F: FnMut(FrameTick, FrameInput, &mut Scene) -> FrameSimulationBreakdown + 'static,
     {
         let Self {
             swap_interval,
             show_fps,
+            show_tower_cooldowns,
+            reduced_motion,
             sprite_atlas,
             turret_headings,
             load_sprites,
+            profile_path,
+            number_format,
         } = self;
+        let numbers = number_format.unwrap_or_else(NumberFormat::from_env);
 
         let Presentation {
             window_title,
@@ -332,13 +564,37 @@ impl RenderingBackend for MacroquadBackend {
             scene,
         } = presentation;
 
+        let mut profile = match profile_path.as_deref().map(Profile::load) {
+            Some(Ok(profile)) => profile,
+            Some(Err(error)) => {
+                eprintln!("Ignoring profile: {error:#}");
+                Profile::default()
+            }
+            None => Profile::default(),
+        };
+        let mut settings = profile.render;
+        if let Some(show) = show_fps {
+            settings.show_fps = show;
+        }
+        if let Some(show) = show_tower_cooldowns {
+            settings.tower_cooldowns = show;
+        }
+        if let Some(reduce) = reduced_motion {
+            settings.motion = if reduce {
+                MotionPreference::Reduced
+            } else {
+                MotionPreference::Full
+            };
+        }
+        let system_reduces_motion = motion::system_prefers_reduced_motion();
+
         let mut config = macroquad::window::Conf {
             window_title,
             window_width: 960,
             window_height: 960,
             ..macroquad::window::Conf::default()
         };
-        if let Some(swap_interval) = swap_interval {
+        if let Some(swap_interval) = swap_interval.or(settings.vsync.swap_interval()) {
             config.platform.swap_interval = Some(swap_interval);
         }
 
@@ -388,22 +644,38 @@ impl RenderingBackend for MacroquadBackend {
 
             let background = to_macroquad_color(clear_color);
             let mut fps_counter = FpsCounter::default();
-            let mut show_tower_target_lines = false;
-            let mut show_bug_health_bars = false;
             let mut control_panel_input = ControlPanelInputState::default();
+            let mut saved_profile = profile.clone();
+            let mut idle_throttle = IdleThrottle::default();
+            let mut last_cursor = mouse_position();
+            let mut record_banner: Option<RecordBanner> = None;
+            let mut debug_overlay = false;
+            let mut debug_timings: Option<FpsMetrics> = None;
+            let mut timestep = FixedTimestep::default();
+            let mut restore_selection_groups = Some(profile.selection_groups.clone());
+            let mut game_rules = Some(profile.prestige.game_rules());
+            let mut prestige_seen = 0;
 
             loop {
+                let frame_start = Instant::now();
+                let input_active = input_activity(&mut last_cursor);
                 let keyboard = KeyboardShortcuts::poll();
                 if keyboard.quit_requested {
                     break;
                 }
 
-                if keyboard.toggle_target_lines {
-                    show_tower_target_lines = !show_tower_target_lines;
+                if keyboard.toggle_debug_overlay {
+                    debug_overlay = !debug_overlay;
                 }
-
-                if keyboard.toggle_bug_health_bars {
-                    show_bug_health_bars = !show_bug_health_bars;
+                for toggle in keyboard.render_toggles() {
+                    settings.toggle(toggle);
+                    profile.render.adopt(&settings, toggle);
+                }
+                if let Some(kind) = keyboard
+                    .hotbar_slot
+                    .and_then(|slot| profile.hotbar.kind(slot))
+                {
+                    control_panel_input.register_select_tower_kind(kind);
                 }
 
                 macroquad::window::clear_background(background);
@@ -413,20 +685,37 @@ impl RenderingBackend for MacroquadBackend {
 
                 let dt_seconds = macroquad::time::get_frame_time();
                 let frame_dt = Duration::from_secs_f32(dt_seconds.max(0.0));
-                let metrics_before = SceneMetrics::from_scene(&scene, screen_width, screen_height);
-                let mode_toggle = control_panel_input.take_mode_toggle();
-                let start_wave = control_panel_input.take_start_wave();
-                let replay_wave = control_panel_input.take_replay_wave();
-                let frame_input = gather_frame_input(
-                    &scene,
-                    &metrics_before,
-                    mode_toggle,
-                    start_wave,
-                    replay_wave,
+                let frame_input = MacroquadFrameInput {
+                    control_panel: &mut control_panel_input,
                     keyboard,
-                );
+                    screen_width,
+                    screen_height,
+                }
+                .next_frame(&scene);
+                let frame_input = FrameInput {
+                    restore_selection_groups: restore_selection_groups.take(),
+                    game_rules: game_rules.take(),
+                    ..frame_input
+                };
 
-                let simulation_breakdown = update_scene(frame_dt, frame_input, &mut scene);
+                let tick = timestep.advance(frame_dt);
+                let simulation_breakdown = update_scene(tick, frame_input, &mut scene);
+                if let Some(groups) = &scene.selection_groups {
+                    profile.selection_groups.clone_from(groups);
+                }
+                if scene.prestige_earned > prestige_seen {
+                    profile.prestige.earn(scene.prestige_earned - prestige_seen);
+                    prestige_seen = scene.prestige_earned;
+                }
+                record_banner = record_banner.and_then(|banner| banner.advance(frame_dt));
+                if let Some(progress) = scene.level_progress {
+                    if let Some(record) = profile
+                        .best_waves
+                        .record(progress.level().get(), progress.wave())
+                    {
+                        record_banner = Some(RecordBanner::new(record));
+                    }
+                }
 
                 if !sprite_support_enabled {
                     debug_assert!(!scene_requests_sprites(&scene));
@@ -435,76 +724,107 @@ impl RenderingBackend for MacroquadBackend {
                 turret_headings
                     .retain(|tower_id, _| scene.towers.iter().any(|tower| tower.id == *tower_id));
 
-                let tile_grid = scene.tile_grid;
+                let throttled = idle_throttle.observe(
+                    frame_dt,
+                    input_active,
+                    scene.play_mode == PlayMode::Builder,
+                );
+
                 let metrics = SceneMetrics::from_scene(&scene, screen_width, screen_height);
+                let reduce_motion = settings.motion.reduces_motion(system_reduces_motion);
 
                 let render_start = Instant::now();
-                draw_ground(&scene, &metrics, sprite_atlas.as_ref());
-                if scene.play_mode == PlayMode::Builder {
-                    let grid_color = to_macroquad_color(tile_grid.line_color);
-
-                    draw_tile_grid(&metrics, &tile_grid, grid_color);
-                }
-                draw_cell_walls(&scene, &metrics);
-                draw_spawn_effects(&scene.spawn_effects, &metrics);
-
-                if scene.play_mode == PlayMode::Builder {
-                    draw_tower_builder_highlights(&scene.towers, &metrics);
-                }
-
-                let builder_preview = active_builder_preview(&scene);
-                if let Some(preview) = builder_preview {
-                    draw_tower_range_indicator(
-                        preview.kind,
-                        preview.region,
-                        &scene.tile_grid,
-                        &metrics,
-                    );
-                } else if let Some(tower) = hovered_tower(&scene) {
-                    draw_tower_range_indicator(
-                        tower.kind,
-                        tower.region,
-                        &scene.tile_grid,
-                        &metrics,
-                    );
-                }
-
-                draw_towers(
-                    &scene.towers,
-                    &scene.bugs,
-                    &scene.tower_targets,
-                    &metrics,
-                    sprite_atlas.as_ref(),
-                    &mut turret_headings,
-                    TowerDrawStage::Base,
-                );
-                if show_bug_health_bars {
-                    draw_bug_health_bars(&scene.bugs, &metrics);
+                let mut layer_context = LayerContext {
+                    scene: &scene,
+                    metrics: &metrics,
+                    sprite_atlas: sprite_atlas.as_ref(),
+                    turret_headings: &mut turret_headings,
+                    settings,
+                    reduce_motion,
+                    builder_preview: active_builder_preview(&scene),
+                    numbers,
+                };
+                for layer in RenderLayer::ALL {
+                    // Effects only animate; idle frames keep what the player reads.
+                    if throttled && layer == RenderLayer::Fx {
+                        continue;
+                    }
+                    draw_layer(layer, &mut layer_context);
                 }
-                draw_bugs(&scene.bugs, &metrics, sprite_atlas.as_ref());
-                draw_towers(
-                    &scene.towers,
-                    &scene.bugs,
-                    &scene.tower_targets,
-                    &metrics,
-                    sprite_atlas.as_ref(),
-                    &mut turret_headings,
-                    TowerDrawStage::Turret,
-                );
 
-                if let Some(preview) = builder_preview {
-                    draw_tower_preview(preview, &metrics);
-                }
+                let board_width = screen_width
+                    - scene
+                        .control_panel
+                        .map(|panel| panel.width.max(0.0))
+                        .unwrap_or(0.0)
+                        .min(screen_width);
+                draw_toasts(&scene.notifications, board_width, screen_height);
 
-                draw_projectiles(&scene.projectiles, &metrics);
-                if let Some(panel_context) = draw_control_panel(&scene, screen_width, screen_height)
-                {
+                if let Some(panel_context) = draw_control_panel(
+                    &scene,
+                    screen_width,
+                    screen_height,
+                    settings,
+                    reduce_motion,
+                    numbers,
+                    &profile,
+                ) {
+                    if let Some(banner) = record_banner {
+                        draw_record_banner(&banner, panel_context.origin.x);
+                    }
+                    if !throttled {
+                        draw_gold_gains(
+                            &scene.gold_feedback,
+                            panel_context.origin.x,
+                            screen_height,
+                            reduce_motion,
+                            numbers,
+                        );
+                    }
                     let mut control_panel_ui = macroquad::ui::root_ui();
                     let ControlPanelUiResult {
                         mode_toggle,
                         start_wave,
                         replay_wave: replay,
-                    } = draw_control_panel_ui(&mut control_panel_ui, panel_context);
+                        skip_countdown,
+                        toggle_contract,
+                        toggle_setting,
+                        select_tower_kind,
+                        wave_seed: seed_action,
+                        buy_prestige_bonus,
+                    } = draw_control_panel_ui(
+                        &mut control_panel_ui,
+                        panel_context,
+                        &mut profile.control_panel,
+                    );
+                    if let Some(contract) = toggle_contract {
+                        control_panel_input.register_toggle_contract(contract);
+                    }
+                    if let Some(kind) = select_tower_kind {
+                        control_panel_input.register_select_tower_kind(kind);
+                    }
+                    match (seed_action, scene.wave_seed) {
+                        (Some(WaveSeedAction::Copy), Some(seed)) => {
+                            wave_seed::copy_to_clipboard(seed);
+                        }
+                        (Some(WaveSeedAction::Paste), _) => {
+                            match wave_seed::paste_from_clipboard() {
+                                Some(seed) => control_panel_input.register_set_wave_seed(seed),
+                                None => eprintln!("The clipboard does not hold a wave seed"),
+                            }
+                        }
+                        (Some(WaveSeedAction::Reroll), Some(seed)) => {
+                            control_panel_input.register_set_wave_seed(wave_seed::rerolled(seed));
+                        }
+                        _ => {}
+                    }
+                    if let Some(toggle) = toggle_setting {
+                        settings.toggle(toggle);
+                        profile.render.adopt(&settings, toggle);
+                    }
+                    if let Some(bonus) = buy_prestige_bonus {
+                        let _ = profile.prestige.buy(bonus);
+                    }
                     if mode_toggle {
                         control_panel_input.register_mode_toggle();
                     }
@@ -514,10 +834,34 @@ impl RenderingBackend for MacroquadBackend {
                     if replay {
                         control_panel_input.register_replay_wave();
                     }
+                    if let Some(reward) = skip_countdown {
+                        control_panel_input.register_skip_countdown(reward);
+                    }
                 }
 
-                if show_tower_target_lines {
-                    draw_tower_targets(&scene.tower_targets, &metrics);
+                if let Some(path) = profile_path.as_deref() {
+                    if profile != saved_profile {
+                        if let Err(error) = profile.save(path) {
+                            eprintln!("Failed to save profile: {error:#}");
+                        }
+                        saved_profile = profile.clone();
+                    }
+                }
+
+                if let Some(report_context) =
+                    wave_report_context(&scene, screen_width, screen_height, numbers)
+                {
+                    let mut report_ui = macroquad::ui::root_ui();
+                    if draw_wave_report_ui(&mut report_ui, report_context) {
+                        control_panel_input.register_dismiss_report();
+                    }
+                } else if let Some(offer_context) =
+                    merchant_offer_context(&scene, screen_width, screen_height)
+                {
+                    let mut offer_ui = macroquad::ui::root_ui();
+                    if let Some(accept) = draw_merchant_offer_ui(&mut offer_ui, offer_context) {
+                        control_panel_input.register_merchant_choice(accept);
+                    }
                 }
 
                 let render_duration = render_start.elapsed();
@@ -531,7 +875,17 @@ impl RenderingBackend for MacroquadBackend {
                 };
 
                 let fps_metrics = fps_counter.record_frame(frame_breakdown);
-                if show_fps {
+                debug_timings = fps_metrics.or(debug_timings);
+                if debug_overlay {
+                    if let Some(stats) = &scene.debug_stats {
+                        draw_debug_overlay(&debug_overlay_lines(
+                            stats,
+                            debug_timings.as_ref(),
+                            numbers,
+                        ));
+                    }
+                }
+                if settings.show_fps {
                     if let Some(FpsMetrics {
                         per_second,
                         trailing_ten_seconds,
@@ -553,6 +907,10 @@ impl RenderingBackend for MacroquadBackend {
                     }
                 }
 
+                if throttled {
+                    std::thread::sleep(IdleThrottle::remaining_frame_time(frame_start.elapsed()));
+                }
+
                 macroquad::window::next_frame().await;
             }
         });
@@ -565,6 +923,7 @@ impl RenderingBackend for MacroquadBackend {
 
 #[derive(Clone, Copy, Debug)]
 struct SceneMetrics {
+    projection: Projection,
     scale: f32,
     offset_x: f32,
     offset_y: f32,
@@ -578,62 +937,61 @@ struct SceneMetrics {
 
 impl SceneMetrics {
     fn from_scene(scene: &Scene, screen_width: f32, screen_height: f32) -> Self {
-        let tile_grid = scene.tile_grid;
-        let world_width = tile_grid.bordered_width();
-        let world_height = tile_grid.bordered_height();
-        let panel_width = scene
-            .control_panel
-            .map(|panel| panel.width.max(0.0))
-            .unwrap_or(0.0)
-            .min(screen_width);
-        let available_width = (screen_width - panel_width).max(0.0);
-        let scale = if world_width == 0.0 || world_height == 0.0 {
-            1.0
-        } else {
-            let width_ratio = if available_width <= f32::EPSILON {
-                f32::INFINITY
-            } else {
-                available_width / world_width
-            };
-            width_ratio.min(screen_height / world_height)
-        };
-
-        let scaled_width = world_width * scale;
-        let scaled_height = world_height * scale;
-        let offset_x = ((available_width - scaled_width) * 0.5).max(0.0);
-        let offset_y = (screen_height - scaled_height) * 0.5;
-
-        let grid_width_scaled = tile_grid.width() * scale;
-        let grid_height_scaled = tile_grid.height() * scale;
-        let tile_step = tile_grid.tile_length * scale;
-        let cell_step = if tile_grid.cells_per_tile == 0 {
-            0.0
-        } else {
-            tile_step / tile_grid.cells_per_tile as f32
-        };
-        let grid_offset_x =
-            offset_x + TileGridPresentation::SIDE_BORDER_CELL_LAYERS as f32 * cell_step;
-        let grid_offset_y =
-            offset_y + TileGridPresentation::TOP_BORDER_CELL_LAYERS as f32 * cell_step;
+        let projection = Projection::for_scene(scene, screen_width, screen_height);
+        let origin = projection.origin();
+        let grid_origin = projection.grid_origin();
+        let grid_size = projection.grid_screen_size();
+        let cell_step = projection.cell_step();
 
         Self {
-            scale,
-            offset_x,
-            offset_y,
-            grid_offset_x,
-            grid_offset_y,
-            grid_width_scaled,
-            grid_height_scaled,
-            tile_step,
+            projection,
+            scale: projection.scale(),
+            offset_x: origin.x,
+            offset_y: origin.y,
+            grid_offset_x: grid_origin.x,
+            grid_offset_y: grid_origin.y,
+            grid_width_scaled: grid_size.x,
+            grid_height_scaled: grid_size.y,
+            tile_step: scene.tile_grid.tile_length * projection.scale(),
             cell_step,
         }
     }
 
     fn bug_center(&self, position: Vec2) -> Vec2 {
-        Vec2::new(
-            self.offset_x + position.x * self.cell_step,
-            self.offset_y + position.y * self.cell_step,
-        )
+        self.projection.cell_to_screen(position)
+    }
+}
+
+/// Gathers frame input from macroquad's mouse state, polled shortcuts and UI latches.
+struct MacroquadFrameInput<'a> {
+    control_panel: &'a mut ControlPanelInputState,
+    keyboard: KeyboardShortcuts,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl FrameInputSource for MacroquadFrameInput<'_> {
+    fn next_frame(&mut self, scene: &Scene) -> FrameInput {
+        let metrics = SceneMetrics::from_scene(scene, self.screen_width, self.screen_height);
+        let mode_toggle = self.control_panel.take_mode_toggle();
+        let start_wave = self.control_panel.take_start_wave();
+        let replay_wave = self.control_panel.take_replay_wave();
+        FrameInput {
+            skip_wave_countdown: self.control_panel.take_skip_countdown(),
+            dismiss_wave_report: self.control_panel.take_dismiss_report(),
+            merchant_choice: self.control_panel.take_merchant_choice(),
+            toggle_contract: self.control_panel.take_toggle_contract(),
+            select_tower_kind: self.control_panel.take_select_tower_kind(),
+            set_wave_seed: self.control_panel.take_set_wave_seed(),
+            ..gather_frame_input(
+                scene,
+                &metrics,
+                mode_toggle,
+                start_wave,
+                replay_wave,
+                self.keyboard,
+            )
+        }
     }
 }
 
@@ -647,15 +1005,14 @@ fn gather_frame_input(
 ) -> FrameInput {
     let (cursor_x, cursor_y) = mouse_position();
     let confirm_click = is_mouse_button_pressed(MouseButton::Left);
+    let confirm_held = is_mouse_button_down(MouseButton::Left);
     let remove_click = is_mouse_button_pressed(MouseButton::Right);
-    let start_wave = ui_start_wave.or_else(|| {
-        if keyboard.spawn_wave {
-            Some(WaveDifficulty::Normal)
-        } else {
-            None
-        }
+    let start_wave = ui_start_wave.or(if keyboard.spawn_wave {
+        Some(WaveDifficulty::Normal)
+    } else {
+        None
     });
-    gather_frame_input_from_observations(
+    let input = gather_frame_input_from_observations(
         scene,
         metrics,
         Vec2::new(cursor_x, cursor_y),
@@ -663,11 +1020,22 @@ fn gather_frame_input(
         start_wave,
         replay_wave,
         confirm_click,
+        confirm_held,
         remove_click,
         keyboard.delete_pressed,
-    )
+    );
+    FrameInput {
+        selection_modifier: keyboard.selection_modifier,
+        group_hotkey: keyboard.group_hotkey,
+        cycle_selection_targeting: keyboard.cycle_selection_targeting,
+        upgrade_selection: keyboard.upgrade_selection,
+        rotate_placement: keyboard.rotate_placement,
+        cycle_tower_kind: keyboard.cycle_tower_kind,
+        ..input
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn gather_frame_input_from_observations(
     scene: &Scene,
     metrics: &SceneMetrics,
@@ -676,6 +1044,7 @@ fn gather_frame_input_from_observations(
     start_wave: Option<WaveDifficulty>,
     replay_wave: bool,
     confirm_click: bool,
+    confirm_held: bool,
     remove_click: bool,
     delete_pressed: bool,
 ) -> FrameInput {
@@ -695,27 +1064,19 @@ fn gather_frame_input_from_observations(
         return input;
     }
 
-    let cursor_x = cursor_position.x;
-    let cursor_y = cursor_position.y;
-
-    let world_position = tile_grid.clamp_world_position(Vec2::new(
-        (cursor_x - metrics.grid_offset_x) / metrics.scale,
-        (cursor_y - metrics.grid_offset_y) / metrics.scale,
-    ));
+    let projection = metrics.projection;
+    let world_position =
+        tile_grid.clamp_world_position(projection.screen_to_world(cursor_position));
 
     input.cursor_world_space = Some(world_position);
 
-    let inside = cursor_x >= metrics.grid_offset_x
-        && cursor_x < metrics.grid_offset_x + metrics.grid_width_scaled
-        && cursor_y >= metrics.grid_offset_y
-        && cursor_y < metrics.grid_offset_y + metrics.grid_height_scaled;
-
-    if inside {
+    if projection.grid_contains(cursor_position) {
         let footprint = scene
             .active_tower_footprint_tiles
             .unwrap_or_else(|| Vec2::splat(1.0));
         input.cursor_tile_space = tile_grid.snap_world_to_tile(world_position, footprint);
         input.confirm_action = confirm_click;
+        input.confirm_held = confirm_held;
     }
 
     input.remove_action = remove_click || delete_pressed;
@@ -723,14 +1084,158 @@ fn gather_frame_input_from_observations(
     input
 }
 
+/// Borrowed frame state shared by the per-layer draw passes.
+struct LayerContext<'a> {
+    scene: &'a Scene,
+    metrics: &'a SceneMetrics,
+    sprite_atlas: Option<&'a SpriteAtlas>,
+    turret_headings: &'a mut HashMap<TowerId, f32>,
+    settings: RenderSettings,
+    reduce_motion: bool,
+    builder_preview: Option<TowerPreview>,
+    numbers: NumberFormat,
+}
+
+/// Draws every scene channel assigned to `layer`.
+///
+/// Scene items are drawn in the layer named by their [`Layered`] metadata; passes without
+/// a scene item of their own, such as the tile grid or bridge decks, name their layer here.
+fn draw_layer(layer: RenderLayer, context: &mut LayerContext<'_>) {
+    let scene = context.scene;
+    let metrics = context.metrics;
+    let sprite_atlas = context.sprite_atlas;
+    let settings = context.settings;
+    let reduce_motion = context.reduce_motion;
+    let builder_preview = context.builder_preview;
+
+    if layer == GroundSpriteTiles::LAYER {
+        draw_ground(scene, metrics, sprite_atlas);
+        draw_void_cells(&scene.void_cells, metrics);
+    }
+    if layer == RenderLayer::Ground && scene.play_mode == PlayMode::Builder {
+        let grid_color = to_macroquad_color(scene.tile_grid.line_color);
+        draw_tile_grid(metrics, &scene.tile_grid, grid_color);
+    }
+    if layer == DangerHeatmap::LAYER && settings.danger_heatmap {
+        if let Some(heatmap) = scene.danger_heatmap.as_ref() {
+            draw_danger_heatmap(heatmap, metrics);
+        }
+    }
+    if layer == SpawnEdge::LAYER {
+        draw_spawn_edges(&scene.spawn_edges, metrics);
+    }
+    if layer == NestPresentation::LAYER {
+        draw_nests(&scene.nests, metrics);
+    }
+    if layer == SpawnEffect::LAYER {
+        draw_spawn_effects(&scene.spawn_effects, metrics, reduce_motion);
+    }
+    if layer == SceneWall::LAYER {
+        draw_cell_walls(scene, metrics);
+    }
+    if layer == ExitPortal::LAYER {
+        if let Some(portal) = scene.exit_portal {
+            draw_exit_portal(portal, metrics);
+        }
+    }
+    if layer == BugEscape::LAYER {
+        let escaping_bugs: Vec<BugPresentation> = scene
+            .escaping_bugs
+            .iter()
+            .map(BugEscape::presentation)
+            .collect();
+        draw_bugs(&escaping_bugs, metrics, sprite_atlas);
+    }
+    if layer == BugPresentation::LAYER {
+        draw_bugs(&scene.bugs, metrics, sprite_atlas);
+    }
+    if layer == RenderLayer::Towers {
+        draw_bridge_decks(&scene.bridges, metrics);
+    }
+    if layer == SceneTower::LAYER {
+        if scene.play_mode == PlayMode::Builder {
+            draw_tower_builder_highlights(&scene.towers, metrics);
+        }
+        if let Some(region) = scene.attack_region.as_ref() {
+            draw_attack_region(region, metrics);
+        } else if let Some(preview) = builder_preview {
+            draw_tower_range_indicator(preview.kind, preview.region, &scene.tile_grid, metrics);
+        } else if let Some(tower) = hovered_tower(scene) {
+            draw_tower_range_indicator(tower.kind, tower.region, &scene.tile_grid, metrics);
+        }
+        for stage in [TowerDrawStage::Base, TowerDrawStage::Turret] {
+            draw_towers(
+                &scene.towers,
+                &scene.bugs,
+                &scene.tower_targets,
+                metrics,
+                sprite_atlas,
+                context.turret_headings,
+                stage,
+            );
+        }
+    }
+    if layer == SceneProjectile::LAYER {
+        draw_projectiles(&scene.projectiles, metrics);
+    }
+    if layer == ProjectileWhiff::LAYER {
+        draw_whiffs(&scene.whiffs, metrics, reduce_motion);
+    }
+    if layer == FormationEntrance::LAYER {
+        draw_formation_entrances(&scene.bugs, metrics, reduce_motion);
+    }
+    if layer == FocusPulse::LAYER {
+        if let Some(pulse) = scene.focus_pulse {
+            draw_focus_pulse(pulse, metrics, reduce_motion);
+        }
+    }
+    if layer == ComboFlash::LAYER {
+        draw_combo_flashes(&scene.combo_flashes, metrics, reduce_motion);
+    }
+    if layer == RenderLayer::Fx {
+        if let Some(priority) = scene.priority_target {
+            draw_priority_marker(&scene.bugs, priority, metrics);
+        }
+    }
+    if layer == TowerTargetLine::LAYER && settings.target_lines {
+        draw_tower_targets(&scene.tower_targets, metrics);
+    }
+    if layer == BugHealthPresentation::LAYER && settings.bug_health_bars {
+        draw_bug_health_bars(&scene.bugs, metrics);
+    }
+    if layer == TowerCooldownIndicator::LAYER && settings.tower_cooldowns {
+        draw_tower_cooldowns(&scene.tower_cooldowns, metrics, reduce_motion);
+    }
+    if layer == RenderLayer::UiOverlays {
+        draw_tower_selection(scene, metrics);
+    }
+    if let Some(preview) = builder_preview {
+        if layer == PathPreview::LAYER {
+            if let Some(path_preview) = scene.path_preview.as_ref() {
+                draw_path_preview(path_preview, preview, metrics);
+            }
+        }
+        if layer == TowerPreview::LAYER {
+            draw_tower_preview(preview, metrics);
+        }
+    }
+    if layer == BugInspection::LAYER {
+        if let Some(inspection) = &scene.hovered_bug {
+            draw_bug_tooltip(inspection, metrics, context.numbers);
+        }
+    }
+}
+
 fn draw_control_panel(
     scene: &Scene,
     screen_width: f32,
     screen_height: f32,
+    settings: RenderSettings,
+    reduce_motion: bool,
+    numbers: NumberFormat,
+    profile: &Profile,
 ) -> Option<ControlPanelUiContext> {
-    let Some(ControlPanelView { width, background }) = scene.control_panel else {
-        return None;
-    };
+    let ControlPanelView { width, background } = scene.control_panel?;
     if width <= f32::EPSILON {
         return None;
     }
@@ -745,13 +1250,101 @@ fn draw_control_panel(
         background: background_color,
         play_mode: scene.play_mode,
         gold: scene.gold,
+        gold_flash: flash_intensity(scene.gold_feedback.flash(), reduce_motion),
+        base_health: scene.base_health,
+        prestige: profile.prestige,
+        clock: scene.clock,
+        best_wave: scene
+            .level_progress
+            .and_then(|progress| profile.best_waves.best(progress.level().get())),
+        wave_countdown: scene.wave_countdown,
+        builder_countdown: scene.builder_countdown,
         difficulty: scene.difficulty,
         difficulty_selection: scene.difficulty_selection,
+        wave_contracts: scene.wave_contracts.clone(),
+        wave_composition: scene.wave_composition.clone(),
         analytics: scene.analytics.clone(),
+        between_wave_event: scene.between_wave_event,
         replay_available: scene.replay_available,
+        sandbox: scene.sandbox,
+        wave_seed: scene.wave_seed,
+        wave_shield: scene.wave_shield,
+        settings,
+        numbers,
+        hotbar: profile.hotbar.bindings().collect(),
+        selected_tower_kind: scene.selected_tower_kind,
+    })
+}
+
+/// Dimensions of the after-action report overlay in screen space.
+const WAVE_REPORT_SIZE: (f32, f32) = (320.0, 340.0);
+
+fn wave_report_context(
+    scene: &Scene,
+    screen_width: f32,
+    screen_height: f32,
+    numbers: NumberFormat,
+) -> Option<WaveReportUiContext> {
+    let report = scene.wave_report.clone()?;
+    let (origin, size) = centred_overlay(scene, screen_width, screen_height, WAVE_REPORT_SIZE);
+
+    Some(WaveReportUiContext {
+        origin,
+        size,
+        background: macroquad::color::Color::from_rgba(20, 20, 20, 235),
+        report,
+        numbers,
+    })
+}
+
+/// Dimensions of the merchant offer dialog in screen space.
+const MERCHANT_OFFER_SIZE: (f32, f32) = (300.0, 170.0);
+
+fn merchant_offer_context(
+    scene: &Scene,
+    screen_width: f32,
+    screen_height: f32,
+) -> Option<MerchantOfferUiContext> {
+    let presentation = scene
+        .between_wave_event
+        .filter(|event| event.awaiting_choice())?;
+    let BetweenWaveEvent::MerchantOffer { discount_percent } = presentation.event() else {
+        return None;
+    };
+    let (origin, size) = centred_overlay(scene, screen_width, screen_height, MERCHANT_OFFER_SIZE);
+
+    Some(MerchantOfferUiContext {
+        origin,
+        size,
+        background: macroquad::color::Color::from_rgba(20, 20, 20, 235),
+        discount_percent,
     })
 }
 
+/// Centres an overlay of the requested size within the play area left of the control panel.
+fn centred_overlay(
+    scene: &Scene,
+    screen_width: f32,
+    screen_height: f32,
+    (width, height): (f32, f32),
+) -> (MacroquadVec2, MacroquadVec2) {
+    let panel_width = scene
+        .control_panel
+        .map_or(0.0, |panel| panel.width.max(0.0))
+        .min(screen_width);
+    let available_width = screen_width - panel_width;
+    let width = width.min(available_width);
+    let height = height.min(screen_height);
+
+    (
+        MacroquadVec2::new(
+            ((available_width - width) * 0.5).max(0.0),
+            ((screen_height - height) * 0.5).max(0.0),
+        ),
+        MacroquadVec2::new(width, height),
+    )
+}
+
 fn active_builder_preview(scene: &Scene) -> Option<TowerPreview> {
     if scene.play_mode == PlayMode::Builder {
         scene.tower_preview
@@ -876,22 +1469,189 @@ fn draw_cell_walls(scene: &Scene, metrics: &SceneMetrics) {
     }
 }
 
-fn draw_spawn_effects(effects: &[SpawnEffect], metrics: &SceneMetrics) {
+/// Hatches spawner cells with faint diagonal strokes.
+/// Draws a mound on every nest cell with the nest's health bar over its middle cell.
+fn draw_nests(nests: &[NestPresentation], metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let mound = macroquad::color::Color::new(0.42, 0.22, 0.36, 0.85);
+    let burrow = macroquad::color::Color::new(0.16, 0.06, 0.12, 0.9);
+    let fill_color = macroquad::color::Color::new(0.78, 0.0, 0.0, 1.0);
+    let bar_height = (cell_step * 0.12).max(2.0) + 2.0;
+    for nest in nests {
+        for cell in &nest.cells {
+            let x = metrics.offset_x + (cell.column() as f32 + 0.5) * cell_step;
+            let y = metrics.offset_y + (cell.row() as f32 + 0.5) * cell_step;
+            macroquad::shapes::draw_circle(x, y, cell_step * 0.45, mound);
+            macroquad::shapes::draw_circle(x, y, cell_step * 0.2, burrow);
+        }
+
+        let Some(middle) = nest.cells.get(nest.cells.len() / 2) else {
+            continue;
+        };
+        let health = nest.health;
+        let bar_width = cell_step * 2.0;
+        let bar_left =
+            metrics.offset_x + (middle.column() as f32 + 0.5) * cell_step - bar_width * 0.5;
+        let bar_top = metrics.offset_y + middle.row() as f32 * cell_step - bar_height;
+        macroquad::shapes::draw_rectangle(bar_left, bar_top, bar_width, bar_height, BLACK);
+        if health.maximum > 0 {
+            let ratio = (health.current as f32 / health.maximum as f32).clamp(0.0, 1.0);
+            macroquad::shapes::draw_rectangle(
+                bar_left,
+                bar_top,
+                bar_width * ratio,
+                bar_height,
+                fill_color,
+            );
+        }
+    }
+}
+
+fn draw_spawn_edges(edges: &[SpawnEdge], metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let hatch = macroquad::color::Color::new(1.0, 0.45, 0.3, 0.3);
+    let thickness = (cell_step * 0.06).max(1.0);
+    for SpawnEdge { column, row } in edges {
+        let x = metrics.offset_x + *column as f32 * cell_step;
+        let y = metrics.offset_y + *row as f32 * cell_step;
+        macroquad::shapes::draw_line(x, y + cell_step, x + cell_step, y, thickness, hatch);
+        macroquad::shapes::draw_line(
+            x,
+            y + cell_step * 0.5,
+            x + cell_step * 0.5,
+            y,
+            thickness,
+            hatch,
+        );
+        macroquad::shapes::draw_line(
+            x + cell_step * 0.5,
+            y + cell_step,
+            x + cell_step,
+            y + cell_step * 0.5,
+            thickness,
+            hatch,
+        );
+    }
+}
+
+/// Lights the exit gap and points an arrow through it, out of the maze.
+fn draw_exit_portal(portal: ExitPortal, metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let origin = portal.region.origin();
+    let size = portal.region.size();
+    let x = metrics.offset_x + origin.column() as f32 * cell_step;
+    let y = metrics.offset_y + origin.row() as f32 * cell_step;
+    let width = size.width() as f32 * cell_step;
+    let height = size.height() as f32 * cell_step;
+    macroquad::shapes::draw_rectangle(
+        x,
+        y,
+        width,
+        height,
+        macroquad::color::Color::new(0.3, 0.9, 0.6, 0.35),
+    );
+
+    let center_x = x + width * 0.5;
+    let half_width = (width * 0.35).min(height);
+    macroquad::shapes::draw_triangle(
+        MacroquadVec2::new(center_x - half_width, y + height * 0.15),
+        MacroquadVec2::new(center_x + half_width, y + height * 0.15),
+        MacroquadVec2::new(center_x, y + height * 0.85),
+        macroquad::color::Color::new(0.85, 1.0, 0.9, 0.85),
+    );
+}
+
+fn draw_danger_heatmap(heatmap: &DangerHeatmap, metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    for (cell, intensity) in heatmap.iter() {
+        let x = metrics.offset_x + cell.column() as f32 * cell_step;
+        let y = metrics.offset_y + cell.row() as f32 * cell_step;
+        macroquad::shapes::draw_rectangle(x, y, cell_step, cell_step, heatmap_color(intensity));
+    }
+}
+
+fn heatmap_color(intensity: u16) -> macroquad::color::Color {
+    let heat = f32::from(intensity) / f32::from(DangerHeatmap::MAX_INTENSITY);
+    macroquad::color::Color::new(1.0, 0.8 * (1.0 - heat), 0.1, 0.15 + 0.45 * heat)
+}
+
+/// Floods void cells with water, leaving a darker rim along the edge of each cell.
+fn draw_void_cells(void_cells: &[CellCoord], metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let water = macroquad::color::Color::new(0.16, 0.36, 0.62, 1.0);
+    let shallows = macroquad::color::Color::new(0.1, 0.25, 0.45, 1.0);
+    let rim = (cell_step * 0.06).max(1.0);
+    for cell in void_cells {
+        let x = metrics.offset_x + cell.column() as f32 * cell_step;
+        let y = metrics.offset_y + cell.row() as f32 * cell_step;
+        macroquad::shapes::draw_rectangle(x, y, cell_step, cell_step, shallows);
+        macroquad::shapes::draw_rectangle(
+            x + rim,
+            y + rim,
+            cell_step - 2.0 * rim,
+            cell_step - 2.0 * rim,
+            water,
+        );
+    }
+}
+
+/// Shades bridge cells with a translucent deck so bugs in the tunnel stay visible beneath it.
+fn draw_bridge_decks(bridges: &[CellCoord], metrics: &SceneMetrics) {
+    let cell_step = metrics.cell_step;
+    if cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let deck = macroquad::color::Color::new(0.55, 0.4, 0.25, 0.45);
+    let plank = macroquad::color::Color::new(0.35, 0.25, 0.15, 0.6);
+    let plank_thickness = (cell_step * 0.08).max(1.0);
+    for cell in bridges {
+        let x = metrics.offset_x + cell.column() as f32 * cell_step;
+        let y = metrics.offset_y + cell.row() as f32 * cell_step;
+        macroquad::shapes::draw_rectangle(x, y, cell_step, cell_step, deck);
+        macroquad::shapes::draw_line(x, y, x + cell_step, y, plank_thickness, plank);
+    }
+}
+
+/// Draws spawn markers; with reduced motion the ring holds still and the fill stops pulsing.
+fn draw_spawn_effects(effects: &[SpawnEffect], metrics: &SceneMetrics, reduce_motion: bool) {
     if effects.is_empty() || metrics.cell_step <= f32::EPSILON {
         return;
     }
 
-    let radius = (metrics.cell_step * 0.35).max(1.0);
     let outline_thickness = (metrics.cell_step * 0.08).max(1.0);
 
     for effect in effects {
+        let radius = (metrics.cell_step * effect.radius).max(1.0);
+        let progress = if reduce_motion { 0.0 } else { effect.progress };
+        let ring_radius = radius * (1.0 + 0.5 * progress);
         let center_x = metrics.offset_x + (effect.column as f32 + 0.5) * metrics.cell_step;
         let center_y = metrics.offset_y + (effect.row as f32 + 0.5) * metrics.cell_step;
         let fill = Color::new(
             effect.color.red,
             effect.color.green,
             effect.color.blue,
-            0.55,
+            0.35 + 0.3 * progress,
         );
         let outline = effect.color.lighten(0.3);
 
@@ -899,7 +1659,7 @@ fn draw_spawn_effects(effects: &[SpawnEffect], metrics: &SceneMetrics) {
         macroquad::shapes::draw_circle_lines(
             center_x,
             center_y,
-            radius,
+            ring_radius,
             outline_thickness,
             to_macroquad_color(outline),
         );
@@ -914,34 +1674,278 @@ fn draw_tower_targets(tower_targets: &[TowerTargetLine], metrics: &SceneMetrics)
     }
 }
 
-fn draw_bug_health_bars(bugs: &[BugPresentation], metrics: &SceneMetrics) {
-    if metrics.cell_step <= f32::EPSILON {
+fn draw_bug_health_bars(bugs: &[BugPresentation], metrics: &SceneMetrics) {
+    if metrics.cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let bug_radius = metrics.cell_step * 0.5;
+    let bar_margin = metrics.cell_step * 0.1;
+    let bar_width = metrics.cell_step;
+    let bar_height = (metrics.cell_step * 0.12).max(2.0) + 2.0;
+
+    for bug in bugs.iter().filter(|bug| bug.health_bar) {
+        let bug_center = metrics.bug_center(bug.position());
+        let health = bug.health;
+        let bar_left = bug_center.x - bar_width * 0.5;
+        let bar_top = bug_center.y + bug_radius + bar_margin;
+
+        macroquad::shapes::draw_rectangle(bar_left, bar_top, bar_width, bar_height, BLACK);
+
+        if health.maximum > 0 && health.current > 0 {
+            let ratio = (health.current as f32 / health.maximum as f32).clamp(0.0, 1.0);
+            let fill_width = bar_width * ratio;
+            if fill_width > f32::EPSILON {
+                let fill_color = macroquad::color::Color::new(0.78, 0.0, 0.0, 1.0);
+                macroquad::shapes::draw_rectangle(
+                    bar_left, bar_top, fill_width, bar_height, fill_color,
+                );
+            }
+        }
+    }
+}
+
+fn draw_tower_cooldowns(
+    cooldowns: &[TowerCooldownIndicator],
+    metrics: &SceneMetrics,
+    reduce_motion: bool,
+) {
+    if metrics.cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let track_color = macroquad::color::Color::new(0.0, 0.0, 0.0, 0.55);
+    let reloading_color = macroquad::color::Color::new(0.95, 0.72, 0.2, 0.9);
+    let ready_color = macroquad::color::Color::new(0.45, 0.9, 0.55, 0.9);
+    let heating_color = macroquad::color::Color::new(0.95, 0.45, 0.15, 0.9);
+    let overheated_color = macroquad::color::Color::new(1.0, 0.15, 0.1, 1.0);
+
+    for indicator in cooldowns {
+        let Some((x, y, width, height)) = cooldown_bar_rectangle(indicator.region, metrics) else {
+            continue;
+        };
+
+        macroquad::shapes::draw_rectangle(x, y, width, height, track_color);
+        let fill_color = if indicator.is_ready() {
+            ready_color
+        } else {
+            reloading_color
+        };
+        let fill_width = width * indicator.progress;
+        if fill_width > f32::EPSILON {
+            macroquad::shapes::draw_rectangle(x, y, fill_width, height, fill_color);
+        }
+
+        if let Some(heat) = indicator.heat {
+            let heat_y = y - height - 1.0;
+            macroquad::shapes::draw_rectangle(x, heat_y, width, height, track_color);
+            let heat_color = if indicator.overheated {
+                overheated_color
+            } else {
+                heating_color
+            };
+            let heat_width = width * heat;
+            if heat_width > f32::EPSILON {
+                macroquad::shapes::draw_rectangle(x, heat_y, heat_width, height, heat_color);
+            }
+        }
+
+        let ready_flash = flash_intensity(indicator.ready_flash, reduce_motion);
+        if ready_flash > f32::EPSILON {
+            let origin = indicator.region.origin();
+            let size = indicator.region.size();
+            let flash = macroquad::color::Color::new(1.0, 1.0, 1.0, 0.35 * ready_flash);
+            macroquad::shapes::draw_rectangle_lines(
+                metrics.offset_x + origin.column() as f32 * metrics.cell_step,
+                metrics.offset_y + origin.row() as f32 * metrics.cell_step,
+                size.width() as f32 * metrics.cell_step,
+                size.height() as f32 * metrics.cell_step,
+                (metrics.cell_step * 0.2).max(1.0),
+                flash,
+            );
+        }
+    }
+}
+
+fn cooldown_bar_rectangle(
+    region: CellRect,
+    metrics: &SceneMetrics,
+) -> Option<(f32, f32, f32, f32)> {
+    if metrics.cell_step <= f32::EPSILON {
+        return None;
+    }
+
+    let size = region.size();
+    if size.width() == 0 || size.height() == 0 {
+        return None;
+    }
+
+    let origin = region.origin();
+    let inset = metrics.cell_step * 0.25;
+    let height = (metrics.cell_step * 0.2).max(2.0);
+    let width = (size.width() as f32 * metrics.cell_step - inset * 2.0).max(0.0);
+    let x = metrics.offset_x + origin.column() as f32 * metrics.cell_step + inset;
+    let y = metrics.offset_y + (origin.row() + size.height()) as f32 * metrics.cell_step
+        - inset
+        - height;
+
+    Some((x, y, width, height))
+}
+
+fn draw_priority_marker(bugs: &[BugPresentation], priority: BugId, metrics: &SceneMetrics) {
+    let Some([apex, left, right]) = priority_marker_triangle(bugs, priority, metrics) else {
+        return;
+    };
+    let fill = macroquad::color::Color::new(1.0, 0.85, 0.2, 1.0);
+    macroquad::shapes::draw_triangle(apex, left, right, fill);
+    macroquad::shapes::draw_triangle_lines(apex, left, right, 1.0, BLACK);
+}
+
+fn priority_marker_triangle(
+    bugs: &[BugPresentation],
+    priority: BugId,
+    metrics: &SceneMetrics,
+) -> Option<[MacroquadVec2; 3]> {
+    if metrics.cell_step <= f32::EPSILON {
+        return None;
+    }
+
+    let bug = bugs.iter().find(|bug| bug.id == priority)?;
+    let center = metrics.bug_center(bug.position());
+    let half_width = metrics.cell_step * 0.3;
+    let height = metrics.cell_step * 0.4;
+    let base_y = center.y - metrics.cell_step * 0.6;
+    let apex = MacroquadVec2::new(center.x, base_y);
+    let left = MacroquadVec2::new(center.x - half_width, base_y - height);
+    let right = MacroquadVec2::new(center.x + half_width, base_y - height);
+    Some([apex, left, right])
+}
+
+/// Ring drawn around a squad entering as a formation, in screen space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FormationRing {
+    center: MacroquadVec2,
+    radius: f32,
+    alpha: f32,
+}
+
+/// Groups bugs by the formation they entered with and frames each squad with a ring that
+/// tightens onto the squad and fades out as the shared entrance progresses.
+/// Rings drawn around squads that are entering the maze; with reduced motion the rings
+/// only fade instead of also tightening around the squad.
+fn formation_entrance_rings(
+    bugs: &[BugPresentation],
+    metrics: &SceneMetrics,
+    reduce_motion: bool,
+) -> Vec<FormationRing> {
+    if metrics.cell_step <= f32::EPSILON {
+        return Vec::new();
+    }
+
+    let mut squads: HashMap<FormationId, (Vec<MacroquadVec2>, f32)> = HashMap::new();
+    for bug in bugs {
+        let Some(entrance) = bug.entrance else {
+            continue;
+        };
+        let center = metrics.bug_center(bug.position());
+        squads
+            .entry(entrance.formation)
+            .or_insert_with(|| (Vec::new(), entrance.progress))
+            .0
+            .push(MacroquadVec2::new(center.x, center.y));
+    }
+
+    let mut rings: Vec<(FormationId, FormationRing)> = squads
+        .into_iter()
+        .map(|(formation, (centers, progress))| {
+            let center = centers
+                .iter()
+                .fold(MacroquadVec2::ZERO, |sum, point| sum + *point)
+                / centers.len() as f32;
+            let spread = centers
+                .iter()
+                .map(|point| point.distance(center))
+                .fold(0.0_f32, f32::max);
+            let settle = if reduce_motion {
+                1.0
+            } else {
+                1.0 + (1.0 - progress)
+            };
+            let ring = FormationRing {
+                center,
+                radius: (spread + metrics.cell_step * 0.75) * settle,
+                alpha: 1.0 - progress,
+            };
+            (formation, ring)
+        })
+        .collect();
+    rings.sort_by_key(|(formation, _)| *formation);
+    rings.into_iter().map(|(_, ring)| ring).collect()
+}
+
+fn draw_formation_entrances(bugs: &[BugPresentation], metrics: &SceneMetrics, reduce_motion: bool) {
+    let thickness = (metrics.cell_step * 0.12).max(1.0);
+    for ring in formation_entrance_rings(bugs, metrics, reduce_motion) {
+        macroquad::shapes::draw_circle_lines(
+            ring.center.x,
+            ring.center.y,
+            ring.radius,
+            thickness,
+            macroquad::color::Color::new(1.0, 0.95, 0.7, 0.8 * ring.alpha),
+        );
+    }
+}
+
+/// Draws two rings closing in on a focus pulse's event; with reduced motion the rings hold
+/// still and only fade.
+fn draw_focus_pulse(pulse: FocusPulse, metrics: &SceneMetrics, reduce_motion: bool) {
+    if metrics.cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let center = metrics.bug_center(pulse.center);
+    let thickness = (metrics.cell_step * 0.15).max(1.5);
+    let alpha = 1.0 - pulse.progress * pulse.progress;
+    for ring in 0..2 {
+        let phase = if reduce_motion {
+            0.5
+        } else {
+            (pulse.progress * 3.0 + ring as f32 * 0.5).fract()
+        };
+        let radius = metrics.cell_step * (2.5 - 1.5 * phase + ring as f32);
+        macroquad::shapes::draw_circle_lines(
+            center.x,
+            center.y,
+            radius,
+            thickness,
+            macroquad::color::Color::new(1.0, 0.35, 0.25, 0.9 * alpha),
+        );
+    }
+}
+
+/// Draws each combo's name above the bug it hit, rising as it fades; with reduced motion
+/// the text fades in place.
+fn draw_combo_flashes(flashes: &[ComboFlash], metrics: &SceneMetrics, reduce_motion: bool) {
+    const FONT_SIZE: u16 = 22;
+    if flashes.is_empty() || metrics.cell_step <= f32::EPSILON {
         return;
     }
 
-    let bug_radius = metrics.cell_step * 0.5;
-    let bar_margin = metrics.cell_step * 0.1;
-    let bar_width = metrics.cell_step;
-    let bar_height = (metrics.cell_step * 0.12).max(2.0) + 2.0;
-
-    for bug in bugs {
-        let bug_center = metrics.bug_center(bug.position());
-        let health = bug.health;
-        let bar_left = bug_center.x - bar_width * 0.5;
-        let bar_top = bug_center.y + bug_radius + bar_margin;
-
-        macroquad::shapes::draw_rectangle(bar_left, bar_top, bar_width, bar_height, BLACK);
-
-        if health.maximum > 0 && health.current > 0 {
-            let ratio = (health.current as f32 / health.maximum as f32).clamp(0.0, 1.0);
-            let fill_width = bar_width * ratio;
-            if fill_width > f32::EPSILON {
-                let fill_color = macroquad::color::Color::new(0.78, 0.0, 0.0, 1.0);
-                macroquad::shapes::draw_rectangle(
-                    bar_left, bar_top, fill_width, bar_height, fill_color,
-                );
-            }
-        }
+    for flash in flashes {
+        let text = match flash.combo {
+            Combo::Shatter => "SHATTER!",
+        };
+        let size = macroquad::text::measure_text(text, None, FONT_SIZE, 1.0);
+        let center = metrics.bug_center(flash.position);
+        let rise = if reduce_motion { 0.0 } else { flash.progress };
+        let y = center.y - metrics.cell_step * (1.0 + 1.5 * rise);
+        macroquad::text::draw_text(
+            text,
+            center.x - size.width * 0.5,
+            y,
+            f32::from(FONT_SIZE),
+            macroquad::color::Color::new(0.6, 0.9, 1.0, 1.0 - flash.progress),
+        );
     }
 }
 
@@ -961,7 +1965,7 @@ fn draw_bugs(bugs: &[BugPresentation], metrics: &SceneMetrics, sprite_atlas: Opt
                     bug_center.x,
                     bug_center.y,
                     bug_radius,
-                    to_macroquad_color(color),
+                    to_macroquad_color(bug.status_tint(color)),
                 );
                 macroquad::shapes::draw_circle_lines(
                     bug_center.x,
@@ -974,12 +1978,28 @@ fn draw_bugs(bugs: &[BugPresentation], metrics: &SceneMetrics, sprite_atlas: Opt
             BugVisual::Sprite { ref sprite, tint } => match sprite_atlas {
                 Some(atlas) => {
                     let base_position = bug.position();
-                    draw_sprite_instance(atlas, sprite, base_position, metrics, None, Some(tint));
+                    draw_sprite_instance(
+                        atlas,
+                        sprite,
+                        base_position,
+                        metrics,
+                        None,
+                        Some(bug.status_tint(tint)),
+                    );
                 }
                 None => {
                     debug_assert!(false, "sprite bug visual requested without sprite atlas",);
                 }
             },
+            BugVisual::Dot { color } => {
+                let bug_center = metrics.bug_center(bug.position());
+                macroquad::shapes::draw_circle(
+                    bug_center.x,
+                    bug_center.y,
+                    bug_radius * 0.5,
+                    to_macroquad_color(bug.status_tint(color)),
+                );
+            }
         }
     }
 }
@@ -997,6 +2017,329 @@ fn draw_projectiles(projectiles: &[SceneProjectile], metrics: &SceneMetrics) {
     }
 }
 
+/// Draws a fading puff where a projectile missed; with reduced motion the puff holds its
+/// size instead of spreading out.
+fn draw_whiffs(whiffs: &[ProjectileWhiff], metrics: &SceneMetrics, reduce_motion: bool) {
+    if whiffs.is_empty() || metrics.cell_step <= f32::EPSILON {
+        return;
+    }
+
+    let thickness = (metrics.cell_step * 0.06).max(1.0);
+    for whiff in whiffs {
+        let center = metrics.bug_center(whiff.position);
+        let spread = if reduce_motion { 0.5 } else { whiff.progress };
+        let radius = metrics.cell_step * (0.3 + 0.3 * spread);
+        let color = macroquad::color::Color::new(0.85, 0.85, 0.9, 0.8 * (1.0 - whiff.progress));
+        for stroke in 0..3 {
+            let angle = std::f32::consts::FRAC_PI_6 + stroke as f32 * std::f32::consts::FRAC_PI_3;
+            let direction = Vec2::new(angle.cos(), -angle.sin());
+            let inner = center + direction * radius * 0.5;
+            let outer = center + direction * radius;
+            macroquad::shapes::draw_line(inner.x, inner.y, outer.x, outer.y, thickness, color);
+        }
+    }
+}
+
+fn bug_tooltip_lines(inspection: &BugInspection, numbers: NumberFormat) -> Vec<String> {
+    let mut species = match inspection.species {
+        Some(species) => format!("Species {}", species.get()),
+        None => "Species –".to_string(),
+    };
+    if let Some(phase) = inspection.boss_phase {
+        let phase = match phase {
+            BossPhase::Opening => "opening",
+            BossPhase::Summoning => "summoning",
+            BossPhase::Enraged => "enraged",
+        };
+        species.push_str(&format!(" · Boss ({phase})"));
+    }
+    let health = format!(
+        "HP: {}/{}",
+        numbers.count(u64::from(inspection.health.current)),
+        numbers.count(u64::from(inspection.health.maximum))
+    );
+    let speed = format!(
+        "Speed: ×{} ({}/step)",
+        numbers.decimal(f64::from(inspection.speed_multiplier), 2),
+        numbers.duration(Duration::from_millis(u64::from(inspection.step_ms)))
+    );
+    let bounty = format!("Bounty: {} gold", numbers.count(inspection.bounty.get()));
+    let mut lines = vec![species, health, speed, bounty];
+    lines.extend(
+        inspection
+            .effects
+            .iter()
+            .map(|effect| bug_effect_line(*effect, numbers)),
+    );
+    lines
+}
+
+fn bug_effect_line(effect: BugEffect, numbers: NumberFormat) -> String {
+    match effect {
+        BugEffect::Status(status) => {
+            let name = match status.effect {
+                StatusEffect::Slowed => "Slowed",
+            };
+            let remaining = Duration::from_millis(u64::from(status.remaining_ms));
+            format!("{name} ({} left)", numbers.duration(remaining))
+        }
+        BugEffect::Archetype(archetype) => match archetype {
+            BugArchetype::Basic => "Basic".to_string(),
+            BugArchetype::Armored => "Armored".to_string(),
+            BugArchetype::Regenerating => "Regenerating".to_string(),
+            BugArchetype::Splitter => "Splitter".to_string(),
+        },
+        BugEffect::Shielded(shield) => format!(
+            "Wave shield: {}/{}",
+            numbers.count(u64::from(shield.current)),
+            numbers.count(u64::from(shield.capacity))
+        ),
+    }
+}
+
+fn draw_bug_tooltip(inspection: &BugInspection, metrics: &SceneMetrics, numbers: NumberFormat) {
+    if metrics.cell_step <= f32::EPSILON {
+        return;
+    }
+
+    const FONT_SIZE: f32 = 18.0;
+    const PADDING: f32 = 6.0;
+    let lines = bug_tooltip_lines(inspection, numbers);
+    let width = lines
+        .iter()
+        .map(|line| macroquad::text::measure_text(line, None, FONT_SIZE as u16, 1.0).width)
+        .fold(0.0, f32::max)
+        + PADDING * 2.0;
+    let height = FONT_SIZE * lines.len() as f32 + PADDING * 2.0;
+
+    let anchor = metrics.bug_center(inspection.position);
+    let left = anchor.x + metrics.cell_step;
+    let top = anchor.y - height - metrics.cell_step * 0.5;
+
+    macroquad::shapes::draw_rectangle(
+        left,
+        top,
+        width,
+        height,
+        macroquad::color::Color::new(0.0, 0.0, 0.0, 0.75),
+    );
+    for (index, line) in lines.iter().enumerate() {
+        let baseline = top + PADDING + FONT_SIZE * (index as f32 + 0.8);
+        macroquad::text::draw_text(
+            line,
+            left + PADDING,
+            baseline,
+            FONT_SIZE,
+            macroquad::color::WHITE,
+        );
+    }
+}
+
+fn draw_path_preview(path_preview: &PathPreview, tower: TowerPreview, metrics: &SceneMetrics) {
+    match path_preview {
+        PathPreview::Route(route) => {
+            let color = macroquad::color::Color::new(0.55, 0.85, 1.0, 0.6);
+            let thickness = (metrics.cell_step * 0.25).max(1.5);
+            for pair in path_preview_points(route, metrics).windows(2) {
+                macroquad::shapes::draw_line(
+                    pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, color,
+                );
+            }
+        }
+        PathPreview::Blocked => {
+            let Some((x, y, _, _)) = preview_rectangle(tower, metrics) else {
+                return;
+            };
+            const FONT_SIZE: f32 = 20.0;
+            macroquad::text::draw_text(
+                "Path blocked",
+                x,
+                y - FONT_SIZE * 0.4,
+                FONT_SIZE,
+                macroquad::color::Color::new(1.0, 0.3, 0.3, 1.0),
+            );
+        }
+    }
+}
+
+/// Approximate screen-space height of the gold counter label inside the control panel.
+const GOLD_COUNTER_ANCHOR_Y: f32 = 48.0;
+/// Horizontal distance from the control panel at which gold gains start floating.
+const GOLD_GAIN_START_OFFSET: f32 = 96.0;
+/// Gap kept between a gold gain's final position and the control panel edge.
+const GOLD_GAIN_END_GAP: f32 = 40.0;
+
+/// Centres the new-record banner near the top of the board, left of the control panel.
+/// Draws rejection toasts centred along the bottom of the board, newest at the bottom.
+fn draw_toasts(notifications: &Notifications, board_width: f32, screen_height: f32) {
+    const FONT_SIZE: u16 = 22;
+    const PADDING: f32 = 8.0;
+    const SPACING: f32 = 6.0;
+    let mut bottom = screen_height - 24.0;
+    for toast in notifications.toasts().iter().rev() {
+        let size = macroquad::text::measure_text(toast.message(), None, FONT_SIZE, 1.0);
+        let width = size.width + PADDING * 2.0;
+        let height = size.height + PADDING * 2.0;
+        let x = ((board_width - width) * 0.5).max(0.0);
+        let y = bottom - height;
+        macroquad::shapes::draw_rectangle(
+            x,
+            y,
+            width,
+            height,
+            macroquad::color::Color::new(0.08, 0.08, 0.1, 0.85 * toast.alpha()),
+        );
+        macroquad::text::draw_text(
+            toast.message(),
+            x + PADDING,
+            y + PADDING + size.offset_y,
+            f32::from(FONT_SIZE),
+            macroquad::color::Color::new(1.0, 0.9, 0.75, toast.alpha()),
+        );
+        bottom = y - SPACING;
+    }
+}
+
+/// Lines of the developer overlay: world counters followed by the per-phase timings
+/// averaged over the last full second, once one has elapsed.
+fn debug_overlay_lines(
+    stats: &WorldDebugStats,
+    timings: Option<&FpsMetrics>,
+    numbers: NumberFormat,
+) -> Vec<String> {
+    let count = |value: u32| numbers.count(u64::from(value));
+    let millis = |duration: Duration| {
+        format!(
+            "{} ms",
+            numbers.decimal(duration.as_secs_f64() * 1_000.0, 2)
+        )
+    };
+    let mut lines = vec![
+        format!("tick {}", numbers.count(stats.tick_index)),
+        format!(
+            "bugs {}  projectiles {}  towers {}",
+            count(stats.bugs),
+            count(stats.projectiles),
+            count(stats.towers)
+        ),
+        format!(
+            "navigation generation {}",
+            numbers.count(stats.navigation_generation)
+        ),
+        format!(
+            "wave cache {} / {} slots",
+            count(stats.wave_cache_entries),
+            count(stats.wave_cache_capacity)
+        ),
+        format!("bug slots {}", count(stats.bug_capacity)),
+    ];
+    if let Some(timings) = timings {
+        lines.push(format!(
+            "fps {}",
+            numbers.decimal(f64::from(timings.per_second), 1)
+        ));
+        lines.push(format!(
+            "sim {} (path {})",
+            millis(timings.avg_simulation),
+            millis(timings.avg_pathfinding)
+        ));
+        lines.push(format!(
+            "scene {}  render {}",
+            millis(timings.avg_scene_population),
+            millis(timings.avg_render)
+        ));
+    }
+    lines
+}
+
+fn draw_debug_overlay(lines: &[String]) {
+    const FONT_SIZE: u16 = 18;
+    const PADDING: f32 = 8.0;
+    const LINE_HEIGHT: f32 = 20.0;
+    let width = lines
+        .iter()
+        .map(|line| macroquad::text::measure_text(line, None, FONT_SIZE, 1.0).width)
+        .fold(0.0, f32::max);
+    macroquad::shapes::draw_rectangle(
+        PADDING,
+        PADDING,
+        width + PADDING * 2.0,
+        lines.len() as f32 * LINE_HEIGHT + PADDING * 2.0,
+        macroquad::color::Color::new(0.0, 0.0, 0.0, 0.7),
+    );
+    for (index, line) in lines.iter().enumerate() {
+        macroquad::text::draw_text(
+            line,
+            PADDING * 2.0,
+            PADDING * 2.0 + (index as f32 + 0.75) * LINE_HEIGHT,
+            f32::from(FONT_SIZE),
+            macroquad::color::Color::new(0.75, 1.0, 0.75, 1.0),
+        );
+    }
+}
+
+fn draw_record_banner(banner: &RecordBanner, panel_left: f32) {
+    const FONT_SIZE: u16 = 32;
+    let text = banner.text();
+    let size = macroquad::text::measure_text(&text, None, FONT_SIZE, 1.0);
+    let x = ((panel_left - size.width) * 0.5).max(0.0);
+    let y = GOLD_COUNTER_ANCHOR_Y + size.height;
+    macroquad::text::draw_text(
+        &text,
+        x,
+        y,
+        f32::from(FONT_SIZE),
+        macroquad::color::Color::new(1.0, 0.85, 0.2, banner.alpha()),
+    );
+}
+
+/// Draws gold gains flying towards the counter; with reduced motion they fade in place
+/// beside it instead.
+fn draw_gold_gains(
+    feedback: &GoldFeedback,
+    panel_left: f32,
+    screen_height: f32,
+    reduce_motion: bool,
+    numbers: NumberFormat,
+) {
+    const FONT_SIZE: f32 = 22.0;
+    for gain in feedback.gains() {
+        let progress = gain.progress();
+        let travelled = if reduce_motion { 1.0 } else { progress };
+        let position = gold_gain_position(travelled, panel_left, screen_height);
+        macroquad::text::draw_text(
+            &format!("+{}", numbers.count(gain.amount().get())),
+            position.x,
+            position.y,
+            FONT_SIZE,
+            macroquad::color::Color::new(1.0, 0.85, 0.2, 1.0 - progress * progress),
+        );
+    }
+}
+
+fn gold_gain_position(progress: f32, panel_left: f32, screen_height: f32) -> Vec2 {
+    let start = Vec2::new(panel_left - GOLD_GAIN_START_OFFSET, screen_height * 0.5);
+    let end = Vec2::new(panel_left - GOLD_GAIN_END_GAP, GOLD_COUNTER_ANCHOR_Y);
+    let eased = 1.0 - (1.0 - progress.clamp(0.0, 1.0)).powi(2);
+    start.lerp(end, eased)
+}
+
+fn path_preview_points(route: &[CellCoord], metrics: &SceneMetrics) -> Vec<Vec2> {
+    if metrics.cell_step <= f32::EPSILON {
+        return Vec::new();
+    }
+
+    route
+        .iter()
+        .map(|cell| {
+            metrics.bug_center(Vec2::new(
+                cell.column() as f32 + 0.5,
+                cell.row() as f32 + 0.5,
+            ))
+        })
+        .collect()
+}
+
 fn tower_target_segments(
     tower_targets: &[TowerTargetLine],
     metrics: &SceneMetrics,
@@ -1008,14 +2351,8 @@ fn tower_target_segments(
     tower_targets
         .iter()
         .map(|line| {
-            let start = Vec2::new(
-                metrics.offset_x + line.from.x * metrics.cell_step,
-                metrics.offset_y + line.from.y * metrics.cell_step,
-            );
-            let end = Vec2::new(
-                metrics.offset_x + line.to.x * metrics.cell_step,
-                metrics.offset_y + line.to.y * metrics.cell_step,
-            );
+            let start = metrics.bug_center(line.from);
+            let end = metrics.bug_center(line.to);
             (start, end)
         })
         .collect()
@@ -1029,12 +2366,7 @@ fn projectile_points(projectiles: &[SceneProjectile], metrics: &SceneMetrics) ->
     Some(
         projectiles
             .iter()
-            .map(|projectile| {
-                Vec2::new(
-                    metrics.offset_x + projectile.position.x * metrics.cell_step,
-                    metrics.offset_y + projectile.position.y * metrics.cell_step,
-                )
-            })
+            .map(|projectile| metrics.bug_center(projectile.position))
             .collect(),
     )
 }
@@ -1149,6 +2481,49 @@ fn builder_highlight_palette() -> BuilderHighlightPalette {
     }
 }
 
+fn draw_tower_selection(scene: &Scene, metrics: &SceneMetrics) {
+    let outline = macroquad::color::Color::new(0.35, 0.9, 1.0, 1.0);
+    let thickness = (metrics.cell_step * 0.12).max(1.5);
+
+    for tower in scene
+        .towers
+        .iter()
+        .filter(|tower| scene.selected_towers.contains(&tower.id))
+    {
+        if let Some((x, y, width, height)) = cell_region_rectangle(tower.region, metrics) {
+            macroquad::shapes::draw_rectangle_lines(x, y, width, height, thickness, outline);
+        }
+    }
+
+    if let Some((x, y, width, height)) = scene
+        .selection_box
+        .and_then(|region| cell_region_rectangle(region, metrics))
+    {
+        let fill = macroquad::color::Color::new(0.35, 0.9, 1.0, 0.15);
+        macroquad::shapes::draw_rectangle(x, y, width, height, fill);
+        macroquad::shapes::draw_rectangle_lines(x, y, width, height, 1.0, outline);
+    }
+}
+
+fn cell_region_rectangle(region: CellRect, metrics: &SceneMetrics) -> Option<(f32, f32, f32, f32)> {
+    if metrics.cell_step <= f32::EPSILON {
+        return None;
+    }
+
+    let size = region.size();
+    if size.width() == 0 || size.height() == 0 {
+        return None;
+    }
+
+    let origin = region.origin();
+    Some((
+        metrics.offset_x + origin.column() as f32 * metrics.cell_step,
+        metrics.offset_y + origin.row() as f32 * metrics.cell_step,
+        size.width() as f32 * metrics.cell_step,
+        size.height() as f32 * metrics.cell_step,
+    ))
+}
+
 fn draw_tower_builder_highlights(towers: &[SceneTower], metrics: &SceneMetrics) {
     if metrics.cell_step <= f32::EPSILON {
         return;
@@ -1258,12 +2633,15 @@ fn draw_primitive_tower(
             );
         }
         TowerDrawStage::Turret => {
+            if !tower.kind.fires() {
+                return;
+            }
             let Some(center_cells) = tower_region_center(region) else {
                 return;
             };
 
-            let max_dimension = size.width().max(size.height()) as f32;
-            let half_length_cells = max_dimension * 0.5;
+            let min_dimension = size.width().min(size.height()) as f32;
+            let half_length_cells = min_dimension * 0.5;
             let direction = turret_direction_for(tower.id, center_cells, tower_targets, bugs);
             draw_turret(
                 center_cells,
@@ -1467,10 +2845,7 @@ fn draw_turret(
     let forward_length = forward_length_cells * metrics.cell_step;
     let total_length = forward_length + back_offset;
 
-    let center = Vec2::new(
-        metrics.offset_x + center_cells.x * metrics.cell_step,
-        metrics.offset_y + center_cells.y * metrics.cell_step,
-    );
+    let center = metrics.bug_center(center_cells);
 
     let start = center - direction * back_offset;
     let end = start + direction * total_length;
@@ -1530,6 +2905,31 @@ fn draw_tower_range_indicator(
     macroquad::shapes::draw_circle_lines(center_x, center_y, radius, outline_thickness, BLACK);
 }
 
+/// Fills a line-of-sight clipped range as a triangle fan around the tower centre.
+fn draw_attack_region(region: &AttackRegion, metrics: &SceneMetrics) {
+    if metrics.cell_step <= f32::EPSILON || region.outline().len() < 3 {
+        return;
+    }
+
+    let to_screen = |cell: Vec2| {
+        MacroquadVec2::new(
+            metrics.offset_x + cell.x * metrics.cell_step,
+            metrics.offset_y + cell.y * metrics.cell_step,
+        )
+    };
+    let center = to_screen(region.center());
+    let fill = macroquad::color::Color::new(1.0, 0.0, 0.0, 0.15);
+    let outline_thickness = (metrics.cell_step * 0.06).max(1.0);
+
+    let outline = region.outline();
+    for (index, &point) in outline.iter().enumerate() {
+        let start = to_screen(point);
+        let end = to_screen(outline[(index + 1) % outline.len()]);
+        macroquad::shapes::draw_triangle(center, start, end, fill);
+        macroquad::shapes::draw_line(start.x, start.y, end.x, end.y, outline_thickness, BLACK);
+    }
+}
+
 fn tower_region_center(region: CellRect) -> Option<Vec2> {
     let size = region.size();
     if size.width() == 0 || size.height() == 0 {
@@ -1606,11 +3006,13 @@ mod tests {
     use super::*;
     use glam::Vec2;
     use maze_defence_core::{
-        BugId, CellCoord, CellRect, CellRectSize, Gold, ProjectileId, TowerId, TowerKind,
+        BugId, BugStatus, CellCoord, CellRect, CellRectSize, Gold, ProjectileId, RoundOutcome,
+        TowerId, TowerKind, WaveId, WaveReport, WaveShieldSnapshot,
     };
     use maze_defence_rendering::{
-        BugHealthPresentation, ControlPanelView, DifficultyPresentation, GoldPresentation,
-        SpriteInstance, SpriteKey, TowerTargetLine,
+        BetweenWaveEventPresentation, BugHealthPresentation, CameraFocus, ControlPanelView,
+        DifficultyPresentation, FormationEntrance, GoldPresentation, SpriteInstance, SpriteKey,
+        TowerTargetLine, WaveReportPresentation,
     };
     use std::{collections::HashMap, f32::consts::FRAC_PI_2, time::Duration};
 
@@ -1625,28 +3027,51 @@ mod tests {
         .expect("valid grid");
         let wall_color = Color::from_rgb_u8(64, 64, 64);
 
-        Scene::new(
-            grid,
-            wall_color,
-            None,
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            None,
-            play_mode,
-            placement_preview,
-            None,
-            None,
-            Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
-            Some(GoldPresentation::new(Gold::new(0))),
-            Some(DifficultyPresentation::new(0)),
-            None,
-            None,
-            false,
-        )
+        Scene {
+            tower_preview: placement_preview,
+            control_panel: Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
+            gold: Some(GoldPresentation::new(Gold::new(0))),
+            difficulty: Some(DifficultyPresentation::new(0)),
+            ..Scene::new(grid, wall_color, play_mode)
+        }
+    }
+
+    #[test]
+    fn formation_rings_frame_each_squad_and_fade_as_entrance_ends() {
+        let scene = base_scene(PlayMode::Attack, None);
+        let metrics = SceneMetrics::from_scene(&scene, 960.0, 960.0);
+        let health = BugHealthPresentation::new(3, 3);
+        let color = Color::from_rgb_u8(200, 40, 40);
+        let squad = |id, x, progress| {
+            BugPresentation::new_circle(BugId::new(id), Vec2::new(x, 2.5), color, health)
+                .with_entrance(Some(FormationEntrance::new(FormationId::new(1), progress)))
+        };
+        let bugs = vec![
+            squad(1, 1.5, 0.25),
+            squad(2, 3.5, 0.25),
+            BugPresentation::new_circle(BugId::new(3), Vec2::new(8.5, 8.5), color, health),
+        ];
+
+        let rings = formation_entrance_rings(&bugs, &metrics, false);
+        assert_eq!(rings.len(), 1);
+        let ring = rings[0];
+        let expected = metrics.bug_center(Vec2::new(2.5, 2.5));
+        assert!((ring.center.x - expected.x).abs() < 1e-3);
+        assert!((ring.center.y - expected.y).abs() < 1e-3);
+        assert!((ring.alpha - 0.75).abs() < 1e-6);
+        assert!(ring.radius > metrics.cell_step);
+
+        let settled = vec![squad(1, 1.5, 1.0), squad(2, 3.5, 1.0)];
+        let settled_ring = formation_entrance_rings(&settled, &metrics, false)[0];
+        assert!(settled_ring.radius < ring.radius);
+        assert_eq!(settled_ring.alpha, 0.0);
+
+        let still_ring = formation_entrance_rings(&bugs, &metrics, true)[0];
+        assert!((still_ring.radius - settled_ring.radius).abs() < 1e-3);
+        assert_eq!(
+            still_ring.alpha, ring.alpha,
+            "reduced motion keeps the fade"
+        );
     }
 
     #[test]
@@ -1684,6 +3109,7 @@ mod tests {
             true,
             false,
             false,
+            false,
         );
         assert!(
             inside_input.confirm_action,
@@ -1701,6 +3127,7 @@ mod tests {
             true,
             false,
             false,
+            false,
         );
         assert!(
             outside_input.cursor_tile_space.is_none(),
@@ -1724,15 +3151,7 @@ mod tests {
             metrics.grid_offset_y + metrics.grid_height_scaled - 1.0,
         );
         let input = gather_frame_input_from_observations(
-            &scene,
-            &metrics,
-            cursor,
-            false,
-            None,
-            false,
-            false,
-            false,
-            false,
+            &scene, &metrics, cursor, false, None, false, false, false, false, false,
         );
 
         let tile = input
@@ -1758,6 +3177,89 @@ mod tests {
         assert!((bordered_height_scaled - expected_height).abs() <= f32::EPSILON);
     }
 
+    #[test]
+    fn wave_report_overlay_centres_in_play_area_when_report_pending() {
+        let mut scene = base_scene(PlayMode::Builder, None);
+        assert!(wave_report_context(&scene, 960.0, 720.0, NumberFormat::ENGLISH).is_none());
+
+        scene.wave_report = Some(WaveReportPresentation::new(WaveReport::new(
+            WaveId::new(0),
+            RoundOutcome::Win,
+            Duration::from_secs(30),
+            0,
+            Gold::new(4),
+            vec![(TowerId::new(1), 12)],
+        )));
+        let context = wave_report_context(&scene, 960.0, 720.0, NumberFormat::ENGLISH)
+            .expect("overlay context");
+        let panel_width = scene.control_panel.map_or(0.0, |panel| panel.width);
+        let centre = context.origin + context.size * 0.5;
+
+        assert!((centre.x - (960.0 - panel_width) * 0.5).abs() <= 1e-3);
+        assert!((centre.y - 360.0).abs() <= 1e-3);
+    }
+
+    #[test]
+    fn merchant_dialog_only_shows_while_an_offer_awaits_an_answer() {
+        let mut scene = base_scene(PlayMode::Builder, None);
+        let offer = BetweenWaveEvent::MerchantOffer {
+            discount_percent: 50,
+        };
+
+        scene.between_wave_event = Some(BetweenWaveEventPresentation::new(offer, false));
+        assert!(merchant_offer_context(&scene, 960.0, 720.0).is_none());
+
+        scene.between_wave_event = Some(BetweenWaveEventPresentation::new(
+            BetweenWaveEvent::GoldRush,
+            true,
+        ));
+        assert!(merchant_offer_context(&scene, 960.0, 720.0).is_none());
+
+        scene.between_wave_event = Some(BetweenWaveEventPresentation::new(offer, true));
+        let context = merchant_offer_context(&scene, 960.0, 720.0).expect("dialog context");
+        assert_eq!(context.discount_percent, 50);
+        let centre = context.origin + context.size * 0.5;
+        assert!((centre.y - 360.0).abs() <= 1e-3);
+    }
+
+    #[test]
+    fn heatmap_color_grows_more_opaque_and_redder_with_intensity() {
+        let cool = heatmap_color(1);
+        let hot = heatmap_color(DangerHeatmap::MAX_INTENSITY);
+
+        assert!(hot.a > cool.a);
+        assert!(hot.g < cool.g);
+        assert!((hot.a - 0.6).abs() <= f32::EPSILON);
+    }
+
+    #[test]
+    fn gold_gains_float_from_play_area_into_counter() {
+        let start = gold_gain_position(0.0, 800.0, 600.0);
+        let middle = gold_gain_position(0.5, 800.0, 600.0);
+        let end = gold_gain_position(1.0, 800.0, 600.0);
+
+        assert_eq!(start, Vec2::new(800.0 - GOLD_GAIN_START_OFFSET, 300.0));
+        assert_eq!(
+            end,
+            Vec2::new(800.0 - GOLD_GAIN_END_GAP, GOLD_COUNTER_ANCHOR_Y)
+        );
+        assert!(middle.y < start.y && middle.y > end.y);
+    }
+
+    #[test]
+    fn camera_focus_centres_requested_cell_and_applies_zoom() {
+        let mut scene = base_scene(PlayMode::Attack, None);
+        let unfocused = SceneMetrics::from_scene(&scene, 640.0, 480.0);
+        scene.camera_focus = Some(CameraFocus::new(Vec2::new(3.5, 4.5), 2.0));
+        let metrics = SceneMetrics::from_scene(&scene, 640.0, 480.0);
+
+        assert!((metrics.scale - unfocused.scale * 2.0).abs() <= 1e-4);
+        let center = metrics.bug_center(Vec2::new(3.5, 4.5));
+        let panel_width = scene.control_panel.map_or(0.0, |panel| panel.width);
+        assert!((center.x - (640.0 - panel_width) * 0.5).abs() <= 1e-3);
+        assert!((center.y - 240.0).abs() <= 1e-3);
+    }
+
     #[test]
     fn scene_metrics_bottom_border_scales_with_cells_per_tile() {
         let tile_length = 48.0;
@@ -1772,28 +3274,12 @@ mod tests {
             let tile_grid =
                 TileGridPresentation::new(6, 4, tile_length, cells_per_tile, tile_color)
                     .expect("cells_per_tile must be positive");
-            let scene = Scene::new(
-                tile_grid,
-                wall_color,
-                None,
-                Vec::new(),
-                Vec::new(),
-                Vec::new(),
-                Vec::new(),
-                Vec::new(),
-                Vec::new(),
-                None,
-                PlayMode::Attack,
-                None,
-                None,
-                None,
-                Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
-                Some(GoldPresentation::new(Gold::new(0))),
-                Some(DifficultyPresentation::new(0)),
-                None,
-                None,
-                false,
-            );
+            let scene = Scene {
+                control_panel: Some(ControlPanelView::new(200.0, Color::from_rgb_u8(0, 0, 0))),
+                gold: Some(GoldPresentation::new(Gold::new(0))),
+                difficulty: Some(DifficultyPresentation::new(0)),
+                ..Scene::new(tile_grid, wall_color, PlayMode::Attack)
+            };
             let metrics = SceneMetrics::from_scene(&scene, screen_width, screen_height);
 
             let bordered_height_scaled = metrics.grid_height_scaled
@@ -2025,11 +3511,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn path_preview_points_follow_cell_centres() {
+        let scene = base_scene(PlayMode::Builder, None);
+        let metrics = SceneMetrics::from_scene(&scene, 960.0, 960.0);
+        let route = [CellCoord::new(1, 1), CellCoord::new(1, 2)];
+
+        let points = path_preview_points(&route, &metrics);
+
+        assert_eq!(points.len(), 2);
+        assert_vec2_close(points[0], metrics.bug_center(Vec2::new(1.5, 1.5)));
+        assert_vec2_close(points[1], metrics.bug_center(Vec2::new(1.5, 2.5)));
+        assert!((points[1].y - points[0].y - metrics.cell_step).abs() <= 1e-4);
+    }
+
+    #[test]
+    fn priority_marker_points_down_at_flagged_bug() {
+        let scene = base_scene(PlayMode::Attack, None);
+        let metrics = SceneMetrics::from_scene(&scene, 960.0, 960.0);
+        let bugs = vec![
+            BugPresentation::new_circle(
+                BugId::new(1),
+                Vec2::new(1.5, 1.5),
+                Color::from_rgb_u8(200, 0, 0),
+                BugHealthPresentation::new(3, 3),
+            ),
+            BugPresentation::new_circle(
+                BugId::new(2),
+                Vec2::new(3.5, 2.5),
+                Color::from_rgb_u8(0, 200, 0),
+                BugHealthPresentation::new(3, 3),
+            ),
+        ];
+
+        let [apex, left, right] = priority_marker_triangle(&bugs, BugId::new(2), &metrics)
+            .expect("flagged bug should receive a marker");
+        let center = metrics.bug_center(Vec2::new(3.5, 2.5));
+        assert!((apex.x - center.x).abs() <= 1e-4);
+        assert!(apex.y < center.y);
+        assert!(left.y < apex.y && right.y < apex.y);
+        assert!(left.x < apex.x && apex.x < right.x);
+
+        assert!(priority_marker_triangle(&bugs, BugId::new(7), &metrics).is_none());
+    }
+
     #[test]
     fn sprite_draw_parameters_reject_zero_cell_step() {
         let instance = SpriteInstance::new(SpriteKey::TowerBase, Vec2::splat(1.0));
         let base_position = Vec2::new(1.0, 2.0);
+        let grid = base_scene(PlayMode::Attack, None).tile_grid;
         let metrics = SceneMetrics {
+            projection: Projection::new(&grid, Vec2::ZERO, None),
             scale: 1.0,
             offset_x: 0.0,
             offset_y: 0.0,
@@ -2200,4 +3732,77 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn bug_tooltip_lists_one_line_per_effect() {
+        let mut inspection = BugInspection {
+            bug: BugId::new(4),
+            position: Vec2::new(2.5, 2.5),
+            species: None,
+            health: BugHealthPresentation::new(6, 8),
+            speed_multiplier: 0.5,
+            step_ms: 500,
+            bounty: Gold::new(3),
+            boss_phase: None,
+            effects: Vec::new(),
+        };
+        assert_eq!(
+            bug_tooltip_lines(&inspection, NumberFormat::ENGLISH),
+            vec![
+                "Species –".to_string(),
+                "HP: 6/8".to_string(),
+                "Speed: ×0.50 (500 ms/step)".to_string(),
+                "Bounty: 3 gold".to_string(),
+            ]
+        );
+
+        inspection.effects = vec![
+            BugEffect::Status(BugStatus {
+                effect: StatusEffect::Slowed,
+                remaining_ms: 1_200,
+            }),
+            BugEffect::Archetype(BugArchetype::Armored),
+            BugEffect::Shielded(WaveShieldSnapshot {
+                current: 40,
+                capacity: 50,
+            }),
+        ];
+        let lines = bug_tooltip_lines(&inspection, NumberFormat::ENGLISH);
+        assert_eq!(
+            lines[4..],
+            [
+                "Slowed (1.2 s left)".to_string(),
+                "Armored".to_string(),
+                "Wave shield: 40/50".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn debug_overlay_lists_world_counters_and_phase_timings() {
+        let stats = WorldDebugStats {
+            tick_index: 12_345,
+            bugs: 40,
+            navigation_generation: 7,
+            wave_cache_entries: 2,
+            wave_cache_capacity: 3,
+            ..WorldDebugStats::default()
+        };
+        let counters = debug_overlay_lines(&stats, None, NumberFormat::ENGLISH);
+        assert_eq!(counters[0], "tick 12,345");
+        assert!(counters.contains(&"navigation generation 7".to_string()));
+        assert!(counters.contains(&"wave cache 2 / 3 slots".to_string()));
+
+        let timings = FpsMetrics {
+            per_second: 60.0,
+            trailing_ten_seconds: 60.0,
+            avg_simulation: Duration::from_micros(1_500),
+            avg_pathfinding: Duration::from_micros(250),
+            avg_scene_population: Duration::ZERO,
+            avg_render: Duration::from_millis(4),
+        };
+        let lines = debug_overlay_lines(&stats, Some(&timings), NumberFormat::CONTINENTAL);
+        assert_eq!(lines.len(), counters.len() + 3);
+        assert!(lines.contains(&"sim 1,50 ms (path 0,25 ms)".to_string()));
+    }
 }
@@ -0,0 +1,96 @@
+//! Detection of the operating system's reduced-motion preference.
+//!
+//! Each platform exposes the preference through a command-line tool, so detection runs a
+//! short query once at startup. Platforms without a known query, or where the query fails,
+//! report no preference.
+
+use std::process::Command;
+
+/// Reports whether the operating system asks applications to reduce motion.
+pub(crate) fn system_prefers_reduced_motion() -> bool {
+    query().unwrap_or(false)
+}
+
+/// GNOME turns animations off through `org.gnome.desktop.interface enable-animations`.
+#[cfg(target_os = "linux")]
+fn query() -> Option<bool> {
+    let output = run(
+        "gsettings",
+        &["get", "org.gnome.desktop.interface", "enable-animations"],
+    )?;
+    parse_flag(&output, "false", "true")
+}
+
+/// macOS stores the accessibility setting under `com.apple.universalaccess reduceMotion`.
+#[cfg(target_os = "macos")]
+fn query() -> Option<bool> {
+    let output = run(
+        "defaults",
+        &["read", "com.apple.universalaccess", "reduceMotion"],
+    )?;
+    parse_flag(&output, "1", "0")
+}
+
+/// Windows records "Animate controls and elements" as the `MinAnimate` registry value.
+#[cfg(target_os = "windows")]
+fn query() -> Option<bool> {
+    let output = run(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Control Panel\Desktop\WindowMetrics",
+            "/v",
+            "MinAnimate",
+        ],
+    )?;
+    parse_flag(&output, "0", "1")
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn query() -> Option<bool> {
+    None
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Reads the last word of a query's output as the reduced or full motion spelling.
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows", test)),
+    allow(dead_code)
+)]
+fn parse_flag(output: &str, reduced: &str, full: &str) -> Option<bool> {
+    let value = output.split_whitespace().last()?;
+    if value == reduced {
+        Some(true)
+    } else if value == full {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_outputs_parse_to_the_reduced_motion_flag() {
+        assert_eq!(parse_flag("false\n", "false", "true"), Some(true));
+        assert_eq!(parse_flag("true\n", "false", "true"), Some(false));
+        let registry =
+            "\r\nHKEY_CURRENT_USER\\Control Panel\\Desktop\\WindowMetrics\r\n    MinAnimate    REG_SZ    0\r\n";
+        assert_eq!(parse_flag(registry, "0", "1"), Some(true));
+        assert_eq!(parse_flag("", "1", "0"), None);
+        assert_eq!(parse_flag("maybe", "1", "0"), None);
+    }
+}
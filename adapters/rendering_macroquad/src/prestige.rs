@@ -0,0 +1,121 @@
+//! Meta currency earned by finished runs and the starting bonuses it buys, persisted in the
+//! profile.
+
+use maze_defence_core::{GameRules, Gold};
+use serde::{Deserialize, Serialize};
+
+/// Starting gold granted by every level of [`PrestigeBonus::StartingGold`].
+const GOLD_PER_LEVEL: u64 = 25;
+/// Price of the first level of [`PrestigeBonus::StartingGold`].
+const STARTING_GOLD_BASE_COST: u32 = 5;
+/// Price of the first level of [`PrestigeBonus::BaseHealth`].
+const BASE_HEALTH_BASE_COST: u32 = 20;
+
+/// Permanent starting bonuses that prestige can buy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PrestigeBonus {
+    /// Extra gold at the start of every game.
+    StartingGold,
+    /// One more bug may reach the exit before a round is lost.
+    BaseHealth,
+}
+
+impl PrestigeBonus {
+    /// Every bonus in the order the control panel lists them.
+    pub(crate) const ALL: [Self; 2] = [Self::StartingGold, Self::BaseHealth];
+}
+
+/// Unspent prestige together with the bonus levels bought so far.
+///
+/// Every level costs its bonus' base price times the level being bought, so the second
+/// level of a bonus costs twice as much as the first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Prestige {
+    balance: u32,
+    starting_gold: u32,
+    base_health: u32,
+}
+
+impl Prestige {
+    /// Unspent prestige.
+    pub(crate) fn balance(&self) -> u32 {
+        self.balance
+    }
+
+    /// Levels of `bonus` bought so far.
+    pub(crate) fn level(&self, bonus: PrestigeBonus) -> u32 {
+        match bonus {
+            PrestigeBonus::StartingGold => self.starting_gold,
+            PrestigeBonus::BaseHealth => self.base_health,
+        }
+    }
+
+    /// Price of the next level of `bonus`.
+    pub(crate) fn cost(&self, bonus: PrestigeBonus) -> u32 {
+        let base = match bonus {
+            PrestigeBonus::StartingGold => STARTING_GOLD_BASE_COST,
+            PrestigeBonus::BaseHealth => BASE_HEALTH_BASE_COST,
+        };
+        base.saturating_mul(self.level(bonus).saturating_add(1))
+    }
+
+    /// Adds prestige awarded by a finished run.
+    pub(crate) fn earn(&mut self, points: u32) {
+        self.balance = self.balance.saturating_add(points);
+    }
+
+    /// Buys the next level of `bonus`, returning whether the balance covered its price.
+    pub(crate) fn buy(&mut self, bonus: PrestigeBonus) -> bool {
+        let cost = self.cost(bonus);
+        if cost > self.balance {
+            return false;
+        }
+        self.balance -= cost;
+        let level = match bonus {
+            PrestigeBonus::StartingGold => &mut self.starting_gold,
+            PrestigeBonus::BaseHealth => &mut self.base_health,
+        };
+        *level = level.saturating_add(1);
+        true
+    }
+
+    /// Starting bonuses a new game receives from the levels bought so far.
+    pub(crate) fn game_rules(&self) -> GameRules {
+        let defaults = GameRules::default();
+        GameRules {
+            bonus_gold: Gold::new(GOLD_PER_LEVEL.saturating_mul(u64::from(self.starting_gold))),
+            base_health: defaults.base_health.saturating_add(self.base_health),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purchases_spend_the_balance_and_raise_the_starting_rules() {
+        let mut prestige = Prestige::default();
+        assert_eq!(prestige.game_rules(), GameRules::default());
+        assert!(!prestige.buy(PrestigeBonus::StartingGold));
+
+        prestige.earn(30);
+        assert!(prestige.buy(PrestigeBonus::StartingGold));
+        assert!(prestige.buy(PrestigeBonus::StartingGold));
+        assert_eq!(prestige.balance(), 15);
+        assert_eq!(prestige.cost(PrestigeBonus::StartingGold), 15);
+        assert!(!prestige.buy(PrestigeBonus::BaseHealth));
+
+        prestige.earn(5);
+        assert!(prestige.buy(PrestigeBonus::BaseHealth));
+        assert_eq!(prestige.balance(), 0);
+        assert_eq!(
+            prestige.game_rules(),
+            GameRules {
+                bonus_gold: Gold::new(50),
+                base_health: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Presentation preferences persisted between launches.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use maze_defence_core::CellCoord;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hotbar::Hotbar, prestige::Prestige, records::BestWaves, settings::RenderSettings,
+    ui::PanelLayout,
+};
+
+/// Player profile stored as TOML; sections missing from the file keep their defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Profile {
+    /// Collapsed sections of the control panel.
+    pub(crate) control_panel: PanelLayout,
+    /// Rendering toggles applied on startup.
+    pub(crate) render: RenderSettings,
+    /// Furthest wave reached on each level.
+    pub(crate) best_waves: BestWaves,
+    /// Tower kinds bound to the number keys.
+    pub(crate) hotbar: Hotbar,
+    /// Tower origin cells stored in each numbered selection group, first slot first.
+    pub(crate) selection_groups: Vec<Vec<CellCoord>>,
+    /// Unspent prestige and the starting bonuses bought with it.
+    pub(crate) prestige: Prestige,
+}
+
+impl Profile {
+    /// Loads the profile at `path`, treating a missing file as an empty profile.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read profile at {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("failed to parse profile at {}", path.display()))
+    }
+
+    /// Writes the profile to `path`, creating missing parent directories.
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let contents = toml::to_string_pretty(self).context("failed to encode profile")?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed to write profile at {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prestige::PrestigeBonus, settings::RenderToggle, ui::PanelSection};
+    use maze_defence_core::TowerKind;
+
+    #[test]
+    fn profiles_round_trip_and_default_when_missing() {
+        let directory =
+            std::env::temp_dir().join(format!("maze-defence-profile-{}", std::process::id()));
+        let path = directory.join("nested").join("profile.toml");
+        assert_eq!(
+            Profile::load(&path).expect("missing profile"),
+            Profile::default()
+        );
+
+        let mut profile = Profile::default();
+        profile.control_panel.toggle(PanelSection::Settings);
+        profile.render.toggle(RenderToggle::TargetLines);
+        profile.render.toggle(RenderToggle::Vsync);
+        let _ = profile.best_waves.record(3, 12);
+        profile.selection_groups = vec![vec![CellCoord::new(4, 2), CellCoord::new(6, 2)], vec![]];
+        profile.prestige.earn(40);
+        assert!(profile.prestige.buy(PrestigeBonus::BaseHealth));
+        profile.save(&path).expect("save profile");
+        let loaded = Profile::load(&path).expect("load profile");
+        assert_eq!(loaded.selection_groups, profile.selection_groups);
+        assert_eq!(loaded.prestige, profile.prestige);
+        assert_eq!(loaded.best_waves.best(3), Some(12));
+        assert!(loaded.control_panel.is_collapsed(PanelSection::Settings));
+        assert!(!loaded.control_panel.is_collapsed(PanelSection::Wave));
+        assert_eq!(loaded.render, profile.render);
+
+        fs::write(&path, "[render]\nbug_health_bars = true\n").expect("write");
+        let partial = Profile::load(&path).expect("partial profile");
+        assert!(partial.render.bug_health_bars);
+        assert!(
+            partial.render.tower_cooldowns,
+            "missing toggles keep defaults"
+        );
+
+        assert_eq!(partial.hotbar, Hotbar::default());
+
+        fs::write(&path, "hotbar = [\"Repulsor\", \"Basic\"]\n").expect("write");
+        let rebound = Profile::load(&path).expect("rebound hotbar");
+        assert_eq!(rebound.hotbar.kind(0), Some(TowerKind::Repulsor));
+        assert_eq!(rebound.hotbar.kind(2), None);
+        rebound.save(&path).expect("save rebound profile");
+        assert_eq!(Profile::load(&path).expect("reload"), rebound);
+
+        fs::write(&path, "control_panel = 3\n").expect("write");
+        assert!(Profile::load(&path).is_err());
+
+        fs::remove_dir_all(&directory).expect("remove profile directory");
+    }
+}
@@ -0,0 +1,128 @@
+//! Furthest waves reached per level, persisted in the profile.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How long the new-record banner stays on screen.
+const BANNER_DURATION: Duration = Duration::from_secs(4);
+
+/// Best wave reached on every level the player has launched a wave on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct BestWaves {
+    levels: Vec<BestWave>,
+}
+
+/// Furthest one-based wave number launched on a level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct BestWave {
+    level: u32,
+    wave: u32,
+}
+
+/// Announces that a level's best wave was beaten.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct NewBestWave {
+    /// Wave number that became the new best.
+    pub(crate) wave: u32,
+    /// Best wave the record replaced.
+    pub(crate) previous: u32,
+}
+
+impl BestWaves {
+    /// Returns the best wave reached on `level`, if any wave launched there.
+    pub(crate) fn best(&self, level: u32) -> Option<u32> {
+        self.levels
+            .iter()
+            .find(|best| best.level == level)
+            .map(|best| best.wave)
+    }
+
+    /// Records that `wave` launched on `level`.
+    ///
+    /// Returns the new record when `wave` beats an earlier best; the first wave recorded
+    /// on a level sets the best without announcing it.
+    pub(crate) fn record(&mut self, level: u32, wave: u32) -> Option<NewBestWave> {
+        let Some(best) = self.levels.iter_mut().find(|best| best.level == level) else {
+            self.levels.push(BestWave { level, wave });
+            self.levels.sort_by_key(|best| best.level);
+            return None;
+        };
+        if wave <= best.wave {
+            return None;
+        }
+        let previous = std::mem::replace(&mut best.wave, wave);
+        Some(NewBestWave { wave, previous })
+    }
+}
+
+/// Banner celebrating a new best wave until it fades out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct RecordBanner {
+    record: NewBestWave,
+    remaining: Duration,
+}
+
+impl RecordBanner {
+    /// Shows `record` for the full banner duration.
+    pub(crate) fn new(record: NewBestWave) -> Self {
+        Self {
+            record,
+            remaining: BANNER_DURATION,
+        }
+    }
+
+    /// Counts the banner down by `dt`, returning `None` once it has expired.
+    pub(crate) fn advance(self, dt: Duration) -> Option<Self> {
+        let remaining = self.remaining.saturating_sub(dt);
+        (!remaining.is_zero()).then_some(Self { remaining, ..self })
+    }
+
+    /// Text shown on the banner.
+    pub(crate) fn text(&self) -> String {
+        format!(
+            "New best: Wave {} (was Wave {})",
+            self.record.wave, self.record.previous
+        )
+    }
+
+    /// Opacity, fading out over the last second.
+    pub(crate) fn alpha(&self) -> f32 {
+        self.remaining.as_secs_f32().min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_beating_an_earlier_best_announces_a_record() {
+        let mut best = BestWaves::default();
+        assert_eq!(best.record(2, 5), None, "first wave sets the best quietly");
+        assert_eq!(best.record(2, 4), None);
+        assert_eq!(best.record(1, 9), None);
+        assert_eq!(
+            best.record(2, 6),
+            Some(NewBestWave {
+                wave: 6,
+                previous: 5
+            })
+        );
+        assert_eq!(best.best(2), Some(6));
+        assert_eq!(best.best(1), Some(9));
+        assert_eq!(best.best(3), None);
+
+        let banner = RecordBanner::new(NewBestWave {
+            wave: 6,
+            previous: 5,
+        });
+        assert_eq!(banner.text(), "New best: Wave 6 (was Wave 5)");
+        let fading = banner
+            .advance(BANNER_DURATION - Duration::from_millis(500))
+            .expect("still showing");
+        assert!((fading.alpha() - 0.5).abs() < 1e-3);
+        assert_eq!(fading.advance(Duration::from_secs(1)), None);
+    }
+}
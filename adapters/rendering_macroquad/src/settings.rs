@@ -0,0 +1,205 @@
+//! Rendering preferences toggled from the keyboard or the control panel.
+
+use serde::{Deserialize, Serialize};
+
+/// Vertical sync requested when the window is created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum VsyncPreference {
+    /// Keep the platform's default swap interval.
+    #[default]
+    PlatformDefault,
+    /// Synchronise presentation with the display refresh rate.
+    On,
+    /// Present frames as fast as possible.
+    Off,
+}
+
+impl VsyncPreference {
+    /// Swap interval requested from the platform, if any.
+    pub(crate) fn swap_interval(self) -> Option<i32> {
+        match self {
+            Self::PlatformDefault => None,
+            Self::On => Some(1),
+            Self::Off => Some(0),
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::PlatformDefault => Self::On,
+            Self::On => Self::Off,
+            Self::Off => Self::PlatformDefault,
+        }
+    }
+}
+
+/// Whether animations and flashing effects are toned down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MotionPreference {
+    /// Follow the operating system's reduced-motion setting, if it can be detected.
+    #[default]
+    System,
+    /// Always reduce motion and flashing.
+    Reduced,
+    /// Always play effects at full strength.
+    Full,
+}
+
+impl MotionPreference {
+    /// Reports whether effects should be subdued, given the system's preference.
+    pub(crate) fn reduces_motion(self, system_prefers_reduced: bool) -> bool {
+        match self {
+            Self::System => system_prefers_reduced,
+            Self::Reduced => true,
+            Self::Full => false,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::System => Self::Reduced,
+            Self::Reduced => Self::Full,
+            Self::Full => Self::System,
+        }
+    }
+}
+
+/// Share of its full strength a flashing effect keeps while motion is reduced.
+const REDUCED_FLASH_STRENGTH: f32 = 0.3;
+
+/// Scales a flash `intensity` down to a subdued glow when motion is reduced.
+pub(crate) fn flash_intensity(intensity: f32, reduce_motion: bool) -> f32 {
+    if reduce_motion {
+        intensity * REDUCED_FLASH_STRENGTH
+    } else {
+        intensity
+    }
+}
+
+/// Individual rendering preference flipped by a key press or a control panel button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RenderToggle {
+    /// Tower targeting lines, bound to `T`.
+    TargetLines,
+    /// Bug health bars, bound to `H`.
+    BugHealthBars,
+    /// Tower reload indicators, bound to `C`.
+    TowerCooldowns,
+    /// Danger heatmap overlay, bound to `M`.
+    DangerHeatmap,
+    /// Frame timing metrics printed to stdout.
+    ShowFps,
+    /// Vertical sync, cycling through the platform default, on and off.
+    Vsync,
+    /// Reduced motion, cycling through the system setting, reduced and full.
+    Motion,
+}
+
+/// Rendering preferences stored in the profile and applied on startup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RenderSettings {
+    /// Draw tower targeting lines.
+    pub(crate) target_lines: bool,
+    /// Draw bug health bars.
+    pub(crate) bug_health_bars: bool,
+    /// Draw tower reload indicators.
+    pub(crate) tower_cooldowns: bool,
+    /// Draw the danger heatmap in builder mode.
+    pub(crate) danger_heatmap: bool,
+    /// Print frame timing metrics once per second.
+    pub(crate) show_fps: bool,
+    /// Vertical sync requested at launch; changes apply on the next launch.
+    pub(crate) vsync: VsyncPreference,
+    /// Whether spawn rings, entrance rings, gold gains and flashes are toned down.
+    pub(crate) motion: MotionPreference,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            target_lines: false,
+            bug_health_bars: false,
+            tower_cooldowns: true,
+            danger_heatmap: false,
+            show_fps: false,
+            vsync: VsyncPreference::PlatformDefault,
+            motion: MotionPreference::System,
+        }
+    }
+}
+
+impl RenderSettings {
+    /// Flips the preference named by `toggle`.
+    pub(crate) fn toggle(&mut self, toggle: RenderToggle) {
+        match toggle {
+            RenderToggle::TargetLines => self.target_lines = !self.target_lines,
+            RenderToggle::BugHealthBars => self.bug_health_bars = !self.bug_health_bars,
+            RenderToggle::TowerCooldowns => self.tower_cooldowns = !self.tower_cooldowns,
+            RenderToggle::DangerHeatmap => self.danger_heatmap = !self.danger_heatmap,
+            RenderToggle::ShowFps => self.show_fps = !self.show_fps,
+            RenderToggle::Vsync => self.vsync = self.vsync.next(),
+            RenderToggle::Motion => self.motion = self.motion.next(),
+        }
+    }
+
+    /// Copies the preference named by `toggle` from `source`, leaving the others untouched.
+    ///
+    /// Used to persist a player's change without also persisting command-line overrides
+    /// of unrelated preferences.
+    pub(crate) fn adopt(&mut self, source: &Self, toggle: RenderToggle) {
+        match toggle {
+            RenderToggle::TargetLines => self.target_lines = source.target_lines,
+            RenderToggle::BugHealthBars => self.bug_health_bars = source.bug_health_bars,
+            RenderToggle::TowerCooldowns => self.tower_cooldowns = source.tower_cooldowns,
+            RenderToggle::DangerHeatmap => self.danger_heatmap = source.danger_heatmap,
+            RenderToggle::ShowFps => self.show_fps = source.show_fps,
+            RenderToggle::Vsync => self.vsync = source.vsync,
+            RenderToggle::Motion => self.motion = source.motion,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adopting_a_toggle_persists_only_that_preference() {
+        let mut saved = RenderSettings::default();
+        let mut active = RenderSettings {
+            show_fps: true,
+            ..saved
+        };
+
+        active.toggle(RenderToggle::BugHealthBars);
+        active.toggle(RenderToggle::Vsync);
+        active.toggle(RenderToggle::Vsync);
+        saved.adopt(&active, RenderToggle::BugHealthBars);
+        saved.adopt(&active, RenderToggle::Vsync);
+
+        assert!(saved.bug_health_bars);
+        assert!(!saved.show_fps, "overrides stay out of the profile");
+        assert_eq!(saved.vsync, VsyncPreference::Off);
+        assert_eq!(saved.vsync.swap_interval(), Some(0));
+    }
+
+    #[test]
+    fn motion_preference_overrides_the_system_only_when_set() {
+        let mut settings = RenderSettings::default();
+        assert!(settings.motion.reduces_motion(true));
+        assert!(!settings.motion.reduces_motion(false));
+
+        settings.toggle(RenderToggle::Motion);
+        assert!(settings.motion.reduces_motion(false));
+        settings.toggle(RenderToggle::Motion);
+        assert!(!settings.motion.reduces_motion(true));
+        settings.toggle(RenderToggle::Motion);
+        assert_eq!(settings.motion, MotionPreference::System);
+
+        assert_eq!(flash_intensity(1.0, false), 1.0);
+        assert!(flash_intensity(1.0, true) < 0.5);
+    }
+}
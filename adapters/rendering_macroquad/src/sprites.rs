@@ -9,23 +9,31 @@ use anyhow::{anyhow, bail, Context, Result};
 use macroquad::prelude::ImageFormat;
 use macroquad::{
     color::{Color as MacroquadColor, WHITE},
-    math::Vec2 as MacroquadVec2,
-    texture::{draw_texture_ex, DrawTextureParams, FilterMode, Texture2D},
+    math::{Rect, Vec2 as MacroquadVec2},
+    texture::{draw_texture_ex, DrawTextureParams, FilterMode, Image, Texture2D},
 };
 use maze_defence_rendering::SpriteKey;
 use serde::Deserialize;
 
+use crate::atlas::PackedAtlas;
+
 /// Default location of the sprite manifest relative to the game binary.
 const MANIFEST_RELATIVE_PATH: &str = "assets/manifest.toml";
+/// Directory next to the manifest that caches the packed atlas between launches.
+const ATLAS_CACHE_DIRECTORY: &str = ".atlas-cache";
 
-/// Collection of textures keyed by their logical sprite identifiers.
+/// Sprites packed into a single texture, addressed by their logical sprite identifiers.
 #[derive(Debug)]
 pub(crate) struct SpriteAtlas {
-    textures: HashMap<SpriteKey, Texture2D>,
+    texture: Texture2D,
+    regions: HashMap<SpriteKey, Rect>,
 }
 
 impl SpriteAtlas {
-    /// Loads all textures declared in `assets/manifest.toml` into GPU memory.
+    /// Packs all sprites declared in `assets/manifest.toml` into one texture in GPU memory.
+    ///
+    /// The packed atlas is cached next to the manifest and reused while neither the
+    /// manifest entries nor the sprite files change.
     pub(crate) fn new() -> Result<Self> {
         assert_sprite_api_references();
 
@@ -53,69 +61,105 @@ impl SpriteAtlas {
         })?;
 
         let sprite_sources = manifest.sprite_sources(&manifest_path)?;
-        let textures = load_textures_with(&sprite_sources, |source| {
-            let bytes = fs::read(&source.path).with_context(|| {
-                format!(
-                    "failed to read sprite file for {:?} at {}",
-                    source.key,
-                    source.path.display()
-                )
-            })?;
-            ensure_valid_image_data(source.format, &bytes, &source.path)?;
-            let texture = Texture2D::from_file_with_format(&bytes, Some(source.format));
-            texture.set_filter(FilterMode::Linear);
-            Ok(texture)
-        })?;
+        let cache_directory = manifest_path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+            .join(ATLAS_CACHE_DIRECTORY);
+        let fingerprint = sources_fingerprint(&sprite_sources);
+        let packed = match PackedAtlas::load_cached(&cache_directory, &fingerprint) {
+            Some(packed) => packed,
+            None => {
+                let images = load_images_with(&sprite_sources, |source| {
+                    let bytes = fs::read(&source.path).with_context(|| {
+                        format!(
+                            "failed to read sprite file for {:?} at {}",
+                            source.key,
+                            source.path.display()
+                        )
+                    })?;
+                    ensure_valid_image_data(source.format, &bytes, &source.path)?;
+                    Ok(Image::from_file_with_format(&bytes, Some(source.format)))
+                })?;
+                let named = images
+                    .into_iter()
+                    .map(|(key, image)| (sprite_key_name(key).to_string(), image))
+                    .collect();
+                let packed = PackedAtlas::pack(&named)?;
+                if let Err(error) = packed.store(&cache_directory, &fingerprint) {
+                    eprintln!("Failed to cache the sprite atlas: {error:#}");
+                }
+                packed
+            }
+        };
+
+        let mut regions = HashMap::with_capacity(packed.layout.regions.len());
+        for (name, region) in &packed.layout.regions {
+            let _ = regions.insert(
+                parse_sprite_key(name)?,
+                Rect::new(
+                    region.x as f32,
+                    region.y as f32,
+                    region.width as f32,
+                    region.height as f32,
+                ),
+            );
+        }
+        ensure_required_sprites(&regions)?;
+
+        let texture = Texture2D::from_rgba8(
+            packed.layout.width as u16,
+            packed.layout.height as u16,
+            &packed.pixels,
+        );
+        texture.set_filter(FilterMode::Linear);
 
-        Ok(Self { textures })
+        Ok(Self { texture, regions })
     }
 
     /// Returns `true` when the atlas contains the provided sprite key.
     pub(crate) fn contains(&self, key: SpriteKey) -> bool {
-        self.textures.contains_key(&key)
+        self.regions.contains_key(&key)
     }
 
-    /// Returns the texture dimensions for the requested sprite key.
+    /// Returns the sprite dimensions for the requested sprite key.
     pub(crate) fn dimensions(&self, key: SpriteKey) -> MacroquadVec2 {
-        let texture = self
-            .textures
-            .get(&key)
-            .unwrap_or_else(|| panic!("missing sprite {key:?} in atlas"));
-        MacroquadVec2::new(texture.width(), texture.height())
+        self.region(key).size()
     }
 
-    /// Returns the number of sprite textures managed by the atlas.
+    /// Returns the number of sprites packed into the atlas.
     pub(crate) fn len(&self) -> usize {
-        self.textures.len()
+        self.regions.len()
     }
 
     /// Draws the requested sprite using the supplied draw parameters.
     pub(crate) fn draw(&self, key: SpriteKey, params: DrawParams) {
-        let texture = self
-            .textures
-            .get(&key)
-            .unwrap_or_else(|| panic!("missing sprite {key:?} in atlas"));
+        let region = self.region(key);
 
-        let dest_size = MacroquadVec2::new(
-            texture.width() * params.scale.x,
-            texture.height() * params.scale.y,
-        );
+        let dest_size = MacroquadVec2::new(region.w * params.scale.x, region.h * params.scale.y);
 
         let draw_params = DrawTextureParams {
             dest_size: Some(dest_size),
+            source: Some(region),
             rotation: params.rotation,
             pivot: Some(params.pivot),
             ..Default::default()
         };
 
         draw_texture_ex(
-            *texture,
+            self.texture,
             params.position.x,
             params.position.y,
             params.tint,
             draw_params,
         );
     }
+
+    fn region(&self, key: SpriteKey) -> Rect {
+        *self
+            .regions
+            .get(&key)
+            .unwrap_or_else(|| panic!("missing sprite {key:?} in atlas"))
+    }
 }
 
 /// Parameters describing how a sprite should be drawn.
@@ -240,12 +284,35 @@ fn manifest_search_paths() -> Vec<PathBuf> {
     ]
 }
 
-fn load_textures_with<F>(
+/// Identifies the manifest entries and the size and modification time of each sprite file,
+/// so edited sprites invalidate the cached atlas.
+fn sources_fingerprint(sources: &[SpriteAssetSource]) -> String {
+    let mut fingerprint = String::new();
+    for source in sources {
+        let stamp = fs::metadata(&source.path).ok().map(|metadata| {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |since_epoch| since_epoch.as_nanos());
+            (metadata.len(), modified)
+        });
+        let _ = write!(
+            fingerprint,
+            "{}={}:{stamp:?};",
+            sprite_key_name(source.key),
+            source.path.display()
+        );
+    }
+    fingerprint
+}
+
+fn load_images_with<F>(
     sources: &[SpriteAssetSource],
     mut loader: F,
-) -> Result<HashMap<SpriteKey, Texture2D>>
+) -> Result<HashMap<SpriteKey, Image>>
 where
-    F: FnMut(&SpriteAssetSource) -> Result<Texture2D>,
+    F: FnMut(&SpriteAssetSource) -> Result<Image>,
 {
     let mut textures = HashMap::with_capacity(sources.len());
 
@@ -274,7 +341,7 @@ where
     Ok(textures)
 }
 
-fn ensure_required_sprites(textures: &HashMap<SpriteKey, Texture2D>) -> Result<()> {
+fn ensure_required_sprites<T>(textures: &HashMap<SpriteKey, T>) -> Result<()> {
     const REQUIRED: &[SpriteKey] = &[
         SpriteKey::TowerBase,
         SpriteKey::TowerTurret,
@@ -313,6 +380,15 @@ fn ensure_valid_image_data(format: ImageFormat, bytes: &[u8], path: &Path) -> Re
     Ok(())
 }
 
+fn sprite_key_name(key: SpriteKey) -> &'static str {
+    match key {
+        SpriteKey::TowerBase => "TowerBase",
+        SpriteKey::TowerTurret => "TowerTurret",
+        SpriteKey::BugBody => "BugBody",
+        SpriteKey::GroundGrass => "GroundGrass",
+    }
+}
+
 fn parse_sprite_key(name: &str) -> Result<SpriteKey> {
     match name {
         "TowerBase" => Ok(SpriteKey::TowerBase),
@@ -386,7 +462,7 @@ fn assert_sprite_api_references() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use macroquad::texture::Texture2D;
+    use macroquad::texture::Image;
     use std::fs;
 
     fn dummy_sources() -> Vec<SpriteAssetSource> {
@@ -436,6 +512,10 @@ mod tests {
     fn parse_sprite_key_rejects_unknown_entries() {
         assert!(parse_sprite_key("Unknown").is_err());
         assert_eq!(parse_sprite_key("TowerBase").unwrap(), SpriteKey::TowerBase);
+        assert_eq!(
+            parse_sprite_key(sprite_key_name(SpriteKey::GroundGrass)).unwrap(),
+            SpriteKey::GroundGrass
+        );
     }
 
     #[test]
@@ -482,7 +562,7 @@ mod tests {
         let mut sources = dummy_sources();
         let _ = sources.pop();
 
-        let result = load_textures_with(&sources, |_| Ok(Texture2D::empty()));
+        let result = load_images_with(&sources, |_| Ok(Image::empty()));
         assert!(result.is_err());
     }
 
@@ -496,7 +576,7 @@ mod tests {
             optional: false,
         });
 
-        let result = load_textures_with(&sources, |_| Ok(Texture2D::empty()));
+        let result = load_images_with(&sources, |_| Ok(Image::empty()));
         assert!(result.is_err());
     }
 
@@ -510,11 +590,11 @@ mod tests {
             optional: true,
         });
 
-        let result = load_textures_with(&sources, |source| {
+        let result = load_images_with(&sources, |source| {
             if source.key == SpriteKey::GroundGrass {
                 Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing").into())
             } else {
-                Ok(Texture2D::empty())
+                Ok(Image::empty())
             }
         });
 
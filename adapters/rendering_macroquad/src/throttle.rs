@@ -0,0 +1,64 @@
+//! Frame-rate throttling while the player is away from a paused game.
+//!
+//! Macroquad does not report window focus changes, so the adapter infers that nobody is
+//! watching from the input stream: once the simulation is paused and no key, button,
+//! wheel or cursor movement arrived for [`IDLE_DELAY`], frames are capped at
+//! [`THROTTLED_FRAME_TIME`]. A window in the background receives no input, so an unfocused
+//! paused game throttles as well. Running simulations are never throttled because their
+//! frames drive the attack the player is watching.
+
+use std::time::Duration;
+
+/// Time without input after which a paused game starts throttling.
+const IDLE_DELAY: Duration = Duration::from_secs(2);
+/// Minimum duration of a throttled frame, capping the loop at ten frames per second.
+const THROTTLED_FRAME_TIME: Duration = Duration::from_millis(100);
+
+/// Tracks player activity and decides whether the current frame is throttled.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct IdleThrottle {
+    idle_for: Duration,
+}
+
+impl IdleThrottle {
+    /// Records the frame's activity and reports whether the frame should be throttled.
+    ///
+    /// Any activity, or a running simulation, restores the full frame rate immediately.
+    pub(crate) fn observe(&mut self, dt: Duration, active: bool, paused: bool) -> bool {
+        if active || !paused {
+            self.idle_for = Duration::ZERO;
+            return false;
+        }
+        self.idle_for = self.idle_for.saturating_add(dt);
+        self.idle_for >= IDLE_DELAY
+    }
+
+    /// Time left to wait so a throttled frame that took `elapsed` lasts its full budget.
+    pub(crate) fn remaining_frame_time(elapsed: Duration) -> Duration {
+        THROTTLED_FRAME_TIME.saturating_sub(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_idle_paused_frames_are_throttled() {
+        let mut throttle = IdleThrottle::default();
+        assert!(!throttle.observe(IDLE_DELAY, false, false), "running sims");
+        assert!(!throttle.observe(IDLE_DELAY / 2, false, true));
+        assert!(throttle.observe(IDLE_DELAY / 2, false, true));
+        assert!(!throttle.observe(Duration::ZERO, true, true), "input wakes");
+        assert!(!throttle.observe(IDLE_DELAY / 2, false, true));
+
+        assert_eq!(
+            IdleThrottle::remaining_frame_time(Duration::from_millis(30)),
+            Duration::from_millis(70)
+        );
+        assert_eq!(
+            IdleThrottle::remaining_frame_time(Duration::from_secs(1)),
+            Duration::ZERO
+        );
+    }
+}
@@ -2,19 +2,39 @@
 //!
 //! This module hosts all uses of `macroquad::ui` so the rest of the adapter can
 //! remain agnostic of Macroquad's UI types. Future control-panel widgets should
-//! be added here via `draw_control_panel_ui`.
+//! be added to one of the [`PanelSection`]s drawn by `draw_control_panel_ui`.
 
 use macroquad::{
-    color::{Color, WHITE},
-    math::{RectOffset, Vec2},
-    ui::{hash, Ui},
+    color::{Color, BLACK, WHITE},
+    math::{Rect, RectOffset, Vec2},
+    ui::{hash, Skin, Ui},
+};
+use maze_defence_core::{
+    BetweenWaveEvent, BugRank, PlayMode, PreparationReward, RoundOutcome, TowerKind, WaveContract,
+    WaveDifficulty,
 };
-use maze_defence_core::{PlayMode, WaveDifficulty};
 use maze_defence_rendering::{
-    AnalyticsPresentation, DifficultyPresentation, DifficultySelectionPresentation,
-    GoldPresentation,
+    AnalyticsPresentation, BetweenWaveEventPresentation, BugHealthPresentation, ClockPresentation,
+    DifficultyPresentation, DifficultySelectionPresentation, GoldPresentation, NumberFormat,
+    SpeciesPreviewPresentation, WaveCompositionPresentation, WaveContractPresentation,
+    WaveCountdownPresentation, WaveReportPresentation,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, time::Duration};
+
+use crate::{
+    prestige::{Prestige, PrestigeBonus},
+    settings::{MotionPreference, RenderSettings, RenderToggle, VsyncPreference},
+    to_macroquad_color,
 };
 
+/// Edge length of the tinted species icons in the wave composition preview.
+const SPECIES_ICON_SIZE: f32 = 14.0;
+/// Height of the wave shield bar.
+const SHIELD_BAR_HEIGHT: f32 = 10.0;
+/// Fill colour of the wave shield bar.
+const SHIELD_BAR_COLOR: Color = Color::new(0.35, 0.75, 1.0, 1.0);
+
 /// Snapshot of the control panel's UI layout and data for the current frame.
 #[derive(Clone, Debug)]
 pub(crate) struct ControlPanelUiContext {
@@ -29,14 +49,48 @@ pub(crate) struct ControlPanelUiContext {
     pub play_mode: PlayMode,
     /// Presentable gold amount exposed by the simulation.
     pub gold: Option<GoldPresentation>,
+    /// Intensity of the red spend/reject flash applied to the gold label.
+    pub gold_flash: f32,
+    /// Bugs that may still reach the exit before the round is lost, if reported.
+    pub base_health: Option<BugHealthPresentation>,
+    /// Prestige balance and the starting bonuses bought with it.
+    pub prestige: Prestige,
+    /// In-game clock and wave stopwatch exposed by the simulation.
+    pub clock: Option<ClockPresentation>,
+    /// Best wave ever reached on the level being played, if one was recorded.
+    pub best_wave: Option<u32>,
+    /// Automatic next-wave countdown exposed by the simulation, if one is running.
+    pub wave_countdown: Option<WaveCountdownPresentation>,
+    /// Building time left before Builder mode ends automatically, if building time is limited.
+    pub builder_countdown: Option<Duration>,
     /// Presentable difficulty level exposed by the simulation.
     pub difficulty: Option<DifficultyPresentation>,
     /// Presentation data for the difficulty selection buttons.
     pub difficulty_selection: Option<DifficultySelectionPresentation>,
+    /// Contracts the player may toggle for the next wave.
+    pub wave_contracts: Vec<WaveContractPresentation>,
+    /// Species composition of the next wave, if its plan is known.
+    pub wave_composition: Option<WaveCompositionPresentation>,
     /// Most recent analytics snapshot published by the simulation, if any.
     pub analytics: Option<AnalyticsPresentation>,
+    /// Random event rolled after the last wave, if any.
+    pub between_wave_event: Option<BetweenWaveEventPresentation>,
     /// Whether the replay button should be enabled.
     pub replay_available: bool,
+    /// Whether the session runs with sandbox rules.
+    pub sandbox: bool,
+    /// Seed the next wave derives from, if the simulation exposes it.
+    pub wave_seed: Option<u64>,
+    /// Shield shared by the active wave's bugs, if the wave was launched shielded.
+    pub wave_shield: Option<BugHealthPresentation>,
+    /// Rendering preferences currently in effect.
+    pub settings: RenderSettings,
+    /// Separators used to write numbers and durations for the player's locale.
+    pub numbers: NumberFormat,
+    /// Tower kinds bound to the hotbar slots, first slot first.
+    pub hotbar: Vec<TowerKind>,
+    /// Tower kind the builder places next, if the simulation exposes one.
+    pub selected_tower_kind: Option<TowerKind>,
 }
 
 /// Captures the UI interactions emitted while drawing the control panel.
@@ -48,25 +102,741 @@ pub(crate) struct ControlPanelUiResult {
     pub start_wave: Option<WaveDifficulty>,
     /// Whether the replay button was pressed this frame.
     pub replay_wave: bool,
+    /// Reward of the early-start button pressed this frame, if any.
+    pub skip_countdown: Option<PreparationReward>,
+    /// Contract whose button was pressed this frame, if any.
+    pub toggle_contract: Option<WaveContract>,
+    /// Rendering preference whose button was pressed this frame, if any.
+    pub toggle_setting: Option<RenderToggle>,
+    /// Tower kind whose hotbar button was pressed this frame, if any.
+    pub select_tower_kind: Option<TowerKind>,
+    /// Wave seed button pressed this frame, if any.
+    pub wave_seed: Option<WaveSeedAction>,
+    /// Prestige bonus whose buy button was pressed this frame, if any.
+    pub buy_prestige_bonus: Option<PrestigeBonus>,
+}
+
+/// Wave seed buttons of the control panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WaveSeedAction {
+    /// Copies the wave seed to the clipboard.
+    Copy,
+    /// Replaces the wave seed with the one on the clipboard; sandbox sessions only.
+    Paste,
+    /// Replaces the wave seed with the next one in its re-roll sequence; sandbox sessions
+    /// only.
+    Reroll,
+}
+
+/// Layout and data for the after-action report overlay.
+#[derive(Clone, Debug)]
+pub(crate) struct WaveReportUiContext {
+    /// Top-left corner of the overlay in screen coordinates.
+    pub origin: Vec2,
+    /// Overlay dimensions in screen space.
+    pub size: Vec2,
+    /// Background colour applied to the overlay window.
+    pub background: Color,
+    /// Report describing the wave that was just resolved.
+    pub report: WaveReportPresentation,
+    /// Separators used to write numbers and durations for the player's locale.
+    pub numbers: NumberFormat,
+}
+
+/// Layout and data for the merchant offer dialog.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MerchantOfferUiContext {
+    /// Top-left corner of the dialog in screen coordinates.
+    pub origin: Vec2,
+    /// Dialog dimensions in screen space.
+    pub size: Vec2,
+    /// Background colour applied to the dialog window.
+    pub background: Color,
+    /// Discount on the next tower granted if the offer is accepted.
+    pub discount_percent: u32,
+}
+
+/// Sections of the control panel, drawn top to bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PanelSection {
+    /// Difficulty, gold, base health, prestige and between-wave events.
+    Economy,
+    /// Play mode, layout analytics and the mode toggle.
+    Build,
+    /// Clocks, wave launch controls, the next wave's composition, contracts and replay.
+    Wave,
+    /// Rendering preferences such as overlays, frame metrics and vsync.
+    Settings,
+}
+
+impl PanelSection {
+    /// Every section in drawing order.
+    pub(crate) const ALL: [Self; 4] = [Self::Economy, Self::Build, Self::Wave, Self::Settings];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Economy => "Economy",
+            Self::Build => "Build",
+            Self::Wave => "Wave",
+            Self::Settings => "Settings",
+        }
+    }
+}
+
+/// Collapsed state of the control panel sections, persisted in the profile.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PanelLayout {
+    collapsed: BTreeSet<PanelSection>,
+}
+
+impl PanelLayout {
+    /// Reports whether the section only shows its header.
+    pub(crate) fn is_collapsed(&self, section: PanelSection) -> bool {
+        self.collapsed.contains(&section)
+    }
+
+    /// Collapses an expanded section or expands a collapsed one.
+    pub(crate) fn toggle(&mut self, section: PanelSection) {
+        if !self.collapsed.remove(&section) {
+            let _ = self.collapsed.insert(section);
+        }
+    }
 }
 
 /// Renders the control panel's interactive elements for the current frame and
 /// returns the resulting interactions.
+///
+/// The panel is a scrollable window stacking every [`PanelSection`] under a header
+/// button that collapses or expands it; header clicks update `layout` in place.
 pub(crate) fn draw_control_panel_ui(
     ui: &mut Ui,
     context: ControlPanelUiContext,
+    layout: &mut PanelLayout,
 ) -> ControlPanelUiResult {
+    let skin = panel_skin(ui, context.background);
+
+    ui.push_skin(&skin);
+
+    let max_label_width = (context.size.x - 32.0).max(0.0);
+
+    let mut result = ControlPanelUiResult::default();
+    let _ = ui.window(hash!("control_panel"), context.origin, context.size, |ui| {
+        if context.sandbox {
+            label_wrapped(ui, "Sandbox: excluded from records", max_label_width);
+        }
+
+        if let Some(selected) = context.selected_tower_kind {
+            draw_hotbar(ui, &context.hotbar, selected, &mut result);
+            ui.separator();
+        }
+
+        for (index, section) in PanelSection::ALL.into_iter().enumerate() {
+            if index > 0 {
+                ui.separator();
+            }
+            let collapsed = layout.is_collapsed(section);
+            let marker = if collapsed { "[+]" } else { "[-]" };
+            if ui.button(None, format!("{marker} {}", section.title()).as_str()) {
+                layout.toggle(section);
+            }
+            if collapsed {
+                continue;
+            }
+            match section {
+                PanelSection::Economy => {
+                    draw_economy_section(ui, &skin, &context, max_label_width, &mut result);
+                }
+                PanelSection::Build => {
+                    draw_build_section(ui, &context, max_label_width, &mut result);
+                }
+                PanelSection::Wave => {
+                    draw_wave_section(ui, &context, max_label_width, &mut result);
+                }
+                PanelSection::Settings => {
+                    draw_settings_section(ui, context.settings, &mut result);
+                }
+            }
+        }
+    });
+
+    ui.pop_skin();
+    result
+}
+
+/// Lists the hotbar slots with their key, marking the kind the builder places next.
+fn draw_hotbar(
+    ui: &mut Ui,
+    hotbar: &[TowerKind],
+    selected: TowerKind,
+    result: &mut ControlPanelUiResult,
+) {
+    for (slot, kind) in hotbar.iter().copied().enumerate() {
+        let marker = if kind == selected { " ★" } else { "" };
+        let label = format!("[{}] {}{marker}", slot + 1, tower_kind_name(kind));
+        if ui.button(None, label.as_str()) {
+            result.select_tower_kind = Some(kind);
+        }
+    }
+}
+
+fn tower_kind_name(kind: TowerKind) -> &'static str {
+    match kind {
+        TowerKind::Basic => "Basic",
+        TowerKind::Barrier => "Barrier",
+        TowerKind::Repulsor => "Repulsor",
+        TowerKind::Frost => "Frost",
+    }
+}
+
+fn draw_economy_section(
+    ui: &mut Ui,
+    skin: &Skin,
+    context: &ControlPanelUiContext,
+    max_label_width: f32,
+    result: &mut ControlPanelUiResult,
+) {
+    let difficulty_text = match context.difficulty {
+        Some(level) => format!("Difficulty: {}", level.level()),
+        None => "Difficulty: –".to_string(),
+    };
+    ui.label(None, difficulty_text.as_str());
+
+    let gold_text = match context.gold {
+        Some(gold) => format!("Gold: {}", context.numbers.count(gold.amount().get())),
+        None => "Gold: –".to_string(),
+    };
+    if context.gold_flash > 0.0 {
+        let mut flash_skin = skin.clone();
+        let flash_color = gold_flash_color(context.gold_flash);
+        flash_skin.label_style = ui
+            .style_builder()
+            .text_color(flash_color)
+            .text_color_hovered(flash_color)
+            .text_color_clicked(flash_color)
+            .margin(RectOffset::new(0.0, 0.0, 4.0, 4.0))
+            .build();
+        ui.push_skin(&flash_skin);
+        ui.label(None, gold_text.as_str());
+        ui.pop_skin();
+    } else {
+        ui.label(None, gold_text.as_str());
+    }
+
+    if let Some(base) = context.base_health {
+        ui.label(
+            None,
+            format!(
+                "Base health: {}/{}",
+                context.numbers.count(u64::from(base.current)),
+                context.numbers.count(u64::from(base.maximum))
+            )
+            .as_str(),
+        );
+    }
+
+    draw_prestige(ui, context.prestige, max_label_width, result);
+
+    if let Some(event) = context.between_wave_event {
+        label_wrapped(
+            ui,
+            between_wave_event_text(event.event()).as_str(),
+            max_label_width,
+        );
+    }
+}
+
+/// Shows the prestige balance with a buy button for every starting bonus.
+fn draw_prestige(
+    ui: &mut Ui,
+    prestige: Prestige,
+    max_label_width: f32,
+    result: &mut ControlPanelUiResult,
+) {
+    ui.label(None, format!("Prestige: {}", prestige.balance()).as_str());
+    for bonus in PrestigeBonus::ALL {
+        let name = match bonus {
+            PrestigeBonus::StartingGold => "+25 starting gold",
+            PrestigeBonus::BaseHealth => "+1 base health",
+        };
+        let cost = prestige.cost(bonus);
+        let label = format!("{name} ({cost}, owned {})", prestige.level(bonus));
+        if ui.button(None, label.as_str()) && cost <= prestige.balance() {
+            result.buy_prestige_bonus = Some(bonus);
+        }
+    }
+    label_wrapped(ui, "Bonuses apply from the next game.", max_label_width);
+}
+
+fn draw_build_section(
+    ui: &mut Ui,
+    context: &ControlPanelUiContext,
+    max_label_width: f32,
+    result: &mut ControlPanelUiResult,
+) {
+    let mode_label = match context.play_mode {
+        PlayMode::Attack => "Mode: Attack",
+        PlayMode::Builder => "Mode: Builder",
+    };
+    ui.label(None, mode_label);
+    if context.play_mode == PlayMode::Builder {
+        match context.analytics.as_ref() {
+            Some(analytics) => {
+                let report = analytics.report();
+                ui.label(None, "Analytics:");
+                label_wrapped(
+                    ui,
+                    format!(
+                        "Path coverage: {}",
+                        context
+                            .numbers
+                            .basis_points(report.tower_coverage_mean_bps())
+                    )
+                    .as_str(),
+                    max_label_width,
+                );
+                label_wrapped(
+                    ui,
+                    format!(
+                        "Firing completion: {}",
+                        context
+                            .numbers
+                            .basis_points(report.firing_complete_percent_bps())
+                    )
+                    .as_str(),
+                    max_label_width,
+                );
+                label_wrapped(
+                    ui,
+                    format!(
+                        "Shortest path: {} cells",
+                        context
+                            .numbers
+                            .count(u64::from(report.shortest_path_length_cells()))
+                    )
+                    .as_str(),
+                    max_label_width,
+                );
+                label_wrapped(
+                    ui,
+                    format!(
+                        "Tower count: {}",
+                        context.numbers.count(u64::from(report.tower_count()))
+                    )
+                    .as_str(),
+                    max_label_width,
+                );
+                label_wrapped(
+                    ui,
+                    format!(
+                        "Total DPS: {}",
+                        context.numbers.count(u64::from(report.total_tower_dps()))
+                    )
+                    .as_str(),
+                    max_label_width,
+                );
+            }
+            None => {
+                label_wrapped(ui, "Analytics: waiting for first report…", max_label_width);
+            }
+        }
+    }
+
+    label_wrapped(ui, "Use the button below to switch modes.", max_label_width);
+
+    if ui.button(None, "Toggle Mode") {
+        result.mode_toggle = true;
+    }
+}
+
+fn draw_wave_section(
+    ui: &mut Ui,
+    context: &ControlPanelUiContext,
+    max_label_width: f32,
+    result: &mut ControlPanelUiResult,
+) {
+    if let Some(clock) = context.clock {
+        ui.label(
+            None,
+            format!("Clock: {}", context.numbers.clock(clock.elapsed())).as_str(),
+        );
+        let wave_text = match clock.wave_elapsed() {
+            Some(elapsed) => format!("Wave time: {}", context.numbers.clock(elapsed)),
+            None => "Wave time: –".to_string(),
+        };
+        ui.label(None, wave_text.as_str());
+    }
+    if let Some(best) = context.best_wave {
+        ui.label(None, format!("Best: Wave {best}").as_str());
+    }
+    if let Some(shield) = context.wave_shield {
+        draw_wave_shield(ui, shield, context.numbers, max_label_width);
+    }
+
+    if let Some(remaining) = context.builder_countdown {
+        let text = if remaining.is_zero() {
+            "Building time is up".to_string()
+        } else {
+            format!("Building time left {}", context.numbers.duration(remaining))
+        };
+        label_wrapped(ui, text.as_str(), max_label_width);
+    }
+
+    if let Some(countdown) = context.wave_countdown {
+        label_wrapped(
+            ui,
+            format!(
+                "Next wave in {}",
+                context.numbers.duration(countdown.remaining())
+            )
+            .as_str(),
+            max_label_width,
+        );
+        let skip_label = format!(
+            "Start now (+{} gold)",
+            context.numbers.count(countdown.early_start_bonus().get())
+        );
+        if ui.button(None, skip_label.as_str()) {
+            result.skip_countdown = Some(PreparationReward::Gold);
+        }
+        let haste_label = format!(
+            "Start now (2x reload for {:.0}s)",
+            countdown.remaining().as_secs_f32().floor()
+        );
+        if ui.button(None, haste_label.as_str()) {
+            result.skip_countdown = Some(PreparationReward::Haste);
+        }
+    }
+
+    label_wrapped(ui, "Select the next wave difficulty.", max_label_width);
+
+    let mut normal_label = "Normal".to_string();
+    let mut hard_label = "Hard".to_string();
+    let mut normal_preview = None;
+    let mut hard_preview = None;
+
+    if let Some(selection) = context.difficulty_selection {
+        let normal = selection.normal();
+        let hard = selection.hard();
+
+        if normal.selected() {
+            normal_label.push_str(" ★");
+        }
+        if hard.selected() {
+            hard_label.push_str(" ★");
+        }
+
+        normal_preview = Some(format!(
+            "Normal rewards: x{} gold (Difficulty {})",
+            normal.reward_multiplier(),
+            normal.effective_level()
+        ));
+        hard_preview = Some(format!(
+            "Hard rewards: x{} gold (Difficulty {})",
+            hard.reward_multiplier(),
+            hard.effective_level()
+        ));
+    }
+
+    if ui.button(None, normal_label.as_str()) {
+        result.start_wave = Some(WaveDifficulty::Normal);
+    }
+    if ui.button(None, hard_label.as_str()) {
+        result.start_wave = Some(WaveDifficulty::Hard);
+    }
+
+    if let Some(text) = normal_preview {
+        label_wrapped(ui, text.as_str(), max_label_width);
+    }
+    if let Some(text) = hard_preview {
+        label_wrapped(ui, text.as_str(), max_label_width);
+        label_wrapped(
+            ui,
+            "Hard victory grants +1 permanent difficulty.",
+            max_label_width,
+        );
+    }
+
+    if let Some(composition) = &context.wave_composition {
+        draw_wave_composition(ui, composition, context.numbers, max_label_width);
+    }
+
+    if !context.wave_contracts.is_empty() {
+        label_wrapped(ui, "Contracts for the next wave:", max_label_width);
+    }
+    for presentation in &context.wave_contracts {
+        if ui.button(None, contract_label(*presentation).as_str()) {
+            result.toggle_contract = Some(presentation.contract());
+        }
+    }
+
+    if let Some(seed) = context.wave_seed {
+        ui.label(None, format!("Wave seed: {seed}").as_str());
+        if ui.button(None, "Copy seed") {
+            result.wave_seed = Some(WaveSeedAction::Copy);
+        }
+        if context.sandbox {
+            if ui.button(None, "Paste seed") {
+                result.wave_seed = Some(WaveSeedAction::Paste);
+            }
+            if ui.button(None, "Re-roll seed") {
+                result.wave_seed = Some(WaveSeedAction::Reroll);
+            }
+        }
+    }
+
+    let replay_label = if context.replay_available {
+        "Replay"
+    } else {
+        "Replay (unavailable)"
+    };
+    if ui.button(None, replay_label) && context.replay_available {
+        result.replay_wave = true;
+    }
+    if !context.replay_available {
+        label_wrapped(
+            ui,
+            "Replay is available after launching at least one wave.",
+            max_label_width,
+        );
+    }
+}
+
+/// Draws the shared wave shield as a bar that empties as towers wear it down.
+fn draw_wave_shield(
+    ui: &mut Ui,
+    shield: BugHealthPresentation,
+    numbers: NumberFormat,
+    max_label_width: f32,
+) {
+    if shield.current == 0 {
+        ui.label(None, "Shield broken");
+        return;
+    }
+    ui.label(
+        None,
+        format!(
+            "Shield: {}/{}",
+            numbers.count(u64::from(shield.current)),
+            numbers.count(u64::from(shield.maximum))
+        )
+        .as_str(),
+    );
+    let mut canvas = ui.canvas();
+    let bar = canvas.request_space(Vec2::new(max_label_width, SHIELD_BAR_HEIGHT));
+    canvas.rect(
+        Rect::new(bar.x, bar.y, max_label_width, SHIELD_BAR_HEIGHT),
+        WHITE,
+        BLACK,
+    );
+    let ratio = (shield.current as f32 / shield.maximum.max(1) as f32).clamp(0.0, 1.0);
+    canvas.rect(
+        Rect::new(bar.x, bar.y, max_label_width * ratio, SHIELD_BAR_HEIGHT),
+        SHIELD_BAR_COLOR,
+        SHIELD_BAR_COLOR,
+    );
+}
+
+/// Lists the next wave's species, most threatening first, each behind a swatch of its tint.
+fn draw_wave_composition(
+    ui: &mut Ui,
+    composition: &WaveCompositionPresentation,
+    numbers: NumberFormat,
+    max_label_width: f32,
+) {
+    label_wrapped(
+        ui,
+        format!(
+            "Next {:?} wave: {} bugs",
+            composition.difficulty(),
+            numbers.count(u64::from(composition.bug_count()))
+        )
+        .as_str(),
+        max_label_width,
+    );
+    for (rank, species) in composition.species().iter().enumerate() {
+        let mut canvas = ui.canvas();
+        let icon = canvas.request_space(Vec2::splat(SPECIES_ICON_SIZE));
+        canvas.rect(
+            Rect::new(icon.x, icon.y, SPECIES_ICON_SIZE, SPECIES_ICON_SIZE),
+            WHITE,
+            to_macroquad_color(species.tint()),
+        );
+        ui.same_line(0.0);
+        ui.label(None, species_preview_label(rank, species, numbers).as_str());
+    }
+}
+
+fn species_preview_label(
+    rank: usize,
+    species: &SpeciesPreviewPresentation,
+    numbers: NumberFormat,
+) -> String {
+    let boss = match species.rank() {
+        BugRank::Regular => "",
+        BugRank::Boss => " boss",
+    };
+    format!(
+        "#{} x{} {}hp{boss} - {:.0}% threat",
+        rank + 1,
+        numbers.count(u64::from(species.count())),
+        numbers.count(u64::from(species.health())),
+        species.threat_share() * 100.0
+    )
+}
+
+fn draw_settings_section(ui: &mut Ui, settings: RenderSettings, result: &mut ControlPanelUiResult) {
+    let toggles = [
+        (
+            RenderToggle::TargetLines,
+            "Target lines (T)",
+            settings.target_lines,
+        ),
+        (
+            RenderToggle::BugHealthBars,
+            "Health bars (H)",
+            settings.bug_health_bars,
+        ),
+        (
+            RenderToggle::TowerCooldowns,
+            "Cooldowns (C)",
+            settings.tower_cooldowns,
+        ),
+        (
+            RenderToggle::DangerHeatmap,
+            "Danger heatmap (M)",
+            settings.danger_heatmap,
+        ),
+        (RenderToggle::ShowFps, "Print FPS", settings.show_fps),
+    ];
+    for (toggle, name, enabled) in toggles {
+        let state = if enabled { "on" } else { "off" };
+        if ui.button(None, format!("{name}: {state}").as_str()) {
+            result.toggle_setting = Some(toggle);
+        }
+    }
+
+    let vsync = match settings.vsync {
+        VsyncPreference::PlatformDefault => "default",
+        VsyncPreference::On => "on",
+        VsyncPreference::Off => "off",
+    };
+    if ui.button(None, format!("Vsync: {vsync}").as_str()) {
+        result.toggle_setting = Some(RenderToggle::Vsync);
+    }
+    ui.label(None, "Vsync changes apply on the next launch.");
+
+    let motion = match settings.motion {
+        MotionPreference::System => "system",
+        MotionPreference::Reduced => "reduced",
+        MotionPreference::Full => "full",
+    };
+    if ui.button(None, format!("Motion: {motion}").as_str()) {
+        result.toggle_setting = Some(RenderToggle::Motion);
+    }
+}
+
+/// Renders the after-action report overlay and returns whether the player pressed continue.
+pub(crate) fn draw_wave_report_ui(ui: &mut Ui, context: WaveReportUiContext) -> bool {
+    let skin = panel_skin(ui, context.background);
+    ui.push_skin(&skin);
+
+    let max_label_width = (context.size.x - 32.0).max(0.0);
+    let mut dismissed = false;
+    let _ = ui.window(hash!("wave_report"), context.origin, context.size, |ui| {
+        for line in wave_report_lines(&context.report, context.numbers) {
+            label_wrapped(ui, line.as_str(), max_label_width);
+        }
+        if ui.button(None, "Continue") {
+            dismissed = true;
+        }
+    });
+
+    ui.pop_skin();
+    dismissed
+}
+
+/// Renders the merchant offer dialog and returns the player's answer, if one was given.
+pub(crate) fn draw_merchant_offer_ui(ui: &mut Ui, context: MerchantOfferUiContext) -> Option<bool> {
+    let skin = panel_skin(ui, context.background);
+    ui.push_skin(&skin);
+
+    let max_label_width = (context.size.x - 32.0).max(0.0);
+    let mut answer = None;
+    let _ = ui.window(
+        hash!("merchant_offer"),
+        context.origin,
+        context.size,
+        |ui| {
+            label_wrapped(
+                ui,
+                format!(
+                    "A merchant offers {}% off your next tower.",
+                    context.discount_percent
+                )
+                .as_str(),
+                max_label_width,
+            );
+            if ui.button(None, "Accept") {
+                answer = Some(true);
+            }
+            if ui.button(None, "Decline") {
+                answer = Some(false);
+            }
+        },
+    );
+
+    ui.pop_skin();
+    answer
+}
+
+fn contract_label(presentation: WaveContractPresentation) -> String {
+    let contract = presentation.contract();
+    let (name, effect) = match contract {
+        WaveContract::Reinforced => ("Reinforced", None),
+        WaveContract::Onslaught => ("Onslaught", None),
+        WaveContract::Shielded => ("Shielded", Some("shared shield")),
+    };
+    let mark = if presentation.accepted() {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    let effect = effect.map_or_else(
+        || format!("+{} difficulty", contract.difficulty_bonus()),
+        str::to_string,
+    );
+    format!(
+        "{mark} {name}: {effect}, +{}% gold",
+        contract.reward_bonus_percent()
+    )
+}
+
+fn between_wave_event_text(event: BetweenWaveEvent) -> String {
+    match event {
+        BetweenWaveEvent::MeteorStrike { tower } => {
+            format!("Meteor strike! Tower #{} was destroyed.", tower.get())
+        }
+        BetweenWaveEvent::MerchantOffer { discount_percent } => {
+            format!("Merchant: {discount_percent}% off your next tower.")
+        }
+        BetweenWaveEvent::GoldRush => "Gold rush! The next wave pays double.".to_string(),
+    }
+}
+
+fn panel_skin(ui: &mut Ui, background: Color) -> Skin {
     let mut skin = ui.default_skin();
     skin.margin = 0.0;
 
     let window_style = ui
         .style_builder()
-        .color(context.background)
-        .color_hovered(context.background)
-        .color_clicked(context.background)
-        .color_selected(context.background)
-        .color_selected_hovered(context.background)
-        .color_inactive(context.background)
+        .color(background)
+        .color_hovered(background)
+        .color_clicked(background)
+        .color_selected(background)
+        .color_selected_hovered(background)
+        .color_inactive(background)
         .text_color(WHITE)
         .text_color_hovered(WHITE)
         .text_color_clicked(WHITE)
@@ -97,154 +867,49 @@ pub(crate) fn draw_control_panel_ui(
         .margin(RectOffset::new(0.0, 0.0, 8.0, 8.0))
         .build();
     skin.button_style = button_style;
+    skin
+}
 
-    ui.push_skin(&skin);
-
-    let selection = context.difficulty_selection;
-
-    let max_label_width = (context.size.x - 32.0).max(0.0);
-
-    let mut result = ControlPanelUiResult::default();
-    let _ = ui.window(hash!("control_panel"), context.origin, context.size, |ui| {
-        let difficulty_text = match context.difficulty {
-            Some(level) => format!("Difficulty: {}", level.level()),
-            None => "Difficulty: –".to_string(),
-        };
-        ui.label(None, difficulty_text.as_str());
-
-        let gold_text = match context.gold {
-            Some(gold) => format!("Gold: {}", gold.amount().get()),
-            None => "Gold: –".to_string(),
-        };
-        ui.label(None, gold_text.as_str());
-
-        let mode_label = match context.play_mode {
-            PlayMode::Attack => "Mode: Attack",
-            PlayMode::Builder => "Mode: Builder",
-        };
-        ui.label(None, mode_label);
-        if context.play_mode == PlayMode::Builder {
-            match context.analytics {
-                Some(analytics) => {
-                    let report = analytics.report();
-                    ui.label(None, "Analytics:");
-                    label_wrapped(
-                        ui,
-                        format!(
-                            "Path coverage: {}",
-                            format_basis_points(report.tower_coverage_mean_bps())
-                        )
-                        .as_str(),
-                        max_label_width,
-                    );
-                    label_wrapped(
-                        ui,
-                        format!(
-                            "Firing completion: {}",
-                            format_basis_points(report.firing_complete_percent_bps())
-                        )
-                        .as_str(),
-                        max_label_width,
-                    );
-                    label_wrapped(
-                        ui,
-                        format!(
-                            "Shortest path: {} cells",
-                            report.shortest_path_length_cells()
-                        )
-                        .as_str(),
-                        max_label_width,
-                    );
-                    label_wrapped(
-                        ui,
-                        format!("Tower count: {}", report.tower_count()).as_str(),
-                        max_label_width,
-                    );
-                    label_wrapped(
-                        ui,
-                        format!("Total DPS: {}", report.total_tower_dps()).as_str(),
-                        max_label_width,
-                    );
-                }
-                None => {
-                    label_wrapped(ui, "Analytics: waiting for first report…", max_label_width);
-                }
-            }
-        }
-        label_wrapped(ui, "Select the next wave difficulty.", max_label_width);
-
-        let mut normal_label = "Normal".to_string();
-        let mut hard_label = "Hard".to_string();
-        let mut normal_preview = None;
-        let mut hard_preview = None;
-
-        if let Some(selection) = selection {
-            let normal = selection.normal();
-            let hard = selection.hard();
-
-            if normal.selected() {
-                normal_label.push_str(" ★");
-            }
-            if hard.selected() {
-                hard_label.push_str(" ★");
-            }
-
-            normal_preview = Some(format!(
-                "Normal rewards: x{} gold (Difficulty {})",
-                normal.reward_multiplier(),
-                normal.effective_level()
-            ));
-            hard_preview = Some(format!(
-                "Hard rewards: x{} gold (Difficulty {})",
-                hard.reward_multiplier(),
-                hard.effective_level()
-            ));
-        }
-
-        if ui.button(None, normal_label.as_str()) {
-            result.start_wave = Some(WaveDifficulty::Normal);
-        }
-        if ui.button(None, hard_label.as_str()) {
-            result.start_wave = Some(WaveDifficulty::Hard);
-        }
-
-        if let Some(text) = normal_preview {
-            label_wrapped(ui, text.as_str(), max_label_width);
-        }
-        if let Some(text) = hard_preview {
-            label_wrapped(ui, text.as_str(), max_label_width);
-            label_wrapped(
-                ui,
-                "Hard victory grants +1 permanent difficulty.",
-                max_label_width,
-            );
-        }
-
-        let replay_label = if context.replay_available {
-            "Replay"
-        } else {
-            "Replay (unavailable)"
-        };
-        if ui.button(None, replay_label) && context.replay_available {
-            result.replay_wave = true;
-        }
-        if !context.replay_available {
-            label_wrapped(
-                ui,
-                "Replay is available after launching at least one wave.",
-                max_label_width,
-            );
-        }
-
-        label_wrapped(ui, "Use the button below to switch modes.", max_label_width);
-
-        if ui.button(None, "Toggle Mode") {
-            result.mode_toggle = true;
-        }
+fn wave_report_lines(presentation: &WaveReportPresentation, numbers: NumberFormat) -> Vec<String> {
+    let report = presentation.report();
+    let outcome = match report.outcome() {
+        RoundOutcome::Win => "cleared",
+        RoundOutcome::Loss => "lost",
+    };
+    let mut lines = vec![
+        format!("Wave {} {outcome}", report.wave().get() + 1),
+        format!("Time taken: {}", numbers.duration(report.duration())),
+        format!("Leaks: {}", numbers.count(u64::from(report.leaks()))),
+        format!("Gold earned: {}", numbers.count(report.gold_earned().get())),
+    ];
+    let mvp = report.mvp().and_then(|mvp| {
+        report
+            .tower_damage()
+            .iter()
+            .find(|(tower, _)| *tower == mvp)
+            .copied()
     });
-
-    ui.pop_skin();
-    result
+    lines.push(match mvp {
+        Some((tower, damage)) => format!(
+            "MVP: Tower #{} ({} damage)",
+            tower.get(),
+            numbers.count(u64::from(damage))
+        ),
+        None => "MVP: –".to_string(),
+    });
+    if !report.tower_damage().is_empty() {
+        lines.push("Damage by tower:".to_string());
+        let mut damage = report.tower_damage().to_vec();
+        damage.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        lines.extend(damage.into_iter().map(|(tower, damage)| {
+            format!(
+                "  Tower #{}: {}",
+                tower.get(),
+                numbers.count(u64::from(damage))
+            )
+        }));
+    }
+    lines
 }
 
 fn label_wrapped(ui: &mut Ui, text: &str, max_width: f32) {
@@ -253,10 +918,9 @@ fn label_wrapped(ui: &mut Ui, text: &str, max_width: f32) {
     }
 }
 
-fn format_basis_points(value: u32) -> String {
-    let whole = value / 100;
-    let fractional = value % 100;
-    format!("{whole}.{fractional:02}%")
+fn gold_flash_color(intensity: f32) -> Color {
+    let intensity = intensity.clamp(0.0, 1.0);
+    Color::new(1.0, 1.0 - 0.7 * intensity, 1.0 - 0.7 * intensity, 1.0)
 }
 
 fn wrap_text(ui: &mut Ui, text: &str, max_width: f32) -> Vec<String> {
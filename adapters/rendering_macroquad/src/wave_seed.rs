@@ -0,0 +1,49 @@
+//! Wave seeds shown in the control panel and shared through the system clipboard.
+//!
+//! Every wave plan derives from the wave seed, so a seed copied by one player and pasted by
+//! another in a sandbox session replays the same waves. Re-rolling steps the seed through
+//! SplitMix64, which keeps a sequence of re-rolls reproducible from the seed it started at.
+
+/// Seed following `seed` when the player re-rolls the waves.
+pub(crate) fn rerolled(seed: u64) -> u64 {
+    let mut value = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// Parses a seed the player copied, ignoring surrounding whitespace.
+pub(crate) fn parse(text: &str) -> Option<u64> {
+    text.trim().parse().ok()
+}
+
+/// Places `seed` on the system clipboard.
+#[allow(unsafe_code)]
+pub(crate) fn copy_to_clipboard(seed: u64) {
+    // SAFETY: called from the render loop between UI passes, so no other borrow of the
+    // internal context is alive; Macroquad's own UI clipboard accesses it the same way.
+    let context = unsafe { macroquad::window::get_internal_gl() };
+    context.quad_context.clipboard_set(&seed.to_string());
+}
+
+/// Reads a seed from the system clipboard, if it holds one.
+#[allow(unsafe_code)]
+pub(crate) fn paste_from_clipboard() -> Option<u64> {
+    // SAFETY: see `copy_to_clipboard`.
+    let context = unsafe { macroquad::window::get_internal_gl() };
+    parse(&context.quad_context.clipboard_get()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_seeds_parse_back_and_rerolls_are_reproducible() {
+        let seed = rerolled(42);
+        assert_ne!(seed, 42);
+        assert_eq!(seed, rerolled(42));
+        assert_eq!(parse(&format!(" {seed}\n")), Some(seed));
+        assert_eq!(parse("not a seed"), None);
+    }
+}
@@ -16,7 +16,7 @@
 //! react to deterministically. Systems consume event streams, query immutable
 //! snapshots, and respond exclusively with new command batches.
 
-use std::{borrow::Cow, num::NonZeroU32, time::Duration};
+use std::{borrow::Cow, fmt, num::NonZeroU32, sync::Arc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
@@ -140,6 +140,120 @@ pub const CONGESTION_WEIGHT: u32 = 3;
 /// determinism.
 pub const DETOUR_RADIUS: u32 = 6;
 
+/// Extra navigation cost per covering tower charged to flankers for each covered cell.
+///
+/// A cell inside the range of two towers costs a flanker `1 + 2 * 4` steps, so
+/// flankers accept detours of up to four cells per tower they avoid. The
+/// weighted field is rebuilt whenever towers change, keeping replays
+/// deterministic.
+pub const FLANKER_COVERAGE_PENALTY: u16 = 4;
+
+/// Route preference a species applies when descending the navigation field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PathingPersonality {
+    /// Takes the shortest route to the exit.
+    #[default]
+    Direct,
+    /// Prefers longer routes that avoid cells inside tower coverage.
+    Flanker,
+}
+
+/// Special ability shared by every bug of a generated species.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BugArchetype {
+    /// No special ability.
+    #[default]
+    Basic,
+    /// Takes reduced damage from every hit.
+    Armored,
+    /// Slowly heals back towards its starting health.
+    Regenerating,
+    /// Splits into weaker bugs when killed.
+    Splitter,
+}
+
+impl BugArchetype {
+    /// Every archetype, in declaration order.
+    pub const ALL: [Self; 4] = [
+        Self::Basic,
+        Self::Armored,
+        Self::Regenerating,
+        Self::Splitter,
+    ];
+}
+
+/// Standing of a bug within its wave.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BugRank {
+    /// Ordinary bug without scripted behaviour.
+    #[default]
+    Regular,
+    /// Boss that follows the scripted [`BossPhase`] sequence.
+    Boss,
+}
+
+/// Scripted phase of a boss bug, advanced as its health drops.
+///
+/// Entering [`BossPhase::Summoning`] summons adds and entering [`BossPhase::Enraged`] doubles
+/// the boss's speed; both transitions grant a short invulnerability window.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum BossPhase {
+    /// Health above two thirds.
+    #[default]
+    Opening,
+    /// Health at or below 66%.
+    Summoning,
+    /// Health at or below 33%.
+    Enraged,
+}
+
+impl BossPhase {
+    /// Phase a boss with `health` out of `max_health` remaining should be in.
+    ///
+    /// ```
+    /// use maze_defence_core::{BossPhase, Health};
+    ///
+    /// let max = Health::new(100);
+    /// assert_eq!(BossPhase::for_health(Health::new(67), max), BossPhase::Opening);
+    /// assert_eq!(BossPhase::for_health(Health::new(66), max), BossPhase::Summoning);
+    /// assert_eq!(BossPhase::for_health(Health::new(33), max), BossPhase::Enraged);
+    /// ```
+    #[must_use]
+    pub fn for_health(health: Health, max_health: Health) -> Self {
+        let remaining = u64::from(health.get()) * 100;
+        let max = u64::from(max_health.get());
+        if remaining <= max * 33 {
+            Self::Enraged
+        } else if remaining <= max * 66 {
+            Self::Summoning
+        } else {
+            Self::Opening
+        }
+    }
+
+    /// Phase following this one, if any.
+    #[must_use]
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            Self::Opening => Some(Self::Summoning),
+            Self::Summoning => Some(Self::Enraged),
+            Self::Enraged => None,
+        }
+    }
+}
+
+/// Reward granted for the preparation time left when a wave is launched early.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PreparationReward {
+    /// Bonus gold for every whole second left on the countdown.
+    #[default]
+    Gold,
+    /// Tower cooldowns recover twice as fast for as long as was left on the countdown.
+    Haste,
+}
+
 /// Describes the active gameplay mode for the simulation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PlayMode {
@@ -150,7 +264,7 @@ pub enum PlayMode {
 }
 
 /// Difficulty selections available when launching a wave.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WaveDifficulty {
     /// Launch the wave at the current base difficulty level with standard rewards.
     Normal,
@@ -158,6 +272,11 @@ pub enum WaveDifficulty {
     Hard,
 }
 
+impl WaveDifficulty {
+    /// Every difficulty selection, in the order the launch controls list them.
+    pub const ALL: [Self; 2] = [Self::Normal, Self::Hard];
+}
+
 /// Describes the wave difficulty currently awaiting launch.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PendingWaveDifficulty {
@@ -188,6 +307,85 @@ impl SpeciesId {
     }
 }
 
+/// Unique identifier allocated by the world to a squad spawned as a formation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FormationId(u32);
+
+impl FormationId {
+    /// Creates a new formation identifier from the provided numeric value.
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the numeric representation of the formation identifier.
+    #[must_use]
+    pub const fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Arrangement of a squad that enters the maze together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FormationShape {
+    /// Side by side along the spawner rim.
+    Line,
+    /// Leader pushed into the maze with its wingmen trailing back to the rim.
+    Wedge,
+    /// Compact block filled rank by rank from the rim inwards.
+    Blob,
+}
+
+impl FormationShape {
+    /// Every formation shape in cycling order.
+    pub const ALL: [Self; 3] = [Self::Line, Self::Wedge, Self::Blob];
+
+    /// Slots of a squad of `size` bugs as `(lateral, depth)` offsets from the anchor.
+    ///
+    /// Lateral offsets run along the spawner rim and depth counts cells into the maze, so
+    /// depth zero lies on the rim itself. Slots are listed leader first.
+    ///
+    /// ```
+    /// use maze_defence_core::FormationShape;
+    ///
+    /// assert_eq!(FormationShape::Line.slots(3), vec![(0, 0), (1, 0), (-1, 0)]);
+    /// assert_eq!(FormationShape::Wedge.slots(3), vec![(0, 1), (1, 0), (-1, 0)]);
+    /// assert_eq!(FormationShape::Blob.slots(4), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    /// ```
+    #[must_use]
+    pub fn slots(self, size: u32) -> Vec<(i32, u32)> {
+        // Lateral slot `index` alternates around the anchor: 0, 1, -1, 2, -2, ...
+        let lateral = |index: u32| {
+            let distance = i32::try_from(index.div_ceil(2)).unwrap_or(i32::MAX);
+            if index % 2 == 1 {
+                distance
+            } else {
+                -distance
+            }
+        };
+        match self {
+            Self::Line => (0..size).map(|index| (lateral(index), 0)).collect(),
+            Self::Wedge => {
+                let tip_depth = size.saturating_sub(1).div_ceil(2);
+                (0..size)
+                    .map(|index| {
+                        let rank = index.div_ceil(2);
+                        (lateral(index), tip_depth - rank)
+                    })
+                    .collect()
+            }
+            Self::Blob => {
+                let width = (1..=size)
+                    .find(|width| width.saturating_mul(*width) >= size)
+                    .unwrap_or(1);
+                (0..size)
+                    .map(|index| (lateral(index % width), index / width))
+                    .collect()
+            }
+        }
+    }
+}
+
 /// Unique identifier assigned to a spawn patch descriptor.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct SpawnPatchId(u32);
@@ -225,22 +423,42 @@ impl SpeciesTableVersion {
 }
 
 /// Integer pressure value sampled for a wave.
+///
+/// Pressure is backed by a `u64` so the difficulty ramp of long endless runs cannot wrap;
+/// narrow it with [`Pressure::to_u32_saturating`] where a 32-bit value is required.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Pressure(u32);
+pub struct Pressure(u64);
 
 impl Pressure {
     /// Creates a new pressure value expressed in integer pressure units.
     #[must_use]
-    pub const fn new(value: u32) -> Self {
+    pub const fn new(value: u64) -> Self {
         Self(value)
     }
 
     /// Returns the stored pressure value.
     #[must_use]
-    pub const fn get(&self) -> u32 {
+    pub const fn get(&self) -> u64 {
         self.0
     }
 
+    /// Returns the stored pressure value clamped into the `u32` range.
+    ///
+    /// ```
+    /// use maze_defence_core::Pressure;
+    ///
+    /// assert_eq!(Pressure::new(35).to_u32_saturating(), 35);
+    /// assert_eq!(Pressure::new(u64::MAX).to_u32_saturating(), u32::MAX);
+    /// ```
+    #[must_use]
+    pub const fn to_u32_saturating(&self) -> u32 {
+        if self.0 > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            self.0 as u32
+        }
+    }
+
     /// Returns `true` when the pressure is zero.
     #[must_use]
     pub const fn is_zero(&self) -> bool {
@@ -250,7 +468,7 @@ impl Pressure {
     /// Multiplies the pressure by the provided factor using saturating arithmetic.
     #[must_use]
     pub const fn saturating_mul(self, factor: u32) -> Self {
-        Self(self.0.saturating_mul(factor))
+        Self(self.0.saturating_mul(factor as u64))
     }
 }
 
@@ -270,6 +488,24 @@ impl PressureWeight {
     pub const fn get(&self) -> NonZeroU32 {
         self.0
     }
+
+    /// Kill bounty paid for a species of this weight: one gold per whole unit of pressure,
+    /// and never less than one gold.
+    ///
+    /// ```
+    /// use maze_defence_core::{Gold, PressureWeight};
+    /// use std::num::NonZeroU32;
+    ///
+    /// let swarm = PressureWeight::new(NonZeroU32::new(400).unwrap());
+    /// let tank = PressureWeight::new(NonZeroU32::new(4_200).unwrap());
+    /// assert_eq!(swarm.bounty(), Gold::new(1));
+    /// assert_eq!(tank.bounty(), Gold::new(4));
+    /// ```
+    #[must_use]
+    pub const fn bounty(&self) -> Gold {
+        let units = self.0.get() / PRESSURE_FIXED_POINT_SCALE;
+        Gold::new(if units == 0 { 1 } else { units as u64 })
+    }
 }
 
 /// Inclusive cadence window (milliseconds between bug spawns) for a species.
@@ -354,6 +590,23 @@ impl BurstSchedulingConfig {
     pub const fn burst_count_max(&self) -> NonZeroU32 {
         self.burst_count_max
     }
+
+    /// Number of bursts a species spawning `spawns` units is split into.
+    ///
+    /// Each burst targets the nominal size, capped at the configured maximum burst count;
+    /// any non-empty species emits at least one burst.
+    #[must_use]
+    pub const fn burst_count(&self, spawns: u32) -> u32 {
+        if spawns == 0 {
+            return 0;
+        }
+        let bursts = spawns.div_ceil(self.nominal_burst_size.get());
+        if bursts > self.burst_count_max.get() {
+            self.burst_count_max.get()
+        } else {
+            bursts
+        }
+    }
 }
 
 /// Immutable configuration describing pressure sampling knobs shared across waves.
@@ -434,12 +687,42 @@ impl PressureCurve {
     }
 }
 
+/// Slowest speed multiplier a spawned bug moves at; slower requests are clamped.
+pub const MIN_BUG_SPEED_MULTIPLIER: f32 = 0.25;
+
+/// Fastest speed multiplier a spawned bug moves at; faster requests are clamped.
+pub const MAX_BUG_SPEED_MULTIPLIER: f32 = 4.0;
+
+/// Shortest step cadence, one 60 Hz frame, that a speed multiplier may produce.
+///
+/// Prototypes whose base cadence is already shorter keep it: the floor only limits how
+/// far a multiplier can accelerate a bug.
+pub const MIN_BUG_STEP_MS: u32 = 16;
+
+/// Most navigation cells a tile edge may be subdivided into.
+pub const MAX_CELLS_PER_TILE: u32 = 64;
+
+/// Most navigation cells, borders included, a configured grid may contain.
+pub const MAX_GRID_CELLS: u64 = 1 << 22;
+
 /// Prototype describing the presentation and cadence resolved for a species.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpeciesPrototype {
     color: BugColor,
     health: Health,
     step_ms: NonZeroU32,
+    #[serde(default)]
+    pathing: PathingPersonality,
+    #[serde(default = "default_bounty")]
+    bounty: Gold,
+    #[serde(default)]
+    rank: BugRank,
+    #[serde(default)]
+    dodge: DodgeChance,
+}
+
+fn default_bounty() -> Gold {
+    Gold::new(1)
 }
 
 impl SpeciesPrototype {
@@ -450,9 +733,41 @@ impl SpeciesPrototype {
             color,
             health,
             step_ms,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
         }
     }
 
+    /// Returns the prototype whose bugs evade projectiles with the provided chance.
+    #[must_use]
+    pub const fn with_dodge(mut self, dodge: DodgeChance) -> Self {
+        self.dodge = dodge;
+        self
+    }
+
+    /// Returns the prototype whose bugs spawn with the provided rank.
+    #[must_use]
+    pub const fn with_rank(mut self, rank: BugRank) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Returns the prototype paying the provided bounty per kill.
+    #[must_use]
+    pub const fn with_bounty(mut self, bounty: Gold) -> Self {
+        self.bounty = bounty;
+        self
+    }
+
+    /// Returns the prototype with the provided pathing personality.
+    #[must_use]
+    pub const fn with_pathing(mut self, pathing: PathingPersonality) -> Self {
+        self.pathing = pathing;
+        self
+    }
+
     /// Returns the colour assigned to spawned bugs of this species.
     #[must_use]
     pub const fn color(&self) -> BugColor {
@@ -470,6 +785,76 @@ impl SpeciesPrototype {
     pub const fn step_ms(&self) -> NonZeroU32 {
         self.step_ms
     }
+
+    /// Returns the cadence of a bug of this species spawned with `speed_mult`.
+    ///
+    /// The multiplier is clamped to [`MIN_BUG_SPEED_MULTIPLIER`]..=[`MAX_BUG_SPEED_MULTIPLIER`]
+    /// and the result to [`MIN_BUG_STEP_MS`]; non-finite or non-positive multipliers keep
+    /// the prototype cadence.
+    #[must_use]
+    pub fn step_ms_at(&self, speed_mult: f32) -> NonZeroU32 {
+        let multiplier = if speed_mult.is_finite() && speed_mult > 0.0 {
+            speed_mult.clamp(MIN_BUG_SPEED_MULTIPLIER, MAX_BUG_SPEED_MULTIPLIER)
+        } else {
+            1.0
+        };
+        let base = self.step_ms.get();
+        let scaled = (base as f32 / multiplier).round().min(u32::MAX as f32) as u32;
+        let floor = MIN_BUG_STEP_MS.min(base);
+        NonZeroU32::new(scaled.max(floor).max(1)).expect("clamped cadence is non-zero")
+    }
+
+    /// Returns the route preference assigned to spawned bugs of this species.
+    #[must_use]
+    pub const fn pathing(&self) -> PathingPersonality {
+        self.pathing
+    }
+
+    /// Returns the gold paid, before wave reward multipliers, for killing a bug of this
+    /// species.
+    #[must_use]
+    pub const fn bounty(&self) -> Gold {
+        self.bounty
+    }
+
+    /// Returns the rank bugs of this species spawn with.
+    #[must_use]
+    pub const fn rank(&self) -> BugRank {
+        self.rank
+    }
+
+    /// Returns the chance that bugs of this species evade a projectile.
+    #[must_use]
+    pub const fn dodge(&self) -> DodgeChance {
+        self.dodge
+    }
+}
+
+/// Chance, in thousandths, that a bug evades a projectile while an [`AccuracyModel`] is
+/// configured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DodgeChance(u16);
+
+impl DodgeChance {
+    /// Bugs that never dodge.
+    pub const NONE: Self = Self(0);
+
+    /// Creates a dodge chance of `per_mille` thousandths, clamped to a certain dodge.
+    #[must_use]
+    pub const fn new(per_mille: u16) -> Self {
+        if per_mille > 1_000 {
+            Self(1_000)
+        } else {
+            Self(per_mille)
+        }
+    }
+
+    /// Returns the chance in thousandths.
+    #[must_use]
+    pub const fn per_mille(&self) -> u16 {
+        self.0
+    }
 }
 
 /// Dirichlet concentration weight applied to a species during pressure partitioning.
@@ -506,6 +891,8 @@ pub struct SpeciesDefinition {
 
 impl SpeciesDefinition {
     /// Creates a new species definition mirroring the pressure specification.
+    ///
+    /// The prototype's kill bounty is derived from the species' pressure weight.
     #[allow(clippy::too_many_arguments)]
     #[must_use]
     pub const fn new(
@@ -522,7 +909,7 @@ impl SpeciesDefinition {
         Self {
             id,
             patch,
-            prototype,
+            prototype: prototype.with_bounty(weight.bounty()),
             weight,
             dirichlet_weight,
             min_burst_spawn,
@@ -623,19 +1010,59 @@ impl<'a> SpeciesTableView<'a> {
     }
 }
 
+/// Bugs a spawn patch releases at once unless configured otherwise.
+pub const SPAWN_PATCH_MAX_SIMULTANEOUS: NonZeroU32 = match NonZeroU32::new(3) {
+    Some(limit) => limit,
+    None => unreachable!(),
+};
+
+/// Cooldown a spawn patch observes between releases unless configured otherwise.
+pub const SPAWN_PATCH_MIN_INTERVAL: Duration = Duration::from_millis(200);
+
 /// Description of a deterministic spawn patch outside the maze.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpawnPatchDescriptor {
     id: SpawnPatchId,
     origin: CellCoord,
     extent: CellRect,
+    #[serde(default = "default_patch_max_simultaneous")]
+    max_simultaneous: NonZeroU32,
+    #[serde(default = "default_patch_min_interval")]
+    min_interval: Duration,
+}
+
+fn default_patch_max_simultaneous() -> NonZeroU32 {
+    SPAWN_PATCH_MAX_SIMULTANEOUS
+}
+
+fn default_patch_min_interval() -> Duration {
+    SPAWN_PATCH_MIN_INTERVAL
 }
 
 impl SpawnPatchDescriptor {
-    /// Creates a new spawn patch descriptor.
+    /// Creates a new spawn patch descriptor with the default spawn throughput limits.
     #[must_use]
     pub const fn new(id: SpawnPatchId, origin: CellCoord, extent: CellRect) -> Self {
-        Self { id, origin, extent }
+        Self {
+            id,
+            origin,
+            extent,
+            max_simultaneous: SPAWN_PATCH_MAX_SIMULTANEOUS,
+            min_interval: SPAWN_PATCH_MIN_INTERVAL,
+        }
+    }
+
+    /// Returns the descriptor releasing at most `max_simultaneous` bugs at once and then
+    /// waiting `min_interval` before its next release.
+    #[must_use]
+    pub const fn with_throughput(
+        mut self,
+        max_simultaneous: NonZeroU32,
+        min_interval: Duration,
+    ) -> Self {
+        self.max_simultaneous = max_simultaneous;
+        self.min_interval = min_interval;
+        self
     }
 
     /// Identifier assigned to the patch.
@@ -655,6 +1082,18 @@ impl SpawnPatchDescriptor {
     pub const fn extent(&self) -> CellRect {
         self.extent
     }
+
+    /// Returns the largest number of bugs the patch releases at once.
+    #[must_use]
+    pub const fn max_simultaneous(&self) -> NonZeroU32 {
+        self.max_simultaneous
+    }
+
+    /// Returns the cooldown the patch observes between two releases.
+    #[must_use]
+    pub const fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
 }
 
 /// Immutable snapshot of spawn patches stored in the world.
@@ -683,6 +1122,112 @@ impl<'a> SpawnPatchTableView<'a> {
     }
 }
 
+/// Grid edge that can host bug spawners. The bottom edge holds the exit and never spawns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GridEdge {
+    /// The topmost row of cells.
+    Top,
+    /// The leftmost column of cells, above the exit rows.
+    Left,
+    /// The rightmost column of cells, above the exit rows.
+    Right,
+}
+
+/// Group of cells that a level designates as bug spawners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpawnerSet {
+    /// Every cell along a grid edge.
+    Edge(GridEdge),
+    /// Every cell inside the extent of a spawn patch.
+    Patch(SpawnPatchId),
+    /// A single cell inside the maze acting as a portal.
+    Portal(CellCoord),
+}
+
+/// Spawner set that opens once a run reaches a given wave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpawnerActivation {
+    wave: WaveId,
+    set: SpawnerSet,
+}
+
+impl SpawnerActivation {
+    /// Creates an activation opening `set` ahead of `wave`.
+    #[must_use]
+    pub const fn new(wave: WaveId, set: SpawnerSet) -> Self {
+        Self { wave, set }
+    }
+
+    /// First wave that spawns from the activated set.
+    #[must_use]
+    pub const fn wave(&self) -> WaveId {
+        self.wave
+    }
+
+    /// Spawner set opened by the activation.
+    #[must_use]
+    pub const fn set(&self) -> SpawnerSet {
+        self.set
+    }
+}
+
+/// Level-defined spawner layout consumed whenever the world rebuilds its bug spawners.
+///
+/// The base sets are active from the start of a run; activations add further sets once the
+/// run reaches their wave so the maze gains entrances as it escalates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpawnerConfig {
+    sets: Vec<SpawnerSet>,
+    activations: Vec<SpawnerActivation>,
+}
+
+impl SpawnerConfig {
+    /// Creates a configuration from the provided spawner sets.
+    #[must_use]
+    pub fn new(sets: Vec<SpawnerSet>) -> Self {
+        Self {
+            sets,
+            activations: Vec::new(),
+        }
+    }
+
+    /// Returns the configuration with staged activations, ordered by wave.
+    #[must_use]
+    pub fn with_activations(mut self, mut activations: Vec<SpawnerActivation>) -> Self {
+        activations.sort_by_key(SpawnerActivation::wave);
+        self.activations = activations;
+        self
+    }
+
+    /// Classic layout spawning along the top, left and right edges.
+    #[must_use]
+    pub fn outer_rim() -> Self {
+        Self::new(vec![
+            SpawnerSet::Edge(GridEdge::Top),
+            SpawnerSet::Edge(GridEdge::Left),
+            SpawnerSet::Edge(GridEdge::Right),
+        ])
+    }
+
+    /// Spawner sets composing the configuration, in declaration order.
+    #[must_use]
+    pub fn sets(&self) -> &[SpawnerSet] {
+        &self.sets
+    }
+
+    /// Staged activations in ascending wave order.
+    #[must_use]
+    pub fn activations(&self) -> &[SpawnerActivation] {
+        &self.activations
+    }
+}
+
+impl Default for SpawnerConfig {
+    fn default() -> Self {
+        Self::outer_rim()
+    }
+}
+
 /// RNG stream label used when sampling the wave pressure.
 pub const RNG_STREAM_PRESSURE: &str = "pressure";
 
@@ -745,64 +1290,239 @@ impl DifficultyLevel {
     }
 }
 
-/// Inputs required by the pressure v2 wave generator.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct PressureWaveInputs {
-    game_seed: u64,
-    level_id: LevelId,
-    wave: WaveId,
-    difficulty: DifficultyLevel,
-}
+/// Effective difficulty expressed on a fixed-point ladder with thousandth-level precision.
+///
+/// Whole rungs correspond to [`DifficultyLevel`] values while the fractional part lets
+/// adaptive scaling nudge difficulty between levels. Arithmetic clamps to the ladder's
+/// bounds instead of wrapping, and values display as `3` or `3.25`.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct DifficultyLadder(u64);
 
-impl PressureWaveInputs {
-    /// Creates a new set of inputs for the pressure v2 generator.
+impl DifficultyLadder {
+    /// Number of fixed-point steps contained in a single difficulty level.
+    pub const MILLI_PER_LEVEL: u64 = 1_000;
+
+    /// Highest representable effective difficulty, the top rung of [`DifficultyLevel`].
+    pub const MAX: Self = Self(u32::MAX as u64 * Self::MILLI_PER_LEVEL);
+
+    /// Creates a ladder positioned exactly on the provided level.
     #[must_use]
-    pub fn new(
-        game_seed: u64,
-        level_id: LevelId,
-        wave: WaveId,
-        difficulty: DifficultyLevel,
-    ) -> Self {
-        Self {
-            game_seed,
-            level_id,
-            wave,
-            difficulty,
+    pub const fn from_level(level: DifficultyLevel) -> Self {
+        Self(level.get() as u64 * Self::MILLI_PER_LEVEL)
+    }
+
+    /// Creates a ladder from thousandths of a level, clamping to [`DifficultyLadder::MAX`].
+    #[must_use]
+    pub const fn from_milli(milli: u64) -> Self {
+        if milli > Self::MAX.0 {
+            Self::MAX
+        } else {
+            Self(milli)
         }
     }
 
-    /// Reports the difficulty scalar supplied to the generator.
+    /// Returns the effective difficulty in thousandths of a level.
     #[must_use]
-    pub fn difficulty(&self) -> DifficultyLevel {
-        self.difficulty
+    pub const fn milli(&self) -> u64 {
+        self.0
     }
 
-    /// Reports the level identifier supplied to the generator.
+    /// Returns the whole level reached on the ladder, rounding fractional rungs down.
     #[must_use]
-    pub fn level_id(&self) -> LevelId {
-        self.level_id
+    pub const fn level(&self) -> DifficultyLevel {
+        DifficultyLevel::new((self.0 / Self::MILLI_PER_LEVEL) as u32)
     }
 
-    /// Reports the wave identifier supplied to the generator.
+    /// Returns the fractional progress towards the next level in thousandths.
     #[must_use]
-    pub fn wave(&self) -> WaveId {
-        self.wave
+    pub const fn fraction_milli(&self) -> u32 {
+        (self.0 % Self::MILLI_PER_LEVEL) as u32
     }
 
-    /// Reports the global seed supplied to the generator.
+    /// Returns the effective difficulty as a floating point level for presentation.
     #[must_use]
-    pub fn game_seed(&self) -> u64 {
-        self.game_seed
+    pub fn as_f32(&self) -> f32 {
+        self.0 as f32 / Self::MILLI_PER_LEVEL as f32
     }
-}
 
-/// Spawn descriptor emitted by the pressure v2 generator.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct PressureSpawnRecord {
-    time_ms: u32,
-    hp: u32,
-    speed_mult: f32,
+    /// Returns the ladder raised by whole levels, clamped to [`DifficultyLadder::MAX`].
+    #[must_use]
+    pub const fn saturating_add_levels(self, levels: u32) -> Self {
+        Self::from_milli(self.0.saturating_add(levels as u64 * Self::MILLI_PER_LEVEL))
+    }
+
+    /// Returns the ladder lowered by whole levels, clamped at zero.
+    #[must_use]
+    pub const fn saturating_sub_levels(self, levels: u32) -> Self {
+        Self(self.0.saturating_sub(levels as u64 * Self::MILLI_PER_LEVEL))
+    }
+
+    /// Returns the ladder scaled by `percent`, clamped to the ladder's bounds.
+    ///
+    /// Adaptive scaling uses this to ease difficulty below or push it above the earned
+    /// level without leaving the ladder.
+    #[must_use]
+    pub const fn scaled_percent(self, percent: u32) -> Self {
+        Self::from_milli(self.0.saturating_mul(percent as u64) / 100)
+    }
+
+    /// Scalar applied to wave pressure: the nearest whole level plus one.
+    ///
+    /// Ladders sitting exactly on a level yield `level + 1`, matching the whole-level math.
+    #[must_use]
+    pub const fn pressure_scalar(&self) -> u32 {
+        let rounded = (self.0 + Self::MILLI_PER_LEVEL / 2) / Self::MILLI_PER_LEVEL;
+        let scalar = rounded + 1;
+        if scalar > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            scalar as u32
+        }
+    }
+}
+
+impl From<DifficultyLevel> for DifficultyLadder {
+    fn from(level: DifficultyLevel) -> Self {
+        Self::from_level(level)
+    }
+}
+
+impl fmt::Display for DifficultyLadder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / Self::MILLI_PER_LEVEL;
+        let fraction = self.0 % Self::MILLI_PER_LEVEL;
+        if fraction == 0 {
+            return write!(f, "{whole}");
+        }
+        let digits = format!("{fraction:03}");
+        write!(f, "{whole}.{}", digits.trim_end_matches('0'))
+    }
+}
+
+/// Inputs required by the pressure v2 wave generator.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PressureWaveInputs {
+    game_seed: u64,
+    level_id: LevelId,
+    wave: WaveId,
+    difficulty: DifficultyLevel,
+    #[serde(default = "full_pressure_share")]
+    pressure_share: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    history: Option<WaveHistoryInputs>,
+}
+
+const fn full_pressure_share() -> u32 {
+    100
+}
+
+/// How the player fared against the previous wave, fed back into the next wave's generation.
+///
+/// The generator only reads the history when its adaptation stage is enabled, and the same
+/// history always yields the same wave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WaveHistoryInputs {
+    /// Time the previous wave took to resolve, in milliseconds.
+    pub clear_time_ms: u64,
+    /// Bugs that reached the exit during the previous wave.
+    pub leaks: u32,
+}
+
+impl WaveHistoryInputs {
+    /// Summarises the outcome of a resolved wave.
+    #[must_use]
+    pub fn from_report(report: &WaveReport) -> Self {
+        Self {
+            clear_time_ms: u64::try_from(report.duration().as_millis()).unwrap_or(u64::MAX),
+            leaks: report.leaks(),
+        }
+    }
+}
+
+impl PressureWaveInputs {
+    /// Creates a new set of inputs for the pressure v2 generator.
+    #[must_use]
+    pub fn new(
+        game_seed: u64,
+        level_id: LevelId,
+        wave: WaveId,
+        difficulty: DifficultyLevel,
+    ) -> Self {
+        Self {
+            game_seed,
+            level_id,
+            wave,
+            difficulty,
+            pressure_share: full_pressure_share(),
+            history: None,
+        }
+    }
+
+    /// Records how the player fared against the previous wave.
+    #[must_use]
+    pub fn with_history(mut self, history: Option<WaveHistoryInputs>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Reports how the player fared against the previous wave, if known.
+    #[must_use]
+    pub fn history(&self) -> Option<WaveHistoryInputs> {
+        self.history
+    }
+
+    /// Scales the wave down to `percent` of its full pressure, e.g. after nests were
+    /// destroyed. Values are clamped to `1..=100`.
+    #[must_use]
+    pub fn with_pressure_share(mut self, percent: u32) -> Self {
+        self.pressure_share = percent.clamp(1, 100);
+        self
+    }
+
+    /// Reports the percentage of the full wave pressure the generator should produce.
+    #[must_use]
+    pub fn pressure_share(&self) -> u32 {
+        self.pressure_share
+    }
+
+    /// Reports the difficulty scalar supplied to the generator.
+    #[must_use]
+    pub fn difficulty(&self) -> DifficultyLevel {
+        self.difficulty
+    }
+
+    /// Reports the level identifier supplied to the generator.
+    #[must_use]
+    pub fn level_id(&self) -> LevelId {
+        self.level_id
+    }
+
+    /// Reports the wave identifier supplied to the generator.
+    #[must_use]
+    pub fn wave(&self) -> WaveId {
+        self.wave
+    }
+
+    /// Reports the global seed supplied to the generator.
+    #[must_use]
+    pub fn game_seed(&self) -> u64 {
+        self.game_seed
+    }
+}
+
+/// Spawn descriptor emitted by the pressure v2 generator.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PressureSpawnRecord {
+    time_ms: u32,
+    hp: u32,
+    speed_mult: f32,
     species_id: u32,
+    #[serde(default)]
+    archetype: BugArchetype,
+    #[serde(default)]
+    lane: Option<u32>,
 }
 
 impl PressureSpawnRecord {
@@ -814,9 +1534,25 @@ impl PressureSpawnRecord {
             hp,
             speed_mult,
             species_id,
+            archetype: BugArchetype::Basic,
+            lane: None,
         }
     }
 
+    /// Returns the record with the bug entering through spawn lane `lane`.
+    #[must_use]
+    pub fn with_lane(mut self, lane: u32) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
+    /// Returns the record with the spawned bug using `archetype`.
+    #[must_use]
+    pub fn with_archetype(mut self, archetype: BugArchetype) -> Self {
+        self.archetype = archetype;
+        self
+    }
+
     /// Reports the scheduled spawn time in milliseconds from wave start.
     #[must_use]
     pub fn time_ms(&self) -> u32 {
@@ -840,6 +1576,21 @@ impl PressureSpawnRecord {
     pub fn species_id(&self) -> u32 {
         self.species_id
     }
+
+    /// Reports the special ability of the spawned bug.
+    #[must_use]
+    pub fn archetype(&self) -> BugArchetype {
+        self.archetype
+    }
+
+    /// Reports the spawn lane the bug enters through, or `None` when any spawner will do.
+    ///
+    /// Lanes index the level's spawn patches in identifier order, wrapping around when the
+    /// level has fewer patches than lanes.
+    #[must_use]
+    pub fn lane(&self) -> Option<u32> {
+        self.lane
+    }
 }
 
 /// Canonical spawn list returned by the pressure v2 generator.
@@ -847,13 +1598,33 @@ impl PressureSpawnRecord {
 pub struct PressureWavePlan {
     spawns: Vec<PressureSpawnRecord>,
     prototypes: Vec<SpeciesPrototype>,
+    #[serde(default)]
+    paced: bool,
 }
 
 impl PressureWavePlan {
     /// Creates a new spawn list populated with generator output.
     #[must_use]
     pub fn new(spawns: Vec<PressureSpawnRecord>, prototypes: Vec<SpeciesPrototype>) -> Self {
-        Self { spawns, prototypes }
+        Self {
+            spawns,
+            prototypes,
+            paced: false,
+        }
+    }
+
+    /// Returns the plan marked as paced, so the world caches its spawn times as they are
+    /// instead of re-timing every species into bursts.
+    #[must_use]
+    pub fn with_paced(mut self) -> Self {
+        self.paced = true;
+        self
+    }
+
+    /// Reports whether the spawn times are final and the world leaves them unpaced.
+    #[must_use]
+    pub fn is_paced(&self) -> bool {
+        self.paced
     }
 
     /// Returns the captured spawn descriptors in deterministic order.
@@ -867,6 +1638,161 @@ impl PressureWavePlan {
     pub fn prototypes(&self) -> &[SpeciesPrototype] {
         &self.prototypes
     }
+
+    /// Returns the plan with `spawn` inserted after every spawn scheduled at or before it,
+    /// keeping the spawn list sorted by time.
+    #[must_use]
+    pub fn with_spawn(mut self, spawn: PressureSpawnRecord) -> Self {
+        let index = self
+            .spawns
+            .partition_point(|existing| existing.time_ms <= spawn.time_ms);
+        self.spawns.insert(index, spawn);
+        self
+    }
+
+    /// Returns the plan with the hit points of every spawn and prototype scaled by
+    /// `percent`, rounded to the nearest point.
+    ///
+    /// Scaled values saturate at `u32::MAX` and never drop below one hit point, so a scaled
+    /// plan stays valid for caching.
+    #[must_use]
+    pub fn with_health_scaled(mut self, percent: u32) -> Self {
+        let scale = |hp: u32| {
+            let scaled = (u64::from(hp) * u64::from(percent) + 50) / 100;
+            u32::try_from(scaled).unwrap_or(u32::MAX).max(1)
+        };
+        for spawn in &mut self.spawns {
+            spawn.hp = scale(spawn.hp);
+        }
+        for prototype in &mut self.prototypes {
+            prototype.health = Health::new(scale(prototype.health.get()));
+        }
+        self
+    }
+
+    /// Returns the plan with every spawn moved `offset_ms` milliseconds later, or earlier
+    /// when negative.
+    ///
+    /// Times clamp to the start of the wave and to `u32::MAX`, so spawn order is preserved.
+    #[must_use]
+    pub fn with_times_shifted(mut self, offset_ms: i64) -> Self {
+        for spawn in &mut self.spawns {
+            let shifted = i64::from(spawn.time_ms).saturating_add(offset_ms);
+            spawn.time_ms = u32::try_from(shifted.max(0)).unwrap_or(u32::MAX);
+        }
+        self
+    }
+
+    /// Returns the plan combined with `other`, interleaving both spawn lists by time.
+    ///
+    /// Spawns scheduled at the same time keep this plan's spawns first. Prototypes are
+    /// indexed by species, so this plan's prototypes win and `other` only contributes
+    /// prototypes for species beyond the end of this plan's list. The merged plan is paced
+    /// only when both plans are.
+    #[must_use]
+    pub fn merged_with(self, other: PressureWavePlan) -> Self {
+        let Self {
+            spawns,
+            mut prototypes,
+            paced,
+        } = self;
+        let mut merged = Vec::with_capacity(spawns.len() + other.spawns.len());
+        let mut ours = spawns.into_iter().peekable();
+        let mut theirs = other.spawns.into_iter().peekable();
+        loop {
+            let take_ours = match (ours.peek(), theirs.peek()) {
+                (Some(left), Some(right)) => left.time_ms <= right.time_ms,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            merged.extend(if take_ours {
+                ours.next()
+            } else {
+                theirs.next()
+            });
+        }
+        prototypes.extend(other.prototypes.into_iter().skip(prototypes.len()));
+        Self {
+            spawns: merged,
+            prototypes,
+            paced: paced && other.paced,
+        }
+    }
+}
+
+#[cfg(test)]
+mod plan_tests {
+    use std::num::NonZeroU32;
+
+    use super::{BugColor, Health, PressureSpawnRecord, PressureWavePlan, SpeciesPrototype};
+
+    fn prototype(health: u32) -> SpeciesPrototype {
+        SpeciesPrototype::new(
+            BugColor::from_rgb(0x10, 0x20, 0x30),
+            Health::new(health),
+            NonZeroU32::new(400).expect("non-zero cadence"),
+        )
+    }
+
+    fn times(plan: &PressureWavePlan) -> Vec<(u32, u32)> {
+        plan.spawns()
+            .iter()
+            .map(|spawn| (spawn.time_ms(), spawn.hp()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_helpers_keep_spawns_sorted_and_valid() {
+        let generated = PressureWavePlan::new(
+            vec![
+                PressureSpawnRecord::new(0, 10, 1.0, 0),
+                PressureSpawnRecord::new(500, 10, 1.0, 0),
+            ],
+            vec![prototype(10)],
+        );
+
+        let edited = generated
+            .clone()
+            .with_spawn(PressureSpawnRecord::new(500, 99, 1.0, 0))
+            .with_spawn(PressureSpawnRecord::new(250, 7, 1.0, 0));
+        assert_eq!(
+            times(&edited),
+            vec![(0, 10), (250, 7), (500, 10), (500, 99)]
+        );
+
+        let scaled = edited.clone().with_health_scaled(125);
+        assert_eq!(
+            times(&scaled),
+            vec![(0, 13), (250, 9), (500, 13), (500, 124)]
+        );
+        assert_eq!(scaled.prototypes()[0].health(), Health::new(13));
+        assert!(edited
+            .clone()
+            .with_health_scaled(0)
+            .spawns()
+            .iter()
+            .all(|spawn| spawn.hp() == 1));
+
+        let shifted = edited.clone().with_times_shifted(-300);
+        assert_eq!(times(&shifted), vec![(0, 10), (0, 7), (200, 10), (200, 99)]);
+        let delayed = generated.clone().with_times_shifted(i64::MAX);
+        assert!(delayed
+            .spawns()
+            .iter()
+            .all(|spawn| spawn.time_ms() == u32::MAX));
+
+        let scripted = PressureWavePlan::new(
+            vec![
+                PressureSpawnRecord::new(0, 50, 2.0, 1),
+                PressureSpawnRecord::new(750, 50, 2.0, 1),
+            ],
+            vec![prototype(99), prototype(50)],
+        );
+        let merged = generated.merged_with(scripted);
+        assert_eq!(times(&merged), vec![(0, 10), (0, 50), (500, 10), (750, 50)]);
+        assert_eq!(merged.prototypes(), &[prototype(10), prototype(50)]);
+    }
 }
 
 /// Deterministic analytics snapshot derived from the world state.
@@ -965,31 +1891,538 @@ impl StatsReport {
 mod stats_tests {
     use super::StatsReport;
 
-    #[test]
-    fn stats_report_preserves_values() {
-        let report = StatsReport::new(8_750, 2_500, 32, 5, 900);
+    #[test]
+    fn stats_report_preserves_values() {
+        let report = StatsReport::new(8_750, 2_500, 32, 5, 900);
+
+        assert_eq!(report.tower_coverage_mean_bps(), 8_750);
+        assert_eq!(report.firing_complete_percent_bps(), 2_500);
+        assert_eq!(report.shortest_path_length_cells(), 32);
+        assert_eq!(report.tower_count(), 5);
+        assert_eq!(report.total_tower_dps(), 900);
+    }
+}
+
+/// Normalised per-cell danger map built from historical bug traffic and leak routes.
+///
+/// Intensities are integers in `0..=DangerHeatmap::MAX_INTENSITY`, scaled so the busiest
+/// cell reaches the maximum. Cells outside the grid report zero.
+///
+/// ```
+/// use maze_defence_core::{CellCoord, DangerHeatmap};
+///
+/// let heatmap = DangerHeatmap::from_counts(2, 1, &[3, 6]);
+///
+/// assert_eq!(heatmap.intensity(CellCoord::new(0, 0)), 500);
+/// assert_eq!(heatmap.intensity(CellCoord::new(1, 0)), DangerHeatmap::MAX_INTENSITY);
+/// assert_eq!(heatmap.intensity(CellCoord::new(5, 5)), 0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DangerHeatmap {
+    columns: u32,
+    rows: u32,
+    intensity: Vec<u16>,
+}
+
+impl DangerHeatmap {
+    /// Intensity reported for the most travelled cell.
+    pub const MAX_INTENSITY: u16 = 1_000;
+
+    /// Normalises raw row-major traffic counts into a heatmap.
+    ///
+    /// Missing trailing counts are treated as zero and extra counts are ignored.
+    #[must_use]
+    pub fn from_counts(columns: u32, rows: u32, counts: &[u32]) -> Self {
+        let len = usize::try_from(u64::from(columns) * u64::from(rows)).unwrap_or(0);
+        let peak = counts.iter().take(len).copied().max().unwrap_or(0);
+        let intensity = (0..len)
+            .map(|index| {
+                let count = counts.get(index).copied().unwrap_or(0);
+                if peak == 0 {
+                    0
+                } else {
+                    let scaled =
+                        u64::from(count) * u64::from(Self::MAX_INTENSITY) / u64::from(peak);
+                    u16::try_from(scaled).unwrap_or(Self::MAX_INTENSITY)
+                }
+            })
+            .collect();
+        Self {
+            columns,
+            rows,
+            intensity,
+        }
+    }
+
+    /// Number of cell columns covered by the heatmap.
+    #[must_use]
+    pub const fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// Number of cell rows covered by the heatmap.
+    #[must_use]
+    pub const fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Intensity recorded for the provided cell.
+    #[must_use]
+    pub fn intensity(&self, cell: CellCoord) -> u16 {
+        if cell.column() >= self.columns || cell.row() >= self.rows {
+            return 0;
+        }
+        let index = u64::from(cell.row()) * u64::from(self.columns) + u64::from(cell.column());
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.intensity.get(index))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Iterates over every cell with a non-zero intensity in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (CellCoord, u16)> + '_ {
+        let columns = self.columns.max(1);
+        self.intensity
+            .iter()
+            .enumerate()
+            .filter(|(_, intensity)| **intensity > 0)
+            .map(move |(index, intensity)| {
+                let index = index as u32;
+                (CellCoord::new(index % columns, index / columns), *intensity)
+            })
+    }
+}
+
+/// After-action summary describing how a single wave played out.
+///
+/// Damage totals count the health actually removed from bugs, so overkill on the
+/// final hit is not credited. The MVP is the tower with the largest total, with ties
+/// going to the lowest tower identifier.
+///
+/// ```
+/// use std::time::Duration;
+/// use maze_defence_core::{Gold, RoundOutcome, TowerId, WaveId, WaveReport};
+///
+/// let report = WaveReport::new(
+///     WaveId::new(3),
+///     RoundOutcome::Win,
+///     Duration::from_secs(42),
+///     0,
+///     Gold::new(12),
+///     vec![(TowerId::new(1), 30), (TowerId::new(2), 45)],
+/// );
+///
+/// assert_eq!(report.mvp(), Some(TowerId::new(2)));
+/// assert_eq!(report.total_damage(), 75);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaveReport {
+    wave: WaveId,
+    outcome: RoundOutcome,
+    duration: Duration,
+    leaks: u32,
+    gold_earned: Gold,
+    tower_damage: Vec<(TowerId, u32)>,
+    species_combat: Vec<SpeciesCombat>,
+}
+
+impl WaveReport {
+    /// Creates a new wave report; damage entries are sorted by tower identifier.
+    #[must_use]
+    pub fn new(
+        wave: WaveId,
+        outcome: RoundOutcome,
+        duration: Duration,
+        leaks: u32,
+        gold_earned: Gold,
+        mut tower_damage: Vec<(TowerId, u32)>,
+    ) -> Self {
+        tower_damage.sort_by_key(|(tower, _)| *tower);
+        Self {
+            wave,
+            outcome,
+            duration,
+            leaks,
+            gold_earned,
+            tower_damage,
+            species_combat: Vec::new(),
+        }
+    }
+
+    /// Attaches how each species' bugs fared; entries are sorted by species identifier.
+    #[must_use]
+    pub fn with_species_combat(mut self, mut species_combat: Vec<SpeciesCombat>) -> Self {
+        species_combat.sort_by_key(|entry| entry.species);
+        self.species_combat = species_combat;
+        self
+    }
+
+    /// Identifier of the wave the report describes.
+    #[must_use]
+    pub const fn wave(&self) -> WaveId {
+        self.wave
+    }
+
+    /// Outcome the wave was resolved with.
+    #[must_use]
+    pub const fn outcome(&self) -> RoundOutcome {
+        self.outcome
+    }
+
+    /// Simulated time between the wave launch and its resolution.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Number of bugs that reached the exit during the wave.
+    #[must_use]
+    pub const fn leaks(&self) -> u32 {
+        self.leaks
+    }
+
+    /// Gold awarded for bugs killed during the wave.
+    #[must_use]
+    pub const fn gold_earned(&self) -> Gold {
+        self.gold_earned
+    }
+
+    /// Damage dealt by each tower that hit at least once, ordered by tower identifier.
+    #[must_use]
+    pub fn tower_damage(&self) -> &[(TowerId, u32)] {
+        &self.tower_damage
+    }
+
+    /// How each species' bugs fared during the wave, ordered by species identifier.
+    #[must_use]
+    pub fn species_combat(&self) -> &[SpeciesCombat] {
+        &self.species_combat
+    }
+
+    /// Total damage dealt by every tower during the wave.
+    #[must_use]
+    pub fn total_damage(&self) -> u32 {
+        self.tower_damage
+            .iter()
+            .fold(0, |total, (_, damage)| total.saturating_add(*damage))
+    }
+
+    /// Tower that dealt the most damage during the wave, if any tower dealt damage.
+    #[must_use]
+    pub fn mvp(&self) -> Option<TowerId> {
+        self.tower_damage
+            .iter()
+            .rev()
+            .max_by_key(|(_, damage)| *damage)
+            .map(|(tower, _)| *tower)
+    }
+}
+
+/// Machine-readable record of a resolved wave for balancing dashboards.
+///
+/// The `game_seed`, `level`, `wave` and `difficulty_level` fields repeat the
+/// [`PressureWaveInputs`] the wave was generated from, so the record joins with generator
+/// telemetry keyed by the same inputs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaveOutcome {
+    /// Identifier of the resolved wave.
+    pub wave: WaveId,
+    /// Level the wave was played on.
+    pub level: LevelId,
+    /// Global seed the wave was generated from.
+    pub game_seed: u64,
+    /// Difficulty selection the wave launched with.
+    pub difficulty: WaveDifficulty,
+    /// Effective difficulty level the wave was generated at.
+    pub difficulty_level: DifficultyLevel,
+    /// Outcome the wave was resolved with.
+    pub outcome: RoundOutcome,
+    /// Simulated milliseconds between the wave launch and its resolution.
+    pub duration_ms: u64,
+    /// Number of bugs that reached the exit during the wave.
+    pub leaks: u32,
+    /// Gold awarded for bugs killed during the wave.
+    pub gold_earned: Gold,
+    /// Spawns planned for each species, ordered by species identifier.
+    pub composition: Vec<SpeciesSpawns>,
+    /// Towers standing when the wave launched, grouped by kind in [`TowerKind::ALL`] order.
+    pub towers: Vec<TowerKindCount>,
+    /// How each species' bugs fared, ordered by species identifier.
+    #[serde(default)]
+    pub species_combat: Vec<SpeciesCombat>,
+}
+
+/// Spawns of one species planned for a wave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeciesSpawns {
+    /// Generated species identifier.
+    pub species: u32,
+    /// Number of bugs of the species the wave spawns.
+    pub count: u32,
+    /// Combined hit points of those bugs.
+    pub total_hp: u64,
+}
+
+/// How the bugs of one species fared during a wave.
+///
+/// Adds summoned by bosses and children released by splitters count towards the species
+/// whose colour they carry. Bugs still in the maze when the wave resolves count as alive
+/// until then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeciesCombat {
+    /// Generated species identifier.
+    pub species: u32,
+    /// Number of bugs of the species that entered the maze.
+    pub bugs: u32,
+    /// Number of those bugs towers killed.
+    pub killed: u32,
+    /// Mean simulated milliseconds a bug spent in the maze.
+    pub average_lifetime_ms: u64,
+    /// Mean number of cells a bug stepped through.
+    pub average_cells_travelled: u32,
+    /// Health towers removed from the species' bugs, regenerated health included.
+    pub damage_absorbed: u64,
+    /// Damage absorbed per second of combined bug lifetime.
+    pub damage_per_second: u64,
+}
+
+/// Number of towers of one kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TowerKindCount {
+    /// Kind of the counted towers.
+    pub kind: TowerKind,
+    /// Number of towers of the kind.
+    pub count: u32,
+}
+
+/// Random event rolled deterministically by the world after a wave resolves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BetweenWaveEvent {
+    /// A meteor strike destroyed one of the towers forming the maze.
+    MeteorStrike {
+        /// Tower that was destroyed.
+        tower: TowerId,
+    },
+    /// A travelling merchant offers a discount on the next tower until the next wave launches.
+    MerchantOffer {
+        /// Percentage knocked off the next tower's build cost when accepted.
+        discount_percent: u32,
+    },
+    /// Bug rewards pay double during the next wave.
+    GoldRush,
+}
+
+/// Optional risk/reward contract the player may accept before launching a wave.
+///
+/// Contracts raise the effective difficulty fed into the pressure inputs, or otherwise
+/// toughen the wave, and add a percentage bonus on top of the wave's reward multiplier.
+///
+/// ```
+/// use maze_defence_core::WaveContract;
+///
+/// assert_eq!(WaveContract::Reinforced.difficulty_bonus(), 1);
+/// assert_eq!(WaveContract::Onslaught.reward_bonus_percent(), 100);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum WaveContract {
+    /// Tougher bugs, one difficulty level up, for 50% more gold.
+    Reinforced,
+    /// A much harder wave, two difficulty levels up, for double gold.
+    Onslaught,
+    /// Bugs share a regenerating shield that soaks every hit until focused fire breaks
+    /// it, for 40% more gold.
+    Shielded,
+}
+
+impl WaveContract {
+    /// Every contract offered to the player, in presentation order.
+    pub const ALL: [Self; 3] = [Self::Reinforced, Self::Onslaught, Self::Shielded];
+
+    /// Difficulty levels added to the wave's effective difficulty.
+    #[must_use]
+    pub const fn difficulty_bonus(self) -> u32 {
+        match self {
+            Self::Reinforced => 1,
+            Self::Onslaught => 2,
+            Self::Shielded => 0,
+        }
+    }
+
+    /// Percentage added to the wave's reward multiplier.
+    #[must_use]
+    pub const fn reward_bonus_percent(self) -> u32 {
+        match self {
+            Self::Reinforced => 50,
+            Self::Onslaught => 100,
+            Self::Shielded => 40,
+        }
+    }
+}
+
+/// Rule toggles that turn a session into an experimentation sandbox.
+///
+/// Any enabled toggle marks the session as a sandbox session for its remaining lifetime,
+/// so results recorded while experimenting can be told apart from regular play.
+///
+/// ```
+/// use maze_defence_core::SandboxRules;
+///
+/// assert!(!SandboxRules::default().is_enabled());
+/// assert!(SandboxRules::preset().free_placement);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SandboxRules {
+    /// Towers are placed without spending or refunding gold.
+    pub free_placement: bool,
+    /// Towers are ready to fire again immediately after shooting.
+    pub instant_cooldowns: bool,
+    /// Debug spawns of arbitrary species on any free cell are honoured.
+    pub debug_spawns: bool,
+}
+
+impl SandboxRules {
+    /// Preset enabling every sandbox toggle.
+    #[must_use]
+    pub const fn preset() -> Self {
+        Self {
+            free_placement: true,
+            instant_cooldowns: true,
+            debug_spawns: true,
+        }
+    }
+
+    /// Reports whether any sandbox toggle is enabled.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.free_placement || self.instant_cooldowns || self.debug_spawns
+    }
+}
+
+/// Starting bonuses fixed when a new game begins.
+///
+/// The defaults leave a game unchanged: no extra gold and a base that falls to the first
+/// bug reaching the exit.
+///
+/// ```
+/// use maze_defence_core::{GameRules, Gold};
+///
+/// let rules = GameRules::default();
+/// assert_eq!(rules.bonus_gold, Gold::ZERO);
+/// assert_eq!(rules.base_health, 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GameRules {
+    /// Gold added to the starting balance.
+    pub bonus_gold: Gold,
+    /// Bugs that must reach the exit before the round is lost; a leak that leaves the base
+    /// standing only costs one point. Refilled whenever a round is lost.
+    pub base_health: u32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            bonus_gold: Gold::ZERO,
+            base_health: 1,
+        }
+    }
+}
+
+/// Decides what happens when a bug reaches the exit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExitBehavior {
+    /// The first bug through the exit ends the round in defeat.
+    #[default]
+    EndRound,
+    /// Exiting bugs re-enter the maze from a spawner, keeping `health_percent` percent of
+    /// their remaining health, rounded down. Bugs worn down to no health leave for good.
+    LoopAround {
+        /// Share of the remaining health a bug keeps on re-entering, clamped to 99 so every
+        /// lap wears it down.
+        health_percent: u32,
+    },
+}
+
+/// Outcome emitted when resolving a round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RoundOutcome {
+    /// Indicates that the defenders successfully cleared the round.
+    Win,
+    /// Indicates that the defenders lost the round.
+    Loss,
+}
+
+/// Virtual simulation time, counted in ticks and elapsed duration.
+///
+/// The world clock and the adapter's wave executor both keep one, so tests can move them
+/// straight to a point in time with [`SimClock::advance_to_ms`] instead of feeding them
+/// thousands of small ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SimClock {
+    ticks: u64,
+    elapsed: Duration,
+}
+
+impl SimClock {
+    /// Creates a clock at time zero that has not ticked yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ticks: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Number of times the clock has advanced.
+    #[must_use]
+    pub const fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Simulated time elapsed since the clock started.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Simulated time elapsed since the clock started, in whole milliseconds.
+    #[must_use]
+    pub fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.elapsed.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// Time left until the clock reads `at_ms`, or zero if it already has.
+    #[must_use]
+    pub fn until_ms(&self, at_ms: u64) -> Duration {
+        Duration::from_millis(at_ms).saturating_sub(self.elapsed)
+    }
 
-        assert_eq!(report.tower_coverage_mean_bps(), 8_750);
-        assert_eq!(report.firing_complete_percent_bps(), 2_500);
-        assert_eq!(report.shortest_path_length_cells(), 32);
-        assert_eq!(report.tower_count(), 5);
-        assert_eq!(report.total_tower_dps(), 900);
+    /// Advances the clock by one tick of `dt`.
+    pub fn advance(&mut self, dt: Duration) {
+        self.ticks = self.ticks.saturating_add(1);
+        self.elapsed = self.elapsed.saturating_add(dt);
     }
-}
 
-/// Outcome emitted when resolving a round.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum RoundOutcome {
-    /// Indicates that the defenders successfully cleared the round.
-    Win,
-    /// Indicates that the defenders lost the round.
-    Loss,
+    /// Advances the clock to `at_ms` in a single tick and returns the time that passed.
+    ///
+    /// A clock already at or past `at_ms` stays put and returns zero.
+    pub fn advance_to_ms(&mut self, at_ms: u64) -> Duration {
+        let dt = self.until_ms(at_ms);
+        if !dt.is_zero() {
+            self.advance(dt);
+        }
+        dt
+    }
 }
 
 /// Commands that express all permissible world mutations.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Command {
     /// Configures the world's tile grid using the provided dimensions.
+    ///
+    /// Degenerate dimensions are rejected with [`Event::TileGridConfigRejected`] and the
+    /// previous grid is kept.
     ConfigureTileGrid {
         /// Number of tile columns laid out in the grid.
         columns: TileCoord,
@@ -1000,6 +2433,15 @@ pub enum Command {
         /// Number of navigation cells subdividing each tile edge.
         cells_per_tile: u32,
     },
+    /// Replaces the level's bug spawner layout.
+    ///
+    /// The layout is validated against the current grid: every set, staged or not, must
+    /// resolve to cells inside the grid and every resulting spawner must be able to reach an
+    /// exit. Activations already due for the upcoming wave open immediately.
+    ConfigureSpawners {
+        /// Spawner layout defined by the level.
+        config: SpawnerConfig,
+    },
     /// Updates the duration a bug must accumulate before attempting another step.
     ConfigureBugStep {
         /// Minimum simulated time required between successive bug steps.
@@ -1015,6 +2457,28 @@ pub enum Command {
         /// Duration of simulated time that elapsed since the previous tick.
         dt: Duration,
     },
+    /// Advances the simulation clock like [`Command::Tick`], but lets the world defer tower
+    /// cooldown, heat and projectile integration while fast-forwarding.
+    ///
+    /// Deferred work is settled on the tick where a cooldown, stall or projectile would
+    /// complete, so bugs, firing and hits resolve exactly as with plain ticks. Until then
+    /// the cooldowns and projectile positions reported by queries may lag behind; any other
+    /// command, including a plain tick, settles them first.
+    BatchedTick {
+        /// Duration of simulated time that elapsed since the previous tick.
+        dt: Duration,
+    },
+    /// Advances the simulation clock to `at_ms` as one [`Command::Tick`] covering the
+    /// remaining time; a clock already past `at_ms` does not move.
+    ///
+    /// Cooldowns, heat, projectiles and wave timers settle exactly as after the equivalent
+    /// run of small ticks, while each bug takes at most one step. Tests use it to skip long
+    /// stretches of waiting. In builder mode the remaining time only drains the builder
+    /// countdown, because the simulation clock is paused there.
+    AdvanceTo {
+        /// Simulation time, in milliseconds since the world was created, to advance to.
+        at_ms: u64,
+    },
     /// Requests that a bug advance a single step in the specified direction.
     StepBug {
         /// Identifier of the bug attempting to move.
@@ -1042,6 +2506,42 @@ pub enum Command {
         health: Health,
         /// Resolved cadence in milliseconds required between steps.
         step_ms: u32,
+        /// Route preference assigned to the spawned bug.
+        pathing: PathingPersonality,
+        /// Gold paid, before wave reward multipliers, when the bug is killed.
+        bounty: Gold,
+        /// Rank of the bug; bosses follow the scripted boss phases.
+        rank: BugRank,
+        /// Chance that the bug evades projectiles.
+        dodge: DodgeChance,
+        /// Special ability of the bug.
+        archetype: BugArchetype,
+    },
+    /// Requests that a squad of identical bugs enter the maze together as a formation.
+    ///
+    /// The world claims every cell of the formation atomically: when any slot is blocked
+    /// or falls outside the grid, no bug of the squad is spawned.
+    SpawnFormation {
+        /// Spawner cell anchoring the formation's leader.
+        anchor: CellCoord,
+        /// Arrangement of the squad.
+        shape: FormationShape,
+        /// Number of bugs in the squad.
+        size: u32,
+        /// Appearance to assign to every squad member.
+        color: BugColor,
+        /// Health assigned to every squad member.
+        health: Health,
+        /// Resolved cadence in milliseconds required between steps.
+        step_ms: u32,
+        /// Route preference assigned to every squad member.
+        pathing: PathingPersonality,
+        /// Gold paid, before wave reward multipliers, when a squad member is killed.
+        bounty: Gold,
+        /// Chance that each squad member evades projectiles.
+        dodge: DodgeChance,
+        /// Special ability of every squad member.
+        archetype: BugArchetype,
     },
     /// Requests that a tower fire a projectile at a targeted bug.
     FireProjectile {
@@ -1050,7 +2550,45 @@ pub enum Command {
         /// Identifier of the targeted bug.
         target: BugId,
     },
-    /// Requests placement of a tower anchored at the provided origin cell.
+    /// Selects the rotation applied to subsequent tower placements.
+    SetPlacementRotation {
+        /// Rotation applied to new footprints.
+        rotation: TowerRotation,
+    },
+    /// Replaces the bridge cells, where towers are built on an upper deck while ground bugs
+    /// keep walking through the tunnel beneath. Cells outside the grid or on walls are
+    /// ignored.
+    ConfigureBridges {
+        /// Cells covered by a bridge deck.
+        cells: Vec<CellCoord>,
+    },
+    /// Turns every spawner set into a destructible nest starting with `health`, or makes
+    /// spawners indestructible again with `None`.
+    ///
+    /// Destroying a nest permanently closes its spawners and shrinks the pressure share of
+    /// later waves. The last open nest cannot be destroyed. Reconfiguring restores every
+    /// nest of the current layout.
+    ConfigureNests {
+        /// Health every nest starts with, or `None` to disable nests.
+        health: Option<Health>,
+    },
+    /// Requests that a tower shoot at a nest. The shot lands at once since nests never move.
+    FireAtNest {
+        /// Identifier of the tower attempting to shoot.
+        tower: TowerId,
+        /// Spawner set of the targeted nest.
+        nest: SpawnerSet,
+    },
+    /// Replaces the void cells, such as water, that bugs cannot walk and towers cannot be
+    /// built on. Projectiles and tower line of sight pass over them, and ground bugs cross
+    /// them only where a bridge deck spans the void. Cells outside the grid or on walls are
+    /// ignored.
+    ConfigureVoidCells {
+        /// Cells that are void.
+        cells: Vec<CellCoord>,
+    },
+    /// Requests placement of a tower anchored at the provided origin cell, using the
+    /// current placement rotation.
     PlaceTower {
         /// Type of tower to construct at the origin.
         kind: TowerKind,
@@ -1068,12 +2606,26 @@ pub enum Command {
         kind: TowerKind,
         /// Upper-left cell that defines the tower's footprint.
         origin: CellCoord,
+        /// Rotation applied to the tower's footprint.
+        rotation: TowerRotation,
     },
     /// Requests removal of an existing tower from the world.
     RemoveTower {
         /// Identifier of the tower targeted for removal.
         tower: TowerId,
     },
+    /// Sets how a tower picks its target among the bugs in its range.
+    SetTowerTargeting {
+        /// Identifier of the tower whose targeting changes.
+        tower: TowerId,
+        /// Policy the tower follows from now on.
+        policy: TargetingPolicy,
+    },
+    /// Requests the next upgrade of a tower, paid from the gold bank.
+    UpgradeTower {
+        /// Identifier of the tower to upgrade.
+        tower: TowerId,
+    },
     /// Resolves the previously concluded round with the provided outcome.
     ResolveRound {
         /// Outcome that should be applied to the world state.
@@ -1085,6 +2637,9 @@ pub enum Command {
         inputs: PressureWaveInputs,
     },
     /// Caches a generated pressure v2 wave inside the world for later reuse.
+    ///
+    /// Plans not marked with [`PressureWavePlan::with_paced`] are paced into species bursts
+    /// first, exactly like plans from [`Command::GeneratePressureWave`].
     CachePressureWave {
         /// Inputs used to generate the cached wave.
         inputs: PressureWaveInputs,
@@ -1098,6 +2653,107 @@ pub enum Command {
         /// Difficulty level selection used for the wave launch.
         difficulty: WaveDifficulty,
     },
+    /// Configures the delay after a resolved round before the next wave launches
+    /// automatically. `None` disables automatic launches.
+    ConfigureAutoWave {
+        /// Inter-wave delay counted down before the next wave starts.
+        delay: Option<Duration>,
+    },
+    /// Limits how long Builder mode may last between waves. `None` lifts the limit.
+    ///
+    /// Building time only runs down while the world is in Builder mode and is restored
+    /// whenever a round resolves. Once it runs out the world switches to Attack mode and
+    /// refuses to re-enter Builder mode until the next round resolves.
+    ConfigureBuilderTimer {
+        /// Building time granted between two waves.
+        limit: Option<Duration>,
+    },
+    /// Replaces the global seed every later wave plan and between-wave roll derives from.
+    ///
+    /// Configuring the tile grid restores the default seed, so configure the seed after it.
+    ConfigureWaveSeed {
+        /// Seed folded into the generator inputs of every following wave.
+        seed: u64,
+    },
+    /// Ends an active next-wave countdown early, banking the unused preparation time as
+    /// the chosen reward.
+    SkipWaveCountdown {
+        /// Reward the remaining countdown is converted into.
+        reward: PreparationReward,
+    },
+    /// Accepts or withdraws a contract applied to the next wave launch.
+    SetWaveContract {
+        /// Contract being accepted or withdrawn.
+        contract: WaveContract,
+        /// Whether the contract should apply to the next wave.
+        accepted: bool,
+    },
+    /// Accepts or declines the merchant offer rolled between waves.
+    AnswerMerchantOffer {
+        /// Whether the player takes the discount on their next tower.
+        accept: bool,
+    },
+    /// Enables the projectile accuracy rules, or disables them with `None` so every
+    /// projectile reaching a living target hits.
+    ConfigureAccuracy {
+        /// Rules deciding when projectiles miss.
+        model: Option<AccuracyModel>,
+    },
+    /// Applies the starting bonuses of a new game: adds the bonus gold to the balance and
+    /// fills the base to its health. Sent once, before the first wave launches.
+    ConfigureGameRules {
+        /// Bonuses granted to the new game.
+        rules: GameRules,
+    },
+    /// Selects what happens when a bug reaches the exit.
+    ConfigureExitBehavior {
+        /// Behaviour applied to bugs reaching the exit from now on.
+        behavior: ExitBehavior,
+    },
+    /// Enables or disables the tower heat rules. Towers start cold whenever the rules change.
+    ConfigureTowerHeat {
+        /// Whether firing builds heat that can overheat and stall towers.
+        enabled: bool,
+    },
+    /// Enables or disables the line-of-sight rules, under which walls and other towers
+    /// block the shots of kinds that [require line of sight].
+    ///
+    /// [require line of sight]: TowerKind::requires_line_of_sight
+    ConfigureLineOfSight {
+        /// Whether obstacles block shots.
+        enabled: bool,
+    },
+    /// Replaces the per-kind tower statistics used for placement, refunds and firing.
+    ConfigureTowerContent {
+        /// Statistics in effect from now on.
+        content: TowerContent,
+    },
+    /// Replaces the sandbox rule toggles.
+    ConfigureSandbox {
+        /// Toggles applied from now on.
+        rules: SandboxRules,
+    },
+    /// Replaces the wave seed from the next wave on so a shared or reported wave can be
+    /// replayed exactly. Only honoured in a sandbox session and never while a wave is
+    /// running.
+    SetWaveSeed {
+        /// Seed folded into the generator inputs of every following wave.
+        seed: u64,
+    },
+    /// Spawns a bug of any species on a free cell. Only honoured while the sandbox
+    /// permits debug spawns.
+    DebugSpawnBug {
+        /// Cell the bug appears on.
+        cell: CellCoord,
+        /// Species from the world's species table used for the bug.
+        species: SpeciesId,
+    },
+    /// Flags a bug as the player's priority target, or clears the flag when the bug is
+    /// already marked.
+    MarkPriorityTarget {
+        /// Identifier of the bug the player clicked.
+        bug: BugId,
+    },
     /// Requests that analytics recompute using the current authoritative layout.
     ///
     /// Builder-mode flows emit this command after structural edits so analytics
@@ -1107,9 +2763,70 @@ pub enum Command {
     RequestAnalyticsRefresh,
 }
 
+/// Resolved parameters reported when a wave launches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaveLaunch {
+    /// Difficulty selection applied to the launch.
+    pub difficulty: WaveDifficulty,
+    /// Effective difficulty applied for this wave, including Hard escalations.
+    pub effective_difficulty: DifficultyLadder,
+    /// Multiplier applied to gold rewards while the wave is active.
+    pub reward_multiplier: u32,
+    /// Scalar applied to pressure calculations for the wave contents.
+    pub pressure_scalar: u32,
+    /// Percentage of the full wave pressure kept after nests were destroyed.
+    pub pressure_share: u32,
+    /// Outcome of the previous wave supplied to the generator, if any.
+    pub history: Option<WaveHistoryInputs>,
+    /// Total pressure budget contained within the cached attack plan.
+    pub plan_pressure: Pressure,
+    /// Version of the species table used when generating the cached plan.
+    pub plan_species_table_version: SpeciesTableVersion,
+    /// Total number of bursts scheduled by the cached plan.
+    pub plan_burst_count: u32,
+    /// Contracts the player accepted for this wave, in ascending order.
+    pub contracts: Vec<WaveContract>,
+}
+
+/// Upper bound on the size of [`Event`].
+///
+/// Every tick pushes many events, so payloads larger than a few words are boxed to keep
+/// the enum cheap to move. Growing past this bound should box the offending variant.
+const EVENT_SIZE_LIMIT: usize = 32;
+
+const _: () = assert!(std::mem::size_of::<Event>() <= EVENT_SIZE_LIMIT);
+
 /// Events broadcast by the world after processing commands.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Event {
+    /// Reports the time left before the next wave launches automatically.
+    ///
+    /// A `remaining_ms` of zero signals that the countdown elapsed or was skipped and the
+    /// next wave should start now.
+    NextWaveCountdown {
+        /// Milliseconds remaining before the automatic launch.
+        remaining_ms: u32,
+    },
+    /// Reports the building time left before Builder mode ends automatically.
+    ///
+    /// A `remaining_ms` of zero signals that building time ran out; the world switches to
+    /// Attack mode in the same step.
+    BuilderCountdown {
+        /// Milliseconds of building time remaining.
+        remaining_ms: u32,
+    },
+    /// Reports that skipping the countdown banked unused preparation time.
+    PreparationBanked {
+        /// Reward the banked time was converted into.
+        reward: PreparationReward,
+        /// Countdown time that was left when the player launched early.
+        banked_ms: u32,
+    },
+    /// Reports that the player-designated priority target changed.
+    PriorityTargetChanged {
+        /// Bug currently flagged as the priority target, if any.
+        bug: Option<BugId>,
+    },
     /// Indicates that the simulation clock advanced.
     TimeAdvanced {
         /// Duration of simulated time that elapsed in the tick.
@@ -1131,6 +2848,33 @@ pub enum Event {
         /// Cell that triggered the exit when the bug entered it.
         cell: CellCoord,
     },
+    /// Reports that a bug reached the exit and re-entered the maze from a spawner under the
+    /// loop-around exit behaviour.
+    BugLooped {
+        /// Identifier of the bug, which it keeps after re-entering.
+        bug_id: BugId,
+        /// Exit cell the bug reached.
+        exit: CellCoord,
+        /// Spawner cell the bug re-entered from.
+        spawner: CellCoord,
+        /// Health the bug re-entered with.
+        remaining: Health,
+    },
+    /// Reports that a bug reached the exit under the loop-around exit behaviour with too
+    /// little health left to re-enter, and left the maze for good without paying a bounty.
+    BugWornOut {
+        /// Identifier of the bug that left the maze.
+        bug_id: BugId,
+        /// Exit cell the bug reached.
+        exit: CellCoord,
+    },
+    /// Reports that a bug reached the exit and the base absorbed the leak.
+    BaseDamaged {
+        /// Identifier of the bug that reached the exit.
+        bug: BugId,
+        /// Base health left before the round is lost.
+        remaining: u32,
+    },
     /// Announces that the current round ended in defeat.
     RoundLost {
         /// Identifier of the bug that caused the loss by reaching the exit.
@@ -1157,6 +2901,30 @@ pub enum Event {
     /// cached inputs are stale and should be re-sampled from the authoritative
     /// world state the next time they recompute.
     MazeLayoutChanged,
+    /// Confirms that a new bug spawner layout was applied.
+    SpawnersConfigured {
+        /// Number of spawner cells resolved from the layout.
+        spawners: u32,
+    },
+    /// Announces that a staged spawner set opened ahead of an upcoming wave.
+    SpawnerActivated {
+        /// Wave the activation was staged for.
+        wave: WaveId,
+        /// Spawner set that opened.
+        set: SpawnerSet,
+        /// Number of spawner cells the set contributed.
+        spawners: u32,
+    },
+    /// Reports that a tile grid configuration was rejected and the previous grid kept.
+    TileGridConfigRejected {
+        /// Specific reason the configuration failed validation.
+        reason: TileGridConfigError,
+    },
+    /// Reports that a bug spawner layout was rejected and the previous one kept.
+    SpawnerConfigRejected {
+        /// Specific reason the layout failed validation.
+        reason: SpawnerConfigError,
+    },
     /// Reports that the player's gold balance changed.
     GoldChanged {
         /// Total gold owned after the adjustment.
@@ -1172,33 +2940,42 @@ pub enum Event {
         /// Version token for the active species table after the change.
         species_table_version: SpeciesTableVersion,
         /// Canonical pressure sampling configuration now active.
-        pressure: PressureConfig,
+        pressure: Box<PressureConfig>,
     },
     /// Reports that a deterministic pressure v2 wave has been generated.
     PressureWaveReady {
         /// Inputs describing the generated wave.
-        inputs: PressureWaveInputs,
+        inputs: Box<PressureWaveInputs>,
         /// Canonical spawn list describing the wave contents.
-        plan: PressureWavePlan,
+        plan: Box<PressureWavePlan>,
+    },
+    /// Reports that a supplied pressure v2 wave plan was rejected and left uncached.
+    PressureWaveRejected {
+        /// Inputs the plan was supplied for.
+        inputs: Box<PressureWaveInputs>,
+        /// Specific reason the plan failed validation.
+        reason: PressurePlanError,
     },
     /// Announces that a wave launched with resolved parameters.
     WaveStarted {
         /// Identifier assigned to the launched wave.
         wave: WaveId,
-        /// Difficulty selection applied to the launch.
-        difficulty: WaveDifficulty,
-        /// Effective difficulty applied for this wave, including Hard escalations.
-        effective_difficulty: DifficultyLevel,
-        /// Multiplier applied to gold rewards while the wave is active.
-        reward_multiplier: u32,
-        /// Scalar applied to pressure calculations for the wave contents.
-        pressure_scalar: u32,
-        /// Total pressure budget contained within the cached attack plan.
-        plan_pressure: Pressure,
-        /// Version of the species table used when generating the cached plan.
-        plan_species_table_version: SpeciesTableVersion,
-        /// Total number of bursts scheduled by the cached plan.
-        plan_burst_count: u32,
+        /// Parameters the wave launched with.
+        launch: Box<WaveLaunch>,
+    },
+    /// Announces that a planned burst of the active wave began spawning.
+    BurstStarted {
+        /// Species spawned by the burst.
+        species: SpeciesId,
+        /// Zero-based index of the burst among the species' bursts in the wave.
+        burst: u32,
+    },
+    /// Announces that the last planned spawn of a burst of the active wave came due.
+    BurstEnded {
+        /// Species spawned by the burst.
+        species: SpeciesId,
+        /// Zero-based index of the burst among the species' bursts in the wave.
+        burst: u32,
     },
     /// Reports that a Hard wave victory granted a permanent difficulty promotion.
     HardWinAchieved {
@@ -1225,6 +3002,16 @@ pub enum Event {
         /// Health assigned to the bug on spawn.
         health: Health,
     },
+    /// Confirms that a squad entered the maze as a formation, after its members'
+    /// `BugSpawned` events.
+    FormationSpawned {
+        /// Identifier allocated to the squad.
+        formation: FormationId,
+        /// Arrangement of the squad.
+        shape: FormationShape,
+        /// Squad members, leader first.
+        bugs: Vec<BugId>,
+    },
     /// Confirms that a tower was placed into the world.
     TowerPlaced {
         /// Identifier assigned to the tower by the world.
@@ -1257,6 +3044,29 @@ pub enum Event {
         /// Specific reason the removal failed.
         reason: RemovalError,
     },
+    /// Confirms that a tower switched to a different targeting policy.
+    TowerTargetingChanged {
+        /// Identifier of the tower whose targeting changed.
+        tower: TowerId,
+        /// Policy the tower now follows.
+        policy: TargetingPolicy,
+    },
+    /// Confirms that a tower was upgraded.
+    TowerUpgraded {
+        /// Identifier of the upgraded tower.
+        tower: TowerId,
+        /// Upgrades the tower carries after this one.
+        upgrades: u32,
+        /// Gold spent on the upgrade.
+        cost: Gold,
+    },
+    /// Reports that a tower upgrade request was rejected.
+    TowerUpgradeRejected {
+        /// Identifier of the tower targeted for the upgrade.
+        tower: TowerId,
+        /// Specific reason the upgrade failed.
+        reason: UpgradeError,
+    },
     /// Confirms that a projectile was fired at a target bug.
     ProjectileFired {
         /// Identifier of the spawned projectile.
@@ -1275,6 +3085,15 @@ pub enum Event {
         /// Damage applied to the bug.
         damage: Damage,
     },
+    /// Reports that a projectile reached a living bug but missed it.
+    ProjectileMissed {
+        /// Identifier of the projectile that missed.
+        projectile: ProjectileId,
+        /// Bug the projectile was fired at.
+        target: BugId,
+        /// Why the projectile missed.
+        cause: MissCause,
+    },
     /// Reports that a projectile expired before hitting a living bug.
     ProjectileExpired {
         /// Identifier of the projectile that expired.
@@ -1296,10 +3115,153 @@ pub enum Event {
         /// Remaining health after damage was applied.
         remaining: Health,
     },
+    /// Reports that a hit on a bug suffering a status effect triggered a combo. Emitted
+    /// before the hit's [`Event::BugDamaged`].
+    ComboTriggered {
+        /// Bug the combo hit.
+        bug: BugId,
+        /// Combo that triggered.
+        combo: Combo,
+        /// Damage the combo added to the hit.
+        bonus: Damage,
+    },
     /// Announces that a bug died because its health reached zero.
     BugDied {
         /// Identifier of the bug that died.
         bug: BugId,
+        /// Gold paid for the kill, including the wave's reward multiplier.
+        bounty: Gold,
+    },
+    /// Publishes the after-action report for the wave that was just resolved.
+    WaveReportReady {
+        /// Summary of the resolved wave.
+        report: Box<WaveReport>,
+    },
+    /// Publishes the machine-readable outcome record of a resolved wave.
+    WaveOutcomeRecorded {
+        /// Outcome record joining the wave's launch, composition and report.
+        outcome: Box<WaveOutcome>,
+    },
+    /// Announces the random event rolled after a wave resolved.
+    BetweenWaveEventRolled {
+        /// Event that took place.
+        event: BetweenWaveEvent,
+    },
+    /// Reports that the open merchant offer was answered or expired.
+    MerchantOfferClosed {
+        /// Whether the player accepted the discount.
+        accepted: bool,
+    },
+    /// Reports the contracts that will apply to the next wave launch.
+    WaveContractsChanged {
+        /// Accepted contracts in ascending order.
+        contracts: Vec<WaveContract>,
+    },
+    /// Reports the starting bonuses applied to a new game.
+    GameRulesConfigured {
+        /// Bonuses now in effect.
+        rules: GameRules,
+    },
+    /// Reports that the exit behaviour changed.
+    ExitBehaviorConfigured {
+        /// Behaviour now applied to bugs reaching the exit.
+        behavior: ExitBehavior,
+    },
+    /// Reports that the projectile accuracy rules changed.
+    AccuracyConfigured {
+        /// Rules now deciding when projectiles miss, if any.
+        model: Option<AccuracyModel>,
+    },
+    /// Reports that the tower heat rules were enabled or disabled.
+    TowerHeatConfigured {
+        /// Whether firing now builds heat.
+        enabled: bool,
+    },
+    /// Reports that the line-of-sight rules were enabled or disabled.
+    LineOfSightConfigured {
+        /// Whether obstacles now block shots.
+        enabled: bool,
+    },
+    /// Reports that the per-kind tower statistics were replaced; query them for the new values.
+    TowerContentConfigured,
+    /// Reports that the sandbox rule toggles changed.
+    SandboxConfigured {
+        /// Toggles now in effect.
+        rules: SandboxRules,
+    },
+    /// Reports that a sandbox session replaced the wave seed.
+    WaveSeedChanged {
+        /// Seed the following waves derive from.
+        seed: u64,
+    },
+    /// Reports the rotation applied to subsequent tower placements.
+    PlacementRotationChanged {
+        /// Rotation now in effect.
+        rotation: TowerRotation,
+    },
+    /// Reports that a boss crossed a health threshold and entered a new scripted phase.
+    BossPhaseChanged {
+        /// Identifier of the boss.
+        bug_id: BugId,
+        /// Phase the boss entered.
+        phase: BossPhase,
+    },
+    /// Reports that a repulsor pulse shoved a bug back along its incoming path.
+    BugPushedBack {
+        /// Identifier of the repulsor tower that emitted the pulse.
+        tower: TowerId,
+        /// Identifier of the bug that was pushed.
+        bug_id: BugId,
+        /// Cell occupied before the push.
+        from: CellCoord,
+        /// Cell occupied after the push.
+        to: CellCoord,
+    },
+    /// Reports that the bridge cells changed.
+    BridgesConfigured {
+        /// Cells now covered by a bridge deck, in row-major order.
+        cells: Vec<CellCoord>,
+    },
+    /// Reports that the void cells changed.
+    VoidCellsConfigured {
+        /// Cells that are now void, in row-major order.
+        cells: Vec<CellCoord>,
+    },
+    /// Reports that nests were enabled, disabled, or restored to full health.
+    NestsConfigured {
+        /// Health every nest starts with, or `None` when nests are disabled.
+        health: Option<Health>,
+    },
+    /// Reports that a tower shot hit a nest.
+    NestDamaged {
+        /// Tower that fired the shot.
+        tower: TowerId,
+        /// Spawner set of the nest that was hit.
+        nest: SpawnerSet,
+        /// Remaining health after damage was applied.
+        remaining: Health,
+    },
+    /// Announces that a nest was destroyed and its spawners closed for good.
+    NestDestroyed {
+        /// Spawner set of the destroyed nest.
+        nest: SpawnerSet,
+        /// Percentage of the full wave pressure later waves keep.
+        pressure_share: u32,
+    },
+    /// Reports that a shot at a nest was rejected by the world.
+    NestShotRejected {
+        /// Tower that attempted to fire.
+        tower: TowerId,
+        /// Spawner set of the intended nest.
+        nest: SpawnerSet,
+        /// Specific reason the request failed.
+        reason: ProjectileRejection,
+    },
+    /// Announces that the shield shared by a shielded wave's bugs was broken, so hits
+    /// reach the bugs' health for the rest of the wave.
+    WaveShieldBroken {
+        /// Wave whose shield was broken.
+        wave: WaveId,
     },
 }
 
@@ -1424,26 +3386,55 @@ impl Damage {
 }
 
 /// Amount of gold owned by the defending player.
+///
+/// Gold is backed by a `u64` so endless runs cannot overflow the economy; use
+/// [`Gold::to_u32_saturating`] at boundaries that still expect 32-bit amounts.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Gold(u32);
+pub struct Gold(u64);
 
 impl Gold {
     /// Canonical zero value representing a lack of gold.
     pub const ZERO: Self = Self(0);
 
+    /// Largest representable gold amount.
+    pub const MAX: Self = Self(u64::MAX);
+
     /// Creates a new gold value from the provided raw integer.
     #[must_use]
-    pub const fn new(value: u32) -> Self {
+    pub const fn new(value: u64) -> Self {
         Self(value)
     }
 
     /// Retrieves the underlying gold amount.
     #[must_use]
-    pub const fn get(&self) -> u32 {
+    pub const fn get(&self) -> u64 {
         self.0
     }
 
-    /// Adds another gold amount while saturating at `u32::MAX`.
+    /// Returns the gold amount clamped into the `u32` range.
+    ///
+    /// ```
+    /// use maze_defence_core::Gold;
+    ///
+    /// assert_eq!(Gold::new(12).to_u32_saturating(), 12);
+    /// assert_eq!(Gold::MAX.to_u32_saturating(), u32::MAX);
+    /// ```
+    #[must_use]
+    pub const fn to_u32_saturating(&self) -> u32 {
+        if self.0 > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            self.0 as u32
+        }
+    }
+
+    /// Multiplies the gold amount by `factor` while saturating at `u64::MAX`.
+    #[must_use]
+    pub const fn saturating_mul(self, factor: u64) -> Self {
+        Self(self.0.saturating_mul(factor))
+    }
+
+    /// Adds another gold amount while saturating at `u64::MAX`.
     #[must_use]
     pub const fn saturating_add(self, other: Self) -> Self {
         Self(self.0.saturating_add(other.0))
@@ -1493,24 +3484,77 @@ impl WaveId {
 }
 
 /// Deterministic seed derivation inputs associated with a wave.
+///
+/// Besides the base difficulty level the context records the effective level each
+/// [`WaveDifficulty`] would launch at, so adapters can derive the generator inputs of every
+/// choice up front and have both plans cached before the player commits to one.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct WaveSeedContext {
     global_seed: u64,
     wave: WaveId,
     difficulty_level: DifficultyLevel,
+    normal_level: DifficultyLevel,
+    hard_level: DifficultyLevel,
+    pressure_share: u32,
+    history: Option<WaveHistoryInputs>,
 }
 
 impl WaveSeedContext {
     /// Creates a new seed context for the provided wave.
+    ///
+    /// Both difficulty choices start out at `difficulty_level`; use
+    /// [`Self::with_effective_levels`] to record the levels they actually launch at.
     #[must_use]
     pub const fn new(global_seed: u64, wave: WaveId, difficulty_level: DifficultyLevel) -> Self {
         Self {
             global_seed,
             wave,
             difficulty_level,
+            normal_level: difficulty_level,
+            hard_level: difficulty_level,
+            pressure_share: full_pressure_share(),
+            history: None,
         }
     }
 
+    /// Records how the player fared against the previous wave.
+    #[must_use]
+    pub const fn with_history(mut self, history: Option<WaveHistoryInputs>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Returns how the player fared against the previous wave, if known.
+    #[must_use]
+    pub const fn history(&self) -> Option<WaveHistoryInputs> {
+        self.history
+    }
+
+    /// Records the percentage of the full wave pressure the next wave keeps.
+    #[must_use]
+    pub const fn with_pressure_share(mut self, percent: u32) -> Self {
+        self.pressure_share = percent;
+        self
+    }
+
+    /// Returns the percentage of the full wave pressure the next wave keeps.
+    #[must_use]
+    pub const fn pressure_share(&self) -> u32 {
+        self.pressure_share
+    }
+
+    /// Records the effective levels a normal and a hard launch of the wave would use.
+    #[must_use]
+    pub const fn with_effective_levels(
+        mut self,
+        normal: DifficultyLevel,
+        hard: DifficultyLevel,
+    ) -> Self {
+        self.normal_level = normal;
+        self.hard_level = hard;
+        self
+    }
+
     /// Returns the canonical global seed supplied by the world.
     #[must_use]
     pub const fn global_seed(&self) -> u64 {
@@ -1528,6 +3572,31 @@ impl WaveSeedContext {
     pub const fn difficulty_level(&self) -> DifficultyLevel {
         self.difficulty_level
     }
+
+    /// Returns the effective level a launch with `difficulty` would use.
+    #[must_use]
+    pub const fn effective_level(&self, difficulty: WaveDifficulty) -> DifficultyLevel {
+        match difficulty {
+            WaveDifficulty::Normal => self.normal_level,
+            WaveDifficulty::Hard => self.hard_level,
+        }
+    }
+
+    /// Builds the pressure generator inputs of a launch with `difficulty` on `level_id`.
+    ///
+    /// Choices launching at different effective levels yield distinct inputs, so their
+    /// plans occupy separate cache entries.
+    #[must_use]
+    pub fn inputs(&self, level_id: LevelId, difficulty: WaveDifficulty) -> PressureWaveInputs {
+        PressureWaveInputs::new(
+            self.global_seed,
+            level_id,
+            self.wave,
+            self.effective_level(difficulty),
+        )
+        .with_pressure_share(self.pressure_share)
+        .with_history(self.history)
+    }
 }
 
 /// Reasons the world may reject a projectile firing request.
@@ -1537,10 +3606,50 @@ pub enum ProjectileRejection {
     InvalidMode,
     /// The tower's cooldown has not yet elapsed.
     CooldownActive,
+    /// The tower overheated and stalls until its heat has fully dissipated.
+    Overheated,
     /// The targeted tower either does not exist or was removed earlier.
     MissingTower,
     /// The intended bug target does not exist or already died.
     MissingTarget,
+    /// The targeted nest is the last open one and cannot be destroyed.
+    LastNest,
+    /// A wall or another tower stands between the tower and its target.
+    LineOfSightBlocked,
+}
+
+/// Rules deciding when a projectile misses the bug it was fired at.
+///
+/// A projectile misses when its target moved farther than the drift allowance between
+/// firing and impact, or when the target dodges according to its [`DodgeChance`]. Dodges
+/// are drawn deterministically from the world's wave seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AccuracyModel {
+    max_drift_cells: u32,
+}
+
+impl AccuracyModel {
+    /// Creates rules under which targets may move up to `max_drift_cells` cells while a
+    /// projectile is in flight and still be hit.
+    #[must_use]
+    pub const fn new(max_drift_cells: u32) -> Self {
+        Self { max_drift_cells }
+    }
+
+    /// Returns how many cells a target may move while a projectile is in flight.
+    #[must_use]
+    pub const fn max_drift_cells(&self) -> u32 {
+        self.max_drift_cells
+    }
+}
+
+/// Reasons a projectile may miss its target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MissCause {
+    /// The target moved beyond the drift allowance while the projectile was in flight.
+    Outran,
+    /// The target dodged the projectile.
+    Dodged,
 }
 
 /// Immutable representation of a projectile maintained by the world.
@@ -1613,6 +3722,35 @@ impl CellCoord {
     }
 }
 
+/// First obstruction met by a segment cast across the cell grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RaycastHit {
+    /// The segment reached its end without crossing a wall or tower.
+    Clear,
+    /// The segment entered a wall cell.
+    Wall {
+        /// First wall cell entered along the segment.
+        cell: CellCoord,
+    },
+    /// The segment entered a cell occupied by a tower.
+    Tower {
+        /// Tower occupying the cell.
+        tower: TowerId,
+        /// First cell of the tower entered along the segment.
+        cell: CellCoord,
+    },
+    /// The segment left the cell grid before reaching its end.
+    OutOfBounds,
+}
+
+impl RaycastHit {
+    /// Reports whether the segment reached its end unobstructed.
+    #[must_use]
+    pub const fn is_clear(&self) -> bool {
+        matches!(self, Self::Clear)
+    }
+}
+
 /// Coordinate anchored to the centre of a cell measured in half-cell units.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CellPointHalf {
@@ -1707,6 +3845,15 @@ impl CellRect {
     pub const fn size(&self) -> CellRectSize {
         self.size
     }
+
+    /// Reports whether `cell` lies inside the rectangle.
+    #[must_use]
+    pub const fn contains(&self, cell: CellCoord) -> bool {
+        cell.column() >= self.origin.column()
+            && cell.row() >= self.origin.row()
+            && cell.column() - self.origin.column() < self.size.width()
+            && cell.row() - self.origin.row() < self.size.height()
+    }
 }
 
 /// Size of a [`CellRect`] measured in whole cells.
@@ -1872,7 +4019,9 @@ pub struct BugSnapshot {
     /// consult when planning steps.
     ///
     /// ```
-    /// use maze_defence_core::{BugColor, BugId, BugSnapshot, CellCoord, Health};
+    /// use maze_defence_core::{
+    ///     BugColor, BugId, BugSnapshot, CellCoord, Gold, Health, PathingPersonality,
+    /// };
     ///
     /// let step_ms = 400;
     /// let mut snapshot = BugSnapshot {
@@ -1884,6 +4033,10 @@ pub struct BugSnapshot {
     ///     step_ms,
     ///     accum_ms: 0,
     ///     ready_for_step: false,
+    ///     pathing: PathingPersonality::Direct,
+    ///     bounty: Gold::new(1),
+    ///     boss_phase: None,
+    ///     status: None,
     /// };
     ///
     /// snapshot.accum_ms = snapshot.accum_ms.saturating_add(200);
@@ -1896,6 +4049,14 @@ pub struct BugSnapshot {
     /// assert_eq!(snapshot.accum_ms, snapshot.step_ms);
     /// ```
     pub ready_for_step: bool,
+    /// Route preference the movement system applies to the bug.
+    pub pathing: PathingPersonality,
+    /// Gold paid, before wave reward multipliers, when the bug is killed.
+    pub bounty: Gold,
+    /// Scripted phase of a boss bug; `None` for ordinary bugs.
+    pub boss_phase: Option<BossPhase>,
+    /// Status effect the bug suffers, if any.
+    pub status: Option<BugStatus>,
 }
 
 /// Read-only snapshot describing all bugs within the maze.
@@ -2139,6 +4300,61 @@ impl<'a> ReservationLedgerView<'a> {
     }
 }
 
+/// Spawner cells held back for a burst that is about to spawn or still spawning.
+///
+/// Nothing can be built on reserved cells, so towers placed mid-wave never bury the
+/// spawners a burst is about to use.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpawnReservation {
+    species: SpeciesId,
+    burst: u32,
+    cells: Vec<CellCoord>,
+    remaining: Duration,
+}
+
+impl SpawnReservation {
+    /// Creates a reservation of `cells` for `burst` of `species` lapsing after `remaining`.
+    #[must_use]
+    pub fn new(species: SpeciesId, burst: u32, cells: Vec<CellCoord>, remaining: Duration) -> Self {
+        Self {
+            species,
+            burst,
+            cells,
+            remaining,
+        }
+    }
+
+    /// Species whose burst holds the reservation.
+    #[must_use]
+    pub const fn species(&self) -> SpeciesId {
+        self.species
+    }
+
+    /// Zero-based index of the burst within its species.
+    #[must_use]
+    pub const fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Spawner cells the burst may use, in ascending order.
+    #[must_use]
+    pub fn cells(&self) -> &[CellCoord] {
+        &self.cells
+    }
+
+    /// Wave time left until the burst's last spawn releases the cells.
+    #[must_use]
+    pub const fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Reports whether any reserved cell lies inside `region`.
+    #[must_use]
+    pub fn overlaps(&self, region: CellRect) -> bool {
+        self.cells.iter().any(|cell| region.contains(*cell))
+    }
+}
+
 /// Read-only view into the dense occupancy grid.
 #[derive(Clone, Copy, Debug)]
 pub struct OccupancyView<'a> {
@@ -2231,6 +4447,55 @@ pub struct TowerSnapshot {
     pub kind: TowerKind,
     /// Region of cells occupied by the tower.
     pub region: CellRect,
+    /// Rotation applied to the tower's footprint.
+    pub rotation: TowerRotation,
+    /// Rule the tower picks its target by when no priority target is in range.
+    pub targeting: TargetingPolicy,
+    /// Upgrades bought for the tower, up to [`TowerStats::MAX_UPGRADES`].
+    pub upgrades: u32,
+}
+
+/// Rule a tower picks its target by among the bugs in its range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TargetingPolicy {
+    /// Shoots the nearest bug.
+    #[default]
+    Nearest,
+    /// Shoots the bug with the least health left, finishing off stragglers.
+    Weakest,
+    /// Shoots the bug with the most health left.
+    Strongest,
+}
+
+impl TargetingPolicy {
+    /// Every policy in the order the player cycles through them.
+    pub const ALL: [Self; 3] = [Self::Nearest, Self::Weakest, Self::Strongest];
+
+    /// Returns the policy following this one, wrapping back to the first.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Nearest => Self::Weakest,
+            Self::Weakest => Self::Strongest,
+            Self::Strongest => Self::Nearest,
+        }
+    }
+
+    /// Picks the bug to shoot from `candidates`, ordered nearest first with ties broken by
+    /// bug identifier; health ties go to the nearer bug.
+    #[must_use]
+    pub fn select(self, candidates: &[TargetCandidate]) -> Option<BugId> {
+        let chosen = match self {
+            Self::Nearest => candidates.first(),
+            Self::Weakest => candidates
+                .iter()
+                .min_by_key(|candidate| candidate.health.get()),
+            Self::Strongest => candidates
+                .iter()
+                .min_by_key(|candidate| std::cmp::Reverse(candidate.health.get())),
+        };
+        chosen.map(|candidate| candidate.bug)
+    }
 }
 
 /// Immutable representation of a tower's firing cooldown state.
@@ -2240,8 +4505,43 @@ pub struct TowerCooldownSnapshot {
     pub tower: TowerId,
     /// Kind of tower that owns the cooldown.
     pub kind: TowerKind,
-    /// Duration remaining before the tower may fire again.
+    /// Duration remaining before the tower may fire again, including any overheat stall.
     pub ready_in: Duration,
+    /// Heat gauge of the tower, present only while the heat rules are enabled.
+    pub heat: Option<TowerHeat>,
+}
+
+/// Heat built up by a tower while the heat rules are enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TowerHeat {
+    /// Heat currently held by the tower, rounded up to whole units.
+    pub heat: u32,
+    /// Heat at which the tower overheats.
+    pub capacity: u32,
+    /// Whether the tower is stalled until its heat has fully dissipated.
+    pub overheated: bool,
+}
+
+impl TowerHeat {
+    /// Fraction of the capacity currently filled, in the inclusive range `0.0..=1.0`.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        (self.heat as f32 / self.capacity as f32).min(1.0)
+    }
+}
+
+/// Describes how a tower kind builds and sheds heat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TowerHeatCurve {
+    /// Heat added by every shot or pulse.
+    pub heat_per_shot: u32,
+    /// Heat at which the tower overheats and stalls.
+    pub capacity: u32,
+    /// Heat shed every second.
+    pub dissipation_per_second: u32,
 }
 
 /// Read-only snapshot describing tower cooldown progress.
@@ -2417,14 +4717,290 @@ pub struct TowerTarget {
     pub bug_center_cells: CellPoint,
 }
 
+/// Target assignment describing a tower aiming at a nest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NestTarget {
+    /// Identifier of the tower emitting the targeting beam.
+    pub tower: TowerId,
+    /// Spawner set of the targeted nest.
+    pub nest: SpawnerSet,
+    /// Centre of the tower footprint expressed in cell coordinates.
+    pub tower_center_cells: CellPoint,
+    /// Centre of the nest cell the tower aims at, expressed in cell coordinates.
+    pub nest_center_cells: CellPoint,
+}
+
+/// Snapshot of an open nest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestSnapshot {
+    /// Spawner set guarded by the nest.
+    pub nest: SpawnerSet,
+    /// Spawner cells covered by the nest, in ascending order.
+    pub cells: Vec<CellCoord>,
+    /// Health the nest has left.
+    pub health: Health,
+    /// Health the nest started with.
+    pub max_health: Health,
+}
+
+/// Snapshot of the shield shared by the bugs of a shielded wave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaveShieldSnapshot {
+    /// Shield points left; zero once the shield is broken.
+    pub current: u32,
+    /// Shield points the wave started with.
+    pub capacity: u32,
+}
+
+impl WaveShieldSnapshot {
+    /// Reports whether the shield was broken and no longer protects the bugs.
+    #[must_use]
+    pub const fn is_broken(&self) -> bool {
+        self.current == 0
+    }
+}
+
+/// Internal counters of a world, shown by developer overlays.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorldDebugStats {
+    /// Simulation ticks advanced since the world was created.
+    pub tick_index: u64,
+    /// Bugs alive on the board.
+    pub bugs: u32,
+    /// Projectiles in flight.
+    pub projectiles: u32,
+    /// Towers standing on the board.
+    pub towers: u32,
+    /// Times the navigation field has been rebuilt; rising while nothing changes on the
+    /// board means the field is being invalidated needlessly.
+    pub navigation_generation: u64,
+    /// Wave plans held in the pressure wave cache.
+    pub wave_cache_entries: u32,
+    /// Bug slots allocated, including those not currently in use.
+    pub bug_capacity: u32,
+    /// Wave plan slots allocated in the pressure wave cache.
+    pub wave_cache_capacity: u32,
+}
+
+/// Kinds of damage a tower's projectiles deal, matched by [`ComboRule`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageType {
+    /// Plain kinetic damage.
+    Physical,
+    /// Cold damage that chills its target.
+    Frost,
+}
+
+/// Lingering conditions a hit can leave on a bug.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatusEffect {
+    /// The bug accumulates step time at [`StatusEffect::SLOWED_SPEED_PERCENT`] of its usual
+    /// rate.
+    Slowed,
+}
+
+impl StatusEffect {
+    /// Share of its usual speed a slowed bug keeps.
+    pub const SLOWED_SPEED_PERCENT: u32 = 50;
+
+    /// How long the effect lasts after the hit that applied it, in milliseconds; a new hit
+    /// restarts it.
+    #[must_use]
+    pub const fn duration_ms(self) -> u32 {
+        match self {
+            Self::Slowed => 2_000,
+        }
+    }
+}
+
+/// Status effect a bug currently suffers, with the time it has left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BugStatus {
+    /// Effect the bug suffers.
+    pub effect: StatusEffect,
+    /// Milliseconds left before the effect wears off.
+    pub remaining_ms: u32,
+}
+
+/// Lasting condition that changes how a bug takes hits or moves, listed when inspecting it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BugEffect {
+    /// The bug suffers a status effect.
+    Status(BugStatus),
+    /// The bug has its species' special ability; never [`BugArchetype::Basic`].
+    Archetype(BugArchetype),
+    /// The active wave's shield soaks hits aimed at the bug until it breaks.
+    Shielded(WaveShieldSnapshot),
+}
+
+/// Bonus effects triggered by hitting a bug that suffers a status effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Combo {
+    /// A physical hit shatters a slowed bug for bonus damage and ends the slow.
+    Shatter,
+}
+
+/// Pairs a status effect with the damage type that turns a hit into a combo.
+///
+/// A combo consumes the status effect and adds `bonus_percent` percent of the hit's damage
+/// before armour and shields apply.
+///
+/// ```
+/// use maze_defence_core::{Combo, ComboRule, DamageType, StatusEffect};
+///
+/// let rule = ComboRule::find(StatusEffect::Slowed, DamageType::Physical).expect("shatter");
+/// assert_eq!(rule.combo, Combo::Shatter);
+/// assert!(ComboRule::find(StatusEffect::Slowed, DamageType::Frost).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ComboRule {
+    /// Status effect the target must suffer.
+    pub status: StatusEffect,
+    /// Damage type the hit must deal.
+    pub damage_type: DamageType,
+    /// Combo the hit triggers.
+    pub combo: Combo,
+    /// Percentage of the hit's damage added as bonus damage.
+    pub bonus_percent: u32,
+}
+
+impl ComboRule {
+    /// Every combo rule, checked in order.
+    pub const ALL: [Self; 1] = [Self {
+        status: StatusEffect::Slowed,
+        damage_type: DamageType::Physical,
+        combo: Combo::Shatter,
+        bonus_percent: 100,
+    }];
+
+    /// Returns the rule a hit of `damage_type` on a bug suffering `status` triggers, if any.
+    #[must_use]
+    pub fn find(status: StatusEffect, damage_type: DamageType) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.status == status && rule.damage_type == damage_type)
+    }
+
+    /// Bonus damage the rule adds to a hit dealing `damage`.
+    #[must_use]
+    pub fn bonus(&self, damage: Damage) -> Damage {
+        let bonus = u64::from(damage.get()) * u64::from(self.bonus_percent) / 100;
+        Damage::new(u32::try_from(bonus).unwrap_or(u32::MAX))
+    }
+}
+
 /// Types of towers that can be constructed in the maze.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TowerKind {
     /// Basic tower with default attack parameters.
     Basic,
+    /// Wall-like 1×3 barrier that shapes the maze without attacking.
+    Barrier,
+    /// Pulse tower that shoves every bug in range back along its incoming path instead of
+    /// shooting.
+    Repulsor,
+    /// Tower whose frost projectiles leave bugs [`StatusEffect::Slowed`].
+    Frost,
 }
 
 impl TowerKind {
+    /// Every tower kind in build-menu order.
+    pub const ALL: [Self; 4] = [Self::Basic, Self::Barrier, Self::Repulsor, Self::Frost];
+
+    /// Reports whether towers of this kind target and shoot bugs.
+    #[must_use]
+    pub const fn fires(self) -> bool {
+        match self {
+            Self::Basic | Self::Repulsor | Self::Frost => true,
+            Self::Barrier => false,
+        }
+    }
+
+    /// Reports whether walls and other towers block this kind's shots while the
+    /// line-of-sight rules are enabled, so its attackable region is its range clipped to
+    /// what it can see rather than the full circle.
+    ///
+    /// Only projectiles are blocked; repulsor pulses still reach every bug in range.
+    #[must_use]
+    pub const fn requires_line_of_sight(self) -> bool {
+        match self {
+            Self::Basic | Self::Frost => true,
+            Self::Barrier | Self::Repulsor => false,
+        }
+    }
+
+    /// Kind of damage this tower's projectiles deal.
+    #[must_use]
+    pub const fn damage_type(self) -> DamageType {
+        match self {
+            Self::Frost => DamageType::Frost,
+            Self::Basic | Self::Barrier | Self::Repulsor => DamageType::Physical,
+        }
+    }
+
+    /// Status effect this tower's hits leave on the bugs they do not kill, if any.
+    #[must_use]
+    pub const fn inflicts(self) -> Option<StatusEffect> {
+        match self {
+            Self::Frost => Some(StatusEffect::Slowed),
+            Self::Basic | Self::Barrier | Self::Repulsor => None,
+        }
+    }
+
+    /// Heat curve applied to this tower kind while the heat rules are enabled.
+    ///
+    /// ```
+    /// use maze_defence_core::TowerKind;
+    ///
+    /// let curve = TowerKind::Basic.heat_curve();
+    /// assert_eq!(curve.capacity / curve.heat_per_shot, 4);
+    /// ```
+    #[must_use]
+    pub const fn heat_curve(self) -> TowerHeatCurve {
+        match self {
+            Self::Basic | Self::Frost => TowerHeatCurve {
+                heat_per_shot: 25,
+                capacity: 100,
+                dissipation_per_second: 20,
+            },
+            Self::Barrier => TowerHeatCurve {
+                heat_per_shot: 0,
+                capacity: 100,
+                dissipation_per_second: 100,
+            },
+            Self::Repulsor => TowerHeatCurve {
+                heat_per_shot: 50,
+                capacity: 100,
+                dissipation_per_second: 10,
+            },
+        }
+    }
+
+    /// Tiles a pulse from this tower pushes each bug back; zero for projectile towers.
+    #[must_use]
+    pub const fn push_back_tiles(self) -> u32 {
+        match self {
+            Self::Repulsor => 1,
+            Self::Basic | Self::Barrier | Self::Frost => 0,
+        }
+    }
+
+    /// Returns the unrotated footprint of the tower measured in cells.
+    ///
+    /// ```
+    /// use maze_defence_core::{TowerKind, TowerRotation};
+    ///
+    /// let barrier = TowerRotation::Deg90.apply(TowerKind::Barrier.footprint());
+    /// assert_eq!((barrier.width(), barrier.height()), (12, 4));
+    /// ```
+    #[must_use]
+    pub const fn footprint(self) -> CellRectSize {
+        match self {
+            Self::Basic | Self::Repulsor | Self::Frost => CellRectSize::new(4, 4),
+            Self::Barrier => CellRectSize::new(4, 12),
+        }
+    }
+
     /// Returns the tower's targeting range measured in tiles.
     ///
     /// `TowerKind::Basic` covers a radius of four tiles.
@@ -2432,6 +5008,9 @@ impl TowerKind {
     pub const fn range_in_tiles(self) -> f32 {
         match self {
             Self::Basic => 4.0,
+            Self::Barrier => 0.0,
+            Self::Repulsor => 2.0,
+            Self::Frost => 3.0,
         }
     }
 
@@ -2486,7 +5065,9 @@ impl TowerKind {
     #[must_use]
     pub const fn fire_cooldown_ms(self) -> u32 {
         match self {
-            Self::Basic => 1_000,
+            Self::Basic | Self::Barrier => 1_000,
+            Self::Frost => 1_500,
+            Self::Repulsor => 4_000,
         }
     }
 
@@ -2494,7 +5075,8 @@ impl TowerKind {
     #[must_use]
     pub const fn projectile_damage(self) -> Damage {
         match self {
-            Self::Basic => Damage::new(1),
+            Self::Basic | Self::Frost => Damage::new(1),
+            Self::Barrier | Self::Repulsor => Damage::new(0),
         }
     }
 
@@ -2502,7 +5084,7 @@ impl TowerKind {
     #[must_use]
     pub const fn projectile_travel_time_ms(self) -> u32 {
         match self {
-            Self::Basic => 1_000,
+            Self::Basic | Self::Barrier | Self::Repulsor | Self::Frost => 1_000,
         }
     }
 
@@ -2511,6 +5093,281 @@ impl TowerKind {
     pub const fn build_cost(self) -> Gold {
         match self {
             Self::Basic => Gold::new(10),
+            Self::Barrier => Gold::new(6),
+            Self::Repulsor => Gold::new(15),
+            Self::Frost => Gold::new(12),
+        }
+    }
+
+    /// Built-in statistics of this tower kind before any content overrides.
+    #[must_use]
+    pub const fn base_stats(self) -> TowerStats {
+        TowerStats {
+            build_cost: self.build_cost(),
+            fire_cooldown_ms: self.fire_cooldown_ms(),
+            projectile_damage: self.projectile_damage(),
+        }
+    }
+
+    const fn content_index(self) -> usize {
+        match self {
+            Self::Basic => 0,
+            Self::Barrier => 1,
+            Self::Repulsor => 2,
+            Self::Frost => 3,
+        }
+    }
+}
+
+/// Balance statistics of a tower kind that content files may override.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TowerStats {
+    /// Gold required to construct the tower; refunded along with any upgrade spending when
+    /// it is removed.
+    pub build_cost: Gold,
+    /// Cooldown between successive shots measured in milliseconds.
+    pub fire_cooldown_ms: u32,
+    /// Damage dealt by each projectile the tower fires.
+    pub projectile_damage: Damage,
+}
+
+impl TowerStats {
+    /// Upgrades a tower can carry on top of its base statistics.
+    pub const MAX_UPGRADES: u32 = 3;
+
+    /// Gold the upgrade taking a tower to `upgrades` upgrades costs: the build cost times
+    /// `upgrades`.
+    #[must_use]
+    pub const fn upgrade_cost(&self, upgrades: u32) -> Gold {
+        Gold::new(self.build_cost.get().saturating_mul(upgrades as u64))
+    }
+
+    /// Gold spent on a tower carrying `upgrades` upgrades, including its construction.
+    #[must_use]
+    pub const fn invested(&self, upgrades: u32) -> Gold {
+        let steps = upgrades as u64 * (upgrades as u64 + 1) / 2;
+        Gold::new(self.build_cost.get().saturating_mul(steps + 1))
+    }
+
+    /// Damage of each projectile fired by a tower carrying `upgrades` upgrades; every
+    /// upgrade adds the base damage once more.
+    #[must_use]
+    pub const fn upgraded_damage(&self, upgrades: u32) -> Damage {
+        Damage::new(
+            self.projectile_damage
+                .get()
+                .saturating_mul(upgrades.saturating_add(1)),
+        )
+    }
+}
+
+/// Tower statistics in effect for every tower kind.
+///
+/// ```
+/// use maze_defence_core::{Gold, TowerContent, TowerKind, TowerStats};
+///
+/// let cheap = TowerStats {
+///     build_cost: Gold::new(4),
+///     ..TowerKind::Basic.base_stats()
+/// };
+/// let content = TowerContent::default().with_stats(TowerKind::Basic, cheap);
+/// assert_eq!(content.stats(TowerKind::Basic).build_cost, Gold::new(4));
+/// assert_eq!(content.stats(TowerKind::Barrier), TowerKind::Barrier.base_stats());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TowerContent {
+    stats: [TowerStats; TowerKind::ALL.len()],
+}
+
+impl TowerContent {
+    /// Returns the statistics in effect for the provided tower kind.
+    #[must_use]
+    pub const fn stats(&self, kind: TowerKind) -> TowerStats {
+        self.stats[kind.content_index()]
+    }
+
+    /// Returns a copy of the content with the statistics of `kind` replaced.
+    #[must_use]
+    pub const fn with_stats(mut self, kind: TowerKind, stats: TowerStats) -> Self {
+        self.stats[kind.content_index()] = stats;
+        self
+    }
+}
+
+impl Default for TowerContent {
+    fn default() -> Self {
+        Self {
+            stats: TowerKind::ALL.map(TowerKind::base_stats),
+        }
+    }
+}
+
+/// Bug within a tower's range that the tower may aim at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetCandidate {
+    /// Identifier of the bug.
+    pub bug: BugId,
+    /// Cell the bug occupies.
+    pub cell: CellCoord,
+    /// Health the bug has left.
+    pub health: Health,
+    /// Squared distance between the tower and bug centres, measured in half cells.
+    pub distance_sq: u128,
+}
+
+/// Decisions a tower makes during combat, registered per [`TowerKind`] in [`TowerBehaviors`].
+///
+/// Every hook has a default matching a plain projectile tower, so a behavior only overrides
+/// what sets it apart. Hooks observe snapshots and emit commands; the world still validates
+/// every command they emit.
+///
+/// ```
+/// use maze_defence_core::{BugId, TargetCandidate, TowerBehavior, TowerBehaviors, TowerKind};
+/// use maze_defence_core::TowerSnapshot;
+///
+/// /// Finishes off the weakest bug in range.
+/// #[derive(Debug)]
+/// struct Executioner;
+///
+/// impl TowerBehavior for Executioner {
+///     fn select_target(
+///         &self,
+///         _tower: &TowerSnapshot,
+///         candidates: &[TargetCandidate],
+///         _priority: Option<BugId>,
+///     ) -> Option<BugId> {
+///         candidates.iter().min_by_key(|candidate| candidate.health.get()).map(|candidate| candidate.bug)
+///     }
+/// }
+///
+/// let behaviors = TowerBehaviors::default().with_behavior(TowerKind::Basic, Executioner);
+/// assert_eq!(format!("{:?}", behaviors.get(TowerKind::Basic)), "Executioner");
+/// ```
+pub trait TowerBehavior: fmt::Debug + Send + Sync {
+    /// Called once per combat pass for every tower of the kind, before any tower fires.
+    fn on_tick(&self, tower: &TowerCooldownSnapshot, out: &mut Vec<Command>) {
+        let _ = (tower, out);
+    }
+
+    /// Picks the bug `tower` aims at, or `None` to hold fire.
+    ///
+    /// `candidates` holds every bug within the tower's range, nearest first with ties broken
+    /// by bug identifier. `priority` is the player's marked target when it is among them.
+    fn select_target(
+        &self,
+        tower: &TowerSnapshot,
+        candidates: &[TargetCandidate],
+        priority: Option<BugId>,
+    ) -> Option<BugId> {
+        priority.or_else(|| tower.targeting.select(candidates))
+    }
+
+    /// Emits the commands a ready tower issues at its target.
+    fn on_fire(&self, target: &TowerTarget, out: &mut Vec<Command>) {
+        out.push(Command::FireProjectile {
+            tower: target.tower,
+            target: target.bug,
+        });
+    }
+}
+
+/// Built-in behavior of towers that shoot the bug their [`TargetingPolicy`] picks,
+/// preferring the marked target.
+///
+/// The world resolves the shot by kind, so Repulsor shots become pulses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProjectileBehavior;
+
+impl TowerBehavior for ProjectileBehavior {}
+
+/// Built-in behavior of towers that never aim or fire, such as barriers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InertBehavior;
+
+impl TowerBehavior for InertBehavior {
+    fn select_target(
+        &self,
+        _tower: &TowerSnapshot,
+        _candidates: &[TargetCandidate],
+        _priority: Option<BugId>,
+    ) -> Option<BugId> {
+        None
+    }
+
+    fn on_fire(&self, _target: &TowerTarget, _out: &mut Vec<Command>) {}
+}
+
+/// Tower behavior in effect for every tower kind.
+///
+/// The default registers [`ProjectileBehavior`] for every kind that [fires](TowerKind::fires)
+/// and [`InertBehavior`] for the rest.
+#[derive(Clone, Debug)]
+pub struct TowerBehaviors {
+    behaviors: [Arc<dyn TowerBehavior>; TowerKind::ALL.len()],
+}
+
+impl TowerBehaviors {
+    /// Returns the behavior registered for the provided tower kind.
+    #[must_use]
+    pub fn get(&self, kind: TowerKind) -> &dyn TowerBehavior {
+        self.behaviors[kind.content_index()].as_ref()
+    }
+
+    /// Returns a copy of the registry with `behavior` registered for `kind`.
+    #[must_use]
+    pub fn with_behavior(
+        mut self,
+        kind: TowerKind,
+        behavior: impl TowerBehavior + 'static,
+    ) -> Self {
+        self.behaviors[kind.content_index()] = Arc::new(behavior);
+        self
+    }
+}
+
+impl Default for TowerBehaviors {
+    fn default() -> Self {
+        Self {
+            behaviors: TowerKind::ALL.map(|kind| -> Arc<dyn TowerBehavior> {
+                if kind.fires() {
+                    Arc::new(ProjectileBehavior)
+                } else {
+                    Arc::new(InertBehavior)
+                }
+            }),
+        }
+    }
+}
+
+/// Orientation applied to a tower footprint when it is placed.
+///
+/// Footprints are rectangles, so a quarter turn covers every distinct orientation and
+/// rotating twice restores the original footprint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TowerRotation {
+    /// Footprint used as declared by the tower kind.
+    #[default]
+    Deg0,
+    /// Footprint turned by 90°, swapping its width and height.
+    Deg90,
+}
+
+impl TowerRotation {
+    /// Returns the orientation reached by turning a further 90°.
+    #[must_use]
+    pub const fn rotated(self) -> Self {
+        match self {
+            Self::Deg0 => Self::Deg90,
+            Self::Deg90 => Self::Deg0,
+        }
+    }
+
+    /// Applies the rotation to an unrotated footprint.
+    #[must_use]
+    pub const fn apply(self, footprint: CellRectSize) -> CellRectSize {
+        match self {
+            Self::Deg0 => footprint,
+            Self::Deg90 => CellRectSize::new(footprint.height(), footprint.width()),
         }
     }
 }
@@ -2526,10 +5383,29 @@ pub enum PlacementError {
     Misaligned,
     /// The requested footprint overlaps an occupied cell.
     Occupied,
+    /// The requested footprint covers a void cell, where nothing can be built.
+    Unbuildable,
     /// The placement would block all paths between the exit and bug spawners.
     PathBlocked,
     /// The world cannot afford the tower's construction cost.
     InsufficientFunds,
+    /// The requested footprint covers spawner cells reserved for an imminent burst.
+    SpawnReserved,
+}
+
+/// Reasons a tower upgrade request may be rejected by the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UpgradeError {
+    /// The simulation is not in builder mode, so upgrades are disabled.
+    InvalidMode,
+    /// No tower with the provided identifier exists.
+    MissingTower,
+    /// The tower's projectiles deal no damage, so an upgrade would change nothing.
+    NotUpgradable,
+    /// The tower already carries [`TowerStats::MAX_UPGRADES`] upgrades.
+    FullyUpgraded,
+    /// The world cannot afford the upgrade.
+    InsufficientFunds,
 }
 
 /// Reasons a tower removal request may be rejected by the world.
@@ -2541,6 +5417,54 @@ pub enum RemovalError {
     MissingTower,
 }
 
+/// Reasons a tile grid configuration may be rejected by the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TileGridConfigError {
+    /// The grid has no tile columns.
+    ZeroColumns,
+    /// The grid has no tile rows.
+    ZeroRows,
+    /// The tile length is not a finite positive number.
+    InvalidTileLength,
+    /// Tiles are not subdivided into any cells.
+    ZeroCellsPerTile,
+    /// Tiles are subdivided into more than [`MAX_CELLS_PER_TILE`] cells per edge.
+    CellsPerTileTooLarge(u32),
+    /// The grid would contain more than [`MAX_GRID_CELLS`] cells.
+    TooManyCells(u64),
+}
+
+/// Reasons a bug spawner layout may be rejected by the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpawnerConfigError {
+    /// The layout resolved to no spawner cells at all.
+    Empty,
+    /// The layout referenced a spawn patch the world does not know.
+    UnknownPatch(SpawnPatchId),
+    /// The layout placed a spawner outside the grid or inside the exit rows.
+    OutOfBounds(CellCoord),
+    /// The spawner cannot reach any exit cell.
+    Unreachable(CellCoord),
+}
+
+/// Reasons a pressure wave plan may be rejected before the world caches it.
+///
+/// Every variant carries the index of the first offending entry in the plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PressurePlanError {
+    /// The spawn is scheduled earlier than the spawn listed before it.
+    UnsortedSpawn(u32),
+    /// The spawn names a species that is neither in the world's species table nor described
+    /// by one of the plan's prototypes.
+    UnknownSpecies(u32),
+    /// The spawn would enter the maze without hit points.
+    ZeroHealthSpawn(u32),
+    /// The spawn's speed multiplier is not a finite positive number.
+    InvalidSpeed(u32),
+    /// The species prototype would replace a species with one that has no hit points.
+    ZeroHealthPrototype(u32),
+}
+
 /// Canonical representation of "The Goal" for a bug.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Goal {
@@ -2584,8 +5508,9 @@ mod tests {
     use std::num::NonZeroU32;
 
     use super::{
-        CellCoord, CellRect, CellRectSize, Damage, Gold, Health, NavigationFieldView,
-        PlacementError, ProjectileId, ProjectileRejection, RemovalError, TowerId, TowerKind,
+        BugColor, CellCoord, CellRect, CellRectSize, Damage, DifficultyLadder, DifficultyLevel,
+        Gold, Health, NavigationFieldView, PlacementError, Pressure, ProjectileId,
+        ProjectileRejection, RemovalError, SpeciesPrototype, TowerId, TowerKind,
         CONGESTION_LOOKAHEAD, CONGESTION_WEIGHT, DETOUR_RADIUS,
     };
     use serde::{de::DeserializeOwned, Serialize};
@@ -2598,6 +5523,25 @@ mod tests {
         assert_eq!(destination.manhattan_distance(origin), 5);
     }
 
+    #[test]
+    fn speed_multipliers_scale_the_step_cadence_within_bounds() {
+        let step = |base: u32, speed_mult: f32| {
+            let base = NonZeroU32::new(base).expect("non-zero base");
+            SpeciesPrototype::new(BugColor::from_rgb(1, 2, 3), Health::new(1), base)
+                .step_ms_at(speed_mult)
+                .get()
+        };
+        assert_eq!(step(400, 1.0), 400);
+        assert_eq!(step(400, 2.0), 200);
+        assert_eq!(step(400, 0.5), 800);
+        assert_eq!(step(400, 0.01), 1_600, "slowest multiplier clamps");
+        assert_eq!(step(400, 40.0), 100, "fastest multiplier clamps");
+        assert_eq!(step(40, 4.0), 16, "cadence floor");
+        assert_eq!(step(8, 4.0), 8, "fast prototypes keep their cadence");
+        assert_eq!(step(400, f32::NAN), 400);
+        assert_eq!(step(400, -1.0), 400);
+    }
+
     fn assert_round_trip<T>(value: &T)
     where
         T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
@@ -2651,11 +5595,54 @@ mod tests {
         assert_round_trip(&ProjectileRejection::CooldownActive);
     }
 
+    #[test]
+    fn widened_economy_values_survive_late_game_magnitudes() {
+        let late_game = u64::from(u32::MAX) * 4;
+        assert_round_trip(&Gold::new(late_game));
+        assert_round_trip(&Pressure::new(late_game));
+        assert_eq!(
+            Pressure::new(u64::from(u32::MAX)).saturating_mul(4),
+            Pressure::new(late_game)
+        );
+        assert_eq!(Pressure::new(u64::MAX).saturating_mul(2).get(), u64::MAX);
+        assert_eq!(Gold::new(late_game).saturating_mul(u64::MAX), Gold::MAX);
+        assert_eq!(Gold::new(late_game).to_u32_saturating(), u32::MAX);
+    }
+
+    #[test]
+    fn difficulty_ladder_clamps_and_formats_fractional_levels() {
+        let ladder = DifficultyLadder::from_level(DifficultyLevel::new(3));
+        assert_eq!(ladder.to_string(), "3");
+        assert_eq!(ladder.pressure_scalar(), 4);
+
+        let eased = ladder.scaled_percent(75);
+        assert_eq!(eased.milli(), 2_250);
+        assert_eq!(eased.level(), DifficultyLevel::new(2));
+        assert_eq!(eased.fraction_milli(), 250);
+        assert_eq!(eased.to_string(), "2.25");
+        assert_eq!(eased.pressure_scalar(), 3);
+        assert_eq!(DifficultyLadder::from_milli(2_500).pressure_scalar(), 4);
+
+        assert_eq!(eased.saturating_sub_levels(5), DifficultyLadder::default());
+        assert_eq!(
+            DifficultyLadder::MAX.saturating_add_levels(1),
+            DifficultyLadder::MAX
+        );
+        assert_eq!(
+            DifficultyLadder::from_milli(u64::MAX).level(),
+            DifficultyLevel::new(u32::MAX)
+        );
+        assert_eq!(DifficultyLadder::MAX.pressure_scalar(), u32::MAX);
+        assert_round_trip(&eased);
+    }
+
     #[test]
     fn placement_error_round_trips_through_bincode() {
         assert_round_trip(&PlacementError::Occupied);
+        assert_round_trip(&PlacementError::Unbuildable);
         assert_round_trip(&PlacementError::PathBlocked);
         assert_round_trip(&PlacementError::InsufficientFunds);
+        assert_round_trip(&PlacementError::SpawnReserved);
     }
 
     #[test]
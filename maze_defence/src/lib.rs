@@ -0,0 +1,61 @@
+#![deny(
+    unsafe_code,
+    missing_docs,
+    dead_code,
+    unused_results,
+    non_snake_case,
+    unreachable_pub
+)]
+
+//! Single-dependency facade over the Maze Defence workspace.
+//!
+//! External experiments and bots depend on this crate instead of naming every workspace
+//! crate by path. Each layer is re-exported under its own module and the most common
+//! types are collected in [`prelude`]. Without features the facade is headless; enable
+//! `macroquad` to pull in the windowed rendering backend.
+
+pub use maze_defence_core as core;
+pub use maze_defence_rendering as rendering;
+pub use maze_defence_world as world;
+
+/// Windowed rendering backend built on Macroquad.
+#[cfg(feature = "macroquad")]
+pub use maze_defence_rendering_macroquad as macroquad;
+
+/// Pure systems that consume events and snapshots and emit commands.
+pub mod systems {
+    pub use maze_defence_pressure_v2 as pressure_v2;
+    pub use maze_defence_system_analytics as analytics;
+    pub use maze_defence_system_autoplayer as autoplayer;
+    pub use maze_defence_system_bootstrap as bootstrap;
+    pub use maze_defence_system_builder as builder;
+    pub use maze_defence_system_movement as movement;
+    pub use maze_defence_system_spawning as spawning;
+    pub use maze_defence_system_tower_combat as tower_combat;
+    pub use maze_defence_system_tower_targeting as tower_targeting;
+}
+
+/// Types needed to drive a simulation: message contracts, the world entry points, every
+/// system and the rendering contracts adapters implement.
+pub mod prelude {
+    pub use maze_defence_core::{
+        BugId, CellCoord, Command, DifficultyLevel, Event, Gold, Health, LevelId, PlayMode,
+        PressureWaveInputs, PressureWavePlan, SpeciesId, TileCoord, TowerBehavior, TowerBehaviors,
+        TowerId, TowerKind, WaveDifficulty, WaveId,
+    };
+    pub use maze_defence_pressure_v2::{PressureTuning, PressureV2};
+    pub use maze_defence_rendering::{
+        FrameInput, FrameInputSource, Presentation, RenderingBackend, Scene, ScriptedFrameInput,
+    };
+    #[cfg(feature = "macroquad")]
+    pub use maze_defence_rendering_macroquad::MacroquadBackend;
+    pub use maze_defence_system_analytics::Analytics;
+    pub use maze_defence_system_autoplayer::Autoplayer;
+    pub use maze_defence_system_bootstrap::Bootstrap;
+    pub use maze_defence_system_builder::Builder;
+    pub use maze_defence_system_movement::Movement;
+    pub use maze_defence_system_spawning::Spawning;
+    pub use maze_defence_system_tower_combat::TowerCombat;
+    pub use maze_defence_system_tower_targeting::TowerTargeting;
+    pub use maze_defence_world::{apply, inspect, query, World};
+}
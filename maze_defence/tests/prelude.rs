@@ -0,0 +1,38 @@
+use maze_defence::prelude::*;
+
+#[test]
+fn prelude_drives_a_world_without_other_dependencies() {
+    let mut world = World::new();
+    let mut events = Vec::new();
+    apply(
+        &mut world,
+        Command::ConfigureTileGrid {
+            columns: TileCoord::new(4),
+            rows: TileCoord::new(4),
+            tile_length: 1.0,
+            cells_per_tile: 2,
+        },
+        &mut events,
+    );
+    apply(
+        &mut world,
+        Command::SetPlayMode {
+            mode: PlayMode::Builder,
+        },
+        &mut events,
+    );
+    apply(
+        &mut world,
+        Command::PlaceTower {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(1, 1),
+        },
+        &mut events,
+    );
+
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, Event::TowerPlaced { .. })));
+    assert_eq!(query::towers(&world).iter().count(), 1);
+    assert!(inspect::grid(&world).contains('T'));
+}
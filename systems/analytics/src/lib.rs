@@ -11,14 +11,18 @@
 
 use std::collections::VecDeque;
 
-use maze_defence_core::{CellCoord, Command, Event, StatsReport};
+use maze_defence_core::{CellCoord, Command, Event, StatsReport, WaveId, WaveLaunch};
 
 mod metrics;
+mod outcomes;
 
 pub use metrics::{
     select_shortest_navigation_path, total_tower_dps, tower_count, tower_coverage_mean_bps,
     tower_firing_completion_percent_bps,
 };
+pub use outcomes::WaveLaunchSnapshot;
+
+use outcomes::PendingOutcome;
 
 /// Pure analytics system that queues recompute requests and emits published reports.
 #[derive(Debug, Default)]
@@ -27,6 +31,7 @@ pub struct Analytics {
     pending_requests: VecDeque<RecomputeRequest>,
     scratch_path: Vec<CellCoord>,
     scratch_frontier: VecDeque<CellCoord>,
+    pending_outcome: Option<PendingOutcome>,
 }
 
 impl Analytics {
@@ -58,9 +63,8 @@ impl Analytics {
         F: FnMut(&mut AnalyticsScratch<'_>) -> Option<StatsReport>,
     {
         for event in events {
-            match event {
-                Event::MazeLayoutChanged => self.enqueue_request(RecomputeRequest::LayoutChanged),
-                _ => {}
+            if matches!(event, Event::MazeLayoutChanged) {
+                self.enqueue_request(RecomputeRequest::LayoutChanged);
             }
         }
 
@@ -83,6 +87,42 @@ impl Analytics {
         }
     }
 
+    /// Joins launched waves with their reports, publishing one `WaveOutcome` per wave.
+    ///
+    /// The `launched` closure is invoked for every `WaveStarted` event and describes the
+    /// world as the wave launched; returning `None` skips the wave. A report is only turned
+    /// into an outcome when it resolves the wave that launched last.
+    pub fn record_wave_outcomes<'a, F>(
+        &mut self,
+        events: &[Event],
+        mut launched: F,
+        out: &mut Vec<Event>,
+    ) where
+        F: FnMut(WaveId, &WaveLaunch) -> Option<WaveLaunchSnapshot<'a>>,
+    {
+        for event in events {
+            match event {
+                Event::WaveStarted { wave, launch } => {
+                    self.pending_outcome = launched(*wave, launch)
+                        .map(|snapshot| PendingOutcome::new(launch.difficulty, snapshot));
+                }
+                Event::WaveReportReady { report }
+                    if self
+                        .pending_outcome
+                        .as_ref()
+                        .is_some_and(|pending| pending.wave() == report.wave()) =>
+                {
+                    if let Some(pending) = self.pending_outcome.take() {
+                        out.push(Event::WaveOutcomeRecorded {
+                            outcome: Box::new(pending.complete(report)),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn enqueue_request(&mut self, request: RecomputeRequest) {
         match request {
             RecomputeRequest::LayoutChanged => {
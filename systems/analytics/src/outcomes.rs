@@ -0,0 +1,96 @@
+//! Joins wave launches with their reports into machine-readable outcome records.
+
+use maze_defence_core::{
+    PressureWaveInputs, PressureWavePlan, SpeciesSpawns, TowerKind, TowerKindCount, WaveDifficulty,
+    WaveId, WaveOutcome, WaveReport,
+};
+
+/// World state captured when a wave launches, described by the caller.
+#[derive(Clone, Debug)]
+pub struct WaveLaunchSnapshot<'a> {
+    /// Generator inputs the launched wave's plan was cached under.
+    pub inputs: PressureWaveInputs,
+    /// Plan the wave spawns.
+    pub plan: &'a PressureWavePlan,
+    /// Kind of every tower standing at launch.
+    pub towers: Vec<TowerKind>,
+}
+
+/// Launch half of an outcome record, waiting for the wave's report.
+#[derive(Clone, Debug)]
+pub(crate) struct PendingOutcome {
+    inputs: PressureWaveInputs,
+    difficulty: WaveDifficulty,
+    composition: Vec<SpeciesSpawns>,
+    towers: Vec<TowerKindCount>,
+}
+
+impl PendingOutcome {
+    pub(crate) fn new(difficulty: WaveDifficulty, snapshot: WaveLaunchSnapshot<'_>) -> Self {
+        Self {
+            composition: composition(snapshot.plan),
+            towers: tower_counts(&snapshot.towers),
+            inputs: snapshot.inputs,
+            difficulty,
+        }
+    }
+
+    pub(crate) fn wave(&self) -> WaveId {
+        self.inputs.wave()
+    }
+
+    pub(crate) fn complete(self, report: &WaveReport) -> WaveOutcome {
+        WaveOutcome {
+            wave: self.inputs.wave(),
+            level: self.inputs.level_id(),
+            game_seed: self.inputs.game_seed(),
+            difficulty: self.difficulty,
+            difficulty_level: self.inputs.difficulty(),
+            outcome: report.outcome(),
+            duration_ms: u64::try_from(report.duration().as_millis()).unwrap_or(u64::MAX),
+            leaks: report.leaks(),
+            gold_earned: report.gold_earned(),
+            composition: self.composition,
+            towers: self.towers,
+            species_combat: report.species_combat().to_vec(),
+        }
+    }
+}
+
+fn composition(plan: &PressureWavePlan) -> Vec<SpeciesSpawns> {
+    let mut composition: Vec<SpeciesSpawns> = Vec::new();
+    for spawn in plan.spawns() {
+        let index =
+            match composition.binary_search_by_key(&spawn.species_id(), |entry| entry.species) {
+                Ok(index) => index,
+                Err(index) => {
+                    composition.insert(
+                        index,
+                        SpeciesSpawns {
+                            species: spawn.species_id(),
+                            count: 0,
+                            total_hp: 0,
+                        },
+                    );
+                    index
+                }
+            };
+        let entry = &mut composition[index];
+        entry.count = entry.count.saturating_add(1);
+        entry.total_hp = entry.total_hp.saturating_add(u64::from(spawn.hp()));
+    }
+    composition
+}
+
+fn tower_counts(towers: &[TowerKind]) -> Vec<TowerKindCount> {
+    TowerKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let count = towers.iter().filter(|tower| **tower == kind).count();
+            (count > 0).then(|| TowerKindCount {
+                kind,
+                count: u32::try_from(count).unwrap_or(u32::MAX),
+            })
+        })
+        .collect()
+}
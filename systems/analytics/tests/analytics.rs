@@ -1,7 +1,12 @@
 use std::time::Duration;
 
-use maze_defence_core::{CellCoord, Command, Event, StatsReport};
-use maze_defence_system_analytics::{Analytics, AnalyticsScratch};
+use maze_defence_core::{
+    CellCoord, Command, DifficultyLadder, DifficultyLevel, Event, Gold, LevelId, Pressure,
+    PressureSpawnRecord, PressureWaveInputs, PressureWavePlan, RoundOutcome, SpeciesCombat,
+    SpeciesSpawns, SpeciesTableVersion, StatsReport, TowerKind, TowerKindCount, WaveDifficulty,
+    WaveId, WaveLaunch, WaveOutcome, WaveReport,
+};
+use maze_defence_system_analytics::{Analytics, AnalyticsScratch, WaveLaunchSnapshot};
 
 fn sample_report(seed: u32) -> StatsReport {
     StatsReport::new(seed, seed + 1, seed + 2, seed + 3, seed + 4)
@@ -105,3 +110,116 @@ fn layout_and_manual_requests_coalesce_per_call() {
     assert!(emitted.is_empty());
     assert_eq!(analytics.last_report(), Some(&sample_report(60)));
 }
+
+#[test]
+fn wave_outcomes_join_the_launch_with_the_report() {
+    let mut analytics = Analytics::new();
+    let mut emitted = Vec::new();
+    let inputs =
+        PressureWaveInputs::new(7, LevelId::new(2), WaveId::new(3), DifficultyLevel::new(4));
+    let plan = PressureWavePlan::new(
+        vec![
+            PressureSpawnRecord::new(0, 10, 1.0, 5),
+            PressureSpawnRecord::new(100, 12, 1.0, 1),
+            PressureSpawnRecord::new(200, 14, 1.0, 5),
+        ],
+        Vec::new(),
+    );
+    let launch = WaveLaunch {
+        difficulty: WaveDifficulty::Hard,
+        effective_difficulty: DifficultyLadder::from_level(DifficultyLevel::new(4)),
+        reward_multiplier: 2,
+        pressure_scalar: 1,
+        pressure_share: 100,
+        history: None,
+        plan_pressure: Pressure::new(36),
+        plan_species_table_version: SpeciesTableVersion::new(1),
+        plan_burst_count: 2,
+        contracts: Vec::new(),
+    };
+    let combat = SpeciesCombat {
+        species: 5,
+        bugs: 2,
+        killed: 1,
+        average_lifetime_ms: 3_000,
+        average_cells_travelled: 12,
+        damage_absorbed: 24,
+        damage_per_second: 4,
+    };
+    let report = |wave| Event::WaveReportReady {
+        report: Box::new(
+            WaveReport::new(
+                WaveId::new(wave),
+                RoundOutcome::Win,
+                Duration::from_millis(4_250),
+                1,
+                Gold::new(30),
+                Vec::new(),
+            )
+            .with_species_combat(vec![combat]),
+        ),
+    };
+
+    analytics.record_wave_outcomes(
+        &[Event::WaveStarted {
+            wave: WaveId::new(3),
+            launch: Box::new(launch),
+        }],
+        |wave, launch| {
+            assert_eq!(wave, WaveId::new(3));
+            assert_eq!(launch.difficulty, WaveDifficulty::Hard);
+            Some(WaveLaunchSnapshot {
+                inputs: inputs.clone(),
+                plan: &plan,
+                towers: vec![TowerKind::Barrier, TowerKind::Basic, TowerKind::Barrier],
+            })
+        },
+        &mut emitted,
+    );
+    assert!(emitted.is_empty(), "outcomes wait for the wave's report");
+
+    analytics.record_wave_outcomes(&[report(2)], |_, _| None, &mut emitted);
+    assert!(emitted.is_empty(), "reports of other waves are ignored");
+
+    analytics.record_wave_outcomes(&[report(3), report(3)], |_, _| None, &mut emitted);
+    assert_eq!(
+        emitted,
+        vec![Event::WaveOutcomeRecorded {
+            outcome: Box::new(WaveOutcome {
+                wave: WaveId::new(3),
+                level: LevelId::new(2),
+                game_seed: 7,
+                difficulty: WaveDifficulty::Hard,
+                difficulty_level: DifficultyLevel::new(4),
+                outcome: RoundOutcome::Win,
+                duration_ms: 4_250,
+                leaks: 1,
+                gold_earned: Gold::new(30),
+                composition: vec![
+                    SpeciesSpawns {
+                        species: 1,
+                        count: 1,
+                        total_hp: 12,
+                    },
+                    SpeciesSpawns {
+                        species: 5,
+                        count: 2,
+                        total_hp: 24,
+                    },
+                ],
+                towers: vec![
+                    TowerKindCount {
+                        kind: TowerKind::Basic,
+                        count: 1,
+                    },
+                    TowerKindCount {
+                        kind: TowerKind::Barrier,
+                        count: 2,
+                    },
+                ],
+                species_combat: vec![combat],
+            }),
+        }],
+        "one record per wave"
+    );
+}
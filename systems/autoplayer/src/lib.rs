@@ -0,0 +1,352 @@
+#![deny(
+    unsafe_code,
+    missing_docs,
+    dead_code,
+    unused_results,
+    non_snake_case,
+    unreachable_pub
+)]
+
+//! Heuristic bot that builds a serpentine maze and buys towers between waves.
+//!
+//! The autoplayer stands in for a player during balance runs: between waves it switches to
+//! builder mode and spends its gold on whichever purchase adds the most damage per second
+//! per gold, either a new tower in the next slot of a serpentine layout or an upgrade of a
+//! tower it already built. Ties go to new towers, which lengthen the maze. It returns to
+//! attack mode once the best purchase is out of reach. Towers keep their default targeting
+//! policy. Adapters launch the next wave whenever [`Autoplayer::ready_to_launch`] reports
+//! that the bot finished building, and read the outcome of every resolved wave from
+//! [`Autoplayer::outcomes`] to estimate win rates.
+
+use maze_defence_core::{
+    CellCoord, CellRect, CellRectSize, Command, Event, Gold, PlacementError, PlayMode,
+    RoundOutcome, TowerContent, TowerId, TowerKind, TowerStats, UpgradeError,
+};
+
+/// Build state of a single maze slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SlotState {
+    /// Nothing has been built in the slot yet.
+    Open,
+    /// A tower occupies the slot.
+    Built,
+    /// The world rejected a tower in the slot, for example because it would block the path.
+    Blocked,
+}
+
+/// Tower the bot built, with the upgrades it carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BuiltTower {
+    id: TowerId,
+    kind: TowerKind,
+    upgrades: u32,
+}
+
+/// Way of spending gold between waves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Purchase {
+    /// Builds a tower of `kind` in the slot with the provided index.
+    Build { slot: usize, kind: TowerKind },
+    /// Adds the next upgrade to a tower the bot built.
+    Upgrade { tower: TowerId },
+}
+
+/// Pure system that turns world events into the builder decisions of a scripted player.
+#[derive(Clone, Debug)]
+pub struct Autoplayer {
+    slots: Vec<(CellCoord, SlotState)>,
+    slot_size: CellRectSize,
+    towers: Vec<BuiltTower>,
+    pending: Option<Purchase>,
+    wave_active: bool,
+    out_of_funds: bool,
+    outcomes: Vec<RoundOutcome>,
+}
+
+impl Autoplayer {
+    /// Creates a bot that builds within `area`, the playable cells of the maze.
+    ///
+    /// `alignment` is the stride tower origins must respect relative to the area's origin;
+    /// slots are spaced by the tower footprint rounded up to that stride.
+    #[must_use]
+    pub fn new(area: CellRect, alignment: u32) -> Self {
+        let footprint = TowerKind::Basic.footprint();
+        let slot_size = CellRectSize::new(
+            round_up(footprint.width(), alignment),
+            round_up(footprint.height(), alignment),
+        );
+        Self {
+            slots: serpentine_slots(area, slot_size)
+                .into_iter()
+                .map(|origin| (origin, SlotState::Open))
+                .collect(),
+            slot_size,
+            towers: Vec::new(),
+            pending: None,
+            wave_active: false,
+            out_of_funds: false,
+            outcomes: Vec::new(),
+        }
+    }
+
+    /// Consumes world events and the current economy to emit builder commands.
+    ///
+    /// At most one purchase is in flight at a time; the next one is emitted once the
+    /// world confirmed or rejected the previous one.
+    pub fn handle(
+        &mut self,
+        events: &[Event],
+        play_mode: PlayMode,
+        gold: Gold,
+        content: &TowerContent,
+        out: &mut Vec<Command>,
+    ) {
+        for event in events {
+            self.observe(event);
+        }
+        if self.wave_active || self.pending.is_some() {
+            return;
+        }
+
+        let purchase = self.next_purchase(gold, content);
+        match (play_mode, purchase) {
+            (PlayMode::Attack, Some(_)) => out.push(Command::SetPlayMode {
+                mode: PlayMode::Builder,
+            }),
+            (PlayMode::Builder, Some(purchase)) => {
+                self.pending = Some(purchase);
+                match purchase {
+                    Purchase::Build { slot, kind } => {
+                        out.push(Command::PlaceTower {
+                            kind,
+                            origin: self.slots[slot].0,
+                        });
+                        out.push(Command::RequestAnalyticsRefresh);
+                    }
+                    Purchase::Upgrade { tower } => out.push(Command::UpgradeTower { tower }),
+                }
+            }
+            (PlayMode::Builder, None) => out.push(Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            }),
+            (PlayMode::Attack, None) => {}
+        }
+    }
+
+    /// Reports whether the bot finished building and waits for the next wave.
+    #[must_use]
+    pub fn ready_to_launch(&self, play_mode: PlayMode, gold: Gold, content: &TowerContent) -> bool {
+        play_mode == PlayMode::Attack
+            && !self.wave_active
+            && self.pending.is_none()
+            && self.next_purchase(gold, content).is_none()
+    }
+
+    /// Outcomes of every wave resolved since the bot was created, oldest first.
+    #[must_use]
+    pub fn outcomes(&self) -> &[RoundOutcome] {
+        &self.outcomes
+    }
+
+    fn observe(&mut self, event: &Event) {
+        match event {
+            Event::TowerPlaced {
+                tower,
+                kind,
+                region,
+            } => {
+                if let Some(slot) = self.slot_at(region.origin()) {
+                    self.slots[slot].1 = SlotState::Built;
+                    self.towers.push(BuiltTower {
+                        id: *tower,
+                        kind: *kind,
+                        upgrades: 0,
+                    });
+                    self.settle_build(slot);
+                }
+            }
+            Event::TowerRemoved { tower, region } => {
+                if let Some(slot) = self.slot_at(region.origin()) {
+                    self.slots[slot].1 = SlotState::Open;
+                }
+                self.towers.retain(|built| built.id != *tower);
+            }
+            Event::TowerPlacementRejected { origin, reason, .. } => {
+                if let Some(slot) = self.slot_at(*origin) {
+                    self.settle_build(slot);
+                    if *reason == PlacementError::InsufficientFunds {
+                        self.out_of_funds = true;
+                    } else {
+                        self.slots[slot].1 = SlotState::Blocked;
+                    }
+                }
+            }
+            Event::TowerUpgraded {
+                tower, upgrades, ..
+            } => {
+                if let Some(built) = self.tower_mut(*tower) {
+                    built.upgrades = *upgrades;
+                }
+                self.settle_upgrade(*tower);
+            }
+            Event::TowerUpgradeRejected { tower, reason } => {
+                self.settle_upgrade(*tower);
+                if *reason == UpgradeError::InsufficientFunds {
+                    self.out_of_funds = true;
+                } else if let Some(built) = self.tower_mut(*tower) {
+                    built.upgrades = TowerStats::MAX_UPGRADES;
+                }
+            }
+            Event::WaveStarted { .. } => self.wave_active = true,
+            Event::WaveReportReady { report } => {
+                self.wave_active = false;
+                self.out_of_funds = false;
+                self.outcomes.push(report.outcome());
+            }
+            _ => {}
+        }
+    }
+
+    fn slot_at(&self, origin: CellCoord) -> Option<usize> {
+        self.slots.iter().position(|(slot, _)| *slot == origin)
+    }
+
+    fn tower_mut(&mut self, tower: TowerId) -> Option<&mut BuiltTower> {
+        self.towers.iter_mut().find(|built| built.id == tower)
+    }
+
+    fn settle_build(&mut self, slot: usize) {
+        if matches!(self.pending, Some(Purchase::Build { slot: pending, .. }) if pending == slot) {
+            self.pending = None;
+        }
+    }
+
+    fn settle_upgrade(&mut self, tower: TowerId) {
+        if self.pending == Some(Purchase::Upgrade { tower }) {
+            self.pending = None;
+        }
+    }
+
+    /// Purchase adding the most damage per second per gold, provided `gold` covers it.
+    ///
+    /// New towers win ties, and upgrades tie among themselves in build order.
+    fn next_purchase(&self, gold: Gold, content: &TowerContent) -> Option<Purchase> {
+        if self.out_of_funds {
+            return None;
+        }
+        let build = best_kind(content, self.slot_size).and_then(|kind| {
+            let slot = self
+                .slots
+                .iter()
+                .position(|(_, state)| *state == SlotState::Open)?;
+            let stats = content.stats(kind);
+            Some((
+                Purchase::Build { slot, kind },
+                stats.build_cost,
+                damage_rate_per_gold(&stats, stats.build_cost),
+            ))
+        });
+        let upgrades = self
+            .towers
+            .iter()
+            .filter(|built| built.upgrades < TowerStats::MAX_UPGRADES)
+            .map(|built| {
+                let stats = content.stats(built.kind);
+                let cost = stats.upgrade_cost(built.upgrades + 1);
+                (
+                    Purchase::Upgrade { tower: built.id },
+                    cost,
+                    damage_rate_per_gold(&stats, cost),
+                )
+            });
+
+        let mut best: Option<(Purchase, Gold, u64)> = None;
+        for candidate in build.into_iter().chain(upgrades) {
+            if candidate.2 > 0 && best.is_none_or(|(_, _, top)| candidate.2 > top) {
+                best = Some(candidate);
+            }
+        }
+        let (purchase, cost, _) = best?;
+        (gold.get() >= cost.get()).then_some(purchase)
+    }
+}
+
+/// Picks the firing tower kind fitting `slot` with the most damage per second per gold.
+///
+/// Kinds that deal no damage are never picked; ties go to the kind listed first in
+/// [`TowerKind::ALL`].
+#[must_use]
+pub fn best_kind(content: &TowerContent, slot: CellRectSize) -> Option<TowerKind> {
+    let mut best: Option<(TowerKind, u64)> = None;
+    for kind in TowerKind::ALL {
+        let footprint = kind.footprint();
+        if !kind.fires() || footprint.width() > slot.width() || footprint.height() > slot.height() {
+            continue;
+        }
+        let stats = content.stats(kind);
+        let score = damage_rate_per_gold(&stats, stats.build_cost);
+        if score > 0 && best.is_none_or(|(_, top)| score > top) {
+            best = Some((kind, score));
+        }
+    }
+    best.map(|(kind, _)| kind)
+}
+
+/// Lays out tower slots as walls across `area` whose gaps alternate between the right and
+/// left ends, forcing bugs to snake through every corridor.
+///
+/// Walls are one slot tall and separated by corridors of the same height, starting one
+/// corridor below the top edge. Slots are returned wall by wall from the top, beginning
+/// beside each wall's gap: towers at the turns cover both legs of the corridor.
+#[must_use]
+pub fn serpentine_slots(area: CellRect, slot: CellRectSize) -> Vec<CellCoord> {
+    let (width, height) = (slot.width(), slot.height());
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let columns = area.size().width() / width;
+    let walls = area
+        .size()
+        .height()
+        .checked_sub(height)
+        .map_or(0, |span| span / (height * 2));
+    if columns < 2 {
+        return Vec::new();
+    }
+
+    let mut slots = Vec::new();
+    for wall in 0..walls {
+        let row = area.origin().row() + height * (wall * 2 + 1);
+        let gap_on_right = wall % 2 == 0;
+        let built = 0..columns - 1;
+        let order: Vec<u32> = if gap_on_right {
+            built.rev().collect()
+        } else {
+            built.map(|column| column + 1).collect()
+        };
+        slots.extend(
+            order
+                .into_iter()
+                .map(|column| CellCoord::new(area.origin().column() + column * width, row)),
+        );
+    }
+    slots
+}
+
+/// Milli-damage per second per gold that spending `cost` on one more projectile damage
+/// buys, kept integral so the choice is deterministic.
+///
+/// A new tower and every upgrade both add the base projectile damage once.
+fn damage_rate_per_gold(stats: &TowerStats, cost: Gold) -> u64 {
+    u64::from(stats.projectile_damage.get()) * 1_000_000
+        / u64::from(stats.fire_cooldown_ms.max(1))
+        / cost.get().max(1)
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    if alignment <= 1 {
+        value
+    } else {
+        value.div_ceil(alignment) * alignment
+    }
+}
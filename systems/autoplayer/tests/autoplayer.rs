@@ -0,0 +1,298 @@
+use std::time::Duration;
+
+use maze_defence_core::{
+    CellCoord, CellRect, CellRectSize, Command, Damage, Event, Gold, PlacementError, PlayMode,
+    RoundOutcome, TowerContent, TowerId, TowerKind, TowerStats, UpgradeError, WaveId, WaveReport,
+};
+use maze_defence_system_autoplayer::{best_kind, serpentine_slots, Autoplayer};
+
+fn area() -> CellRect {
+    CellRect::from_origin_and_size(CellCoord::new(1, 1), CellRectSize::new(16, 20))
+}
+
+fn placed(origin: CellCoord, tower: u32) -> Event {
+    Event::TowerPlaced {
+        tower: TowerId::new(tower),
+        kind: TowerKind::Basic,
+        region: CellRect::from_origin_and_size(origin, TowerKind::Basic.footprint()),
+    }
+}
+
+#[test]
+fn serpentine_walls_alternate_their_gaps_and_start_beside_them() {
+    let slots = serpentine_slots(area(), CellRectSize::new(4, 4));
+
+    let columns = |row: u32| -> Vec<u32> {
+        slots
+            .iter()
+            .filter(|slot| slot.row() == row)
+            .map(|slot| slot.column())
+            .collect()
+    };
+    assert_eq!(slots.len(), 6, "two walls of three slots");
+    assert_eq!(
+        columns(5),
+        vec![9, 5, 1],
+        "gap on the right, filled from it"
+    );
+    assert_eq!(
+        columns(13),
+        vec![5, 9, 13],
+        "gap on the left, filled from it"
+    );
+    assert!(
+        columns(21).is_empty(),
+        "a corridor always remains above the exit"
+    );
+}
+
+#[test]
+fn best_kind_maximises_damage_per_second_per_gold() {
+    let slot = TowerKind::Basic.footprint();
+    let content = TowerContent::default();
+    assert_eq!(best_kind(&content, slot), Some(TowerKind::Basic));
+
+    let lethal_repulsor = content.with_stats(
+        TowerKind::Repulsor,
+        TowerStats {
+            projectile_damage: Damage::new(10),
+            ..TowerKind::Repulsor.base_stats()
+        },
+    );
+    assert_eq!(best_kind(&lethal_repulsor, slot), Some(TowerKind::Repulsor));
+
+    let harmless = content
+        .with_stats(
+            TowerKind::Basic,
+            TowerStats {
+                projectile_damage: Damage::new(0),
+                ..TowerKind::Basic.base_stats()
+            },
+        )
+        .with_stats(
+            TowerKind::Frost,
+            TowerStats {
+                projectile_damage: Damage::new(0),
+                ..TowerKind::Frost.base_stats()
+            },
+        );
+    assert_eq!(best_kind(&harmless, slot), None);
+}
+
+#[test]
+fn builds_between_waves_until_gold_runs_out() {
+    let content = TowerContent::default();
+    let mut bot = Autoplayer::new(area(), 2);
+    let mut commands = Vec::new();
+
+    bot.handle(
+        &[],
+        PlayMode::Attack,
+        Gold::new(25),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands,
+        vec![Command::SetPlayMode {
+            mode: PlayMode::Builder
+        }]
+    );
+
+    commands.clear();
+    bot.handle(
+        &[],
+        PlayMode::Builder,
+        Gold::new(25),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands,
+        vec![
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(9, 5),
+            },
+            Command::RequestAnalyticsRefresh,
+        ]
+    );
+
+    commands.clear();
+    bot.handle(
+        &[],
+        PlayMode::Builder,
+        Gold::new(25),
+        &content,
+        &mut commands,
+    );
+    assert!(commands.is_empty(), "waits for the placement to resolve");
+
+    let rejected = Event::TowerPlacementRejected {
+        kind: TowerKind::Basic,
+        origin: CellCoord::new(9, 5),
+        reason: PlacementError::PathBlocked,
+    };
+    bot.handle(
+        &[rejected],
+        PlayMode::Builder,
+        Gold::new(25),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands[0],
+        Command::PlaceTower {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(5, 5),
+        },
+        "blocked slots are skipped"
+    );
+
+    commands.clear();
+    bot.handle(
+        &[placed(CellCoord::new(5, 5), 1)],
+        PlayMode::Builder,
+        Gold::new(5),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands,
+        vec![Command::SetPlayMode {
+            mode: PlayMode::Attack
+        }]
+    );
+    assert!(bot.ready_to_launch(PlayMode::Attack, Gold::new(5), &content));
+    assert!(!bot.ready_to_launch(PlayMode::Attack, Gold::new(50), &content));
+}
+
+#[test]
+fn upgrades_towers_once_the_maze_is_full() {
+    let content = TowerContent::default();
+    let single_slot =
+        CellRect::from_origin_and_size(CellCoord::new(1, 1), CellRectSize::new(8, 12));
+    let mut bot = Autoplayer::new(single_slot, 2);
+    let mut commands = Vec::new();
+    let tower = TowerId::new(1);
+
+    bot.handle(
+        &[],
+        PlayMode::Builder,
+        Gold::new(100),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands[0],
+        Command::PlaceTower {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(1, 5),
+        }
+    );
+
+    commands.clear();
+    bot.handle(
+        &[placed(CellCoord::new(1, 5), 1)],
+        PlayMode::Builder,
+        Gold::new(90),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(commands, vec![Command::UpgradeTower { tower }]);
+
+    commands.clear();
+    bot.handle(
+        &[],
+        PlayMode::Builder,
+        Gold::new(90),
+        &content,
+        &mut commands,
+    );
+    assert!(commands.is_empty(), "waits for the upgrade to resolve");
+
+    let upgraded = Event::TowerUpgraded {
+        tower,
+        upgrades: 1,
+        cost: Gold::new(10),
+    };
+    bot.handle(
+        &[upgraded],
+        PlayMode::Builder,
+        Gold::new(15),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands,
+        vec![Command::SetPlayMode {
+            mode: PlayMode::Attack
+        }],
+        "saves up for the second upgrade, which costs twice the build cost"
+    );
+    assert!(!bot.ready_to_launch(PlayMode::Attack, Gold::new(20), &content));
+
+    commands.clear();
+    let maxed = Event::TowerUpgradeRejected {
+        tower,
+        reason: UpgradeError::FullyUpgraded,
+    };
+    bot.handle(
+        &[maxed],
+        PlayMode::Attack,
+        Gold::new(1_000),
+        &content,
+        &mut commands,
+    );
+    assert!(commands.is_empty(), "rejected towers are not retried");
+    assert!(bot.ready_to_launch(PlayMode::Attack, Gold::new(1_000), &content));
+}
+
+#[test]
+fn prefers_new_towers_over_upgrades_of_equal_value() {
+    let content = TowerContent::default();
+    let mut bot = Autoplayer::new(area(), 2);
+    let mut commands = Vec::new();
+
+    bot.handle(
+        &[placed(CellCoord::new(9, 5), 1)],
+        PlayMode::Builder,
+        Gold::new(25),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(
+        commands[0],
+        Command::PlaceTower {
+            kind: TowerKind::Basic,
+            origin: CellCoord::new(5, 5),
+        },
+        "the first upgrade buys as much damage per gold as a new tower"
+    );
+}
+
+#[test]
+fn records_the_outcome_of_every_resolved_wave() {
+    let content = TowerContent::default();
+    let mut bot = Autoplayer::new(area(), 2);
+    let mut commands = Vec::new();
+    let report = |outcome| Event::WaveReportReady {
+        report: Box::new(WaveReport::new(
+            WaveId::new(0),
+            outcome,
+            Duration::from_secs(1),
+            0,
+            Gold::new(0),
+            Vec::new(),
+        )),
+    };
+
+    bot.handle(
+        &[report(RoundOutcome::Win), report(RoundOutcome::Loss)],
+        PlayMode::Attack,
+        Gold::new(0),
+        &content,
+        &mut commands,
+    );
+    assert_eq!(bot.outcomes(), &[RoundOutcome::Win, RoundOutcome::Loss]);
+}
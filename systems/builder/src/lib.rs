@@ -10,7 +10,7 @@
 //! Pure builder-mode system responsible for emitting tower placement and removal commands.
 
 use maze_defence_core::{
-    CellCoord, CellRect, Command, Event, PlacementError, PlayMode, TowerId, TowerKind,
+    CellCoord, CellRect, CellRectSize, Command, Event, PlacementError, PlayMode, TowerId, TowerKind,
 };
 
 /// Declarative placement preview describing a potential tower construction.
@@ -149,4 +149,75 @@ impl Builder {
             }
         }
     }
+    /// Emits placement commands for the previews accumulated while dragging.
+    ///
+    /// Previews are processed in order and only placeable entries produce commands, so
+    /// callers can validate every footprint along the dragged line independently.
+    pub fn handle_drag(&self, previews: &[PlacementPreview], out: &mut Vec<Command>) {
+        if self.play_mode != PlayMode::Builder {
+            return;
+        }
+
+        let mut placed_any = false;
+        for preview in previews.iter().filter(|preview| preview.placeable) {
+            out.push(Command::PlaceTower {
+                kind: preview.kind,
+                origin: preview.origin,
+            });
+            placed_any = true;
+        }
+
+        if placed_any {
+            out.push(Command::RequestAnalyticsRefresh);
+        }
+    }
+}
+
+/// Enumerates the tower origins visited by a drag from `anchor` towards `target`.
+///
+/// Origins advance in whole footprints so consecutive towers sit edge to edge. The
+/// traversal follows a Bresenham line across the footprint lattice anchored at `anchor`,
+/// includes `anchor` itself, and never overshoots `target`.
+#[must_use]
+pub fn drag_line_origins(
+    anchor: CellCoord,
+    target: CellCoord,
+    footprint: CellRectSize,
+) -> Vec<CellCoord> {
+    let step_x = i64::from(footprint.width().max(1));
+    let step_y = i64::from(footprint.height().max(1));
+    let delta_x = (i64::from(target.column()) - i64::from(anchor.column())) / step_x;
+    let delta_y = (i64::from(target.row()) - i64::from(anchor.row())) / step_y;
+
+    let steps_x = delta_x.abs();
+    let steps_y = delta_y.abs();
+    let sign_x = delta_x.signum();
+    let sign_y = delta_y.signum();
+
+    let mut origins = Vec::with_capacity(usize::try_from(steps_x.max(steps_y) + 1).unwrap_or(1));
+    let (mut x, mut y) = (0_i64, 0_i64);
+    let mut error = steps_x - steps_y;
+    loop {
+        let column = i64::from(anchor.column()) + x * step_x;
+        let row = i64::from(anchor.row()) + y * step_y;
+        if let (Ok(column), Ok(row)) = (u32::try_from(column), u32::try_from(row)) {
+            origins.push(CellCoord::new(column, row));
+        }
+
+        if x.abs() == steps_x && y.abs() == steps_y {
+            break;
+        }
+
+        let doubled = error * 2;
+        if doubled > -steps_y {
+            error -= steps_y;
+            x += sign_x;
+        }
+        if doubled < steps_x {
+            error += steps_x;
+            y += sign_y;
+        }
+    }
+
+    origins
 }
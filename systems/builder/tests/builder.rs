@@ -1,7 +1,7 @@
 use maze_defence_core::{
     CellCoord, CellRect, CellRectSize, Command, Event, PlayMode, TowerId, TowerKind,
 };
-use maze_defence_system_builder::{Builder, BuilderInput, PlacementPreview};
+use maze_defence_system_builder::{drag_line_origins, Builder, BuilderInput, PlacementPreview};
 
 fn basic_preview_at(cell: CellCoord, placeable: bool) -> PlacementPreview {
     PlacementPreview::new(
@@ -175,3 +175,84 @@ fn remove_ignored_in_attack_mode() {
         "system must not emit removal commands in attack mode",
     );
 }
+
+#[test]
+fn drag_line_origins_step_by_footprint_along_dominant_axis() {
+    let footprint = CellRectSize::new(4, 4);
+
+    let horizontal = drag_line_origins(CellCoord::new(2, 6), CellCoord::new(15, 7), footprint);
+    assert_eq!(
+        horizontal,
+        vec![
+            CellCoord::new(2, 6),
+            CellCoord::new(6, 6),
+            CellCoord::new(10, 6),
+            CellCoord::new(14, 6),
+        ],
+    );
+
+    let diagonal = drag_line_origins(CellCoord::new(10, 10), CellCoord::new(2, 2), footprint);
+    assert_eq!(
+        diagonal,
+        vec![
+            CellCoord::new(10, 10),
+            CellCoord::new(6, 6),
+            CellCoord::new(2, 2),
+        ],
+    );
+
+    let stationary = drag_line_origins(CellCoord::new(3, 3), CellCoord::new(5, 4), footprint);
+    assert_eq!(stationary, vec![CellCoord::new(3, 3)]);
+}
+
+#[test]
+fn drag_emits_commands_only_for_placeable_previews() {
+    let mut builder = Builder::default();
+    let mut commands = Vec::new();
+    builder.handle(
+        &[Event::PlayModeChanged {
+            mode: PlayMode::Builder,
+        }],
+        None,
+        BuilderInput::default(),
+        |_| None,
+        &mut commands,
+    );
+
+    builder.handle_drag(
+        &[
+            basic_preview_at(CellCoord::new(2, 2), true),
+            basic_preview_at(CellCoord::new(6, 2), false),
+            basic_preview_at(CellCoord::new(10, 2), true),
+        ],
+        &mut commands,
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(10, 2),
+            },
+            Command::RequestAnalyticsRefresh,
+        ],
+    );
+}
+
+#[test]
+fn drag_ignored_in_attack_mode() {
+    let builder = Builder::default();
+    let mut commands = Vec::new();
+
+    builder.handle_drag(
+        &[basic_preview_at(CellCoord::new(2, 2), true)],
+        &mut commands,
+    );
+
+    assert!(commands.is_empty());
+}
@@ -11,7 +11,8 @@
 
 use maze_defence_core::{
     BugId, BugSnapshot, BugView, CellCoord, Command, Direction, Event, NavigationFieldView,
-    OccupancyView, PlayMode, ReservationLedgerView, CONGESTION_LOOKAHEAD, DETOUR_RADIUS,
+    OccupancyView, PathingPersonality, PlayMode, ReservationLedgerView, CONGESTION_LOOKAHEAD,
+    DETOUR_RADIUS,
 };
 use maze_defence_world::query::select_goal;
 
@@ -24,6 +25,9 @@ pub struct Movement {
 
 impl Movement {
     /// Consumes world events and immutable views to emit movement commands.
+    ///
+    /// Bugs with the [`PathingPersonality::Flanker`] personality descend
+    /// `flanker_navigation_view` instead of `navigation_view`.
     #[allow(clippy::too_many_arguments)]
     pub fn handle<F>(
         &mut self,
@@ -31,6 +35,7 @@ impl Movement {
         bug_view: &BugView,
         occupancy_view: OccupancyView<'_>,
         navigation_view: NavigationFieldView<'_>,
+        flanker_navigation_view: NavigationFieldView<'_>,
         reservation_ledger: ReservationLedgerView<'_>,
         targets: &[CellCoord],
         is_cell_blocked: F,
@@ -67,6 +72,7 @@ impl Movement {
             bug_view,
             occupancy_view,
             &navigation_view,
+            &flanker_navigation_view,
             &reservation_ledger,
             &is_cell_blocked,
             out,
@@ -141,11 +147,13 @@ impl CrowdPlanner {
         node_count
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn plan<F>(
         &mut self,
         bug_view: &BugView,
         occupancy_view: OccupancyView<'_>,
         navigation_view: &NavigationFieldView<'_>,
+        flanker_navigation_view: &NavigationFieldView<'_>,
         reservation_ledger: &ReservationLedgerView<'_>,
         is_cell_blocked: &F,
         out: &mut Vec<Command>,
@@ -160,6 +168,7 @@ impl CrowdPlanner {
             &ordered,
             occupancy_view,
             navigation_view,
+            flanker_navigation_view,
             reservation_ledger,
             is_cell_blocked,
             out,
@@ -185,11 +194,13 @@ impl CrowdPlanner {
         self.reserved_destinations.reserve(ordered.len());
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn emit_step_commands<F>(
         &mut self,
         ordered: &[&BugSnapshot],
         occupancy_view: OccupancyView<'_>,
         navigation_view: &NavigationFieldView<'_>,
+        flanker_navigation_view: &NavigationFieldView<'_>,
         reservation_ledger: &ReservationLedgerView<'_>,
         is_cell_blocked: &F,
         out: &mut Vec<Command>,
@@ -213,11 +224,15 @@ impl CrowdPlanner {
                 continue;
             }
 
+            let bug_navigation_view = match bug.pathing {
+                PathingPersonality::Direct => navigation_view,
+                PathingPersonality::Flanker => flanker_navigation_view,
+            };
             let Some(next_cell) = self.plan_next_hop(
                 index,
                 bug,
                 ordered,
-                navigation_view,
+                bug_navigation_view,
                 occupancy_view,
                 reservation_ledger,
                 is_cell_blocked,
@@ -853,7 +868,7 @@ fn lexicographically_less(left: CellCoord, right: CellCoord) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use maze_defence_core::{BugColor, Health, ReservationClaim, ReservationLedgerView};
+    use maze_defence_core::{BugColor, Gold, Health, ReservationClaim, ReservationLedgerView};
 
     #[test]
     fn direction_between_neighbors() {
@@ -946,6 +961,48 @@ mod tests {
         assert_eq!(movement.planner.stalled_for.value(0), 0);
     }
 
+    #[test]
+    fn flankers_descend_the_flanker_navigation_field() {
+        let navigation = NavigationFieldView::from_owned(vec![4, 3, 2, 5, 2, 1, 4, 1, 0], 3, 3);
+        let flanker_navigation =
+            NavigationFieldView::from_owned(vec![4, 5, 2, 3, 2, 1, 2, 1, 0], 3, 3);
+        let target = CellCoord::new(2, 2);
+        let occupancy_cells: Vec<Option<BugId>> = vec![None; 9];
+        let reservation = ReservationLedgerView::from_owned(Vec::new());
+
+        for (pathing, expected) in [
+            (PathingPersonality::Direct, Direction::East),
+            (PathingPersonality::Flanker, Direction::South),
+        ] {
+            let mut movement = Movement::default();
+            let _ = movement
+                .planner
+                .prepare_workspace(3, 3, &navigation, &[target]);
+            let mut bug = bug_snapshot_at(CellCoord::new(0, 0));
+            bug.pathing = pathing;
+            let bug_view = BugView::from_snapshots(vec![bug]);
+
+            let mut out = Vec::new();
+            movement.planner.plan(
+                &bug_view,
+                OccupancyView::new(&occupancy_cells, 3, 3),
+                &navigation,
+                &flanker_navigation,
+                &reservation,
+                &|_| false,
+                &mut out,
+            );
+
+            assert_eq!(
+                out,
+                vec![Command::StepBug {
+                    bug_id: BugId::new(1),
+                    direction: expected,
+                }]
+            );
+        }
+    }
+
     #[test]
     fn plan_next_hop_breaks_ties_by_column_then_row() {
         let mut movement = Movement::default();
@@ -1326,6 +1383,10 @@ mod tests {
             step_ms: 1,
             accum_ms: 1,
             ready_for_step: true,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            boss_phase: None,
+            status: None,
         }
     }
 }
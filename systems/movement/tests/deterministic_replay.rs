@@ -5,9 +5,9 @@ use std::{
 };
 
 use maze_defence_core::{
-    BugColor, BugId, BugSnapshot, CellCoord, Command, Event, Gold, Health, NavigationFieldView,
-    PendingWaveDifficulty, PlayMode, SpeciesTableVersion, TileCoord, TowerKind, WaveDifficulty,
-    WaveId,
+    BugArchetype, BugColor, BugId, BugRank, BugSnapshot, CellCoord, Command, DodgeChance, Event,
+    Gold, Health, NavigationFieldView, PathingPersonality, PendingWaveDifficulty, PlayMode,
+    SpeciesTableVersion, TileCoord, TowerKind, WaveDifficulty, WaveId,
 };
 use maze_defence_system_movement::Movement;
 use maze_defence_world::{self as world, query, World};
@@ -28,7 +28,7 @@ fn dense_corridor_replay_is_stable() {
 
 #[test]
 fn side_hallway_diversion_replay_is_stable() {
-    assert_stable_replay(side_hallway_diversion_commands(), 0x52ec_3a31_e295_0b7a);
+    assert_stable_replay(side_hallway_diversion_commands(), 0x90ef_5dc3_544e_cf86);
 }
 
 #[test]
@@ -100,12 +100,14 @@ fn process_movement(
         let mut commands = Vec::new();
         let target_cells = query::target_cells(world);
         let navigation_view = query::navigation_field(world);
+        let flanker_navigation_view = query::flanker_navigation_field(world);
         let reservation_ledger = query::reservation_ledger(world);
         movement.handle(
             &events,
             &bug_view,
             occupancy_view,
             navigation_view,
+            flanker_navigation_view,
             reservation_ledger,
             &target_cells,
             |cell| query::is_cell_blocked(&*world, cell),
@@ -146,6 +148,11 @@ fn baseline_commands() -> Vec<Command> {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         Command::Tick {
             dt: Duration::from_millis(500),
@@ -193,6 +200,11 @@ fn dense_corridor_commands() -> Vec<Command> {
             color: BugColor::from_rgb(red, green, blue),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         });
         commands.push(Command::Tick {
             dt: Duration::from_millis(250),
@@ -247,6 +259,11 @@ fn side_hallway_diversion_commands() -> Vec<Command> {
             color: BugColor::from_rgb(red, green, blue),
             health: Health::new(5),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         });
         commands.push(Command::Tick {
             dt: Duration::from_millis(250),
@@ -278,6 +295,11 @@ fn stall_regression_commands() -> Vec<Command> {
             color: BugColor::from_rgb(0x9a, 0x4c, 0x2f),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     ];
 
@@ -292,6 +314,11 @@ fn stall_regression_commands() -> Vec<Command> {
         color: BugColor::from_rgb(0x2f, 0x8c, 0xc0),
         health: Health::new(3),
         step_ms: DEFAULT_STEP_MS,
+        pathing: PathingPersonality::Direct,
+        bounty: Gold::new(1),
+        rank: BugRank::Regular,
+        dodge: DodgeChance::NONE,
+        archetype: BugArchetype::Basic,
     });
 
     for _ in 0..18 {
@@ -319,6 +346,11 @@ fn mixed_cadence_commands() -> Vec<Command> {
             color: BugColor::from_rgb(0xf2, 0x69, 0x35),
             health: Health::new(4),
             step_ms: FAST_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         Command::Tick {
             dt: Duration::from_millis(100),
@@ -334,6 +366,11 @@ fn mixed_cadence_commands() -> Vec<Command> {
             color: BugColor::from_rgb(0x2f, 0x70, 0xc5),
             health: Health::new(5),
             step_ms: SLOW_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     ];
 
@@ -377,6 +414,11 @@ fn movement_pauses_in_builder_mode() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         &mut events,
     );
@@ -400,6 +442,7 @@ fn movement_pauses_in_builder_mode() {
 
     let mut commands = Vec::new();
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     movement.handle(
         &[Event::TimeAdvanced {
@@ -408,6 +451,7 @@ fn movement_pauses_in_builder_mode() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -420,6 +464,7 @@ fn movement_pauses_in_builder_mode() {
 
     commands.clear();
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     movement.handle(
         &[
@@ -433,6 +478,7 @@ fn movement_pauses_in_builder_mode() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -602,24 +648,15 @@ impl From<&Event> for EventRecord {
                 wave: inputs.wave(),
                 spawn_count: plan.spawns().len(),
             },
-            Event::WaveStarted {
-                wave,
-                difficulty,
-                effective_difficulty,
-                reward_multiplier,
-                pressure_scalar,
-                plan_pressure,
-                plan_species_table_version,
-                plan_burst_count,
-            } => Self::WaveStarted {
+            Event::WaveStarted { wave, launch } => Self::WaveStarted {
                 wave: *wave,
-                difficulty: *difficulty,
-                effective_difficulty: effective_difficulty.get(),
-                reward_multiplier: *reward_multiplier,
-                pressure_scalar: *pressure_scalar,
-                plan_pressure: plan_pressure.get(),
-                plan_species_table_version: plan_species_table_version.get(),
-                plan_burst_count: *plan_burst_count,
+                difficulty: launch.difficulty,
+                effective_difficulty: launch.effective_difficulty.level().get(),
+                reward_multiplier: launch.reward_multiplier,
+                pressure_scalar: launch.pressure_scalar,
+                plan_pressure: launch.plan_pressure.to_u32_saturating(),
+                plan_species_table_version: launch.plan_species_table_version.get(),
+                plan_burst_count: launch.plan_burst_count,
             },
             Event::TowerPlaced {
                 tower,
@@ -643,14 +680,57 @@ impl From<&Event> for EventRecord {
             Event::TowerRemoved { .. }
             | Event::TowerPlacementRejected { .. }
             | Event::TowerRemovalRejected { .. }
+            | Event::TowerTargetingChanged { .. }
+            | Event::TowerUpgraded { .. }
+            | Event::TowerUpgradeRejected { .. }
             | Event::ProjectileFired { .. }
             | Event::ProjectileHit { .. }
             | Event::ProjectileExpired { .. }
+            | Event::ProjectileMissed { .. }
             | Event::ProjectileRejected { .. }
             | Event::HardWinAchieved { .. }
             | Event::DifficultyLevelChanged { .. }
             | Event::BugDamaged { .. }
-            | Event::BugDied { .. } => {
+            | Event::BugDied { .. }
+            | Event::PriorityTargetChanged { .. }
+            | Event::NextWaveCountdown { .. }
+            | Event::BuilderCountdown { .. }
+            | Event::WaveReportReady { .. }
+            | Event::WaveOutcomeRecorded { .. }
+            | Event::BetweenWaveEventRolled { .. }
+            | Event::MerchantOfferClosed { .. }
+            | Event::WaveContractsChanged { .. }
+            | Event::SandboxConfigured { .. }
+            | Event::WaveSeedChanged { .. }
+            | Event::PlacementRotationChanged { .. }
+            | Event::BridgesConfigured { .. }
+            | Event::VoidCellsConfigured { .. }
+            | Event::NestsConfigured { .. }
+            | Event::NestDamaged { .. }
+            | Event::NestDestroyed { .. }
+            | Event::NestShotRejected { .. }
+            | Event::WaveShieldBroken { .. }
+            | Event::BugPushedBack { .. }
+            | Event::BossPhaseChanged { .. }
+            | Event::PreparationBanked { .. }
+            | Event::TowerHeatConfigured { .. }
+            | Event::LineOfSightConfigured { .. }
+            | Event::AccuracyConfigured { .. }
+            | Event::BaseDamaged { .. }
+            | Event::ComboTriggered { .. }
+            | Event::GameRulesConfigured { .. }
+            | Event::ExitBehaviorConfigured { .. }
+            | Event::BugLooped { .. }
+            | Event::BugWornOut { .. }
+            | Event::FormationSpawned { .. }
+            | Event::SpawnersConfigured { .. }
+            | Event::SpawnerActivated { .. }
+            | Event::SpawnerConfigRejected { .. }
+            | Event::TileGridConfigRejected { .. }
+            | Event::PressureWaveRejected { .. }
+            | Event::BurstStarted { .. }
+            | Event::BurstEnded { .. }
+            | Event::TowerContentConfigured => {
                 unreachable!("tower events are not expected in movement replay tests")
             }
         }
@@ -1,8 +1,8 @@
 use std::{convert::TryFrom, time::Duration};
 
 use maze_defence_core::{
-    BugColor, BugId, BugView, CellCoord, Command, Direction, Event, Health, OccupancyView,
-    PlayMode, TileCoord, TowerKind,
+    BugArchetype, BugColor, BugId, BugRank, BugView, CellCoord, Command, Direction, DodgeChance,
+    Event, Gold, Health, OccupancyView, PathingPersonality, PlayMode, TileCoord, TowerKind,
 };
 use maze_defence_system_movement::Movement;
 use maze_defence_world::{self as world, query, World};
@@ -40,6 +40,11 @@ fn emits_step_commands_toward_target() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -56,6 +61,7 @@ fn emits_step_commands_toward_target() {
     let occupancy_view = query::occupancy_view(&world);
     let target_cells = query::target_cells(&world);
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     let mut commands = Vec::new();
     movement.handle(
@@ -63,6 +69,7 @@ fn emits_step_commands_toward_target() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -133,6 +140,11 @@ fn bugs_progress_despite_distant_blockers() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -148,6 +160,11 @@ fn bugs_progress_despite_distant_blockers() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -171,6 +188,7 @@ fn bugs_progress_despite_distant_blockers() {
     let occupancy_view = query::occupancy_view(&world);
     let targets = query::target_cells(&world);
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     let mut commands = Vec::new();
     movement.handle(
@@ -178,6 +196,7 @@ fn bugs_progress_despite_distant_blockers() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &targets,
         |cell| query::is_cell_blocked(&world, cell),
@@ -227,6 +246,11 @@ fn step_commands_target_free_cells() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -244,12 +268,14 @@ fn step_commands_target_free_cells() {
     let mut commands = Vec::new();
     let target_cells = query::target_cells(&world);
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     movement.handle(
         &tick_events,
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -295,6 +321,11 @@ fn emits_step_commands_in_bug_id_order() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         &mut events,
     );
@@ -305,6 +336,11 @@ fn emits_step_commands_in_bug_id_order() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         &mut events,
     );
@@ -328,6 +364,11 @@ fn emits_step_commands_in_bug_id_order() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -344,6 +385,7 @@ fn emits_step_commands_in_bug_id_order() {
     let occupancy_view = query::occupancy_view(&world);
     let target_cells = query::target_cells(&world);
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     let mut commands = Vec::new();
     movement.handle(
@@ -351,6 +393,7 @@ fn emits_step_commands_in_bug_id_order() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -404,6 +447,11 @@ fn replans_after_failed_step() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -450,12 +498,14 @@ fn replans_after_failed_step() {
     let occupancy_view = query::occupancy_view(&world);
     let mut commands = Vec::new();
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     movement.handle(
         &tick_events,
         &bug_view_after_failure,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -547,6 +597,11 @@ fn bugs_respect_tower_blockers() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
         &mut spawn_events,
     );
@@ -578,6 +633,7 @@ fn bugs_respect_tower_blockers() {
     let occupancy_view = query::occupancy_view(&world);
     let target_cells = query::target_cells(&world);
     let navigation_view = query::navigation_field(&world);
+    let flanker_navigation_view = query::flanker_navigation_field(&world);
     let reservation_ledger = query::reservation_ledger(&world);
     let mut commands = Vec::new();
     movement.handle(
@@ -585,6 +641,7 @@ fn bugs_respect_tower_blockers() {
         &bug_view,
         occupancy_view,
         navigation_view,
+        flanker_navigation_view,
         reservation_ledger,
         &target_cells,
         |cell| query::is_cell_blocked(&world, cell),
@@ -634,6 +691,7 @@ fn blocked_bugs_do_not_accumulate_extra_step_time() {
             let occupancy_view = query::occupancy_view(world_state);
             let targets = query::target_cells(world_state);
             let navigation_view = query::navigation_field(world_state);
+            let flanker_navigation_view = query::flanker_navigation_field(world_state);
             let reservation_ledger = query::reservation_ledger(world_state);
             let mut commands = Vec::new();
             movement.handle(
@@ -641,6 +699,7 @@ fn blocked_bugs_do_not_accumulate_extra_step_time() {
                 &bug_view,
                 occupancy_view,
                 navigation_view,
+                flanker_navigation_view,
                 reservation_ledger,
                 &targets,
                 |cell| query::is_cell_blocked(world_state, cell),
@@ -714,6 +773,11 @@ fn blocked_bugs_do_not_accumulate_extra_step_time() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(3),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -815,6 +879,11 @@ fn bug_reaches_exit_when_central_tower_blocks_direct_route() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(1000),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -910,6 +979,11 @@ fn bug_reaches_exit_when_two_towers_force_wide_detour() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(1000),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -1008,6 +1082,11 @@ fn corner_spawn_bug_reaches_exit_through_chicane() {
             color: BugColor::from_rgb(0x2f, 0x95, 0x32),
             health: Health::new(1000),
             step_ms: DEFAULT_STEP_MS,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
         },
     );
 
@@ -1068,6 +1147,7 @@ fn drive_tick_and_collect(world: &mut World, movement: &mut Movement, dt: Durati
         let occupancy_view = query::occupancy_view(world);
         let targets = query::target_cells(world);
         let navigation_view = query::navigation_field(world);
+        let flanker_navigation_view = query::flanker_navigation_field(world);
         let reservation_ledger = query::reservation_ledger(world);
         let mut commands = Vec::new();
         movement.handle(
@@ -1075,6 +1155,7 @@ fn drive_tick_and_collect(world: &mut World, movement: &mut Movement, dt: Durati
             &bug_view,
             occupancy_view,
             navigation_view,
+            flanker_navigation_view,
             reservation_ledger,
             &targets,
             |cell| query::is_cell_blocked(&*world, cell),
@@ -1109,12 +1190,14 @@ fn pump_system(world: &mut World, movement: &mut Movement, mut events: Vec<Event
         let mut commands = Vec::new();
         let target_cells = query::target_cells(world);
         let navigation_view = query::navigation_field(world);
+        let flanker_navigation_view = query::flanker_navigation_field(world);
         let reservation_ledger = query::reservation_ledger(world);
         movement.handle(
             &events,
             &bug_view,
             occupancy_view,
             navigation_view,
+            flanker_navigation_view,
             reservation_ledger,
             &target_cells,
             |cell| query::is_cell_blocked(&*world, cell),
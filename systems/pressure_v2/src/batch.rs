@@ -0,0 +1,205 @@
+//! Headless sweeps that generate many wave plans and aggregate their statistics.
+
+use std::ops::RangeInclusive;
+
+use maze_defence_core::{
+    DifficultyLevel, LevelId, PressureSpawnRecord, PressureWaveInputs, SpeciesPrototype, WaveId,
+};
+use serde::Serialize;
+
+use crate::PressureV2;
+
+/// Grid of generator inputs swept by [`PressureV2::generate_batch`].
+///
+/// Every combination of level, wave and difficulty in the ranges is generated once, levels
+/// outermost and difficulties innermost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchSweep {
+    /// Game seed shared by every generated wave.
+    pub game_seed: u64,
+    /// Level identifiers to sweep.
+    pub levels: RangeInclusive<u32>,
+    /// Zero-based wave identifiers to sweep.
+    pub waves: RangeInclusive<u32>,
+    /// Difficulty levels to sweep.
+    pub difficulties: RangeInclusive<u32>,
+    /// Width of each wave-duration histogram bucket in milliseconds; zero is treated as one.
+    pub duration_bucket_ms: u32,
+}
+
+impl Default for BatchSweep {
+    fn default() -> Self {
+        Self {
+            game_seed: 0,
+            levels: 1..=1,
+            waves: 0..=9,
+            difficulties: 1..=10,
+            duration_bucket_ms: 5_000,
+        }
+    }
+}
+
+impl BatchSweep {
+    /// Generator inputs of every wave in the sweep, in generation order.
+    pub fn inputs(&self) -> impl Iterator<Item = PressureWaveInputs> + '_ {
+        self.levels.clone().flat_map(move |level| {
+            self.waves.clone().flat_map(move |wave| {
+                self.difficulties.clone().map(move |difficulty| {
+                    PressureWaveInputs::new(
+                        self.game_seed,
+                        LevelId::new(level),
+                        WaveId::new(wave),
+                        DifficultyLevel::new(difficulty),
+                    )
+                })
+            })
+        })
+    }
+}
+
+/// Statistics of one generated wave.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WaveSample {
+    /// Inputs the wave was generated from.
+    pub inputs: PressureWaveInputs,
+    /// Bugs scheduled to spawn.
+    pub bug_count: u32,
+    /// Species the bugs belong to.
+    pub species: u32,
+    /// Sum of every scheduled bug's health.
+    pub total_hp: u64,
+    /// Time of the last scheduled spawn in milliseconds.
+    pub duration_ms: u32,
+}
+
+/// Minimum, maximum and total of a statistic over every wave in a batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct BatchRange {
+    /// Smallest per-wave value.
+    pub min: u64,
+    /// Largest per-wave value.
+    pub max: u64,
+    /// Sum of the per-wave values.
+    pub total: u64,
+}
+
+impl BatchRange {
+    fn record(&mut self, value: u64, first: bool) {
+        if first {
+            *self = Self {
+                min: value,
+                max: value,
+                total: value,
+            };
+            return;
+        }
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.total = self.total.saturating_add(value);
+    }
+}
+
+/// Aggregate statistics of the waves generated by [`PressureV2::generate_batch`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct BatchReport {
+    /// Statistics of every generated wave, in generation order.
+    pub samples: Vec<WaveSample>,
+    /// Bugs scheduled per wave.
+    pub bug_count: BatchRange,
+    /// Total health scheduled per wave.
+    pub total_hp: BatchRange,
+    /// Wave durations in milliseconds.
+    pub duration_ms: BatchRange,
+    /// Width of each duration histogram bucket in milliseconds.
+    pub duration_bucket_ms: u32,
+    /// Waves per duration bucket; bucket `i` covers durations from `i` up to, but
+    /// excluding, `i + 1` bucket widths.
+    pub duration_histogram: Vec<u32>,
+}
+
+impl PressureV2 {
+    /// Generates every wave of `sweep` without a world and aggregates their statistics.
+    ///
+    /// Each wave is generated exactly as [`Self::generate`] would for the same inputs, so a
+    /// sweep audits the plans the game would play. Telemetry afterwards describes the last
+    /// generated wave.
+    pub fn generate_batch(&mut self, sweep: &BatchSweep) -> BatchReport {
+        let bucket_ms = sweep.duration_bucket_ms.max(1);
+        let mut report = BatchReport {
+            duration_bucket_ms: bucket_ms,
+            ..BatchReport::default()
+        };
+        let mut spawns: Vec<PressureSpawnRecord> = Vec::new();
+        let mut prototypes: Vec<SpeciesPrototype> = Vec::new();
+        for inputs in sweep.inputs() {
+            spawns.clear();
+            prototypes.clear();
+            self.generate(&inputs, &mut spawns, &mut prototypes);
+
+            let sample = WaveSample {
+                inputs,
+                bug_count: u32::try_from(spawns.len()).unwrap_or(u32::MAX),
+                species: u32::try_from(prototypes.len()).unwrap_or(u32::MAX),
+                total_hp: spawns.iter().map(|spawn| u64::from(spawn.hp())).sum(),
+                duration_ms: spawns
+                    .iter()
+                    .map(PressureSpawnRecord::time_ms)
+                    .max()
+                    .unwrap_or(0),
+            };
+            let first = report.samples.is_empty();
+            report.bug_count.record(u64::from(sample.bug_count), first);
+            report.total_hp.record(sample.total_hp, first);
+            report
+                .duration_ms
+                .record(u64::from(sample.duration_ms), first);
+            let bucket = (sample.duration_ms / bucket_ms) as usize;
+            if report.duration_histogram.len() <= bucket {
+                report.duration_histogram.resize(bucket + 1, 0);
+            }
+            report.duration_histogram[bucket] += 1;
+            report.samples.push(sample);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_match_individual_generation_and_aggregate_every_wave() {
+        let sweep = BatchSweep {
+            game_seed: 42,
+            levels: 1..=2,
+            waves: 0..=2,
+            difficulties: 1..=3,
+            duration_bucket_ms: 2_000,
+        };
+        let report = PressureV2::default().generate_batch(&sweep);
+        assert_eq!(report.samples.len(), 18);
+        assert_eq!(
+            report.duration_histogram.iter().sum::<u32>(),
+            18,
+            "every wave lands in one bucket"
+        );
+
+        let mut generator = PressureV2::default();
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        let mut total_bugs = 0;
+        for (sample, inputs) in report.samples.iter().zip(sweep.inputs()) {
+            assert_eq!(sample.inputs, inputs);
+            spawns.clear();
+            prototypes.clear();
+            generator.generate(&inputs, &mut spawns, &mut prototypes);
+            assert_eq!(sample.bug_count as usize, spawns.len());
+            total_bugs += spawns.len() as u64;
+        }
+        assert_eq!(report.bug_count.total, total_bugs);
+        assert!(report.bug_count.min <= report.bug_count.max);
+        let longest = report.duration_ms.max as u32 / report.duration_bucket_ms;
+        assert_eq!(report.duration_histogram.len(), longest as usize + 1);
+    }
+}
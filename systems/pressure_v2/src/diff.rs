@@ -0,0 +1,230 @@
+//! Side-by-side comparison of two wave plans, showing how a tuning change altered a wave.
+
+use std::collections::BTreeMap;
+
+use maze_defence_core::PressureSpawnRecord;
+use serde::Serialize;
+
+/// How one species changed between two plans.
+///
+/// Timing drift pairs the species' spawns in order: the first spawn of `a` with the first
+/// of `b`, and so on. Spawns without a partner only show up in the count delta.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SpeciesDiff {
+    /// Generated species identifier.
+    pub species: u32,
+    /// Spawns of the species in `a`.
+    pub count_before: u32,
+    /// Spawns of the species in `b`.
+    pub count_after: u32,
+    /// Combined health of the species' spawns in `a`.
+    pub hp_before: u64,
+    /// Combined health of the species' spawns in `b`.
+    pub hp_after: u64,
+    /// Mean of `b`'s spawn time minus `a`'s over the paired spawns, in milliseconds.
+    pub mean_drift_ms: f32,
+    /// Paired drift with the largest magnitude, in milliseconds; later in `b` is positive.
+    pub max_drift_ms: i64,
+}
+
+impl SpeciesDiff {
+    /// Spawns gained by the species, negative when it lost spawns.
+    #[must_use]
+    pub fn count_delta(&self) -> i64 {
+        i64::from(self.count_after) - i64::from(self.count_before)
+    }
+
+    /// Health gained by the species, negative when it lost health.
+    #[must_use]
+    pub fn hp_delta(&self) -> i64 {
+        self.hp_after as i64 - self.hp_before as i64
+    }
+}
+
+/// Differences between two wave plans, produced by [`compare_plans`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PlanDiff {
+    /// Every species spawned by either plan, ordered by identifier.
+    pub species: Vec<SpeciesDiff>,
+    /// Time of the last spawn in `b` minus that in `a`, in milliseconds.
+    pub end_drift_ms: i64,
+}
+
+impl PlanDiff {
+    /// Spawns gained over every species, negative when the wave shrank.
+    #[must_use]
+    pub fn count_delta(&self) -> i64 {
+        self.species.iter().map(SpeciesDiff::count_delta).sum()
+    }
+
+    /// Health gained over every species, negative when the wave got weaker.
+    #[must_use]
+    pub fn hp_delta(&self) -> i64 {
+        self.species.iter().map(SpeciesDiff::hp_delta).sum()
+    }
+
+    /// Reports whether the plans spawn the same bugs at the same times.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.end_drift_ms == 0
+            && self.species.iter().all(|species| {
+                species.count_delta() == 0 && species.hp_delta() == 0 && species.max_drift_ms == 0
+            })
+    }
+}
+
+/// Compares plan `a` with plan `b`, reporting how `b` differs.
+///
+/// Speed multipliers, archetypes and lanes are not compared.
+#[must_use]
+pub fn compare_plans(a: &[PressureSpawnRecord], b: &[PressureSpawnRecord]) -> PlanDiff {
+    let (before, after) = (species_spawns(a), species_spawns(b));
+    let mut ids: Vec<u32> = before.keys().chain(after.keys()).copied().collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let species = ids
+        .into_iter()
+        .map(|species| {
+            let empty = Vec::new();
+            let before = before.get(&species).unwrap_or(&empty);
+            let after = after.get(&species).unwrap_or(&empty);
+            let drifts: Vec<i64> = before
+                .iter()
+                .zip(after)
+                .map(|(a, b)| i64::from(b.time_ms()) - i64::from(a.time_ms()))
+                .collect();
+            let mean_drift_ms = if drifts.is_empty() {
+                0.0
+            } else {
+                drifts.iter().sum::<i64>() as f32 / drifts.len() as f32
+            };
+            SpeciesDiff {
+                species,
+                count_before: count(before),
+                count_after: count(after),
+                hp_before: total_hp(before),
+                hp_after: total_hp(after),
+                mean_drift_ms,
+                max_drift_ms: drifts
+                    .iter()
+                    .copied()
+                    .max_by_key(|drift| drift.abs())
+                    .unwrap_or(0),
+            }
+        })
+        .collect();
+
+    PlanDiff {
+        species,
+        end_drift_ms: i64::from(end_ms(b)) - i64::from(end_ms(a)),
+    }
+}
+
+/// Spawns of every species in time order.
+fn species_spawns(plan: &[PressureSpawnRecord]) -> BTreeMap<u32, Vec<&PressureSpawnRecord>> {
+    let mut spawns: BTreeMap<u32, Vec<&PressureSpawnRecord>> = BTreeMap::new();
+    for spawn in plan {
+        spawns.entry(spawn.species_id()).or_default().push(spawn);
+    }
+    for species in spawns.values_mut() {
+        species.sort_by_key(|spawn| spawn.time_ms());
+    }
+    spawns
+}
+
+fn count(spawns: &[&PressureSpawnRecord]) -> u32 {
+    u32::try_from(spawns.len()).unwrap_or(u32::MAX)
+}
+
+fn total_hp(spawns: &[&PressureSpawnRecord]) -> u64 {
+    spawns.iter().map(|spawn| u64::from(spawn.hp())).sum()
+}
+
+fn end_ms(plan: &[PressureSpawnRecord]) -> u32 {
+    plan.iter()
+        .map(PressureSpawnRecord::time_ms)
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PressureV2;
+    use maze_defence_core::{DifficultyLevel, LevelId, PressureWaveInputs, WaveId};
+
+    #[test]
+    fn diffs_report_count_hp_and_timing_changes_per_species() {
+        let a = [
+            PressureSpawnRecord::new(0, 10, 1.0, 0),
+            PressureSpawnRecord::new(100, 10, 1.0, 0),
+            PressureSpawnRecord::new(50, 20, 1.0, 1),
+        ];
+        let b = [
+            PressureSpawnRecord::new(30, 12, 1.0, 0),
+            PressureSpawnRecord::new(90, 12, 1.0, 0),
+            PressureSpawnRecord::new(400, 12, 1.0, 0),
+            PressureSpawnRecord::new(70, 5, 1.0, 2),
+        ];
+
+        let diff = compare_plans(&a, &b);
+        assert_eq!(
+            diff.species,
+            vec![
+                SpeciesDiff {
+                    species: 0,
+                    count_before: 2,
+                    count_after: 3,
+                    hp_before: 20,
+                    hp_after: 36,
+                    mean_drift_ms: 10.0,
+                    max_drift_ms: 30,
+                },
+                SpeciesDiff {
+                    species: 1,
+                    count_before: 1,
+                    count_after: 0,
+                    hp_before: 20,
+                    hp_after: 0,
+                    mean_drift_ms: 0.0,
+                    max_drift_ms: 0,
+                },
+                SpeciesDiff {
+                    species: 2,
+                    count_before: 0,
+                    count_after: 1,
+                    hp_before: 0,
+                    hp_after: 5,
+                    mean_drift_ms: 0.0,
+                    max_drift_ms: 0,
+                },
+            ]
+        );
+        assert_eq!(diff.count_delta(), 1);
+        assert_eq!(diff.hp_delta(), 1);
+        assert_eq!(diff.end_drift_ms, 300);
+        assert!(!diff.is_unchanged());
+    }
+
+    #[test]
+    fn tuning_changes_show_up_in_the_diff_of_a_generated_wave() {
+        let inputs =
+            PressureWaveInputs::new(5, LevelId::new(1), WaveId::new(2), DifficultyLevel::new(6));
+        let mut generator = PressureV2::default();
+        let (mut baseline, mut prototypes) = (Vec::new(), Vec::new());
+        generator.generate(&inputs, &mut baseline, &mut prototypes);
+        assert!(compare_plans(&baseline, &baseline).is_unchanged());
+
+        generator.tuning_mut().count.cap *= 2.0;
+        generator.tuning_mut().count.minimum *= 2.0;
+        let mut tuned = Vec::new();
+        generator.generate(&inputs, &mut tuned, &mut prototypes);
+        let diff = compare_plans(&baseline, &tuned);
+        assert!(!diff.is_unchanged());
+        assert_eq!(
+            diff.count_delta(),
+            tuned.len() as i64 - baseline.len() as i64
+        );
+    }
+}
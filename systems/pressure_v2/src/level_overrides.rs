@@ -0,0 +1,214 @@
+//! Per-level overrides layered over the global [`PressureTuning`].
+
+use std::collections::BTreeMap;
+
+use maze_defence_core::LevelId;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{PressureTuning, TuningError};
+
+/// Subset of the tuning surface that one level replaces, written as a partial tuning
+/// document such as `{ "speed": { "post_pivot_growth": 1.12 } }`.
+///
+/// Fields left out keep the global value, so an override stays small and survives later
+/// changes to unrelated defaults.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TuningOverride(Map<String, Value>);
+
+impl TuningOverride {
+    /// Returns the override with the field at the dotted `path`, e.g. `speed.deviation`,
+    /// set to `value`.
+    #[must_use]
+    pub fn with(mut self, path: &str, value: impl Into<Value>) -> Self {
+        let mut table = &mut self.0;
+        let mut segments = path.split('.').peekable();
+        while let Some(segment) = segments.next() {
+            if segments.peek().is_none() {
+                let _ = table.insert(segment.to_string(), value.into());
+                break;
+            }
+            let entry = table
+                .entry(segment)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            table = entry.as_object_mut().expect("entry was just made a table");
+        }
+        self
+    }
+
+    /// Dotted paths of every field the override replaces, in lexicographic order.
+    #[must_use]
+    pub fn fields(&self) -> Vec<String> {
+        fn collect(prefix: &str, table: &Map<String, Value>, fields: &mut Vec<String>) {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                match value {
+                    Value::Object(nested) => collect(&path, nested, fields),
+                    _ => fields.push(path),
+                }
+            }
+        }
+        let mut fields = Vec::new();
+        collect("", &self.0, &mut fields);
+        fields.sort();
+        fields
+    }
+
+    /// Applies the override on top of `base` and validates the result.
+    pub fn apply(&self, base: &PressureTuning) -> Result<PressureTuning, TuningError> {
+        let mut document = serde_json::to_value(base)?;
+        if let Value::Object(table) = &mut document {
+            merge(table, &self.0);
+        }
+        let tuning: PressureTuning = serde_json::from_value(document)?;
+        tuning.validate()?;
+        Ok(tuning)
+    }
+}
+
+/// Replaces the values in `target` with those in `patch`, descending into nested tables.
+fn merge(target: &mut Map<String, Value>, patch: &Map<String, Value>) {
+    for (key, value) in patch {
+        match (target.get_mut(key), value) {
+            (Some(Value::Object(nested)), Value::Object(patch)) => merge(nested, patch),
+            _ => {
+                let _ = target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Tuning overrides keyed by the campaign level they apply to.
+///
+/// The generator resolves tuning in a fixed order: it starts from the global tuning and
+/// then applies the override of the level being generated, if any. The outcome is
+/// recorded in [`TuningResolutionTelemetry`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LevelTuningOverrides {
+    levels: BTreeMap<u32, TuningOverride>,
+}
+
+impl LevelTuningOverrides {
+    /// Returns the overrides with `level` using `tuning`, replacing any earlier override.
+    ///
+    /// Fails when the override names an unknown field, has a value of the wrong type or
+    /// would make the default tuning invalid.
+    pub fn with_level(
+        mut self,
+        level: LevelId,
+        tuning: TuningOverride,
+    ) -> Result<Self, TuningError> {
+        let _ = tuning.apply(&PressureTuning::default())?;
+        let _ = self.levels.insert(level.get(), tuning);
+        Ok(self)
+    }
+
+    /// Override registered for `level`, if any.
+    #[must_use]
+    pub fn get(&self, level: LevelId) -> Option<&TuningOverride> {
+        self.levels.get(&level.get())
+    }
+
+    /// Removes the override of `level`, returning it if one was registered.
+    pub fn remove(&mut self, level: LevelId) -> Option<TuningOverride> {
+        self.levels.remove(&level.get())
+    }
+}
+
+/// How the tuning for the most recent wave was resolved.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TuningResolutionTelemetry {
+    /// Level whose override was consulted, or `None` when the level has no override.
+    pub level: Option<LevelId>,
+    /// Dotted paths of the fields the level override replaced, in lexicographic order.
+    pub overridden_fields: Vec<String>,
+    /// Why the level override was ignored in favour of the global tuning, if it was.
+    pub rejected: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PressureV2;
+    use maze_defence_core::{DifficultyLevel, PressureWaveInputs, WaveId};
+
+    #[test]
+    fn level_overrides_replace_only_the_named_fields_of_their_level() {
+        let rush = LevelId::new(2);
+        let overrides = LevelTuningOverrides::default()
+            .with_level(
+                rush,
+                TuningOverride::default()
+                    .with("speed.post_pivot_growth", 1.2)
+                    .with("speed.soft_boost_fraction", 0.9),
+            )
+            .expect("valid override");
+        let mut generator = PressureV2::default().with_level_overrides(overrides);
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+
+        let inputs =
+            |level| PressureWaveInputs::new(7, level, WaveId::new(4), DifficultyLevel::new(6));
+        generator.generate(&inputs(LevelId::new(1)), &mut spawns, &mut prototypes);
+        let plain = generator
+            .telemetry()
+            .difficulty_latents()
+            .speed_mean_multiplier;
+        assert_eq!(
+            generator.telemetry().tuning_resolution(),
+            &TuningResolutionTelemetry::default()
+        );
+
+        generator.generate(&inputs(rush), &mut spawns, &mut prototypes);
+        let resolution = generator.telemetry().tuning_resolution();
+        assert_eq!(resolution.level, Some(rush));
+        assert_eq!(
+            resolution.overridden_fields,
+            ["speed.post_pivot_growth", "speed.soft_boost_fraction"]
+        );
+        assert!(
+            generator
+                .telemetry()
+                .difficulty_latents()
+                .speed_mean_multiplier
+                > plain,
+            "the rush level samples faster bugs"
+        );
+
+        generator.generate(&inputs(LevelId::new(1)), &mut spawns, &mut prototypes);
+        assert_eq!(
+            generator
+                .telemetry()
+                .difficulty_latents()
+                .speed_mean_multiplier,
+            plain,
+            "the global tuning is untouched by a level override"
+        );
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected_up_front() {
+        let unknown = TuningOverride::default().with("speed.typo", 1.0);
+        assert!(LevelTuningOverrides::default()
+            .with_level(LevelId::new(1), unknown)
+            .is_err());
+
+        let inverted = TuningOverride::default().with("speed.max_multiplier", 0.1);
+        assert!(matches!(
+            LevelTuningOverrides::default().with_level(LevelId::new(1), inverted),
+            Err(TuningError::OutOfRange { .. })
+        ));
+
+        let parsed: TuningOverride =
+            serde_json::from_str(r#"{ "count": { "cap": 50.0 } }"#).expect("json override");
+        assert_eq!(parsed, TuningOverride::default().with("count.cap", 50.0));
+    }
+}
@@ -9,16 +9,29 @@
 
 //! Deterministic pressure v2 wave generation system stub.
 
-use std::{cmp::Ordering, num::NonZeroU32};
+mod batch;
+mod diff;
+mod level_overrides;
+mod math;
+mod preview;
+mod tuning_file;
+
+use std::{cmp::Ordering, io, io::Write, num::NonZeroU32};
 
 use macroquad::color::Color as MacroquadColor;
 use maze_defence_core::{
-    BugColor, DifficultyLevel, Health, LevelId, PressureSpawnRecord, PressureWaveInputs,
-    SpeciesPrototype, WaveId,
+    BugArchetype, BugColor, BugRank, BurstGapRange, DifficultyLevel, Gold, Health, LevelId,
+    PressureSpawnRecord, PressureWaveInputs, SpeciesPrototype, WaveId,
 };
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
-use rand_distr::{Distribution, Gamma, Poisson, StandardNormal};
+use serde::{Deserialize, Serialize};
+
+pub use batch::{BatchRange, BatchReport, BatchSweep, WaveSample};
+pub use diff::{compare_plans, PlanDiff, SpeciesDiff};
+pub use level_overrides::{LevelTuningOverrides, TuningOverride, TuningResolutionTelemetry};
+pub use preview::CurvePoint;
+pub use tuning_file::TuningError;
 
 const DEFAULT_RNG_SEED: u64 = 0x8955_06d3_3f6b_11d7;
 const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
@@ -26,9 +39,24 @@ const FNV_PRIME: u64 = 0x0000_0001_0000_01b3;
 const ETA_MIN: f32 = 0.75;
 const ETA_MAX: f32 = 1.5;
 const ETA_BISECTION_STEPS: u32 = 24;
+/// Salt separating the archetype stream from the main generator stream, so sampling
+/// archetypes leaves every other draw of a wave unchanged.
+const ARCHETYPE_SALT: u64 = 0x5be0_cd19_137e_2179;
+/// Salt separating the lane stream from the main generator stream.
+const LANE_SALT: u64 = 0x1f83_d9ab_fb41_bd6b;
+/// Salt separating the smoothing jitter stream from the main generator stream.
+const SMOOTHING_SALT: u64 = 0x9b05_688c_2b3e_6c1f;
+/// Salt separating the burst gap stream from the main generator stream.
+const BURST_SALT: u64 = 0x2545_f491_4f6c_dd1d;
+/// Bounty, in nominal bugs' worth of pressure, from which a species spawns as a scripted boss.
+const BOSS_BOUNTY_THRESHOLD: u64 = 8;
 
 /// Aggregated tuning knobs controlling every adjustable aspect of the pressure generator.
-#[derive(Clone, Debug)]
+///
+/// Serializes to and from tuning data files; omitted fields keep their default values. See
+/// [`PressureTuning::from_reader`] for loading JSON with validation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PressureTuning {
     /// Controls the logistic bug-count curve and sampling spread per §3.2 of the spec.
     pub count: CountTuning,
@@ -42,23 +70,27 @@ pub struct PressureTuning {
     pub pressure_weights: PressureWeightTuning,
     /// Controls cadence, start offsets, duration clamps, and compression behaviour from §6.
     pub cadence: CadenceTuning,
-}
-
-impl Default for PressureTuning {
-    fn default() -> Self {
-        Self {
-            count: CountTuning::default(),
-            hp: HpTuning::default(),
-            speed: SpeedTuning::default(),
-            components: ComponentTuning::default(),
-            pressure_weights: PressureWeightTuning::default(),
-            cadence: CadenceTuning::default(),
-        }
-    }
+    /// Keeps species tints clear of the map palette so bugs never blend into the board.
+    pub tint: TintTuning,
+    /// Opt-in conversion of one component into a sturdier, scarcer elite species.
+    pub elite: EliteTuning,
+    /// Chance of each bug archetype per component and the pressure each one costs.
+    pub archetypes: ArchetypeTuning,
+    /// Opt-in assignment of every component to its own spawn lane.
+    pub lanes: LaneTuning,
+    /// Opt-in grouping of each species' spawns into bursts separated by gaps.
+    pub bursts: BurstTuning,
+    /// Opt-in pass spreading out spawns that bunch up at the same moment.
+    pub smoothing: SmoothingTuning,
+    /// Opt-in adjustment of the bug-count and HP means from the previous wave's outcome.
+    pub adaptation: AdaptationTuning,
+    /// Hard ceilings on the size of specific waves.
+    pub budget: BudgetTuning,
 }
 
 /// Bug-count logistic curve and sampling parameters.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CountTuning {
     /// Lower-asymptote bug count C_min; raising this inflates how many bugs appear at tutorial difficulty.
     pub minimum: f32,
@@ -88,7 +120,8 @@ impl Default for CountTuning {
 }
 
 /// HP latent parameters controlling wave durability.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct HpTuning {
     /// Amplitude of the early additive HP boost h_soft; larger values make low-D waves sturdier immediately.
     pub soft_boost_fraction: f32,
@@ -124,7 +157,8 @@ impl Default for HpTuning {
 }
 
 /// Speed latent parameters controlling wave pacing.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SpeedTuning {
     /// Amplitude of the early additive speed boost analogous to h_soft; higher values quicken low-D waves.
     pub soft_boost_fraction: f32,
@@ -157,7 +191,8 @@ impl Default for SpeedTuning {
 }
 
 /// Parameters that control provisional component sampling and merging.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ComponentTuning {
     /// Baseline κ(D) intercept; increasing this raises the expected component count even at low difficulty.
     pub poisson_intercept: f32,
@@ -183,6 +218,8 @@ pub struct ComponentTuning {
     pub speed_multiplier_min: f32,
     /// Maximum speed multiplier allowed for component centres before scaling.
     pub speed_multiplier_max: f32,
+    /// Strategy picking each species' tint.
+    pub palette: TintPalette,
 }
 
 impl Default for ComponentTuning {
@@ -200,12 +237,61 @@ impl Default for ComponentTuning {
             hp_multiplier_spread: 1.6,
             speed_multiplier_min: 0.6,
             speed_multiplier_max: 2.4,
+            palette: TintPalette::default(),
+        }
+    }
+}
+
+/// Strategy picking the tints that tell species apart.
+///
+/// Every strategy skips colours already taken by the wave or too close to the
+/// [`TintTuning::excluded`] board colours, and falls back to deterministic hues once its
+/// own colours run out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TintPalette {
+    /// Hue, saturation and value drawn from the wave's RNG stream.
+    #[default]
+    RandomHsv,
+    /// The eight Okabe-Ito colours, which stay distinguishable under the common forms of
+    /// colour blindness.
+    OkabeIto,
+    /// A fixed hand-picked palette, identical for every wave.
+    Designer,
+}
+
+impl TintPalette {
+    /// Colours a fixed palette hands out in order, or `None` for random tints.
+    fn fixed_colors(self) -> Option<&'static [(u8, u8, u8)]> {
+        match self {
+            Self::RandomHsv => None,
+            Self::OkabeIto => Some(&[
+                (230, 159, 0),
+                (86, 180, 233),
+                (213, 94, 0),
+                (0, 114, 178),
+                (204, 121, 167),
+                (0, 158, 115),
+                (240, 228, 66),
+                (0, 0, 0),
+            ]),
+            Self::Designer => Some(&[
+                (231, 76, 60),
+                (52, 152, 219),
+                (155, 89, 182),
+                (243, 156, 18),
+                (236, 240, 241),
+                (26, 188, 156),
+                (233, 30, 99),
+                (121, 85, 72),
+            ]),
         }
     }
 }
 
 /// Weighting parameters used by the pressure alignment function.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PressureWeightTuning {
     /// Linear HP weight α in pressure(hp, v); increasing this makes toughness dominate the pressure budget.
     pub alpha: f32,
@@ -226,7 +312,8 @@ impl Default for PressureWeightTuning {
 }
 
 /// Cadence, start offset, and duration tuning parameters.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CadenceTuning {
     /// Asymptotic minimum cadence cad_floor enforced before and after compression.
     pub cadence_floor_ms: u32,
@@ -270,10 +357,376 @@ impl Default for CadenceTuning {
     }
 }
 
+/// Species tint contrast requirements against the map palette.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TintTuning {
+    /// Board colours as `[red, green, blue]` that species tints must stand apart from; the
+    /// defaults are the grass ground, grid lines, perimeter wall and projectiles.
+    pub excluded: Vec<[u8; 3]>,
+    /// Minimum CIE76 ΔE between a species tint and every excluded colour; 2.3 is about the
+    /// smallest difference a viewer notices.
+    pub min_delta_e: f32,
+}
+
+impl Default for TintTuning {
+    fn default() -> Self {
+        Self {
+            excluded: vec![[85, 142, 52], [31, 54, 22], [68, 45, 15], [242, 235, 64]],
+            min_delta_e: 20.0,
+        }
+    }
+}
+
+impl TintTuning {
+    /// Smallest ΔE between `rgb` and any excluded colour, or infinity without exclusions.
+    fn separation(&self, rgb: (u8, u8, u8)) -> f32 {
+        let lab = Lab::from_rgb(rgb);
+        self.excluded
+            .iter()
+            .map(|&[red, green, blue]| lab.delta_e(Lab::from_rgb((red, green, blue))))
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+/// Archetype sampling parameters.
+///
+/// Each component draws one archetype with probability proportional to its weight. The
+/// pressure factors scale a component's per-bug pressure, so η alignment budgets armored,
+/// regenerating and splitting bugs as the tougher threats they are.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchetypeTuning {
+    /// Relative chance that a component has no special ability.
+    pub basic_weight: f32,
+    /// Relative chance that a component is armored.
+    pub armored_weight: f32,
+    /// Relative chance that a component regenerates.
+    pub regenerating_weight: f32,
+    /// Relative chance that a component splits on death.
+    pub splitter_weight: f32,
+    /// Pressure of an armored bug relative to a basic bug with the same HP and speed.
+    pub armored_pressure: f32,
+    /// Pressure of a regenerating bug relative to a basic bug with the same HP and speed.
+    pub regenerating_pressure: f32,
+    /// Pressure of a splitter relative to a basic bug with the same HP and speed.
+    pub splitter_pressure: f32,
+}
+
+impl Default for ArchetypeTuning {
+    fn default() -> Self {
+        Self {
+            basic_weight: 4.0,
+            armored_weight: 1.0,
+            regenerating_weight: 1.0,
+            splitter_weight: 1.0,
+            armored_pressure: 1.6,
+            regenerating_pressure: 1.25,
+            splitter_pressure: 1.5,
+        }
+    }
+}
+
+impl ArchetypeTuning {
+    /// Sampling weight of `archetype`.
+    fn weight(&self, archetype: BugArchetype) -> f32 {
+        match archetype {
+            BugArchetype::Basic => self.basic_weight,
+            BugArchetype::Armored => self.armored_weight,
+            BugArchetype::Regenerating => self.regenerating_weight,
+            BugArchetype::Splitter => self.splitter_weight,
+        }
+    }
+
+    /// Per-bug pressure factor of `archetype`.
+    fn pressure(&self, archetype: BugArchetype) -> f32 {
+        match archetype {
+            BugArchetype::Basic => 1.0,
+            BugArchetype::Armored => self.armored_pressure,
+            BugArchetype::Regenerating => self.regenerating_pressure,
+            BugArchetype::Splitter => self.splitter_pressure,
+        }
+    }
+
+    /// Archetype whose cumulative weight band contains `draw`, a uniform sample in `[0, 1)`.
+    fn pick(&self, draw: f32) -> BugArchetype {
+        let total: f32 = BugArchetype::ALL
+            .iter()
+            .map(|archetype| self.weight(*archetype))
+            .sum();
+        let mut remaining = draw * total;
+        for archetype in BugArchetype::ALL {
+            let weight = self.weight(archetype);
+            if weight > 0.0 && remaining < weight {
+                return archetype;
+            }
+            remaining -= weight;
+        }
+        BugArchetype::Basic
+    }
+}
+
+/// Elite species injection applied to hard waves after species sampling.
+///
+/// The component with the highest per-bug pressure becomes the elite: its HP and speed are
+/// scaled and its bug count shrinks, so late waves mix a few tough bugs into the swarm.
+/// η alignment then rescales every species alike, keeping the elite distinct.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EliteTuning {
+    /// Whether the stage runs at all; disabled by default so existing tunings are unchanged.
+    pub enabled: bool,
+    /// Lowest difficulty level whose waves receive an elite species.
+    pub min_difficulty: f32,
+    /// Factor applied to the elite component's HP before η alignment.
+    pub hp_multiplier: f32,
+    /// Factor applied to the elite component's speed before η alignment.
+    pub speed_multiplier: f32,
+    /// Share of the component's bugs kept as elites, rounded up to at least one bug.
+    pub count_fraction: f32,
+}
+
+impl Default for EliteTuning {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_difficulty: 8.0,
+            hp_multiplier: 3.0,
+            speed_multiplier: 0.85,
+            count_fraction: 0.25,
+        }
+    }
+}
+
+/// Spawn lane assignment applied to the final components.
+///
+/// Components take consecutive lanes starting from a per-wave offset, so each species enters
+/// from its own rim segment. With more components than lanes the assignment wraps around
+/// round-robin; the world does the same when a level has fewer spawn patches than lanes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LaneTuning {
+    /// Whether components are given lanes; disabled by default so bugs use any spawner.
+    pub enabled: bool,
+    /// Number of lanes the components are spread across.
+    pub lanes: u32,
+}
+
+impl Default for LaneTuning {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lanes: 4,
+        }
+    }
+}
+
+/// Anti-burst smoothing applied to the final spawn schedule.
+///
+/// Species sampled with similar start offsets and cadences can land several spawns on the
+/// same millisecond, which floods the cells around a spawner. Whenever more than
+/// `max_simultaneous` spawns fall inside one `window_ms`, the later ones are pushed past the
+/// window plus a small jitter, so the schedule keeps its shape but never stacks up.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmoothingTuning {
+    /// Whether the pass runs; disabled by default so existing tunings are unchanged.
+    pub enabled: bool,
+    /// Most spawns allowed inside any one window.
+    pub max_simultaneous: u32,
+    /// Length of the window in milliseconds.
+    pub window_ms: u32,
+    /// Largest extra delay drawn for each spawn pushed out of a crowded window.
+    pub jitter_ms: u32,
+}
+
+impl Default for SmoothingTuning {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_simultaneous: 2,
+            window_ms: 100,
+            jitter_ms: 50,
+        }
+    }
+}
+
+/// Gives every species a rhythm: runs of `burst_size` spawns at the species' cadence,
+/// separated by pauses drawn from `gap`.
+///
+/// Each pause is drawn uniformly from the inclusive gap range and replaces one cadence step.
+/// Cadence compression shortens the pauses along with the cadences. Waves grouped here are
+/// reported by [`PressureV2::paced_last_wave`], so the world keeps these bursts instead of
+/// re-timing species with its own burst scheduler.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BurstTuning {
+    /// Whether spawns are grouped; disabled by default so existing tunings are unchanged.
+    pub enabled: bool,
+    /// Spawns in every burst; the last burst of a species may be shorter.
+    pub burst_size: u32,
+    /// Range the pause between two bursts is drawn from, in milliseconds.
+    pub gap: BurstGapRange,
+}
+
+impl Default for BurstTuning {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            burst_size: 5,
+            gap: BurstGapRange::new(
+                NonZeroU32::new(1_500).expect("non-zero gap min"),
+                NonZeroU32::new(3_000).expect("non-zero gap max"),
+            ),
+        }
+    }
+}
+
+/// Adapts waves to the player by shifting the bug-count and HP means by how the previous
+/// wave went.
+///
+/// Clearing the previous wave faster than `par_clear_ms` raises both means, a slower clear
+/// lowers them, and every leaked bug lowers them further. Only the means move, so a given
+/// seed and history always produce the same wave.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdaptationTuning {
+    /// Whether history is read; disabled by default so waves depend on their seed alone.
+    pub enabled: bool,
+    /// Clear time at which the means stay put, in milliseconds.
+    pub par_clear_ms: u32,
+    /// Fraction the means rise for an instant clear, scaled down linearly towards par.
+    pub pace_weight: f32,
+    /// Fraction the means drop for every leaked bug.
+    pub leak_relief: f32,
+    /// Largest fraction the means may move in either direction.
+    pub max_shift: f32,
+}
+
+impl Default for AdaptationTuning {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            par_clear_ms: 60_000,
+            pace_weight: 0.2,
+            leak_relief: 0.05,
+            max_shift: 0.3,
+        }
+    }
+}
+
+/// Absolute ceilings on individual waves, enforced after η alignment.
+///
+/// Unlike the latent curves, the ceilings hold whatever the rest of the tuning says, so a
+/// campaign can guarantee an early wave stays survivable. Register a per-level list through
+/// [`LevelTuningOverrides`]; an override replaces the whole list.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetTuning {
+    /// Ceilings of every capped wave; waves without an entry are unbounded.
+    pub waves: Vec<WaveBudget>,
+}
+
+impl BudgetTuning {
+    /// Ceilings of `wave`, if it is capped.
+    #[must_use]
+    pub fn for_wave(&self, wave: WaveId) -> Option<&WaveBudget> {
+        self.waves.iter().find(|budget| budget.wave == wave.get())
+    }
+}
+
+/// Ceilings of one wave; omitted ceilings leave that dimension unbounded.
+///
+/// The bug count is trimmed first, then every bug's health until the total health fits, then
+/// every bug's health again until the pressure fits; speeds are left alone. Every species
+/// keeps at least one bug with at least 1 HP, so a ceiling below that floor is as close as
+/// the wave gets.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveBudget {
+    /// Zero-based index of the capped wave.
+    pub wave: u32,
+    /// Most bugs the wave may spawn.
+    pub max_bug_count: Option<u32>,
+    /// Most health the wave's bugs may carry combined.
+    pub max_total_hp: Option<u64>,
+    /// Most pressure the wave may carry combined.
+    pub max_pressure: Option<f32>,
+}
+
+/// Colour in CIE L*a*b* space under the D65 white point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Lab {
+    lightness: f32,
+    a: f32,
+    b: f32,
+}
+
+impl Lab {
+    fn from_rgb((red, green, blue): (u8, u8, u8)) -> Self {
+        fn linear(channel: u8) -> f32 {
+            let value = f32::from(channel) / 255.0;
+            if value <= 0.040_45 {
+                value / 12.92
+            } else {
+                math::powf((value + 0.055) / 1.055, 2.4)
+            }
+        }
+        fn pivot(value: f32) -> f32 {
+            if value > 0.008_856 {
+                math::powf(value, 1.0 / 3.0)
+            } else {
+                7.787 * value + 16.0 / 116.0
+            }
+        }
+
+        let (r, g, b) = (linear(red), linear(green), linear(blue));
+        let x = pivot((0.412_4 * r + 0.357_6 * g + 0.180_5 * b) / 0.950_47);
+        let y = pivot(0.212_6 * r + 0.715_2 * g + 0.072_2 * b);
+        let z = pivot((0.019_3 * r + 0.119_2 * g + 0.950_5 * b) / 1.088_83);
+        Self {
+            lightness: 116.0 * y - 16.0,
+            a: 500.0 * (x - y),
+            b: 200.0 * (y - z),
+        }
+    }
+
+    /// CIE76 colour difference.
+    fn delta_e(self, other: Self) -> f32 {
+        let dl = self.lightness - other.lightness;
+        let da = self.a - other.a;
+        let db = self.b - other.b;
+        (dl * dl + da * da + db * db).sqrt()
+    }
+}
+
+/// RNG draw-order schema used by [`PressureV2::generate`].
+///
+/// A change to the order or number of draws ships as a new variant next to the old ones,
+/// so seeds and replays recorded under an older schema keep generating the waves they were
+/// recorded with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorVersion {
+    /// Count, HP and speed latents, components, then cadence and tints, as documented in
+    /// `generate_v1`.
+    #[default]
+    V1,
+}
+
+impl GeneratorVersion {
+    /// Every schema, oldest first.
+    pub const ALL: [Self; 1] = [Self::V1];
+    /// Newest schema, used by generators that do not pin a version.
+    pub const LATEST: Self = Self::V1;
+}
+
 /// Stub implementation of the pressure v2 generator.
 #[derive(Debug)]
 pub struct PressureV2 {
+    version: GeneratorVersion,
     tuning: PressureTuning,
+    level_overrides: LevelTuningOverrides,
     rng: ChaCha8Rng,
     telemetry: PressureTelemetry,
     work: WaveWork,
@@ -290,32 +743,81 @@ impl PressureV2 {
     #[must_use]
     pub fn new(tuning: PressureTuning) -> Self {
         Self {
+            version: GeneratorVersion::LATEST,
             tuning,
+            level_overrides: LevelTuningOverrides::default(),
             rng: ChaCha8Rng::seed_from_u64(DEFAULT_RNG_SEED),
             telemetry: PressureTelemetry::default(),
             work: WaveWork::default(),
         }
     }
 
+    /// Returns the generator with `overrides` layered over the global tuning of their levels.
+    #[must_use]
+    pub fn with_level_overrides(mut self, overrides: LevelTuningOverrides) -> Self {
+        self.level_overrides = overrides;
+        self
+    }
+
+    /// Returns the generator pinned to the RNG draw order of `version`.
+    #[must_use]
+    pub fn with_generator_version(mut self, version: GeneratorVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Returns the RNG draw-order schema waves are generated with.
+    pub fn generator_version(&self) -> GeneratorVersion {
+        self.version
+    }
+
     /// Returns a mutable reference to the global tuning knobs so designers can adjust wave behaviour.
     pub fn tuning_mut(&mut self) -> &mut PressureTuning {
         &mut self.tuning
     }
 
+    /// Returns a mutable reference to the per-level tuning overrides.
+    pub fn level_overrides_mut(&mut self) -> &mut LevelTuningOverrides {
+        &mut self.level_overrides
+    }
+
+    /// Reports whether the most recent wave's spawn times came out of the burst scheduler
+    /// or the smoothing pass, in which case its plan should be marked with
+    /// [`PressureWavePlan::with_paced`] so the world does not re-time it.
+    ///
+    /// [`PressureWavePlan::with_paced`]: maze_defence_core::PressureWavePlan::with_paced
+    pub fn paced_last_wave(&self) -> bool {
+        self.work.spawn_times_paced
+    }
+
     /// Returns the most recent telemetry snapshot emitted by the generator.
     pub fn telemetry(&self) -> &PressureTelemetry {
         &self.telemetry
     }
 
-    /// Generates v2 pressure spawns according to the provided inputs.
+    /// Generates v2 pressure spawns according to the provided inputs, drawing random
+    /// numbers in the order of [`Self::generator_version`].
     pub fn generate(
         &mut self,
         inputs: &PressureWaveInputs,
         spawns: &mut Vec<PressureSpawnRecord>,
         prototypes: &mut Vec<SpeciesPrototype>,
+    ) {
+        match self.version {
+            GeneratorVersion::V1 => self.generate_v1(inputs, spawns, prototypes),
+        }
+    }
+
+    fn generate_v1(
+        &mut self,
+        inputs: &PressureWaveInputs,
+        spawns: &mut Vec<PressureSpawnRecord>,
+        prototypes: &mut Vec<SpeciesPrototype>,
     ) {
         self.reseed_rng(inputs);
         // RNG draw order (documented for determinism auditing):
+        //   History adaptation: `history_adaptation` scales the means of
+        //      draws #1 and #2 when `adaptation.enabled` and consumes no draws.
         //   1: `draw_bug_count` pulls a truncated normal using
         //      `PressureTuning::count.{deviation_ratio,floor,cap}`.
         //   2: `draw_hp_multiplier` pulls a truncated normal using
@@ -327,29 +829,79 @@ impl PressureV2 {
         //   4: `draw_raw_component_count` samples the Poisson proposal with
         //      `PressureTuning::components.{poisson_intercept,poisson_slope}`.
         //   5-6 per provisional component: `populate_component_centres` consumes
-        //      two `StandardNormal` draws to build log-space HP/speed using the
+        //      two standard normal draws to build log-space HP/speed using the
         //      `components.log_*` sigmas, correlation, and dynamic multiplier
         //      clamps.
         //   7+ per provisional component: `allocate_dirichlet_counts` draws
         //      Gammas parameterised by `components.dirichlet_concentration`.
+        //   Archetypes: `assign_component_archetypes` draws one uniform sample
+        //      per surviving component from a separate stream salted with
+        //      `ARCHETYPE_SALT`, weighted by `archetypes.*_weight`; the main
+        //      stream is untouched.
+        //   Elite injection: `inject_elite_component` picks its component
+        //      deterministically and consumes no draws.
+        //   Budget ceilings: `enforce_budget_caps` consumes no draws.
+        //   Lanes: `assign_component_lanes` draws one lane offset from a
+        //      separate stream salted with `LANE_SALT` when `lanes.enabled`.
         //   Cadence realisation: for each surviving component,
         //      `sample_cadence_and_start_offsets` pulls a cadence draw bounded
         //      by `cadence_floor_ms`/`cadence_max_ms` and a start-offset draw
         //      capped by `start_max_ms` with deviations derived from
         //      `cadence_deviation_ratio`/`start_deviation_ratio`.
-        //   Tint assignment: `draw_unique_tint` consumes hue, saturation, then
-        //      value for each component before falling back to deterministic
-        //      hues when the random attempts collide.
+        //   Bursts: `draw_burst_gaps` draws one gap per burst after a species'
+        //      first from a separate stream salted with `BURST_SALT` when
+        //      `bursts.enabled`.
+        //   Spawn smoothing: `smooth_spawn_schedule` draws one jitter per
+        //      delayed spawn from a separate stream salted with
+        //      `SMOOTHING_SALT` when `smoothing.enabled`.
+        //   Tint assignment: with the `RandomHsv` palette `draw_unique_tint`
+        //      consumes hue, saturation, then value for each component before
+        //      falling back to deterministic hues when the random attempts
+        //      collide or sit within `tint.min_delta_e` of a `tint.excluded`
+        //      palette colour. The fixed palettes consume no draws.
         self.telemetry.reset();
         self.telemetry.ensure_placeholders();
+        self.telemetry.inputs = Some(inputs.clone());
+        let global = self
+            .resolve_level_tuning(inputs.level_id())
+            .map(|tuning| std::mem::replace(&mut self.tuning, tuning));
         self.work.reset();
         self.compute_difficulty_latents(inputs);
         self.sample_provisional_species(inputs);
+        self.assign_component_archetypes(inputs);
+        self.inject_elite_component(inputs);
+        self.assign_component_lanes(inputs);
         self.align_pressure_with_eta();
+        self.enforce_budget_caps(inputs);
         self.sample_cadence_and_start_offsets(inputs);
         self.enforce_duration_caps(inputs);
+        self.smooth_spawn_schedule(inputs);
+        self.work.spawn_times_paced = self.tuning.bursts.enabled || self.tuning.smoothing.enabled;
         self.write_final_spawn_records(spawns);
         self.write_final_species_prototypes(prototypes);
+        if let Some(global) = global {
+            self.tuning = global;
+        }
+    }
+
+    /// Applies the override of `level` to the global tuning and records the outcome.
+    ///
+    /// An override that no longer validates against the current global tuning is ignored,
+    /// so the wave is generated from the global tuning alone.
+    fn resolve_level_tuning(&mut self, level: LevelId) -> Option<PressureTuning> {
+        let tuning_override = self.level_overrides.get(level)?;
+        let resolution = &mut self.telemetry.tuning_resolution;
+        resolution.level = Some(level);
+        match tuning_override.apply(&self.tuning) {
+            Ok(tuning) => {
+                resolution.overridden_fields = tuning_override.fields();
+                Some(tuning)
+            }
+            Err(error) => {
+                resolution.rejected = Some(error.to_string());
+                None
+            }
+        }
     }
 
     fn reseed_rng(&mut self, inputs: &PressureWaveInputs) {
@@ -364,15 +916,18 @@ impl PressureV2 {
 
     fn compute_difficulty_latents(&mut self, inputs: &PressureWaveInputs) {
         let difficulty = inputs.difficulty().get() as f32;
-        let count_latent = self.draw_bug_count(difficulty);
-        let hp_latent = self.draw_hp_multiplier(difficulty);
+        let adaptation = self.history_adaptation(inputs);
+        let count_latent = self
+            .draw_bug_count(difficulty, adaptation)
+            .with_share(inputs.pressure_share());
+        let hp_latent = self.draw_hp_multiplier(difficulty, adaptation);
         let speed_latent = self.draw_speed_multiplier(difficulty);
 
         let hp_wave = BASE_HP * hp_latent.multiplier;
         let speed_wave = speed_latent.multiplier;
         let per_bug_pressure = self.tuning.pressure_weights.alpha * hp_wave
             + self.tuning.pressure_weights.beta
-                * speed_wave.powf(self.tuning.pressure_weights.gamma);
+                * math::powf(speed_wave, self.tuning.pressure_weights.gamma);
         let pressure_target = (count_latent.sampled as f32 * per_bug_pressure).round() as u32;
 
         let difficulty_work = &mut self.work.difficulty;
@@ -409,8 +964,20 @@ impl PressureV2 {
         debug_assert!(self.work.per_bug_pressure >= 0.0);
     }
 
-    fn draw_bug_count(&mut self, difficulty: f32) -> CountLatent {
-        let logistic = self.count_mean(difficulty);
+    /// Factor the bug-count and HP means are scaled by for the previous wave's outcome;
+    /// `1.0` without history or while `adaptation.enabled` is unset.
+    fn history_adaptation(&self, inputs: &PressureWaveInputs) -> f32 {
+        let tuning = &self.tuning.adaptation;
+        let Some(history) = inputs.history().filter(|_| tuning.enabled) else {
+            return 1.0;
+        };
+        let pace = 1.0 - history.clear_time_ms as f32 / tuning.par_clear_ms as f32;
+        let shift = tuning.pace_weight * pace.max(-1.0) - tuning.leak_relief * history.leaks as f32;
+        1.0 + shift.clamp(-tuning.max_shift, tuning.max_shift)
+    }
+
+    fn draw_bug_count(&mut self, difficulty: f32, adaptation: f32) -> CountLatent {
+        let logistic = self.count_mean(difficulty) * adaptation;
         let deviation = logistic * self.tuning.count.deviation_ratio;
         let floor = self.tuning.count.floor as f32;
         // RNG draw #1: bug count latent truncated normal sample using
@@ -430,8 +997,8 @@ impl PressureV2 {
         }
     }
 
-    fn draw_hp_multiplier(&mut self, difficulty: f32) -> HpLatent {
-        let mean_multiplier = self.hp_mean_multiplier(difficulty);
+    fn draw_hp_multiplier(&mut self, difficulty: f32, adaptation: f32) -> HpLatent {
+        let mean_multiplier = self.hp_mean_multiplier(difficulty) * adaptation;
         let max_multiplier = self.hp_multiplier_upper_bound_from_mean(mean_multiplier);
         // RNG draw #2: HP multiplier truncated normal sample controlled by
         // `hp.deviation` and clamped to `hp.min_multiplier` and the dynamic
@@ -486,6 +1053,101 @@ impl PressureV2 {
         self.enforce_minimum_share();
     }
 
+    /// Samples an archetype for every surviving component and scales its pressure weight by
+    /// the archetype's pressure factor.
+    fn assign_component_archetypes(&mut self, inputs: &PressureWaveInputs) {
+        let seed = wave_seed_hash(
+            inputs.game_seed(),
+            inputs.level_id(),
+            inputs.wave(),
+            inputs.difficulty(),
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ ARCHETYPE_SALT);
+        let tuning = &self.tuning.archetypes;
+        for component in &mut self.work.provisional_species {
+            let archetype = tuning.pick(rng.gen());
+            component.archetype = archetype;
+            component.pressure_weight_pre *= tuning.pressure(archetype);
+            component.pressure_weight_post = component.pressure_weight_pre;
+        }
+    }
+
+    /// Converts the component with the highest per-bug pressure into the wave's elite.
+    ///
+    /// Runs only when `elite.enabled` is set, the difficulty reaches `elite.min_difficulty`
+    /// and at least two species survived merging, so elites always stand out against a
+    /// regular species. Consumes no RNG draws.
+    fn inject_elite_component(&mut self, inputs: &PressureWaveInputs) {
+        let tuning = &self.tuning.elite;
+        let difficulty = inputs.difficulty().get() as f32;
+        if !tuning.enabled
+            || difficulty < tuning.min_difficulty
+            || self.work.provisional_species.len() < 2
+        {
+            return;
+        }
+
+        let (index, _) = self
+            .work
+            .provisional_species
+            .iter()
+            .enumerate()
+            .fold(
+                None,
+                |best: Option<(usize, f32)>, (index, component)| match best {
+                    Some((_, weight)) if weight >= component.pressure_weight_pre => best,
+                    _ => Some((index, component.pressure_weight_pre)),
+                },
+            )
+            .expect("at least two components are present");
+
+        let weights = &self.tuning.pressure_weights;
+        let component = &mut self.work.provisional_species[index];
+        let count_before = component.bug_count;
+        let count_after = ((count_before as f32 * tuning.count_fraction).ceil() as u32)
+            .clamp(1, count_before.max(1));
+        component.hp_pre *= tuning.hp_multiplier;
+        component.speed_pre *= tuning.speed_multiplier;
+        component.pressure_weight_pre = (weights.alpha * component.hp_pre
+            + weights.beta * math::powf(component.speed_pre, weights.gamma))
+            * self.tuning.archetypes.pressure(component.archetype);
+        component.hp_post = component.hp_pre;
+        component.speed_post = component.speed_pre;
+        component.pressure_weight_post = component.pressure_weight_pre;
+        component.bug_count = count_after;
+
+        let (hp_multiplier, speed_multiplier) = (tuning.hp_multiplier, tuning.speed_multiplier);
+        let telemetry = self.telemetry.elite_mut();
+        telemetry.component = index as u32;
+        telemetry.bug_count_before = count_before;
+        telemetry.bug_count_after = count_after;
+        telemetry.hp_multiplier = hp_multiplier;
+        telemetry.speed_multiplier = speed_multiplier;
+    }
+
+    /// Gives the components consecutive spawn lanes, wrapping around `lanes.lanes`.
+    ///
+    /// The first lane is drawn per wave so the same species slot does not always enter from
+    /// the same rim segment.
+    fn assign_component_lanes(&mut self, inputs: &PressureWaveInputs) {
+        let tuning = &self.tuning.lanes;
+        if !tuning.enabled || tuning.lanes == 0 {
+            return;
+        }
+        let seed = wave_seed_hash(
+            inputs.game_seed(),
+            inputs.level_id(),
+            inputs.wave(),
+            inputs.difficulty(),
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ LANE_SALT);
+        let lanes = tuning.lanes;
+        let offset = rng.gen_range(0..lanes);
+        for (index, component) in self.work.provisional_species.iter_mut().enumerate() {
+            component.lane = Some((offset + index as u32) % lanes);
+        }
+    }
+
     // §5.2 fixed-step bisection
     fn align_pressure_with_eta(&mut self) {
         if self.work.provisional_species.is_empty() {
@@ -531,12 +1193,14 @@ impl PressureV2 {
         };
 
         let weights = &self.tuning.pressure_weights;
+        let archetypes = &self.tuning.archetypes;
         let mut realised_pressure = 0.0;
         for component in self.work.provisional_species.iter_mut() {
             let hp_post = eta * component.hp_pre;
             let speed_post = eta * component.speed_pre;
-            let pressure_weight_post =
-                weights.alpha * hp_post + weights.beta * speed_post.powf(weights.gamma);
+            let pressure_weight_post = (weights.alpha * hp_post
+                + weights.beta * math::powf(speed_post, weights.gamma))
+                * archetypes.pressure(component.archetype);
             component.hp_post = hp_post;
             component.speed_post = speed_post;
             component.pressure_weight_post = pressure_weight_post;
@@ -554,6 +1218,125 @@ impl PressureV2 {
         telemetry.pressure_after_eta = realised_pressure;
     }
 
+    fn enforce_budget_caps(&mut self, inputs: &PressureWaveInputs) {
+        let Some(budget) = self.tuning.budget.for_wave(inputs.wave()).cloned() else {
+            return;
+        };
+        if self.work.provisional_species.is_empty() {
+            return;
+        }
+
+        let bug_count_before = self.total_bug_count();
+        let total_hp_before = self.total_hp();
+        let pressure_before = self.total_pressure_for_hp_scale(1.0);
+
+        let bug_count_capped = budget
+            .max_bug_count
+            .is_some_and(|cap| bug_count_before > cap);
+        if let Some(cap) = budget.max_bug_count.filter(|_| bug_count_capped) {
+            // Trims the largest species first, keeping the lowest index on ties.
+            let mut excess = bug_count_before - cap;
+            while excess > 0 {
+                let Some(largest) = self
+                    .work
+                    .provisional_species
+                    .iter_mut()
+                    .filter(|component| component.bug_count > 1)
+                    .rev()
+                    .max_by_key(|component| component.bug_count)
+                else {
+                    break;
+                };
+                largest.bug_count -= 1;
+                excess -= 1;
+            }
+        }
+
+        let total_hp = self.total_hp();
+        let total_hp_capped = budget.max_total_hp.is_some_and(|cap| total_hp > cap);
+        if let Some(cap) = budget.max_total_hp.filter(|_| total_hp_capped) {
+            let scale = cap as f32 / total_hp as f32;
+            for component in self.work.provisional_species.iter_mut() {
+                component.hp_post = (component.hp_post.round() * scale).floor().max(1.0);
+            }
+        }
+
+        let pressure = self.total_pressure_for_hp_scale(1.0);
+        let pressure_capped = budget.max_pressure.is_some_and(|cap| pressure > cap);
+        if let Some(cap) = budget.max_pressure.filter(|_| pressure_capped) {
+            let (mut lower, mut upper) = (0.0_f32, 1.0_f32);
+            for _ in 0..ETA_BISECTION_STEPS {
+                let midpoint = 0.5 * (lower + upper);
+                if self.total_pressure_for_hp_scale(midpoint) > cap {
+                    upper = midpoint;
+                } else {
+                    lower = midpoint;
+                }
+            }
+            for component in self.work.provisional_species.iter_mut() {
+                component.hp_post = (component.hp_post * lower).max(1.0);
+            }
+        }
+
+        let weights = &self.tuning.pressure_weights;
+        let archetypes = &self.tuning.archetypes;
+        for component in self.work.provisional_species.iter_mut() {
+            component.pressure_weight_post = (weights.alpha * component.hp_post
+                + weights.beta * math::powf(component.speed_post, weights.gamma))
+                * archetypes.pressure(component.archetype);
+        }
+
+        let bug_count_after = self.total_bug_count();
+        let total_hp_after = self.total_hp();
+        let pressure_after = self.total_pressure_for_hp_scale(1.0);
+        let telemetry = self.telemetry.budget_mut();
+        telemetry.wave = budget.wave;
+        telemetry.bug_count_capped = bug_count_capped;
+        telemetry.total_hp_capped = total_hp_capped;
+        telemetry.pressure_capped = pressure_capped;
+        telemetry.bug_count_before = bug_count_before;
+        telemetry.bug_count_after = bug_count_after;
+        telemetry.total_hp_before = total_hp_before;
+        telemetry.total_hp_after = total_hp_after;
+        telemetry.pressure_before = pressure_before;
+        telemetry.pressure_after = pressure_after;
+    }
+
+    fn total_bug_count(&self) -> u32 {
+        self.work
+            .provisional_species
+            .iter()
+            .map(|component| component.bug_count)
+            .sum()
+    }
+
+    /// Combined health of the wave as spawned, with every bug's health rounded.
+    fn total_hp(&self) -> u64 {
+        self.work
+            .provisional_species
+            .iter()
+            .map(|component| {
+                u64::from(component.bug_count)
+                    * component.hp_post.round().clamp(1.0, u32::MAX as f32) as u64
+            })
+            .sum()
+    }
+
+    /// Combined pressure of the wave with every bug's health scaled by `scale`.
+    fn total_pressure_for_hp_scale(&self, scale: f32) -> f32 {
+        let weights = &self.tuning.pressure_weights;
+        self.work
+            .provisional_species
+            .iter()
+            .fold(0.0, |acc, component| {
+                let hp = (scale * component.hp_post).max(1.0);
+                let per_bug = (weights.alpha * hp
+                    + weights.beta * math::powf(component.speed_post, weights.gamma))
+                    * self.tuning.archetypes.pressure(component.archetype);
+                acc + component.bug_count as f32 * per_bug
+            })
+    }
+
     fn sample_cadence_and_start_offsets(&mut self, inputs: &PressureWaveInputs) {
         if self.work.provisional_species.is_empty() {
             return;
@@ -594,15 +1377,36 @@ impl PressureV2 {
             );
             let start_offset = start_sample.round().clamp(0.0, start_max) as u32;
             component.start_offset_ms = start_offset;
+        }
 
-            component.spawn_times.clear();
-            component.spawn_times.reserve(component.bug_count as usize);
-            let cadence = component.cadence_ms as u64;
-            let start = component.start_offset_ms as u64;
-            for index in 0..component.bug_count {
-                let time = start.saturating_add(cadence.saturating_mul(index as u64));
-                component.spawn_times.push(time.min(u32::MAX as u64) as u32);
-            }
+        self.draw_burst_gaps(inputs);
+        for component in self.work.provisional_species.iter_mut() {
+            component.schedule_spawn_times();
+        }
+    }
+
+    /// Splits every species into bursts and draws the gaps between them from a stream
+    /// salted with `BURST_SALT`, one uniform draw per gap.
+    fn draw_burst_gaps(&mut self, inputs: &PressureWaveInputs) {
+        let tuning = &self.tuning.bursts;
+        if !tuning.enabled {
+            return;
+        }
+        let seed = wave_seed_hash(
+            inputs.game_seed(),
+            inputs.level_id(),
+            inputs.wave(),
+            inputs.difficulty(),
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ BURST_SALT);
+        let burst_size = tuning.burst_size.max(1);
+        let (gap_min, gap_max) = (tuning.gap.min_ms().get(), tuning.gap.max_ms().get());
+        for component in self.work.provisional_species.iter_mut() {
+            let gaps = component.bug_count.saturating_sub(1) / burst_size;
+            component.burst_size = burst_size;
+            component.burst_gaps_ms = (0..gaps)
+                .map(|_| rng.gen_range(gap_min..=gap_max.max(gap_min)))
+                .collect();
         }
     }
 
@@ -636,14 +1440,10 @@ impl PressureV2 {
                     cadence = cadence_min;
                 }
                 component.cadence_ms = cadence;
-                component.spawn_times.clear();
-                component.spawn_times.reserve(component.bug_count as usize);
-                let cadence_u64 = cadence as u64;
-                let start_u64 = component.start_offset_ms as u64;
-                for index in 0..component.bug_count {
-                    let time = start_u64.saturating_add(cadence_u64.saturating_mul(index as u64));
-                    component.spawn_times.push(time.min(u32::MAX as u64) as u32);
+                for gap in component.burst_gaps_ms.iter_mut() {
+                    *gap = (f64::from(*gap) / factor).floor().max(1.0) as u32;
                 }
+                component.schedule_spawn_times();
                 if let Some(&last) = component.spawn_times.last() {
                     t_end_after = t_end_after.max(last);
                 }
@@ -663,6 +1463,77 @@ impl PressureV2 {
         telemetry.compression_factor = compression_factor;
         telemetry.hit_cadence_min = hit_cadence_min;
         telemetry.t_end_after = t_end_after;
+
+        for (species, component) in self.work.provisional_species.iter().enumerate() {
+            if component.burst_size == 0 {
+                continue;
+            }
+            self.telemetry.bursts.push(BurstTelemetry {
+                species: species as u32,
+                burst_size: component.burst_size,
+                bursts: component.burst_gaps_ms.len() as u32 + 1,
+                cadence_ms: component.cadence_ms,
+                gaps_ms: component.burst_gaps_ms.clone(),
+            });
+        }
+    }
+
+    /// Delays spawns so no window of `smoothing.window_ms` holds more than
+    /// `smoothing.max_simultaneous` of them.
+    ///
+    /// Spawns are visited in time order. One that would crowd the window of the spawn
+    /// `max_simultaneous` places earlier moves to the end of that window plus a jitter drawn
+    /// up to `smoothing.jitter_ms`, and everything behind it keeps its order, so species
+    /// still spawn their bugs in sequence.
+    fn smooth_spawn_schedule(&mut self, inputs: &PressureWaveInputs) {
+        let tuning = &self.tuning.smoothing;
+        if !tuning.enabled {
+            return;
+        }
+        let limit = tuning.max_simultaneous.max(1) as usize;
+        let (window, jitter) = (tuning.window_ms, tuning.jitter_ms);
+        let seed = wave_seed_hash(
+            inputs.game_seed(),
+            inputs.level_id(),
+            inputs.wave(),
+            inputs.difficulty(),
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ SMOOTHING_SALT);
+
+        let mut order: Vec<(u32, u32, u32)> = Vec::new();
+        for (species, component) in self.work.provisional_species.iter().enumerate() {
+            for (index, &time) in component.spawn_times.iter().enumerate() {
+                order.push((time, species as u32, index as u32));
+            }
+        }
+        order.sort_unstable();
+
+        let mut smoothed: Vec<u32> = Vec::with_capacity(order.len());
+        let mut adjustments = Vec::new();
+        for (position, &(time, species, index)) in order.iter().enumerate() {
+            let mut at = time.max(smoothed.last().copied().unwrap_or(0));
+            if let Some(crowded) = position.checked_sub(limit).map(|earlier| smoothed[earlier]) {
+                let clear = crowded.saturating_add(window);
+                if at < clear {
+                    // RNG draw: jitter for a spawn pushed out of a crowded window.
+                    at = clear.saturating_add(rng.gen_range(0..=jitter));
+                }
+            }
+            if at != time {
+                self.work.provisional_species[species as usize].spawn_times[index as usize] = at;
+                adjustments.push(SpawnAdjustment {
+                    species,
+                    spawn: index,
+                    from_ms: time,
+                    to_ms: at,
+                });
+            }
+            smoothed.push(at);
+        }
+
+        let telemetry = self.telemetry.spawn_smoothing_mut();
+        telemetry.adjustments = adjustments;
+        telemetry.t_end_after = smoothed.last().copied().unwrap_or(0);
     }
 
     fn write_final_spawn_records(&self, out: &mut Vec<PressureSpawnRecord>) {
@@ -678,16 +1549,10 @@ impl PressureV2 {
             return;
         }
 
-        let mut scratch: Vec<(u32, u32, u32, f32, f32)> = Vec::with_capacity(total_spawns);
+        let mut scratch: Vec<(u32, u32, u32)> = Vec::with_capacity(total_spawns);
         for (species_id, component) in self.work.provisional_species.iter().enumerate() {
             for (index, &time) in component.spawn_times.iter().enumerate() {
-                scratch.push((
-                    time,
-                    species_id as u32,
-                    index as u32,
-                    component.hp_post,
-                    component.speed_post,
-                ));
+                scratch.push((time, species_id as u32, index as u32));
             }
         }
 
@@ -698,9 +1563,15 @@ impl PressureV2 {
         });
 
         out.reserve(scratch.len());
-        for (time, species_id, _, hp, speed) in scratch {
-            let hp_value = hp.round().clamp(1.0, u32::MAX as f32) as u32;
-            out.push(PressureSpawnRecord::new(time, hp_value, speed, species_id));
+        for (time, species_id, _) in scratch {
+            let component = &self.work.provisional_species[species_id as usize];
+            let hp_value = component.hp_post.round().clamp(1.0, u32::MAX as f32) as u32;
+            let record = PressureSpawnRecord::new(time, hp_value, component.speed_post, species_id)
+                .with_archetype(component.archetype);
+            out.push(match component.lane {
+                Some(lane) => record.with_lane(lane),
+                None => record,
+            });
         }
     }
 
@@ -711,6 +1582,8 @@ impl PressureV2 {
         }
 
         out.reserve(self.work.provisional_species.len());
+        let weights = &self.tuning.pressure_weights;
+        let nominal_weight = (weights.alpha * BASE_HP + weights.beta).max(f32::EPSILON);
         for component in &self.work.provisional_species {
             let (red, green, blue) = quantize_color(component.tint);
             let color = BugColor::from_rgb(red, green, blue);
@@ -718,7 +1591,19 @@ impl PressureV2 {
             let health = Health::new(hp_value);
             let cadence = component.cadence_ms.max(1);
             let step_ms = NonZeroU32::new(cadence).expect("component cadence should be non-zero");
-            out.push(SpeciesPrototype::new(color, health, step_ms));
+            // Tanks pay more than swarm: the bounty tracks pressure relative to a nominal bug.
+            let bounty = (component.pressure_weight_post / nominal_weight)
+                .round()
+                .clamp(1.0, u32::MAX as f32) as u64;
+            out.push(
+                SpeciesPrototype::new(color, health, step_ms)
+                    .with_bounty(Gold::new(bounty))
+                    .with_rank(if bounty >= BOSS_BOUNTY_THRESHOLD {
+                        BugRank::Boss
+                    } else {
+                        BugRank::Regular
+                    }),
+            );
         }
     }
 
@@ -730,7 +1615,9 @@ impl PressureV2 {
             .fold(0.0, |acc, component| {
                 let hp = eta * component.hp_pre;
                 let speed = eta * component.speed_pre;
-                let per_bug = weights.alpha * hp + weights.beta * speed.powf(weights.gamma);
+                let per_bug = (weights.alpha * hp
+                    + weights.beta * math::powf(speed, weights.gamma))
+                    * self.tuning.archetypes.pressure(component.archetype);
                 acc + component.bug_count as f32 * per_bug
             })
     }
@@ -739,8 +1626,7 @@ impl PressureV2 {
         let mean = self.component_poisson_mean(difficulty);
         // RNG draw #4: provisional component Poisson proposal using the
         // `components.poisson_intercept` + `poisson_slope` growth curve.
-        let distribution = Poisson::new(f64::from(mean)).expect("positive Poisson mean");
-        distribution.sample(&mut self.rng) as u32
+        math::poisson(&mut self.rng, f64::from(mean)) as u32
     }
 
     fn component_poisson_mean(&self, difficulty: f32) -> f32 {
@@ -772,26 +1658,25 @@ impl PressureV2 {
             // RNG draws #5-6: bivariate log-space component centre using
             // `components.log_hp_sigma`, `log_speed_sigma`, and `log_correlation`
             // before clamping to the multiplier bounds.
-            let z_hp: f32 = self.rng.sample(StandardNormal);
-            let z_speed: f32 = self.rng.sample(StandardNormal);
+            let z_hp = math::standard_normal(&mut self.rng);
+            let z_speed = math::standard_normal(&mut self.rng);
 
-            let log_hp = mean_hp_multiplier.ln() + tuning.log_hp_sigma * z_hp;
-            let log_speed = mean_speed_multiplier.ln()
+            let log_hp = math::ln(mean_hp_multiplier) + tuning.log_hp_sigma * z_hp;
+            let log_speed = math::ln(mean_speed_multiplier)
                 + tuning.log_speed_sigma * (rho * z_hp + orthogonal_scale * z_speed);
 
-            let hp_multiplier = log_hp.exp().clamp(tuning.hp_multiplier_min, hp_cap);
-            let speed_multiplier = log_speed
-                .exp()
+            let hp_multiplier = math::exp(log_hp).clamp(tuning.hp_multiplier_min, hp_cap);
+            let speed_multiplier = math::exp(log_speed)
                 .clamp(tuning.speed_multiplier_min, tuning.speed_multiplier_max);
 
-            let log_hp_clamped = hp_multiplier.ln();
-            let log_speed_clamped = speed_multiplier.ln();
+            let log_hp_clamped = math::ln(hp_multiplier);
+            let log_speed_clamped = math::ln(speed_multiplier);
 
             let hp_pre = BASE_HP * hp_multiplier;
             let speed_pre = speed_multiplier;
             let pressure_weight = self.tuning.pressure_weights.alpha * hp_pre
                 + self.tuning.pressure_weights.beta
-                    * speed_pre.powf(self.tuning.pressure_weights.gamma);
+                    * math::powf(speed_pre, self.tuning.pressure_weights.gamma);
 
             self.work.provisional_species.push(ComponentWork::new(
                 hp_pre,
@@ -808,13 +1693,11 @@ impl PressureV2 {
         debug_assert!(component_count > 0);
 
         let alpha = self.tuning.components.dirichlet_concentration;
-        let dirichlet =
-            Gamma::new(f64::from(alpha), 1.0).expect("positive Dirichlet concentration");
         let mut draws = Vec::with_capacity(component_count);
         for _ in 0..component_count {
             // RNG draw #7+: Dirichlet gamma sample per component governed by
             // `components.dirichlet_concentration`.
-            draws.push(dirichlet.sample(&mut self.rng) as f32);
+            draws.push(math::gamma(&mut self.rng, f64::from(alpha)) as f32);
         }
 
         let sum: f32 = draws.iter().sum();
@@ -863,7 +1746,7 @@ impl PressureV2 {
                         .unwrap_or(Ordering::Equal)
                         .then_with(|| a.cmp(b))
                 });
-                for index in indices.into_iter().take(diff.abs() as usize) {
+                for index in indices.into_iter().take(diff.unsigned_abs() as usize) {
                     self.work.provisional_species[index].bug_count = self.work.provisional_species
                         [index]
                         .bug_count
@@ -1012,41 +1895,77 @@ impl PressureV2 {
             let tint = self.draw_unique_tint(&mut used);
             self.work.provisional_species[index].tint = tint;
         }
+
+        let telemetry = self.telemetry.species_tints_mut();
+        telemetry.palette = self.tuning.components.palette;
+        telemetry.tints = used
+            .into_iter()
+            .map(|(red, green, blue)| [red, green, blue])
+            .collect();
     }
 
     fn draw_unique_tint(&mut self, used: &mut Vec<(u8, u8, u8)>) -> MacroquadColor {
         const MAX_ATTEMPTS: usize = 24;
-        for _ in 0..MAX_ATTEMPTS {
-            // RNG draws: species tint hue, saturation, and value in that order;
-            // saturation/value ranges ensure readable contrast without ever
-            // dipping below 0.55/0.85.
-            let hue: f32 = self.rng.gen();
-            let saturation: f32 = self.rng.gen_range(0.55..0.85);
-            let value: f32 = self.rng.gen_range(0.85..0.98);
-            let tint = hsv_to_color(hue, saturation, value);
-            let quantized = quantize_color(tint);
-            if !used.contains(&quantized) {
-                used.push(quantized);
-                return tint;
+        const FALLBACK_CANDIDATES: usize = 64;
+        let min_delta_e = self.tuning.tint.min_delta_e;
+        if let Some(colors) = self.tuning.components.palette.fixed_colors() {
+            // Fixed palettes consume no RNG draws and hand out their colours in order.
+            for &rgb in colors {
+                if !used.contains(&rgb) && self.tuning.tint.separation(rgb) >= min_delta_e {
+                    used.push(rgb);
+                    return MacroquadColor::from_rgba(rgb.0, rgb.1, rgb.2, 255);
+                }
+            }
+        } else {
+            for _ in 0..MAX_ATTEMPTS {
+                // RNG draws: species tint hue, saturation, and value in that order;
+                // saturation/value ranges ensure readable contrast without ever
+                // dipping below 0.55/0.85.
+                let hue: f32 = self.rng.gen();
+                let saturation: f32 = self.rng.gen_range(0.55..0.85);
+                let value: f32 = self.rng.gen_range(0.85..0.98);
+                let tint = hsv_to_color(hue, saturation, value);
+                let quantized = quantize_color(tint);
+                if !used.contains(&quantized)
+                    && self.tuning.tint.separation(quantized) >= min_delta_e
+                {
+                    used.push(quantized);
+                    return tint;
+                }
             }
         }
 
+        // Fallback hues are scanned in order; when none of the first candidates clears
+        // the palette, the unique candidate standing furthest apart from it wins.
+        let mut furthest: Option<(f32, MacroquadColor, (u8, u8, u8))> = None;
         let mut offset = 0usize;
         loop {
             let tint = fallback_tint(used.len() + offset);
             let quantized = quantize_color(tint);
             if !used.contains(&quantized) {
-                used.push(quantized);
-                return tint;
+                let separation = self.tuning.tint.separation(quantized);
+                if separation >= min_delta_e {
+                    used.push(quantized);
+                    return tint;
+                }
+                if furthest.is_none_or(|(best, _, _)| separation > best) {
+                    furthest = Some((separation, tint, quantized));
+                }
             }
             offset += 1;
+            if offset >= FALLBACK_CANDIDATES {
+                if let Some((_, tint, quantized)) = furthest {
+                    used.push(quantized);
+                    return tint;
+                }
+            }
         }
     }
 
     fn count_mean(&self, difficulty: f32) -> f32 {
         let tuning = &self.tuning.count;
         let exponent = -tuning.slope * (difficulty - tuning.midpoint);
-        tuning.minimum + (tuning.cap - tuning.minimum) / (1.0 + exponent.exp())
+        tuning.minimum + (tuning.cap - tuning.minimum) / (1.0 + math::exp(exponent))
     }
 
     fn hp_mean_multiplier(&self, difficulty: f32) -> f32 {
@@ -1056,10 +1975,10 @@ impl PressureV2 {
         // HP padding, while the logarithmic term grows without a hard ceiling
         // once `growth_pivot` is exceeded.
         let soft_boost =
-            tuning.soft_boost_fraction * (1.0 - (-tuning.soft_boost_rate * delta).exp());
+            tuning.soft_boost_fraction * (1.0 - math::exp(-tuning.soft_boost_rate * delta));
         let post_pivot = (difficulty - tuning.growth_pivot).max(0.0);
         let log_argument = (1.0 + tuning.log_growth_rate * post_pivot).max(1.0);
-        let logarithmic = 1.0 + tuning.log_growth_scale * log_argument.ln();
+        let logarithmic = 1.0 + tuning.log_growth_scale * math::ln(log_argument);
         (1.0 + soft_boost) * logarithmic
     }
 
@@ -1079,10 +1998,11 @@ impl PressureV2 {
         // the additive low-D bump while `post_pivot_growth` + `growth_pivot`
         // dictate the late-game exponential acceleration.
         let soft_boost =
-            tuning.soft_boost_fraction * (1.0 - (-tuning.soft_boost_rate * delta).exp());
-        let multiplicative = tuning
-            .post_pivot_growth
-            .powf((difficulty - tuning.growth_pivot).max(0.0));
+            tuning.soft_boost_fraction * (1.0 - math::exp(-tuning.soft_boost_rate * delta));
+        let multiplicative = math::powf(
+            tuning.post_pivot_growth,
+            (difficulty - tuning.growth_pivot).max(0.0),
+        );
         (1.0 + soft_boost) * multiplicative
     }
 
@@ -1094,7 +2014,7 @@ impl PressureV2 {
         let delta = (difficulty - 1.0).max(0.0);
         // Exponential decay toward the configured floor keeps high difficulties
         // near `cadence_floor_ms` while preserving the low-D intercept.
-        let mean = floor + (base - floor) * (-decay * delta).exp();
+        let mean = floor + (base - floor) * math::exp(-decay * delta);
         mean.clamp(floor, tuning.cadence_max_ms as f32)
     }
 
@@ -1211,7 +2131,7 @@ fn draw_truncated_normal(
     min: f32,
     max: f32,
 ) -> f32 {
-    let z: f32 = rng.sample(StandardNormal);
+    let z = math::standard_normal(rng);
     let value = mean + deviation * z;
     value.clamp(min, max)
 }
@@ -1251,7 +2171,7 @@ fn quantize_channel(value: f32) -> u8 {
 }
 
 fn fallback_tint(index: usize) -> MacroquadColor {
-    const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_033_988_75;
+    const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_034;
     let base_hue = (index as f32 * GOLDEN_RATIO_CONJUGATE).fract();
     let saturation_cycle = match index % 5 {
         0 => 0.70,
@@ -1297,19 +2217,33 @@ fn hsv_to_color(hue: f32, saturation: f32, value: f32) -> MacroquadColor {
 /// Telemetry accumulator for the pressure generator.
 #[derive(Clone, Debug, Default)]
 pub struct PressureTelemetry {
+    inputs: Option<PressureWaveInputs>,
     difficulty_latents: DifficultyLatentsTelemetry,
     species_merge: Vec<SpeciesMergeTelemetry>,
     eta_scaling: EtaScalingTelemetry,
     cadence_compression: CadenceCompressionTelemetry,
+    tuning_resolution: TuningResolutionTelemetry,
+    elite: EliteTelemetry,
+    spawn_smoothing: SpawnSmoothingTelemetry,
+    species_tints: SpeciesTintTelemetry,
+    budget: BudgetTelemetry,
+    bursts: Vec<BurstTelemetry>,
 }
 
 impl PressureTelemetry {
     /// Clears any accumulated telemetry back to placeholder defaults.
     pub fn reset(&mut self) {
+        self.inputs = None;
         self.difficulty_latents = DifficultyLatentsTelemetry::default();
         self.species_merge.clear();
         self.eta_scaling = EtaScalingTelemetry::default();
         self.cadence_compression = CadenceCompressionTelemetry::default();
+        self.tuning_resolution = TuningResolutionTelemetry::default();
+        self.elite = EliteTelemetry::default();
+        self.spawn_smoothing = SpawnSmoothingTelemetry::default();
+        self.species_tints = SpeciesTintTelemetry::default();
+        self.budget = BudgetTelemetry::default();
+        self.bursts.clear();
     }
 
     /// Drops any accumulated species merge telemetry.
@@ -1322,6 +2256,10 @@ impl PressureTelemetry {
         self.difficulty_latents.recorded = false;
         self.eta_scaling.recorded = false;
         self.cadence_compression.recorded = false;
+        self.elite.recorded = false;
+        self.spawn_smoothing.recorded = false;
+        self.species_tints.recorded = false;
+        self.budget.recorded = false;
         if self.species_merge.is_empty() {
             self.species_merge.push(SpeciesMergeTelemetry::default());
         }
@@ -1370,6 +2308,30 @@ impl PressureTelemetry {
         &mut self.cadence_compression
     }
 
+    /// Accesses the elite injection telemetry entry.
+    pub fn elite_mut(&mut self) -> &mut EliteTelemetry {
+        self.elite.recorded = true;
+        &mut self.elite
+    }
+
+    /// Accesses the spawn smoothing telemetry entry.
+    pub fn spawn_smoothing_mut(&mut self) -> &mut SpawnSmoothingTelemetry {
+        self.spawn_smoothing.recorded = true;
+        &mut self.spawn_smoothing
+    }
+
+    /// Accesses the budget ceiling telemetry entry.
+    pub fn budget_mut(&mut self) -> &mut BudgetTelemetry {
+        self.budget.recorded = true;
+        &mut self.budget
+    }
+
+    /// Accesses the species tint telemetry entry.
+    pub fn species_tints_mut(&mut self) -> &mut SpeciesTintTelemetry {
+        self.species_tints.recorded = true;
+        &mut self.species_tints
+    }
+
     /// Returns the η scaling telemetry entry.
     pub fn eta_scaling(&self) -> &EtaScalingTelemetry {
         &self.eta_scaling
@@ -1379,6 +2341,114 @@ impl PressureTelemetry {
     pub fn cadence_compression(&self) -> &CadenceCompressionTelemetry {
         &self.cadence_compression
     }
+
+    /// Returns how the tuning of the most recent wave was resolved.
+    pub fn tuning_resolution(&self) -> &TuningResolutionTelemetry {
+        &self.tuning_resolution
+    }
+
+    /// Returns the elite injection telemetry entry.
+    pub fn elite(&self) -> &EliteTelemetry {
+        &self.elite
+    }
+
+    /// Returns the spawn smoothing telemetry entry.
+    pub fn spawn_smoothing(&self) -> &SpawnSmoothingTelemetry {
+        &self.spawn_smoothing
+    }
+
+    /// Returns the species tint telemetry entry.
+    pub fn species_tints(&self) -> &SpeciesTintTelemetry {
+        &self.species_tints
+    }
+
+    /// Returns the budget ceiling telemetry entry.
+    pub fn budget(&self) -> &BudgetTelemetry {
+        &self.budget
+    }
+
+    /// Returns the burst structure of every species, empty unless bursts are enabled.
+    pub fn bursts(&self) -> &[BurstTelemetry] {
+        &self.bursts
+    }
+
+    /// Writes the recorded difficulty latents, species merges, species tints, η scaling,
+    /// budget ceilings, cadence compression, species bursts and spawn smoothing of the most
+    /// recent wave to `output`, one JSON object per line.
+    ///
+    /// Every line names its stage in a `record` field and carries the `game_seed`,
+    /// `level`, `wave` and `difficulty_level` the wave was generated from, so the lines of
+    /// many waves can be appended to one file and joined with wave outcome records.
+    /// Placeholder entries are skipped, and nothing is written before the first wave.
+    pub fn write_jsonl(&self, output: &mut impl Write) -> io::Result<()> {
+        let Some(inputs) = &self.inputs else {
+            return Ok(());
+        };
+        let mut records = Vec::new();
+        if self.difficulty_latents.recorded {
+            records.push(TelemetryRecord::DifficultyLatents(&self.difficulty_latents));
+        }
+        records.extend(
+            self.species_merge
+                .iter()
+                .filter(|merge| merge.recorded)
+                .map(TelemetryRecord::SpeciesMerge),
+        );
+        if self.species_tints.recorded {
+            records.push(TelemetryRecord::SpeciesTints(&self.species_tints));
+        }
+        if self.eta_scaling.recorded {
+            records.push(TelemetryRecord::EtaScaling(&self.eta_scaling));
+        }
+        if self.budget.recorded {
+            records.push(TelemetryRecord::Budget(&self.budget));
+        }
+        if self.cadence_compression.recorded {
+            records.push(TelemetryRecord::CadenceCompression(
+                &self.cadence_compression,
+            ));
+        }
+        records.extend(self.bursts.iter().map(TelemetryRecord::Burst));
+        if self.spawn_smoothing.recorded {
+            records.push(TelemetryRecord::SpawnSmoothing(&self.spawn_smoothing));
+        }
+        for record in records {
+            let line = TelemetryLine {
+                game_seed: inputs.game_seed(),
+                level: inputs.level_id(),
+                wave: inputs.wave(),
+                difficulty_level: inputs.difficulty(),
+                record,
+            };
+            serde_json::to_writer(&mut *output, &line)?;
+            writeln!(output)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line written by [`PressureTelemetry::write_jsonl`].
+#[derive(Serialize)]
+struct TelemetryLine<'a> {
+    game_seed: u64,
+    level: LevelId,
+    wave: WaveId,
+    difficulty_level: DifficultyLevel,
+    #[serde(flatten)]
+    record: TelemetryRecord<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum TelemetryRecord<'a> {
+    DifficultyLatents(&'a DifficultyLatentsTelemetry),
+    SpeciesMerge(&'a SpeciesMergeTelemetry),
+    SpeciesTints(&'a SpeciesTintTelemetry),
+    EtaScaling(&'a EtaScalingTelemetry),
+    Budget(&'a BudgetTelemetry),
+    CadenceCompression(&'a CadenceCompressionTelemetry),
+    Burst(&'a BurstTelemetry),
+    SpawnSmoothing(&'a SpawnSmoothingTelemetry),
 }
 
 #[derive(Clone, Debug, Default)]
@@ -1394,6 +2464,7 @@ struct WaveWork {
     eta: f32,
     eta_clamped: bool,
     pressure_after_eta: f32,
+    spawn_times_paced: bool,
 }
 
 impl WaveWork {
@@ -1409,6 +2480,7 @@ impl WaveWork {
         self.eta = 1.0;
         self.eta_clamped = false;
         self.pressure_after_eta = 0.0;
+        self.spawn_times_paced = false;
     }
 }
 
@@ -1426,13 +2498,41 @@ struct ComponentWork {
     bug_count: u32,
     log_hp_multiplier: f32,
     log_speed_multiplier: f32,
+    archetype: BugArchetype,
+    lane: Option<u32>,
     tint: MacroquadColor,
     cadence_ms: u32,
     start_offset_ms: u32,
+    /// Spawns per burst, or zero when the species spawns at a flat cadence.
+    burst_size: u32,
+    burst_gaps_ms: Vec<u32>,
     spawn_times: Vec<u32>,
 }
 
 impl ComponentWork {
+    /// Lays the spawns out from the start offset: one cadence step apart within a burst,
+    /// one gap apart between bursts.
+    fn schedule_spawn_times(&mut self) {
+        self.spawn_times.clear();
+        self.spawn_times.reserve(self.bug_count as usize);
+        let mut time = u64::from(self.start_offset_ms);
+        for index in 0..self.bug_count {
+            if index > 0 {
+                let step = match self.burst_size {
+                    0 => self.cadence_ms,
+                    size if index % size == 0 => self
+                        .burst_gaps_ms
+                        .get((index / size - 1) as usize)
+                        .copied()
+                        .unwrap_or(self.cadence_ms),
+                    _ => self.cadence_ms,
+                };
+                time = time.saturating_add(u64::from(step));
+            }
+            self.spawn_times.push(time.min(u64::from(u32::MAX)) as u32);
+        }
+    }
+
     fn new(
         hp_pre: f32,
         speed_pre: f32,
@@ -1452,9 +2552,13 @@ impl ComponentWork {
             bug_count: 0,
             log_hp_multiplier,
             log_speed_multiplier,
+            archetype: BugArchetype::Basic,
+            lane: None,
             tint: MacroquadColor::new(1.0, 1.0, 1.0, 1.0),
             cadence_ms: 0,
             start_offset_ms: 0,
+            burst_size: 0,
+            burst_gaps_ms: Vec::new(),
             spawn_times: Vec::new(),
         }
     }
@@ -1483,9 +2587,24 @@ struct CountLatent {
     sampled: u32,
 }
 
+impl CountLatent {
+    /// Keeps `percent` of the sampled bugs, but never fewer than one.
+    fn with_share(self, percent: u32) -> Self {
+        if percent >= 100 {
+            return self;
+        }
+        let kept = u64::from(self.sampled) * u64::from(percent) / 100;
+        Self {
+            mean: self.mean * percent as f32 / 100.0,
+            sampled: u32::try_from(kept).unwrap_or(u32::MAX).max(1),
+        }
+    }
+}
+
 /// Difficulty latent telemetry entry carrying placeholder values until the latent implementation lands.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct DifficultyLatentsTelemetry {
+    #[serde(skip)]
     recorded: bool,
     /// Placeholder bug count mean stored for upcoming implementations.
     pub bug_count_mean: f32,
@@ -1518,8 +2637,9 @@ impl DifficultyLatentsTelemetry {
 }
 
 /// Species merge telemetry entry which records each merge that occurs during §4.4.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct SpeciesMergeTelemetry {
+    #[serde(skip)]
     recorded: bool,
     /// Flag indicating that the record represents an explicit no-merge outcome.
     no_merge: bool,
@@ -1541,10 +2661,11 @@ impl SpeciesMergeTelemetry {
     /// Creates a placeholder merge record flagged as an actual merge.
     #[must_use]
     fn merge_placeholder() -> Self {
-        let mut record = Self::default();
-        record.recorded = true;
-        record.no_merge = false;
-        record
+        Self {
+            recorded: true,
+            no_merge: false,
+            ..Self::default()
+        }
     }
 
     /// Creates an explicit no-merge telemetry record.
@@ -1575,8 +2696,9 @@ impl SpeciesMergeTelemetry {
 }
 
 /// Telemetry entry describing the η scaling decision made in §5.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct EtaScalingTelemetry {
+    #[serde(skip)]
     recorded: bool,
     /// Placeholder resolved η value.
     pub eta_final: f32,
@@ -1596,9 +2718,66 @@ impl EtaScalingTelemetry {
     }
 }
 
+/// Telemetry entry describing the bursts of one species.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BurstTelemetry {
+    /// Generated species identifier.
+    pub species: u32,
+    /// Spawns per burst; the last burst may be shorter.
+    pub burst_size: u32,
+    /// Number of bursts the species spawns in.
+    pub bursts: u32,
+    /// Time between two spawns of one burst after compression, in milliseconds.
+    pub cadence_ms: u32,
+    /// Pause before each burst after the first, after compression, in milliseconds.
+    pub gaps_ms: Vec<u32>,
+}
+
+/// Telemetry entry describing the budget ceilings of a capped wave.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BudgetTelemetry {
+    #[serde(skip)]
+    recorded: bool,
+    /// Zero-based index of the capped wave.
+    pub wave: u32,
+    /// Whether the bug-count ceiling trimmed the wave.
+    pub bug_count_capped: bool,
+    /// Whether the total-health ceiling weakened the wave.
+    pub total_hp_capped: bool,
+    /// Whether the pressure ceiling weakened the wave.
+    pub pressure_capped: bool,
+    /// Bugs in the wave after η alignment.
+    pub bug_count_before: u32,
+    /// Bugs in the wave after the ceilings.
+    pub bug_count_after: u32,
+    /// Combined health after η alignment.
+    pub total_hp_before: u64,
+    /// Combined health after the ceilings.
+    pub total_hp_after: u64,
+    /// Combined pressure after η alignment.
+    pub pressure_before: f32,
+    /// Combined pressure after the ceilings.
+    pub pressure_after: f32,
+}
+
+impl BudgetTelemetry {
+    /// Indicates whether the most recent wave had budget ceilings.
+    #[must_use]
+    pub fn is_recorded(&self) -> bool {
+        self.recorded
+    }
+
+    /// Reports whether any ceiling changed the wave.
+    #[must_use]
+    pub fn engaged(&self) -> bool {
+        self.bug_count_capped || self.total_hp_capped || self.pressure_capped
+    }
+}
+
 /// Telemetry entry describing cadence compression results from §6.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct CadenceCompressionTelemetry {
+    #[serde(skip)]
     recorded: bool,
     /// Maximum spawn time encountered prior to enforcing the duration cap.
     pub t_end_before: u32,
@@ -1620,11 +2799,87 @@ impl CadenceCompressionTelemetry {
     }
 }
 
+/// Telemetry entry describing the elite species injected into the wave, if any.
+#[derive(Clone, Debug, Default)]
+pub struct EliteTelemetry {
+    recorded: bool,
+    /// Index of the component converted into the elite species.
+    pub component: u32,
+    /// Bug count of the component before the conversion.
+    pub bug_count_before: u32,
+    /// Bug count of the elite species after the conversion.
+    pub bug_count_after: u32,
+    /// HP factor applied to the elite before η alignment.
+    pub hp_multiplier: f32,
+    /// Speed factor applied to the elite before η alignment.
+    pub speed_multiplier: f32,
+}
+
+impl EliteTelemetry {
+    /// Indicates whether an elite species was injected into the most recent wave.
+    #[must_use]
+    pub fn is_recorded(&self) -> bool {
+        self.recorded
+    }
+}
+
+/// Telemetry entry listing the spawns moved by the anti-burst smoothing pass.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SpawnSmoothingTelemetry {
+    #[serde(skip)]
+    recorded: bool,
+    /// Every spawn the pass delayed, in the order it visited them.
+    pub adjustments: Vec<SpawnAdjustment>,
+    /// Latest spawn time after smoothing.
+    pub t_end_after: u32,
+}
+
+impl SpawnSmoothingTelemetry {
+    /// Indicates whether the smoothing pass ran for the most recent wave.
+    #[must_use]
+    pub fn is_recorded(&self) -> bool {
+        self.recorded
+    }
+}
+
+/// Telemetry entry listing the tints handed to the wave's species.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SpeciesTintTelemetry {
+    #[serde(skip)]
+    recorded: bool,
+    /// Strategy the tints were picked with.
+    pub palette: TintPalette,
+    /// Tint of every species as `[red, green, blue]`, in species order.
+    pub tints: Vec<[u8; 3]>,
+}
+
+impl SpeciesTintTelemetry {
+    /// Indicates whether tints were assigned for the most recent wave.
+    #[must_use]
+    pub fn is_recorded(&self) -> bool {
+        self.recorded
+    }
+}
+
+/// One spawn delayed by the anti-burst smoothing pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SpawnAdjustment {
+    /// Species the spawn belongs to.
+    pub species: u32,
+    /// Zero-based index of the spawn within its species.
+    pub spawn: u32,
+    /// Spawn time before smoothing.
+    pub from_ms: u32,
+    /// Spawn time after smoothing.
+    pub to_ms: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
+    use std::collections::{BTreeMap, HashSet};
 
+    use maze_defence_core::WaveHistoryInputs;
     use rand::RngCore;
 
     fn build_component(
@@ -1636,7 +2891,8 @@ mod tests {
     ) -> ComponentWork {
         let hp_pre = BASE_HP * hp_multiplier;
         let speed_pre = speed_multiplier;
-        let pressure_weight = weights.alpha * hp_pre + weights.beta * speed_pre.powf(weights.gamma);
+        let pressure_weight =
+            weights.alpha * hp_pre + weights.beta * math::powf(speed_pre, weights.gamma);
         let share = if total_bugs > 0 {
             bug_count as f32 / total_bugs as f32
         } else {
@@ -1653,11 +2909,15 @@ mod tests {
             dirichlet_share: share,
             fractional_count: share * total_bugs as f32,
             bug_count,
-            log_hp_multiplier: hp_multiplier.ln(),
-            log_speed_multiplier: speed_multiplier.ln(),
+            log_hp_multiplier: math::ln(hp_multiplier),
+            log_speed_multiplier: math::ln(speed_multiplier),
+            archetype: BugArchetype::Basic,
+            lane: None,
             tint: MacroquadColor::new(1.0, 1.0, 1.0, 1.0),
             cadence_ms: 0,
             start_offset_ms: 0,
+            burst_size: 0,
+            burst_gaps_ms: Vec::new(),
             spawn_times: Vec::new(),
         }
     }
@@ -1746,6 +3006,7 @@ mod tests {
         assert!(!telemetry.difficulty_latents().is_recorded());
         assert!(!telemetry.eta_scaling().is_recorded());
         assert!(!telemetry.cadence_compression().is_recorded());
+        assert!(!telemetry.elite().is_recorded());
 
         let merge = telemetry.push_species_merge();
         assert!(merge.is_recorded());
@@ -1790,6 +3051,359 @@ mod tests {
         assert!(high.count_mean > low.count_mean);
     }
 
+    #[test]
+    fn pressure_share_thins_the_wave_without_reseeding() {
+        let mut generator = PressureV2::default();
+        let full =
+            PressureWaveInputs::new(99, LevelId::new(1), WaveId::new(3), DifficultyLevel::new(6));
+        let half = full.clone().with_pressure_share(50);
+
+        generator.reseed_rng(&full);
+        generator.work.reset();
+        generator.compute_difficulty_latents(&full);
+        let full_latents = generator.difficulty_work().clone();
+
+        generator.reseed_rng(&half);
+        generator.work.reset();
+        generator.compute_difficulty_latents(&half);
+        let half_latents = generator.difficulty_work().clone();
+
+        assert_eq!(half_latents.bug_count, (full_latents.bug_count / 2).max(1));
+        assert_eq!(half_latents.hp_multiplier, full_latents.hp_multiplier);
+        assert_eq!(half_latents.speed_multiplier, full_latents.speed_multiplier);
+        assert_eq!(full.clone().with_pressure_share(0).pressure_share(), 1);
+    }
+
+    #[test]
+    fn elite_stage_converts_the_toughest_component_on_hard_waves() {
+        let inputs = PressureWaveInputs::new(
+            17,
+            LevelId::new(1),
+            WaveId::new(8),
+            DifficultyLevel::new(12),
+        );
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        let mut plain = PressureV2::default();
+        plain.generate(&inputs, &mut spawns, &mut prototypes);
+        assert!(
+            !plain.telemetry().elite().is_recorded(),
+            "the stage is opt-in"
+        );
+        let plain_species: Vec<(u32, u32)> = plain
+            .work
+            .provisional_species
+            .iter()
+            .map(|component| (component.bug_count, component.hp_post.round() as u32))
+            .collect();
+        assert!(
+            plain_species.len() >= 2,
+            "seed should yield several species"
+        );
+
+        let mut generator = PressureV2::default();
+        generator.tuning_mut().elite.enabled = true;
+        generator.generate(&inputs, &mut spawns, &mut prototypes);
+        let elite = generator.telemetry().elite().clone();
+        assert!(elite.is_recorded());
+        assert_eq!(elite.bug_count_after, elite.bug_count_before.div_ceil(4));
+        assert_eq!(
+            elite.bug_count_before,
+            plain_species[elite.component as usize].0
+        );
+
+        let components = &generator.work.provisional_species;
+        let elite_component = &components[elite.component as usize];
+        assert_eq!(elite_component.bug_count, elite.bug_count_after);
+        for (index, component) in components.iter().enumerate() {
+            if index != elite.component as usize {
+                assert!(
+                    elite_component.hp_post > 2.0 * component.hp_post,
+                    "elites are far sturdier than the rest of the wave"
+                );
+            }
+        }
+
+        let easy =
+            PressureWaveInputs::new(17, LevelId::new(1), WaveId::new(8), DifficultyLevel::new(3));
+        generator.generate(&easy, &mut spawns, &mut prototypes);
+        assert!(!generator.telemetry().elite().is_recorded());
+    }
+
+    #[test]
+    fn archetypes_come_from_their_own_stream_and_stay_inside_the_budget() {
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        let mut basic_spawns = Vec::new();
+        let mut sampled = HashSet::new();
+        for wave in 0..12 {
+            let inputs = PressureWaveInputs::new(
+                5,
+                LevelId::new(1),
+                WaveId::new(wave),
+                DifficultyLevel::new(9),
+            );
+            let mut generator = PressureV2::default();
+            generator.generate(&inputs, &mut spawns, &mut prototypes);
+            sampled.extend(spawns.iter().map(PressureSpawnRecord::archetype));
+            let components = &generator.work.provisional_species;
+            let rounded_hp = |component: &ComponentWork| component.hp_post.round() as u32;
+            for component in components {
+                let hp = rounded_hp(component);
+                if components
+                    .iter()
+                    .filter(|other| rounded_hp(other) == hp)
+                    .count()
+                    > 1
+                {
+                    continue;
+                }
+                assert!(spawns
+                    .iter()
+                    .filter(|spawn| spawn.hp() == hp)
+                    .all(|spawn| spawn.archetype() == component.archetype));
+            }
+            let eta = generator.telemetry().eta_scaling();
+            if !eta.eta_clamped {
+                let drift = (eta.pressure_after_eta - eta.pressure_target).abs();
+                assert!(
+                    drift <= eta.pressure_target * 0.01,
+                    "η absorbs archetype costs"
+                );
+            }
+
+            let mut basic = PressureV2::default();
+            let archetypes = &mut basic.tuning_mut().archetypes;
+            archetypes.armored_weight = 0.0;
+            archetypes.regenerating_weight = 0.0;
+            archetypes.splitter_weight = 0.0;
+            basic.generate(&inputs, &mut basic_spawns, &mut prototypes);
+            assert!(basic_spawns
+                .iter()
+                .all(|spawn| spawn.archetype() == BugArchetype::Basic));
+            let times = |records: &[PressureSpawnRecord]| {
+                records
+                    .iter()
+                    .map(PressureSpawnRecord::time_ms)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                times(&spawns),
+                times(&basic_spawns),
+                "archetype draws leave the main stream untouched"
+            );
+        }
+        assert!(sampled.len() > 1, "defaults mix several archetypes");
+    }
+
+    #[test]
+    fn lanes_are_handed_out_round_robin_without_moving_spawns() {
+        let mut generator = PressureV2::default();
+        generator.tuning_mut().lanes = LaneTuning {
+            enabled: true,
+            lanes: 2,
+        };
+        let mut prototypes = Vec::new();
+        let mut unlaned = Vec::new();
+        let mut laned = Vec::new();
+        let mut wrapped = false;
+        for wave in 0..12 {
+            let inputs = PressureWaveInputs::new(
+                11,
+                LevelId::new(1),
+                WaveId::new(wave),
+                DifficultyLevel::new(9),
+            );
+            PressureV2::default().generate(&inputs, &mut unlaned, &mut prototypes);
+            assert!(unlaned.iter().all(|spawn| spawn.lane().is_none()));
+
+            generator.generate(&inputs, &mut laned, &mut prototypes);
+            wrapped |= prototypes.len() > 2;
+            let mut species_lanes = BTreeMap::new();
+            for spawn in &laned {
+                let lane = spawn.lane().expect("every spawn has a lane");
+                assert!(lane < 2);
+                assert_eq!(
+                    *species_lanes.entry(spawn.species_id()).or_insert(lane),
+                    lane,
+                    "a species keeps one lane"
+                );
+            }
+            let lanes: Vec<u32> = species_lanes.values().copied().collect();
+            assert!(lanes.windows(2).all(|pair| pair[1] == (pair[0] + 1) % 2));
+            let times = |spawns: &[PressureSpawnRecord]| {
+                spawns
+                    .iter()
+                    .map(|spawn| (spawn.time_ms(), spawn.species_id(), spawn.hp()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(times(&laned), times(&unlaned));
+        }
+        assert!(wrapped, "some wave has more species than lanes");
+    }
+
+    #[test]
+    fn smoothing_spreads_stacked_spawns_and_records_every_move() {
+        let mut tuning = PressureTuning::default();
+        // Without start spread every species opens on the same millisecond.
+        tuning.cadence.start_deviation_ratio = 0.0;
+        let mut stacked = PressureV2::new(tuning.clone());
+        tuning.smoothing.enabled = true;
+        let mut smoothed = PressureV2::new(tuning);
+        let crowded = |spawns: &[PressureSpawnRecord]| {
+            spawns
+                .windows(3)
+                .any(|spawns| spawns[2].time_ms() - spawns[0].time_ms() < 100)
+        };
+        let per_species = |spawns: &[PressureSpawnRecord]| {
+            let mut times: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+            for spawn in spawns {
+                times
+                    .entry(spawn.species_id())
+                    .or_default()
+                    .push(spawn.time_ms());
+            }
+            times
+        };
+        let mut prototypes = Vec::new();
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        let mut moved = false;
+        for wave in 0..8 {
+            let inputs = PressureWaveInputs::new(
+                11,
+                LevelId::new(1),
+                WaveId::new(wave),
+                DifficultyLevel::new(9),
+            );
+            stacked.generate(&inputs, &mut before, &mut prototypes);
+            assert!(!stacked.telemetry().spawn_smoothing().is_recorded());
+            smoothed.generate(&inputs, &mut after, &mut prototypes);
+            assert!(!crowded(&after), "wave {wave} still stacks spawns");
+
+            let telemetry = smoothed.telemetry().spawn_smoothing();
+            assert!(telemetry.is_recorded());
+            assert_eq!(
+                telemetry.t_end_after,
+                after.last().expect("spawns").time_ms()
+            );
+            let mut expected = per_species(&before);
+            for adjustment in &telemetry.adjustments {
+                let time = &mut expected.get_mut(&adjustment.species).expect("species")
+                    [adjustment.spawn as usize];
+                assert_eq!(*time, adjustment.from_ms);
+                assert!(adjustment.to_ms > adjustment.from_ms);
+                *time = adjustment.to_ms;
+            }
+            assert_eq!(per_species(&after), expected);
+            moved |= crowded(&before) && !telemetry.adjustments.is_empty();
+
+            let mut again = Vec::new();
+            smoothed.generate(&inputs, &mut again, &mut prototypes);
+            assert_eq!(again, after);
+        }
+        assert!(moved, "some wave stacked spawns before smoothing");
+    }
+
+    #[test]
+    fn bursts_group_spawns_and_record_their_gaps() {
+        let mut tuning = PressureTuning::default();
+        // Small waves fit their duration target, so their gaps stay uncompressed.
+        tuning.count.minimum = 6.0;
+        tuning.count.cap = 12.0;
+        let mut flat = PressureV2::new(tuning.clone());
+        tuning.bursts.enabled = true;
+        tuning.bursts.burst_size = 3;
+        let mut bursty = PressureV2::new(tuning.clone());
+        let (gap_min, gap_max) = (
+            tuning.bursts.gap.min_ms().get(),
+            tuning.bursts.gap.max_ms().get(),
+        );
+        let mut prototypes = Vec::new();
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        let mut uncompressed_gaps = false;
+        for wave in 0..4 {
+            let inputs = PressureWaveInputs::new(
+                23,
+                LevelId::new(1),
+                WaveId::new(wave),
+                DifficultyLevel::new(2),
+            );
+            flat.generate(&inputs, &mut before, &mut prototypes);
+            assert!(flat.telemetry().bursts().is_empty());
+            bursty.generate(&inputs, &mut after, &mut prototypes);
+
+            let diff = compare_plans(&before, &after);
+            assert_eq!((diff.count_delta(), diff.hp_delta()), (0, 0));
+            let telemetry = bursty.telemetry();
+            assert_eq!(telemetry.bursts().len(), diff.species.len());
+            let compressed = telemetry.cadence_compression().compression_factor > 1.0;
+            for burst in telemetry.bursts() {
+                let times: Vec<u32> = after
+                    .iter()
+                    .filter(|spawn| spawn.species_id() == burst.species)
+                    .map(PressureSpawnRecord::time_ms)
+                    .collect();
+                assert_eq!(burst.bursts, (times.len() as u32).div_ceil(3));
+                for (index, pair) in times.windows(2).enumerate() {
+                    let next = index + 1;
+                    let expected = if next % 3 == 0 {
+                        burst.gaps_ms[next / 3 - 1]
+                    } else {
+                        burst.cadence_ms
+                    };
+                    assert_eq!(pair[1] - pair[0], expected);
+                }
+                if !compressed {
+                    assert!(burst
+                        .gaps_ms
+                        .iter()
+                        .all(|gap| (gap_min..=gap_max).contains(gap)));
+                    uncompressed_gaps |= !burst.gaps_ms.is_empty();
+                }
+            }
+        }
+        assert!(uncompressed_gaps, "some wave has gaps left uncompressed");
+    }
+
+    #[test]
+    fn history_shifts_the_latent_means_only_when_adaptation_is_enabled() {
+        let inputs =
+            PressureWaveInputs::new(5, LevelId::new(1), WaveId::new(3), DifficultyLevel::new(6));
+        let fast = inputs.clone().with_history(Some(WaveHistoryInputs {
+            clear_time_ms: 15_000,
+            leaks: 0,
+        }));
+        let leaky = inputs.clone().with_history(Some(WaveHistoryInputs {
+            clear_time_ms: 90_000,
+            leaks: 4,
+        }));
+        let mut prototypes = Vec::new();
+        let mut generate = |generator: &mut PressureV2, inputs: &PressureWaveInputs| {
+            let mut spawns = Vec::new();
+            generator.generate(inputs, &mut spawns, &mut prototypes);
+            let latents = generator.telemetry().difficulty_latents();
+            (spawns, latents.bug_count_mean, latents.hp_mean_multiplier)
+        };
+
+        let mut seeded = PressureV2::default();
+        let baseline = generate(&mut seeded, &inputs);
+        assert_eq!(generate(&mut seeded, &fast), baseline);
+        assert_eq!(generate(&mut seeded, &leaky), baseline);
+
+        let mut adaptive = PressureV2::default();
+        adaptive.tuning_mut().adaptation.enabled = true;
+        assert_eq!(generate(&mut adaptive, &inputs), baseline);
+        let harder = generate(&mut adaptive, &fast);
+        assert!(harder.1 > baseline.1 && harder.2 > baseline.2);
+        let easier = generate(&mut adaptive, &leaky);
+        assert!(easier.1 < baseline.1 && easier.2 < baseline.2);
+        assert_eq!(
+            easier.1,
+            baseline.1 * (1.0 - AdaptationTuning::default().max_shift)
+        );
+        assert_eq!(generate(&mut adaptive, &fast), harder);
+    }
+
     #[test]
     fn difficulty_ten_waves_cross_fifty_hp() {
         let mut generator = PressureV2::default();
@@ -1884,7 +3498,7 @@ mod tests {
         let tuning = generator.tuning();
         let per_bug_pressure = tuning.pressure_weights.alpha * work_state.hp_wave
             + tuning.pressure_weights.beta
-                * work_state.speed_wave.powf(tuning.pressure_weights.gamma);
+                * math::powf(work_state.speed_wave, tuning.pressure_weights.gamma);
         let expected = (work.bug_count as f32 * per_bug_pressure).round() as u32;
         assert_eq!(expected, work_state.pressure_target);
         assert_eq!(
@@ -2195,6 +3809,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn species_tints_keep_clear_of_the_excluded_palette() {
+        let mut generator = PressureV2::default();
+        generator.telemetry.reset();
+        generator.work.reset();
+        let total_bugs = 48;
+        generator.work.difficulty.bug_count = total_bugs;
+        generator.work.minimum_species_size = 4;
+        let weights = generator.tuning().pressure_weights.clone();
+        generator.work.provisional_species = (0..6)
+            .map(|_| build_component(&weights, 1.0, 1.0, 8, total_bugs))
+            .collect();
+        generator.work.provisional_species_count = 6;
+
+        generator.assign_species_tints_for_test();
+        let tuning = generator.tuning().tint.clone();
+        for component in generator.provisional_components() {
+            let separation = tuning.separation(quantize_color(component.tint));
+            assert!(
+                separation >= tuning.min_delta_e,
+                "tint only {separation} ΔE from the palette"
+            );
+        }
+
+        let mut impossible = generator.tuning().clone();
+        impossible.tint.min_delta_e = 1_000.0;
+        let mut generator = PressureV2::new(impossible);
+        generator.telemetry.reset();
+        generator.work.reset();
+        generator.work.provisional_species = (0..6)
+            .map(|_| build_component(&weights, 1.0, 1.0, 8, total_bugs))
+            .collect();
+        generator.work.provisional_species_count = 6;
+        generator.assign_species_tints_for_test();
+        let unique: HashSet<_> = generator
+            .provisional_components()
+            .iter()
+            .map(|component| quantize_color(component.tint))
+            .collect();
+        assert_eq!(
+            unique.len(),
+            6,
+            "unreachable contrast still yields unique tints"
+        );
+    }
+
+    #[test]
+    fn delta_e_separates_black_and_white_by_full_lightness() {
+        let black = Lab::from_rgb((0, 0, 0));
+        let white = Lab::from_rgb((255, 255, 255));
+        assert!((black.delta_e(white) - 100.0).abs() < 0.1);
+        assert!(black.delta_e(black).abs() < 1e-6);
+    }
+
     #[test]
     fn species_tints_are_deterministic() {
         let mut generator_a = PressureV2::default();
@@ -2240,6 +3908,92 @@ mod tests {
         assert_eq!(tints_a, tints_b);
     }
 
+    #[test]
+    fn budget_ceilings_cap_their_wave_and_leave_the_others_alone() {
+        let inputs =
+            PressureWaveInputs::new(11, LevelId::new(1), WaveId::new(2), DifficultyLevel::new(8));
+        let mut generator = PressureV2::default();
+        let (mut baseline, mut prototypes) = (Vec::new(), Vec::new());
+        generator.generate(&inputs, &mut baseline, &mut prototypes);
+        assert!(!generator.telemetry().budget().is_recorded());
+        let baseline_hp: u64 = baseline.iter().map(|spawn| u64::from(spawn.hp())).sum();
+
+        let max_bug_count = (baseline.len() as u32 / 2).max(1);
+        let max_total_hp = baseline_hp / 3;
+        generator.tuning_mut().budget.waves = vec![WaveBudget {
+            wave: 2,
+            max_bug_count: Some(max_bug_count),
+            max_total_hp: Some(max_total_hp),
+            max_pressure: None,
+        }];
+        let mut capped = Vec::new();
+        generator.generate(&inputs, &mut capped, &mut prototypes);
+        assert!(capped.len() as u32 <= max_bug_count);
+        let capped_hp: u64 = capped.iter().map(|spawn| u64::from(spawn.hp())).sum();
+        assert!(capped_hp <= max_total_hp, "{capped_hp} > {max_total_hp}");
+        let telemetry = generator.telemetry().budget().clone();
+        assert!(telemetry.bug_count_capped && telemetry.total_hp_capped);
+        assert!(!telemetry.pressure_capped);
+        assert_eq!(telemetry.bug_count_before, baseline.len() as u32);
+        assert_eq!(telemetry.bug_count_after, capped.len() as u32);
+        assert_eq!(telemetry.total_hp_after, capped_hp);
+
+        let pressure_cap = telemetry.pressure_after / 2.0;
+        generator.tuning_mut().budget.waves[0] = WaveBudget {
+            wave: 2,
+            max_pressure: Some(pressure_cap),
+            ..WaveBudget::default()
+        };
+        generator.generate(&inputs, &mut capped, &mut prototypes);
+        let telemetry = generator.telemetry().budget();
+        assert!(telemetry.pressure_capped && telemetry.engaged());
+        assert!(telemetry.pressure_after <= pressure_cap);
+
+        let other_wave =
+            PressureWaveInputs::new(11, LevelId::new(1), WaveId::new(3), DifficultyLevel::new(8));
+        let mut uncapped = Vec::new();
+        generator.generate(&other_wave, &mut uncapped, &mut prototypes);
+        assert!(!generator.telemetry().budget().is_recorded());
+        let mut reference = Vec::new();
+        PressureV2::default().generate(&other_wave, &mut reference, &mut prototypes);
+        assert!(compare_plans(&reference, &uncapped).is_unchanged());
+    }
+
+    #[test]
+    fn fixed_tint_palettes_hand_out_their_colours_without_rng_draws() {
+        let mut tuning = PressureTuning::default();
+        tuning.components.palette = TintPalette::OkabeIto;
+        let mut generator = PressureV2::new(tuning);
+        generator.telemetry.reset();
+        generator.work.reset();
+        let total_bugs = 32;
+        generator.work.difficulty.bug_count = total_bugs;
+        let weights = generator.tuning().pressure_weights.clone();
+        generator.work.provisional_species = (0..4)
+            .map(|_| build_component(&weights, 1.0, 1.0, 8, total_bugs))
+            .collect();
+        generator.work.provisional_species_count = 4;
+        let rng_before = generator.rng.clone();
+
+        generator.assign_species_tints_for_test();
+        let tints: Vec<_> = generator
+            .provisional_components()
+            .iter()
+            .map(|component| quantize_color(component.tint))
+            .collect();
+        assert_eq!(
+            tints,
+            vec![(230, 159, 0), (86, 180, 233), (213, 94, 0), (0, 114, 178)]
+        );
+        assert_eq!(generator.rng, rng_before, "fixed palettes draw nothing");
+
+        let telemetry = generator.telemetry().species_tints();
+        assert!(telemetry.is_recorded());
+        assert_eq!(telemetry.palette, TintPalette::OkabeIto);
+        assert_eq!(telemetry.tints[0], [230, 159, 0]);
+        assert_eq!(telemetry.tints.len(), 4);
+    }
+
     #[test]
     fn eta_scaling_aligns_pressure_when_target_inside_bounds() {
         let mut generator = PressureV2::default();
@@ -2257,7 +4011,7 @@ mod tests {
         let mut pressure_sum = 0.0;
         for component in &components {
             let per_bug = weights.alpha * component.hp_pre
-                + weights.beta * component.speed_pre.powf(weights.gamma);
+                + weights.beta * math::powf(component.speed_pre, weights.gamma);
             pressure_sum += component.bug_count as f32 * per_bug;
         }
         generator.work.pressure_target = pressure_sum.round() as u32;
@@ -2550,4 +4304,96 @@ mod tests {
         assert_eq!(proto_b.health().get(), 23);
         assert_eq!(proto_b.step_ms().get(), 320);
     }
+
+    #[test]
+    fn telemetry_exports_one_tagged_json_line_per_recorded_stage() {
+        let mut generator = PressureV2::default();
+        let mut output = Vec::new();
+        generator
+            .telemetry()
+            .write_jsonl(&mut output)
+            .expect("write to memory");
+        assert!(output.is_empty());
+
+        let inputs =
+            PressureWaveInputs::new(5, LevelId::new(2), WaveId::new(3), DifficultyLevel::new(4));
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        generator.generate(&inputs, &mut spawns, &mut prototypes);
+        generator
+            .telemetry()
+            .write_jsonl(&mut output)
+            .expect("write to memory");
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .expect("utf-8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("line parses"))
+            .collect();
+        let kinds: Vec<&str> = lines
+            .iter()
+            .map(|line| line["record"].as_str().expect("tagged record"))
+            .collect();
+        let merges = generator.telemetry().species_merge().len();
+        assert_eq!(kinds.len(), merges + 4);
+        assert_eq!(kinds[0], "difficulty_latents");
+        assert!(kinds[1..=merges]
+            .iter()
+            .all(|kind| *kind == "species_merge"));
+        assert_eq!(
+            &kinds[merges + 1..],
+            ["species_tints", "eta_scaling", "cadence_compression"]
+        );
+        for line in &lines {
+            assert_eq!(line["game_seed"], 5);
+            assert_eq!(line["level"], 2);
+            assert_eq!(line["wave"], 3);
+            assert_eq!(line["difficulty_level"], 4);
+        }
+        assert_eq!(
+            lines[0]["pressure_target"],
+            generator.telemetry().difficulty_latents().pressure_target
+        );
+        assert!(lines[0].get("recorded").is_none());
+    }
+
+    #[test]
+    fn generator_versions_lock_their_output_for_a_fixed_seed() {
+        let inputs = PressureWaveInputs::new(
+            2024,
+            LevelId::new(1),
+            WaveId::new(6),
+            DifficultyLevel::new(8),
+        );
+        for version in GeneratorVersion::ALL {
+            let json = serde_json::to_string(&version).expect("version serializes");
+            let restored: GeneratorVersion = serde_json::from_str(&json).expect("version parses");
+            assert_eq!(restored, version);
+
+            let mut generator = PressureV2::default().with_generator_version(restored);
+            let mut spawns = Vec::new();
+            let mut prototypes = Vec::new();
+            generator.generate(&inputs, &mut spawns, &mut prototypes);
+            let bytes = serde_json::to_vec(&(&spawns, &prototypes)).expect("plan serializes");
+            let fingerprint = fnv1a(FNV_OFFSET_BASIS, &bytes);
+            // The portable samplers draw different values from the same seed, so each math
+            // configuration pins its own fingerprint.
+            #[cfg(not(feature = "deterministic_math"))]
+            let expected = match version {
+                GeneratorVersion::V1 => 0x61c5_e644_b500_59b6,
+            };
+            #[cfg(feature = "deterministic_math")]
+            let expected = match version {
+                GeneratorVersion::V1 => 0x341d_55f6_2a9c_8f7d,
+            };
+            assert_eq!(
+                fingerprint, expected,
+                "{version:?} no longer reproduces its waves; add a new GeneratorVersion instead"
+            );
+        }
+        assert_eq!(
+            PressureV2::default().generator_version(),
+            GeneratorVersion::LATEST
+        );
+    }
 }
@@ -0,0 +1,228 @@
+//! Transcendental functions and distribution samplers used by the generator.
+//!
+//! The standard library forwards `exp`, `ln` and `powf` to the platform's math library,
+//! whose last-bit rounding differs between operating systems and compiler versions, and
+//! the `rand_distr` samplers call the same functions internally. With the
+//! `deterministic_math` feature the functions use the portable software implementations of
+//! the `libm` crate instead, and the normal, gamma and Poisson samplers are implemented here
+//! on top of `libm`, so the same inputs generate bit-identical plans everywhere. Basic
+//! arithmetic and `sqrt` are correctly rounded by IEEE 754 and need no replacement.
+//!
+//! The two configurations draw different samples from the same seed, so a plan generated
+//! with the feature differs from one generated without it.
+
+use rand::Rng;
+#[cfg(not(feature = "deterministic_math"))]
+use rand_distr::{Distribution, Gamma, Poisson, StandardNormal};
+
+/// Returns `e` raised to the power `x`.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn exp(x: f32) -> f32 {
+    libm::expf(x)
+}
+
+/// Returns `e` raised to the power `x`.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn exp(x: f32) -> f32 {
+    x.exp()
+}
+
+/// Returns the natural logarithm of `x`.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn ln(x: f32) -> f32 {
+    libm::logf(x)
+}
+
+/// Returns the natural logarithm of `x`.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn ln(x: f32) -> f32 {
+    x.ln()
+}
+
+/// Returns `x` raised to the power `y`.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    libm::powf(x, y)
+}
+
+/// Returns `x` raised to the power `y`.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    x.powf(y)
+}
+
+/// Draws a sample from the standard normal distribution.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn standard_normal<R: Rng>(rng: &mut R) -> f32 {
+    // Marsaglia's polar method needs only `log` and `sqrt`.
+    loop {
+        let u = 2.0 * rng.gen::<f64>() - 1.0;
+        let v = 2.0 * rng.gen::<f64>() - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            return (u * (-2.0 * libm::log(s) / s).sqrt()) as f32;
+        }
+    }
+}
+
+/// Draws a sample from the standard normal distribution.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn standard_normal<R: Rng>(rng: &mut R) -> f32 {
+    rng.sample(StandardNormal)
+}
+
+/// Draws a sample from the gamma distribution with the given positive `shape` and unit
+/// scale.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn gamma<R: Rng>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        // Boosts a Gamma(shape + 1) sample back down, as Marsaglia and Tsang suggest.
+        let boost = libm::pow(rng.gen::<f64>(), 1.0 / shape);
+        return gamma(rng, shape + 1.0) * boost;
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = f64::from(standard_normal(rng));
+        let cube_root = 1.0 + c * x;
+        if cube_root <= 0.0 {
+            continue;
+        }
+        let v = cube_root * cube_root * cube_root;
+        let u: f64 = rng.gen();
+        let x_squared = x * x;
+        if u < 1.0 - 0.0331 * x_squared * x_squared
+            || libm::log(u) < 0.5 * x_squared + d * (1.0 - v + libm::log(v))
+        {
+            return d * v;
+        }
+    }
+}
+
+/// Draws a sample from the gamma distribution with the given positive `shape` and unit
+/// scale.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn gamma<R: Rng>(rng: &mut R, shape: f64) -> f64 {
+    Gamma::new(shape, 1.0)
+        .expect("positive gamma shape")
+        .sample(rng)
+}
+
+/// Draws a sample from the Poisson distribution with the given positive `mean`.
+#[cfg(feature = "deterministic_math")]
+pub(crate) fn poisson<R: Rng>(rng: &mut R, mean: f64) -> u64 {
+    if mean < 12.0 {
+        // Knuth's multiplication method for small means.
+        let limit = libm::exp(-mean);
+        let mut count = 0;
+        let mut product: f64 = rng.gen();
+        while product > limit {
+            count += 1;
+            product *= rng.gen::<f64>();
+        }
+        return count;
+    }
+
+    // Rejection from a Lorentzian envelope for large means.
+    let spread = (2.0 * mean).sqrt();
+    let log_mean = libm::log(mean);
+    let normaliser = mean * log_mean - libm::lgamma(mean + 1.0);
+    loop {
+        let (candidate, slope) = loop {
+            let slope = libm::tan(std::f64::consts::PI * rng.gen::<f64>());
+            let candidate = spread * slope + mean;
+            if candidate >= 0.0 {
+                break (candidate.floor(), slope);
+            }
+        };
+        let acceptance = 0.9
+            * (1.0 + slope * slope)
+            * libm::exp(candidate * log_mean - libm::lgamma(candidate + 1.0) - normaliser);
+        if rng.gen::<f64>() <= acceptance {
+            return candidate as u64;
+        }
+    }
+}
+
+/// Draws a sample from the Poisson distribution with the given positive `mean`.
+#[cfg(not(feature = "deterministic_math"))]
+pub(crate) fn poisson<R: Rng>(rng: &mut R, mean: f64) -> u64 {
+    Poisson::new(mean)
+        .expect("positive Poisson mean")
+        .sample(rng) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions_agree_with_the_standard_library_to_within_rounding() {
+        let close = |left: f32, right: f32| (left - right).abs() <= 1e-6 * right.abs().max(1.0);
+        for x in [0.0_f32, 0.25, 1.0, 2.5, -3.0, 17.75] {
+            assert!(close(exp(x), x.exp()), "exp({x})");
+            if x > 0.0 {
+                assert!(close(ln(x), x.ln()), "ln({x})");
+                for y in [0.0_f32, 0.5, 1.3, -2.0] {
+                    assert!(close(powf(x, y), x.powf(y)), "powf({x}, {y})");
+                }
+            }
+        }
+        assert!(ln(0.0).is_infinite());
+        assert!(ln(-1.0).is_nan());
+    }
+
+    #[test]
+    fn samplers_match_their_distribution_moments() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        const SAMPLES: usize = 20_000;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let moments = |samples: &[f64]| {
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            let variance = samples
+                .iter()
+                .map(|sample| (sample - mean) * (sample - mean))
+                .sum::<f64>()
+                / samples.len() as f64;
+            (mean, variance)
+        };
+
+        let normals: Vec<f64> = (0..SAMPLES)
+            .map(|_| f64::from(standard_normal(&mut rng)))
+            .collect();
+        let (mean, variance) = moments(&normals);
+        assert!(mean.abs() < 0.05 && (variance - 1.0).abs() < 0.05);
+
+        for shape in [0.4, 1.0, 3.5] {
+            let gammas: Vec<f64> = (0..SAMPLES).map(|_| gamma(&mut rng, shape)).collect();
+            assert!(gammas.iter().all(|sample| *sample >= 0.0));
+            let (mean, variance) = moments(&gammas);
+            assert!(
+                (mean - shape).abs() < 0.05 * shape.max(1.0),
+                "gamma({shape}) mean"
+            );
+            assert!(
+                (variance - shape).abs() < 0.15 * shape.max(1.0),
+                "gamma({shape}) variance"
+            );
+        }
+
+        for expected in [0.5, 4.0, 30.0] {
+            let counts: Vec<f64> = (0..SAMPLES)
+                .map(|_| poisson(&mut rng, expected) as f64)
+                .collect();
+            let (mean, variance) = moments(&counts);
+            assert!(
+                (mean - expected).abs() < 0.05 * expected.max(1.0),
+                "poisson({expected}) mean"
+            );
+            assert!(
+                (variance - expected).abs() < 0.1 * expected.max(1.0),
+                "poisson({expected}) variance"
+            );
+        }
+    }
+}
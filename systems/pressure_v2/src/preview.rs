@@ -0,0 +1,86 @@
+//! Deterministic difficulty curves for plotting a tuning without generating waves.
+
+use std::ops::RangeInclusive;
+
+use maze_defence_core::DifficultyLevel;
+use serde::Serialize;
+
+use crate::PressureV2;
+
+/// Means the generator samples around at one difficulty level.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CurvePoint {
+    /// Difficulty level the means were evaluated at.
+    pub difficulty: DifficultyLevel,
+    /// Mean of the logistic bug-count curve before sampling.
+    pub count_mean: f32,
+    /// Mean wave-wide HP multiplier before sampling.
+    pub hp_mean_multiplier: f32,
+    /// Mean wave-wide speed multiplier before sampling.
+    pub speed_mean_multiplier: f32,
+    /// Mean spawn cadence of a species in milliseconds.
+    pub cadence_mean_ms: f32,
+    /// Deploy duration above which cadences are compressed, in milliseconds.
+    pub duration_target_ms: u32,
+}
+
+impl PressureV2 {
+    /// Evaluates the deterministic difficulty curves of the global tuning at every level in
+    /// `difficulties`, in ascending order.
+    ///
+    /// Consumes no RNG state and leaves telemetry untouched, so a tuning UI can call it after
+    /// every knob change. Level overrides are not applied.
+    #[must_use]
+    pub fn preview_curves(&self, difficulties: RangeInclusive<u32>) -> Vec<CurvePoint> {
+        difficulties
+            .map(|level| {
+                let difficulty = level as f32;
+                CurvePoint {
+                    difficulty: DifficultyLevel::new(level),
+                    count_mean: self.count_mean(difficulty),
+                    hp_mean_multiplier: self.hp_mean_multiplier(difficulty),
+                    speed_mean_multiplier: self.speed_mean_multiplier(difficulty),
+                    cadence_mean_ms: self.cadence_mean_ms(difficulty),
+                    duration_target_ms: self.duration_target_ms(difficulty),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maze_defence_core::{LevelId, PressureWaveInputs, WaveId};
+
+    #[test]
+    fn curves_match_the_means_a_generated_wave_records() {
+        let mut generator = PressureV2::default();
+        let curves = generator.preview_curves(1..=12);
+        assert_eq!(curves.len(), 12);
+        assert!(curves
+            .windows(2)
+            .all(|pair| pair[0].count_mean <= pair[1].count_mean));
+
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        for point in &curves {
+            let inputs =
+                PressureWaveInputs::new(3, LevelId::new(1), WaveId::new(0), point.difficulty);
+            generator.generate(&inputs, &mut spawns, &mut prototypes);
+            let latents = generator.telemetry().difficulty_latents();
+            assert_eq!(point.count_mean, latents.bug_count_mean);
+            assert_eq!(point.hp_mean_multiplier, latents.hp_mean_multiplier);
+            assert_eq!(point.speed_mean_multiplier, latents.speed_mean_multiplier);
+            assert_eq!(
+                point.duration_target_ms,
+                generator.telemetry().cadence_compression().t_target
+            );
+            assert!(point.cadence_mean_ms >= generator.tuning().cadence.cadence_floor_ms as f32);
+        }
+
+        generator.tuning_mut().count.cap *= 2.0;
+        let steeper = generator.preview_curves(12..=12);
+        assert!(steeper[0].count_mean > curves[11].count_mean);
+    }
+}
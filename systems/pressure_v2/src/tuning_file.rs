@@ -0,0 +1,447 @@
+//! Loading, saving and validating [`PressureTuning`] data files.
+
+use std::{error::Error, fmt, io};
+
+use crate::PressureTuning;
+
+/// Errors raised while reading, writing or validating pressure tuning data.
+#[derive(Debug)]
+pub enum TuningError {
+    /// The data could not be read, written or parsed as JSON.
+    Format(serde_json::Error),
+    /// A tuning value lies outside the range the generator supports.
+    OutOfRange {
+        /// Dotted path of the offending field, e.g. `hp.deviation`.
+        field: &'static str,
+        /// What the value must satisfy and what it was.
+        reason: String,
+    },
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format(error) => write!(f, "tuning data is not valid JSON: {error}"),
+            Self::OutOfRange { field, reason } => {
+                write!(f, "tuning field `{field}` is out of range: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for TuningError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Format(error) => Some(error),
+            Self::OutOfRange { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for TuningError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Format(error)
+    }
+}
+
+impl PressureTuning {
+    /// Reads tuning from JSON and validates it; omitted fields keep their default values.
+    pub fn from_reader(reader: impl io::Read) -> Result<Self, TuningError> {
+        let tuning: Self = serde_json::from_reader(reader)?;
+        tuning.validate()?;
+        Ok(tuning)
+    }
+
+    /// Writes every tuning value as pretty-printed JSON that [`Self::from_reader`] reads back.
+    pub fn to_writer(&self, writer: impl io::Write) -> Result<(), TuningError> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Rejects values the generator cannot sample from, such as negative deviations or
+    /// minimums above their maximums.
+    ///
+    /// Every float must also be finite. The first offending field is reported.
+    pub fn validate(&self) -> Result<(), TuningError> {
+        let count = &self.count;
+        non_negative("count.minimum", count.minimum)?;
+        ordered(("count.minimum", count.minimum), ("count.cap", count.cap))?;
+        non_negative("count.slope", count.slope)?;
+        finite("count.midpoint", count.midpoint)?;
+        non_negative("count.deviation_ratio", count.deviation_ratio)?;
+
+        let hp = &self.hp;
+        non_negative("hp.soft_boost_fraction", hp.soft_boost_fraction)?;
+        non_negative("hp.soft_boost_rate", hp.soft_boost_rate)?;
+        non_negative("hp.log_growth_scale", hp.log_growth_scale)?;
+        non_negative("hp.log_growth_rate", hp.log_growth_rate)?;
+        finite("hp.growth_pivot", hp.growth_pivot)?;
+        non_negative("hp.deviation", hp.deviation)?;
+        positive("hp.min_multiplier", hp.min_multiplier)?;
+        non_negative("hp.max_standard_deviations", hp.max_standard_deviations)?;
+
+        let speed = &self.speed;
+        non_negative("speed.soft_boost_fraction", speed.soft_boost_fraction)?;
+        non_negative("speed.soft_boost_rate", speed.soft_boost_rate)?;
+        positive("speed.post_pivot_growth", speed.post_pivot_growth)?;
+        finite("speed.growth_pivot", speed.growth_pivot)?;
+        non_negative("speed.deviation", speed.deviation)?;
+        positive("speed.min_multiplier", speed.min_multiplier)?;
+        ordered(
+            ("speed.min_multiplier", speed.min_multiplier),
+            ("speed.max_multiplier", speed.max_multiplier),
+        )?;
+
+        let components = &self.components;
+        non_negative("components.poisson_intercept", components.poisson_intercept)?;
+        non_negative("components.poisson_slope", components.poisson_slope)?;
+        if components.poisson_cap == 0 {
+            return Err(out_of_range(
+                "components.poisson_cap",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+        within(
+            "components.minimum_share",
+            components.minimum_share,
+            0.0..1.0,
+        )?;
+        positive(
+            "components.dirichlet_concentration",
+            components.dirichlet_concentration,
+        )?;
+        non_negative("components.log_hp_sigma", components.log_hp_sigma)?;
+        non_negative("components.log_speed_sigma", components.log_speed_sigma)?;
+        finite("components.log_correlation", components.log_correlation)?;
+        if components.log_correlation.abs() > 1.0 {
+            return Err(out_of_range(
+                "components.log_correlation",
+                format!(
+                    "must lie between -1 and 1 (got {})",
+                    components.log_correlation
+                ),
+            ));
+        }
+        positive("components.hp_multiplier_min", components.hp_multiplier_min)?;
+        non_negative(
+            "components.hp_multiplier_spread",
+            components.hp_multiplier_spread,
+        )?;
+        positive(
+            "components.speed_multiplier_min",
+            components.speed_multiplier_min,
+        )?;
+        ordered(
+            (
+                "components.speed_multiplier_min",
+                components.speed_multiplier_min,
+            ),
+            (
+                "components.speed_multiplier_max",
+                components.speed_multiplier_max,
+            ),
+        )?;
+
+        let weights = &self.pressure_weights;
+        non_negative("pressure_weights.alpha", weights.alpha)?;
+        non_negative("pressure_weights.beta", weights.beta)?;
+        finite("pressure_weights.gamma", weights.gamma)?;
+
+        let cadence = &self.cadence;
+        ordered(
+            ("cadence.cadence_floor_ms", cadence.cadence_floor_ms as f32),
+            ("cadence.cadence_max_ms", cadence.cadence_max_ms as f32),
+        )?;
+        non_negative(
+            "cadence.cadence_deviation_ratio",
+            cadence.cadence_deviation_ratio,
+        )?;
+        non_negative("cadence.cadence_base_ms", cadence.cadence_base_ms)?;
+        non_negative("cadence.cadence_decay_rate", cadence.cadence_decay_rate)?;
+        non_negative("cadence.start_base_ms", cadence.start_base_ms)?;
+        finite("cadence.start_slope_ms", cadence.start_slope_ms)?;
+        non_negative(
+            "cadence.start_deviation_ratio",
+            cadence.start_deviation_ratio,
+        )?;
+        non_negative("cadence.duration_base_ms", cadence.duration_base_ms)?;
+        finite("cadence.duration_slope_ms", cadence.duration_slope_ms)?;
+
+        non_negative("tint.min_delta_e", self.tint.min_delta_e)?;
+
+        let archetypes = &self.archetypes;
+        non_negative("archetypes.basic_weight", archetypes.basic_weight)?;
+        non_negative("archetypes.armored_weight", archetypes.armored_weight)?;
+        non_negative(
+            "archetypes.regenerating_weight",
+            archetypes.regenerating_weight,
+        )?;
+        non_negative("archetypes.splitter_weight", archetypes.splitter_weight)?;
+        let total_weight = archetypes.basic_weight
+            + archetypes.armored_weight
+            + archetypes.regenerating_weight
+            + archetypes.splitter_weight;
+        if total_weight <= 0.0 {
+            return Err(out_of_range(
+                "archetypes.basic_weight",
+                "at least one archetype weight must be positive".to_string(),
+            ));
+        }
+        positive("archetypes.armored_pressure", archetypes.armored_pressure)?;
+        positive(
+            "archetypes.regenerating_pressure",
+            archetypes.regenerating_pressure,
+        )?;
+        positive("archetypes.splitter_pressure", archetypes.splitter_pressure)?;
+
+        if self.lanes.lanes == 0 {
+            return Err(out_of_range(
+                "lanes.lanes",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+
+        let elite = &self.elite;
+        finite("elite.min_difficulty", elite.min_difficulty)?;
+        positive("elite.hp_multiplier", elite.hp_multiplier)?;
+        positive("elite.speed_multiplier", elite.speed_multiplier)?;
+        positive("elite.count_fraction", elite.count_fraction)?;
+        if elite.count_fraction > 1.0 {
+            return Err(out_of_range(
+                "elite.count_fraction",
+                format!("must not exceed 1 (got {})", elite.count_fraction),
+            ));
+        }
+
+        let bursts = &self.bursts;
+        if bursts.burst_size == 0 {
+            return Err(out_of_range(
+                "bursts.burst_size",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+        if bursts.gap.min_ms() > bursts.gap.max_ms() {
+            return Err(out_of_range(
+                "bursts.gap",
+                format!(
+                    "`min_ms` must not exceed `max_ms` (got {} > {})",
+                    bursts.gap.min_ms(),
+                    bursts.gap.max_ms()
+                ),
+            ));
+        }
+
+        let smoothing = &self.smoothing;
+        if smoothing.max_simultaneous == 0 {
+            return Err(out_of_range(
+                "smoothing.max_simultaneous",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+        if smoothing.window_ms == 0 {
+            return Err(out_of_range(
+                "smoothing.window_ms",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+
+        let adaptation = &self.adaptation;
+        if adaptation.par_clear_ms == 0 {
+            return Err(out_of_range(
+                "adaptation.par_clear_ms",
+                "must be at least 1 (got 0)".to_string(),
+            ));
+        }
+        non_negative("adaptation.pace_weight", adaptation.pace_weight)?;
+        non_negative("adaptation.leak_relief", adaptation.leak_relief)?;
+        non_negative("adaptation.max_shift", adaptation.max_shift)?;
+        if adaptation.max_shift >= 1.0 {
+            return Err(out_of_range(
+                "adaptation.max_shift",
+                format!("must be below 1 (got {})", adaptation.max_shift),
+            ));
+        }
+
+        for (index, budget) in self.budget.waves.iter().enumerate() {
+            if self.budget.waves[..index]
+                .iter()
+                .any(|earlier| earlier.wave == budget.wave)
+            {
+                return Err(out_of_range(
+                    "budget.waves",
+                    format!("lists wave {} more than once", budget.wave),
+                ));
+            }
+            if budget.max_bug_count == Some(0) {
+                return Err(out_of_range(
+                    "budget.waves.max_bug_count",
+                    "must be at least 1 (got 0)".to_string(),
+                ));
+            }
+            if budget.max_total_hp == Some(0) {
+                return Err(out_of_range(
+                    "budget.waves.max_total_hp",
+                    "must be at least 1 (got 0)".to_string(),
+                ));
+            }
+            if let Some(max_pressure) = budget.max_pressure {
+                positive("budget.waves.max_pressure", max_pressure)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn out_of_range(field: &'static str, reason: String) -> TuningError {
+    TuningError::OutOfRange { field, reason }
+}
+
+fn finite(field: &'static str, value: f32) -> Result<(), TuningError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(out_of_range(field, format!("must be finite (got {value})")))
+    }
+}
+
+fn non_negative(field: &'static str, value: f32) -> Result<(), TuningError> {
+    finite(field, value)?;
+    if value < 0.0 {
+        return Err(out_of_range(
+            field,
+            format!("must not be negative (got {value})"),
+        ));
+    }
+    Ok(())
+}
+
+fn positive(field: &'static str, value: f32) -> Result<(), TuningError> {
+    finite(field, value)?;
+    if value <= 0.0 {
+        return Err(out_of_range(
+            field,
+            format!("must be greater than zero (got {value})"),
+        ));
+    }
+    Ok(())
+}
+
+fn within(field: &'static str, value: f32, range: std::ops::Range<f32>) -> Result<(), TuningError> {
+    finite(field, value)?;
+    if !range.contains(&value) {
+        return Err(out_of_range(
+            field,
+            format!(
+                "must be at least {} and below {} (got {value})",
+                range.start, range.end
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Requires the lower bound of a pair not to exceed the upper one.
+fn ordered(
+    (min_field, min): (&'static str, f32),
+    (max_field, max): (&'static str, f32),
+) -> Result<(), TuningError> {
+    finite(min_field, min)?;
+    finite(max_field, max)?;
+    if min > max {
+        return Err(out_of_range(
+            min_field,
+            format!("must not exceed `{max_field}` (got {min} > {max})"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuning_round_trips_through_json_and_keeps_defaults_for_omitted_fields() {
+        let mut tuning = PressureTuning::default();
+        tuning.count.cap = 400.0;
+        tuning.tint.excluded.push([1, 2, 3]);
+        let mut json = Vec::new();
+        tuning.to_writer(&mut json).expect("writes");
+        let restored = PressureTuning::from_reader(json.as_slice()).expect("reads back");
+        assert_eq!(restored.count.cap, 400.0);
+        assert_eq!(restored.tint.excluded, tuning.tint.excluded);
+
+        let partial = PressureTuning::from_reader(r#"{ "hp": { "deviation": 0.2 } }"#.as_bytes())
+            .expect("partial tuning");
+        assert_eq!(partial.hp.deviation, 0.2);
+        assert_eq!(partial.hp.min_multiplier, tuning.hp.min_multiplier);
+
+        assert!(matches!(
+            PressureTuning::from_reader(r#"{ "hp": { "typo": 1 } }"#.as_bytes()),
+            Err(TuningError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn validation_names_the_out_of_range_field() {
+        let rejected = |json: &str| match PressureTuning::from_reader(json.as_bytes()) {
+            Err(TuningError::OutOfRange { field, .. }) => field,
+            other => panic!("expected an out-of-range error, got {other:?}"),
+        };
+        assert_eq!(
+            rejected(r#"{ "hp": { "deviation": -0.1 } }"#),
+            "hp.deviation"
+        );
+        assert_eq!(
+            rejected(r#"{ "speed": { "min_multiplier": 3.0, "max_multiplier": 2.0 } }"#),
+            "speed.min_multiplier"
+        );
+        assert_eq!(
+            rejected(r#"{ "cadence": { "cadence_floor_ms": 900, "cadence_max_ms": 500 } }"#),
+            "cadence.cadence_floor_ms"
+        );
+        assert_eq!(
+            rejected(r#"{ "components": { "minimum_share": 1.5 } }"#),
+            "components.minimum_share"
+        );
+        assert_eq!(
+            rejected(r#"{ "archetypes": { "armored_pressure": 0.0 } }"#),
+            "archetypes.armored_pressure"
+        );
+        assert_eq!(rejected(r#"{ "lanes": { "lanes": 0 } }"#), "lanes.lanes");
+        assert_eq!(
+            rejected(r#"{ "elite": { "count_fraction": 1.5 } }"#),
+            "elite.count_fraction"
+        );
+        assert_eq!(
+            rejected(r#"{ "smoothing": { "window_ms": 0 } }"#),
+            "smoothing.window_ms"
+        );
+        assert_eq!(
+            rejected(r#"{ "adaptation": { "max_shift": 1.0 } }"#),
+            "adaptation.max_shift"
+        );
+        assert_eq!(
+            rejected(r#"{ "bursts": { "gap": { "min_ms": 900, "max_ms": 500 } } }"#),
+            "bursts.gap"
+        );
+        assert_eq!(
+            rejected(r#"{ "budget": { "waves": [{ "wave": 2 }, { "wave": 2 }] } }"#),
+            "budget.waves"
+        );
+        assert_eq!(
+            rejected(r#"{ "budget": { "waves": [{ "wave": 0, "max_pressure": -1.0 }] } }"#),
+            "budget.waves.max_pressure"
+        );
+
+        let error = PressureTuning::from_reader(r#"{ "count": { "cap": 1.0 } }"#.as_bytes())
+            .expect_err("cap below minimum");
+        assert_eq!(
+            error.to_string(),
+            "tuning field `count.minimum` is out of range: must not exceed `count.cap` (got 20 > 1)"
+        );
+        PressureTuning::default()
+            .validate()
+            .expect("defaults are valid");
+    }
+}
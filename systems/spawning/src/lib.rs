@@ -11,7 +11,10 @@
 
 use std::{convert::TryFrom, time::Duration};
 
-use maze_defence_core::{BugColor, CellCoord, Command, Event, Health, PlayMode};
+use maze_defence_core::{
+    BugArchetype, BugColor, BugRank, CellCoord, Command, DodgeChance, Event, Gold, Health,
+    PathingPersonality, PlayMode,
+};
 
 const RNG_MULTIPLIER: u64 = 6_364_136_223_846_793_005;
 const RNG_INCREMENT: u64 = 1;
@@ -111,6 +114,11 @@ impl Spawning {
                 color,
                 health: DEFAULT_BUG_HEALTH,
                 step_ms: self.step_ms,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
             });
         }
     }
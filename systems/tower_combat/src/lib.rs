@@ -10,12 +10,14 @@
 //! Pure system that emits projectile firing commands from targeting data.
 
 use maze_defence_core::{
-    Command, PlayMode, TowerCooldownSnapshot, TowerCooldownView, TowerId, TowerTarget,
+    Command, NestTarget, PlayMode, TowerBehaviors, TowerCooldownSnapshot, TowerCooldownView,
+    TowerId, TowerTarget,
 };
 
 /// Tower combat system that queues firing commands for ready towers.
 #[derive(Debug, Default)]
 pub struct TowerCombat {
+    behaviors: TowerBehaviors,
     scratch: Vec<Command>,
 }
 
@@ -26,7 +28,18 @@ impl TowerCombat {
         Self::default()
     }
 
-    /// Emits `Command::FireProjectile` entries for towers ready to fire.
+    /// Returns the system with `behaviors` deciding how each tower kind fires.
+    #[must_use]
+    pub fn with_behaviors(mut self, behaviors: TowerBehaviors) -> Self {
+        self.behaviors = behaviors;
+        self
+    }
+
+    /// Emits the commands of every tower's registered behavior.
+    ///
+    /// Every tower first runs its `on_tick` hook; towers ready to fire then run
+    /// `on_fire` at their target, which for the built-in behaviors queues
+    /// `Command::FireProjectile`.
     pub fn handle(
         &mut self,
         play_mode: PlayMode,
@@ -38,10 +51,6 @@ impl TowerCombat {
             return;
         }
 
-        if tower_targets.is_empty() {
-            return;
-        }
-
         let cooldowns = tower_cooldowns.into_vec();
         if cooldowns.is_empty() {
             return;
@@ -49,13 +58,18 @@ impl TowerCombat {
 
         self.scratch.clear();
 
+        for snapshot in &cooldowns {
+            self.behaviors
+                .get(snapshot.kind)
+                .on_tick(snapshot, &mut self.scratch);
+        }
+
         for target in tower_targets {
             if let Some(snapshot) = find_cooldown(&cooldowns, target.tower) {
                 if snapshot.ready_in.is_zero() {
-                    self.scratch.push(Command::FireProjectile {
-                        tower: target.tower,
-                        target: target.bug,
-                    });
+                    self.behaviors
+                        .get(snapshot.kind)
+                        .on_fire(target, &mut self.scratch);
                 }
             }
         }
@@ -67,6 +81,31 @@ impl TowerCombat {
         out.reserve(self.scratch.len());
         out.append(&mut self.scratch);
     }
+
+    /// Emits `Command::FireAtNest` entries for ready towers aiming at nests.
+    pub fn handle_nests(
+        &mut self,
+        play_mode: PlayMode,
+        tower_cooldowns: TowerCooldownView,
+        nest_targets: &[NestTarget],
+        out: &mut Vec<Command>,
+    ) {
+        if play_mode != PlayMode::Attack || nest_targets.is_empty() {
+            return;
+        }
+
+        let cooldowns = tower_cooldowns.into_vec();
+        for target in nest_targets {
+            if find_cooldown(&cooldowns, target.tower)
+                .is_some_and(|snapshot| snapshot.ready_in.is_zero())
+            {
+                out.push(Command::FireAtNest {
+                    tower: target.tower,
+                    nest: target.nest,
+                });
+            }
+        }
+    }
 }
 
 fn find_cooldown(
@@ -82,7 +121,7 @@ fn find_cooldown(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use maze_defence_core::{BugId, CellPoint, TowerKind};
+    use maze_defence_core::{BugId, CellPoint, GridEdge, SpawnerSet, TowerBehavior, TowerKind};
     use std::time::Duration;
 
     #[test]
@@ -145,11 +184,82 @@ mod tests {
         );
     }
 
+    #[derive(Debug)]
+    struct Volley;
+
+    impl TowerBehavior for Volley {
+        fn on_tick(&self, tower: &TowerCooldownSnapshot, out: &mut Vec<Command>) {
+            if !tower.ready_in.is_zero() {
+                out.push(Command::RequestAnalyticsRefresh);
+            }
+        }
+
+        fn on_fire(&self, target: &TowerTarget, out: &mut Vec<Command>) {
+            for _ in 0..2 {
+                out.push(Command::FireProjectile {
+                    tower: target.tower,
+                    target: target.bug,
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn registered_behaviors_tick_every_tower_and_fire_ready_ones() {
+        let mut system = TowerCombat::new()
+            .with_behaviors(TowerBehaviors::default().with_behavior(TowerKind::Basic, Volley));
+        let cooldowns = TowerCooldownView::from_snapshots(vec![
+            snapshot(1, Duration::ZERO),
+            snapshot(2, Duration::from_millis(100)),
+        ]);
+        let mut out = Vec::new();
+
+        system.handle(PlayMode::Attack, cooldowns, &[target(1, 5)], &mut out);
+
+        let shot = Command::FireProjectile {
+            tower: TowerId::new(1),
+            target: BugId::new(5),
+        };
+        assert_eq!(
+            out,
+            vec![Command::RequestAnalyticsRefresh, shot.clone(), shot,]
+        );
+    }
+
+    #[test]
+    fn ready_towers_fire_at_their_nests() {
+        let mut system = TowerCombat::new();
+        let cooldowns = TowerCooldownView::from_snapshots(vec![
+            snapshot(1, Duration::ZERO),
+            snapshot(2, Duration::from_millis(100)),
+        ]);
+        let nest = SpawnerSet::Edge(GridEdge::Top);
+        let targets: Vec<_> = [1, 2]
+            .map(|tower| NestTarget {
+                tower: TowerId::new(tower),
+                nest,
+                tower_center_cells: CellPoint::new(0.0, 0.0),
+                nest_center_cells: CellPoint::new(0.0, 0.0),
+            })
+            .into();
+        let mut out = Vec::new();
+
+        system.handle_nests(PlayMode::Attack, cooldowns, &targets, &mut out);
+        assert_eq!(
+            out,
+            vec![Command::FireAtNest {
+                tower: TowerId::new(1),
+                nest,
+            }]
+        );
+    }
+
     fn snapshot(tower: u32, ready_in: Duration) -> TowerCooldownSnapshot {
         TowerCooldownSnapshot {
             tower: TowerId::new(tower),
             kind: TowerKind::Basic,
             ready_in,
+            heat: None,
         }
     }
 
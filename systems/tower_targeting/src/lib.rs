@@ -10,14 +10,17 @@
 //! Pure system that computes deterministic tower targets from world snapshots.
 
 use maze_defence_core::{
-    BugId, BugView, CellPoint, PlayMode, TowerId, TowerKind, TowerTarget, TowerView,
+    BugId, BugView, CellCoord, CellPoint, CellPointHalf, Health, NestSnapshot, NestTarget,
+    PlayMode, TargetCandidate, TowerBehaviors, TowerSnapshot, TowerTarget, TowerView,
 };
 
 /// Tower targeting system that reuses scratch buffers to avoid repeated allocations.
 #[derive(Debug, Default)]
 pub struct TowerTargeting {
+    behaviors: TowerBehaviors,
     tower_workspace: Vec<TowerWorkspace>,
     bug_workspace: Vec<BugCandidate>,
+    candidates: Vec<TargetCandidate>,
 }
 
 impl TowerTargeting {
@@ -27,16 +30,56 @@ impl TowerTargeting {
         Self::default()
     }
 
+    /// Returns the system with `behaviors` choosing each tower kind's target.
+    #[must_use]
+    pub fn with_behaviors(mut self, behaviors: TowerBehaviors) -> Self {
+        self.behaviors = behaviors;
+        self
+    }
+
     /// Computes tower targets for the provided world snapshot.
     ///
     /// The output buffer is cleared before populating it with the latest
-    /// assignments.
+    /// assignments. Each tower's registered [`TowerBehavior`] picks among the bugs
+    /// within its range; `priority` is passed on when it names one of them, and the
+    /// built-in behaviors target it ahead of the nearest candidate. A pick outside
+    /// the tower's range leaves the tower without a target.
+    ///
+    /// [`TowerBehavior`]: maze_defence_core::TowerBehavior
     pub fn handle(
         &mut self,
         play_mode: PlayMode,
         towers: &TowerView,
         bugs: &BugView,
         cells_per_tile: u32,
+        priority: Option<BugId>,
+        out: &mut Vec<TowerTarget>,
+    ) {
+        self.handle_in_sight(
+            play_mode,
+            towers,
+            bugs,
+            cells_per_tile,
+            priority,
+            |_, _| true,
+            out,
+        );
+    }
+
+    /// Computes tower targets like [`Self::handle`], except that towers whose kind
+    /// [requires line of sight] only consider bugs `in_sight` reports visible from the
+    /// tower centre.
+    ///
+    /// [requires line of sight]: maze_defence_core::TowerKind::requires_line_of_sight
+    #[allow(clippy::too_many_arguments)]
+    pub fn handle_in_sight(
+        &mut self,
+        play_mode: PlayMode,
+        towers: &TowerView,
+        bugs: &BugView,
+        cells_per_tile: u32,
+        priority: Option<BugId>,
+        mut in_sight: impl FnMut(CellPointHalf, CellPointHalf) -> bool,
         out: &mut Vec<TowerTarget>,
     ) {
         out.clear();
@@ -60,45 +103,120 @@ impl TowerTargeting {
         }
 
         for tower in &self.tower_workspace {
-            let radius_cells = tower.kind.range_in_cells(cells_per_tile);
-            let radius_half = i128::from(radius_cells) * 2;
+            let radius_cells = tower.snapshot.kind.range_in_cells(cells_per_tile);
+            let radius_half = u128::from(radius_cells) * 2;
             let max_distance = radius_half * radius_half;
 
-            let mut best: Option<BestCandidate> = None;
-
+            self.candidates.clear();
             for candidate in &self.bug_workspace {
-                let dx = i128::from(candidate.center.column - tower.center.column);
-                let dy = i128::from(candidate.center.row - tower.center.row);
-                let distance_sq = dx * dx + dy * dy;
+                let dx = candidate.center.column.abs_diff(tower.center.column);
+                let dy = candidate.center.row.abs_diff(tower.center.row);
+                let distance_sq = u128::from(dx) * u128::from(dx) + u128::from(dy) * u128::from(dy);
 
                 if distance_sq > max_distance {
                     continue;
                 }
+                if tower.snapshot.kind.requires_line_of_sight()
+                    && !in_sight(tower.center.to_half(), candidate.center.to_half())
+                {
+                    continue;
+                }
 
-                let current = BestCandidate {
-                    distance_sq,
+                self.candidates.push(TargetCandidate {
                     bug: candidate.id,
-                    bug_column: candidate.column,
-                    bug_row: candidate.row,
-                    bug_center: candidate.center,
-                };
-
-                match &mut best {
-                    Some(existing) => {
-                        if current.precedes(existing) {
-                            *existing = current;
-                        }
+                    cell: candidate.cell,
+                    health: candidate.health,
+                    distance_sq,
+                });
+            }
+            if self.candidates.is_empty() {
+                continue;
+            }
+            self.candidates.sort_unstable_by(|left, right| {
+                left.distance_sq
+                    .cmp(&right.distance_sq)
+                    .then(left.bug.cmp(&right.bug))
+            });
+
+            let in_range = priority.filter(|bug| {
+                self.candidates
+                    .iter()
+                    .any(|candidate| candidate.bug == *bug)
+            });
+            let Some(selected) = self
+                .behaviors
+                .get(tower.snapshot.kind)
+                .select_target(&tower.snapshot, &self.candidates, in_range)
+                .and_then(|bug| {
+                    self.candidates
+                        .iter()
+                        .find(|candidate| candidate.bug == bug)
+                })
+            else {
+                continue;
+            };
+
+            out.push(TowerTarget {
+                tower: tower.snapshot.id,
+                bug: selected.bug,
+                tower_center_cells: tower.center.to_cell_point(),
+                bug_center_cells: HalfCellPoint::of_cell(selected.cell).to_cell_point(),
+            });
+        }
+    }
+
+    /// Aims every tower without a bug target at the nearest nest cell within its range.
+    ///
+    /// Bugs always take precedence, so towers listed in `busy` are skipped. Ties go to the
+    /// nest listed first and then to the smaller cell. The output buffer is cleared first.
+    pub fn handle_nests(
+        &mut self,
+        play_mode: PlayMode,
+        towers: &TowerView,
+        nests: &[NestSnapshot],
+        busy: &[TowerTarget],
+        cells_per_tile: u32,
+        out: &mut Vec<NestTarget>,
+    ) {
+        out.clear();
+
+        if play_mode != PlayMode::Attack || nests.is_empty() {
+            return;
+        }
+
+        self.prepare_tower_workspace(towers);
+        for tower in &self.tower_workspace {
+            if !tower.snapshot.kind.fires()
+                || busy.iter().any(|target| target.tower == tower.snapshot.id)
+            {
+                continue;
+            }
+
+            let radius_half = i128::from(tower.snapshot.kind.range_in_cells(cells_per_tile)) * 2;
+            let max_distance = radius_half * radius_half;
+
+            let mut best: Option<(i128, &NestSnapshot, HalfCellPoint)> = None;
+            for nest in nests {
+                for cell in &nest.cells {
+                    let center = HalfCellPoint::of_cell(*cell);
+                    let dx = i128::from(center.column - tower.center.column);
+                    let dy = i128::from(center.row - tower.center.row);
+                    let distance_sq = dx * dx + dy * dy;
+                    if distance_sq > max_distance
+                        || best.is_some_and(|(closest, _, _)| closest <= distance_sq)
+                    {
+                        continue;
                     }
-                    None => best = Some(current),
+                    best = Some((distance_sq, nest, center));
                 }
             }
 
-            if let Some(best_candidate) = best {
-                out.push(TowerTarget {
-                    tower: tower.id,
-                    bug: best_candidate.bug,
+            if let Some((_, nest, center)) = best {
+                out.push(NestTarget {
+                    tower: tower.snapshot.id,
+                    nest: nest.nest,
                     tower_center_cells: tower.center.to_cell_point(),
-                    bug_center_cells: best_candidate.bug_center.to_cell_point(),
+                    nest_center_cells: center.to_cell_point(),
                 });
             }
         }
@@ -123,8 +241,7 @@ impl TowerTargeting {
             };
 
             self.tower_workspace.push(TowerWorkspace {
-                id: snapshot.id,
-                kind: snapshot.kind,
+                snapshot: *snapshot,
                 center,
             });
         }
@@ -136,17 +253,11 @@ impl TowerTargeting {
         self.bug_workspace.reserve(lower);
 
         for snapshot in bugs.iter() {
-            let cell = snapshot.cell;
-            let center = HalfCellPoint {
-                column: i64::from(cell.column()) * 2 + 1,
-                row: i64::from(cell.row()) * 2 + 1,
-            };
-
             self.bug_workspace.push(BugCandidate {
                 id: snapshot.id,
-                column: cell.column(),
-                row: cell.row(),
-                center,
+                cell: snapshot.cell,
+                health: snapshot.health,
+                center: HalfCellPoint::of_cell(snapshot.cell),
             });
         }
     }
@@ -154,16 +265,15 @@ impl TowerTargeting {
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct TowerWorkspace {
-    id: TowerId,
-    kind: TowerKind,
+    snapshot: TowerSnapshot,
     center: HalfCellPoint,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct BugCandidate {
     id: BugId,
-    column: u32,
-    row: u32,
+    cell: CellCoord,
+    health: Health,
     center: HalfCellPoint,
 }
 
@@ -174,44 +284,29 @@ struct HalfCellPoint {
 }
 
 impl HalfCellPoint {
+    fn of_cell(cell: CellCoord) -> Self {
+        Self {
+            column: i64::from(cell.column()) * 2 + 1,
+            row: i64::from(cell.row()) * 2 + 1,
+        }
+    }
+
     fn to_cell_point(self) -> CellPoint {
         CellPoint::new(self.column as f32 / 2.0, self.row as f32 / 2.0)
     }
-}
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct BestCandidate {
-    distance_sq: i128,
-    bug: BugId,
-    bug_column: u32,
-    bug_row: u32,
-    bug_center: HalfCellPoint,
-}
-
-impl BestCandidate {
-    fn precedes(&self, other: &Self) -> bool {
-        if self.distance_sq != other.distance_sq {
-            return self.distance_sq < other.distance_sq;
-        }
-
-        if self.bug != other.bug {
-            return self.bug < other.bug;
-        }
-
-        if self.bug_column != other.bug_column {
-            return self.bug_column < other.bug_column;
-        }
-
-        self.bug_row < other.bug_row
+    fn to_half(self) -> CellPointHalf {
+        CellPointHalf::new(self.column, self.row)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CellPoint, TowerTarget, TowerTargeting};
+    use super::{CellPoint, CellPointHalf, NestSnapshot, NestTarget, TowerTarget, TowerTargeting};
     use maze_defence_core::{
-        BugId, BugSnapshot, BugView, CellCoord, CellRect, CellRectSize, Health, PlayMode, TowerId,
-        TowerKind, TowerSnapshot, TowerView,
+        BugId, BugSnapshot, BugView, CellCoord, CellRect, CellRectSize, Gold, GridEdge, Health,
+        InertBehavior, PathingPersonality, PlayMode, SpawnerSet, TargetCandidate, TargetingPolicy,
+        TowerBehavior, TowerBehaviors, TowerId, TowerKind, TowerRotation, TowerSnapshot, TowerView,
     };
     fn tower_view(snapshots: Vec<TowerSnapshot>) -> TowerView {
         TowerView::from_snapshots(snapshots)
@@ -229,6 +324,9 @@ mod tests {
                 CellCoord::new(origin.0, origin.1),
                 CellRectSize::new(size.0, size.1),
             ),
+            rotation: TowerRotation::Deg0,
+            targeting: TargetingPolicy::Nearest,
+            upgrades: 0,
         }
     }
 
@@ -242,6 +340,10 @@ mod tests {
             step_ms: 250,
             accum_ms: 0,
             ready_for_step: true,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            boss_phase: None,
+            status: None,
         }
     }
 
@@ -252,7 +354,7 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(2, (7, 5))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
 
         assert_eq!(out.len(), 1);
         let target = out[0];
@@ -262,6 +364,129 @@ mod tests {
         assert_eq!(target.bug_center_cells, CellPoint::new(7.5, 5.5));
     }
 
+    #[test]
+    fn towers_requiring_sight_skip_bugs_hidden_behind_obstacles() {
+        let mut system = TowerTargeting::new();
+        let towers = tower_view(vec![tower_snapshot(1, (4, 4), (2, 2))]);
+        let bugs = bug_view(vec![bug_snapshot(2, (7, 5)), bug_snapshot(3, (5, 7))]);
+
+        let mut sight_lines = Vec::new();
+        let mut out = Vec::new();
+        system.handle_in_sight(
+            PlayMode::Attack,
+            &towers,
+            &bugs,
+            2,
+            None,
+            |from, to| {
+                sight_lines.push((from, to));
+                to.column_half() != 15
+            },
+            &mut out,
+        );
+
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].bug, BugId::new(3), "the nearer bug is hidden");
+        assert_eq!(
+            sight_lines,
+            vec![
+                (CellPointHalf::new(10, 10), CellPointHalf::new(15, 11)),
+                (CellPointHalf::new(10, 10), CellPointHalf::new(11, 15)),
+            ]
+        );
+    }
+
+    #[derive(Debug)]
+    struct Weakest;
+
+    impl TowerBehavior for Weakest {
+        fn select_target(
+            &self,
+            _tower: &TowerSnapshot,
+            candidates: &[TargetCandidate],
+            _priority: Option<BugId>,
+        ) -> Option<BugId> {
+            candidates
+                .iter()
+                .min_by_key(|candidate| candidate.health.get())
+                .map(|candidate| candidate.bug)
+        }
+    }
+
+    #[test]
+    fn registered_behaviors_choose_among_bugs_in_range() {
+        let mut system = TowerTargeting::new()
+            .with_behaviors(TowerBehaviors::default().with_behavior(TowerKind::Basic, Weakest));
+        let towers = tower_view(vec![tower_snapshot(1, (4, 4), (2, 2))]);
+        let mut wounded = bug_snapshot(3, (8, 5));
+        wounded.health = Health::new(1);
+        let mut out_of_range = bug_snapshot(4, (30, 30));
+        out_of_range.health = Health::new(0);
+        let bugs = bug_view(vec![bug_snapshot(2, (6, 5)), wounded, out_of_range]);
+
+        let mut out = Vec::new();
+        system.handle(
+            PlayMode::Attack,
+            &towers,
+            &bugs,
+            2,
+            Some(BugId::new(2)),
+            &mut out,
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].bug, BugId::new(3));
+        assert_eq!(out[0].bug_center_cells, CellPoint::new(8.5, 5.5));
+
+        let mut system = TowerTargeting::new().with_behaviors(
+            TowerBehaviors::default().with_behavior(TowerKind::Basic, InertBehavior),
+        );
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
+        assert!(out.is_empty(), "inert towers hold fire");
+    }
+
+    #[test]
+    fn idle_towers_target_the_nearest_nest_in_range() {
+        let mut system = TowerTargeting::new();
+        let towers = tower_view(vec![
+            tower_snapshot(1, (4, 4), (2, 2)),
+            tower_snapshot(2, (30, 30), (2, 2)),
+            tower_snapshot(3, (4, 8), (2, 2)),
+        ]);
+        let nest = |set, cells: &[(u32, u32)]| NestSnapshot {
+            nest: set,
+            cells: cells
+                .iter()
+                .map(|&(column, row)| CellCoord::new(column, row))
+                .collect(),
+            health: Health::new(5),
+            max_health: Health::new(10),
+        };
+        let nests = vec![
+            nest(SpawnerSet::Edge(GridEdge::Top), &[(4, 0), (5, 0), (6, 0)]),
+            nest(SpawnerSet::Portal(CellCoord::new(7, 5)), &[(7, 5)]),
+        ];
+        let busy = vec![TowerTarget {
+            tower: TowerId::new(3),
+            bug: BugId::new(9),
+            tower_center_cells: CellPoint::new(5.0, 9.0),
+            bug_center_cells: CellPoint::new(5.5, 10.5),
+        }];
+
+        let mut out = Vec::new();
+        system.handle_nests(PlayMode::Attack, &towers, &nests, &busy, 2, &mut out);
+
+        assert_eq!(
+            out,
+            vec![NestTarget {
+                tower: TowerId::new(1),
+                nest: SpawnerSet::Portal(CellCoord::new(7, 5)),
+                tower_center_cells: CellPoint::new(5.0, 5.0),
+                nest_center_cells: CellPoint::new(7.5, 5.5),
+            }],
+            "out-of-range and busy towers leave nests alone"
+        );
+    }
+
     #[test]
     fn bug_outside_range_is_ignored() {
         let mut system = TowerTargeting::new();
@@ -269,7 +494,7 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(2, (20, 20))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
 
         assert!(out.is_empty());
     }
@@ -281,12 +506,42 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(20, (4, 3)), bug_snapshot(10, (1, 3))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
 
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].bug, BugId::new(10));
     }
 
+    #[test]
+    fn priority_target_in_range_overrides_nearest_bug() {
+        let mut system = TowerTargeting::new();
+        let towers = tower_view(vec![tower_snapshot(1, (4, 4), (2, 2))]);
+        let bugs = bug_view(vec![bug_snapshot(2, (6, 5)), bug_snapshot(3, (8, 5))]);
+
+        let mut out = Vec::new();
+        system.handle(
+            PlayMode::Attack,
+            &towers,
+            &bugs,
+            2,
+            Some(BugId::new(3)),
+            &mut out,
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].bug, BugId::new(3));
+
+        system.handle(
+            PlayMode::Attack,
+            &towers,
+            &bugs,
+            2,
+            Some(BugId::new(99)),
+            &mut out,
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].bug, BugId::new(2));
+    }
+
     #[test]
     fn column_tie_break_prefers_smaller_column() {
         let mut system = TowerTargeting::new();
@@ -294,7 +549,7 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(10, (6, 5)), bug_snapshot(10, (4, 5))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 4, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 4, None, &mut out);
 
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].bug_center_cells, CellPoint::new(4.5, 5.5));
@@ -307,7 +562,7 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(10, (5, 6)), bug_snapshot(10, (5, 4))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 4, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 4, None, &mut out);
 
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].bug_center_cells, CellPoint::new(5.5, 4.5));
@@ -320,7 +575,7 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(1, (2, 2))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
 
         assert!(out.is_empty());
     }
@@ -338,7 +593,7 @@ mod tests {
             bug_center_cells: CellPoint::new(0.0, 0.0),
         }];
 
-        system.handle(PlayMode::Builder, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Builder, &towers, &bugs, 2, None, &mut out);
         assert!(out.is_empty());
     }
 
@@ -349,12 +604,12 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(1, (1, 1))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
         assert!(out.is_empty());
 
         let towers = tower_view(vec![tower_snapshot(1, (0, 0), (2, 2))]);
         let bugs = bug_view(Vec::new());
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
         assert!(out.is_empty());
     }
 
@@ -365,14 +620,14 @@ mod tests {
         let bugs = bug_view(vec![bug_snapshot(1, (7, 0))]);
 
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
         assert_eq!(
             out.len(),
             1,
             "bug should be in range with larger cells_per_tile"
         );
 
-        system.handle(PlayMode::Attack, &towers, &bugs, 1, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 1, None, &mut out);
         assert!(
             out.is_empty(),
             "bug should fall out of range with smaller cells_per_tile"
@@ -385,12 +640,43 @@ mod tests {
         let towers = tower_view(vec![tower_snapshot(1, (0, 0), (2, 2))]);
         let bugs = bug_view(vec![bug_snapshot(1, (2, 0)), bug_snapshot(2, (20, 0))]);
         let mut out = Vec::new();
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].bug, BugId::new(1));
 
         let bugs = bug_view(vec![bug_snapshot(2, (20, 0))]);
-        system.handle(PlayMode::Attack, &towers, &bugs, 2, &mut out);
+        system.handle(PlayMode::Attack, &towers, &bugs, 2, None, &mut out);
         assert!(out.is_empty(), "far bug should not be targeted when alone");
     }
+
+    #[test]
+    fn targeting_policies_pick_by_health_and_yield_to_the_priority_target() {
+        let mut system = TowerTargeting::new();
+        let bug = |id, cell, health| BugSnapshot {
+            health: Health::new(health),
+            ..bug_snapshot(id, cell)
+        };
+        let bugs = bug_view(vec![
+            bug(2, (6, 5), 2),
+            bug(3, (7, 5), 1),
+            bug(4, (8, 5), 3),
+            bug(5, (6, 4), 3),
+        ]);
+
+        let mut out = Vec::new();
+        for (policy, expected, priority) in [
+            (TargetingPolicy::Nearest, 2, None),
+            (TargetingPolicy::Weakest, 3, None),
+            (TargetingPolicy::Strongest, 5, None),
+            (TargetingPolicy::Weakest, 4, Some(BugId::new(4))),
+        ] {
+            let towers = tower_view(vec![TowerSnapshot {
+                targeting: policy,
+                ..tower_snapshot(1, (4, 4), (2, 2))
+            }]);
+            system.handle(PlayMode::Attack, &towers, &bugs, 2, priority, &mut out);
+            assert_eq!(out.len(), 1);
+            assert_eq!(out[0].bug, BugId::new(expected), "{policy:?}");
+        }
+    }
 }
@@ -4,9 +4,9 @@ use std::{
 };
 
 use maze_defence_core::{
-    BugColor, BugId, CellCoord, CellPoint, CellRect, Command, Event, Gold, Health,
-    NavigationFieldView, PendingWaveDifficulty, PlayMode, SpeciesTableVersion, TileCoord, TowerId,
-    TowerKind, TowerTarget, WaveDifficulty, WaveId,
+    BugArchetype, BugColor, BugId, BugRank, CellCoord, CellPoint, CellRect, Command, DodgeChance,
+    Event, Gold, Health, NavigationFieldView, PathingPersonality, PendingWaveDifficulty, PlayMode,
+    SpeciesTableVersion, TileCoord, TowerId, TowerKind, TowerTarget, WaveDifficulty, WaveId,
 };
 use maze_defence_system_tower_targeting::TowerTargeting;
 use maze_defence_world::{self as world, query, World};
@@ -22,7 +22,7 @@ fn deterministic_replay_handles_equidistant_bugs_and_builder_mode() {
     assert_eq!(first.assignments.len(), script_len);
 
     let fingerprint = first.fingerprint();
-    let expected = 0x4b30_a91f_5799_920e;
+    let expected = 0x5096_0ed2_06c9_34d6;
     assert_eq!(
         fingerprint, expected,
         "fingerprint mismatch: {fingerprint:#x}"
@@ -85,6 +85,7 @@ fn replay(commands: Vec<Command>) -> ReplayOutcome {
             &towers,
             &bugs,
             cells_per_tile,
+            query::priority_target(&world),
             &mut current_targets,
         );
 
@@ -130,12 +131,22 @@ fn scripted_commands() -> Vec<Command> {
         color: BugColor::from_rgb(0xff, 0, 0),
         health: Health::new(3),
         step_ms: 250,
+        pathing: PathingPersonality::Direct,
+        bounty: Gold::new(1),
+        rank: BugRank::Regular,
+        dodge: DodgeChance::NONE,
+        archetype: BugArchetype::Basic,
     };
     let spawn_second = Command::SpawnBug {
         spawner: second_spawner,
         color: BugColor::from_rgb(0, 0xff, 0),
         health: Health::new(3),
         step_ms: 250,
+        pathing: PathingPersonality::Direct,
+        bounty: Gold::new(1),
+        rank: BugRank::Regular,
+        dodge: DodgeChance::NONE,
+        archetype: BugArchetype::Basic,
     };
     let exit_to_builder = Command::SetPlayMode {
         mode: PlayMode::Builder,
@@ -362,24 +373,15 @@ impl From<Event> for EventRecord {
                 wave: inputs.wave(),
                 spawn_count: plan.spawns().len(),
             },
-            Event::WaveStarted {
+            Event::WaveStarted { wave, launch } => Self::WaveStarted {
                 wave,
-                difficulty,
-                effective_difficulty,
-                reward_multiplier,
-                pressure_scalar,
-                plan_pressure,
-                plan_species_table_version,
-                plan_burst_count,
-            } => Self::WaveStarted {
-                wave,
-                difficulty,
-                effective_difficulty: effective_difficulty.get(),
-                reward_multiplier,
-                pressure_scalar,
-                plan_pressure: plan_pressure.get(),
-                plan_species_table_version: plan_species_table_version.get(),
-                plan_burst_count,
+                difficulty: launch.difficulty,
+                effective_difficulty: launch.effective_difficulty.level().get(),
+                reward_multiplier: launch.reward_multiplier,
+                pressure_scalar: launch.pressure_scalar,
+                plan_pressure: launch.plan_pressure.to_u32_saturating(),
+                plan_species_table_version: launch.plan_species_table_version.get(),
+                plan_burst_count: launch.plan_burst_count,
             },
             Event::AnalyticsUpdated { report } => Self::AnalyticsUpdated {
                 coverage_bps: report.tower_coverage_mean_bps(),
@@ -1,10 +1,9 @@
 //! Immutable analytics snapshots sourced from the authoritative world state.
 
 use crate::World;
-use maze_defence_core::{AnalyticsInputs, AnalyticsLayoutSnapshot, CellCoord, TowerAnalyticsView};
-
-#[cfg(any(test, feature = "tower_scaffolding"))]
-use maze_defence_core::TowerAnalyticsSnapshot;
+use maze_defence_core::{
+    AnalyticsInputs, AnalyticsLayoutSnapshot, CellCoord, TowerAnalyticsSnapshot, TowerAnalyticsView,
+};
 
 /// Captures the full set of analytics inputs required for recomputation.
 pub(crate) fn snapshot(world: &World) -> AnalyticsInputs {
@@ -25,7 +24,6 @@ pub(crate) fn tower_view(world: &World) -> TowerAnalyticsView {
     gather_towers(world)
 }
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 fn gather_towers(world: &World) -> TowerAnalyticsView {
     if world.towers.is_empty() {
         return TowerAnalyticsView::default();
@@ -40,22 +38,16 @@ fn gather_towers(world: &World) -> TowerAnalyticsView {
             kind: tower.kind,
             region: tower.region,
             range_cells: tower.kind.range_in_cells(cells_per_tile),
-            damage_per_second: compute_tower_dps(tower.kind),
+            damage_per_second: compute_tower_dps(world.tower_content.stats(tower.kind)),
         })
         .collect();
 
     TowerAnalyticsView::from_snapshots(snapshots)
 }
 
-#[cfg(not(any(test, feature = "tower_scaffolding")))]
-fn gather_towers(_world: &World) -> TowerAnalyticsView {
-    TowerAnalyticsView::default()
-}
-
-#[cfg(any(test, feature = "tower_scaffolding"))]
-fn compute_tower_dps(kind: maze_defence_core::TowerKind) -> u32 {
-    let damage = u64::from(kind.projectile_damage().get());
-    let cooldown_ms = u64::from(kind.fire_cooldown_ms().max(1));
+fn compute_tower_dps(stats: maze_defence_core::TowerStats) -> u32 {
+    let damage = u64::from(stats.projectile_damage.get());
+    let cooldown_ms = u64::from(stats.fire_cooldown_ms.max(1));
     let per_second = damage.saturating_mul(1_000).saturating_div(cooldown_ms);
     per_second.min(u64::from(u32::MAX)) as u32
 }
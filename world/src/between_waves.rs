@@ -0,0 +1,77 @@
+//! Deterministic rolls for the random events that happen between waves.
+
+use maze_defence_core::{BetweenWaveEvent, TowerId, WaveId};
+
+/// Chance, in percent, that a meteor destroys a tower after a wave.
+const METEOR_STRIKE_PERCENT: u64 = 15;
+/// Chance, in percent, that a merchant offers a tower discount after a wave.
+const MERCHANT_OFFER_PERCENT: u64 = 20;
+/// Chance, in percent, that the next wave pays double rewards.
+const GOLD_RUSH_PERCENT: u64 = 15;
+/// Discount granted on the next tower when the merchant offer is accepted.
+pub(crate) const MERCHANT_DISCOUNT_PERCENT: u32 = 50;
+
+/// Rolls the event following `wave`, if any.
+///
+/// The roll depends only on the world seed, the wave identifier and the towers present, so
+/// replays see the same events. A meteor roll without any tower to strike yields no event.
+pub(crate) fn roll(seed: u64, wave: WaveId, towers: &[TowerId]) -> Option<BetweenWaveEvent> {
+    let sample = splitmix64(seed ^ u64::from(wave.get()).wrapping_mul(0xa076_1d64_78bd_642f));
+    let percentile = sample % 100;
+    let pick = sample / 100;
+
+    if percentile < METEOR_STRIKE_PERCENT {
+        let index = usize::try_from(pick % towers.len().max(1) as u64).ok()?;
+        return towers
+            .get(index)
+            .map(|tower| BetweenWaveEvent::MeteorStrike { tower: *tower });
+    }
+    if percentile < METEOR_STRIKE_PERCENT + MERCHANT_OFFER_PERCENT {
+        return Some(BetweenWaveEvent::MerchantOffer {
+            discount_percent: MERCHANT_DISCOUNT_PERCENT,
+        });
+    }
+    if percentile < METEOR_STRIKE_PERCENT + MERCHANT_OFFER_PERCENT + GOLD_RUSH_PERCENT {
+        return Some(BetweenWaveEvent::GoldRush);
+    }
+    None
+}
+
+pub(crate) fn splitmix64(mut state: u64) -> u64 {
+    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    state ^ (state >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_are_deterministic_and_cover_every_event() {
+        let towers = [TowerId::new(4), TowerId::new(9)];
+        let mut seen_meteor = false;
+        let mut seen_merchant = false;
+        let mut seen_gold_rush = false;
+        let mut seen_none = false;
+
+        for wave in 0..200 {
+            let wave = WaveId::new(wave);
+            let event = roll(7, wave, &towers);
+            assert_eq!(event, roll(7, wave, &towers));
+            match event {
+                Some(BetweenWaveEvent::MeteorStrike { tower }) => {
+                    assert!(towers.contains(&tower));
+                    assert_eq!(roll(7, wave, &[]), None);
+                    seen_meteor = true;
+                }
+                Some(BetweenWaveEvent::MerchantOffer { .. }) => seen_merchant = true,
+                Some(BetweenWaveEvent::GoldRush) => seen_gold_rush = true,
+                None => seen_none = true,
+            }
+        }
+
+        assert!(seen_meteor && seen_merchant && seen_gold_rush && seen_none);
+    }
+}
@@ -0,0 +1,371 @@
+//! Paces pressure wave plans into species bursts and tracks them during a wave.
+//!
+//! Every species' spawns are split into the number of bursts reported by
+//! [`BurstSchedulingConfig::burst_count`], so the bursts announced during a wave match the
+//! plan summary's burst count. Plans cached without being marked paced, whether the world
+//! generated them or not, are additionally re-timed: spawns of a burst follow a cadence
+//! drawn from the species' [`CadenceRange`] and consecutive bursts are separated by a gap
+//! drawn from its [`BurstGapRange`].
+//!
+//! [`CadenceRange`]: maze_defence_core::CadenceRange
+//! [`BurstGapRange`]: maze_defence_core::BurstGapRange
+
+use std::collections::BTreeMap;
+
+use maze_defence_core::{
+    BurstSchedulingConfig, Event, PressureSpawnRecord, PressureWaveInputs, PressureWavePlan,
+    SpeciesDefinition, SpeciesId,
+};
+
+use crate::between_waves::splitmix64;
+
+/// Salt separating cadence draws from gap draws of the same burst.
+const CADENCE_SALT: u64 = 0x1d8e_4e27_c47d_124f;
+/// Salt separating gap draws from cadence draws of the same burst.
+const GAP_SALT: u64 = 0x7a6d_76e9_9a1c_3b05;
+
+/// Deterministic pacing seed derived from the generator inputs of a plan.
+pub(crate) fn seed(inputs: &PressureWaveInputs) -> u64 {
+    [
+        u64::from(inputs.level_id().get()),
+        u64::from(inputs.wave().get()),
+        u64::from(inputs.difficulty().get()),
+    ]
+    .into_iter()
+    .fold(splitmix64(inputs.game_seed()), |state, value| {
+        splitmix64(state ^ value)
+    })
+}
+
+/// Re-times the spawns of every species with a definition into paced bursts.
+///
+/// Each species keeps its first spawn time, its spawn order and its spawn count, so pacing
+/// an already paced plan with the same seed leaves it unchanged. Species without a
+/// definition keep their planned times.
+pub(crate) fn pace(
+    plan: &PressureWavePlan,
+    definitions: &[SpeciesDefinition],
+    config: BurstSchedulingConfig,
+    seed: u64,
+) -> PressureWavePlan {
+    let mut times: Vec<u32> = plan.spawns().iter().map(|spawn| spawn.time_ms()).collect();
+    for (species, indices) in species_spawns(plan) {
+        let Some(definition) = definitions
+            .iter()
+            .find(|definition| definition.id() == species)
+        else {
+            continue;
+        };
+        let cadence = definition.cadence_range();
+        let gap = definition.gap_range();
+        let mut at = times[indices[0]];
+        for (burst, members) in bursts(&indices, config).enumerate() {
+            let burst = u64::try_from(burst).unwrap_or(u64::MAX);
+            let key = seed ^ u64::from(species.get()).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ burst;
+            let cadence_ms = draw(
+                key ^ CADENCE_SALT,
+                cadence.min_ms().get(),
+                cadence.max_ms().get(),
+            );
+            for (offset, &index) in members.iter().enumerate() {
+                if offset > 0 {
+                    at = at.saturating_add(cadence_ms);
+                }
+                times[index] = at;
+            }
+            at = at.saturating_add(draw(key ^ GAP_SALT, gap.min_ms().get(), gap.max_ms().get()));
+        }
+    }
+
+    let mut order: Vec<usize> = (0..times.len()).collect();
+    order.sort_by_key(|&index| (times[index], index));
+    let spawns = order
+        .into_iter()
+        .map(|index| {
+            let spawn = &plan.spawns()[index];
+            PressureSpawnRecord::new(
+                times[index],
+                spawn.hp(),
+                spawn.speed_mult(),
+                spawn.species_id(),
+            )
+        })
+        .collect();
+    PressureWavePlan::new(spawns, plan.prototypes().to_vec())
+}
+
+/// Start or end of a planned burst, relative to the wave start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BurstMark {
+    at_ms: u32,
+    ends: bool,
+    species: SpeciesId,
+    burst: u32,
+}
+
+impl BurstMark {
+    fn into_event(self) -> Event {
+        let Self { species, burst, .. } = self;
+        if self.ends {
+            Event::BurstEnded { species, burst }
+        } else {
+            Event::BurstStarted { species, burst }
+        }
+    }
+}
+
+/// Planned spawn times of a burst, relative to the wave start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BurstWindow {
+    pub(crate) species: SpeciesId,
+    pub(crate) burst: u32,
+    pub(crate) first_ms: u32,
+    pub(crate) last_ms: u32,
+}
+
+/// Burst starts and ends of an active wave, announced as the wave clock reaches them.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BurstTimeline {
+    marks: Vec<BurstMark>,
+    windows: Vec<BurstWindow>,
+    next: usize,
+}
+
+impl BurstTimeline {
+    /// Builds the timeline of `plan`, splitting every species into its configured bursts.
+    pub(crate) fn new(plan: &PressureWavePlan, config: BurstSchedulingConfig) -> Self {
+        let spawns = plan.spawns();
+        let mut marks = Vec::new();
+        let mut windows = Vec::new();
+        for (species, indices) in species_spawns(plan) {
+            for (burst, members) in bursts(&indices, config).enumerate() {
+                let burst = u32::try_from(burst).unwrap_or(u32::MAX);
+                let (Some(&first), Some(&last)) = (members.first(), members.last()) else {
+                    continue;
+                };
+                windows.push(BurstWindow {
+                    species,
+                    burst,
+                    first_ms: spawns[first].time_ms(),
+                    last_ms: spawns[last].time_ms(),
+                });
+                for (index, ends) in [(first, false), (last, true)] {
+                    marks.push(BurstMark {
+                        at_ms: spawns[index].time_ms(),
+                        ends,
+                        species,
+                        burst,
+                    });
+                }
+            }
+        }
+        marks.sort_by_key(|mark| (mark.at_ms, mark.ends, mark.species, mark.burst));
+        Self {
+            marks,
+            windows,
+            next: 0,
+        }
+    }
+
+    /// Bursts holding their spawn cells `elapsed_ms` into the wave: from `lead_ms` before
+    /// their first spawn until their last.
+    pub(crate) fn reserved(
+        &self,
+        elapsed_ms: u32,
+        lead_ms: u32,
+    ) -> impl Iterator<Item = BurstWindow> + '_ {
+        self.windows.iter().copied().filter(move |window| {
+            window.first_ms.saturating_sub(lead_ms) <= elapsed_ms && elapsed_ms <= window.last_ms
+        })
+    }
+
+    /// Announces every burst start or end due `elapsed_ms` into the wave.
+    pub(crate) fn advance(&mut self, elapsed_ms: u32, out_events: &mut Vec<Event>) {
+        while let Some(mark) = self.marks.get(self.next) {
+            if mark.at_ms > elapsed_ms {
+                break;
+            }
+            out_events.push(mark.into_event());
+            self.next += 1;
+        }
+    }
+}
+
+/// Plan indices of every species' spawns, in plan order, keyed by species.
+fn species_spawns(plan: &PressureWavePlan) -> BTreeMap<SpeciesId, Vec<usize>> {
+    let mut spawns: BTreeMap<SpeciesId, Vec<usize>> = BTreeMap::new();
+    for (index, spawn) in plan.spawns().iter().enumerate() {
+        spawns
+            .entry(SpeciesId::new(spawn.species_id()))
+            .or_default()
+            .push(index);
+    }
+    spawns
+}
+
+/// Splits a species' spawns into consecutive bursts whose sizes differ by at most one.
+fn bursts(indices: &[usize], config: BurstSchedulingConfig) -> impl Iterator<Item = &[usize]> + '_ {
+    let count = u32::try_from(indices.len()).unwrap_or(u32::MAX);
+    let bursts = usize::try_from(config.burst_count(count)).unwrap_or(usize::MAX);
+    let (base, larger) = match bursts {
+        0 => (0, 0),
+        bursts => (indices.len() / bursts, indices.len() % bursts),
+    };
+    let mut start = 0;
+    (0..bursts).map(move |burst| {
+        let len = base + usize::from(burst < larger);
+        let members = &indices[start..start + len];
+        start += len;
+        members
+    })
+}
+
+/// Uniform draw from the inclusive range `min..=max` keyed by `key`.
+fn draw(key: u64, min: u32, max: u32) -> u32 {
+    if max <= min {
+        return min;
+    }
+    let span = u64::from(max - min) + 1;
+    min + u32::try_from(splitmix64(key) % span).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use maze_defence_core::{
+        BugColor, BurstGapRange, CadenceRange, DirichletWeight, Health, PressureWeight,
+        SpawnPatchId, SpeciesPrototype,
+    };
+
+    use super::*;
+
+    fn non_zero(value: u32) -> NonZeroU32 {
+        NonZeroU32::new(value).expect("non-zero")
+    }
+
+    fn definition(id: u32, cadence: (u32, u32), gap: (u32, u32)) -> SpeciesDefinition {
+        SpeciesDefinition::new(
+            SpeciesId::new(id),
+            SpawnPatchId::new(0),
+            SpeciesPrototype::new(BugColor::from_rgb(1, 2, 3), Health::new(5), non_zero(400)),
+            PressureWeight::new(non_zero(1_000)),
+            DirichletWeight::new(non_zero(1)),
+            0,
+            non_zero(1_000),
+            CadenceRange::new(non_zero(cadence.0), non_zero(cadence.1)),
+            BurstGapRange::new(non_zero(gap.0), non_zero(gap.1)),
+        )
+    }
+
+    fn plan(species: &[(u32, u32)]) -> PressureWavePlan {
+        let mut spawns = Vec::new();
+        for &(id, count) in species {
+            for index in 0..count {
+                spawns.push(PressureSpawnRecord::new(100 * index + id, 5, 1.0, id));
+            }
+        }
+        spawns.sort_by_key(PressureSpawnRecord::time_ms);
+        PressureWavePlan::new(spawns, Vec::new())
+    }
+
+    #[test]
+    fn pacing_honours_species_ranges_and_is_idempotent() {
+        let config = BurstSchedulingConfig::new(non_zero(4), non_zero(3));
+        let definitions = [
+            definition(0, (200, 300), (5_000, 6_000)),
+            definition(1, (50, 50), (1_000, 1_000)),
+        ];
+        let source = plan(&[(0, 10), (1, 5), (2, 3)]);
+
+        let paced = pace(&source, &definitions, config, 99);
+        assert_eq!(paced.spawns().len(), source.spawns().len());
+        assert!(paced
+            .spawns()
+            .windows(2)
+            .all(|pair| pair[0].time_ms() <= pair[1].time_ms()));
+        assert_eq!(pace(&paced, &definitions, config, 99), paced);
+
+        let times = |id: u32| -> Vec<u32> {
+            paced
+                .spawns()
+                .iter()
+                .filter(|spawn| spawn.species_id() == id)
+                .map(PressureSpawnRecord::time_ms)
+                .collect()
+        };
+        // Ten spawns in three bursts of 4, 3 and 3.
+        let first = times(0);
+        assert_eq!(first[0], 0, "species keep their first spawn time");
+        for (range, bound) in [(1..4, 200..=300), (4..5, 5_000..=6_000)] {
+            for index in range {
+                assert!(bound.contains(&(first[index] - first[index - 1])));
+            }
+        }
+        assert_eq!(
+            times(1),
+            vec![1, 51, 101, 1_101, 1_151],
+            "bursts of 3 and 2"
+        );
+        assert_eq!(times(2), vec![2, 102, 202], "species without a definition");
+    }
+
+    #[test]
+    fn timeline_announces_bursts_matching_the_burst_count() {
+        let config = BurstSchedulingConfig::new(non_zero(2), non_zero(8));
+        let source = plan(&[(0, 3), (1, 1)]);
+        let mut timeline = BurstTimeline::new(&source, config);
+
+        let mut events = Vec::new();
+        timeline.advance(0, &mut events);
+        assert_eq!(
+            events,
+            vec![Event::BurstStarted {
+                species: SpeciesId::new(0),
+                burst: 0,
+            }]
+        );
+
+        events.clear();
+        timeline.advance(u32::MAX, &mut events);
+        let started = events
+            .iter()
+            .filter(|event| matches!(event, Event::BurstStarted { .. }))
+            .count();
+        let ended = events
+            .iter()
+            .filter(|event| matches!(event, Event::BurstEnded { .. }))
+            .count();
+        assert_eq!(started, 2, "species 0 bursts twice, species 1 once");
+        assert_eq!(ended, 3);
+        assert_eq!(
+            events.last(),
+            Some(&Event::BurstEnded {
+                species: SpeciesId::new(0),
+                burst: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn bursts_reserve_their_cells_from_the_lead_until_their_last_spawn() {
+        let config = BurstSchedulingConfig::new(non_zero(2), non_zero(8));
+        let source = plan(&[(0, 4)]);
+        let timeline = BurstTimeline::new(&source, config);
+        let reserved = |elapsed_ms| -> Vec<u32> {
+            timeline
+                .reserved(elapsed_ms, 150)
+                .map(|window| window.burst)
+                .collect()
+        };
+
+        assert_eq!(
+            reserved(0),
+            vec![0],
+            "the second burst is more than the lead away"
+        );
+        assert_eq!(reserved(100), vec![0, 1]);
+        assert_eq!(reserved(150), vec![1], "the first burst has spawned");
+        assert_eq!(reserved(301), Vec::<u32>::new());
+    }
+}
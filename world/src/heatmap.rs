@@ -0,0 +1,52 @@
+//! Historical bug traffic accumulated across waves for the danger heatmap.
+
+use maze_defence_core::{CellCoord, DangerHeatmap};
+
+/// Extra weight added to every cell along the route of a bug that reached the exit.
+pub(crate) const LEAK_ROUTE_WEIGHT: u32 = 4;
+
+/// Row-major per-cell traffic counters covering the full cell grid.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TrafficHeatmap {
+    columns: u32,
+    rows: u32,
+    counts: Vec<u32>,
+}
+
+impl TrafficHeatmap {
+    /// Creates an empty heatmap sized for the provided cell grid.
+    pub(crate) fn new(columns: u32, rows: u32) -> Self {
+        let len = usize::try_from(u64::from(columns) * u64::from(rows)).unwrap_or(0);
+        Self {
+            columns,
+            rows,
+            counts: vec![0; len],
+        }
+    }
+
+    /// Adds `weight` to the counter of the provided cell, ignoring cells outside the grid.
+    pub(crate) fn record(&mut self, cell: CellCoord, weight: u32) {
+        if cell.column() >= self.columns || cell.row() >= self.rows {
+            return;
+        }
+        let index = u64::from(cell.row()) * u64::from(self.columns) + u64::from(cell.column());
+        if let Some(count) = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.counts.get_mut(index))
+        {
+            *count = count.saturating_add(weight);
+        }
+    }
+
+    /// Adds the leak weight to every cell along a route that reached the exit.
+    pub(crate) fn record_leak_route(&mut self, route: &[CellCoord]) {
+        for cell in route {
+            self.record(*cell, LEAK_ROUTE_WEIGHT);
+        }
+    }
+
+    /// Normalises the accumulated counters into a presentation-ready heatmap.
+    pub(crate) fn snapshot(&self) -> DangerHeatmap {
+        DangerHeatmap::from_counts(self.columns, self.rows, &self.counts)
+    }
+}
@@ -0,0 +1,223 @@
+//! Plain-text dumps of world state for debugging and failing tests.
+//!
+//! Every function renders a deterministic, human-readable snapshot so the output can be
+//! printed from a test, diffed between runs or driven interactively from the CLI REPL.
+
+use std::fmt::Write;
+
+use maze_defence_core::CellCoord;
+
+use crate::{query, World};
+
+/// Symbols used by [`grid`], listed in the order they take precedence.
+pub const GRID_LEGEND: &str =
+    "B boss, b bug, T tower, X exit, S spawner, = bridge, # blocked, . free";
+
+/// Renders the full cell grid as ASCII art, one text line per cell row.
+///
+/// Cells show the first matching symbol of [`GRID_LEGEND`]; the bottom row is the
+/// virtual exit row below the perimeter wall.
+#[must_use]
+pub fn grid(world: &World) -> String {
+    let occupancy = query::occupancy_view(world);
+    let (columns, rows) = occupancy.dimensions();
+    let bosses: Vec<_> = query::bug_view(world)
+        .iter()
+        .filter(|bug| bug.boss_phase.is_some())
+        .map(|bug| bug.id)
+        .collect();
+    let targets = query::target_cells(world);
+    let spawners = query::bug_spawners(world);
+    let bridges = query::bridge_cells(world);
+
+    let mut output = String::new();
+    for row in 0..rows {
+        for column in 0..columns {
+            let cell = CellCoord::new(column, row);
+            let symbol = match occupancy.occupant(cell) {
+                Some(bug) if bosses.contains(&bug) => 'B',
+                Some(_) => 'b',
+                None if tower_covers(world, cell) => 'T',
+                None if targets.contains(&cell) => 'X',
+                None if spawners.contains(&cell) => 'S',
+                None if bridges.contains(&cell) => '=',
+                None if query::is_cell_blocked(world, cell) => '#',
+                None => '.',
+            };
+            output.push(symbol);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn tower_covers(world: &World, cell: CellCoord) -> bool {
+    query::tower_at(world, cell).is_some()
+}
+
+/// Renders one table row per living bug, ordered by identifier.
+#[must_use]
+pub fn bugs(world: &World) -> String {
+    let mut output = format!(
+        "{:>6} {:>11} {:>9} {:>11} {:>6} {:<10} {}\n",
+        "id", "cell", "health", "step", "bounty", "pathing", "boss"
+    );
+    for bug in query::bug_view(world).iter() {
+        let boss = bug
+            .boss_phase
+            .map_or_else(|| "-".to_owned(), |phase| format!("{phase:?}"));
+        let _ = writeln!(
+            output,
+            "{:>6} {:>11} {:>9} {:>11} {:>6} {:<10} {boss}",
+            bug.id.get(),
+            cell_label(bug.cell),
+            format!("{}/{}", bug.health.get(), bug.max_health.get()),
+            format!("{}/{}ms", bug.accum_ms, bug.step_ms),
+            bug.bounty.get(),
+            format!("{:?}", bug.pathing),
+        );
+    }
+    output
+}
+
+/// Renders one table row per tower with its footprint and reload state.
+#[must_use]
+pub fn towers(world: &World) -> String {
+    let cooldowns = query::tower_cooldowns(world);
+    let mut output = format!(
+        "{:>6} {:<9} {:>11} {:>7} {:<10} {}\n",
+        "id", "kind", "origin", "size", "rotation", "ready in"
+    );
+    for tower in query::towers(world).iter() {
+        let ready_in = cooldowns
+            .iter()
+            .find(|cooldown| cooldown.tower == tower.id)
+            .map_or(0, |cooldown| cooldown.ready_in.as_millis());
+        let _ = writeln!(
+            output,
+            "{:>6} {:<9} {:>11} {:>7} {:<10} {ready_in}ms",
+            tower.id.get(),
+            format!("{:?}", tower.kind),
+            cell_label(tower.region.origin()),
+            format!(
+                "{}x{}",
+                tower.region.size().width(),
+                tower.region.size().height()
+            ),
+            format!("{:?}", tower.rotation),
+        );
+    }
+    output
+}
+
+/// Renders the clock, economy and wave progress as `key: value` lines.
+#[must_use]
+pub fn wave_status(world: &World) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "mode: {:?}", query::play_mode(world));
+    let _ = writeln!(output, "tick: {}", world.clock.ticks());
+    let _ = writeln!(output, "elapsed: {}ms", query::elapsed(world).as_millis());
+    let _ = writeln!(output, "gold: {}", query::gold(world).get());
+    let _ = writeln!(output, "difficulty: {}", query::difficulty_ladder(world));
+    match world.active_wave {
+        Some(wave) => {
+            let _ = writeln!(
+                output,
+                "wave: #{} {:?} at {}, running for {}ms",
+                wave.id.get(),
+                wave.difficulty,
+                wave.effective_difficulty,
+                query::wave_elapsed(world).unwrap_or_default().as_millis()
+            );
+        }
+        None => {
+            let _ = writeln!(output, "wave: none");
+        }
+    }
+    if let Some(countdown) = query::next_wave_countdown(world) {
+        let _ = writeln!(output, "next wave in: {}ms", countdown.as_millis());
+    }
+    let _ = writeln!(
+        output,
+        "bugs alive: {}",
+        query::bug_view(world).iter().count()
+    );
+    let _ = writeln!(output, "projectiles: {}", query::projectiles(world).count());
+    output
+}
+
+/// Renders every section, each under a `== name ==` heading.
+#[must_use]
+pub fn dump(world: &World) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "== status ==\n{}", wave_status(world));
+    let _ = writeln!(output, "== grid ({GRID_LEGEND}) ==\n{}", grid(world));
+    let _ = writeln!(output, "== bugs ==\n{}", bugs(world));
+    let _ = writeln!(output, "== towers ==\n{}", towers(world));
+    output
+}
+
+fn cell_label(cell: CellCoord) -> String {
+    format!("({},{})", cell.column(), cell.row())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply;
+    use maze_defence_core::{Command, PlayMode, TileCoord, TowerKind};
+
+    #[test]
+    fn dumps_render_the_board_bugs_and_towers() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(4),
+                rows: TileCoord::new(3),
+                tile_length: 1.0,
+                cells_per_tile: 2,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Builder,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(1, 1),
+            },
+            &mut events,
+        );
+
+        let grid = grid(&world);
+        let lines: Vec<&str> = grid.lines().collect();
+        assert_eq!(lines.len(), 9, "border rows plus the exit row");
+        assert!(lines.iter().all(|line| line.len() == 10));
+        assert_eq!(lines[0], "SSSSSSSSSS");
+        assert_eq!(lines[1], "STTTT....S");
+        assert_eq!(lines[6], "S........S");
+        assert_eq!(lines[7].matches('#').count(), 8, "wall with the exit gap");
+        assert_eq!(lines[8].matches('X').count(), 2);
+
+        let towers = towers(&world);
+        assert_eq!(towers.lines().count(), 2);
+        assert!(towers.contains("Basic"));
+        assert_eq!(bugs(&world).lines().count(), 1, "header only");
+
+        let status = wave_status(&world);
+        assert!(status.contains("mode: Builder"));
+        assert!(status.contains("wave: none"));
+        let dump = dump(&world);
+        for heading in ["== status", "== grid", "== bugs", "== towers"] {
+            assert!(dump.contains(heading), "missing {heading}");
+        }
+    }
+}
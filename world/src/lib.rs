@@ -10,47 +10,50 @@
 //! Authoritative world state management for Maze Defence.
 
 mod analytics;
+mod between_waves;
+mod bursts;
+mod heatmap;
+pub mod inspect;
 mod navigation;
+mod raycast;
+mod shield;
 
+use bursts::BurstTimeline;
+use heatmap::TrafficHeatmap;
+use shield::WaveShield;
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
     convert::TryFrom,
     time::Duration,
 };
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
-use std::collections::VecDeque;
-
-#[cfg(any(test, feature = "tower_scaffolding"))]
 mod towers;
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 use towers::{footprint_for, TowerRegistry, TowerState};
 
 use maze_defence_core::{
-    BugColor, BugId, BurstGapRange, BurstSchedulingConfig, CadenceRange, CellCoord, CellPointHalf,
-    CellRect, CellRectSize, Command, Damage, DifficultyLevel, Direction, DirichletWeight, Event,
-    Gold, Health, LevelId, PendingWaveDifficulty, PlayMode, Pressure, PressureConfig,
-    PressureCurve, PressureWaveInputs, PressureWavePlan, PressureWeight, ProjectileId,
-    ReservationClaim, RoundOutcome, SpawnPatchDescriptor, SpawnPatchId, SpeciesDefinition,
-    SpeciesId, SpeciesPrototype, SpeciesTableVersion, Target, TargetCell, TileCoord, TileGrid,
-    WaveDifficulty, WaveId, PRESSURE_FIXED_POINT_SCALE, WELCOME_BANNER,
+    AccuracyModel, BetweenWaveEvent, BossPhase, BugArchetype, BugColor, BugId, BugRank,
+    BurstGapRange, BurstSchedulingConfig, CadenceRange, CellCoord, CellPointHalf, CellRect,
+    CellRectSize, ComboRule, Command, Damage, DamageType, DifficultyLadder, Direction,
+    DirichletWeight, DodgeChance, Event, ExitBehavior, FormationId, FormationShape, GameRules,
+    Gold, GridEdge, Health, LevelId, MissCause, PathingPersonality, PendingWaveDifficulty,
+    PlacementError, PlayMode, PreparationReward, Pressure, PressureConfig, PressureCurve,
+    PressurePlanError, PressureWaveInputs, PressureWavePlan, PressureWeight, ProjectileId,
+    ProjectileRejection, RemovalError, ReservationClaim, RoundOutcome, SandboxRules, SimClock,
+    SpawnPatchDescriptor, SpawnPatchId, SpawnReservation, SpawnerConfig, SpawnerConfigError,
+    SpawnerSet, SpeciesCombat, SpeciesDefinition, SpeciesId, SpeciesPrototype, SpeciesTableVersion,
+    StatusEffect, Target, TargetCell, TargetingPolicy, TileCoord, TileGrid, TileGridConfigError,
+    TowerContent, TowerId, TowerKind, TowerRotation, TowerStats, UpgradeError, WaveContract,
+    WaveDifficulty, WaveHistoryInputs, WaveId, WaveLaunch, WaveReport, FLANKER_COVERAGE_PENALTY,
+    MAX_CELLS_PER_TILE, MAX_GRID_CELLS, PRESSURE_FIXED_POINT_SCALE, WELCOME_BANNER,
 };
 
 use maze_defence_pressure_v2::PressureV2;
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
-use maze_defence_core::ProjectileRejection;
-
 use maze_defence_core::structures::Wall as CellWall;
 
 use navigation::NavigationField;
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
-use maze_defence_core::{PlacementError, RemovalError, TowerKind};
-
-use maze_defence_core::TowerId;
-
 use std::num::NonZeroU32;
 
 const DEFAULT_GRID_COLUMNS: TileCoord = TileCoord::new(10);
@@ -67,10 +70,30 @@ const EXIT_CELL_LAYERS: u32 = 1;
 const INITIAL_GOLD: Gold = Gold::new(100);
 const HARD_WIN_DIFFICULTY_PROMOTION: u32 = 1;
 const ROUND_LOSS_DIFFICULTY_PENALTY: u32 = 1;
-#[cfg(any(test, feature = "tower_scaffolding"))]
 const ROUND_LOSS_TOWER_REMOVAL_PERCENT: u32 = 50;
 const DEFAULT_WAVE_GLOBAL_SEED: u64 = 0;
+/// Adds a boss summons when it enters [`BossPhase::Summoning`].
+const BOSS_SUMMONED_ADDS: usize = 2;
+/// Fraction of the boss's maximum health given to each summoned add.
+const BOSS_ADD_HEALTH_DIVISOR: u32 = 10;
+/// Share, in percent, of each hit's damage an armored bug takes; never less than one point.
+const ARMORED_DAMAGE_PERCENT: u32 = 60;
+/// Time a regenerating bug needs to heal one point of health.
+const REGENERATION_INTERVAL_MS: u32 = 500;
+/// Bugs a splitter breaks into when it dies.
+const SPLITTER_CHILDREN: usize = 2;
+/// Fraction of the splitter's maximum health given to each child.
+const SPLITTER_CHILD_HEALTH_DIVISOR: u32 = 3;
+/// Invulnerability granted to a boss whenever it changes phase.
+const BOSS_PHASE_INVULNERABILITY_MS: u32 = 1_500;
+/// Wave time before a burst's first spawn from which its spawner cells are reserved.
+const SPAWN_RESERVATION_LEAD_MS: u32 = 1_500;
+/// Largest share of its health, in percent, a bug keeps when it loops around.
+const MAX_LOOP_HEALTH_PERCENT: u32 = 99;
 const DEFAULT_LEVEL_ID: LevelId = LevelId::new(0);
+const EARLY_START_GOLD_PER_SECOND: u64 = 1;
+/// Salt separating dodge draws from the other draws keyed by the wave seed.
+const DODGE_SALT: u64 = 0x3c6e_f372_fe94_f82b;
 
 /// Represents the authoritative Maze Defence world state.
 #[derive(Debug)]
@@ -83,16 +106,26 @@ pub struct World {
     bugs: Vec<Bug>,
     bug_positions: HashMap<BugId, usize>,
     bug_spawners: BugSpawnerRegistry,
+    spawner_config: SpawnerConfig,
+    spawner_activations_applied: usize,
+    nest_health: Option<Health>,
+    nests: Vec<Nest>,
+    destroyed_nests: Vec<SpawnerSet>,
     next_bug_id: u32,
+    next_formation_id: u32,
     projectiles: BTreeMap<ProjectileId, ProjectileState>,
-    #[cfg_attr(not(any(test, feature = "tower_scaffolding")), allow(dead_code))]
     next_projectile_id: ProjectileId,
     occupancy: OccupancyGrid,
+    traffic_heatmap: TrafficHeatmap,
     walls: MazeWalls,
+    bridges: BitGrid,
+    void_cells: BitGrid,
     navigation_field: NavigationField,
+    flanker_navigation_field: NavigationField,
     navigation_dirty: bool,
+    navigation_generation: u64,
     gold: Gold,
-    difficulty_level: DifficultyLevel,
+    difficulty_level: DifficultyLadder,
     pending_wave_difficulty: PendingWaveDifficulty,
     species_table_version: SpeciesTableVersion,
     species_definitions: Vec<SpeciesDefinition>,
@@ -103,13 +136,36 @@ pub struct World {
     wave_seed_global: u64,
     level_id: LevelId,
     active_wave: Option<ActiveWaveContext>,
+    burst_timeline: BurstTimeline,
+    wave_shield: Option<WaveShield>,
+    wave_tally: WaveTally,
+    wave_history: Option<WaveHistoryInputs>,
     next_wave_id: WaveId,
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     towers: TowerRegistry,
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     tower_occupancy: BitGrid,
     reservations: ReservationFrame,
-    tick_index: u64,
+    priority_target: Option<BugId>,
+    clock: SimClock,
+    auto_wave_delay: Option<Duration>,
+    next_wave_countdown: Option<Duration>,
+    builder_time_limit: Option<Duration>,
+    builder_time_left: Option<Duration>,
+    haste_remaining: Duration,
+    deferred: DeferredIntegration,
+    merchant_offer: Option<u32>,
+    merchant_discount: Option<u32>,
+    gold_rush_pending: bool,
+    accepted_contracts: Vec<WaveContract>,
+    sandbox: SandboxRules,
+    tower_heat: bool,
+    line_of_sight: bool,
+    accuracy: Option<AccuracyModel>,
+    exit_behavior: ExitBehavior,
+    game_rules: GameRules,
+    base_health: u32,
+    tower_content: TowerContent,
+    sandbox_session: bool,
+    placement_rotation: TowerRotation,
     step_quantum: Duration,
     play_mode: PlayMode,
 }
@@ -118,8 +174,9 @@ pub struct World {
 #[derive(Clone, Copy, Debug)]
 struct ActiveWaveContext {
     id: WaveId,
+    started_at: Duration,
     difficulty: WaveDifficulty,
-    effective_difficulty: DifficultyLevel,
+    effective_difficulty: DifficultyLadder,
     reward_multiplier: u32,
     pressure_scalar: u32,
 }
@@ -130,6 +187,153 @@ impl ActiveWaveContext {
     }
 }
 
+/// Running totals collected while a wave is active, published as a `WaveReport`.
+#[derive(Clone, Debug, Default)]
+struct WaveTally {
+    damage_by_tower: BTreeMap<TowerId, u32>,
+    leaks: u32,
+    gold_earned: u64,
+    species_colors: Vec<(BugColor, u32)>,
+    live_bugs: BTreeMap<BugId, TrackedBug>,
+    species: BTreeMap<u32, SpeciesLedger>,
+}
+
+/// Bug of the active wave, attributed to a species by its colour.
+#[derive(Clone, Copy, Debug)]
+struct TrackedBug {
+    species: u32,
+    spawned_at: Duration,
+    cells: u32,
+    damage: u64,
+}
+
+/// Attributes of a bug about to enter the maze at `cell`.
+#[derive(Clone, Copy, Debug)]
+struct BugSpawn {
+    cell: CellCoord,
+    color: BugColor,
+    health: Health,
+    step_ms: u32,
+    pathing: PathingPersonality,
+    bounty: Gold,
+    rank: BugRank,
+    dodge: DodgeChance,
+    archetype: BugArchetype,
+}
+
+/// Totals over the bugs of one species that have left the maze.
+#[derive(Clone, Copy, Debug, Default)]
+struct SpeciesLedger {
+    bugs: u32,
+    killed: u32,
+    lifetime_ms: u64,
+    cells: u64,
+    damage: u64,
+}
+
+impl WaveTally {
+    /// Starts the tally of a wave whose species spawn bugs of the given colours.
+    fn for_species(species_colors: Vec<(BugColor, u32)>) -> Self {
+        Self {
+            species_colors,
+            ..Self::default()
+        }
+    }
+
+    fn track_spawn(&mut self, bug: BugId, color: BugColor, now: Duration) {
+        let Some(&(_, species)) = self
+            .species_colors
+            .iter()
+            .find(|(candidate, _)| *candidate == color)
+        else {
+            return;
+        };
+        let _ = self.live_bugs.insert(
+            bug,
+            TrackedBug {
+                species,
+                spawned_at: now,
+                cells: 0,
+                damage: 0,
+            },
+        );
+    }
+
+    fn track_step(&mut self, bug: BugId) {
+        if let Some(tracked) = self.live_bugs.get_mut(&bug) {
+            tracked.cells = tracked.cells.saturating_add(1);
+        }
+    }
+
+    fn track_damage(&mut self, bug: BugId, dealt: u32) {
+        if let Some(tracked) = self.live_bugs.get_mut(&bug) {
+            tracked.damage = tracked.damage.saturating_add(u64::from(dealt));
+        }
+    }
+
+    /// Closes the record of `bug`, which left the maze at `now`.
+    fn track_removal(&mut self, bug: BugId, now: Duration, killed: bool) {
+        let Some(tracked) = self.live_bugs.remove(&bug) else {
+            return;
+        };
+        let lifetime = now.saturating_sub(tracked.spawned_at);
+        let ledger = self.species.entry(tracked.species).or_default();
+        ledger.bugs = ledger.bugs.saturating_add(1);
+        ledger.killed = ledger.killed.saturating_add(u32::from(killed));
+        ledger.lifetime_ms = ledger
+            .lifetime_ms
+            .saturating_add(u64::try_from(lifetime.as_millis()).unwrap_or(u64::MAX));
+        ledger.cells = ledger.cells.saturating_add(u64::from(tracked.cells));
+        ledger.damage = ledger.damage.saturating_add(tracked.damage);
+    }
+
+    /// Closes the records of every bug still in the maze.
+    fn track_clear(&mut self, now: Duration) {
+        let bugs: Vec<BugId> = self.live_bugs.keys().copied().collect();
+        for bug in bugs {
+            self.track_removal(bug, now, false);
+        }
+    }
+
+    fn into_report(
+        mut self,
+        context: &ActiveWaveContext,
+        outcome: RoundOutcome,
+        now: Duration,
+    ) -> WaveReport {
+        self.track_clear(now);
+        let species_combat = self
+            .species
+            .iter()
+            .map(|(&species, ledger)| {
+                let bugs = u64::from(ledger.bugs.max(1));
+                SpeciesCombat {
+                    species,
+                    bugs: ledger.bugs,
+                    killed: ledger.killed,
+                    average_lifetime_ms: ledger.lifetime_ms / bugs,
+                    average_cells_travelled: u32::try_from(ledger.cells / bugs).unwrap_or(u32::MAX),
+                    damage_absorbed: ledger.damage,
+                    damage_per_second: ledger
+                        .damage
+                        .saturating_mul(1_000)
+                        .checked_div(ledger.lifetime_ms)
+                        .unwrap_or(0),
+                }
+            })
+            .collect();
+        WaveReport::new(
+            context.id,
+            outcome,
+            now.saturating_sub(context.started_at),
+            self.leaks,
+            Gold::new(self.gold_earned),
+            self.damage_by_tower.into_iter().collect(),
+        )
+        .with_species_combat(species_combat)
+    }
+}
+
 fn default_species_table() -> (SpeciesTableVersion, Vec<SpeciesDefinition>) {
     let version = SpeciesTableVersion::new(1);
     let mut definitions = vec![SpeciesDefinition::new(
@@ -198,22 +402,32 @@ impl World {
         let (species_table_version, species_definitions) = default_species_table();
         let spawn_patches = default_spawn_patches();
         let pressure_config = default_pressure_config();
-        #[cfg(any(test, feature = "tower_scaffolding"))]
         let tower_occupancy = BitGrid::new(total_columns, total_rows);
         let mut world = Self {
             banner: WELCOME_BANNER,
             bugs: Vec::new(),
             bug_positions: HashMap::new(),
             bug_spawners: BugSpawnerRegistry::new(),
+            spawner_config: SpawnerConfig::default(),
+            spawner_activations_applied: 0,
+            nest_health: None,
+            nests: Vec::new(),
+            destroyed_nests: Vec::new(),
             next_bug_id: 0,
+            next_formation_id: 0,
             projectiles: BTreeMap::new(),
             next_projectile_id: ProjectileId::new(0),
             occupancy,
+            traffic_heatmap: TrafficHeatmap::new(total_columns, total_rows),
             walls,
+            bridges: BitGrid::new(total_columns, total_rows),
+            void_cells: BitGrid::new(total_columns, total_rows),
             navigation_field: NavigationField::default(),
+            flanker_navigation_field: NavigationField::default(),
             navigation_dirty: true,
+            navigation_generation: 0,
             gold: INITIAL_GOLD,
-            difficulty_level: DifficultyLevel::new(0),
+            difficulty_level: DifficultyLadder::default(),
             pending_wave_difficulty: PendingWaveDifficulty::Unset,
             species_table_version,
             species_definitions,
@@ -224,17 +438,40 @@ impl World {
             wave_seed_global: DEFAULT_WAVE_GLOBAL_SEED,
             level_id: DEFAULT_LEVEL_ID,
             active_wave: None,
+            burst_timeline: BurstTimeline::default(),
+            wave_shield: None,
+            wave_tally: WaveTally::default(),
+            wave_history: None,
             next_wave_id: WaveId::new(0),
-            #[cfg(any(test, feature = "tower_scaffolding"))]
             towers: TowerRegistry::new(),
-            #[cfg(any(test, feature = "tower_scaffolding"))]
             tower_occupancy,
             reservations: ReservationFrame::new(),
+            priority_target: None,
             target,
             targets,
             tile_grid,
             cells_per_tile,
-            tick_index: 0,
+            clock: SimClock::new(),
+            auto_wave_delay: None,
+            next_wave_countdown: None,
+            builder_time_limit: None,
+            builder_time_left: None,
+            haste_remaining: Duration::ZERO,
+            deferred: DeferredIntegration::default(),
+            merchant_offer: None,
+            merchant_discount: None,
+            gold_rush_pending: false,
+            accepted_contracts: Vec::new(),
+            sandbox: SandboxRules::default(),
+            tower_heat: false,
+            line_of_sight: false,
+            accuracy: None,
+            exit_behavior: ExitBehavior::default(),
+            game_rules: GameRules::default(),
+            base_health: GameRules::default().base_health,
+            tower_content: TowerContent::default(),
+            sandbox_session: false,
+            placement_rotation: TowerRotation::Deg0,
             step_quantum: DEFAULT_STEP_QUANTUM,
             play_mode: PlayMode::Builder,
         };
@@ -245,17 +482,167 @@ impl World {
     }
 
     fn clear_bugs(&mut self) {
+        self.wave_tally.track_clear(self.clock.elapsed());
         self.bugs.clear();
         self.bug_positions.clear();
         self.occupancy.clear();
         self.reservations.clear();
+        self.priority_target = None;
         self.next_bug_id = 0;
+        self.next_formation_id = 0;
+    }
+
+    /// Advances the clock by `dt`.
+    ///
+    /// Tower cooldowns, heat and projectiles integrate the time owed to them on every plain
+    /// tick. A batched tick only records the time until one of them would complete, which
+    /// lands on the same tick it would have without batching. Bug timers always advance.
+    fn tick(&mut self, dt: Duration, batched: bool, out_events: &mut Vec<Event>) {
+        if self.play_mode == PlayMode::Builder {
+            self.advance_builder_timer(dt, out_events);
+            return;
+        }
+
+        self.rebuild_navigation_field_if_dirty();
+        self.clock.advance(dt);
+        out_events.push(Event::TimeAdvanced { dt });
+        self.advance_wave_countdown(dt, out_events);
+        if let Some(elapsed) = query::wave_elapsed(self) {
+            let elapsed_ms = u32::try_from(elapsed.as_millis()).unwrap_or(u32::MAX);
+            self.burst_timeline.advance(elapsed_ms, out_events);
+        }
+
+        let recovery = dt.saturating_add(dt.min(self.haste_remaining));
+        self.haste_remaining = self.haste_remaining.saturating_sub(dt);
+        let dt_millis = u32::try_from(dt.as_millis()).unwrap_or(u32::MAX);
+        if let Some(shield) = self.wave_shield.as_mut() {
+            shield.regenerate(dt_millis);
+        }
+        self.deferred.recovery = self.deferred.recovery.saturating_add(recovery);
+        self.deferred.millis = self.deferred.millis.saturating_add(u128::from(dt_millis));
+
+        if !batched || self.deferred_work_due() {
+            let completed = self.settle_deferred_integration();
+            for (projectile_id, target, damage) in completed {
+                self.resolve_projectile_completion(projectile_id, target, damage, out_events);
+            }
+        }
+
+        for bug in self.iter_bugs_mut() {
+            let advanced = bug.accum_ms.saturating_add(bug.step_progress(dt_millis));
+            bug.accum_ms = advanced.min(bug.step_ms);
+            bug.invulnerable_ms = bug.invulnerable_ms.saturating_sub(dt_millis);
+            if bug.archetype == BugArchetype::Regenerating {
+                bug.regenerate(dt_millis);
+            }
+        }
+    }
+
+    /// Whether the deferred time completes a tower cooldown, a heat stall or a projectile.
+    fn deferred_work_due(&mut self) -> bool {
+        let deadline = match self.deferred.deadline {
+            Some(deadline) => deadline,
+            None => {
+                let deadline = self.integration_deadline();
+                self.deferred.deadline = Some(deadline);
+                deadline
+            }
+        };
+        self.deferred.recovery >= deadline.recovery || self.deferred.millis >= deadline.millis
+    }
+
+    /// Earliest recovery and elapsed time at which deferred integration must settle.
+    fn integration_deadline(&self) -> IntegrationDeadline {
+        let mut deadline = IntegrationDeadline {
+            recovery: Duration::MAX,
+            millis: u128::MAX,
+        };
+        for tower in self.towers.iter() {
+            if !tower.cooldown_remaining.is_zero() {
+                deadline.recovery = deadline.recovery.min(tower.cooldown_remaining);
+            }
+            if self.tower_heat && tower.overheated {
+                deadline.millis = deadline.millis.min(tower.stall_remaining().as_millis());
+            }
+        }
+        for projectile in self.projectiles.values() {
+            let remaining = projectile
+                .travel_time_ms
+                .saturating_sub(projectile.elapsed_ms);
+            deadline.millis = deadline.millis.min(remaining);
+        }
+        deadline
+    }
+
+    /// Applies the deferred time to tower cooldowns, heat and projectiles.
+    ///
+    /// Returns the projectiles that reached their target, in identifier order.
+    fn settle_deferred_integration(&mut self) -> Vec<(ProjectileId, BugId, Damage)> {
+        let deferred = std::mem::take(&mut self.deferred);
+        let heat_dt = Duration::from_millis(u64::try_from(deferred.millis).unwrap_or(u64::MAX));
+        let tower_ids: Vec<_> = self.towers.iter().map(|state| state.id).collect();
+        for tower_id in tower_ids {
+            if let Some(state) = self.towers.get_mut(tower_id) {
+                state.cooldown_remaining =
+                    state.cooldown_remaining.saturating_sub(deferred.recovery);
+                if self.tower_heat {
+                    state.dissipate_heat(heat_dt);
+                }
+            }
+        }
+
+        let mut completed = Vec::new();
+        for projectile in self.projectiles.values_mut() {
+            if projectile.travel_time_ms == 0 {
+                projectile.travelled_half = projectile.distance_half;
+                completed.push((projectile.id, projectile.target, projectile.damage));
+                continue;
+            }
+
+            let new_elapsed = projectile
+                .elapsed_ms
+                .saturating_add(deferred.millis)
+                .min(projectile.travel_time_ms);
+            projectile.elapsed_ms = new_elapsed;
+            projectile.travelled_half =
+                projectile.distance_half.saturating_mul(new_elapsed) / projectile.travel_time_ms;
+
+            if projectile.elapsed_ms >= projectile.travel_time_ms {
+                completed.push((projectile.id, projectile.target, projectile.damage));
+            }
+        }
+        completed
+    }
+
+    fn mark_priority_target(&mut self, bug: BugId, out_events: &mut Vec<Event>) {
+        if self.play_mode != PlayMode::Attack || self.bug_index(bug).is_none() {
+            return;
+        }
+
+        self.priority_target = if self.priority_target == Some(bug) {
+            None
+        } else {
+            Some(bug)
+        };
+        out_events.push(Event::PriorityTargetChanged {
+            bug: self.priority_target,
+        });
+    }
+
+    fn release_priority_target(&mut self, bug: BugId, out_events: &mut Vec<Event>) {
+        if self.priority_target == Some(bug) {
+            self.priority_target = None;
+            out_events.push(Event::PriorityTargetChanged { bug: None });
+        }
     }
 
     fn transition_to_play_mode(&mut self, mode: PlayMode, out_events: &mut Vec<Event>) -> bool {
         if self.play_mode == mode {
             return false;
         }
+        if mode == PlayMode::Builder && self.builder_time_left == Some(Duration::ZERO) {
+            return false;
+        }
 
         self.play_mode = mode;
 
@@ -269,9 +656,42 @@ impl World {
         }
 
         out_events.push(Event::PlayModeChanged { mode });
+        if mode == PlayMode::Builder {
+            self.announce_builder_time(out_events);
+        }
         true
     }
 
+    fn announce_builder_time(&self, out_events: &mut Vec<Event>) {
+        if let Some(remaining) = self.builder_time_left {
+            out_events.push(Event::BuilderCountdown {
+                remaining_ms: duration_to_millis(remaining),
+            });
+        }
+    }
+
+    fn restore_builder_time(&mut self, out_events: &mut Vec<Event>) {
+        self.builder_time_left = self.builder_time_limit;
+        if self.play_mode == PlayMode::Builder {
+            self.announce_builder_time(out_events);
+        }
+    }
+
+    fn advance_builder_timer(&mut self, dt: Duration, out_events: &mut Vec<Event>) {
+        let Some(remaining) = self.builder_time_left else {
+            return;
+        };
+
+        let remaining = remaining.saturating_sub(dt);
+        self.builder_time_left = Some(remaining);
+        out_events.push(Event::BuilderCountdown {
+            remaining_ms: duration_to_millis(remaining),
+        });
+        if remaining.is_zero() {
+            let _ = self.transition_to_play_mode(PlayMode::Attack, out_events);
+        }
+    }
+
     fn update_gold(&mut self, amount: Gold, out_events: &mut Vec<Event>) {
         if self.gold == amount {
             return;
@@ -281,13 +701,74 @@ impl World {
         out_events.push(Event::GoldChanged { amount });
     }
 
-    fn update_difficulty_level(&mut self, level: DifficultyLevel, out_events: &mut Vec<Event>) {
-        if self.difficulty_level == level {
+    fn configure_game_rules(&mut self, rules: GameRules, out_events: &mut Vec<Event>) {
+        let rules = GameRules {
+            base_health: rules.base_health.max(1),
+            ..rules
+        };
+        self.game_rules = rules;
+        self.base_health = rules.base_health;
+        self.update_gold(self.gold.saturating_add(rules.bonus_gold), out_events);
+        out_events.push(Event::GameRulesConfigured { rules });
+    }
+
+    fn start_wave_countdown(&mut self, out_events: &mut Vec<Event>) {
+        let Some(delay) = self.auto_wave_delay else {
+            return;
+        };
+
+        self.next_wave_countdown = Some(delay);
+        out_events.push(Event::NextWaveCountdown {
+            remaining_ms: duration_to_millis(delay),
+        });
+    }
+
+    fn advance_wave_countdown(&mut self, dt: Duration, out_events: &mut Vec<Event>) {
+        let Some(remaining) = self.next_wave_countdown else {
+            return;
+        };
+
+        let remaining = remaining.saturating_sub(dt);
+        self.next_wave_countdown = if remaining.is_zero() {
+            None
+        } else {
+            Some(remaining)
+        };
+        out_events.push(Event::NextWaveCountdown {
+            remaining_ms: duration_to_millis(remaining),
+        });
+    }
+
+    fn skip_wave_countdown(&mut self, reward: PreparationReward, out_events: &mut Vec<Event>) {
+        let Some(remaining) = self.next_wave_countdown.take() else {
+            return;
+        };
+
+        match reward {
+            PreparationReward::Gold => {
+                let bonus = early_start_bonus(remaining);
+                self.update_gold(self.gold.saturating_add(bonus), out_events);
+            }
+            PreparationReward::Haste => {
+                self.haste_remaining = self.haste_remaining.saturating_add(remaining);
+            }
+        }
+        out_events.push(Event::PreparationBanked {
+            reward,
+            banked_ms: duration_to_millis(remaining),
+        });
+        out_events.push(Event::NextWaveCountdown { remaining_ms: 0 });
+    }
+
+    fn update_difficulty_level(&mut self, ladder: DifficultyLadder, out_events: &mut Vec<Event>) {
+        if self.difficulty_level == ladder {
             return;
         }
 
-        self.difficulty_level = level;
-        out_events.push(Event::DifficultyLevelChanged { level });
+        self.difficulty_level = ladder;
+        out_events.push(Event::DifficultyLevelChanged {
+            level: ladder.level(),
+        });
     }
 
     fn assign_pending_wave_difficulty(
@@ -310,11 +791,77 @@ impl World {
         plan: PressureWavePlan,
         out_events: &mut Vec<Event>,
     ) {
+        if let Err(reason) = self.validate_pressure_plan(&plan) {
+            out_events.push(Event::PressureWaveRejected {
+                inputs: Box::new(inputs),
+                reason,
+            });
+            return;
+        }
+
+        // Plans generated outside the world, e.g. by a hot-reloaded generator, are paced
+        // like the world's own; paced plans, such as replays, keep their spawn times.
+        let plan = if plan.is_paced() {
+            plan
+        } else {
+            bursts::pace(
+                &plan,
+                &self.species_definitions,
+                self.pressure_config.burst_scheduling(),
+                bursts::seed(&inputs),
+            )
+            .with_paced()
+        };
         self.apply_wave_prototypes(&plan);
         let cached_inputs = inputs.clone();
         let cached_plan = plan.clone();
         let _ = self.pressure_wave_cache.insert(cached_inputs, cached_plan);
-        out_events.push(Event::PressureWaveReady { inputs, plan });
+        out_events.push(Event::PressureWaveReady {
+            inputs: Box::new(inputs),
+            plan: Box::new(plan),
+        });
+    }
+
+    /// Checks a plan before caching so hostile or malformed plans, e.g. from mods or the
+    /// network, cannot corrupt a wave.
+    ///
+    /// Spawns must be sorted by time, name a species from the species table or the plan's
+    /// prototypes and carry hit points and a finite positive speed; prototypes must carry
+    /// hit points.
+    fn validate_pressure_plan(&self, plan: &PressureWavePlan) -> Result<(), PressurePlanError> {
+        let index_of = |index: usize| u32::try_from(index).unwrap_or(u32::MAX);
+        let mut previous_time = 0;
+        for (index, spawn) in plan.spawns().iter().enumerate() {
+            let index = index_of(index);
+            if spawn.time_ms() < previous_time {
+                return Err(PressurePlanError::UnsortedSpawn(index));
+            }
+            previous_time = spawn.time_ms();
+            let described = usize::try_from(spawn.species_id())
+                .is_ok_and(|species| species < plan.prototypes().len());
+            if !described
+                && !self
+                    .species_definitions
+                    .iter()
+                    .any(|definition| definition.id().get() == spawn.species_id())
+            {
+                return Err(PressurePlanError::UnknownSpecies(index));
+            }
+            if spawn.hp() == 0 {
+                return Err(PressurePlanError::ZeroHealthSpawn(index));
+            }
+            if !(spawn.speed_mult().is_finite() && spawn.speed_mult() > 0.0) {
+                return Err(PressurePlanError::InvalidSpeed(index));
+            }
+        }
+        if let Some(index) = plan
+            .prototypes()
+            .iter()
+            .position(|prototype| prototype.health().is_zero())
+        {
+            return Err(PressurePlanError::ZeroHealthPrototype(index_of(index)));
+        }
+        Ok(())
     }
 
     fn apply_wave_prototypes(&mut self, plan: &PressureWavePlan) {
@@ -364,32 +911,98 @@ impl World {
             return;
         }
 
-        let context = self.prepare_wave_context(wave, difficulty);
+        let mut context = self.prepare_wave_context(wave, difficulty);
+        let pressure_share = self.pressure_share();
+        let history = self.wave_history;
         let inputs = PressureWaveInputs::new(
             self.wave_seed_global,
             self.level_id,
             wave,
-            context.effective_difficulty,
-        );
+            context.effective_difficulty.level(),
+        )
+        .with_pressure_share(pressure_share)
+        .with_history(history);
 
         let Some(plan) = self.pressure_wave_cache.get(&inputs) else {
             return;
         };
 
         let (plan_pressure, plan_burst_count) = self.summarise_plan(plan);
+        let species_colors = self.plan_species_colors(plan);
+        let burst_timeline = BurstTimeline::new(plan, self.pressure_config.burst_scheduling());
+        let plan_health: u64 = plan
+            .spawns()
+            .iter()
+            .map(|spawn| u64::from(spawn.hp()))
+            .sum();
+        let contracts = std::mem::take(&mut self.accepted_contracts);
+        self.wave_shield = contracts
+            .contains(&WaveContract::Shielded)
+            .then(|| WaveShield::for_plan_health(plan_health));
+        if !contracts.is_empty() {
+            out_events.push(Event::WaveContractsChanged {
+                contracts: Vec::new(),
+            });
+        }
 
+        self.next_wave_countdown = None;
+        if std::mem::take(&mut self.gold_rush_pending) {
+            context.reward_multiplier = context.reward_multiplier.saturating_mul(2);
+        }
+        if self.merchant_offer.take().is_some() {
+            out_events.push(Event::MerchantOfferClosed { accepted: false });
+        }
         self.active_wave = Some(context);
+        self.wave_tally = WaveTally::for_species(species_colors);
         self.assign_pending_wave_difficulty(PendingWaveDifficulty::Unset, out_events, true);
         out_events.push(Event::WaveStarted {
             wave,
-            difficulty,
-            effective_difficulty: context.effective_difficulty,
-            reward_multiplier: context.reward_multiplier,
-            pressure_scalar: context.pressure_scalar,
-            plan_pressure,
-            plan_species_table_version: self.species_table_version,
-            plan_burst_count,
+            launch: Box::new(WaveLaunch {
+                difficulty,
+                effective_difficulty: context.effective_difficulty,
+                reward_multiplier: context.reward_multiplier,
+                pressure_scalar: context.pressure_scalar,
+                pressure_share,
+                history,
+                plan_pressure,
+                plan_species_table_version: self.species_table_version,
+                plan_burst_count,
+                contracts,
+            }),
         });
+        self.burst_timeline = burst_timeline;
+        self.burst_timeline.advance(0, out_events);
+    }
+
+    /// Colour each species of `plan` spawns its bugs in, resolved like the wave spawner does:
+    /// from the species table when it defines the species, otherwise from the plan. A
+    /// colour shared by several species is attributed to the lowest identifier.
+    fn plan_species_colors(&self, plan: &PressureWavePlan) -> Vec<(BugColor, u32)> {
+        let species: BTreeSet<u32> = plan
+            .spawns()
+            .iter()
+            .map(|spawn| spawn.species_id())
+            .collect();
+        let mut colors: Vec<(BugColor, u32)> = Vec::new();
+        for id in species {
+            let color = self
+                .species_definitions
+                .iter()
+                .find(|definition| definition.id() == SpeciesId::new(id))
+                .map(|definition| definition.prototype().color())
+                .or_else(|| {
+                    usize::try_from(id)
+                        .ok()
+                        .and_then(|index| plan.prototypes().get(index))
+                        .map(SpeciesPrototype::color)
+                });
+            if let Some(color) = color {
+                if !colors.iter().any(|(known, _)| *known == color) {
+                    colors.push((color, id));
+                }
+            }
+        }
+        colors
     }
 
     #[allow(dead_code)]
@@ -407,14 +1020,12 @@ impl World {
         if wave.get() >= expected.get() {
             self.next_wave_id = WaveId::new(wave.get().saturating_add(1));
         }
-        let effective_difficulty = match difficulty {
-            WaveDifficulty::Normal => self.difficulty_level,
-            WaveDifficulty::Hard => self.difficulty_level.saturating_add(1),
-        };
-        let reward_multiplier = effective_difficulty.get().saturating_add(1);
-        let pressure_scalar = effective_difficulty.get().saturating_add(1);
+        let effective_difficulty = self.effective_difficulty(difficulty);
+        let reward_multiplier = self.launch_reward_multiplier(difficulty);
+        let pressure_scalar = effective_difficulty.pressure_scalar();
         ActiveWaveContext {
             id: wave,
+            started_at: self.clock.elapsed(),
             difficulty,
             effective_difficulty,
             reward_multiplier,
@@ -422,82 +1033,253 @@ impl World {
         }
     }
 
-    fn reward_multiplier(&self) -> u32 {
-        self.active_wave
-            .as_ref()
-            .map(ActiveWaveContext::reward_multiplier)
-            .unwrap_or_else(|| self.difficulty_level.saturating_add(1).get())
+    fn selected_difficulty(&self, difficulty: WaveDifficulty) -> DifficultyLadder {
+        match difficulty {
+            WaveDifficulty::Normal => self.difficulty_level,
+            WaveDifficulty::Hard => self.difficulty_level.saturating_add_levels(1),
+        }
     }
 
-    fn resolve_round_win(
+    fn effective_difficulty(&self, difficulty: WaveDifficulty) -> DifficultyLadder {
+        let bonus: u32 = self
+            .accepted_contracts
+            .iter()
+            .map(|contract| contract.difficulty_bonus())
+            .sum();
+        self.selected_difficulty(difficulty)
+            .saturating_add_levels(bonus)
+    }
+
+    /// Reward multiplier for a launch: the selected level plus one, raised by the
+    /// percentage bonus of every accepted contract and rounded up.
+    fn launch_reward_multiplier(&self, difficulty: WaveDifficulty) -> u32 {
+        let bonus_percent: u32 = self
+            .accepted_contracts
+            .iter()
+            .map(|contract| contract.reward_bonus_percent())
+            .sum();
+        self.selected_difficulty(difficulty)
+            .level()
+            .get()
+            .saturating_add(1)
+            .saturating_mul(bonus_percent.saturating_add(100))
+            .div_ceil(100)
+    }
+
+    fn set_wave_contract(
         &mut self,
-        active_wave: Option<ActiveWaveContext>,
+        contract: WaveContract,
+        accepted: bool,
         out_events: &mut Vec<Event>,
     ) {
-        let previous_level = self.difficulty_level;
-        let mut hard_wave = None;
-
-        if let Some(context) = active_wave {
-            if context.difficulty == WaveDifficulty::Hard {
-                let new_level = previous_level.saturating_add(HARD_WIN_DIFFICULTY_PROMOTION);
-                self.update_difficulty_level(new_level, out_events);
-                hard_wave = Some(context);
-            }
+        if self.active_wave.is_some() {
+            return;
         }
 
-        if let Some(context) = hard_wave {
-            out_events.push(Event::HardWinAchieved {
-                wave: context.id,
-                previous_level,
-                new_level: self.difficulty_level,
-            });
+        let position = self.accepted_contracts.binary_search(&contract);
+        match (accepted, position) {
+            (true, Err(index)) => self.accepted_contracts.insert(index, contract),
+            (false, Ok(index)) => {
+                let _ = self.accepted_contracts.remove(index);
+            }
+            _ => return,
         }
+        out_events.push(Event::WaveContractsChanged {
+            contracts: self.accepted_contracts.clone(),
+        });
     }
 
-    fn resolve_round_loss(
-        &mut self,
-        active_wave: Option<ActiveWaveContext>,
-        out_events: &mut Vec<Event>,
-    ) {
-        let _ = active_wave;
-        let new_level = self
-            .difficulty_level
-            .saturating_sub(ROUND_LOSS_DIFFICULTY_PENALTY);
-        self.update_difficulty_level(new_level, out_events);
+    fn configure_tower_heat(&mut self, enabled: bool, out_events: &mut Vec<Event>) {
+        if self.tower_heat == enabled {
+            return;
+        }
 
-        #[cfg(any(test, feature = "tower_scaffolding"))]
-        self.remove_towers_after_loss(out_events);
+        self.tower_heat = enabled;
+        let tower_ids: Vec<_> = self.towers.iter().map(|state| state.id).collect();
+        for tower_id in tower_ids {
+            if let Some(state) = self.towers.get_mut(tower_id) {
+                state.cool_down();
+            }
+        }
+        out_events.push(Event::TowerHeatConfigured { enabled });
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
-    fn remove_towers_after_loss(&mut self, out_events: &mut Vec<Event>) {
-        let total_towers = self.towers.iter().count();
-        if total_towers == 0 {
+    fn configure_line_of_sight(&mut self, enabled: bool, out_events: &mut Vec<Event>) {
+        if self.line_of_sight == enabled {
             return;
         }
 
-        let percent = ROUND_LOSS_TOWER_REMOVAL_PERCENT as usize;
-        if percent == 0 {
+        self.line_of_sight = enabled;
+        out_events.push(Event::LineOfSightConfigured { enabled });
+    }
+
+    fn configure_tower_content(&mut self, content: TowerContent, out_events: &mut Vec<Event>) {
+        if self.tower_content == content {
             return;
         }
 
-        let numerator = total_towers.saturating_mul(percent);
-        let mut to_remove = numerator / 100;
-        if numerator % 100 != 0 {
-            to_remove = to_remove.saturating_add(1);
+        self.tower_content = content;
+        out_events.push(Event::TowerContentConfigured);
+    }
+
+    fn configure_sandbox(&mut self, rules: SandboxRules, out_events: &mut Vec<Event>) {
+        if self.sandbox == rules {
+            return;
         }
-        to_remove = to_remove.max(1);
 
-        let mut ordered_ids: Vec<_> = self.towers.iter().map(|state| state.id).collect();
-        ordered_ids.reverse();
-        let candidate_ids: Vec<_> = ordered_ids.into_iter().take(to_remove).collect();
+        self.sandbox = rules;
+        self.sandbox_session |= rules.is_enabled();
+        out_events.push(Event::SandboxConfigured { rules });
+    }
 
-        if candidate_ids.is_empty() {
+    fn set_wave_seed(&mut self, seed: u64, out_events: &mut Vec<Event>) {
+        if !self.sandbox_session || self.active_wave.is_some() || self.wave_seed_global == seed {
             return;
         }
 
-        let mut removed_states = Vec::with_capacity(candidate_ids.len());
-        for tower_id in candidate_ids {
+        self.wave_seed_global = seed;
+        out_events.push(Event::WaveSeedChanged { seed });
+    }
+
+    fn debug_spawn_bug(
+        &mut self,
+        cell: CellCoord,
+        species: SpeciesId,
+        out_events: &mut Vec<Event>,
+    ) {
+        if !self.sandbox.debug_spawns || query::is_cell_blocked(self, cell) {
+            return;
+        }
+
+        let Some(prototype) = self
+            .species_definitions
+            .iter()
+            .find(|definition| definition.id() == species)
+            .map(SpeciesDefinition::prototype)
+        else {
+            return;
+        };
+
+        self.spawn_bug_at(
+            BugSpawn {
+                cell,
+                color: prototype.color(),
+                health: prototype.health(),
+                step_ms: prototype.step_ms().get(),
+                pathing: prototype.pathing(),
+                bounty: prototype.bounty(),
+                rank: prototype.rank(),
+                dodge: prototype.dodge(),
+                archetype: BugArchetype::Basic,
+            },
+            out_events,
+        );
+    }
+
+    fn roll_between_wave_event(&mut self, wave: WaveId, out_events: &mut Vec<Event>) {
+        let towers: Vec<TowerId> = self.towers.iter().map(|state| state.id).collect();
+
+        let Some(event) = between_waves::roll(self.wave_seed_global, wave, &towers) else {
+            return;
+        };
+        out_events.push(Event::BetweenWaveEventRolled { event });
+        match event {
+            BetweenWaveEvent::MeteorStrike { tower } => {
+                self.destroy_tower_by_meteor(tower, out_events);
+            }
+            BetweenWaveEvent::MerchantOffer { discount_percent } => {
+                self.merchant_offer = Some(discount_percent);
+            }
+            BetweenWaveEvent::GoldRush => {
+                self.gold_rush_pending = true;
+            }
+        }
+    }
+
+    fn answer_merchant_offer(&mut self, accept: bool, out_events: &mut Vec<Event>) {
+        let Some(discount_percent) = self.merchant_offer.take() else {
+            return;
+        };
+        if accept {
+            self.merchant_discount = Some(discount_percent);
+        }
+        out_events.push(Event::MerchantOfferClosed { accepted: accept });
+    }
+
+    fn reward_multiplier(&self) -> u32 {
+        self.active_wave
+            .as_ref()
+            .map(ActiveWaveContext::reward_multiplier)
+            .unwrap_or_else(|| self.difficulty_level.level().saturating_add(1).get())
+    }
+
+    fn resolve_round_win(
+        &mut self,
+        active_wave: Option<ActiveWaveContext>,
+        out_events: &mut Vec<Event>,
+    ) {
+        let previous_level = self.difficulty_level;
+        let mut hard_wave = None;
+
+        if let Some(context) = active_wave {
+            if context.difficulty == WaveDifficulty::Hard {
+                let new_level = previous_level.saturating_add_levels(HARD_WIN_DIFFICULTY_PROMOTION);
+                self.update_difficulty_level(new_level, out_events);
+                hard_wave = Some(context);
+            }
+        }
+
+        if let Some(context) = hard_wave {
+            out_events.push(Event::HardWinAchieved {
+                wave: context.id,
+                previous_level: previous_level.level(),
+                new_level: self.difficulty_level.level(),
+            });
+        }
+    }
+
+    fn resolve_round_loss(
+        &mut self,
+        active_wave: Option<ActiveWaveContext>,
+        out_events: &mut Vec<Event>,
+    ) {
+        let _ = active_wave;
+        let new_level = self
+            .difficulty_level
+            .saturating_sub_levels(ROUND_LOSS_DIFFICULTY_PENALTY);
+        self.update_difficulty_level(new_level, out_events);
+
+        self.remove_towers_after_loss(out_events);
+    }
+
+    fn remove_towers_after_loss(&mut self, out_events: &mut Vec<Event>) {
+        let total_towers = self.towers.iter().count();
+        if total_towers == 0 {
+            return;
+        }
+
+        let percent = ROUND_LOSS_TOWER_REMOVAL_PERCENT as usize;
+        if percent == 0 {
+            return;
+        }
+
+        let numerator = total_towers.saturating_mul(percent);
+        let mut to_remove = numerator / 100;
+        if !numerator.is_multiple_of(100) {
+            to_remove = to_remove.saturating_add(1);
+        }
+        to_remove = to_remove.max(1);
+
+        let mut ordered_ids: Vec<_> = self.towers.iter().map(|state| state.id).collect();
+        ordered_ids.reverse();
+        let candidate_ids: Vec<_> = ordered_ids.into_iter().take(to_remove).collect();
+
+        if candidate_ids.is_empty() {
+            return;
+        }
+
+        let mut removed_states = Vec::with_capacity(candidate_ids.len());
+        for tower_id in candidate_ids {
             if let Some(state) = self.towers.remove(tower_id) {
                 self.mark_tower_region(state.region, false);
                 removed_states.push(state);
@@ -535,24 +1317,52 @@ impl World {
         }
 
         let (columns, rows) = self.occupancy.dimensions();
+        let coverage = self.tower_coverage_counts(columns, rows);
         let walls = &self.walls;
-        #[cfg(any(test, feature = "tower_scaffolding"))]
+        let void_cells = &self.void_cells;
         let tower_occupancy = &self.tower_occupancy;
-        self.navigation_field
-            .rebuild_with(columns, rows, &self.targets, |cell| {
-                if walls.contains(cell) {
-                    return true;
-                }
+        let bridges = &self.bridges;
+        let is_blocked = |cell: CellCoord| {
+            if walls.contains(cell) {
+                return true;
+            }
 
-                #[cfg(any(test, feature = "tower_scaffolding"))]
-                {
-                    if tower_occupancy.contains(cell) {
-                        return true;
-                    }
-                }
+            if void_cells.contains(cell) && !bridges.contains(cell) {
+                return true;
+            }
 
-                false
-            });
+            if tower_occupancy.contains(cell) && !bridges.contains(cell) {
+                return true;
+            }
+
+            false
+        };
+        self.navigation_field
+            .rebuild_with(columns, rows, &self.targets, is_blocked);
+        // Without tower coverage every cell costs one step, so the weighted search would
+        // only reproduce the plain field at several times the cost on large maps.
+        if coverage.iter().all(|covering| *covering == 0) {
+            self.flanker_navigation_field
+                .copy_distances_from(&self.navigation_field);
+        } else {
+            self.flanker_navigation_field.rebuild_weighted_with(
+                columns,
+                rows,
+                &self.targets,
+                is_blocked,
+                |cell| {
+                    let index =
+                        u64::from(cell.row()) * u64::from(columns) + u64::from(cell.column());
+                    let covering = usize::try_from(index)
+                        .ok()
+                        .and_then(|index| coverage.get(index).copied())
+                        .unwrap_or(0);
+                    covering
+                        .saturating_mul(FLANKER_COVERAGE_PENALTY)
+                        .saturating_add(1)
+                },
+            );
+        }
         let field_width = self.navigation_field.width();
         let field_height = self.navigation_field.height();
         debug_assert_eq!(field_width, columns);
@@ -572,6 +1382,48 @@ impl World {
                 .all(|exit| { self.navigation_field.distance(*exit) == Some(0) }));
         }
         self.navigation_dirty = false;
+        self.navigation_generation = self.navigation_generation.saturating_add(1);
+    }
+
+    /// Counts, per cell in row-major order, how many towers have the cell within range.
+    fn tower_coverage_counts(&self, columns: u32, rows: u32) -> Vec<u16> {
+        let len = usize::try_from(u64::from(columns) * u64::from(rows)).unwrap_or(0);
+        let mut coverage = vec![0_u16; len];
+        for tower in self.towers.iter().filter(|tower| tower.kind.fires()) {
+            let range_cells = tower.kind.range_in_cells(self.cells_per_tile);
+            let max_distance_half = u128::from(range_cells) * 2;
+            let center = tower_center_half(tower.region);
+            let origin = tower.region.origin();
+            let size = tower.region.size();
+            let first_column = origin.column().saturating_sub(range_cells);
+            let last_column = origin
+                .column()
+                .saturating_add(size.width())
+                .saturating_add(range_cells)
+                .min(columns);
+            let first_row = origin.row().saturating_sub(range_cells);
+            let last_row = origin
+                .row()
+                .saturating_add(size.height())
+                .saturating_add(range_cells)
+                .min(rows);
+            for row in first_row..last_row {
+                for column in first_column..last_column {
+                    let cell = CellCoord::new(column, row);
+                    if center.distance_to(bug_center_half(cell)) > max_distance_half {
+                        continue;
+                    }
+                    let index = u64::from(row) * u64::from(columns) + u64::from(column);
+                    if let Some(count) = usize::try_from(index)
+                        .ok()
+                        .and_then(|index| coverage.get_mut(index))
+                    {
+                        *count = count.saturating_add(1);
+                    }
+                }
+            }
+        }
+        coverage
     }
 
     fn iter_bugs_mut(&mut self) -> impl Iterator<Item = &mut Bug> {
@@ -585,6 +1437,8 @@ impl World {
     fn remove_bug_at_index(&mut self, index: usize) {
         let removed = self.bugs.swap_remove(index);
         let _ = self.bug_positions.remove(&removed.id);
+        self.wave_tally
+            .track_removal(removed.id, self.clock.elapsed(), removed.health.is_zero());
         if index < self.bugs.len() {
             let moved_bug = &self.bugs[index];
             let replaced = self.bug_positions.insert(moved_bug.id, index);
@@ -592,32 +1446,92 @@ impl World {
         }
     }
 
-    fn spawn_from_spawner(
+    fn spawn_from_spawner(&mut self, spawn: BugSpawn, out_events: &mut Vec<Event>) {
+        if !self.bug_spawners.contains(spawn.cell) {
+            return;
+        }
+
+        self.spawn_bug_at(spawn, out_events);
+    }
+
+    /// Spawns a squad on every slot of `shape` around `anchor`, or nothing when any slot is
+    /// unavailable.
+    ///
+    /// Slots are laid out along the rim edge holding the anchor, with depth pointing into
+    /// the maze: downwards from the top row and sideways from the side columns. Every squad
+    /// member copies `leader`, which spawns on the anchor cell.
+    fn spawn_formation(
         &mut self,
-        cell: CellCoord,
-        color: BugColor,
-        health: Health,
-        step_ms: u32,
+        shape: FormationShape,
+        size: u32,
+        leader: BugSpawn,
         out_events: &mut Vec<Event>,
     ) {
-        if !self.bug_spawners.contains(cell) {
+        let anchor = leader.cell;
+        if size == 0 || !self.bug_spawners.contains(anchor) {
             return;
         }
 
+        let (columns, _) = self.occupancy.dimensions();
+        let cells: Option<Vec<CellCoord>> = shape
+            .slots(size)
+            .into_iter()
+            .map(|(lateral, depth)| formation_slot_cell(anchor, columns, lateral, depth))
+            .collect();
+        let Some(cells) = cells else {
+            return;
+        };
+        if cells.iter().any(|cell| query::is_cell_blocked(self, *cell)) {
+            return;
+        }
+
+        let formation = FormationId::new(self.next_formation_id);
+        self.next_formation_id = self.next_formation_id.saturating_add(1);
+        let mut bugs = Vec::with_capacity(cells.len());
+        for cell in cells {
+            bugs.push(BugId::new(self.next_bug_id));
+            self.spawn_bug_at(BugSpawn { cell, ..leader }, out_events);
+        }
+        out_events.push(Event::FormationSpawned {
+            formation,
+            shape,
+            bugs,
+        });
+    }
+
+    fn spawn_bug_at(&mut self, spawn: BugSpawn, out_events: &mut Vec<Event>) {
+        let BugSpawn {
+            cell,
+            color,
+            health,
+            step_ms,
+            pathing,
+            bounty,
+            rank,
+            dodge,
+            archetype,
+        } = spawn;
         if self.occupancy.index(cell).is_none() || !self.occupancy.can_enter(cell) {
             return;
         }
 
-        if self.walls.contains(cell) {
+        if self.walls.contains(cell) || self.void_blocks_ground(cell) {
             return;
         }
 
         let bug_id = self.next_bug_identifier();
-        let bug = Bug::new(bug_id, cell, color, health, step_ms);
+        let mut bug = Bug::new(bug_id, cell, color, health, step_ms, pathing, bounty);
+        if rank == BugRank::Boss {
+            bug.boss_phase = Some(BossPhase::Opening);
+        }
+        bug.dodge = dodge;
+        bug.archetype = archetype;
         let bug_health = bug.health();
         self.occupancy.occupy(bug_id, cell);
         let index = self.bugs.len();
         self.bugs.push(bug);
+        self.wave_tally
+            .track_spawn(bug_id, color, self.clock.elapsed());
         let replaced = self.bug_positions.insert(bug_id, index);
         debug_assert!(replaced.is_none());
         out_events.push(Event::BugSpawned {
@@ -628,13 +1542,102 @@ impl World {
         });
     }
 
+    /// Steps a boss through every phase whose health threshold it has crossed, running each
+    /// phase's script in order.
+    fn advance_boss_phase(&mut self, bug_id: BugId, out_events: &mut Vec<Event>) {
+        loop {
+            let Some(index) = self.bug_index(bug_id) else {
+                return;
+            };
+            let bug = &mut self.bugs[index];
+            let Some(next) = bug.boss_phase.and_then(BossPhase::next) else {
+                return;
+            };
+            if BossPhase::for_health(bug.health, bug.max_health) < next {
+                return;
+            }
+
+            bug.boss_phase = Some(next);
+            bug.invulnerable_ms = BOSS_PHASE_INVULNERABILITY_MS;
+            if next == BossPhase::Enraged {
+                bug.step_ms = (bug.step_ms / 2).max(1);
+                bug.accum_ms = bug.accum_ms.min(bug.step_ms);
+            }
+            let (cell, color, max_health, step_ms, pathing) = (
+                bug.cell,
+                bug.color,
+                bug.max_health,
+                bug.step_ms,
+                bug.pathing,
+            );
+            out_events.push(Event::BossPhaseChanged {
+                bug_id,
+                phase: next,
+            });
+            if next == BossPhase::Summoning {
+                let health = Health::new((max_health.get() / BOSS_ADD_HEALTH_DIVISOR).max(1));
+                self.summon_adds(
+                    cell,
+                    BOSS_SUMMONED_ADDS,
+                    color,
+                    health,
+                    step_ms,
+                    pathing,
+                    out_events,
+                );
+            }
+        }
+    }
+
+    /// Spawns up to `count` adds, such as a boss's summons or a splitter's children, on the
+    /// free cells next to `cell`, scanning directions in a fixed order.
+    #[allow(clippy::too_many_arguments)]
+    fn summon_adds(
+        &mut self,
+        cell: CellCoord,
+        count: usize,
+        color: BugColor,
+        health: Health,
+        step_ms: u32,
+        pathing: PathingPersonality,
+        out_events: &mut Vec<Event>,
+    ) {
+        let (columns, rows) = self.occupancy.dimensions();
+        let cells: Vec<CellCoord> = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .filter_map(|direction| advance_cell(cell, direction, columns, rows))
+        .filter(|neighbor| !query::is_cell_blocked(self, *neighbor))
+        .take(count)
+        .collect();
+        for add in cells {
+            self.spawn_bug_at(
+                BugSpawn {
+                    cell: add,
+                    color,
+                    health,
+                    step_ms,
+                    pathing,
+                    bounty: Gold::new(1),
+                    rank: BugRank::Regular,
+                    dodge: DodgeChance::NONE,
+                    archetype: BugArchetype::Basic,
+                },
+                out_events,
+            );
+        }
+    }
+
     fn next_bug_identifier(&mut self) -> BugId {
         let bug_id = BugId::new(self.next_bug_id);
         self.next_bug_id = self.next_bug_id.saturating_add(1);
         bug_id
     }
 
-    #[cfg_attr(not(any(test, feature = "tower_scaffolding")), allow(dead_code))]
     fn next_projectile_identifier(&mut self) -> ProjectileId {
         let id = self.next_projectile_id;
         let next = self.next_projectile_id.get().saturating_add(1);
@@ -670,14 +1673,18 @@ impl World {
                 continue;
             }
 
-            if self.walls.contains(next_cell) {
+            if self.walls.contains(next_cell)
+                || (self.void_cells.contains(next_cell) && !self.bridges.contains(next_cell))
+            {
                 continue;
             }
 
             self.occupancy.vacate(from);
             self.occupancy.occupy(bug.id, next_cell);
             bug.advance(next_cell);
+            self.traffic_heatmap.record(next_cell, 1);
             bug.accum_ms = bug.accum_ms.saturating_sub(bug.step_ms);
+            self.wave_tally.track_step(bug.id);
             out_events.push(Event::BugAdvanced {
                 bug_id: bug.id,
                 from,
@@ -700,22 +1707,116 @@ impl World {
             }
         }
 
-        let triggering_bug = exited.first().map(|(bug_id, _)| *bug_id);
+        if let ExitBehavior::LoopAround { health_percent } = self.exit_behavior {
+            for (bug_id, exit) in exited {
+                self.loop_exited_bug(bug_id, exit, health_percent, out_events);
+            }
+            return;
+        }
+
+        let mut triggering_bug = None;
 
         for (bug_id, cell) in exited {
             self.occupancy.vacate(cell);
             if let Some(position) = self.bug_index(bug_id) {
+                self.traffic_heatmap
+                    .record_leak_route(&self.bugs[position].route);
                 self.remove_bug_at_index(position);
             }
             out_events.push(Event::BugExited { bug_id, cell });
+            self.wave_tally.leaks = self.wave_tally.leaks.saturating_add(1);
+            self.release_priority_target(bug_id, out_events);
+            if triggering_bug.is_none() {
+                self.base_health = self.base_health.saturating_sub(1);
+                if self.base_health == 0 {
+                    triggering_bug = Some(bug_id);
+                } else {
+                    out_events.push(Event::BaseDamaged {
+                        bug: bug_id,
+                        remaining: self.base_health,
+                    });
+                }
+            }
         }
 
         if let Some(bug) = triggering_bug {
+            self.base_health = self.game_rules.base_health;
             let _ = self.transition_to_play_mode(PlayMode::Builder, out_events);
             out_events.push(Event::RoundLost { bug });
         }
     }
 
+    /// Sends a bug that reached `exit` back to a spawner with reduced health, or out of the
+    /// maze for good once it has no health left to re-enter with.
+    ///
+    /// The retained share is capped at [`MAX_LOOP_HEALTH_PERCENT`], so a bug loses at least
+    /// one hit point per lap and a looping wave always ends.
+    ///
+    /// Spawners are tried in order starting from one picked by the bug's identifier, so bugs
+    /// exiting together spread across the spawners. A bug that finds every spawner blocked
+    /// stays on the exit and tries again on the next tick.
+    fn loop_exited_bug(
+        &mut self,
+        bug_id: BugId,
+        exit: CellCoord,
+        health_percent: u32,
+        out_events: &mut Vec<Event>,
+    ) {
+        let Some(index) = self.bug_index(bug_id) else {
+            return;
+        };
+        let retained = u64::from(self.bugs[index].health.get())
+            * u64::from(health_percent.min(MAX_LOOP_HEALTH_PERCENT))
+            / 100;
+        let remaining = Health::new(u32::try_from(retained).unwrap_or(u32::MAX));
+
+        let spawner = if remaining.is_zero() {
+            None
+        } else {
+            let spawners: Vec<CellCoord> = self.bug_spawners.iter().collect();
+            if spawners.is_empty() {
+                return;
+            }
+            let start = usize::try_from(bug_id.get()).unwrap_or(0) % spawners.len();
+            let free = spawners
+                .iter()
+                .cycle()
+                .skip(start)
+                .take(spawners.len())
+                .copied()
+                .find(|cell| !query::is_cell_blocked(self, *cell));
+            let Some(spawner) = free else {
+                return;
+            };
+            Some(spawner)
+        };
+
+        self.traffic_heatmap
+            .record_leak_route(&self.bugs[index].route);
+        self.wave_tally.leaks = self.wave_tally.leaks.saturating_add(1);
+        self.occupancy.vacate(exit);
+
+        let Some(spawner) = spawner else {
+            self.remove_bug_at_index(index);
+            out_events.push(Event::BugWornOut { bug_id, exit });
+            self.release_priority_target(bug_id, out_events);
+            return;
+        };
+
+        self.occupancy.occupy(bug_id, spawner);
+        let bug = &mut self.bugs[index];
+        bug.health = remaining;
+        bug.cell = spawner;
+        bug.route = vec![spawner];
+        bug.accum_ms = 0;
+        out_events.push(Event::BugLooped {
+            bug_id,
+            exit,
+            spawner,
+            remaining,
+        });
+    }
+
     #[allow(dead_code)]
     fn cleanup_dead_bugs(&mut self) {
         let mut index = 0;
@@ -739,6 +1840,16 @@ impl Default for World {
 
 /// Applies the provided command to the world, mutating state deterministically.
 pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
+    if settles_deferred_integration(&command) {
+        if world.deferred.is_pending() {
+            let completed = world.settle_deferred_integration();
+            debug_assert!(
+                completed.is_empty(),
+                "batched ticks settle before anything completes"
+            );
+        }
+        world.deferred.deadline = None;
+    }
     match command {
         Command::ConfigureTileGrid {
             columns,
@@ -746,20 +1857,26 @@ pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
             tile_length,
             cells_per_tile,
         } => {
+            if let Err(reason) = validate_tile_grid(columns, rows, tile_length, cells_per_tile) {
+                out_events.push(Event::TileGridConfigRejected { reason });
+                return;
+            }
             world.tile_grid = TileGrid::new(columns, rows, tile_length);
-            let normalized_cells = cells_per_tile.max(1);
-            world.cells_per_tile = normalized_cells;
-            let (target, targets) = build_target(columns, rows, normalized_cells);
+            world.cells_per_tile = cells_per_tile;
+            let (target, targets) = build_target(columns, rows, cells_per_tile);
             world.target = target;
             world.targets = targets;
-            let total_columns = total_cell_columns(columns, normalized_cells);
-            let total_rows = total_cell_rows(rows, normalized_cells);
+            let total_columns = total_cell_columns(columns, cells_per_tile);
+            let total_rows = total_cell_rows(rows, cells_per_tile);
             world.occupancy = OccupancyGrid::new(total_columns, total_rows);
+            world.traffic_heatmap = TrafficHeatmap::new(total_columns, total_rows);
             world.walls.rebuild(
                 total_columns,
                 total_rows,
-                build_cell_walls(columns, rows, normalized_cells),
+                build_cell_walls(columns, rows, cells_per_tile),
             );
+            world.bridges = BitGrid::new(total_columns, total_rows);
+            world.void_cells = BitGrid::new(total_columns, total_rows);
             let (species_table_version, species_definitions) = default_species_table();
             let spawn_patches = default_spawn_patches();
             let pressure_config = default_pressure_config();
@@ -771,75 +1888,28 @@ pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
             world.wave_seed_global = DEFAULT_WAVE_GLOBAL_SEED;
             world.level_id = DEFAULT_LEVEL_ID;
             world.mark_navigation_dirty();
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                world.tower_occupancy = BitGrid::new(total_columns, total_rows);
-                world.towers = TowerRegistry::new();
-            }
+            world.tower_occupancy = BitGrid::new(total_columns, total_rows);
+            world.towers = TowerRegistry::new();
+            world.spawner_config = SpawnerConfig::default();
+            world.spawner_activations_applied = 0;
+            world.reset_nests();
             world.rebuild_bug_spawners();
             world.clear_bugs();
             world.rebuild_navigation_field_if_dirty();
-            world.update_difficulty_level(DifficultyLevel::new(0), out_events);
+            world.update_difficulty_level(DifficultyLadder::default(), out_events);
             world.assign_pending_wave_difficulty(PendingWaveDifficulty::Unset, out_events, true);
             out_events.push(Event::PressureConfigChanged {
                 species_table_version: world.species_table_version,
-                pressure: pressure_config,
+                pressure: Box::new(pressure_config),
             });
             out_events.push(Event::MazeLayoutChanged);
         }
-        Command::Tick { dt } => {
-            if world.play_mode == PlayMode::Builder {
-                return;
-            }
-
-            world.rebuild_navigation_field_if_dirty();
-            world.tick_index = world.tick_index.saturating_add(1);
-            out_events.push(Event::TimeAdvanced { dt });
-
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                let tower_ids: Vec<_> = world.towers.iter().map(|state| state.id).collect();
-                for tower_id in tower_ids {
-                    if let Some(state) = world.towers.get_mut(tower_id) {
-                        state.cooldown_remaining = state.cooldown_remaining.saturating_sub(dt);
-                    }
-                }
-            }
-
-            let dt_millis = u32::try_from(dt.as_millis()).unwrap_or(u32::MAX);
-            let projectile_ids: Vec<_> = world.projectiles.keys().copied().collect();
-            let mut completed = Vec::new();
-            for projectile_id in projectile_ids {
-                if let Some(projectile) = world.projectiles.get_mut(&projectile_id) {
-                    if projectile.travel_time_ms == 0 {
-                        projectile.travelled_half = projectile.distance_half;
-                        completed.push((projectile_id, projectile.target, projectile.damage));
-                        continue;
-                    }
-
-                    let new_elapsed = projectile
-                        .elapsed_ms
-                        .saturating_add(u128::from(dt_millis))
-                        .min(projectile.travel_time_ms);
-                    projectile.elapsed_ms = new_elapsed;
-
-                    let travelled = projectile.distance_half.saturating_mul(new_elapsed)
-                        / projectile.travel_time_ms;
-                    projectile.travelled_half = travelled;
-
-                    if projectile.elapsed_ms >= projectile.travel_time_ms {
-                        completed.push((projectile_id, projectile.target, projectile.damage));
-                    }
-                }
-            }
-
-            for (projectile_id, target, damage) in completed {
-                world.resolve_projectile_completion(projectile_id, target, damage, out_events);
-            }
-
-            for bug in world.iter_bugs_mut() {
-                let advanced = bug.accum_ms.saturating_add(dt_millis);
-                bug.accum_ms = advanced.min(bug.step_ms);
+        Command::Tick { dt } => world.tick(dt, false, out_events),
+        Command::BatchedTick { dt } => world.tick(dt, true, out_events),
+        Command::AdvanceTo { at_ms } => {
+            let dt = world.clock.until_ms(at_ms);
+            if !dt.is_zero() {
+                world.tick(dt, false, out_events);
             }
         }
         Command::ConfigureBugStep { step_duration } => {
@@ -853,65 +1923,124 @@ pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
             if world.play_mode == PlayMode::Builder {
                 return;
             }
-            world
-                .reservations
-                .queue(world.tick_index, ReservationClaim::new(bug_id, direction));
+            world.reservations.queue(
+                world.clock.ticks(),
+                ReservationClaim::new(bug_id, direction),
+            );
             world.resolve_pending_steps(out_events);
             world.process_exit_cells(out_events);
         }
+        Command::ConfigureSpawners { config } => {
+            world.configure_spawners(config, out_events);
+        }
         Command::SetPlayMode { mode } => {
             let _ = world.transition_to_play_mode(mode, out_events);
         }
         Command::SetDifficultyLevel { level } => {
-            world.update_difficulty_level(level, out_events);
+            world.update_difficulty_level(DifficultyLadder::from_level(level), out_events);
         }
         Command::SpawnBug {
             spawner,
             color,
             health,
             step_ms,
+            pathing,
+            bounty,
+            rank,
+            dodge,
+            archetype,
         } => {
             if world.play_mode == PlayMode::Builder {
                 return;
             }
 
-            world.spawn_from_spawner(spawner, color, health, step_ms, out_events);
+            let spawn = BugSpawn {
+                cell: spawner,
+                color,
+                health,
+                step_ms,
+                pathing,
+                bounty,
+                rank,
+                dodge,
+                archetype,
+            };
+            world.spawn_from_spawner(spawn, out_events);
         }
-        Command::FireProjectile { tower, target } => {
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                world.handle_fire_projectile(tower, target, out_events);
+        Command::SpawnFormation {
+            anchor,
+            shape,
+            size,
+            color,
+            health,
+            step_ms,
+            pathing,
+            bounty,
+            dodge,
+            archetype,
+        } => {
+            if world.play_mode == PlayMode::Builder {
+                return;
             }
 
-            #[cfg(not(any(test, feature = "tower_scaffolding")))]
-            let _ = (tower, target);
+            let leader = BugSpawn {
+                cell: anchor,
+                color,
+                health,
+                step_ms,
+                pathing,
+                bounty,
+                rank: BugRank::Regular,
+                dodge,
+                archetype,
+            };
+            world.spawn_formation(shape, size, leader, out_events);
+        }
+        Command::FireProjectile { tower, target } => {
+            world.handle_fire_projectile(tower, target, out_events);
+        }
+        Command::ConfigureNests { health } => world.configure_nests(health, out_events),
+        Command::FireAtNest { tower, nest } => {
+            world.handle_fire_at_nest(tower, nest, out_events);
         }
         Command::PlaceTower { kind, origin } => {
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                world.handle_place_tower(kind, origin, TowerPlacementCost::SpendGold, out_events);
-            }
-
-            #[cfg(not(any(test, feature = "tower_scaffolding")))]
-            let _ = (kind, origin);
+            let cost_policy = if world.sandbox.free_placement {
+                TowerPlacementCost::IgnoreGold
+            } else {
+                TowerPlacementCost::SpendGold
+            };
+            let rotation = world.placement_rotation;
+            world.handle_place_tower(kind, origin, rotation, cost_policy, out_events);
         }
-        Command::ImportTower { kind, origin } => {
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                world.handle_place_tower(kind, origin, TowerPlacementCost::IgnoreGold, out_events);
+        Command::ImportTower {
+            kind,
+            origin,
+            rotation,
+        } => {
+            world.handle_place_tower(
+                kind,
+                origin,
+                rotation,
+                TowerPlacementCost::IgnoreGold,
+                out_events,
+            );
+        }
+        Command::SetPlacementRotation { rotation } => {
+            if world.placement_rotation != rotation {
+                world.placement_rotation = rotation;
+                out_events.push(Event::PlacementRotationChanged { rotation });
             }
-
-            #[cfg(not(any(test, feature = "tower_scaffolding")))]
-            let _ = (kind, origin);
         }
+        Command::ConfigureBridges { cells } => world.configure_bridges(cells, out_events),
+        Command::ConfigureVoidCells { cells } => world.configure_void_cells(cells, out_events),
         Command::RemoveTower { tower } => {
-            #[cfg(any(test, feature = "tower_scaffolding"))]
-            {
-                world.handle_remove_tower(tower, out_events);
-            }
-
-            #[cfg(not(any(test, feature = "tower_scaffolding")))]
-            let _ = tower;
+            world.handle_remove_tower(tower, out_events);
+        }
+        Command::SetTowerTargeting { tower, policy } => {
+            world.set_tower_targeting(tower, policy, out_events);
+        }
+        Command::UpgradeTower { tower } => {
+            world.handle_upgrade_tower(tower, out_events);
         }
         Command::GeneratePressureWave { inputs } => {
             let mut spawns = Vec::new();
@@ -919,7 +2048,10 @@ pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
             world
                 .pressure_v2
                 .generate(&inputs, &mut spawns, &mut prototypes);
-            let plan = PressureWavePlan::new(spawns, prototypes);
+            let mut plan = PressureWavePlan::new(spawns, prototypes);
+            if world.pressure_v2.paced_last_wave() {
+                plan = plan.with_paced();
+            }
             world.cache_pressure_wave(inputs, plan, out_events);
         }
         Command::CachePressureWave { inputs, plan } => {
@@ -928,16 +2060,97 @@ pub fn apply(world: &mut World, command: Command, out_events: &mut Vec<Event>) {
         Command::StartWave { wave, difficulty } => {
             world.launch_wave(wave, difficulty, out_events);
         }
+        Command::ConfigureWaveSeed { seed } => {
+            world.wave_seed_global = seed;
+        }
+        Command::ConfigureAutoWave { delay } => {
+            world.auto_wave_delay = delay.filter(|delay| !delay.is_zero());
+            if world.auto_wave_delay.is_none() {
+                world.next_wave_countdown = None;
+            }
+        }
+        Command::ConfigureBuilderTimer { limit } => {
+            world.builder_time_limit = limit.filter(|limit| !limit.is_zero());
+            world.restore_builder_time(out_events);
+        }
+        Command::SkipWaveCountdown { reward } => {
+            world.skip_wave_countdown(reward, out_events);
+        }
+        Command::SetWaveContract { contract, accepted } => {
+            world.set_wave_contract(contract, accepted, out_events);
+        }
+        Command::AnswerMerchantOffer { accept } => {
+            world.answer_merchant_offer(accept, out_events);
+        }
+        Command::ConfigureGameRules { rules } => {
+            world.configure_game_rules(rules, out_events);
+        }
+        Command::ConfigureExitBehavior { behavior } => {
+            if world.exit_behavior != behavior {
+                world.exit_behavior = behavior;
+                out_events.push(Event::ExitBehaviorConfigured { behavior });
+            }
+        }
+        Command::ConfigureAccuracy { model } => {
+            if world.accuracy != model {
+                world.accuracy = model;
+                out_events.push(Event::AccuracyConfigured { model });
+            }
+        }
+        Command::ConfigureTowerHeat { enabled } => {
+            world.configure_tower_heat(enabled, out_events);
+        }
+        Command::ConfigureLineOfSight { enabled } => {
+            world.configure_line_of_sight(enabled, out_events);
+        }
+        Command::ConfigureTowerContent { content } => {
+            world.configure_tower_content(content, out_events);
+        }
+        Command::ConfigureSandbox { rules } => {
+            world.configure_sandbox(rules, out_events);
+        }
+        Command::SetWaveSeed { seed } => {
+            world.set_wave_seed(seed, out_events);
+        }
+        Command::DebugSpawnBug { cell, species } => {
+            if world.play_mode == PlayMode::Builder {
+                return;
+            }
+
+            world.debug_spawn_bug(cell, species, out_events);
+        }
+        Command::MarkPriorityTarget { bug } => {
+            world.mark_priority_target(bug, out_events);
+        }
         Command::RequestAnalyticsRefresh => {
             // Analytics recomputation runs in a dedicated system; the world
             // acknowledges the request without mutating authoritative state.
         }
         Command::ResolveRound { outcome } => {
             let active_wave = world.active_wave.take();
+            world.burst_timeline = BurstTimeline::default();
+            world.wave_shield = None;
+            let concluded_wave = active_wave.map(|context| context.id);
+            if let Some(context) = active_wave.as_ref() {
+                let tally = std::mem::take(&mut world.wave_tally);
+                let report = tally.into_report(context, outcome, world.clock.elapsed());
+                world.wave_history = Some(WaveHistoryInputs::from_report(&report));
+                out_events.push(Event::WaveReportReady {
+                    report: Box::new(report),
+                });
+            }
             match outcome {
                 RoundOutcome::Win => world.resolve_round_win(active_wave, out_events),
                 RoundOutcome::Loss => world.resolve_round_loss(active_wave, out_events),
             }
+            if let Some(wave) = concluded_wave {
+                world.roll_between_wave_event(wave, out_events);
+            }
+            if world.activate_due_spawners(out_events) {
+                out_events.push(Event::MazeLayoutChanged);
+            }
+            world.start_wave_countdown(out_events);
+            world.restore_builder_time(out_events);
         }
     }
 }
@@ -952,8 +2165,6 @@ impl World {
         }
 
         let burst_config = self.pressure_config.burst_scheduling();
-        let nominal = burst_config.nominal_burst_size().get();
-        let max_bursts = burst_config.burst_count_max().get();
 
         let mut total_pressure_fixed: u64 = 0;
         let mut total_bursts: u32 = 0;
@@ -972,39 +2183,232 @@ impl World {
                 total_pressure_fixed =
                     total_pressure_fixed.saturating_add(u64::from(count) * u64::from(weight_fixed));
 
-                let bursts = Self::burst_count_for(count, nominal, max_bursts);
-                total_bursts = total_bursts.saturating_add(bursts);
+                total_bursts = total_bursts.saturating_add(burst_config.burst_count(count));
             }
         }
 
-        let pressure_value = (total_pressure_fixed / u64::from(PRESSURE_FIXED_POINT_SCALE))
-            .min(u64::from(u32::MAX)) as u32;
+        let pressure_value = total_pressure_fixed / u64::from(PRESSURE_FIXED_POINT_SCALE);
 
         (Pressure::new(pressure_value), total_bursts)
     }
 
-    fn burst_count_for(count: u32, nominal: u32, max_bursts: u32) -> u32 {
-        if count == 0 {
-            return 0;
+    fn rebuild_bug_spawners(&mut self) {
+        let config = &self.spawner_config;
+        let open: Vec<SpawnerSet> = config
+            .sets()
+            .iter()
+            .copied()
+            .filter(|set| !self.destroyed_nests.contains(set))
+            .collect();
+        let mut cells = self.resolve_spawner_cells(&open).unwrap_or_default();
+        for activation in &config.activations()[..self.spawner_activations_applied] {
+            if self.destroyed_nests.contains(&activation.set()) {
+                continue;
+            }
+            if let Ok(opened) = self.resolve_spawner_cells(&[activation.set()]) {
+                cells.extend(opened);
+            }
+        }
+        self.bug_spawners.assign(cells);
+    }
+
+    /// Spawner sets opened so far: the base sets followed by applied activations, without
+    /// repeats.
+    fn opened_spawner_sets(&self) -> Vec<SpawnerSet> {
+        let config = &self.spawner_config;
+        let mut sets: Vec<SpawnerSet> = Vec::new();
+        let activated = config.activations()[..self.spawner_activations_applied]
+            .iter()
+            .map(|activation| activation.set());
+        for set in config.sets().iter().copied().chain(activated) {
+            if !sets.contains(&set) {
+                sets.push(set);
+            }
         }
+        sets
+    }
+
+    /// Restores a full-health nest on every opened spawner set, or drops all nests when
+    /// nests are disabled.
+    fn reset_nests(&mut self) {
+        self.destroyed_nests.clear();
+        self.nests = match self.nest_health {
+            Some(health) => self
+                .opened_spawner_sets()
+                .into_iter()
+                .map(|set| Nest { set, health })
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+
+    fn configure_nests(&mut self, health: Option<Health>, out_events: &mut Vec<Event>) {
+        self.nest_health = health.filter(|health| !health.is_zero());
+        self.reset_nests();
+        self.rebuild_bug_spawners();
+        out_events.push(Event::NestsConfigured {
+            health: self.nest_health,
+        });
+        out_events.push(Event::MazeLayoutChanged);
+    }
 
-        let nominal = nominal.max(1);
-        let max_bursts = max_bursts.max(1);
-        let mut bursts = count / nominal;
-        if count % nominal != 0 {
-            bursts = bursts.saturating_add(1);
+    /// Percentage of the full wave pressure left once destroyed nests stop contributing.
+    fn pressure_share(&self) -> u32 {
+        let total = self.nests.len() + self.destroyed_nests.len();
+        if self.destroyed_nests.is_empty() || total == 0 {
+            return 100;
         }
-        bursts = bursts.max(1);
-        bursts.min(max_bursts)
+        let open = self.nests.len() * 100 / total;
+        u32::try_from(open).unwrap_or(100).max(1)
     }
 
-    fn rebuild_bug_spawners(&mut self) {
+    fn configure_spawners(&mut self, config: SpawnerConfig, out_events: &mut Vec<Event>) {
+        let resolved = self.resolve_spawner_cells(config.sets()).and_then(|cells| {
+            let mut staged = BTreeSet::new();
+            for activation in config.activations() {
+                staged.extend(self.resolve_spawner_cells(&[activation.set()])?);
+            }
+            Ok((cells, staged))
+        });
+        let (cells, staged) = match resolved {
+            Ok(resolved) => resolved,
+            Err(reason) => {
+                out_events.push(Event::SpawnerConfigRejected { reason });
+                return;
+            }
+        };
+
+        self.rebuild_navigation_field_if_dirty();
+        let unreachable = cells.iter().chain(&staged).copied().find(|cell| {
+            self.navigation_field
+                .distance(*cell)
+                .is_none_or(|distance| distance == u16::MAX)
+        });
+        if let Some(cell) = unreachable {
+            out_events.push(Event::SpawnerConfigRejected {
+                reason: SpawnerConfigError::Unreachable(cell),
+            });
+            return;
+        }
+
+        let spawners = u32::try_from(cells.len()).unwrap_or(u32::MAX);
+        self.spawner_config = config;
+        self.spawner_activations_applied = 0;
+        self.bug_spawners.assign(cells);
+        self.reset_nests();
+        out_events.push(Event::SpawnersConfigured { spawners });
+        let _ = self.activate_due_spawners(out_events);
+        out_events.push(Event::MazeLayoutChanged);
+    }
+
+    /// Opens every staged spawner set whose wave is the upcoming wave or earlier.
+    ///
+    /// Returns whether any set opened. Staged cells are not rechecked for reachability, so a
+    /// set walled in by towers opens without spawning until a path to the exit returns.
+    fn activate_due_spawners(&mut self, out_events: &mut Vec<Event>) -> bool {
+        let mut activated = false;
+        while let Some(activation) = self
+            .spawner_config
+            .activations()
+            .get(self.spawner_activations_applied)
+            .copied()
+        {
+            if activation.wave() > self.next_wave_id {
+                break;
+            }
+
+            self.spawner_activations_applied += 1;
+            let set = activation.set();
+            if self.destroyed_nests.contains(&set) {
+                continue;
+            }
+            let cells = self.resolve_spawner_cells(&[set]).unwrap_or_default();
+            let spawners = u32::try_from(cells.len()).unwrap_or(u32::MAX);
+            self.bug_spawners.extend(cells);
+            if let Some(health) = self.nest_health {
+                if !self.nests.iter().any(|nest| nest.set == set) {
+                    self.nests.push(Nest { set, health });
+                }
+            }
+            out_events.push(Event::SpawnerActivated {
+                wave: activation.wave(),
+                set: activation.set(),
+                spawners,
+            });
+            activated = true;
+        }
+        activated
+    }
+
+    /// Resolves spawner sets into spawner cells on the current grid.
+    ///
+    /// Edges stop above the exit and bottom border rows; patches and portals placed there
+    /// or outside the grid are rejected.
+    fn resolve_spawner_cells(
+        &self,
+        sets: &[SpawnerSet],
+    ) -> Result<BTreeSet<CellCoord>, SpawnerConfigError> {
         let (columns, rows) = self.occupancy.dimensions();
-        self.bug_spawners.assign_outer_rim(columns, rows);
-        self.bug_spawners.remove_bottom_row(columns, rows);
+        let spawn_rows = rows.saturating_sub(EXIT_CELL_LAYERS + BOTTOM_BORDER_CELL_LAYERS);
+        let mut cells = BTreeSet::new();
+        let mut claim = |cell: CellCoord| {
+            if cell.column() >= columns || cell.row() >= spawn_rows {
+                return Err(SpawnerConfigError::OutOfBounds(cell));
+            }
+            let _ = cells.insert(cell);
+            Ok(())
+        };
+
+        for set in sets {
+            match *set {
+                SpawnerSet::Edge(GridEdge::Top) => {
+                    if spawn_rows > 0 {
+                        for column in 0..columns {
+                            claim(CellCoord::new(column, 0))?;
+                        }
+                    }
+                }
+                SpawnerSet::Edge(GridEdge::Left) => {
+                    if columns > 0 {
+                        for row in 0..spawn_rows {
+                            claim(CellCoord::new(0, row))?;
+                        }
+                    }
+                }
+                SpawnerSet::Edge(GridEdge::Right) => {
+                    if let Some(last_column) = columns.checked_sub(1) {
+                        for row in 0..spawn_rows {
+                            claim(CellCoord::new(last_column, row))?;
+                        }
+                    }
+                }
+                SpawnerSet::Patch(id) => {
+                    let patch = self
+                        .spawn_patches
+                        .iter()
+                        .find(|patch| patch.id() == id)
+                        .ok_or(SpawnerConfigError::UnknownPatch(id))?;
+                    let origin = patch.extent().origin();
+                    let size = patch.extent().size();
+                    for row in 0..size.height() {
+                        for column in 0..size.width() {
+                            claim(CellCoord::new(
+                                origin.column().saturating_add(column),
+                                origin.row().saturating_add(row),
+                            ))?;
+                        }
+                    }
+                }
+                SpawnerSet::Portal(cell) => claim(cell)?,
+            }
+        }
+
+        if cells.is_empty() {
+            return Err(SpawnerConfigError::Empty);
+        }
+        Ok(cells)
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn handle_fire_projectile(
         &mut self,
         tower: TowerId,
@@ -1038,8 +2442,18 @@ impl World {
             return;
         }
 
+        if self.tower_heat && tower_state.overheated {
+            out_events.push(Event::ProjectileRejected {
+                tower,
+                target,
+                reason: ProjectileRejection::Overheated,
+            });
+            return;
+        }
+
         let tower_region = tower_state.region;
         let tower_kind = tower_state.kind;
+        let tower_upgrades = tower_state.upgrades;
 
         let Some(bug_index) = self.bug_index(target) else {
             out_events.push(Event::ProjectileRejected {
@@ -1063,9 +2477,27 @@ impl World {
             bug.cell
         };
 
-        let projectile_id = self.next_projectile_identifier();
+        if tower_kind.push_back_tiles() > 0 {
+            self.push_back_bugs(tower, tower_kind, tower_region, out_events);
+            self.start_tower_cooldown(tower, tower_kind);
+            return;
+        }
+
         let start = tower_center_half(tower_region);
         let end = bug_center_half(bug_cell);
+        if self.line_of_sight
+            && tower_kind.requires_line_of_sight()
+            && !query::raycast(self, start, end).is_clear()
+        {
+            out_events.push(Event::ProjectileRejected {
+                tower,
+                target,
+                reason: ProjectileRejection::LineOfSightBlocked,
+            });
+            return;
+        }
+
+        let projectile_id = self.next_projectile_identifier();
         let distance_half = start.distance_to(end);
         let range_cells = tower_kind.range_in_cells(self.cells_per_tile);
         let max_range_half = u128::from(range_cells) * 2;
@@ -1082,15 +2514,17 @@ impl World {
             travelled_half: 0,
             travel_time_ms,
             elapsed_ms: 0,
-            damage: tower_kind.projectile_damage(),
+            damage_type: tower_kind.damage_type(),
+            inflicts: tower_kind.inflicts(),
+            damage: self
+                .tower_content
+                .stats(tower_kind)
+                .upgraded_damage(tower_upgrades),
         };
         let replaced = self.projectiles.insert(projectile_id, projectile_state);
         debug_assert!(replaced.is_none());
 
-        if let Some(state) = self.towers.get_mut(tower) {
-            state.cooldown_remaining =
-                Duration::from_millis(u64::from(tower_kind.fire_cooldown_ms()));
-        }
+        self.start_tower_cooldown(tower, tower_kind);
 
         out_events.push(Event::ProjectileFired {
             projectile: projectile_id,
@@ -1099,6 +2533,180 @@ impl World {
         });
     }
 
+    fn handle_fire_at_nest(
+        &mut self,
+        tower: TowerId,
+        nest: SpawnerSet,
+        out_events: &mut Vec<Event>,
+    ) {
+        let reject = |reason, out_events: &mut Vec<Event>| {
+            out_events.push(Event::NestShotRejected {
+                tower,
+                nest,
+                reason,
+            });
+        };
+        if self.play_mode != PlayMode::Attack {
+            return reject(ProjectileRejection::InvalidMode, out_events);
+        }
+        let Some(tower_state) = self.towers.get(tower) else {
+            return reject(ProjectileRejection::MissingTower, out_events);
+        };
+        if tower_state.cooldown_remaining > Duration::ZERO {
+            return reject(ProjectileRejection::CooldownActive, out_events);
+        }
+        if self.tower_heat && tower_state.overheated {
+            return reject(ProjectileRejection::Overheated, out_events);
+        }
+        let tower_kind = tower_state.kind;
+        let tower_upgrades = tower_state.upgrades;
+        let Some(index) = self.nests.iter().position(|open| open.set == nest) else {
+            return reject(ProjectileRejection::MissingTarget, out_events);
+        };
+        if self.nests.len() == 1 {
+            return reject(ProjectileRejection::LastNest, out_events);
+        }
+
+        let damage = self
+            .tower_content
+            .stats(tower_kind)
+            .upgraded_damage(tower_upgrades);
+        let remaining = self.nests[index].health.saturating_sub(damage);
+        self.nests[index].health = remaining;
+        self.start_tower_cooldown(tower, tower_kind);
+        out_events.push(Event::NestDamaged {
+            tower,
+            nest,
+            remaining,
+        });
+        if !remaining.is_zero() {
+            return;
+        }
+
+        let _ = self.nests.remove(index);
+        self.destroyed_nests.push(nest);
+        self.rebuild_bug_spawners();
+        out_events.push(Event::NestDestroyed {
+            nest,
+            pressure_share: self.pressure_share(),
+        });
+        out_events.push(Event::MazeLayoutChanged);
+    }
+
+    fn start_tower_cooldown(&mut self, tower: TowerId, kind: TowerKind) {
+        if let Some(state) = self.towers.get_mut(tower) {
+            state.cooldown_remaining = if self.sandbox.instant_cooldowns {
+                Duration::ZERO
+            } else {
+                Duration::from_millis(u64::from(self.tower_content.stats(kind).fire_cooldown_ms))
+            };
+            if self.tower_heat {
+                state.add_shot_heat();
+            }
+        }
+    }
+
+    /// Shoves every bug within the pulse radius back along its incoming path.
+    ///
+    /// Bugs farthest from the exit move first, with ties broken by identifier, so a bug
+    /// never retreats into a cell that the bug behind it has yet to vacate and replays
+    /// resolve identically. Each step retreats to a free neighbour one navigation step
+    /// farther from the exit; a bug stops early once no such neighbour is free.
+    fn push_back_bugs(
+        &mut self,
+        tower: TowerId,
+        kind: TowerKind,
+        region: CellRect,
+        out_events: &mut Vec<Event>,
+    ) {
+        self.rebuild_navigation_field_if_dirty();
+        let steps = kind.push_back_tiles().saturating_mul(self.cells_per_tile);
+        let center = tower_center_half(region);
+        let range_half = u128::from(kind.range_in_cells(self.cells_per_tile)) * 2;
+        let mut order: Vec<(u16, BugId)> = self
+            .bugs
+            .iter()
+            .filter(|bug| {
+                !bug.health.is_zero() && center.distance_to(bug_center_half(bug.cell)) <= range_half
+            })
+            .map(|bug| {
+                let distance = self.navigation_field.distance(bug.cell);
+                (distance.unwrap_or(u16::MAX), bug.id)
+            })
+            .collect();
+        order.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(&right.1)));
+
+        for (_, bug_id) in order {
+            let Some(index) = self.bug_index(bug_id) else {
+                continue;
+            };
+            let from = self.bugs[index].cell;
+            let mut to = from;
+            for _ in 0..steps {
+                let Some(next) = self.retreat_cell(to) else {
+                    break;
+                };
+                to = next;
+            }
+            if to == from {
+                continue;
+            }
+
+            self.occupancy.vacate(from);
+            self.occupancy.occupy(bug_id, to);
+            let bug = &mut self.bugs[index];
+            bug.advance(to);
+            bug.accum_ms = 0;
+            self.reservations.release(bug_id);
+            out_events.push(Event::BugPushedBack {
+                tower,
+                bug_id,
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Finds the free neighbour one navigation step farther from the exit than `cell`,
+    /// scanning directions in a fixed order.
+    fn retreat_cell(&self, cell: CellCoord) -> Option<CellCoord> {
+        let distance = self.navigation_field.distance(cell)?;
+        let retreat = distance.checked_add(1).filter(|next| *next < u16::MAX)?;
+        let (columns, rows) = self.occupancy.dimensions();
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .filter_map(|direction| advance_cell(cell, direction, columns, rows))
+        .find(|neighbor| {
+            self.navigation_field.distance(*neighbor) == Some(retreat)
+                && self.occupancy.can_enter(*neighbor)
+                && !self.walls.contains(*neighbor)
+                && !self.void_blocks_ground(*neighbor)
+        })
+    }
+
+    /// Soaks `damage` into the wave shield, if one holds, and returns what reaches the bug.
+    fn absorb_with_wave_shield(&mut self, damage: Damage, out_events: &mut Vec<Event>) -> Damage {
+        let Some(shield) = self
+            .wave_shield
+            .as_mut()
+            .filter(|shield| !shield.is_broken())
+        else {
+            return damage;
+        };
+        let passing = shield.absorb(damage.get());
+        if shield.is_broken() {
+            if let Some(context) = self.active_wave.as_ref() {
+                out_events.push(Event::WaveShieldBroken { wave: context.id });
+            }
+        }
+        Damage::new(passing)
+    }
+
     fn resolve_projectile_completion(
         &mut self,
         projectile_id: ProjectileId,
@@ -1108,6 +2716,11 @@ impl World {
     ) {
         let removed = self.projectiles.remove(&projectile_id);
         debug_assert!(removed.is_some());
+        let source = removed.as_ref().map(|projectile| projectile.tower);
+        let effects = removed
+            .as_ref()
+            .map(|projectile| (projectile.damage_type, projectile.inflicts));
+        let aimed_at = removed.map(|projectile| projectile.end);
 
         let Some(index) = self.bug_index(target) else {
             out_events.push(Event::ProjectileExpired {
@@ -1123,16 +2736,69 @@ impl World {
             return;
         }
 
-        let (remaining, death_cell) = {
+        if let Some(cause) =
+            aimed_at.and_then(|end| self.projectile_miss(projectile_id, index, end))
+        {
+            out_events.push(Event::ProjectileMissed {
+                projectile: projectile_id,
+                target,
+                cause,
+            });
+            return;
+        }
+
+        let combo = effects.and_then(|(damage_type, _)| {
+            let (status, _) = self.bugs[index].status?;
+            ComboRule::find(status, damage_type)
+        });
+        let damage = match combo {
+            Some(rule) => {
+                let bonus = rule.bonus(damage);
+                self.bugs[index].status = None;
+                out_events.push(Event::ComboTriggered {
+                    bug: target,
+                    combo: rule.combo,
+                    bonus,
+                });
+                Damage::new(damage.get().saturating_add(bonus.get()))
+            }
+            None => damage,
+        };
+        let damage = if self.bugs[index].invulnerable_ms > 0 {
+            Damage::new(0)
+        } else if self.bugs[index].archetype == BugArchetype::Armored {
+            let reduced = u64::from(damage.get()) * u64::from(ARMORED_DAMAGE_PERCENT) / 100;
+            Damage::new(u32::try_from(reduced).unwrap_or(u32::MAX).max(1))
+        } else {
+            damage
+        };
+        let damage = self.absorb_with_wave_shield(damage, out_events);
+        let split = {
+            let bug = &self.bugs[index];
+            (bug.archetype == BugArchetype::Splitter).then_some((
+                bug.color,
+                Health::new((bug.max_health.get() / SPLITTER_CHILD_HEALTH_DIVISOR).max(1)),
+                bug.step_ms,
+                bug.pathing,
+            ))
+        };
+
+        let (remaining, death_cell, bounty) = {
             let bug = &mut self.bugs[index];
             let updated = bug.health.saturating_sub(damage);
+            let dealt = bug.health.get().saturating_sub(updated.get());
+            self.wave_tally.track_damage(target, dealt);
+            if let Some(tower) = source {
+                let total = self.wave_tally.damage_by_tower.entry(tower).or_insert(0);
+                *total = total.saturating_add(dealt);
+            }
             let death_cell = if updated.is_zero() {
                 Some(bug.cell)
             } else {
                 None
             };
             bug.health = updated;
-            (updated, death_cell)
+            (updated, death_cell, bug.bounty)
         };
 
         out_events.push(Event::BugDamaged {
@@ -1143,12 +2809,35 @@ impl World {
         if let Some(cell) = death_cell {
             self.occupancy.vacate(cell);
             self.remove_bug_at_index(index);
-            let base_reward = Gold::new(1);
             let multiplier = self.reward_multiplier();
-            let scaled_reward = Gold::new(base_reward.get().saturating_mul(multiplier));
+            let scaled_reward = bounty.saturating_mul(u64::from(multiplier));
+            self.wave_tally.gold_earned = self
+                .wave_tally
+                .gold_earned
+                .saturating_add(scaled_reward.get());
             let updated = self.gold.saturating_add(scaled_reward);
             self.update_gold(updated, out_events);
-            out_events.push(Event::BugDied { bug: target });
+            out_events.push(Event::BugDied {
+                bug: target,
+                bounty: scaled_reward,
+            });
+            self.release_priority_target(target, out_events);
+            if let Some((color, health, step_ms, pathing)) = split {
+                self.summon_adds(
+                    cell,
+                    SPLITTER_CHILDREN,
+                    color,
+                    health,
+                    step_ms,
+                    pathing,
+                    out_events,
+                );
+            }
+        } else {
+            if let Some(effect) = effects.and_then(|(_, inflicts)| inflicts) {
+                self.bugs[index].status = Some((effect, effect.duration_ms()));
+            }
+            self.advance_boss_phase(target, out_events);
         }
 
         out_events.push(Event::ProjectileHit {
@@ -1158,11 +2847,40 @@ impl World {
         });
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
+    /// Decides whether a projectile aimed at `aimed_at` misses the bug at `index`.
+    ///
+    /// Without accuracy rules every projectile hits. Otherwise a target that drifted beyond
+    /// the allowance outruns the projectile, and the remaining shots are dodged according to
+    /// a draw keyed by the wave seed, the projectile and the target.
+    fn projectile_miss(
+        &self,
+        projectile: ProjectileId,
+        index: usize,
+        aimed_at: CellPointHalf,
+    ) -> Option<MissCause> {
+        let model = self.accuracy?;
+        let bug = &self.bugs[index];
+        let drift_half = aimed_at.distance_to(bug_center_half(bug.cell));
+        if drift_half > u128::from(model.max_drift_cells()) * 2 {
+            return Some(MissCause::Outran);
+        }
+        let dodge = bug.dodge.per_mille();
+        if dodge == 0 {
+            return None;
+        }
+        let key = self.wave_seed_global
+            ^ DODGE_SALT
+            ^ u64::from(projectile.get())
+            ^ u64::from(bug.id.get()).rotate_left(32);
+        let roll = between_waves::splitmix64(key) % 1_000;
+        (roll < u64::from(dodge)).then_some(MissCause::Dodged)
+    }
+
     fn handle_place_tower(
         &mut self,
         kind: TowerKind,
         origin: CellCoord,
+        rotation: TowerRotation,
         cost_policy: TowerPlacementCost,
         out_events: &mut Vec<Event>,
     ) {
@@ -1203,7 +2921,7 @@ impl World {
             }
         }
 
-        let footprint = footprint_for(kind);
+        let footprint = footprint_for(kind, rotation);
         let region = CellRect::from_origin_and_size(origin, footprint);
 
         if !self.tower_region_within_bounds(region) {
@@ -1215,16 +2933,38 @@ impl World {
             return;
         }
 
-        if self.tower_region_occupied(region) {
+        if self.tower_region_covers_void(region) {
             out_events.push(Event::TowerPlacementRejected {
                 kind,
                 origin,
-                reason: PlacementError::Occupied,
+                reason: PlacementError::Unbuildable,
             });
             return;
         }
 
-        if !self.exit_path_remains_available(region) {
+        if self.tower_region_occupied(region) {
+            out_events.push(Event::TowerPlacementRejected {
+                kind,
+                origin,
+                reason: PlacementError::Occupied,
+            });
+            return;
+        }
+
+        if self
+            .spawn_reservations()
+            .iter()
+            .any(|reservation| reservation.overlaps(region))
+        {
+            out_events.push(Event::TowerPlacementRejected {
+                kind,
+                origin,
+                reason: PlacementError::SpawnReserved,
+            });
+            return;
+        }
+
+        if !self.exit_path_remains_available(region) {
             out_events.push(Event::TowerPlacementRejected {
                 kind,
                 origin,
@@ -1234,7 +2974,13 @@ impl World {
         }
 
         if matches!(cost_policy, TowerPlacementCost::SpendGold) {
-            let cost = kind.build_cost();
+            let build_cost = self.tower_content.stats(kind).build_cost;
+            let cost = match self.merchant_discount {
+                Some(percent) => Gold::new(
+                    build_cost.get() * u64::from(100_u32.saturating_sub(percent.min(100))) / 100,
+                ),
+                None => build_cost,
+            };
             if self.gold.get() < cost.get() {
                 out_events.push(Event::TowerPlacementRejected {
                     kind,
@@ -1246,19 +2992,25 @@ impl World {
 
             let remaining = self.gold.saturating_sub(cost);
             self.update_gold(remaining, out_events);
+            self.merchant_discount = None;
         }
 
         let id = self.towers.allocate();
         self.mark_tower_region(region, true);
-        self.mark_navigation_dirty();
-        self.rebuild_navigation_field_if_dirty();
         self.towers.insert(TowerState {
             id,
             kind,
             region,
+            rotation,
             cooldown_remaining: Duration::ZERO,
+            heat_milli: 0,
+            overheated: false,
+            targeting: TargetingPolicy::default(),
+            upgrades: 0,
         });
         debug_assert!(self.towers.get(id).is_some());
+        self.mark_navigation_dirty();
+        self.rebuild_navigation_field_if_dirty();
         out_events.push(Event::TowerPlaced {
             tower: id,
             kind,
@@ -1267,7 +3019,6 @@ impl World {
         out_events.push(Event::MazeLayoutChanged);
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn handle_remove_tower(&mut self, tower: TowerId, out_events: &mut Vec<Event>) {
         if self.play_mode != PlayMode::Builder {
             out_events.push(Event::TowerRemovalRejected {
@@ -1285,9 +3036,85 @@ impl World {
             return;
         };
 
-        let refund = state.kind.build_cost();
-        let updated = self.gold.saturating_add(refund);
-        self.update_gold(updated, out_events);
+        if !self.sandbox.free_placement {
+            let refund = self
+                .tower_content
+                .stats(state.kind)
+                .invested(state.upgrades);
+            let updated = self.gold.saturating_add(refund);
+            self.update_gold(updated, out_events);
+        }
+
+        self.mark_tower_region(state.region, false);
+        self.mark_navigation_dirty();
+        self.rebuild_navigation_field_if_dirty();
+        out_events.push(Event::TowerRemoved {
+            tower: state.id,
+            region: state.region,
+        });
+        out_events.push(Event::MazeLayoutChanged);
+    }
+
+    /// Switches `tower` to `policy`; unknown towers are ignored.
+    fn set_tower_targeting(
+        &mut self,
+        tower: TowerId,
+        policy: TargetingPolicy,
+        out_events: &mut Vec<Event>,
+    ) {
+        let Some(state) = self.towers.get_mut(tower) else {
+            return;
+        };
+        if state.targeting != policy {
+            state.targeting = policy;
+            out_events.push(Event::TowerTargetingChanged { tower, policy });
+        }
+    }
+
+    /// Buys the next upgrade of `tower`, which multiplies the damage of its projectiles.
+    fn handle_upgrade_tower(&mut self, tower: TowerId, out_events: &mut Vec<Event>) {
+        let reject = |reason, out_events: &mut Vec<Event>| {
+            out_events.push(Event::TowerUpgradeRejected { tower, reason });
+        };
+        if self.play_mode != PlayMode::Builder {
+            return reject(UpgradeError::InvalidMode, out_events);
+        }
+        let Some(state) = self.towers.get(tower) else {
+            return reject(UpgradeError::MissingTower, out_events);
+        };
+        let stats = self.tower_content.stats(state.kind);
+        if stats.projectile_damage.get() == 0 {
+            return reject(UpgradeError::NotUpgradable, out_events);
+        }
+        if state.upgrades >= TowerStats::MAX_UPGRADES {
+            return reject(UpgradeError::FullyUpgraded, out_events);
+        }
+        let upgrades = state.upgrades + 1;
+        let cost = if self.sandbox.free_placement {
+            Gold::new(0)
+        } else {
+            stats.upgrade_cost(upgrades)
+        };
+        if self.gold.get() < cost.get() {
+            return reject(UpgradeError::InsufficientFunds, out_events);
+        }
+
+        let remaining = self.gold.saturating_sub(cost);
+        self.update_gold(remaining, out_events);
+        if let Some(state) = self.towers.get_mut(tower) {
+            state.upgrades = upgrades;
+        }
+        out_events.push(Event::TowerUpgraded {
+            tower,
+            upgrades,
+            cost,
+        });
+    }
+
+    fn destroy_tower_by_meteor(&mut self, tower: TowerId, out_events: &mut Vec<Event>) {
+        let Some(state) = self.towers.remove(tower) else {
+            return;
+        };
 
         self.mark_tower_region(state.region, false);
         self.mark_navigation_dirty();
@@ -1299,7 +3126,6 @@ impl World {
         out_events.push(Event::MazeLayoutChanged);
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn tower_alignment_stride(&self) -> Option<u32> {
         let stride = self.cells_per_tile / 2;
         if stride <= 1 {
@@ -1309,7 +3135,6 @@ impl World {
         }
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn tower_region_within_bounds(&self, region: CellRect) -> bool {
         let (columns, rows) = self.tower_occupancy.dimensions();
         let size = region.size();
@@ -1332,7 +3157,47 @@ impl World {
         end_column <= columns && end_row <= rows
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
+    /// Spawner cells held by bursts of the active wave that are imminent or spawning.
+    ///
+    /// A burst holds the spawners inside its species' patch, or every spawner when none
+    /// lies there, since bugs then fall back to the remaining spawners.
+    fn spawn_reservations(&self) -> Vec<SpawnReservation> {
+        let Some(elapsed) = query::wave_elapsed(self) else {
+            return Vec::new();
+        };
+        let elapsed_ms = u32::try_from(elapsed.as_millis()).unwrap_or(u32::MAX);
+        self.burst_timeline
+            .reserved(elapsed_ms, SPAWN_RESERVATION_LEAD_MS)
+            .map(|window| {
+                let extent = self
+                    .species_definitions
+                    .iter()
+                    .find(|definition| definition.id() == window.species)
+                    .and_then(|definition| {
+                        self.spawn_patches
+                            .iter()
+                            .find(|patch| patch.id() == definition.patch())
+                    })
+                    .map(SpawnPatchDescriptor::extent);
+                let mut cells: Vec<CellCoord> = self
+                    .bug_spawners
+                    .iter()
+                    .filter(|cell| extent.is_some_and(|extent| extent.contains(*cell)))
+                    .collect();
+                if cells.is_empty() {
+                    cells = self.bug_spawners.iter().collect();
+                }
+                let remaining_ms = window.last_ms.saturating_sub(elapsed_ms);
+                SpawnReservation::new(
+                    window.species,
+                    window.burst,
+                    cells,
+                    Duration::from_millis(u64::from(remaining_ms)),
+                )
+            })
+            .collect()
+    }
+
     fn tower_region_occupied(&self, region: CellRect) -> bool {
         let origin = region.origin();
         let size = region.size();
@@ -1354,7 +3219,7 @@ impl World {
                 if self.walls.contains(cell) {
                     return true;
                 }
-                if !self.occupancy.can_enter(cell) {
+                if !self.bridges.contains(cell) && !self.occupancy.can_enter(cell) {
                     return true;
                 }
             }
@@ -1363,7 +3228,34 @@ impl World {
         false
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
+    fn tower_region_covers_void(&self, region: CellRect) -> bool {
+        let origin = region.origin();
+        let size = region.size();
+        (0..size.height()).any(|row_offset| {
+            (0..size.width()).any(|column_offset| {
+                self.void_cells.contains(CellCoord::new(
+                    origin.column().saturating_add(column_offset),
+                    origin.row().saturating_add(row_offset),
+                ))
+            })
+        })
+    }
+
+    /// Reports whether `cell` is void that no bridge deck spans, so ground bugs cannot enter.
+    fn void_blocks_ground(&self, cell: CellCoord) -> bool {
+        self.void_cells.contains(cell) && !self.bridges.contains(cell)
+    }
+
+    /// Reports whether a tower, or the candidate footprint, blocks ground bugs on `cell`.
+    /// Towers standing on a bridge deck leave the tunnel beneath open.
+    fn tower_blocks_ground(&self, cell: CellCoord, candidate: Option<CellRect>) -> bool {
+        if self.bridges.contains(cell) {
+            return false;
+        }
+        candidate.is_some_and(|candidate| cell_rect_contains(candidate, cell))
+            || self.tower_occupancy.contains(cell)
+    }
+
     fn mark_tower_region(&mut self, region: CellRect, occupied: bool) {
         let origin = region.origin();
         let size = region.size();
@@ -1388,7 +3280,6 @@ impl World {
         }
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn exit_path_remains_available(&self, candidate: CellRect) -> bool {
         if self.targets.is_empty() {
             return true;
@@ -1460,9 +3351,8 @@ impl World {
         false
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn is_cell_blocked_with_candidate(&self, cell: CellCoord, candidate: CellRect) -> bool {
-        if cell_rect_contains(candidate, cell) {
+        if self.tower_blocks_ground(cell, Some(candidate)) || self.void_blocks_ground(cell) {
             return true;
         }
 
@@ -1478,22 +3368,46 @@ impl World {
             return true;
         }
 
-        if self.tower_occupancy.contains(cell) {
-            return true;
+        false
+    }
+
+    fn configure_void_cells(&mut self, cells: Vec<CellCoord>, out_events: &mut Vec<Event>) {
+        let (columns, rows) = self.occupancy.dimensions();
+        self.void_cells = BitGrid::new(columns, rows);
+        for cell in cells {
+            if self.occupancy.index(cell).is_some() && !self.walls.contains(cell) {
+                self.void_cells.set(cell);
+            }
         }
+        self.mark_navigation_dirty();
+        self.rebuild_navigation_field_if_dirty();
+        out_events.push(Event::VoidCellsConfigured {
+            cells: self.void_cells.iter().collect(),
+        });
+    }
 
-        false
+    fn configure_bridges(&mut self, cells: Vec<CellCoord>, out_events: &mut Vec<Event>) {
+        let (columns, rows) = self.occupancy.dimensions();
+        self.bridges = BitGrid::new(columns, rows);
+        for cell in cells {
+            if self.occupancy.index(cell).is_some() && !self.walls.contains(cell) {
+                self.bridges.set(cell);
+            }
+        }
+        self.mark_navigation_dirty();
+        self.rebuild_navigation_field_if_dirty();
+        out_events.push(Event::BridgesConfigured {
+            cells: self.bridges.iter().collect(),
+        });
     }
 }
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 #[derive(Clone, Copy)]
 enum TowerPlacementCost {
     SpendGold,
     IgnoreGold,
 }
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 fn cell_rect_contains(region: CellRect, cell: CellCoord) -> bool {
     let origin = region.origin();
     let size = region.size();
@@ -1512,21 +3426,40 @@ fn cell_rect_contains(region: CellRect, cell: CellCoord) -> bool {
 
 /// Query functions that provide read-only access to the world state.
 pub mod query {
-    use super::{analytics, Bug, World};
+    use super::{analytics, raycast::CellTraversal, Bug, WaveShield, World};
     use maze_defence_core::{
-        AnalyticsInputs, AnalyticsLayoutSnapshot, BugSnapshot, BugView, CellCoord, DifficultyLevel,
-        Goal, Gold, LevelId, NavigationFieldView, OccupancyView, PendingWaveDifficulty, PlayMode,
-        PressureConfig, PressureWaveInputs, PressureWavePlan, ProjectileSnapshot,
-        ReservationLedgerView, SpawnPatchTableView, SpeciesTableView, StatsReport, Target,
-        TileGrid, TowerAnalyticsView, WaveSeedContext,
+        AccuracyModel, AnalyticsInputs, AnalyticsLayoutSnapshot, BugArchetype, BugEffect, BugId,
+        BugSnapshot, BugStatus, BugView, CellCoord, CellPointHalf, CellRect, DangerHeatmap,
+        DifficultyLadder, DifficultyLevel, ExitBehavior, GameRules, Goal, Gold, Health, LevelId,
+        NavigationFieldView, NestSnapshot, OccupancyView, PendingWaveDifficulty, PlayMode,
+        PressureConfig, PressureWaveInputs, PressureWavePlan, ProjectileSnapshot, RaycastHit,
+        ReservationLedgerView, SandboxRules, SimClock, SpawnPatchTableView, SpawnReservation,
+        SpeciesTableView, StatsReport, Target, TileGrid, TowerAnalyticsView, TowerContent,
+        TowerCooldownSnapshot, TowerCooldownView, TowerId, TowerRotation, TowerSnapshot, TowerView,
+        WaveContract, WaveDifficulty, WaveSeedContext, WaveShieldSnapshot, WorldDebugStats,
     };
 
     use maze_defence_core::structures::{Wall as CellWall, WallView as CellWallView};
+    use std::time::Duration;
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
-    use maze_defence_core::{
-        CellRect, TowerCooldownSnapshot, TowerCooldownView, TowerId, TowerSnapshot, TowerView,
-    };
+    use super::navigation::NavigationField;
+
+    /// Captures internal counters for developer overlays without exposing world state.
+    #[must_use]
+    pub fn debug_stats(world: &World) -> WorldDebugStats {
+        let count = |len: usize| u32::try_from(len).unwrap_or(u32::MAX);
+        let towers = count(world.towers.len());
+        WorldDebugStats {
+            tick_index: world.clock.ticks(),
+            bugs: count(world.bugs.len()),
+            projectiles: count(world.projectiles.len()),
+            towers,
+            navigation_generation: world.navigation_generation,
+            wave_cache_entries: count(world.pressure_wave_cache.len()),
+            bug_capacity: count(world.bugs.capacity()),
+            wave_cache_capacity: count(world.pressure_wave_cache.capacity()),
+        }
+    }
 
     /// Reports the active play mode for the world.
     #[must_use]
@@ -1534,15 +3467,235 @@ pub mod query {
         world.play_mode
     }
 
+    /// Reports the in-game time accumulated by ticks since the world was created.
+    #[must_use]
+    pub fn elapsed(world: &World) -> Duration {
+        world.clock.elapsed()
+    }
+
+    /// Reports the world's simulation clock.
+    #[must_use]
+    pub fn clock(world: &World) -> SimClock {
+        world.clock
+    }
+
+    /// Reports how long the active wave has been running, if a wave is in progress.
+    #[must_use]
+    pub fn wave_elapsed(world: &World) -> Option<Duration> {
+        world
+            .active_wave
+            .map(|wave| world.clock.elapsed().saturating_sub(wave.started_at))
+    }
+
+    /// Reports the spawner cells reserved for imminent or spawning bursts of the active
+    /// wave; towers cannot be built over them.
+    #[must_use]
+    pub fn spawn_reservations(world: &World) -> Vec<SpawnReservation> {
+        world.spawn_reservations()
+    }
+
+    /// Reports the time left before the next wave launches automatically, if a countdown
+    /// is running.
+    #[must_use]
+    pub fn next_wave_countdown(world: &World) -> Option<Duration> {
+        world.next_wave_countdown
+    }
+
+    /// Reports the building time left before Builder mode ends automatically, if building
+    /// time is limited.
+    #[must_use]
+    pub fn builder_time_left(world: &World) -> Option<Duration> {
+        world.builder_time_left
+    }
+
+    /// Reports how much longer banked preparation time keeps tower cooldowns recovering
+    /// at double speed.
+    #[must_use]
+    pub fn haste_remaining(world: &World) -> Duration {
+        world.haste_remaining
+    }
+
+    /// Reports the gold awarded for skipping the running countdown right now, if any.
+    #[must_use]
+    pub fn early_start_bonus(world: &World) -> Option<Gold> {
+        world.next_wave_countdown.map(super::early_start_bonus)
+    }
+
+    /// Normalised danger heatmap built from bug traffic and leak routes across all waves.
+    #[must_use]
+    pub fn danger_heatmap(world: &World) -> DangerHeatmap {
+        world.traffic_heatmap.snapshot()
+    }
+
+    /// Reports the discount percentage of the open merchant offer, if one awaits an answer.
+    #[must_use]
+    pub fn merchant_offer(world: &World) -> Option<u32> {
+        world.merchant_offer
+    }
+
+    /// Reports the accepted merchant discount waiting to be applied to the next tower, if any.
+    #[must_use]
+    pub fn merchant_discount(world: &World) -> Option<u32> {
+        world.merchant_discount
+    }
+
+    /// Reports whether the next wave will pay double rewards because of a gold rush.
+    #[must_use]
+    pub fn gold_rush_pending(world: &World) -> bool {
+        world.gold_rush_pending
+    }
+
+    /// Reports the contracts accepted for the next wave launch, in ascending order.
+    #[must_use]
+    pub fn accepted_contracts(world: &World) -> &[WaveContract] {
+        &world.accepted_contracts
+    }
+
+    /// Reports the rotation applied to subsequent tower placements.
+    #[must_use]
+    pub fn placement_rotation(world: &World) -> TowerRotation {
+        world.placement_rotation
+    }
+
+    /// Casts a segment between two half-cell points and reports the first wall or tower
+    /// cell it enters.
+    ///
+    /// Cells are visited with an exact integer DDA in travel order; a segment passing
+    /// through a cell corner crosses the column boundary first, and cells only touched by
+    /// the end point are not entered. A segment starting inside a tower ignores that
+    /// tower, so casts from a tower centre measure line of sight to whatever lies beyond
+    /// its footprint. Bridges do not hide the towers standing on them, and void cells never
+    /// stop a cast.
+    #[must_use]
+    pub fn raycast(world: &World, from_half: CellPointHalf, to_half: CellPointHalf) -> RaycastHit {
+        let origin_tower = CellTraversal::new(from_half, to_half)
+            .next()
+            .and_then(|(column, row)| grid_cell(column, row))
+            .and_then(|cell| tower_at(world, cell));
+
+        for (column, row) in CellTraversal::new(from_half, to_half) {
+            let Some(cell) =
+                grid_cell(column, row).filter(|cell| world.occupancy.index(*cell).is_some())
+            else {
+                return RaycastHit::OutOfBounds;
+            };
+            if world.walls.contains(cell) {
+                return RaycastHit::Wall { cell };
+            }
+            if let Some(tower) = tower_at(world, cell).filter(|tower| Some(*tower) != origin_tower)
+            {
+                return RaycastHit::Tower { tower, cell };
+            }
+        }
+        RaycastHit::Clear
+    }
+
+    fn grid_cell(column: i64, row: i64) -> Option<CellCoord> {
+        Some(CellCoord::new(
+            u32::try_from(column).ok()?,
+            u32::try_from(row).ok()?,
+        ))
+    }
+
+    /// Reports whether firing currently builds tower heat.
+    #[must_use]
+    pub fn tower_heat_enabled(world: &World) -> bool {
+        world.tower_heat
+    }
+
+    /// Reports whether walls and other towers block the shots of kinds that require line
+    /// of sight.
+    #[must_use]
+    pub fn line_of_sight_enabled(world: &World) -> bool {
+        world.line_of_sight
+    }
+
+    /// Reports what happens when a bug reaches the exit.
+    #[must_use]
+    pub fn exit_behavior(world: &World) -> ExitBehavior {
+        world.exit_behavior
+    }
+
+    /// Reports the starting bonuses the current game was configured with.
+    #[must_use]
+    pub fn game_rules(world: &World) -> GameRules {
+        world.game_rules
+    }
+
+    /// Reports how many more bugs may reach the exit before the round is lost.
+    #[must_use]
+    pub fn base_health(world: &World) -> u32 {
+        world.base_health
+    }
+
+    /// Reports the projectile accuracy rules in effect, if any.
+    #[must_use]
+    pub fn accuracy_model(world: &World) -> Option<AccuracyModel> {
+        world.accuracy
+    }
+
+    /// Reports the per-kind tower statistics currently in effect.
+    #[must_use]
+    pub fn tower_content(world: &World) -> TowerContent {
+        world.tower_content
+    }
+
+    /// Reports the sandbox rule toggles currently in effect.
+    #[must_use]
+    pub fn sandbox_rules(world: &World) -> SandboxRules {
+        world.sandbox
+    }
+
+    /// Reports whether any sandbox toggle has been enabled during this session.
+    ///
+    /// The flag stays set after the toggles are disabled again so results recorded while
+    /// experimenting are never mistaken for regular play.
+    #[must_use]
+    pub fn is_sandbox_session(world: &World) -> bool {
+        world.sandbox_session
+    }
+
+    /// Reports the effective difficulty a launch with `difficulty` would use, including
+    /// the Hard escalation and any accepted contracts.
+    #[must_use]
+    pub fn effective_difficulty(world: &World, difficulty: WaveDifficulty) -> DifficultyLadder {
+        world.effective_difficulty(difficulty)
+    }
+
+    /// Reports the gold reward multiplier a launch with `difficulty` would use, including
+    /// the bonus of any accepted contracts.
+    #[must_use]
+    pub fn reward_multiplier(world: &World, difficulty: WaveDifficulty) -> u32 {
+        world.launch_reward_multiplier(difficulty)
+    }
+
+    /// Reports the multiplier applied to kill bounties right now.
+    #[must_use]
+    pub fn kill_reward_multiplier(world: &World) -> u32 {
+        world.reward_multiplier()
+    }
+
+    /// Reports the bug the player flagged as the priority target, if any.
+    #[must_use]
+    pub fn priority_target(world: &World) -> Option<BugId> {
+        world.priority_target
+    }
+
     /// Reports the amount of gold owned by the defender.
     #[must_use]
     pub fn gold(world: &World) -> Gold {
         world.gold
     }
 
-    /// Reports the current difficulty level tracked by the world.
+    /// Reports the current difficulty level tracked by the world, rounded down to a whole level.
     #[must_use]
     pub fn difficulty_level(world: &World) -> DifficultyLevel {
+        world.difficulty_level.level()
+    }
+
+    /// Reports the world's difficulty ladder, including any fractional progress.
+    #[must_use]
+    pub fn difficulty_ladder(world: &World) -> DifficultyLadder {
         world.difficulty_level
     }
 
@@ -1591,8 +3744,47 @@ pub mod query {
         WaveSeedContext::new(
             world.wave_seed_global,
             world.next_wave_id,
-            world.difficulty_level,
+            world.difficulty_level.level(),
         )
+        .with_effective_levels(
+            world.effective_difficulty(WaveDifficulty::Normal).level(),
+            world.effective_difficulty(WaveDifficulty::Hard).level(),
+        )
+        .with_pressure_share(world.pressure_share())
+        .with_history(world.wave_history)
+    }
+
+    /// Shield shared by the bugs of the active wave, if it was launched shielded.
+    #[must_use]
+    pub fn wave_shield(world: &World) -> Option<WaveShieldSnapshot> {
+        world.wave_shield.as_ref().map(WaveShield::snapshot)
+    }
+
+    /// Health every nest starts with, or `None` when spawners are indestructible.
+    #[must_use]
+    pub fn nest_health(world: &World) -> Option<Health> {
+        world.nest_health
+    }
+
+    /// Open nests in the order their spawner sets were opened.
+    #[must_use]
+    pub fn nests(world: &World) -> Vec<NestSnapshot> {
+        let Some(max_health) = world.nest_health else {
+            return Vec::new();
+        };
+        world
+            .nests
+            .iter()
+            .map(|nest| NestSnapshot {
+                nest: nest.set,
+                cells: world
+                    .resolve_spawner_cells(&[nest.set])
+                    .map(|cells| cells.into_iter().collect())
+                    .unwrap_or_default(),
+                health: nest.health,
+                max_health,
+            })
+            .collect()
     }
 
     /// Retrieves the welcome banner that adapters may display to players.
@@ -1646,6 +3838,30 @@ pub mod query {
         select_goal(origin, &world.targets)
     }
 
+    /// Lasting effects on the bug, status first, then its archetype and the wave shield;
+    /// empty when the bug does not exist.
+    #[must_use]
+    pub fn bug_effects(world: &World, bug: BugId) -> Vec<BugEffect> {
+        let Some(index) = world.bug_index(bug) else {
+            return Vec::new();
+        };
+        let bug = &world.bugs[index];
+        let mut effects = Vec::new();
+        if let Some((effect, remaining_ms)) = bug.status {
+            effects.push(BugEffect::Status(BugStatus {
+                effect,
+                remaining_ms,
+            }));
+        }
+        if bug.archetype != BugArchetype::Basic {
+            effects.push(BugEffect::Archetype(bug.archetype));
+        }
+        if let Some(shield) = wave_shield(world).filter(|shield| !shield.is_broken()) {
+            effects.push(BugEffect::Shielded(shield));
+        }
+        effects
+    }
+
     /// Captures a read-only view of the bugs inhabiting the maze.
     #[must_use]
     pub fn bug_view(world: &World) -> BugView {
@@ -1670,6 +3886,13 @@ pub mod query {
             step_ms: bug.step_ms,
             accum_ms: bug.accum_ms,
             ready_for_step,
+            pathing: bug.pathing,
+            bounty: bug.bounty,
+            boss_phase: bug.boss_phase,
+            status: bug.status.map(|(effect, remaining_ms)| BugStatus {
+                effect,
+                remaining_ms,
+            }),
         }
     }
 
@@ -1694,6 +3917,46 @@ pub mod query {
         )
     }
 
+    /// Provides the navigation distances followed by flanker bugs.
+    ///
+    /// Cells inside tower coverage cost [`FLANKER_COVERAGE_PENALTY`](maze_defence_core::FLANKER_COVERAGE_PENALTY) extra steps per
+    /// covering tower, so descending this field favours longer but less-defended routes.
+    #[must_use]
+    pub fn flanker_navigation_field(world: &World) -> NavigationFieldView<'_> {
+        debug_assert!(
+            !world.navigation_dirty,
+            "navigation field must be rebuilt before queries"
+        );
+
+        NavigationFieldView::from_slice(
+            world.flanker_navigation_field.cells(),
+            world.flanker_navigation_field.width(),
+            world.flanker_navigation_field.height(),
+        )
+    }
+
+    /// Computes the route bugs would follow if `candidate` were occupied by a tower.
+    ///
+    /// The navigation field is rebuilt into a scratch buffer so the world itself is left
+    /// untouched. The shortest route across all spawners is returned, ordered from the
+    /// spawner to the exit, or `None` when the candidate would cut every spawner off.
+    #[must_use]
+    pub fn path_preview(world: &World, candidate: CellRect) -> Option<Vec<CellCoord>> {
+        let (columns, rows) = world.occupancy.dimensions();
+        let mut field = NavigationField::default();
+        field.rebuild_with(columns, rows, &world.targets, |cell| {
+            world.walls.contains(cell)
+                || world.void_blocks_ground(cell)
+                || world.tower_blocks_ground(cell, Some(candidate))
+        });
+
+        world
+            .bug_spawners
+            .iter()
+            .filter_map(|spawner| field.descend_from(spawner))
+            .min_by_key(Vec::len)
+    }
+
     /// Exposes a read-only view of the dense occupancy grid.
     #[must_use]
     pub fn occupancy_view(world: &World) -> OccupancyView<'_> {
@@ -1743,7 +4006,6 @@ pub mod query {
     }
 
     /// Captures a read-only snapshot of all towers stored in the world.
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     #[must_use]
     pub fn towers(world: &World) -> TowerView {
         if world.towers.is_empty() {
@@ -1757,6 +4019,9 @@ pub mod query {
                 id: tower.id,
                 kind: tower.kind,
                 region: tower.region,
+                rotation: tower.rotation,
+                targeting: tower.targeting,
+                upgrades: tower.upgrades,
             })
             .collect();
         TowerView::from_snapshots(snapshots)
@@ -1769,20 +4034,30 @@ pub mod query {
             return true;
         }
 
-        if world.walls.contains(cell) {
+        if world.walls.contains(cell) || world.void_blocks_ground(cell) {
             return true;
         }
 
-        #[cfg(any(test, feature = "tower_scaffolding"))]
-        if world.tower_occupancy.contains(cell) {
+        if world.tower_blocks_ground(cell, None) {
             return true;
         }
 
         false
     }
 
+    /// Lists the cells covered by a bridge deck in row-major order.
+    #[must_use]
+    pub fn bridge_cells(world: &World) -> Vec<CellCoord> {
+        world.bridges.iter().collect()
+    }
+
+    /// Lists the void cells in row-major order.
+    #[must_use]
+    pub fn void_cells(world: &World) -> Vec<CellCoord> {
+        world.void_cells.iter().collect()
+    }
+
     /// Identifies the tower occupying the provided cell, if any.
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     #[must_use]
     pub fn tower_at(world: &World, cell: CellCoord) -> Option<TowerId> {
         if !world.tower_occupancy.contains(cell) {
@@ -1797,7 +4072,6 @@ pub mod query {
     }
 
     /// Captures a read-only snapshot of tower cooldown progress.
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     #[must_use]
     pub fn tower_cooldowns(world: &World) -> TowerCooldownView {
         let snapshots: Vec<TowerCooldownSnapshot> = world
@@ -1806,7 +4080,12 @@ pub mod query {
             .map(|tower| TowerCooldownSnapshot {
                 tower: tower.id,
                 kind: tower.kind,
-                ready_in: tower.cooldown_remaining,
+                ready_in: if world.tower_heat {
+                    tower.cooldown_remaining.max(tower.stall_remaining())
+                } else {
+                    tower.cooldown_remaining
+                },
+                heat: world.tower_heat.then(|| tower.heat()),
             })
             .collect();
         TowerCooldownView::from_snapshots(snapshots)
@@ -1841,13 +4120,11 @@ pub mod query {
             })
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn tower_region_contains_cell(region: CellRect, cell: CellCoord) -> bool {
         super::cell_rect_contains(region, cell)
     }
 }
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 fn tower_center_half(region: CellRect) -> CellPointHalf {
     let origin = region.origin();
     let size = region.size();
@@ -1857,7 +4134,6 @@ fn tower_center_half(region: CellRect) -> CellPointHalf {
     )
 }
 
-#[cfg(any(test, feature = "tower_scaffolding"))]
 fn bug_center_half(cell: CellCoord) -> CellPointHalf {
     CellPointHalf::new(
         i64::from(cell.column()) * 2 + 1,
@@ -1865,7 +4141,6 @@ fn bug_center_half(cell: CellCoord) -> CellPointHalf {
     )
 }
 
-#[cfg_attr(not(any(test, feature = "tower_scaffolding")), allow(dead_code))]
 fn compute_projectile_travel_time(
     distance_half: u128,
     max_range_half: u128,
@@ -1892,6 +4167,8 @@ struct ProjectileState {
     travelled_half: u128,
     travel_time_ms: u128,
     elapsed_ms: u128,
+    damage_type: DamageType,
+    inflicts: Option<StatusEffect>,
     damage: Damage,
 }
 
@@ -1904,10 +4181,31 @@ struct Bug {
     health: Health,
     step_ms: u32,
     accum_ms: u32,
+    route: Vec<CellCoord>,
+    pathing: PathingPersonality,
+    bounty: Gold,
+    boss_phase: Option<BossPhase>,
+    invulnerable_ms: u32,
+    dodge: DodgeChance,
+    archetype: BugArchetype,
+    regeneration_ms: u32,
+    /// Status effect the bug suffers with the milliseconds it has left, if any.
+    status: Option<(StatusEffect, u32)>,
+    /// Hundredths of a millisecond of slowed step time not yet credited, so short ticks
+    /// add up to the same progress as one long tick.
+    slow_remainder: u32,
 }
 
 impl Bug {
-    fn new(id: BugId, cell: CellCoord, color: BugColor, health: Health, step_ms: u32) -> Self {
+    fn new(
+        id: BugId,
+        cell: CellCoord,
+        color: BugColor,
+        health: Health,
+        step_ms: u32,
+        pathing: PathingPersonality,
+        bounty: Gold,
+    ) -> Self {
         Self {
             id,
             cell,
@@ -1916,6 +4214,16 @@ impl Bug {
             health,
             step_ms,
             accum_ms: step_ms,
+            route: vec![cell],
+            pathing,
+            bounty,
+            boss_phase: None,
+            invulnerable_ms: 0,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
+            regeneration_ms: 0,
+            status: None,
+            slow_remainder: 0,
         }
     }
 
@@ -1923,8 +4231,26 @@ impl Bug {
         self.health
     }
 
-    fn max_health(&self) -> Health {
-        self.max_health
+    /// Heals one point of health for every full regeneration interval accumulated, up to
+    /// the bug's maximum health.
+    fn regenerate(&mut self, dt_millis: u32) {
+        if self.health.get() >= self.max_health.get() || self.health.is_zero() {
+            self.regeneration_ms = 0;
+            return;
+        }
+        self.regeneration_ms = self.regeneration_ms.saturating_add(dt_millis);
+        let healed = self.regeneration_ms / REGENERATION_INTERVAL_MS;
+        self.regeneration_ms %= REGENERATION_INTERVAL_MS;
+        self.health = Health::new(
+            self.health
+                .get()
+                .saturating_add(healed)
+                .min(self.max_health.get()),
+        );
+    }
+
+    fn max_health(&self) -> Health {
+        self.max_health
     }
 
     #[allow(dead_code)]
@@ -1934,11 +4260,76 @@ impl Bug {
 
     fn advance(&mut self, destination: CellCoord) {
         self.cell = destination;
+        self.route.push(destination);
     }
 
     fn ready_for_step(&self) -> bool {
         self.accum_ms >= self.step_ms
     }
+
+    /// Step time gained over `dt_millis`, wearing off the status effect as it passes.
+    fn step_progress(&mut self, dt_millis: u32) -> u32 {
+        let Some((effect, remaining)) = self.status else {
+            return dt_millis;
+        };
+        let affected = dt_millis.min(remaining);
+        self.status = (remaining > dt_millis).then(|| (effect, remaining - dt_millis));
+        match effect {
+            StatusEffect::Slowed => {
+                let scaled = self
+                    .slow_remainder
+                    .saturating_add(affected.saturating_mul(StatusEffect::SLOWED_SPEED_PERCENT));
+                self.slow_remainder = scaled % 100;
+                scaled / 100 + (dt_millis - affected)
+            }
+        }
+    }
+}
+
+/// Whether `command` may read or change tower cooldowns, heat or projectiles, and so has to
+/// see the time batched ticks deferred.
+///
+/// Bug movement and spawning never touch them, which keeps fast-forwarded waves batching
+/// between shots.
+fn settles_deferred_integration(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::BatchedTick { .. }
+            | Command::StepBug { .. }
+            | Command::SpawnBug { .. }
+            | Command::SpawnFormation { .. }
+    )
+}
+
+/// Time owed to tower cooldowns, heat and projectiles by batched ticks.
+#[derive(Clone, Copy, Debug, Default)]
+struct DeferredIntegration {
+    /// Cooldown recovery owed to every tower, including haste.
+    recovery: Duration,
+    /// Whole milliseconds owed to heat dissipation and projectile flight.
+    millis: u128,
+    /// Cached point at which the owed time completes something, if computed.
+    deadline: Option<IntegrationDeadline>,
+}
+
+impl DeferredIntegration {
+    fn is_pending(&self) -> bool {
+        !self.recovery.is_zero() || self.millis > 0
+    }
+}
+
+/// Smallest owed time at which a cooldown, stall or projectile completes.
+#[derive(Clone, Copy, Debug)]
+struct IntegrationDeadline {
+    recovery: Duration,
+    millis: u128,
+}
+
+/// Destructible nest guarding a spawner set.
+#[derive(Clone, Copy, Debug)]
+struct Nest {
+    set: SpawnerSet,
+    health: Health,
 }
 
 #[derive(Clone, Debug)]
@@ -1953,51 +4344,12 @@ impl BugSpawnerRegistry {
         }
     }
 
-    fn assign_outer_rim(&mut self, columns: u32, rows: u32) {
-        self.cells.clear();
-
-        if columns == 0 || rows == 0 {
-            return;
-        }
-
-        let last_column = columns.saturating_sub(1);
-        let last_row = rows.saturating_sub(1);
-
-        for column in 0..columns {
-            let _ = self.cells.insert(CellCoord::new(column, 0));
-            let _ = self.cells.insert(CellCoord::new(column, last_row));
-        }
-
-        for row in 0..rows {
-            let _ = self.cells.insert(CellCoord::new(0, row));
-            let _ = self.cells.insert(CellCoord::new(last_column, row));
-        }
+    fn assign(&mut self, cells: BTreeSet<CellCoord>) {
+        self.cells = cells;
     }
 
-    fn remove_bottom_row(&mut self, columns: u32, rows: u32) {
-        if columns == 0 || rows == 0 {
-            return;
-        }
-
-        for exit_offset in 0..EXIT_CELL_LAYERS {
-            let Some(row) = rows.checked_sub(exit_offset + 1) else {
-                break;
-            };
-
-            for column in 0..columns {
-                let _ = self.cells.remove(&CellCoord::new(column, row));
-            }
-        }
-
-        for border_offset in 0..BOTTOM_BORDER_CELL_LAYERS {
-            let Some(row) = rows.checked_sub(EXIT_CELL_LAYERS + border_offset + 1) else {
-                break;
-            };
-
-            for column in 0..columns {
-                let _ = self.cells.remove(&CellCoord::new(column, row));
-            }
-        }
+    fn extend(&mut self, cells: BTreeSet<CellCoord>) {
+        self.cells.extend(cells);
     }
 
     fn contains(&self, cell: CellCoord) -> bool {
@@ -2050,6 +4402,10 @@ impl ReservationFrame {
     fn drain_sorted(&mut self) -> Vec<ReservationClaim> {
         self.claims.drain(..).collect()
     }
+
+    fn release(&mut self, bug_id: BugId) {
+        self.claims.retain(|claim| claim.bug_id() != bug_id);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -2190,7 +4546,6 @@ impl BitGrid {
         }
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn clear(&mut self, cell: CellCoord) {
         if let Some((index, bit_offset)) = self.bit_position(cell) {
             if let Some(word) = self.words.get_mut(index) {
@@ -2199,11 +4554,16 @@ impl BitGrid {
         }
     }
 
-    #[cfg(any(test, feature = "tower_scaffolding"))]
     fn dimensions(&self) -> (u32, u32) {
         (self.columns, self.rows)
     }
 
+    fn iter(&self) -> impl Iterator<Item = CellCoord> + '_ {
+        (0..self.rows)
+            .flat_map(move |row| (0..self.columns).map(move |column| CellCoord::new(column, row)))
+            .filter(move |cell| self.contains(*cell))
+    }
+
     fn bit_position(&self, cell: CellCoord) -> Option<(usize, u32)> {
         if cell.column() >= self.columns || cell.row() >= self.rows {
             return None;
@@ -2227,6 +4587,36 @@ fn interior_cell_rows(rows: TileCoord, cells_per_tile: u32) -> u32 {
     rows.get().saturating_mul(cells_per_tile)
 }
 
+/// Rejects grids that would be empty, unmeasurable or too large to allocate.
+fn validate_tile_grid(
+    columns: TileCoord,
+    rows: TileCoord,
+    tile_length: f32,
+    cells_per_tile: u32,
+) -> Result<(), TileGridConfigError> {
+    if columns.get() == 0 {
+        return Err(TileGridConfigError::ZeroColumns);
+    }
+    if rows.get() == 0 {
+        return Err(TileGridConfigError::ZeroRows);
+    }
+    if !tile_length.is_finite() || tile_length <= 0.0 {
+        return Err(TileGridConfigError::InvalidTileLength);
+    }
+    if cells_per_tile == 0 {
+        return Err(TileGridConfigError::ZeroCellsPerTile);
+    }
+    if cells_per_tile > MAX_CELLS_PER_TILE {
+        return Err(TileGridConfigError::CellsPerTileTooLarge(cells_per_tile));
+    }
+    let cells = u64::from(total_cell_columns(columns, cells_per_tile))
+        * u64::from(total_cell_rows(rows, cells_per_tile));
+    if cells > MAX_GRID_CELLS {
+        return Err(TileGridConfigError::TooManyCells(cells));
+    }
+    Ok(())
+}
+
 fn total_cell_columns(columns: TileCoord, cells_per_tile: u32) -> u32 {
     let interior = interior_cell_columns(columns, cells_per_tile);
     if interior == 0 {
@@ -2248,6 +4638,18 @@ fn total_cell_rows(rows: TileCoord, cells_per_tile: u32) -> u32 {
     }
 }
 
+fn early_start_bonus(remaining: Duration) -> Gold {
+    Gold::new(
+        remaining
+            .as_secs()
+            .saturating_mul(EARLY_START_GOLD_PER_SECOND),
+    )
+}
+
+fn duration_to_millis(duration: Duration) -> u32 {
+    u32::try_from(duration.as_millis()).unwrap_or(u32::MAX)
+}
+
 fn exit_row_for_tile_grid(rows: TileCoord, cells_per_tile: u32) -> u32 {
     total_cell_rows(rows, cells_per_tile).saturating_sub(1)
 }
@@ -2258,11 +4660,9 @@ fn exit_columns_for_tile_grid(columns: TileCoord, cells_per_tile: u32) -> Vec<u3
         return Vec::new();
     }
 
-    let center_tile = if tile_columns % 2 == 0 {
-        tile_columns.saturating_sub(1) / 2
-    } else {
-        tile_columns / 2
-    };
+    // Odd widths centre the opening exactly; even widths have no centre tile, so the
+    // opening takes the tile just left of centre and stays aligned with the tile grid.
+    let center_tile = (tile_columns - 1) / 2;
     let left_margin = SIDE_BORDER_CELL_LAYERS;
     let start_column = left_margin.saturating_add(center_tile.saturating_mul(cells_per_tile));
 
@@ -2289,6 +4689,39 @@ fn walkway_row_for_tile_grid(rows: TileCoord, cells_per_tile: u32) -> Option<u32
     wall_row.checked_sub(1)
 }
 
+/// Resolves a formation slot relative to an anchor on the spawner rim.
+///
+/// The top row takes precedence for corner anchors. Returns `None` for slots that fall
+/// outside the grid.
+fn formation_slot_cell(
+    anchor: CellCoord,
+    columns: u32,
+    lateral: i32,
+    depth: u32,
+) -> Option<CellCoord> {
+    let offset = |base: u32, delta: i64| u32::try_from(i64::from(base) + delta).ok();
+    let depth = i64::from(depth);
+    let lateral = i64::from(lateral);
+    if anchor.row() == 0 {
+        Some(CellCoord::new(
+            offset(anchor.column(), lateral)?,
+            offset(anchor.row(), depth)?,
+        ))
+    } else if anchor.column() == 0 {
+        Some(CellCoord::new(
+            offset(anchor.column(), depth)?,
+            offset(anchor.row(), lateral)?,
+        ))
+    } else if anchor.column().saturating_add(1) == columns {
+        Some(CellCoord::new(
+            offset(anchor.column(), -depth)?,
+            offset(anchor.row(), lateral)?,
+        ))
+    } else {
+        None
+    }
+}
+
 fn build_cell_walls(columns: TileCoord, rows: TileCoord, cells_per_tile: u32) -> Vec<CellWall> {
     let total_columns = total_cell_columns(columns, cells_per_tile);
     let Some(visible_wall_row) = visible_wall_row_for_tile_grid(rows, cells_per_tile) else {
@@ -2373,9 +4806,11 @@ fn advance_cell(
 mod tests {
     use super::*;
     use maze_defence_core::{
-        BugColor, CellCoord, DifficultyLevel, Health, LevelId, PlayMode, PressureSpawnRecord,
-        PressureWaveInputs, PressureWavePlan, SpeciesPrototype, TileCoord, TowerKind,
-        WaveDifficulty, WaveId,
+        BugColor, BugEffect, BugStatus, CellCoord, CellRectSize, Combo, DangerHeatmap,
+        DifficultyLevel, FormationId, FormationShape, Health, LevelId, PlayMode,
+        PressureSpawnRecord, PressureWaveInputs, PressureWavePlan, RaycastHit, SpawnerActivation,
+        SpeciesPrototype, TileCoord, TowerHeat, TowerKind, TowerStats, WaveDifficulty, WaveId,
+        WaveShieldSnapshot,
     };
     use std::num::NonZeroU32;
 
@@ -2402,7 +4837,7 @@ mod tests {
         else {
             panic!("expected pressure wave ready event");
         };
-        assert_eq!(ready_inputs, &inputs);
+        assert_eq!(ready_inputs.as_ref(), &inputs);
         assert!(
             !plan.spawns().is_empty(),
             "generated plan should contain spawns"
@@ -2414,7 +4849,7 @@ mod tests {
 
         let cached =
             query::pressure_wave_plan(&world, &inputs).expect("world should cache generated plan");
-        assert_eq!(cached, plan);
+        assert_eq!(cached, plan.as_ref());
     }
 
     #[test]
@@ -2449,12 +4884,17 @@ mod tests {
         else {
             panic!("expected pressure wave ready event");
         };
-        assert_eq!(ready_inputs, &inputs);
-        assert_eq!(cached_plan, &plan);
+        assert_eq!(ready_inputs.as_ref(), &inputs);
+        let paced = plan.clone().with_paced();
+        assert_eq!(
+            cached_plan.as_ref(),
+            &paced,
+            "a single spawn keeps its time"
+        );
 
         let cached =
             query::pressure_wave_plan(&world, &inputs).expect("world should cache supplied plan");
-        assert_eq!(cached, &plan);
+        assert_eq!(cached, &paced);
 
         let table = query::species_table(&world);
         assert!(table.version().get() > initial_version.get());
@@ -2465,6 +4905,194 @@ mod tests {
         assert_eq!(first.prototype(), tinted);
     }
 
+    #[test]
+    fn cached_plans_are_paced_like_generated_ones() {
+        let inputs =
+            PressureWaveInputs::new(11, LevelId::new(1), WaveId::new(4), DifficultyLevel::new(6));
+        let ready_plan = |command: Command| {
+            let mut world = World::new();
+            let mut events = Vec::new();
+            apply(&mut world, command, &mut events);
+            match events.pop() {
+                Some(Event::PressureWaveReady { plan, .. }) => *plan,
+                other => panic!("expected pressure wave ready event, got {other:?}"),
+            }
+        };
+
+        let generated = ready_plan(Command::GeneratePressureWave {
+            inputs: inputs.clone(),
+        });
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        PressureV2::default().generate(&inputs, &mut spawns, &mut prototypes);
+        let unpaced = PressureWavePlan::new(spawns, prototypes);
+        assert_ne!(
+            unpaced.spawns(),
+            generated.spawns(),
+            "pacing re-times spawns"
+        );
+
+        let cached = ready_plan(Command::CachePressureWave {
+            inputs: inputs.clone(),
+            plan: unpaced,
+        });
+        assert_eq!(cached, generated);
+        assert!(cached.is_paced());
+        let replayed = ready_plan(Command::CachePressureWave {
+            inputs,
+            plan: cached.clone(),
+        });
+        assert_eq!(replayed, cached);
+    }
+
+    #[test]
+    fn generated_plans_keep_the_generators_own_bursts() {
+        let inputs =
+            PressureWaveInputs::new(11, LevelId::new(1), WaveId::new(4), DifficultyLevel::new(6));
+        let mut world = World::new();
+        world.pressure_v2.tuning_mut().bursts.enabled = true;
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::GeneratePressureWave {
+                inputs: inputs.clone(),
+            },
+            &mut events,
+        );
+        let Some(Event::PressureWaveReady { plan, .. }) = events.pop() else {
+            panic!("expected pressure wave ready event");
+        };
+
+        let mut generator = PressureV2::default();
+        generator.tuning_mut().bursts.enabled = true;
+        let mut spawns = Vec::new();
+        let mut prototypes = Vec::new();
+        generator.generate(&inputs, &mut spawns, &mut prototypes);
+        assert!(generator.paced_last_wave());
+        assert!(!generator.telemetry().bursts().is_empty());
+        assert!(plan.is_paced());
+        assert_eq!(
+            plan.spawns(),
+            spawns.as_slice(),
+            "the world keeps the bursts"
+        );
+
+        apply(
+            &mut world,
+            Command::CachePressureWave {
+                inputs,
+                plan: PressureWavePlan::new(spawns.clone(), prototypes),
+            },
+            &mut events,
+        );
+        let Some(Event::PressureWaveReady { plan: repaced, .. }) = events.pop() else {
+            panic!("expected pressure wave ready event");
+        };
+        assert_ne!(
+            repaced.spawns(),
+            spawns.as_slice(),
+            "unmarked plans are re-timed"
+        );
+    }
+
+    #[test]
+    fn cache_pressure_wave_rejects_malformed_plans() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let inputs =
+            PressureWaveInputs::new(7, LevelId::new(2), WaveId::new(1), DifficultyLevel::new(3));
+        let prototype = SpeciesPrototype::new(
+            BugColor::from_rgb(0x11, 0x22, 0x33),
+            Health::new(15),
+            NonZeroU32::new(320).expect("non-zero cadence"),
+        );
+        let hollow = SpeciesPrototype::new(
+            BugColor::from_rgb(0x11, 0x22, 0x33),
+            Health::ZERO,
+            NonZeroU32::new(320).expect("non-zero cadence"),
+        );
+        let valid = PressureSpawnRecord::new(100, 15, 1.0, 0);
+        let hostile = [
+            (
+                vec![valid.clone(), PressureSpawnRecord::new(50, 15, 1.0, 0)],
+                vec![prototype],
+                PressurePlanError::UnsortedSpawn(1),
+            ),
+            (
+                vec![valid.clone(), PressureSpawnRecord::new(100, 15, 1.0, 42)],
+                vec![prototype],
+                PressurePlanError::UnknownSpecies(1),
+            ),
+            (
+                vec![PressureSpawnRecord::new(0, 0, 1.0, 0)],
+                vec![prototype],
+                PressurePlanError::ZeroHealthSpawn(0),
+            ),
+            (
+                vec![PressureSpawnRecord::new(0, 15, f32::NAN, 0)],
+                vec![prototype],
+                PressurePlanError::InvalidSpeed(0),
+            ),
+            (
+                vec![PressureSpawnRecord::new(0, 15, -1.0, 0)],
+                vec![prototype],
+                PressurePlanError::InvalidSpeed(0),
+            ),
+            (
+                vec![valid],
+                vec![prototype, hollow],
+                PressurePlanError::ZeroHealthPrototype(1),
+            ),
+        ];
+        let table = query::species_table(&world).version();
+
+        for (spawns, prototypes, reason) in hostile {
+            events.clear();
+            apply(
+                &mut world,
+                Command::CachePressureWave {
+                    inputs: inputs.clone(),
+                    plan: PressureWavePlan::new(spawns, prototypes),
+                },
+                &mut events,
+            );
+            assert_eq!(
+                events,
+                vec![Event::PressureWaveRejected {
+                    inputs: Box::new(inputs.clone()),
+                    reason,
+                }]
+            );
+            assert!(query::pressure_wave_plan(&world, &inputs).is_none());
+            assert_eq!(query::species_table(&world).version(), table);
+        }
+    }
+
+    #[test]
+    fn generated_plans_pass_validation_when_they_introduce_species() {
+        for wave in 0..8 {
+            for level in 0..3 {
+                let mut world = World::new();
+                let mut events = Vec::new();
+                let inputs = PressureWaveInputs::new(
+                    query::wave_seed_context(&world).global_seed(),
+                    query::level_id(&world),
+                    WaveId::new(wave),
+                    DifficultyLevel::new(level),
+                );
+                apply(
+                    &mut world,
+                    Command::GeneratePressureWave { inputs },
+                    &mut events,
+                );
+                assert!(
+                    matches!(events.as_slice(), [Event::PressureWaveReady { .. }]),
+                    "wave {wave} at level {level} was rejected: {events:?}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn launch_wave_emits_wave_started_with_plan_summary() {
         let mut world = World::new();
@@ -2498,7 +5126,8 @@ mod tests {
                 Health::new(20),
                 NonZeroU32::new(400).expect("non-zero cadence"),
             )],
-        );
+        )
+        .with_paced();
 
         apply(
             &mut world,
@@ -2512,27 +5141,32 @@ mod tests {
 
         world.launch_wave(context.wave(), WaveDifficulty::Normal, &mut events);
 
-        assert_eq!(events.len(), 2);
+        assert_eq!(events.len(), 3);
         assert!(matches!(
             events[0],
             Event::PendingWaveDifficultyChanged { .. }
         ));
-        let Some(Event::WaveStarted {
-            wave,
+        let Some(Event::WaveStarted { wave, launch }) = events.get(1) else {
+            panic!("expected wave started event");
+        };
+        let WaveLaunch {
             difficulty,
             effective_difficulty,
             reward_multiplier,
             pressure_scalar,
+            pressure_share,
+            history,
             plan_pressure,
             plan_species_table_version,
             plan_burst_count,
-        }) = events.get(1)
-        else {
-            panic!("expected wave started event");
-        };
+            contracts,
+        } = launch.as_ref();
+        assert_eq!(*pressure_share, 100);
+        assert_eq!(*history, None);
         assert_eq!(*wave, context.wave());
         assert_eq!(*difficulty, WaveDifficulty::Normal);
-        assert_eq!(*effective_difficulty, context.difficulty_level());
+        assert!(contracts.is_empty());
+        assert_eq!(effective_difficulty.level(), context.difficulty_level());
         assert_eq!(
             *reward_multiplier,
             context.difficulty_level().get().saturating_add(1)
@@ -2545,86 +5179,3467 @@ mod tests {
         assert_eq!(plan_species_table_version, &world.species_table_version);
         assert_eq!(*plan_burst_count, 1);
         assert!(world.active_wave.is_some());
+        let burst = Event::BurstStarted {
+            species: SpeciesId::new(0),
+            burst: 0,
+        };
+        assert_eq!(events[2], burst);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(499),
+            },
+            &mut events,
+        );
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::BurstEnded { .. })));
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1),
+            },
+            &mut events,
+        );
+        assert!(events.contains(&Event::BurstEnded {
+            species: SpeciesId::new(0),
+            burst: 0,
+        }));
     }
 
     #[test]
-    fn removing_tower_refunds_build_cost() {
+    fn imminent_bursts_reserve_their_spawners_against_towers() {
         let mut world = World::new();
         let mut events = Vec::new();
-
-        let initial_gold = query::gold(&world);
-
-        let origin = CellCoord::new(2, 2);
+        let (other, portal) = (CellCoord::new(2, 4), CellCoord::new(5, 6));
         apply(
             &mut world,
-            Command::PlaceTower {
-                kind: TowerKind::Basic,
-                origin,
+            Command::ConfigureSpawners {
+                config: SpawnerConfig::new(vec![
+                    SpawnerSet::Portal(other),
+                    SpawnerSet::Portal(portal),
+                ]),
             },
             &mut events,
         );
-
-        let tower_id = events
-            .iter()
-            .find_map(|event| {
-                if let Event::TowerPlaced { tower, .. } = event {
-                    Some(*tower)
-                } else {
-                    None
-                }
-            })
-            .expect("tower should be placed");
-        assert!(query::gold(&world).get() < initial_gold.get());
-        events.clear();
-
         apply(
             &mut world,
-            Command::RemoveTower { tower: tower_id },
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
             &mut events,
         );
+        let context = query::wave_seed_context(&world);
+        let inputs = PressureWaveInputs::new(
+            context.global_seed(),
+            query::level_id(&world),
+            context.wave(),
+            context.difficulty_level(),
+        );
+        let plan = PressureWavePlan::new(
+            vec![
+                PressureSpawnRecord::new(0, 20, 1.0, 0),
+                PressureSpawnRecord::new(5_000, 20, 1.0, 0),
+            ],
+            vec![SpeciesPrototype::new(
+                BugColor::from_rgb(0x44, 0x55, 0x66),
+                Health::new(20),
+                NonZeroU32::new(400).expect("non-zero cadence"),
+            )],
+        )
+        .with_paced();
+        apply(
+            &mut world,
+            Command::CachePressureWave { inputs, plan },
+            &mut events,
+        );
+        assert!(query::spawn_reservations(&world).is_empty());
 
-        let refunded_gold = events.iter().find_map(|event| {
-            if let Event::GoldChanged { amount } = event {
-                Some(*amount)
-            } else {
-                None
+        world.launch_wave(context.wave(), WaveDifficulty::Normal, &mut events);
+        let place = |world: &mut World, events: &mut Vec<Event>| {
+            for mode in [PlayMode::Builder, PlayMode::Attack] {
+                apply(world, Command::SetPlayMode { mode }, events);
+                if mode == PlayMode::Builder {
+                    events.clear();
+                    apply(
+                        world,
+                        Command::PlaceTower {
+                            kind: TowerKind::Basic,
+                            origin: portal,
+                        },
+                        events,
+                    );
+                }
             }
-        });
+        };
+        place(&mut world, &mut events);
         assert_eq!(
-            refunded_gold,
-            Some(initial_gold),
-            "removal should emit gold refund",
+            query::spawn_reservations(&world),
+            vec![SpawnReservation::new(
+                SpeciesId::new(0),
+                0,
+                vec![other, portal],
+                Duration::from_millis(5_000),
+            )],
+            "patches without spawners fall back to every spawner"
         );
+        assert!(events.contains(&Event::TowerPlacementRejected {
+            kind: TowerKind::Basic,
+            origin: portal,
+            reason: PlacementError::SpawnReserved,
+        }));
 
-        let removed = events
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(5_001),
+            },
+            &mut events,
+        );
+        assert!(query::spawn_reservations(&world).is_empty());
+        place(&mut world, &mut events);
+        assert!(events
             .iter()
-            .any(|event| matches!(event, Event::TowerRemoved { tower, .. } if *tower == tower_id));
-        assert!(removed, "removal should emit tower removed event");
-        assert_eq!(query::gold(&world), initial_gold);
+            .any(|event| matches!(event, Event::TowerPlaced { .. })));
     }
 
     #[test]
-    fn placing_tower_emits_layout_changed_and_updates_analytics_snapshot() {
+    fn wave_seed_context_keys_each_difficulty_choice_separately() {
         let mut world = World::new();
         let mut events = Vec::new();
-        let origin = CellCoord::new(2, 2);
-
         apply(
             &mut world,
-            Command::PlaceTower {
-                kind: TowerKind::Basic,
-                origin,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
             },
             &mut events,
         );
 
-        let layout_changed = events
-            .iter()
-            .any(|event| matches!(event, Event::MazeLayoutChanged));
-        assert!(
-            layout_changed,
-            "placement should broadcast layout change event"
-        );
+        let context = query::wave_seed_context(&world);
+        let level_id = query::level_id(&world);
+        let [normal, hard] = WaveDifficulty::ALL.map(|difficulty| {
+            assert_eq!(
+                context.effective_level(difficulty),
+                query::effective_difficulty(&world, difficulty).level()
+            );
+            context.inputs(level_id, difficulty)
+        });
+        assert_ne!(normal, hard, "choices must not share a cache entry");
+
+        for inputs in [&normal, &hard] {
+            apply(
+                &mut world,
+                Command::GeneratePressureWave {
+                    inputs: inputs.clone(),
+                },
+                &mut events,
+            );
+        }
+        assert!(query::pressure_wave_plan(&world, &normal).is_some());
+        assert!(query::pressure_wave_plan(&world, &hard).is_some());
+
+        events.clear();
+        world.launch_wave(context.wave(), WaveDifficulty::Hard, &mut events);
+        let Some(Event::WaveStarted { launch, .. }) = events
+            .iter()
+            .find(|event| matches!(event, Event::WaveStarted { .. }))
+        else {
+            panic!("expected wave started event");
+        };
+        assert_eq!(launch.effective_difficulty.level(), hard.difficulty());
+    }
+
+    #[test]
+    fn degenerate_tile_grids_are_rejected_and_the_previous_grid_kept() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(6),
+                rows: TileCoord::new(5),
+                tile_length: 10.0,
+                cells_per_tile: 2,
+            },
+            &mut events,
+        );
+        let grid = query::tile_grid(&world).clone();
+        let targets = query::target_cells(&world);
+        let huge = TileCoord::new(u32::MAX);
+        let rejections = [
+            ((0, 5, 10.0, 2), TileGridConfigError::ZeroColumns),
+            ((6, 0, 10.0, 2), TileGridConfigError::ZeroRows),
+            ((6, 5, 0.0, 2), TileGridConfigError::InvalidTileLength),
+            ((6, 5, -4.0, 2), TileGridConfigError::InvalidTileLength),
+            ((6, 5, f32::NAN, 2), TileGridConfigError::InvalidTileLength),
+            (
+                (6, 5, f32::INFINITY, 2),
+                TileGridConfigError::InvalidTileLength,
+            ),
+            ((6, 5, 10.0, 0), TileGridConfigError::ZeroCellsPerTile),
+            (
+                (6, 5, 10.0, MAX_CELLS_PER_TILE + 1),
+                TileGridConfigError::CellsPerTileTooLarge(MAX_CELLS_PER_TILE + 1),
+            ),
+            (
+                (u32::MAX, 1, 10.0, 1),
+                TileGridConfigError::TooManyCells(
+                    u64::from(total_cell_columns(huge, 1))
+                        * u64::from(total_cell_rows(TileCoord::new(1), 1)),
+                ),
+            ),
+        ];
+        for ((columns, rows, tile_length, cells_per_tile), reason) in rejections {
+            events.clear();
+            apply(
+                &mut world,
+                Command::ConfigureTileGrid {
+                    columns: TileCoord::new(columns),
+                    rows: TileCoord::new(rows),
+                    tile_length,
+                    cells_per_tile,
+                },
+                &mut events,
+            );
+            assert_eq!(events, vec![Event::TileGridConfigRejected { reason }]);
+            assert_eq!(*query::tile_grid(&world), grid);
+            assert_eq!(query::cells_per_tile(&world), 2);
+            assert_eq!(query::target_cells(&world), targets);
+        }
+    }
+
+    #[test]
+    fn configured_wave_seed_keys_later_plans_until_the_grid_is_reconfigured() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let default_seed = query::wave_seed_context(&world).global_seed();
+        apply(
+            &mut world,
+            Command::ConfigureWaveSeed { seed: 42 },
+            &mut events,
+        );
+        assert!(events.is_empty());
+        let context = query::wave_seed_context(&world);
+        assert_eq!(context.global_seed(), 42);
+        assert_eq!(
+            context
+                .inputs(query::level_id(&world), WaveDifficulty::Normal)
+                .game_seed(),
+            42
+        );
+
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(4),
+                rows: TileCoord::new(4),
+                tile_length: 1.0,
+                cells_per_tile: 1,
+            },
+            &mut events,
+        );
+        assert_eq!(query::wave_seed_context(&world).global_seed(), default_seed);
+    }
+
+    #[test]
+    fn wave_seeds_are_only_rerolled_in_sandbox_sessions() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let default_seed = query::wave_seed_context(&world).global_seed();
+        apply(&mut world, Command::SetWaveSeed { seed: 7 }, &mut events);
+        assert!(events.is_empty());
+        assert_eq!(query::wave_seed_context(&world).global_seed(), default_seed);
+
+        apply(
+            &mut world,
+            Command::ConfigureSandbox {
+                rules: SandboxRules::preset(),
+            },
+            &mut events,
+        );
+        events.clear();
+        apply(&mut world, Command::SetWaveSeed { seed: 7 }, &mut events);
+        assert_eq!(events, [Event::WaveSeedChanged { seed: 7 }]);
+        assert_eq!(query::wave_seed_context(&world).global_seed(), 7);
+
+        events.clear();
+        apply(&mut world, Command::SetWaveSeed { seed: 7 }, &mut events);
+        assert!(events.is_empty(), "an unchanged seed is not reported");
+    }
+
+    #[test]
+    fn accepted_contracts_raise_difficulty_and_rewards_for_the_next_wave_only() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        for contract in [WaveContract::Onslaught, WaveContract::Reinforced] {
+            apply(
+                &mut world,
+                Command::SetWaveContract {
+                    contract,
+                    accepted: true,
+                },
+                &mut events,
+            );
+        }
+        assert_eq!(
+            query::accepted_contracts(&world),
+            &[WaveContract::Reinforced, WaveContract::Onslaught]
+        );
+
+        let context = query::wave_seed_context(&world);
+        let base = context.difficulty_level();
+        let effective = query::effective_difficulty(&world, WaveDifficulty::Normal);
+        assert_eq!(effective.level(), base.saturating_add(3));
+
+        let inputs = PressureWaveInputs::new(
+            context.global_seed(),
+            query::level_id(&world),
+            context.wave(),
+            effective.level(),
+        );
+        let plan = PressureWavePlan::new(
+            vec![PressureSpawnRecord::new(0, 20, 1.0, 0)],
+            vec![SpeciesPrototype::new(
+                BugColor::from_rgb(0x44, 0x55, 0x66),
+                Health::new(20),
+                NonZeroU32::new(400).expect("non-zero cadence"),
+            )],
+        );
+        apply(
+            &mut world,
+            Command::CachePressureWave { inputs, plan },
+            &mut events,
+        );
+        events.clear();
+
+        world.launch_wave(context.wave(), WaveDifficulty::Normal, &mut events);
+
+        assert!(events.contains(&Event::WaveContractsChanged {
+            contracts: Vec::new()
+        }));
+        let Some(Event::WaveStarted { launch, .. }) = events
+            .iter()
+            .find(|event| matches!(event, Event::WaveStarted { .. }))
+        else {
+            panic!("expected wave started event");
+        };
+        let WaveLaunch {
+            effective_difficulty,
+            reward_multiplier,
+            contracts,
+            ..
+        } = launch.as_ref();
+        assert_eq!(*effective_difficulty, effective);
+        let base_multiplier = base.get() + 1;
+        assert_eq!(*reward_multiplier, (base_multiplier * 250).div_ceil(100));
+        assert_eq!(
+            contracts,
+            &vec![WaveContract::Reinforced, WaveContract::Onslaught]
+        );
+        assert!(query::accepted_contracts(&world).is_empty());
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::SetWaveContract {
+                contract: WaveContract::Reinforced,
+                accepted: true,
+            },
+            &mut events,
+        );
+        assert!(
+            events.is_empty(),
+            "contracts are locked while a wave is active"
+        );
+    }
+
+    #[test]
+    fn shielded_waves_soak_hits_until_the_shared_shield_breaks() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetWaveContract {
+                contract: WaveContract::Shielded,
+                accepted: true,
+            },
+            &mut events,
+        );
+        let context = query::wave_seed_context(&world);
+        let inputs = context.inputs(query::level_id(&world), WaveDifficulty::Normal);
+        let color = BugColor::from_rgb(0x44, 0x55, 0x66);
+        let plan = PressureWavePlan::new(
+            vec![PressureSpawnRecord::new(0, 20, 1.0, 0); 4],
+            vec![SpeciesPrototype::new(
+                color,
+                Health::new(20),
+                NonZeroU32::new(400).expect("non-zero cadence"),
+            )],
+        );
+        apply(
+            &mut world,
+            Command::CachePressureWave { inputs, plan },
+            &mut events,
+        );
+        world.launch_wave(context.wave(), WaveDifficulty::Normal, &mut events);
+        assert_eq!(
+            query::wave_shield(&world),
+            Some(WaveShieldSnapshot {
+                current: 20,
+                capacity: 20,
+            }),
+            "a quarter of the plan's health"
+        );
+
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color,
+                health: Health::new(10),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug = query::bug_view(&world)
+            .iter()
+            .next()
+            .expect("bug should spawn")
+            .id;
+        let hit = |world: &mut World, index: u32, damage: u32| {
+            let projectile = ProjectileId::new(index);
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target: bug,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(damage),
+                },
+            );
+            let mut events = Vec::new();
+            world.resolve_projectile_completion(projectile, bug, Damage::new(damage), &mut events);
+            let health = query::bug_view(world)
+                .iter()
+                .find(|view| view.id == bug)
+                .map(|view| view.health);
+            (health, events)
+        };
+
+        let (health, events) = hit(&mut world, 0, 15);
+        assert_eq!(health, Some(Health::new(10)), "the shield soaks the hit");
+        assert!(!events.contains(&Event::WaveShieldBroken {
+            wave: context.wave()
+        }));
+        assert_eq!(
+            query::wave_shield(&world).map(|shield| shield.current),
+            Some(5)
+        );
+        assert_eq!(
+            query::bug_effects(&world, bug),
+            vec![BugEffect::Shielded(WaveShieldSnapshot {
+                current: 5,
+                capacity: 20,
+            })]
+        );
+
+        let (health, mut events) = hit(&mut world, 1, 8);
+        assert!(events.contains(&Event::WaveShieldBroken {
+            wave: context.wave()
+        }));
+        assert_eq!(health, Some(Health::new(7)), "the breaking hit spills over");
+        assert!(query::wave_shield(&world).is_some_and(|shield| shield.is_broken()));
+        assert!(query::bug_effects(&world, bug).is_empty());
+
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        assert_eq!(query::wave_shield(&world), None);
+    }
+
+    #[test]
+    fn mark_priority_target_toggles_flag_for_living_bug() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(3),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug = query::bug_view(&world)
+            .iter()
+            .next()
+            .expect("bug should spawn")
+            .id;
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::MarkPriorityTarget {
+                bug: BugId::new(bug.get() + 1),
+            },
+            &mut events,
+        );
+        assert!(events.is_empty());
+        assert_eq!(query::priority_target(&world), None);
+
+        apply(&mut world, Command::MarkPriorityTarget { bug }, &mut events);
+        assert_eq!(
+            events,
+            vec![Event::PriorityTargetChanged { bug: Some(bug) }]
+        );
+        assert_eq!(query::priority_target(&world), Some(bug));
+        events.clear();
+
+        apply(&mut world, Command::MarkPriorityTarget { bug }, &mut events);
+        assert_eq!(events, vec![Event::PriorityTargetChanged { bug: None }]);
+        assert_eq!(query::priority_target(&world), None);
+    }
+
+    #[test]
+    fn resolving_wave_reports_damage_by_tower_and_gold_earned() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let context = world.prepare_wave_context(WaveId::new(0), WaveDifficulty::Normal);
+        world.active_wave = Some(context);
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(3),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug = query::bug_view(&world)
+            .iter()
+            .next()
+            .expect("bug should spawn")
+            .id;
+
+        for (index, tower) in [TowerId::new(1), TowerId::new(2)].into_iter().enumerate() {
+            let projectile = ProjectileId::new(index as u32);
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower,
+                    target: bug,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(2),
+                },
+            );
+            world.resolve_projectile_completion(projectile, bug, Damage::new(2), &mut events);
+        }
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1_500),
+            },
+            &mut events,
+        );
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        let report = events
+            .iter()
+            .find_map(|event| match event {
+                Event::WaveReportReady { report } => Some(report.clone()),
+                _ => None,
+            })
+            .expect("resolving an active wave publishes a report");
+        assert_eq!(report.wave(), WaveId::new(0));
+        assert_eq!(report.outcome(), RoundOutcome::Win);
+        assert_eq!(report.duration(), Duration::from_millis(1_500));
+        assert_eq!(report.leaks(), 0);
+        assert_eq!(report.gold_earned(), Gold::new(1));
+        assert_eq!(
+            report.tower_damage(),
+            &[(TowerId::new(1), 2), (TowerId::new(2), 1)]
+        );
+        assert_eq!(report.mvp(), Some(TowerId::new(1)));
+        assert_eq!(
+            query::wave_seed_context(&world).history(),
+            Some(WaveHistoryInputs {
+                clear_time_ms: 1_500,
+                leaks: 0,
+            }),
+            "the next wave is generated from this wave's outcome"
+        );
+    }
+
+    #[test]
+    fn wave_reports_tally_how_each_species_fared() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let plan = PressureWavePlan::new(
+            vec![
+                PressureSpawnRecord::new(0, 3, 1.0, 0),
+                PressureSpawnRecord::new(0, 10, 1.0, 1),
+            ],
+            vec![
+                SpeciesPrototype::new(
+                    BugColor::from_rgb(0x20, 0x30, 0x40),
+                    Health::new(3),
+                    NonZeroU32::new(250).expect("non-zero cadence"),
+                ),
+                SpeciesPrototype::new(
+                    BugColor::from_rgb(0x50, 0x60, 0x70),
+                    Health::new(10),
+                    NonZeroU32::new(250).expect("non-zero cadence"),
+                ),
+            ],
+        );
+        let table_color = world.species_definitions[0].prototype().color();
+        let plan_color = BugColor::from_rgb(0x50, 0x60, 0x70);
+        let colors = world.plan_species_colors(&plan);
+        assert_eq!(colors, vec![(table_color, 0), (plan_color, 1)]);
+
+        let context = world.prepare_wave_context(WaveId::new(0), WaveDifficulty::Normal);
+        world.active_wave = Some(context);
+        world.wave_tally = WaveTally::for_species(colors);
+        let spawners = query::bug_spawners(&world);
+        let spawn = |world: &mut World, spawner, color, health| {
+            let mut spawned = Vec::new();
+            apply(
+                world,
+                Command::SpawnBug {
+                    spawner,
+                    color,
+                    health: Health::new(health),
+                    step_ms: 250,
+                    pathing: PathingPersonality::Direct,
+                    bounty: Gold::new(1),
+                    rank: BugRank::Regular,
+                    dodge: DodgeChance::NONE,
+                    archetype: BugArchetype::Basic,
+                },
+                &mut spawned,
+            );
+            spawned
+                .iter()
+                .find_map(|event| match event {
+                    Event::BugSpawned { bug_id, .. } => Some(*bug_id),
+                    _ => None,
+                })
+                .expect("bug should spawn")
+        };
+        let doomed = spawn(&mut world, spawners[0], table_color, 3);
+        let survivor = spawn(&mut world, spawners[2], plan_color, 10);
+        let _ = spawn(&mut world, spawners[4], BugColor::from_rgb(1, 2, 3), 5);
+
+        for (index, (target, damage)) in [(doomed, 2), (doomed, 2), (survivor, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            let projectile = ProjectileId::new(index as u32);
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(damage),
+                },
+            );
+            world.resolve_projectile_completion(
+                projectile,
+                target,
+                Damage::new(damage),
+                &mut events,
+            );
+        }
+        events.clear();
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1_000),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::StepBug {
+                bug_id: survivor,
+                direction: Direction::South,
+            },
+            &mut events,
+        );
+        let steps = events
+            .iter()
+            .filter(
+                |event| matches!(event, Event::BugAdvanced { bug_id, .. } if *bug_id == survivor),
+            )
+            .count() as u32;
+        assert!(steps > 0);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Loss,
+            },
+            &mut events,
+        );
+        let report = events
+            .iter()
+            .find_map(|event| match event {
+                Event::WaveReportReady { report } => Some(report.clone()),
+                _ => None,
+            })
+            .expect("resolving an active wave publishes a report");
+        assert_eq!(
+            report.species_combat(),
+            &[
+                SpeciesCombat {
+                    species: 0,
+                    bugs: 1,
+                    killed: 1,
+                    average_lifetime_ms: 0,
+                    average_cells_travelled: 0,
+                    damage_absorbed: 3,
+                    damage_per_second: 0,
+                },
+                SpeciesCombat {
+                    species: 1,
+                    bugs: 1,
+                    killed: 0,
+                    average_lifetime_ms: 1_000,
+                    average_cells_travelled: steps,
+                    damage_absorbed: 4,
+                    damage_per_second: 4,
+                },
+            ],
+            "bugs of unknown colours are not attributed"
+        );
+    }
+
+    #[test]
+    fn loop_around_exits_send_bugs_back_to_a_spawner_until_they_wear_out() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(6),
+                rows: TileCoord::new(6),
+                tile_length: 10.0,
+                cells_per_tile: 2,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ConfigureExitBehavior {
+                behavior: ExitBehavior::LoopAround { health_percent: 50 },
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let exits = query::target_cells(&world);
+        for (exit, health) in [(exits[0], 5), (exits[1], 1)] {
+            world.spawn_bug_at(regular_bug(exit, health), &mut events);
+        }
+        let looper = BugId::new(0);
+        let worn = BugId::new(1);
+
+        events.clear();
+        world.process_exit_cells(&mut events);
+        let Some(Event::BugLooped {
+            spawner, remaining, ..
+        }) = events
+            .iter()
+            .find(|event| matches!(event, Event::BugLooped { .. }))
+        else {
+            panic!("expected the healthy bug to loop around");
+        };
+        assert!(query::bug_spawners(&world).contains(spawner));
+        assert_eq!(*remaining, Health::new(2));
+        assert!(events.contains(&Event::BugWornOut {
+            bug_id: worn,
+            exit: exits[1],
+        }));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::RoundLost { .. })));
+        assert_eq!(query::play_mode(&world), PlayMode::Attack);
+
+        let bugs: Vec<(BugId, CellCoord, u32)> = query::bug_view(&world)
+            .iter()
+            .map(|bug| (bug.id, bug.cell, bug.health.get()))
+            .collect();
+        assert_eq!(bugs, vec![(looper, *spawner, 2)]);
+        assert_eq!(world.wave_tally.leaks, 2);
+    }
+
+    #[test]
+    fn physical_hits_shatter_slowed_bugs_for_bonus_damage() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        world.spawn_bug_at(regular_bug(CellCoord::new(3, 5), 10), &mut events);
+        let bug = BugId::new(0);
+        let hit = |world: &mut World, index: u32, kind: TowerKind| {
+            let projectile = ProjectileId::new(index);
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target: bug,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: kind.damage_type(),
+                    inflicts: kind.inflicts(),
+                    damage: Damage::new(2),
+                },
+            );
+            let mut events = Vec::new();
+            world.resolve_projectile_completion(projectile, bug, Damage::new(2), &mut events);
+            events
+        };
+        let health = |world: &World| query::bug_view(world).iter().next().expect("bug").health;
+
+        let chilled = hit(&mut world, 1, TowerKind::Frost);
+        assert!(!chilled
+            .iter()
+            .any(|event| matches!(event, Event::ComboTriggered { .. })));
+        assert_eq!(health(&world), Health::new(8));
+        assert_eq!(
+            query::bug_view(&world).iter().next().expect("bug").status,
+            Some(BugStatus {
+                effect: StatusEffect::Slowed,
+                remaining_ms: StatusEffect::Slowed.duration_ms(),
+            })
+        );
+        assert!(matches!(
+            query::bug_effects(&world, bug)[..],
+            [BugEffect::Status(BugStatus {
+                effect: StatusEffect::Slowed,
+                ..
+            })]
+        ));
+        assert_eq!(
+            world.bugs[0].step_progress(1_000),
+            500,
+            "slowed bugs gain step time at half speed"
+        );
+
+        let shattered = hit(&mut world, 2, TowerKind::Basic);
+        assert_eq!(
+            shattered.first(),
+            Some(&Event::ComboTriggered {
+                bug,
+                combo: Combo::Shatter,
+                bonus: Damage::new(2),
+            })
+        );
+        assert_eq!(health(&world), Health::new(4));
+        assert_eq!(world.bugs[0].status, None, "the combo ends the slow");
+        assert!(query::bug_effects(&world, bug).is_empty());
+
+        let plain = hit(&mut world, 3, TowerKind::Basic);
+        assert!(!plain
+            .iter()
+            .any(|event| matches!(event, Event::ComboTriggered { .. })));
+        assert_eq!(health(&world), Health::new(2));
+        assert_eq!(world.bugs[0].step_progress(1_000), 1_000);
+    }
+
+    #[test]
+    fn slowed_step_progress_does_not_depend_on_tick_length() {
+        let progress = |tick_ms: u32| {
+            let mut bug = Bug::new(
+                BugId::new(0),
+                CellCoord::new(0, 0),
+                BugColor::from_rgb(0x20, 0x30, 0x40),
+                Health::new(1),
+                250,
+                PathingPersonality::Direct,
+                Gold::new(1),
+            );
+            bug.status = Some((StatusEffect::Slowed, StatusEffect::Slowed.duration_ms()));
+            let mut left = 2_500;
+            let mut gained = 0;
+            while left > 0 {
+                let dt = tick_ms.min(left);
+                gained += bug.step_progress(dt);
+                left -= dt;
+            }
+            (gained, bug.status)
+        };
+
+        let (whole, status) = progress(2_500);
+        assert_eq!(whole, 1_500, "half speed for 2 s, then full speed");
+        assert_eq!(status, None);
+        assert_eq!(progress(1), (whole, None), "1 ms ticks");
+        assert_eq!(progress(17), (whole, None), "17 ms ticks");
+    }
+
+    #[test]
+    fn game_rules_grant_bonus_gold_and_a_base_that_absorbs_leaks() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let starting_gold = query::gold(&world);
+        let rules = GameRules {
+            bonus_gold: Gold::new(25),
+            base_health: 2,
+        };
+        apply(
+            &mut world,
+            Command::ConfigureGameRules { rules },
+            &mut events,
+        );
+        assert_eq!(
+            query::gold(&world),
+            starting_gold.saturating_add(Gold::new(25))
+        );
+        assert_eq!(query::game_rules(&world), rules);
+        assert!(events.contains(&Event::GameRulesConfigured { rules }));
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+
+        let exit = query::target_cells(&world)[0];
+        let leak = |world: &mut World, events: &mut Vec<Event>| {
+            events.clear();
+            world.spawn_bug_at(regular_bug(exit, 5), events);
+            world.process_exit_cells(events);
+        };
+
+        leak(&mut world, &mut events);
+        assert!(events.contains(&Event::BaseDamaged {
+            bug: BugId::new(0),
+            remaining: 1,
+        }));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::RoundLost { .. })));
+        assert_eq!(query::play_mode(&world), PlayMode::Attack);
+
+        leak(&mut world, &mut events);
+        assert!(events.contains(&Event::RoundLost { bug: BugId::new(1) }));
+        assert_eq!(
+            query::base_health(&world),
+            2,
+            "a lost round refills the base"
+        );
+    }
+
+    #[test]
+    fn looping_bugs_wear_out_even_when_configured_to_keep_all_their_health() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureExitBehavior {
+                behavior: ExitBehavior::LoopAround {
+                    health_percent: 100,
+                },
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let exit = query::target_cells(&world)[0];
+        world.spawn_bug_at(regular_bug(exit, 5), &mut events);
+        let bug_id = BugId::new(0);
+
+        let mut laps = 0;
+        loop {
+            events.clear();
+            world.process_exit_cells(&mut events);
+            if events.contains(&Event::BugWornOut { bug_id, exit }) {
+                break;
+            }
+            laps += 1;
+            assert!(laps < 5, "every lap costs the bug health");
+            let index = world
+                .bug_index(bug_id)
+                .expect("looping bug stays in the maze");
+            let spawner = world.bugs[index].cell;
+            world.occupancy.vacate(spawner);
+            world.occupancy.occupy(bug_id, exit);
+            world.bugs[index].cell = exit;
+        }
+        assert_eq!(laps, 4, "5 -> 4 -> 3 -> 2 -> 1 -> worn out");
+        assert!(query::bug_view(&world).iter().next().is_none());
+    }
+
+    #[test]
+    fn accuracy_rules_let_projectiles_miss_drifting_and_dodging_bugs() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawners = query::bug_spawners(&world);
+        for (spawner, dodge) in [(spawners[0], 0), (spawners[2], 1_000)] {
+            apply(
+                &mut world,
+                Command::SpawnBug {
+                    spawner,
+                    color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                    health: Health::new(10),
+                    step_ms: 250,
+                    pathing: PathingPersonality::Direct,
+                    bounty: Gold::new(1),
+                    rank: BugRank::Regular,
+                    dodge: DodgeChance::new(dodge),
+                    archetype: BugArchetype::Basic,
+                },
+                &mut events,
+            );
+        }
+        let bugs: Vec<(BugId, CellCoord)> = query::bug_view(&world)
+            .iter()
+            .map(|bug| (bug.id, bug.cell))
+            .collect();
+        let (steady, steady_cell) = bugs[0];
+        let (dodger, dodger_cell) = bugs[1];
+
+        let shoot = |world: &mut World, target: BugId, end: CellPointHalf| {
+            let projectile = world.next_projectile_identifier();
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target,
+                    start: end,
+                    end,
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(1),
+                },
+            );
+            let mut events = Vec::new();
+            world.resolve_projectile_completion(projectile, target, Damage::new(1), &mut events);
+            events.iter().find_map(|event| match event {
+                Event::ProjectileMissed { cause, .. } => Some(*cause),
+                _ => None,
+            })
+        };
+
+        let far = bug_center_half(CellCoord::new(steady_cell.column() + 3, steady_cell.row()));
+        assert_eq!(
+            shoot(&mut world, steady, far),
+            None,
+            "every shot hits by default"
+        );
+        assert_eq!(
+            shoot(&mut world, dodger, bug_center_half(dodger_cell)),
+            None
+        );
+
+        apply(
+            &mut world,
+            Command::ConfigureAccuracy {
+                model: Some(AccuracyModel::new(2)),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::AccuracyConfigured {
+                model: Some(AccuracyModel::new(2)),
+            })
+        );
+        assert_eq!(shoot(&mut world, steady, far), Some(MissCause::Outran));
+        let near = bug_center_half(CellCoord::new(steady_cell.column() + 2, steady_cell.row()));
+        assert_eq!(shoot(&mut world, steady, near), None);
+        assert_eq!(
+            shoot(&mut world, dodger, bug_center_half(dodger_cell)),
+            Some(MissCause::Dodged)
+        );
+        let health = |world: &World, id: BugId| {
+            query::bug_view(world)
+                .iter()
+                .find(|bug| bug.id == id)
+                .map(|bug| bug.health.get())
+        };
+        assert_eq!(health(&world, steady), Some(8));
+        assert_eq!(health(&world, dodger), Some(9));
+    }
+
+    #[test]
+    fn late_game_economy_grows_past_the_u32_range_without_wrapping() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        world.gold = Gold::new(u64::from(u32::MAX));
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        let bounty = Gold::new(u64::from(u32::MAX));
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(1),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty,
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug = query::bug_view(&world)
+            .iter()
+            .next()
+            .expect("bug should spawn")
+            .id;
+
+        let multiplier = u64::from(query::kill_reward_multiplier(&world));
+        let projectile = ProjectileId::new(0);
+        let _ = world.projectiles.insert(
+            projectile,
+            ProjectileState {
+                id: projectile,
+                tower: TowerId::new(1),
+                target: bug,
+                start: CellPointHalf::new(0, 0),
+                end: CellPointHalf::new(0, 0),
+                distance_half: 0,
+                travelled_half: 0,
+                travel_time_ms: 0,
+                elapsed_ms: 0,
+                damage_type: DamageType::Physical,
+                inflicts: None,
+                damage: Damage::new(1),
+            },
+        );
+        world.resolve_projectile_completion(projectile, bug, Damage::new(1), &mut events);
+
+        let expected = u64::from(u32::MAX) * (1 + multiplier);
+        assert!(expected > u64::from(u32::MAX));
+        assert_eq!(query::gold(&world), Gold::new(expected));
+        assert_eq!(query::gold(&world).to_u32_saturating(), u32::MAX);
+        assert_eq!(
+            world.wave_tally.gold_earned,
+            u64::from(u32::MAX) * multiplier
+        );
+
+        world.gold = Gold::MAX;
+        assert_eq!(world.gold.saturating_add(bounty), Gold::MAX);
+        assert_eq!(
+            early_start_bonus(Duration::from_secs(u64::from(u32::MAX) + 1)),
+            Gold::new((u64::from(u32::MAX) + 1) * EARLY_START_GOLD_PER_SECOND)
+        );
+    }
+
+    #[test]
+    fn killing_a_bug_pays_its_bounty_scaled_by_the_reward_multiplier() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(3),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(3),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug = query::bug_view(&world)
+            .iter()
+            .next()
+            .cloned()
+            .expect("bug should spawn");
+        assert_eq!(bug.bounty, Gold::new(3));
+        let bug = bug.id;
+
+        let multiplier = query::kill_reward_multiplier(&world);
+        let gold_before = query::gold(&world);
+        let projectile = ProjectileId::new(0);
+        let _ = world.projectiles.insert(
+            projectile,
+            ProjectileState {
+                id: projectile,
+                tower: TowerId::new(1),
+                target: bug,
+                start: CellPointHalf::new(0, 0),
+                end: CellPointHalf::new(0, 0),
+                distance_half: 0,
+                travelled_half: 0,
+                travel_time_ms: 0,
+                elapsed_ms: 0,
+                damage_type: DamageType::Physical,
+                inflicts: None,
+                damage: Damage::new(3),
+            },
+        );
+        events.clear();
+        world.resolve_projectile_completion(projectile, bug, Damage::new(3), &mut events);
+
+        let paid = Gold::new(3 * u64::from(multiplier));
+        assert!(events.contains(&Event::BugDied { bug, bounty: paid }));
+        assert_eq!(query::gold(&world), gold_before.saturating_add(paid));
+    }
+
+    #[test]
+    fn boss_phases_summon_adds_then_enrage_with_invulnerable_transitions() {
+        fn hit(world: &mut World, bug: BugId, damage: u32) -> Vec<Event> {
+            let projectile = world.next_projectile_identifier();
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target: bug,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(damage),
+                },
+            );
+            let mut events = Vec::new();
+            world.resolve_projectile_completion(projectile, bug, Damage::new(damage), &mut events);
+            events
+        }
+
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x80, 0x10, 0x10),
+                health: Health::new(30),
+                step_ms: 400,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(8),
+                rank: BugRank::Boss,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let boss = query::bug_view(&world)
+            .iter()
+            .next()
+            .cloned()
+            .expect("boss should spawn");
+        assert_eq!(boss.boss_phase, Some(BossPhase::Opening));
+        let boss = boss.id;
+
+        let events = hit(&mut world, boss, 11);
+        assert!(events.contains(&Event::BossPhaseChanged {
+            bug_id: boss,
+            phase: BossPhase::Summoning,
+        }));
+        let adds = events
+            .iter()
+            .filter(|event| matches!(event, Event::BugSpawned { .. }))
+            .count();
+        assert_eq!(adds, 2);
+        assert_eq!(query::bug_view(&world).iter().count(), 3);
+
+        let events = hit(&mut world, boss, 10);
+        assert!(events.contains(&Event::BugDamaged {
+            bug: boss,
+            remaining: Health::new(19),
+        }));
+
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(u64::from(BOSS_PHASE_INVULNERABILITY_MS)),
+            },
+            &mut events,
+        );
+        let events = hit(&mut world, boss, 10);
+        assert!(events.contains(&Event::BossPhaseChanged {
+            bug_id: boss,
+            phase: BossPhase::Enraged,
+        }));
+        let enraged = query::bug_view(&world)
+            .iter()
+            .find(|bug| bug.id == boss)
+            .cloned()
+            .expect("boss alive");
+        assert_eq!(enraged.boss_phase, Some(BossPhase::Enraged));
+        assert_eq!(enraged.step_ms, 200);
+    }
+
+    #[test]
+    fn archetypes_armor_regenerate_and_split() {
+        fn hit(world: &mut World, bug: BugId, damage: u32) -> Vec<Event> {
+            let projectile = world.next_projectile_identifier();
+            let _ = world.projectiles.insert(
+                projectile,
+                ProjectileState {
+                    id: projectile,
+                    tower: TowerId::new(1),
+                    target: bug,
+                    start: CellPointHalf::new(0, 0),
+                    end: CellPointHalf::new(0, 0),
+                    distance_half: 0,
+                    travelled_half: 0,
+                    travel_time_ms: 0,
+                    elapsed_ms: 0,
+                    damage_type: DamageType::Physical,
+                    inflicts: None,
+                    damage: Damage::new(damage),
+                },
+            );
+            let mut events = Vec::new();
+            world.resolve_projectile_completion(projectile, bug, Damage::new(damage), &mut events);
+            events
+        }
+        fn spawn(archetype: BugArchetype) -> (World, BugId) {
+            let mut world = World::new();
+            let mut events = Vec::new();
+            apply(
+                &mut world,
+                Command::SetPlayMode {
+                    mode: PlayMode::Attack,
+                },
+                &mut events,
+            );
+            let spawner = query::bug_spawners(&world)[0];
+            apply(
+                &mut world,
+                Command::SpawnBug {
+                    spawner,
+                    color: BugColor::from_rgb(0x40, 0x80, 0x40),
+                    health: Health::new(30),
+                    step_ms: 10_000,
+                    pathing: PathingPersonality::Direct,
+                    bounty: Gold::new(1),
+                    rank: BugRank::Regular,
+                    dodge: DodgeChance::NONE,
+                    archetype,
+                },
+                &mut events,
+            );
+            let bug = query::bug_view(&world)
+                .iter()
+                .next()
+                .map(|bug| bug.id)
+                .expect("bug should spawn");
+            (world, bug)
+        }
+
+        let (mut armored, bug) = spawn(BugArchetype::Armored);
+        assert!(hit(&mut armored, bug, 10).contains(&Event::BugDamaged {
+            bug,
+            remaining: Health::new(24),
+        }));
+        assert!(hit(&mut armored, bug, 1).contains(&Event::BugDamaged {
+            bug,
+            remaining: Health::new(23),
+        }));
+
+        let (mut regenerating, bug) = spawn(BugArchetype::Regenerating);
+        let _ = hit(&mut regenerating, bug, 10);
+        let mut events = Vec::new();
+        apply(
+            &mut regenerating,
+            Command::Tick {
+                dt: Duration::from_millis(u64::from(REGENERATION_INTERVAL_MS) * 2),
+            },
+            &mut events,
+        );
+        let healed = query::bug_view(&regenerating)
+            .iter()
+            .find(|snapshot| snapshot.id == bug)
+            .map(|snapshot| snapshot.health)
+            .expect("bug alive");
+        assert_eq!(healed, Health::new(22));
+
+        let (mut splitter, bug) = spawn(BugArchetype::Splitter);
+        let events = hit(&mut splitter, bug, 30);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::BugDied { bug: died, .. } if *died == bug)));
+        let children: Vec<Health> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::BugSpawned { health, .. } => Some(*health),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(children, vec![Health::new(10); SPLITTER_CHILDREN]);
+    }
+
+    #[test]
+    fn danger_heatmap_accumulates_bug_traffic_until_grid_reconfigured() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(3),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let bug_id = query::bug_view(&world)
+            .iter()
+            .next()
+            .expect("bug should spawn")
+            .id;
+        assert!(query::danger_heatmap(&world).iter().next().is_none());
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::StepBug {
+                bug_id,
+                direction: Direction::South,
+            },
+            &mut events,
+        );
+        let Some(Event::BugAdvanced { from, to, .. }) = events.first().cloned() else {
+            panic!("bug should advance south from its spawner");
+        };
+        let heatmap = query::danger_heatmap(&world);
+        assert_eq!(heatmap.intensity(to), DangerHeatmap::MAX_INTENSITY);
+        assert_eq!(heatmap.intensity(from), 0);
+
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(4),
+                rows: TileCoord::new(4),
+                tile_length: 100.0,
+                cells_per_tile: 1,
+            },
+            &mut events,
+        );
+        assert!(query::danger_heatmap(&world).iter().next().is_none());
+    }
+
+    #[test]
+    fn path_preview_reroutes_around_candidate_or_reports_blockage() {
+        let world = World::new();
+        let (columns, rows) = world.occupancy.dimensions();
+        let spawners = query::bug_spawners(&world);
+
+        let open = query::path_preview(
+            &world,
+            CellRect::from_origin_and_size(CellCoord::new(0, rows / 2), CellRectSize::new(1, 1)),
+        )
+        .expect("a single cell should not block the maze");
+        assert!(spawners.contains(&open[0]));
+        assert!(world
+            .targets
+            .contains(open.last().expect("path is non-empty")));
+        assert!(open
+            .windows(2)
+            .all(|pair| pair[0].manhattan_distance(pair[1]) == 1));
+
+        let wall = CellRect::from_origin_and_size(
+            CellCoord::new(0, rows - EXIT_CELL_LAYERS - 1),
+            CellRectSize::new(columns, 1),
+        );
+        assert!(query::path_preview(&world, wall).is_none());
+    }
+
+    #[test]
+    fn ticks_advance_world_clock_and_active_wave_stopwatch() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(40),
+            },
+            &mut events,
+        );
+        assert_eq!(query::elapsed(&world), Duration::ZERO);
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(100),
+            },
+            &mut events,
+        );
+        assert_eq!(query::elapsed(&world), Duration::from_millis(100));
+        assert_eq!(query::wave_elapsed(&world), None);
+
+        let context = world.prepare_wave_context(WaveId::new(0), WaveDifficulty::Normal);
+        world.active_wave = Some(context);
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(50),
+            },
+            &mut events,
+        );
+        assert_eq!(query::elapsed(&world), Duration::from_millis(150));
+        assert_eq!(query::wave_elapsed(&world), Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn advance_to_jumps_the_clock_in_one_tick() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureAutoWave {
+                delay: Some(Duration::from_millis(2_500)),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        let start = query::clock(&world);
+        events.clear();
+
+        let at_ms = start.elapsed_ms() + 2_500;
+        apply(&mut world, Command::AdvanceTo { at_ms }, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                Event::TimeAdvanced {
+                    dt: Duration::from_millis(2_500),
+                },
+                Event::NextWaveCountdown { remaining_ms: 0 },
+            ]
+        );
+        let clock = query::clock(&world);
+        assert_eq!(clock.elapsed_ms(), at_ms);
+        assert_eq!(clock.ticks(), start.ticks() + 1);
+        assert_eq!(query::next_wave_countdown(&world), None);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::AdvanceTo { at_ms: at_ms - 1 },
+            &mut events,
+        );
+        assert!(events.is_empty(), "the clock never runs backwards");
+        assert_eq!(query::clock(&world), clock);
+    }
+
+    #[test]
+    fn skipping_countdown_for_haste_banks_the_remaining_time() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureAutoWave {
+                delay: Some(Duration::from_millis(2_000)),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        events.clear();
+
+        let gold_before = query::gold(&world);
+        apply(
+            &mut world,
+            Command::SkipWaveCountdown {
+                reward: PreparationReward::Haste,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event::PreparationBanked {
+                    reward: PreparationReward::Haste,
+                    banked_ms: 2_000,
+                },
+                Event::NextWaveCountdown { remaining_ms: 0 },
+            ]
+        );
+        assert_eq!(query::gold(&world), gold_before);
+        assert_eq!(query::haste_remaining(&world), Duration::from_millis(2_000));
+
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1_500),
+            },
+            &mut events,
+        );
+        assert_eq!(query::haste_remaining(&world), Duration::from_millis(500));
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1_500),
+            },
+            &mut events,
+        );
+        assert_eq!(query::haste_remaining(&world), Duration::ZERO);
+    }
+
+    #[test]
+    fn auto_wave_countdown_ticks_down_and_skip_awards_bonus() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureAutoWave {
+                delay: Some(Duration::from_millis(2_500)),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::NextWaveCountdown {
+                remaining_ms: 2_500
+            }]
+        );
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(1_000),
+            },
+            &mut events,
+        );
+        assert!(events.contains(&Event::NextWaveCountdown {
+            remaining_ms: 1_500
+        }));
+        events.clear();
+
+        let gold_before = query::gold(&world);
+        apply(
+            &mut world,
+            Command::SkipWaveCountdown {
+                reward: PreparationReward::Gold,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event::GoldChanged {
+                    amount: gold_before.saturating_add(Gold::new(EARLY_START_GOLD_PER_SECOND)),
+                },
+                Event::PreparationBanked {
+                    reward: PreparationReward::Gold,
+                    banked_ms: 1_500,
+                },
+                Event::NextWaveCountdown { remaining_ms: 0 },
+            ]
+        );
+        assert_eq!(query::next_wave_countdown(&world), None);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: Duration::from_millis(3_000),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::NextWaveCountdown { remaining_ms: 0 })
+        );
+        assert_eq!(query::next_wave_countdown(&world), None);
+    }
+
+    #[test]
+    fn builder_timer_runs_down_in_builder_mode_and_forces_attack() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureBuilderTimer {
+                limit: Some(Duration::from_millis(2_000)),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        events.clear();
+
+        let builder = Command::SetPlayMode {
+            mode: PlayMode::Builder,
+        };
+        apply(&mut world, builder.clone(), &mut events);
+        assert_eq!(
+            events,
+            vec![
+                Event::PlayModeChanged {
+                    mode: PlayMode::Builder,
+                },
+                Event::BuilderCountdown {
+                    remaining_ms: 2_000
+                },
+            ]
+        );
+        events.clear();
+
+        let tick = |world: &mut World, millis, events: &mut Vec<Event>| {
+            apply(
+                world,
+                Command::Tick {
+                    dt: Duration::from_millis(millis),
+                },
+                events,
+            );
+        };
+        tick(&mut world, 1_500, &mut events);
+        assert_eq!(events, vec![Event::BuilderCountdown { remaining_ms: 500 }]);
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        tick(&mut world, 1_000, &mut events);
+        events.clear();
+        apply(&mut world, builder.clone(), &mut events);
+        assert_eq!(
+            events.last(),
+            Some(&Event::BuilderCountdown { remaining_ms: 500 }),
+            "time spent attacking does not count"
+        );
+        events.clear();
+
+        tick(&mut world, 800, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                Event::BuilderCountdown { remaining_ms: 0 },
+                Event::PlayModeChanged {
+                    mode: PlayMode::Attack,
+                },
+            ]
+        );
+        events.clear();
+        apply(&mut world, builder.clone(), &mut events);
+        assert!(events.is_empty(), "no building time left this round");
+        assert_eq!(query::play_mode(&world), PlayMode::Attack);
+
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            query::builder_time_left(&world),
+            Some(Duration::from_millis(2_000))
+        );
+        apply(&mut world, builder, &mut events);
+        assert_eq!(query::play_mode(&world), PlayMode::Builder);
+
+        apply(
+            &mut world,
+            Command::ConfigureBuilderTimer { limit: None },
+            &mut events,
+        );
+        events.clear();
+        tick(&mut world, 60_000, &mut events);
+        assert!(events.is_empty());
+        assert_eq!(query::play_mode(&world), PlayMode::Builder);
+    }
+
+    #[test]
+    fn accepted_merchant_offer_discounts_only_the_next_tower() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        world.set_gold_for_tests(Gold::new(1_000));
+        world.merchant_offer = Some(50);
+
+        apply(
+            &mut world,
+            Command::AnswerMerchantOffer { accept: true },
+            &mut events,
+        );
+        assert_eq!(events, vec![Event::MerchantOfferClosed { accepted: true }]);
+        assert_eq!(query::merchant_offer(&world), None);
+        assert_eq!(query::merchant_discount(&world), Some(50));
+
+        let full_cost = TowerKind::Basic.build_cost().get();
+        for (origin, expected_cost) in [
+            (CellCoord::new(2, 2), full_cost / 2),
+            (CellCoord::new(8, 2), full_cost),
+        ] {
+            let before = query::gold(&world).get();
+            apply(
+                &mut world,
+                Command::PlaceTower {
+                    kind: TowerKind::Basic,
+                    origin,
+                },
+                &mut events,
+            );
+            assert_eq!(before - query::gold(&world).get(), expected_cost);
+        }
+        assert_eq!(query::merchant_discount(&world), None);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::AnswerMerchantOffer { accept: true },
+            &mut events,
+        );
+        assert!(
+            events.is_empty(),
+            "answering without an open offer is ignored"
+        );
+    }
+
+    #[test]
+    fn configured_tower_content_sets_build_cost_and_refund() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let stats = TowerStats {
+            build_cost: Gold::new(7),
+            ..TowerKind::Basic.base_stats()
+        };
+        let content = TowerContent::default().with_stats(TowerKind::Basic, stats);
+        for _ in 0..2 {
+            apply(
+                &mut world,
+                Command::ConfigureTowerContent { content },
+                &mut events,
+            );
+        }
+        assert_eq!(events, vec![Event::TowerContentConfigured]);
+        assert_eq!(query::tower_content(&world), content);
+
+        let before = query::gold(&world);
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        assert_eq!(query::gold(&world), before.saturating_sub(Gold::new(7)));
+        let tower = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            })
+            .expect("tower placed");
+
+        apply(&mut world, Command::RemoveTower { tower }, &mut events);
+        assert_eq!(query::gold(&world), before);
+    }
+
+    #[test]
+    fn debug_stats_count_towers_and_navigation_rebuilds() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let before = query::debug_stats(&world);
+        assert_eq!(before.towers, 0);
+        assert_eq!(before.bugs, 0);
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let after = query::debug_stats(&world);
+        assert_eq!(after.towers, 1);
+        assert!(after.navigation_generation > before.navigation_generation);
+        assert_eq!(after.tick_index, before.tick_index);
+        assert!(after.bug_capacity >= after.bugs);
+    }
+
+    #[test]
+    fn flanker_navigation_field_penalises_tower_coverage_until_tower_removed() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            })
+            .expect("tower should be placed");
+
+        let direct = query::navigation_field(&world).cells().to_vec();
+        let flanker = query::flanker_navigation_field(&world).cells().to_vec();
+        assert_eq!(direct.len(), flanker.len());
+        assert!(direct
+            .iter()
+            .zip(&flanker)
+            .all(|(direct, flanker)| flanker >= direct));
+        assert!(direct
+            .iter()
+            .zip(&flanker)
+            .any(|(direct, flanker)| *direct != u16::MAX && flanker > direct));
+
+        apply(&mut world, Command::RemoveTower { tower }, &mut events);
+        assert_eq!(
+            query::flanker_navigation_field(&world).cells(),
+            query::navigation_field(&world).cells()
+        );
+    }
+
+    #[test]
+    fn repulsor_pulse_pushes_bugs_back_farthest_first() {
+        fn pulse() -> (World, Vec<Event>) {
+            let mut world = World::new();
+            let mut events = Vec::new();
+            apply(
+                &mut world,
+                Command::ConfigureTileGrid {
+                    columns: TileCoord::new(6),
+                    rows: TileCoord::new(6),
+                    tile_length: 100.0,
+                    cells_per_tile: 4,
+                },
+                &mut events,
+            );
+            world.set_gold_for_tests(Gold::new(100));
+            apply(
+                &mut world,
+                Command::PlaceTower {
+                    kind: TowerKind::Repulsor,
+                    origin: CellCoord::new(1, 11),
+                },
+                &mut events,
+            );
+            let tower = query::tower_at(&world, CellCoord::new(1, 11)).expect("repulsor");
+            apply(
+                &mut world,
+                Command::SetPlayMode {
+                    mode: PlayMode::Attack,
+                },
+                &mut events,
+            );
+            for row in [14, 13] {
+                world.spawn_bug_at(regular_bug(CellCoord::new(7, row), 3), &mut events);
+            }
+            let target = query::bug_view(&world)
+                .iter()
+                .next()
+                .map(|bug| bug.id)
+                .expect("bugs spawned");
+            events.clear();
+            apply(
+                &mut world,
+                Command::FireProjectile { tower, target },
+                &mut events,
+            );
+            (world, events)
+        }
+
+        let (world, events) = pulse();
+        assert_eq!(events, pulse().1);
+        assert!(world.projectiles.is_empty());
+
+        let pushes: Vec<(BugId, CellCoord, CellCoord)> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::BugPushedBack {
+                    bug_id, from, to, ..
+                } => Some((*bug_id, *from, *to)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(pushes.len(), 2);
+        assert_eq!(pushes[0].1, CellCoord::new(7, 13));
+        assert_eq!(pushes[1].1, CellCoord::new(7, 14));
+        assert_ne!(pushes[0].2, pushes[1].2);
+
+        let field = query::navigation_field(&world);
+        for (bug_id, from, to) in pushes {
+            let from_distance = field.distance(from).expect("from in bounds");
+            assert_eq!(field.distance(to), Some(from_distance + 4));
+            let bug = query::bug_view(&world)
+                .iter()
+                .find(|bug| bug.id == bug_id)
+                .cloned()
+                .expect("pushed bug alive");
+            assert_eq!(bug.cell, to);
+        }
+    }
+
+    #[test]
+    fn void_cells_block_bugs_and_building_but_not_sight() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let water: Vec<CellCoord> = (2..6)
+            .map(|row| CellCoord::new(4, row))
+            .chain([CellCoord::new(u32::MAX, 0)])
+            .collect();
+        apply(
+            &mut world,
+            Command::ConfigureVoidCells {
+                cells: water.clone(),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::VoidCellsConfigured {
+                cells: water[..4].to_vec(),
+            }]
+        );
+        assert_eq!(query::void_cells(&world), water[..4].to_vec());
+
+        let pond = CellCoord::new(4, 3);
+        assert!(query::is_cell_blocked(&world, pond));
+        assert_eq!(
+            query::navigation_field(&world).distance(pond),
+            Some(u16::MAX)
+        );
+        let center = |column: i64, row: i64| CellPointHalf::new(column * 2 + 1, row * 2 + 1);
+        assert!(query::raycast(&world, center(2, 3), center(6, 3)).is_clear());
+
+        let place = |world: &mut World| {
+            let mut events = Vec::new();
+            apply(
+                world,
+                Command::PlaceTower {
+                    kind: TowerKind::Basic,
+                    origin: CellCoord::new(4, 2),
+                },
+                &mut events,
+            );
+            events
+        };
+        assert_eq!(
+            place(&mut world),
+            vec![Event::TowerPlacementRejected {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(4, 2),
+                reason: PlacementError::Unbuildable,
+            }]
+        );
+
+        apply(
+            &mut world,
+            Command::ConfigureBridges { cells: vec![pond] },
+            &mut events,
+        );
+        assert!(
+            !query::is_cell_blocked(&world, pond),
+            "bugs cross on the deck"
+        );
+        assert!(query::navigation_field(&world).distance(pond) < Some(u16::MAX));
+        assert!(
+            matches!(
+                place(&mut world).as_slice(),
+                [Event::TowerPlacementRejected {
+                    reason: PlacementError::Unbuildable,
+                    ..
+                }]
+            ),
+            "the deck over a void carries bugs, not towers"
+        );
+    }
+
+    #[test]
+    fn towers_on_bridges_leave_the_tunnel_beneath_open() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let origin = CellCoord::new(2, 2);
+        let deck: Vec<CellCoord> = (0..4)
+            .flat_map(|row| (0..4).map(move |column| CellCoord::new(2 + column, 2 + row)))
+            .chain([CellCoord::new(u32::MAX, 0)])
+            .collect();
+        apply(
+            &mut world,
+            Command::ConfigureBridges {
+                cells: deck.clone(),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::BridgesConfigured {
+                cells: deck[..16].to_vec(),
+            }]
+        );
+        assert_eq!(query::bridge_cells(&world), deck[..16].to_vec());
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin,
+            },
+            &mut events,
+        );
+        let tower = query::tower_at(&world, origin).expect("tower on the bridge deck");
+        assert!(!query::is_cell_blocked(&world, origin));
+        assert!(query::navigation_field(&world).distance(origin) < Some(u16::MAX));
+
+        apply(&mut world, Command::RemoveTower { tower }, &mut events);
+        apply(
+            &mut world,
+            Command::ConfigureBridges { cells: Vec::new() },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin,
+            },
+            &mut events,
+        );
+        assert!(query::tower_at(&world, origin).is_some());
+        assert!(query::is_cell_blocked(&world, origin));
+        assert_eq!(
+            query::navigation_field(&world).distance(origin),
+            Some(u16::MAX)
+        );
+    }
+
+    #[test]
+    fn rotated_barrier_swaps_its_wall_orientation() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlacementRotation {
+                rotation: TowerRotation::Deg90,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::PlacementRotationChanged {
+                rotation: TowerRotation::Deg90,
+            }]
+        );
+        assert_eq!(query::placement_rotation(&world), TowerRotation::Deg90);
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::SetPlacementRotation {
+                rotation: TowerRotation::Deg90,
+            },
+            &mut events,
+        );
+        assert!(events.is_empty());
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Barrier,
+                origin: CellCoord::new(0, 0),
+            },
+            &mut events,
+        );
+        let region = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { region, .. } => Some(*region),
+                _ => None,
+            })
+            .expect("barrier should be placed");
+        assert_eq!(region.size(), CellRectSize::new(12, 4));
+        let towers = query::towers(&world);
+        let barrier = towers.iter().next().expect("placed barrier");
+        assert_eq!(barrier.rotation, TowerRotation::Deg90);
+    }
+
+    #[test]
+    fn formations_claim_every_slot_or_spawn_nothing() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let squad = |anchor, shape| Command::SpawnFormation {
+            anchor,
+            shape,
+            size: 3,
+            color: BugColor::from_rgb(0x20, 0x30, 0x40),
+            health: Health::new(3),
+            step_ms: 250,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
+        };
+
+        events.clear();
+        apply(
+            &mut world,
+            squad(CellCoord::new(5, 0), FormationShape::Wedge),
+            &mut events,
+        );
+        let cells: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::BugSpawned { cell, .. } => Some(*cell),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                CellCoord::new(5, 1),
+                CellCoord::new(6, 0),
+                CellCoord::new(4, 0),
+            ]
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::FormationSpawned {
+                formation: FormationId::new(0),
+                shape: FormationShape::Wedge,
+                bugs: vec![BugId::new(0), BugId::new(1), BugId::new(2)],
+            })
+        );
+
+        events.clear();
+        apply(
+            &mut world,
+            squad(CellCoord::new(5, 0), FormationShape::Line),
+            &mut events,
+        );
+        assert!(events.is_empty(), "occupied slots reject the whole squad");
+        assert_eq!(query::bug_view(&world).iter().count(), 3);
+
+        let (columns, _) = world.occupancy.dimensions();
+        apply(
+            &mut world,
+            squad(CellCoord::new(columns - 1, 4), FormationShape::Line),
+            &mut events,
+        );
+        let cells: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::BugSpawned { cell, .. } => Some(*cell),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                CellCoord::new(columns - 1, 4),
+                CellCoord::new(columns - 1, 5),
+                CellCoord::new(columns - 1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn spawner_layouts_resolve_from_level_config_and_must_reach_an_exit() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let (columns, rows) = world.occupancy.dimensions();
+        let rim = query::bug_spawners(&world);
+        assert_eq!(rim.len() as u32, columns + 2 * (rows - 3));
+
+        apply(
+            &mut world,
+            Command::ConfigureSpawners {
+                config: SpawnerConfig::new(vec![
+                    SpawnerSet::Edge(GridEdge::Top),
+                    SpawnerSet::Patch(SpawnPatchId::new(0)),
+                    SpawnerSet::Portal(CellCoord::new(5, 6)),
+                ]),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event::SpawnersConfigured {
+                    spawners: columns + 1
+                },
+                Event::MazeLayoutChanged,
+            ]
+        );
+        let spawners = query::bug_spawners(&world);
+        assert!(spawners.contains(&CellCoord::new(5, 6)));
+        assert!(!spawners.contains(&CellCoord::new(0, 4)));
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let rejections = [
+            (
+                SpawnerSet::Portal(CellCoord::new(2, 2)),
+                SpawnerConfigError::Unreachable(CellCoord::new(2, 2)),
+            ),
+            (
+                SpawnerSet::Portal(CellCoord::new(3, rows - 1)),
+                SpawnerConfigError::OutOfBounds(CellCoord::new(3, rows - 1)),
+            ),
+            (
+                SpawnerSet::Patch(SpawnPatchId::new(9)),
+                SpawnerConfigError::UnknownPatch(SpawnPatchId::new(9)),
+            ),
+        ];
+        for (set, reason) in rejections {
+            events.clear();
+            apply(
+                &mut world,
+                Command::ConfigureSpawners {
+                    config: SpawnerConfig::new(vec![set]),
+                },
+                &mut events,
+            );
+            assert_eq!(events, vec![Event::SpawnerConfigRejected { reason }]);
+        }
+        events.clear();
+        apply(
+            &mut world,
+            Command::ConfigureSpawners {
+                config: SpawnerConfig::new(Vec::new()),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::SpawnerConfigRejected {
+                reason: SpawnerConfigError::Empty,
+            }]
+        );
+        assert_eq!(query::bug_spawners(&world), spawners);
+    }
+
+    #[test]
+    fn staged_spawners_open_once_the_run_reaches_their_wave() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let portal = SpawnerSet::Portal(CellCoord::new(5, 6));
+        apply(
+            &mut world,
+            Command::ConfigureSpawners {
+                config: SpawnerConfig::new(vec![SpawnerSet::Edge(GridEdge::Top)]).with_activations(
+                    vec![
+                        SpawnerActivation::new(WaveId::new(2), portal),
+                        SpawnerActivation::new(WaveId::new(0), SpawnerSet::Edge(GridEdge::Left)),
+                    ],
+                ),
+            },
+            &mut events,
+        );
+        assert!(events.contains(&Event::SpawnerActivated {
+            wave: WaveId::new(0),
+            set: SpawnerSet::Edge(GridEdge::Left),
+            spawners: world.occupancy.dimensions().1 - 2,
+        }));
+        assert!(!query::bug_spawners(&world).contains(&CellCoord::new(5, 6)));
+
+        events.clear();
+        world.next_wave_id = WaveId::new(1);
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::SpawnerActivated { .. })));
+
+        events.clear();
+        world.next_wave_id = WaveId::new(2);
+        apply(
+            &mut world,
+            Command::ResolveRound {
+                outcome: RoundOutcome::Win,
+            },
+            &mut events,
+        );
+        assert!(events.contains(&Event::SpawnerActivated {
+            wave: WaveId::new(2),
+            set: portal,
+            spawners: 1,
+        }));
+        assert!(events.contains(&Event::MazeLayoutChanged));
+        assert!(query::bug_spawners(&world).contains(&CellCoord::new(5, 6)));
+        assert!(query::bug_spawners(&world).contains(&CellCoord::new(0, 3)));
+    }
+
+    /// Plain grey bug with `health` that enters the maze at `cell`.
+    fn regular_bug(cell: CellCoord, health: u32) -> BugSpawn {
+        BugSpawn {
+            cell,
+            color: BugColor::from_rgb(0x20, 0x30, 0x40),
+            health: Health::new(health),
+            step_ms: 250,
+            pathing: PathingPersonality::Direct,
+            bounty: Gold::new(1),
+            rank: BugRank::Regular,
+            dodge: DodgeChance::NONE,
+            archetype: BugArchetype::Basic,
+        }
+    }
+
+    /// Hash of the state tower firing, projectiles and bugs leave behind.
+    fn state_hash(world: &World) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        format!(
+            "{:?}",
+            (
+                query::bug_view(world).into_vec(),
+                query::tower_cooldowns(world).into_vec(),
+                query::projectiles(world).collect::<Vec<_>>(),
+                query::gold(world),
+            )
+        )
+        .hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn batched_ticks_match_plain_tick_state_hashes() {
+        const FRAME: Duration = Duration::from_millis(16);
+        const BATCH: usize = 8;
+
+        let run = |batched: bool| {
+            let mut world = World::new();
+            let mut events = Vec::new();
+            apply(
+                &mut world,
+                Command::ConfigureSandbox {
+                    rules: SandboxRules {
+                        debug_spawns: true,
+                        ..SandboxRules::default()
+                    },
+                },
+                &mut events,
+            );
+            apply(
+                &mut world,
+                Command::ConfigureTowerHeat { enabled: true },
+                &mut events,
+            );
+            for origin in [CellCoord::new(2, 2), CellCoord::new(6, 2)] {
+                apply(
+                    &mut world,
+                    Command::PlaceTower {
+                        kind: TowerKind::Basic,
+                        origin,
+                    },
+                    &mut events,
+                );
+            }
+            apply(
+                &mut world,
+                Command::SetPlayMode {
+                    mode: PlayMode::Attack,
+                },
+                &mut events,
+            );
+            let species = query::species_table(&world)
+                .iter()
+                .next()
+                .expect("default species")
+                .id();
+            for column in 2..8 {
+                apply(
+                    &mut world,
+                    Command::DebugSpawnBug {
+                        cell: CellCoord::new(column, 6),
+                        species,
+                    },
+                    &mut events,
+                );
+            }
+
+            let mut frames = Vec::new();
+            for frame in 1..=300 {
+                events.clear();
+                let dt = FRAME;
+                let tick = if batched && frame % BATCH != 0 {
+                    Command::BatchedTick { dt }
+                } else {
+                    Command::Tick { dt }
+                };
+                apply(&mut world, tick, &mut events);
+                let ready: Vec<_> = query::tower_cooldowns(&world)
+                    .iter()
+                    .filter(|snapshot| snapshot.ready_in.is_zero())
+                    .map(|snapshot| snapshot.tower)
+                    .collect();
+                if let Some(target) = query::bug_view(&world).iter().next().map(|bug| bug.id) {
+                    for tower in ready {
+                        apply(
+                            &mut world,
+                            Command::FireProjectile { tower, target },
+                            &mut events,
+                        );
+                    }
+                }
+                let hash = (frame % BATCH == 0).then(|| state_hash(&world));
+                frames.push((events.clone(), hash));
+            }
+            frames
+        };
+
+        let plain = run(false);
+        assert!(plain.iter().any(|(events, _)| events
+            .iter()
+            .any(|event| matches!(event, Event::BugDied { .. }))));
+        assert_eq!(run(true), plain);
+    }
+
+    #[test]
+    fn destroyed_nests_close_their_spawners_and_shrink_wave_pressure() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let portal = SpawnerSet::Portal(CellCoord::new(5, 6));
+        apply(
+            &mut world,
+            Command::ConfigureSpawners {
+                config: SpawnerConfig::new(vec![SpawnerSet::Edge(GridEdge::Top), portal]),
+            },
+            &mut events,
+        );
+        let damage = query::tower_content(&world)
+            .stats(TowerKind::Basic)
+            .projectile_damage;
+        let health = Health::new(damage.get() * 2);
+        events.clear();
+        apply(
+            &mut world,
+            Command::ConfigureNests {
+                health: Some(health),
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event::NestsConfigured {
+                    health: Some(health)
+                },
+                Event::MazeLayoutChanged,
+            ]
+        );
+        assert_eq!(query::nests(&world).len(), 2);
+        assert_eq!(query::nests(&world)[1].cells, vec![CellCoord::new(5, 6)]);
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = query::towers(&world).iter().next().expect("tower").id;
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let shoot = |world: &mut World, nest| {
+            world
+                .towers
+                .get_mut(tower)
+                .expect("tower")
+                .cooldown_remaining = Duration::ZERO;
+            let mut events = Vec::new();
+            apply(world, Command::FireAtNest { tower, nest }, &mut events);
+            events
+        };
+
+        assert_eq!(
+            shoot(&mut world, portal),
+            vec![Event::NestDamaged {
+                tower,
+                nest: portal,
+                remaining: Health::new(damage.get()),
+            }]
+        );
+        assert!(shoot(&mut world, portal).contains(&Event::NestDestroyed {
+            nest: portal,
+            pressure_share: 50,
+        }));
+        assert!(!query::bug_spawners(&world).contains(&CellCoord::new(5, 6)));
+        assert_eq!(query::wave_seed_context(&world).pressure_share(), 50);
+        assert_eq!(
+            query::wave_seed_context(&world)
+                .inputs(query::level_id(&world), WaveDifficulty::Normal)
+                .pressure_share(),
+            50
+        );
+
+        assert_eq!(
+            shoot(&mut world, SpawnerSet::Edge(GridEdge::Top)),
+            vec![Event::NestShotRejected {
+                tower,
+                nest: SpawnerSet::Edge(GridEdge::Top),
+                reason: ProjectileRejection::LastNest,
+            }],
+            "the last nest stands so waves can still spawn"
+        );
+        assert_eq!(
+            shoot(&mut world, portal),
+            vec![Event::NestShotRejected {
+                tower,
+                nest: portal,
+                reason: ProjectileRejection::MissingTarget,
+            }]
+        );
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::ConfigureNests {
+                health: Some(health),
+            },
+            &mut events,
+        );
+        assert!(query::bug_spawners(&world).contains(&CellCoord::new(5, 6)));
+        assert_eq!(query::wave_seed_context(&world).pressure_share(), 100);
+    }
+
+    #[test]
+    fn raycast_reports_the_first_wall_or_tower_along_the_segment() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            })
+            .expect("tower should be placed");
+        let center = |column: i64, row: i64| CellPointHalf::new(column * 2 + 1, row * 2 + 1);
+
+        assert_eq!(
+            query::raycast(&world, center(1, 3), center(7, 3)),
+            RaycastHit::Tower {
+                tower,
+                cell: CellCoord::new(2, 3),
+            }
+        );
+        assert!(query::raycast(&world, center(1, 1), center(7, 1)).is_clear());
+        assert!(
+            query::raycast(&world, CellPointHalf::new(8, 8), center(8, 3)).is_clear(),
+            "casts leaving a tower ignore the tower itself"
+        );
+        assert_eq!(
+            query::raycast(&world, center(1, 1), CellPointHalf::new(-3, 3)),
+            RaycastHit::OutOfBounds
+        );
+
+        let wall = query::walls(&world)
+            .iter()
+            .next()
+            .expect("default grid has walls")
+            .cell();
+        let above = center(i64::from(wall.column()), 1);
+        assert_eq!(
+            query::raycast(
+                &world,
+                above,
+                center(i64::from(wall.column()), i64::from(wall.row()))
+            ),
+            RaycastHit::Wall { cell: wall }
+        );
+    }
+
+    #[test]
+    fn line_of_sight_rules_reject_shots_through_other_towers() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(16),
+                rows: TileCoord::new(16),
+                tile_length: 100.0,
+                cells_per_tile: 1,
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ConfigureSandbox {
+                rules: SandboxRules::preset(),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ConfigureLineOfSight { enabled: true },
+            &mut events,
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::LineOfSightConfigured { enabled: true })
+        );
+        assert!(query::line_of_sight_enabled(&world));
+        let mut towers = Vec::new();
+        for origin in [CellCoord::new(2, 2), CellCoord::new(2, 6)] {
+            events.clear();
+            apply(
+                &mut world,
+                Command::PlaceTower {
+                    kind: TowerKind::Basic,
+                    origin,
+                },
+                &mut events,
+            );
+            towers.extend(events.iter().find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            }));
+        }
+        let [tower, _blocker] = towers[..] else {
+            panic!("both towers should be placed");
+        };
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let species = query::species_table(&world)
+            .iter()
+            .next()
+            .expect("default species")
+            .id();
+        events.clear();
+        apply(
+            &mut world,
+            Command::DebugSpawnBug {
+                cell: CellCoord::new(3, 11),
+                species,
+            },
+            &mut events,
+        );
+        let bug = events
+            .iter()
+            .find_map(|event| match event {
+                Event::BugSpawned { bug_id, .. } => Some(*bug_id),
+                _ => None,
+            })
+            .expect("debug spawn should create a bug");
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::FireProjectile { tower, target: bug },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::ProjectileRejected {
+                tower,
+                target: bug,
+                reason: ProjectileRejection::LineOfSightBlocked,
+            }]
+        );
+
+        apply(
+            &mut world,
+            Command::ConfigureLineOfSight { enabled: false },
+            &mut events,
+        );
+        events.clear();
+        apply(
+            &mut world,
+            Command::FireProjectile { tower, target: bug },
+            &mut events,
+        );
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::ProjectileFired { .. })));
+    }
+
+    #[test]
+    fn tower_heat_stalls_overheated_towers_until_they_cool() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureSandbox {
+                rules: SandboxRules::preset(),
+            },
+            &mut events,
+        );
+        apply(
+            &mut world,
+            Command::ConfigureTowerHeat { enabled: true },
+            &mut events,
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::TowerHeatConfigured { enabled: true })
+        );
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            })
+            .expect("tower should be placed");
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        let species = query::species_table(&world)
+            .iter()
+            .next()
+            .expect("default species")
+            .id();
+        events.clear();
+        apply(
+            &mut world,
+            Command::DebugSpawnBug {
+                cell: CellCoord::new(2, 6),
+                species,
+            },
+            &mut events,
+        );
+        let bug = events
+            .iter()
+            .find_map(|event| match event {
+                Event::BugSpawned { bug_id, .. } => Some(*bug_id),
+                _ => None,
+            })
+            .expect("debug spawn should create a bug");
+
+        let curve = TowerKind::Basic.heat_curve();
+        for _ in 0..curve.capacity / curve.heat_per_shot {
+            events.clear();
+            apply(
+                &mut world,
+                Command::FireProjectile { tower, target: bug },
+                &mut events,
+            );
+            assert!(events
+                .iter()
+                .any(|event| matches!(event, Event::ProjectileFired { .. })));
+        }
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::FireProjectile { tower, target: bug },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::ProjectileRejected {
+                tower,
+                target: bug,
+                reason: ProjectileRejection::Overheated,
+            }]
+        );
+        let snapshot = *query::tower_cooldowns(&world)
+            .iter()
+            .next()
+            .expect("tower cooldown");
+        assert_eq!(
+            snapshot.heat,
+            Some(TowerHeat {
+                heat: curve.capacity,
+                capacity: curve.capacity,
+                overheated: true,
+            })
+        );
+        assert_eq!(
+            snapshot.ready_in,
+            Duration::from_secs(u64::from(curve.capacity / curve.dissipation_per_second))
+        );
+
+        apply(
+            &mut world,
+            Command::Tick {
+                dt: snapshot.ready_in,
+            },
+            &mut events,
+        );
+        let snapshot = *query::tower_cooldowns(&world)
+            .iter()
+            .next()
+            .expect("tower cooldown");
+        assert_eq!(snapshot.ready_in, Duration::ZERO);
+        assert!(snapshot.heat.is_some_and(|heat| !heat.overheated));
+
+        apply(
+            &mut world,
+            Command::ConfigureTowerHeat { enabled: false },
+            &mut events,
+        );
+        assert!(!query::tower_heat_enabled(&world));
+        assert!(query::tower_cooldowns(&world)
+            .iter()
+            .all(|snapshot| snapshot.heat.is_none()));
+    }
+
+    #[test]
+    fn sandbox_rules_waive_costs_and_cooldowns_and_flag_the_session() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        apply(
+            &mut world,
+            Command::ConfigureSandbox {
+                rules: SandboxRules::preset(),
+            },
+            &mut events,
+        );
+        assert!(query::is_sandbox_session(&world));
+        let gold = query::gold(&world);
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = events
+            .iter()
+            .find_map(|event| match event {
+                Event::TowerPlaced { tower, .. } => Some(*tower),
+                _ => None,
+            })
+            .expect("tower should be placed");
+        assert_eq!(query::gold(&world), gold);
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        events.clear();
+        let species = query::species_table(&world)
+            .iter()
+            .next()
+            .expect("default species")
+            .id();
+        apply(
+            &mut world,
+            Command::DebugSpawnBug {
+                cell: CellCoord::new(2, 6),
+                species,
+            },
+            &mut events,
+        );
+        let bug = events
+            .iter()
+            .find_map(|event| match event {
+                Event::BugSpawned { bug_id, cell, .. } => {
+                    assert_eq!(*cell, CellCoord::new(2, 6));
+                    Some(*bug_id)
+                }
+                _ => None,
+            })
+            .expect("debug spawn should create a bug away from the spawners");
+
+        for _ in 0..2 {
+            events.clear();
+            apply(
+                &mut world,
+                Command::FireProjectile { tower, target: bug },
+                &mut events,
+            );
+            assert!(events
+                .iter()
+                .any(|event| matches!(event, Event::ProjectileFired { .. })));
+        }
+
+        apply(
+            &mut world,
+            Command::ConfigureSandbox {
+                rules: SandboxRules::default(),
+            },
+            &mut events,
+        );
+        events.clear();
+        apply(
+            &mut world,
+            Command::DebugSpawnBug {
+                cell: CellCoord::new(3, 6),
+                species,
+            },
+            &mut events,
+        );
+        assert!(events.is_empty(), "debug spawns need the sandbox toggle");
+        assert!(query::is_sandbox_session(&world));
+    }
+
+    #[test]
+    fn removing_tower_refunds_build_cost() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+
+        let initial_gold = query::gold(&world);
+
+        let origin = CellCoord::new(2, 2);
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin,
+            },
+            &mut events,
+        );
+
+        let tower_id = events
+            .iter()
+            .find_map(|event| {
+                if let Event::TowerPlaced { tower, .. } = event {
+                    Some(*tower)
+                } else {
+                    None
+                }
+            })
+            .expect("tower should be placed");
+        assert!(query::gold(&world).get() < initial_gold.get());
+        events.clear();
+
+        apply(
+            &mut world,
+            Command::RemoveTower { tower: tower_id },
+            &mut events,
+        );
+
+        let refunded_gold = events.iter().find_map(|event| {
+            if let Event::GoldChanged { amount } = event {
+                Some(*amount)
+            } else {
+                None
+            }
+        });
+        assert_eq!(
+            refunded_gold,
+            Some(initial_gold),
+            "removal should emit gold refund",
+        );
+
+        let removed = events
+            .iter()
+            .any(|event| matches!(event, Event::TowerRemoved { tower, .. } if *tower == tower_id));
+        assert!(removed, "removal should emit tower removed event");
+        assert_eq!(query::gold(&world), initial_gold);
+    }
+
+    #[test]
+    fn upgrades_cost_gold_multiply_damage_and_are_refunded() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let initial_gold = query::gold(&world);
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin: CellCoord::new(2, 2),
+            },
+            &mut events,
+        );
+        let tower = query::towers(&world).iter().next().expect("placed").id;
+
+        events.clear();
+        for _ in 0..=TowerStats::MAX_UPGRADES {
+            apply(&mut world, Command::UpgradeTower { tower }, &mut events);
+        }
+        let upgraded: Vec<(u32, Gold)> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::TowerUpgraded { upgrades, cost, .. } => Some((*upgrades, *cost)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            upgraded,
+            vec![(1, Gold::new(10)), (2, Gold::new(20)), (3, Gold::new(30))]
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::TowerUpgradeRejected {
+                tower,
+                reason: UpgradeError::FullyUpgraded,
+            })
+        );
+        assert_eq!(query::gold(&world), Gold::new(initial_gold.get() - 70));
+
+        events.clear();
+        apply(
+            &mut world,
+            Command::SetTowerTargeting {
+                tower,
+                policy: TargetingPolicy::Weakest,
+            },
+            &mut events,
+        );
+        assert_eq!(
+            events,
+            vec![Event::TowerTargetingChanged {
+                tower,
+                policy: TargetingPolicy::Weakest,
+            }]
+        );
+        let snapshot = *query::towers(&world).iter().next().expect("tower");
+        assert_eq!(
+            (snapshot.upgrades, snapshot.targeting),
+            (3, TargetingPolicy::Weakest)
+        );
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Attack,
+            },
+            &mut events,
+        );
+        events.clear();
+        apply(&mut world, Command::UpgradeTower { tower }, &mut events);
+        assert_eq!(
+            events,
+            vec![Event::TowerUpgradeRejected {
+                tower,
+                reason: UpgradeError::InvalidMode,
+            }]
+        );
+        let spawner = query::bug_spawners(&world)[0];
+        apply(
+            &mut world,
+            Command::SpawnBug {
+                spawner,
+                color: BugColor::from_rgb(0x20, 0x30, 0x40),
+                health: Health::new(10),
+                step_ms: 250,
+                pathing: PathingPersonality::Direct,
+                bounty: Gold::new(1),
+                rank: BugRank::Regular,
+                dodge: DodgeChance::NONE,
+                archetype: BugArchetype::Basic,
+            },
+            &mut events,
+        );
+        let target = query::bug_view(&world).iter().next().expect("spawned").id;
+        apply(
+            &mut world,
+            Command::FireProjectile { tower, target },
+            &mut events,
+        );
+        let damage = world.projectiles.values().next().expect("fired").damage;
+        assert_eq!(
+            damage,
+            Damage::new(4),
+            "three upgrades quadruple the damage"
+        );
+
+        apply(
+            &mut world,
+            Command::SetPlayMode {
+                mode: PlayMode::Builder,
+            },
+            &mut events,
+        );
+        apply(&mut world, Command::RemoveTower { tower }, &mut events);
+        assert_eq!(query::gold(&world), initial_gold, "upgrades are refunded");
+    }
+
+    #[test]
+    fn placing_tower_emits_layout_changed_and_updates_analytics_snapshot() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        let origin = CellCoord::new(2, 2);
+
+        apply(
+            &mut world,
+            Command::PlaceTower {
+                kind: TowerKind::Basic,
+                origin,
+            },
+            &mut events,
+        );
+
+        let layout_changed = events
+            .iter()
+            .any(|event| matches!(event, Event::MazeLayoutChanged));
+        assert!(
+            layout_changed,
+            "placement should broadcast layout change event"
+        );
 
         let placed_tower = events.iter().find_map(|event| {
             if let Event::TowerPlaced { tower, .. } = event {
@@ -2701,6 +8716,134 @@ mod tests {
         );
     }
 
+    fn configure_grid(world: &mut World, columns: u32, rows: u32, cells_per_tile: u32) {
+        let mut events = Vec::new();
+        apply(
+            world,
+            Command::ConfigureTileGrid {
+                columns: TileCoord::new(columns),
+                rows: TileCoord::new(rows),
+                tile_length: 50.0,
+                cells_per_tile,
+            },
+            &mut events,
+        );
+    }
+
+    #[test]
+    fn exit_opening_is_tile_aligned_and_centred_left_of_centre_for_even_widths() {
+        for columns in 1..=9 {
+            for cells_per_tile in 1..=4 {
+                let mut world = World::new();
+                configure_grid(&mut world, columns, 3, cells_per_tile);
+                let (total_columns, total_rows) = world.occupancy.dimensions();
+
+                let exits: Vec<u32> = world.targets.iter().map(|cell| cell.column()).collect();
+                assert!(world
+                    .targets
+                    .iter()
+                    .all(|cell| cell.row() + 1 == total_rows));
+                assert_eq!(exits.len(), cells_per_tile as usize);
+                assert!(exits.windows(2).all(|pair| pair[1] == pair[0] + 1));
+                assert_eq!((exits[0] - SIDE_BORDER_CELL_LAYERS) % cells_per_tile, 0);
+
+                let left_margin = exits[0];
+                let right_margin = total_columns - 1 - exits[exits.len() - 1];
+                let expected_skew = if columns % 2 == 0 { cells_per_tile } else { 0 };
+                assert_eq!(
+                    right_margin - left_margin,
+                    expected_skew,
+                    "{columns} tiles at {cells_per_tile} cells per tile"
+                );
+
+                let wall_row = visible_wall_row_for_tile_grid(TileCoord::new(3), cells_per_tile)
+                    .expect("wall row");
+                let gap: Vec<u32> = (0..total_columns)
+                    .filter(|column| !world.walls.contains(CellCoord::new(*column, wall_row)))
+                    .collect();
+                assert_eq!(gap, exits);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_and_asymmetric_grids_route_every_spawner_to_the_exit() {
+        for (columns, rows, cells_per_tile) in [
+            (64, 64, 4),
+            (1, 48, 4),
+            (48, 1, 4),
+            (128, 2, 2),
+            (2, 128, 2),
+        ] {
+            let mut world = World::new();
+            configure_grid(&mut world, columns, rows, cells_per_tile);
+            let (total_columns, total_rows) = world.occupancy.dimensions();
+            assert_eq!(
+                (total_columns, total_rows),
+                (
+                    total_cell_columns(TileCoord::new(columns), cells_per_tile),
+                    total_cell_rows(TileCoord::new(rows), cells_per_tile)
+                )
+            );
+            assert_eq!(
+                query::navigation_field(&world).cells().len(),
+                (total_columns * total_rows) as usize
+            );
+
+            let spawners = query::bug_spawners(&world);
+            assert!(!spawners.is_empty());
+            for spawner in spawners {
+                let route = world
+                    .navigation_field
+                    .descend_from(spawner)
+                    .unwrap_or_else(|| panic!("{spawner:?} cannot reach the exit"));
+                assert!(world.targets.contains(route.last().expect("route")));
+            }
+            assert_eq!(
+                world.flanker_navigation_field.cells(),
+                world.navigation_field.cells(),
+                "an empty board costs every cell the same for flankers"
+            );
+        }
+    }
+
+    #[test]
+    fn towers_on_a_huge_grid_reroute_both_navigation_fields() {
+        let mut world = World::new();
+        let mut events = Vec::new();
+        configure_grid(&mut world, 64, 64, 4);
+        let exit = world.targets[0];
+        let spawner = CellCoord::new(exit.column(), 0);
+        let open_distance = world.navigation_field.distance(spawner).expect("distance");
+
+        let row = TOP_BORDER_CELL_LAYERS + 60 * 4;
+        for offset in [-4, 0, 4] {
+            apply(
+                &mut world,
+                Command::PlaceTower {
+                    kind: TowerKind::Basic,
+                    origin: CellCoord::new(exit.column().saturating_add_signed(offset), row),
+                },
+                &mut events,
+            );
+        }
+        assert_eq!(query::towers(&world).iter().count(), 3);
+
+        let walled_distance = world.navigation_field.distance(spawner).expect("distance");
+        assert!(
+            walled_distance > open_distance,
+            "bugs detour around the towers"
+        );
+        assert!(
+            world
+                .flanker_navigation_field
+                .distance(spawner)
+                .expect("distance")
+                > walled_distance,
+            "flankers pay for crossing tower coverage"
+        );
+    }
+
     #[test]
     fn configure_tile_grid_emits_layout_changed_event() {
         let mut world = World::new();
@@ -1,6 +1,9 @@
 //! Static navigation field builder used by the world crate.
 
-use std::collections::VecDeque;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
 
 use maze_defence_core::CellCoord;
 
@@ -9,12 +12,19 @@ use maze_defence_core::CellCoord;
 /// The field mirrors the world's occupancy dimensions, including the hidden
 /// exit row, and stores the reverse breadth-first search results that drive the
 /// crowd planner. Distances default to `u16::MAX` for unreachable cells so
-/// callers can distinguish walls from traversable tiles.
+/// callers can distinguish walls from traversable tiles. Distances saturate at
+/// `u16::MAX - 1`, which bounds routes to 65,534 cells; a 64×64-tile map at four
+/// cells per tile holds about 67k cells, so even a serpentine maze stays inside it.
+///
+/// The search frontier is kept between rebuilds so large grids do not reallocate it
+/// every time the layout changes.
 #[derive(Clone, Debug, Default)]
 pub(crate) struct NavigationField {
     width: u32,
     height: u32,
     distances: Vec<u16>,
+    queue: VecDeque<CellCoord>,
+    heap: BinaryHeap<Reverse<(u16, u32, u32)>>,
 }
 
 impl NavigationField {
@@ -48,7 +58,9 @@ impl NavigationField {
         self.width = width;
         self.height = height;
 
-        let mut queue = VecDeque::new();
+        let mut queue = std::mem::take(&mut self.queue);
+        queue.clear();
+        queue.reserve(cell_count);
 
         for &exit in exits {
             if exit.column() >= width || exit.row() >= height {
@@ -98,6 +110,87 @@ impl NavigationField {
                 queue.push_back(neighbor);
             }
         }
+        self.queue = queue;
+    }
+
+    /// Rebuilds the navigation distances using a reverse Dijkstra search.
+    ///
+    /// Occupying a cell costs `cell_cost(cell)` steps, clamped to at least one so the
+    /// distances still strictly decrease towards the exits. Ties are settled in row-major
+    /// order, keeping the field deterministic.
+    pub(crate) fn rebuild_weighted_with<F, C>(
+        &mut self,
+        width: u32,
+        height: u32,
+        exits: &[CellCoord],
+        mut is_blocked: F,
+        mut cell_cost: C,
+    ) where
+        F: FnMut(CellCoord) -> bool,
+        C: FnMut(CellCoord) -> u16,
+    {
+        let width_usize = usize::try_from(width).unwrap_or(0);
+        let height_usize = usize::try_from(height).unwrap_or(0);
+        let cell_count = width_usize.checked_mul(height_usize).unwrap_or(0);
+
+        self.width = width;
+        self.height = height;
+        self.distances.clear();
+        self.distances.resize(cell_count, u16::MAX);
+        if cell_count == 0 {
+            return;
+        }
+
+        let mut heap = std::mem::take(&mut self.heap);
+        heap.clear();
+        for &exit in exits {
+            if exit.column() >= width || exit.row() >= height || is_blocked(exit) {
+                continue;
+            }
+
+            if let Some(index) = index(width_usize, exit) {
+                self.distances[index] = 0;
+                heap.push(Reverse((0_u16, exit.row(), exit.column())));
+            }
+        }
+
+        while let Some(Reverse((distance, row, column))) = heap.pop() {
+            let cell = CellCoord::new(column, row);
+            let Some(current_index) = index(width_usize, cell) else {
+                continue;
+            };
+            if self.distances[current_index] < distance {
+                continue;
+            }
+
+            for neighbor in neighbors(cell, width, height) {
+                if is_blocked(neighbor) {
+                    continue;
+                }
+
+                let Some(neighbor_index) = index(width_usize, neighbor) else {
+                    continue;
+                };
+
+                let next_distance = distance
+                    .saturating_add(cell_cost(neighbor).max(1))
+                    .min(u16::MAX - 1);
+                if self.distances[neighbor_index] <= next_distance {
+                    continue;
+                }
+
+                self.distances[neighbor_index] = next_distance;
+                heap.push(Reverse((next_distance, neighbor.row(), neighbor.column())));
+            }
+        }
+        self.heap = heap;
+    }
+
+    /// Copies the distances of `other`, reusing this field's allocation.
+    pub(crate) fn copy_distances_from(&mut self, other: &Self) {
+        self.width = other.width;
+        self.height = other.height;
+        self.distances.clone_from(&other.distances);
     }
 
     /// Width of the navigation field in cells.
@@ -128,6 +221,31 @@ impl NavigationField {
         let width = usize::try_from(self.width).ok()?;
         index(width, cell).and_then(|offset| self.distances.get(offset).copied())
     }
+
+    /// Follows strictly decreasing distances from `start` until an exit is reached.
+    ///
+    /// Returns `None` when `start` cannot reach any exit.
+    #[must_use]
+    pub(crate) fn descend_from(&self, start: CellCoord) -> Option<Vec<CellCoord>> {
+        let mut current = start;
+        let mut current_distance = self.distance(current)?;
+        if current_distance == u16::MAX {
+            return None;
+        }
+
+        let mut path = vec![current];
+        while current_distance > 0 {
+            let (next, distance) = neighbors(current, self.width, self.height)
+                .filter_map(|neighbor| Some((neighbor, self.distance(neighbor)?)))
+                .filter(|(_, distance)| *distance < current_distance)
+                .min_by_key(|(_, distance)| *distance)?;
+            path.push(next);
+            current = next;
+            current_distance = distance;
+        }
+
+        Some(path)
+    }
 }
 
 fn neighbors(cell: CellCoord, width: u32, height: u32) -> impl Iterator<Item = CellCoord> {
@@ -196,4 +314,38 @@ mod tests {
         assert_eq!(field.distance(CellCoord::new(1, 0)), Some(4));
         assert_eq!(field.distance(CellCoord::new(0, 1)), Some(2));
     }
+
+    #[test]
+    fn weighted_rebuild_routes_around_expensive_cells() {
+        let mut field = NavigationField::default();
+        let exits = [CellCoord::new(1, 2)];
+        let expensive = CellCoord::new(1, 1);
+
+        field.rebuild_weighted_with(
+            3,
+            3,
+            &exits,
+            |_| false,
+            |cell| {
+                if cell == expensive {
+                    5
+                } else {
+                    1
+                }
+            },
+        );
+
+        assert_eq!(field.distance(expensive), Some(5));
+        assert_eq!(field.distance(CellCoord::new(1, 0)), Some(4));
+        assert_eq!(
+            field.descend_from(CellCoord::new(1, 0)),
+            Some(vec![
+                CellCoord::new(1, 0),
+                CellCoord::new(2, 0),
+                CellCoord::new(2, 1),
+                CellCoord::new(2, 2),
+                CellCoord::new(1, 2),
+            ])
+        );
+    }
 }
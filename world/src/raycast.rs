@@ -0,0 +1,144 @@
+//! Grid traversal for line-of-sight and wall raycasts.
+
+use maze_defence_core::CellPointHalf;
+
+/// Walks every cell a segment between two half-cell points passes through.
+///
+/// Cells are yielded in travel order as signed `(column, row)` pairs so callers can detect
+/// segments that leave the grid. The traversal is an integer DDA: boundary crossings are
+/// compared with exact cross-multiplication, and a segment passing exactly through a cell
+/// corner crosses the column boundary before the row boundary. Cells the segment merely
+/// touches at its end point are not entered.
+#[derive(Clone, Debug)]
+pub(crate) struct CellTraversal {
+    cell: Option<(i64, i64)>,
+    column: Axis,
+    row: Axis,
+}
+
+/// Per-axis stepping state measured in half-cell units from the segment start.
+#[derive(Clone, Copy, Debug)]
+struct Axis {
+    step: i64,
+    length: i128,
+    next_boundary: i128,
+}
+
+impl Axis {
+    fn new(start: i64, end: i64) -> (i64, Self) {
+        let delta = i128::from(end) - i128::from(start);
+        let cell = if delta < 0 && start.rem_euclid(2) == 0 {
+            start.div_euclid(2) - 1
+        } else {
+            start.div_euclid(2)
+        };
+        let boundary = if delta < 0 {
+            i128::from(cell) * 2
+        } else {
+            (i128::from(cell) + 1) * 2
+        };
+        let step = match delta {
+            0 => 0,
+            delta if delta > 0 => 1,
+            _ => -1,
+        };
+        (
+            cell,
+            Self {
+                step,
+                length: delta.abs(),
+                next_boundary: (boundary - i128::from(start)).abs(),
+            },
+        )
+    }
+
+    fn is_moving(self) -> bool {
+        self.length != 0
+    }
+}
+
+impl CellTraversal {
+    /// Prepares a traversal from `from` towards `to`.
+    pub(crate) fn new(from: CellPointHalf, to: CellPointHalf) -> Self {
+        let (column_cell, column) = Axis::new(from.column_half(), to.column_half());
+        let (row_cell, row) = Axis::new(from.row_half(), to.row_half());
+        Self {
+            cell: Some((column_cell, row_cell)),
+            column,
+            row,
+        }
+    }
+}
+
+impl Iterator for CellTraversal {
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.cell.take()?;
+
+        // Crossing fractions along the segment are `next_boundary / length` per axis;
+        // multiplying through by both lengths keeps the comparison exact.
+        let step_column = match (self.column.is_moving(), self.row.is_moving()) {
+            (true, true) => {
+                self.column.next_boundary * self.row.length
+                    <= self.row.next_boundary * self.column.length
+            }
+            (true, false) => true,
+            (false, true) => false,
+            (false, false) => return Some(current),
+        };
+        let axis = if step_column {
+            &mut self.column
+        } else {
+            &mut self.row
+        };
+        if axis.next_boundary < axis.length {
+            axis.next_boundary += 2;
+            let step = axis.step;
+            self.cell = Some(if step_column {
+                (current.0 + step, current.1)
+            } else {
+                (current.0, current.1 + step)
+            });
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(from: (i64, i64), to: (i64, i64)) -> Vec<(i64, i64)> {
+        CellTraversal::new(
+            CellPointHalf::new(from.0, from.1),
+            CellPointHalf::new(to.0, to.1),
+        )
+        .collect()
+    }
+
+    #[test]
+    fn traversal_visits_cells_in_travel_order() {
+        assert_eq!(cells((1, 1), (1, 1)), vec![(0, 0)]);
+        assert_eq!(cells((1, 1), (7, 1)), vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(cells((7, 5), (7, 1)), vec![(3, 2), (3, 1), (3, 0)]);
+        assert_eq!(
+            cells((1, 1), (7, 3)),
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (3, 1)]
+        );
+        assert_eq!(
+            cells((1, 1), (5, 5)),
+            vec![(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn traversal_stops_at_boundaries_touched_by_the_end_point() {
+        assert_eq!(cells((1, 1), (4, 1)), vec![(0, 0), (1, 0)]);
+        assert_eq!(cells((4, 1), (1, 1)), vec![(1, 0), (0, 0)]);
+        assert_eq!(
+            cells((3, 1), (-3, 1)),
+            vec![(1, 0), (0, 0), (-1, 0), (-2, 0)]
+        );
+    }
+}
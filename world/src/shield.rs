@@ -0,0 +1,91 @@
+//! Shield shared by every bug of a wave launched under the shielded contract.
+//!
+//! While the shield holds, it soaks every hit aimed at the wave's bugs and slowly
+//! regenerates, so only sustained focus fire outpaces it. Once broken it stays down for
+//! the rest of the wave and hits reach the bugs' health again.
+
+use maze_defence_core::WaveShieldSnapshot;
+
+/// Share, in percent, of the wave plan's total bug health given to the shield.
+const SHIELD_CAPACITY_PERCENT: u64 = 25;
+/// Share, in percent, of its capacity the shield regenerates every second.
+const SHIELD_REGENERATION_PERCENT: u64 = 4;
+
+/// Wave-scoped shield pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WaveShield {
+    capacity: u32,
+    current: u32,
+    per_second: u64,
+    regeneration: u64,
+}
+
+impl WaveShield {
+    /// Shield protecting a wave whose bugs start with `plan_health` in total.
+    pub(crate) fn for_plan_health(plan_health: u64) -> Self {
+        let capacity = (plan_health * SHIELD_CAPACITY_PERCENT / 100).max(1);
+        let capacity = u32::try_from(capacity).unwrap_or(u32::MAX);
+        Self {
+            capacity,
+            current: capacity,
+            per_second: (u64::from(capacity) * SHIELD_REGENERATION_PERCENT / 100).max(1),
+            regeneration: 0,
+        }
+    }
+
+    /// Soaks `damage` into the shield and returns the damage left for the bug, which is
+    /// zero unless the hit breaks the shield or it was already broken.
+    pub(crate) fn absorb(&mut self, damage: u32) -> u32 {
+        let absorbed = damage.min(self.current);
+        self.current -= absorbed;
+        damage - absorbed
+    }
+
+    /// Restores shield points for `dt_millis` of wave time unless the shield is broken.
+    pub(crate) fn regenerate(&mut self, dt_millis: u32) {
+        if self.is_broken() || self.current >= self.capacity {
+            self.regeneration = 0;
+            return;
+        }
+        self.regeneration = self
+            .regeneration
+            .saturating_add(u64::from(dt_millis) * self.per_second);
+        let restored = u32::try_from(self.regeneration / 1_000).unwrap_or(u32::MAX);
+        self.regeneration %= 1_000;
+        self.current = self.current.saturating_add(restored).min(self.capacity);
+    }
+
+    pub(crate) fn is_broken(&self) -> bool {
+        self.current == 0
+    }
+
+    pub(crate) fn snapshot(&self) -> WaveShieldSnapshot {
+        WaveShieldSnapshot {
+            current: self.current,
+            capacity: self.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shields_soak_hits_regenerate_and_stay_down_once_broken() {
+        let mut shield = WaveShield::for_plan_health(400);
+        assert_eq!(shield.snapshot().capacity, 100);
+
+        assert_eq!(shield.absorb(30), 0);
+        shield.regenerate(500);
+        assert_eq!(shield.snapshot().current, 72, "four points a second");
+        shield.regenerate(10_000);
+        assert_eq!(shield.snapshot().current, 100, "never above capacity");
+
+        assert_eq!(shield.absorb(130), 30, "the breaking hit spills over");
+        assert!(shield.is_broken());
+        shield.regenerate(10_000);
+        assert!(shield.snapshot().is_broken());
+        assert_eq!(shield.absorb(7), 7);
+    }
+}
@@ -2,7 +2,12 @@
 
 use std::{collections::BTreeMap, time::Duration};
 
-use maze_defence_core::{CellRect, CellRectSize, TowerId, TowerKind};
+use maze_defence_core::{
+    CellRect, CellRectSize, TargetingPolicy, TowerHeat, TowerId, TowerKind, TowerRotation,
+};
+
+/// Heat is tracked in thousandths so dissipation stays exact for millisecond ticks.
+const HEAT_SCALE: u64 = 1_000;
 
 /// Snapshot of a tower stored inside the world.
 #[derive(Clone, Debug)]
@@ -13,9 +18,72 @@ pub(crate) struct TowerState {
     pub(crate) kind: TowerKind,
     /// Region of cells occupied by the tower.
     pub(crate) region: CellRect,
+    /// Rotation applied to the tower's footprint.
+    pub(crate) rotation: TowerRotation,
     /// Remaining cooldown before the tower may fire again.
     #[allow(dead_code)]
     pub(crate) cooldown_remaining: Duration,
+    /// Heat held by the tower in thousandths of a heat unit.
+    pub(crate) heat_milli: u64,
+    /// Whether the tower stalls until its heat has fully dissipated.
+    pub(crate) overheated: bool,
+    /// Rule the tower picks its target by.
+    pub(crate) targeting: TargetingPolicy,
+    /// Upgrades bought for the tower.
+    pub(crate) upgrades: u32,
+}
+
+impl TowerState {
+    /// Adds the heat of one shot, overheating the tower once its capacity is reached.
+    pub(crate) fn add_shot_heat(&mut self) {
+        let curve = self.kind.heat_curve();
+        let capacity = u64::from(curve.capacity) * HEAT_SCALE;
+        self.heat_milli = self
+            .heat_milli
+            .saturating_add(u64::from(curve.heat_per_shot) * HEAT_SCALE)
+            .min(capacity);
+        if curve.heat_per_shot > 0 && self.heat_milli >= capacity {
+            self.overheated = true;
+        }
+    }
+
+    /// Sheds heat for the elapsed time, ending a stall once the tower is fully cold.
+    pub(crate) fn dissipate_heat(&mut self, dt: Duration) {
+        let dt_millis = u64::try_from(dt.as_millis()).unwrap_or(u64::MAX);
+        let shed =
+            u64::from(self.kind.heat_curve().dissipation_per_second).saturating_mul(dt_millis);
+        self.heat_milli = self.heat_milli.saturating_sub(shed);
+        if self.heat_milli == 0 {
+            self.overheated = false;
+        }
+    }
+
+    /// Clears all accumulated heat.
+    pub(crate) fn cool_down(&mut self) {
+        self.heat_milli = 0;
+        self.overheated = false;
+    }
+
+    /// Time remaining before an overheated tower has shed all of its heat.
+    pub(crate) fn stall_remaining(&self) -> Duration {
+        if !self.overheated {
+            return Duration::ZERO;
+        }
+        let rate = u64::from(self.kind.heat_curve().dissipation_per_second);
+        if rate == 0 {
+            return Duration::MAX;
+        }
+        Duration::from_millis(self.heat_milli.div_ceil(rate))
+    }
+
+    /// Presentation-ready heat gauge of the tower.
+    pub(crate) fn heat(&self) -> TowerHeat {
+        TowerHeat {
+            heat: u32::try_from(self.heat_milli.div_ceil(HEAT_SCALE)).unwrap_or(u32::MAX),
+            capacity: self.kind.heat_curve().capacity,
+            overheated: self.overheated,
+        }
+    }
 }
 
 /// Registry that stores towers and manages identifier allocation.
@@ -68,17 +136,20 @@ impl TowerRegistry {
         self.entries.is_empty()
     }
 
+    /// Reports how many towers the registry stores.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
     /// Returns an iterator over all tower states in identifier order.
     pub(crate) fn iter(&self) -> impl Iterator<Item = &TowerState> {
         self.entries.values()
     }
 }
 
-/// Reports the footprint size associated with a tower kind.
-pub(crate) fn footprint_for(kind: TowerKind) -> CellRectSize {
-    match kind {
-        TowerKind::Basic => CellRectSize::new(4, 4),
-    }
+/// Reports the footprint size associated with a tower kind placed with `rotation`.
+pub(crate) fn footprint_for(kind: TowerKind, rotation: TowerRotation) -> CellRectSize {
+    rotation.apply(kind.footprint())
 }
 
 #[cfg(test)]
@@ -88,11 +159,23 @@ mod tests {
 
     #[test]
     fn basic_tower_footprint_is_four_by_four() {
-        let footprint = footprint_for(TowerKind::Basic);
+        let footprint = footprint_for(TowerKind::Basic, TowerRotation::Deg0);
         assert_eq!(footprint.width(), 4);
         assert_eq!(footprint.height(), 4);
     }
 
+    #[test]
+    fn rotating_barrier_footprint_swaps_its_axes() {
+        let upright = footprint_for(TowerKind::Barrier, TowerRotation::Deg0);
+        let turned = footprint_for(TowerKind::Barrier, TowerRotation::Deg90);
+        assert_eq!((upright.width(), upright.height()), (4, 12));
+        assert_eq!((turned.width(), turned.height()), (12, 4));
+        assert_eq!(
+            footprint_for(TowerKind::Barrier, TowerRotation::Deg90.rotated()),
+            upright
+        );
+    }
+
     #[test]
     fn registry_starts_empty_with_zero_identifier() {
         let registry = TowerRegistry::new();
@@ -120,7 +203,12 @@ mod tests {
             id,
             kind: TowerKind::Basic,
             region,
+            rotation: TowerRotation::Deg0,
             cooldown_remaining: Duration::ZERO,
+            heat_milli: 0,
+            overheated: false,
+            targeting: TargetingPolicy::default(),
+            upgrades: 0,
         });
 
         let retrieved = registry.get(id).expect("tower present");
@@ -139,7 +227,12 @@ mod tests {
             id: TowerId::new(7),
             kind: TowerKind::Basic,
             region,
+            rotation: TowerRotation::Deg0,
             cooldown_remaining: Duration::ZERO,
+            heat_milli: 0,
+            overheated: false,
+            targeting: TargetingPolicy::default(),
+            upgrades: 0,
         };
 
         assert_eq!(state.id, TowerId::new(7));
@@ -147,4 +240,35 @@ mod tests {
         assert_eq!(state.region, region);
         assert_eq!(state.cooldown_remaining, Duration::ZERO);
     }
+
+    #[test]
+    fn sustained_fire_overheats_until_heat_fully_dissipates() {
+        let mut state = TowerState {
+            id: TowerId::new(0),
+            kind: TowerKind::Basic,
+            region: CellRect::from_origin_and_size(CellCoord::new(0, 0), CellRectSize::new(4, 4)),
+            rotation: TowerRotation::Deg0,
+            cooldown_remaining: Duration::ZERO,
+            heat_milli: 0,
+            overheated: false,
+            targeting: TargetingPolicy::default(),
+            upgrades: 0,
+        };
+
+        for _ in 0..3 {
+            state.add_shot_heat();
+        }
+        assert!(!state.overheated);
+        state.add_shot_heat();
+        assert!(state.overheated);
+        assert_eq!(state.heat().heat, 100);
+        assert_eq!(state.stall_remaining(), Duration::from_secs(5));
+
+        state.dissipate_heat(Duration::from_millis(4_950));
+        assert!(state.overheated);
+        assert_eq!(state.heat().heat, 1);
+        state.dissipate_heat(Duration::from_millis(50));
+        assert!(!state.overheated);
+        assert_eq!(state.stall_remaining(), Duration::ZERO);
+    }
 }